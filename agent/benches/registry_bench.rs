@@ -37,6 +37,7 @@ fn create_mock_entry(id: usize) -> RegistryEntry {
         public_key: None,
         kid: None,
         signature_scheme: None,
+        policy_id: None,
         blob_manifest: None,
         blob_proof: None,
         blob_wasm: None,
@@ -0,0 +1,119 @@
+//! Merkle Accumulator over Audit Events
+//!
+//! Compact, append-only Merkle accumulator over the per-event hashes of an
+//! audit log (v1 `digest`, v2 `self_hash`). Only the roots of the perfect
+//! subtrees ("peaks") are kept, so appending is O(log n) and the accumulator
+//! never has to reread the log. The root is identical to
+//! `MerkleTree::from_leaf_hashes` over the same leaves (RFC 6962 tree shape),
+//! which lets inclusion proofs be built with `merkle` and checked against a
+//! checkpointed root.
+
+use crate::merkle::{self, Hash32};
+
+/// Merkle scheme of the accumulator
+pub const ACCUMULATOR_SCHEME: &str = "blake3-merkle.v1";
+
+/// Leaf hash of an audit event (over its hash string, e.g. `0x…`)
+pub fn event_leaf(event_hash: &str) -> Hash32 {
+    merkle::leaf_hash(event_hash.as_bytes())
+}
+
+/// Append-only Merkle accumulator
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MerkleAccumulator {
+    size: u64,
+    /// Roots of the perfect subtrees, largest first
+    peaks: Vec<Hash32>,
+}
+
+impl MerkleAccumulator {
+    /// Creates an empty accumulator
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builds an accumulator over a sequence of event hashes
+    pub fn from_event_hashes<I, S>(hashes: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut accumulator = Self::new();
+        for hash in hashes {
+            accumulator.push_event(hash.as_ref());
+        }
+        accumulator
+    }
+
+    /// Appends an audit event by its hash
+    pub fn push_event(&mut self, event_hash: &str) {
+        self.push_leaf(event_leaf(event_hash));
+    }
+
+    /// Appends a leaf hash
+    pub fn push_leaf(&mut self, leaf: Hash32) {
+        // Binary counter: every trailing 1-bit of the size is a peak of equal
+        // size that merges with the new subtree
+        let mut node = leaf;
+        let mut size = self.size;
+        while size & 1 == 1 {
+            let left = self.peaks.pop().expect("peak for every set bit");
+            node = merkle::node_hash(&left, &node);
+            size >>= 1;
+        }
+        self.peaks.push(node);
+        self.size += 1;
+    }
+
+    /// Number of leaves
+    pub fn len(&self) -> u64 {
+        self.size
+    }
+
+    /// True if no leaf was appended
+    #[allow(dead_code)] // Public API - pairs with len()
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    /// Merkle root (`BLAKE3("")` for an empty accumulator)
+    pub fn root(&self) -> Hash32 {
+        let mut peaks = self.peaks.iter().rev();
+        match peaks.next() {
+            None => merkle::empty_root(),
+            Some(last) => peaks.fold(*last, |right, left| merkle::node_hash(left, &right)),
+        }
+    }
+
+    /// Merkle root as 0x-prefixed hex
+    pub fn root_hex(&self) -> String {
+        merkle::to_hex(&self.root())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::merkle::MerkleTree;
+
+    #[test]
+    fn test_accumulator_matches_merkle_tree() {
+        let hashes: Vec<String> = (0..17).map(|i| format!("0x{:064x}", i)).collect();
+
+        let mut accumulator = MerkleAccumulator::new();
+        assert_eq!(accumulator.root(), merkle::empty_root());
+
+        for size in 1..=hashes.len() {
+            accumulator.push_event(&hashes[size - 1]);
+            let leaves = hashes[..size].iter().map(|h| event_leaf(h)).collect();
+            let tree = MerkleTree::from_leaf_hashes(leaves);
+            assert_eq!(accumulator.len(), size as u64);
+            assert_eq!(accumulator.root(), tree.root(), "size {}", size);
+        }
+
+        assert_eq!(
+            MerkleAccumulator::from_event_hashes(&hashes).root_hex(),
+            accumulator.root_hex()
+        );
+    }
+}
@@ -0,0 +1,544 @@
+//! Signed Audit Checkpoints
+//!
+//! The hash chains of `AuditChain` (v2) and `AuditLog` (v1) detect edits
+//! inside the log, but not a truncated tail or a chain that was rewritten from
+//! scratch. A checkpoint commits to the size and tail hash of an audit log at
+//! a point in time and is signed through a `KeyProvider` (software, PKCS#11,
+//! CloudKMS). Checkpoints are appended to `<log>.checkpoints.jsonl` next to the
+//! log; `verify_checkpoints` checks the log against every one of them.
+//!
+//! Checkpoints also carry the Merkle root over the covered event hashes (see
+//! `accumulator`), which anchors per-event inclusion proofs (`event_proof`).
+
+use anyhow::{anyhow, bail, Result};
+use base64::{engine::general_purpose, Engine};
+use chrono::Utc;
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write as IoWrite};
+use std::path::{Path, PathBuf};
+
+use super::accumulator::MerkleAccumulator;
+use crate::providers::KeyProvider;
+
+/// Checkpoint format version
+pub const CHECKPOINT_VERSION: &str = "audit.checkpoint.v1";
+
+/// Signature scheme of all key providers
+pub const SIGNATURE_SCHEME: &str = "ed25519";
+
+/// Audit log format a checkpoint refers to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    /// `AuditLog` entries (seq, prev_digest, digest)
+    V1,
+    /// `AuditChain` events (prev_hash, self_hash)
+    V2,
+}
+
+impl std::fmt::Display for LogFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::V1 => write!(f, "v1"),
+            Self::V2 => write!(f, "v2"),
+        }
+    }
+}
+
+/// Reads the per-event hashes of an audit log in file order
+///
+/// The format is detected from the first event (`digest` → v1,
+/// `self_hash` → v2). Empty logs are reported as v2.
+pub fn read_event_hashes<P: AsRef<Path>>(path: P) -> Result<(LogFormat, Vec<String>)> {
+    let reader = BufReader::new(File::open(path)?);
+    let mut format = None;
+    let mut hashes = Vec::new();
+
+    for line in reader.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let value: serde_json::Value = serde_json::from_str(&line)?;
+        let line_format = if value.get("self_hash").is_some() {
+            LogFormat::V2
+        } else if value.get("digest").is_some() {
+            LogFormat::V1
+        } else {
+            bail!(
+                "Event {} is neither a v1 nor a v2 audit event",
+                hashes.len()
+            );
+        };
+        if *format.get_or_insert(line_format) != line_format {
+            bail!("Event {} mixes v1 and v2 audit formats", hashes.len());
+        }
+
+        let field = match line_format {
+            LogFormat::V1 => "digest",
+            LogFormat::V2 => "self_hash",
+        };
+        let hash = value[field]
+            .as_str()
+            .ok_or_else(|| anyhow!("Event {}: {} is not a string", hashes.len(), field))?;
+        hashes.push(hash.to_string());
+    }
+
+    Ok((format.unwrap_or(LogFormat::V2), hashes))
+}
+
+/// Default checkpoint file next to an audit log
+///
+/// `build/audit_chain.jsonl` → `build/audit_chain.checkpoints.jsonl`
+pub fn checkpoint_path<P: AsRef<Path>>(log_path: P) -> PathBuf {
+    log_path.as_ref().with_extension("checkpoints.jsonl")
+}
+
+/// Signed checkpoint over an audit log
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditCheckpoint {
+    pub version: String,
+    pub format: LogFormat,
+    /// Number of events covered by the checkpoint
+    pub tree_size: u64,
+    /// Hash of the last covered event (digest or self_hash)
+    pub tail_hash: String,
+    /// Merkle root over the covered event hashes (0x-prefixed)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub root_hash: Option<String>,
+    /// RFC3339 creation time
+    pub timestamp: String,
+    /// Key ID of the signer (as derived by the key provider)
+    pub kid: String,
+    /// Key provider that produced the signature (software, pkcs11, cloudkms)
+    pub provider: String,
+    /// Base64 Ed25519 public key of the signer
+    pub public_key: String,
+    pub signature_scheme: String,
+    /// Base64 Ed25519 signature over `core_hash`
+    pub signature: String,
+}
+
+/// Signed part of a checkpoint
+#[derive(Serialize)]
+struct CheckpointCore<'a> {
+    version: &'a str,
+    format: LogFormat,
+    tree_size: u64,
+    tail_hash: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    root_hash: Option<&'a str>,
+    timestamp: &'a str,
+    kid: &'a str,
+    provider: &'a str,
+    public_key: &'a str,
+}
+
+impl AuditCheckpoint {
+    /// Creates and signs a checkpoint over the current state of an audit log
+    ///
+    /// # Arguments
+    /// * `log_path` - Audit log (v1 or v2)
+    /// * `provider` - Key provider used for signing
+    /// * `kid` - Signing key (default: the provider's current key)
+    pub fn create<P: AsRef<Path>>(
+        log_path: P,
+        provider: &dyn KeyProvider,
+        kid: Option<&str>,
+    ) -> Result<Self> {
+        let (format, hashes) = read_event_hashes(&log_path)?;
+        let tail_hash = hashes
+            .last()
+            .ok_or_else(|| anyhow!("Audit log {} is empty", log_path.as_ref().display()))?
+            .clone();
+
+        let kid = match kid {
+            Some(kid) => kid.to_string(),
+            None => provider.current_kid()?,
+        };
+        let public_key = provider.public_key(&kid)?;
+
+        let mut checkpoint = AuditCheckpoint {
+            version: CHECKPOINT_VERSION.to_string(),
+            format,
+            tree_size: hashes.len() as u64,
+            tail_hash,
+            root_hash: Some(MerkleAccumulator::from_event_hashes(&hashes).root_hex()),
+            timestamp: Utc::now().to_rfc3339(),
+            kid,
+            provider: provider.provider_id().to_string(),
+            public_key: general_purpose::STANDARD.encode(public_key),
+            signature_scheme: SIGNATURE_SCHEME.to_string(),
+            signature: String::new(),
+        };
+        let signature = provider.sign(Some(&checkpoint.kid), &checkpoint.core_hash()?)?;
+        checkpoint.signature = general_purpose::STANDARD.encode(signature);
+
+        Ok(checkpoint)
+    }
+
+    /// BLAKE3 hash over the signed fields
+    pub fn core_hash(&self) -> Result<[u8; 32]> {
+        let core = CheckpointCore {
+            version: &self.version,
+            format: self.format,
+            tree_size: self.tree_size,
+            tail_hash: &self.tail_hash,
+            root_hash: self.root_hash.as_deref(),
+            timestamp: &self.timestamp,
+            kid: &self.kid,
+            provider: &self.provider,
+            public_key: &self.public_key,
+        };
+        Ok(*blake3::hash(&serde_json::to_vec(&core)?).as_bytes())
+    }
+
+    /// Verifies the signature against the embedded public key
+    pub fn verify_signature(&self) -> Result<()> {
+        if self.version != CHECKPOINT_VERSION {
+            bail!("Unsupported checkpoint version {}", self.version);
+        }
+        if self.signature_scheme != SIGNATURE_SCHEME {
+            bail!("Unsupported signature scheme {}", self.signature_scheme);
+        }
+
+        verify_ed25519(&self.public_key, &self.signature, &self.core_hash()?)
+    }
+
+    /// Checks that the signer is a key known to `provider`
+    ///
+    /// Without this check a rewritten log could come with freshly signed
+    /// checkpoints from an arbitrary key.
+    pub fn verify_signer(&self, provider: &dyn KeyProvider) -> Result<()> {
+        check_trusted_signer(provider, &self.kid, &self.public_key)
+    }
+}
+
+/// Checks that `kid` is a key of `provider` with the given base64 public key
+pub(crate) fn check_trusted_signer(
+    provider: &dyn KeyProvider,
+    kid: &str,
+    public_key_b64: &str,
+) -> Result<()> {
+    let known = provider
+        .public_key(kid)
+        .map_err(|e| anyhow!("Signer {} is not trusted: {}", kid, e))?;
+    if general_purpose::STANDARD.encode(known) != public_key_b64 {
+        bail!("Public key does not match trusted key {}", kid);
+    }
+    Ok(())
+}
+
+/// Verifies a base64 Ed25519 signature over `msg` with a base64 public key
+pub(crate) fn verify_ed25519(public_key_b64: &str, signature_b64: &str, msg: &[u8]) -> Result<()> {
+    let pubkey_bytes = general_purpose::STANDARD.decode(public_key_b64)?;
+    let sig_bytes = general_purpose::STANDARD.decode(signature_b64)?;
+    let verifying_key = VerifyingKey::from_bytes(
+        &pubkey_bytes
+            .try_into()
+            .map_err(|_| anyhow!("Invalid public key length"))?,
+    )?;
+    let signature = Signature::from_bytes(
+        &sig_bytes
+            .try_into()
+            .map_err(|_| anyhow!("Invalid signature length"))?,
+    );
+    verifying_key
+        .verify(msg, &signature)
+        .map_err(|_| anyhow!("Signature does not verify"))
+}
+
+/// Appends a checkpoint to a checkpoint file (JSONL)
+pub fn append_checkpoint<P: AsRef<Path>>(path: P, checkpoint: &AuditCheckpoint) -> Result<()> {
+    if let Some(parent) = path.as_ref().parent() {
+        if !parent.as_os_str().is_empty() {
+            std::fs::create_dir_all(parent)?;
+        }
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", serde_json::to_string(checkpoint)?)?;
+    Ok(())
+}
+
+/// Loads all checkpoints of a checkpoint file in file order
+pub fn load_checkpoints<P: AsRef<Path>>(path: P) -> Result<Vec<AuditCheckpoint>> {
+    let reader = BufReader::new(File::open(path)?);
+    let mut checkpoints = Vec::new();
+    for line in reader.lines() {
+        let line = line?;
+        if !line.trim().is_empty() {
+            checkpoints.push(serde_json::from_str(&line)?);
+        }
+    }
+    Ok(checkpoints)
+}
+
+/// Result of checking an audit log against its checkpoints
+#[derive(Debug, Clone, Serialize)]
+pub struct CheckpointReport {
+    /// Number of checkpoints checked
+    pub checkpoints: usize,
+
+    /// Events in the log
+    pub total_events: usize,
+
+    /// Events covered by the last checkpoint (later events are unsigned)
+    pub covered_events: u64,
+
+    /// Verification result
+    pub ok: bool,
+
+    /// Index of the first checkpoint that failed (if any)
+    pub failed_checkpoint: Option<usize>,
+
+    /// Error message (if any)
+    pub error: Option<String>,
+}
+
+/// Checks an audit log against signed checkpoints
+///
+/// Every checkpoint must carry a valid signature (from a key of `trusted`, if
+/// given), the log must still contain at least `tree_size` events
+/// (truncation), the event at `tree_size - 1` must still have the
+/// checkpointed tail hash and the Merkle root over the first `tree_size`
+/// events must match `root_hash` (rewrite).
+///
+/// # Arguments
+/// * `format` - Format of the log
+/// * `hashes` - Per-event hashes of the log (see `read_event_hashes`)
+/// * `checkpoints` - Checkpoints in file order
+/// * `trusted` - Optional key provider whose keys are accepted as signers
+pub fn verify_checkpoints(
+    format: LogFormat,
+    hashes: &[String],
+    checkpoints: &[AuditCheckpoint],
+    trusted: Option<&dyn KeyProvider>,
+) -> CheckpointReport {
+    let mut report = CheckpointReport {
+        checkpoints: checkpoints.len(),
+        total_events: hashes.len(),
+        covered_events: 0,
+        ok: true,
+        failed_checkpoint: None,
+        error: None,
+    };
+
+    let mut accumulator = MerkleAccumulator::new();
+    for (index, checkpoint) in checkpoints.iter().enumerate() {
+        if let Err(e) = check_one(format, hashes, checkpoint, &mut accumulator, trusted) {
+            report.ok = false;
+            report.failed_checkpoint = Some(index);
+            report.error = Some(format!("Checkpoint {}: {}", index, e));
+            break;
+        }
+        report.covered_events = checkpoint.tree_size;
+    }
+
+    report
+}
+
+fn check_one(
+    format: LogFormat,
+    hashes: &[String],
+    checkpoint: &AuditCheckpoint,
+    accumulator: &mut MerkleAccumulator,
+    trusted: Option<&dyn KeyProvider>,
+) -> Result<()> {
+    // The accumulator covers exactly the events of the previous checkpoint
+    let previous_size = accumulator.len();
+    checkpoint
+        .verify_signature()
+        .map_err(|e| anyhow!("invalid signature: {}", e))?;
+    if let Some(provider) = trusted {
+        checkpoint.verify_signer(provider)?;
+    }
+
+    if checkpoint.tree_size < previous_size {
+        bail!(
+            "covers {} events, fewer than the previous checkpoint ({})",
+            checkpoint.tree_size,
+            previous_size
+        );
+    }
+    if checkpoint.tree_size > hashes.len() as u64 {
+        bail!(
+            "log truncated: checkpoint covers {} events, log has {}",
+            checkpoint.tree_size,
+            hashes.len()
+        );
+    }
+    if checkpoint.tree_size == 0 {
+        return Ok(());
+    }
+    if checkpoint.format != format {
+        bail!(
+            "log rewritten: checkpoint is for a {} log, log is {}",
+            checkpoint.format,
+            format
+        );
+    }
+
+    let index = (checkpoint.tree_size - 1) as usize;
+    if hashes[index] != checkpoint.tail_hash {
+        bail!(
+            "log rewritten: event {} has hash {}, checkpoint expects {}",
+            index,
+            hashes[index],
+            checkpoint.tail_hash
+        );
+    }
+
+    for hash in &hashes[previous_size as usize..checkpoint.tree_size as usize] {
+        accumulator.push_event(hash);
+    }
+    if let Some(root_hash) = &checkpoint.root_hash {
+        if accumulator.root_hex() != *root_hash {
+            bail!(
+                "log rewritten: Merkle root of the first {} events is {}, checkpoint expects {}",
+                checkpoint.tree_size,
+                accumulator.root_hex(),
+                root_hash
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Checks an audit log file against a checkpoint file
+///
+/// A missing checkpoint file yields a report with zero checkpoints.
+pub fn verify_log_checkpoints<P: AsRef<Path>, Q: AsRef<Path>>(
+    log_path: P,
+    checkpoints_path: Q,
+    trusted: Option<&dyn KeyProvider>,
+) -> Result<CheckpointReport> {
+    let (format, hashes) = read_event_hashes(log_path)?;
+    let checkpoints = if checkpoints_path.as_ref().exists() {
+        load_checkpoints(checkpoints_path)?
+    } else {
+        Vec::new()
+    };
+    Ok(verify_checkpoints(format, &hashes, &checkpoints, trusted))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audit::{AuditChain, AuditLog};
+    use crate::providers::SoftwareProvider;
+    use ed25519_dalek::SigningKey;
+    use tempfile::tempdir;
+
+    fn software_provider(keys_dir: &Path, name: &str) -> SoftwareProvider {
+        let signing_key = SigningKey::generate(&mut rand::rngs::OsRng);
+        let metadata = crate::keys::KeyMetadata::new(
+            &signing_key.verifying_key().to_bytes(),
+            "Audit",
+            "ed25519",
+            365,
+        )
+        .unwrap();
+        metadata
+            .save(keys_dir.join(format!("{}.json", name)))
+            .unwrap();
+        std::fs::write(
+            keys_dir.join(format!("{}.ed25519", name)),
+            signing_key.to_bytes(),
+        )
+        .unwrap();
+        SoftwareProvider::new(keys_dir, Some(name.to_string())).unwrap()
+    }
+
+    fn append_events(chain: &mut AuditChain, names: &[&str]) {
+        for name in names {
+            chain
+                .append(name.to_string(), None, None, None, None, None)
+                .unwrap();
+        }
+    }
+
+    #[test]
+    fn test_checkpoint_detects_truncation_and_rewrite() {
+        let dir = tempdir().unwrap();
+        let provider = software_provider(dir.path(), "audit");
+        let log = dir.path().join("audit_chain.jsonl");
+
+        let mut chain = AuditChain::new(&log).unwrap();
+        append_events(&mut chain, &["a", "b", "c"]);
+        let checkpoint = AuditCheckpoint::create(&log, &provider, None).unwrap();
+        assert_eq!(checkpoint.tree_size, 3);
+        assert_eq!(checkpoint.format, LogFormat::V2);
+        assert_eq!(checkpoint.kid, provider.current_kid().unwrap());
+        assert_eq!(
+            checkpoint.root_hash.as_deref(),
+            Some(chain.merkle_root().as_str())
+        );
+        checkpoint.verify_signature().unwrap();
+        checkpoint.verify_signer(&provider).unwrap();
+
+        let cp_path = checkpoint_path(&log);
+        append_checkpoint(&cp_path, &checkpoint).unwrap();
+
+        // Appending after a checkpoint is fine
+        append_events(&mut chain, &["d"]);
+        let report = verify_log_checkpoints(&log, &cp_path, Some(&provider)).unwrap();
+        assert!(report.ok, "{:?}", report.error);
+        assert_eq!(report.covered_events, 3);
+        assert_eq!(report.total_events, 4);
+
+        // Truncated tail (chain itself stays valid)
+        let content = std::fs::read_to_string(&log).unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+        std::fs::write(&log, format!("{}\n{}\n", lines[0], lines[1])).unwrap();
+        let report = verify_log_checkpoints(&log, &cp_path, None).unwrap();
+        assert!(!report.ok);
+        assert!(report.error.unwrap().contains("truncated"));
+
+        // Chain rewritten from scratch with the same number of events
+        std::fs::remove_file(&log).unwrap();
+        let mut chain = AuditChain::new(&log).unwrap();
+        append_events(&mut chain, &["x", "y", "z"]);
+        let report = verify_log_checkpoints(&log, &cp_path, None).unwrap();
+        assert!(!report.ok);
+        assert_eq!(report.failed_checkpoint, Some(0));
+        assert!(report.error.unwrap().contains("rewritten"));
+    }
+
+    #[test]
+    fn test_checkpoint_v1_log_and_untrusted_signer() {
+        let dir = tempdir().unwrap();
+        let keys_a = dir.path().join("a");
+        let keys_b = dir.path().join("b");
+        std::fs::create_dir_all(&keys_a).unwrap();
+        std::fs::create_dir_all(&keys_b).unwrap();
+        let signer = software_provider(&keys_a, "audit");
+        let other = software_provider(&keys_b, "audit");
+
+        let log = dir.path().join("agent.audit.jsonl");
+        let mut audit = AuditLog::new(&log).unwrap();
+        audit.log_event("a", serde_json::json!({})).unwrap();
+        audit.log_event("b", serde_json::json!({})).unwrap();
+
+        let checkpoint = AuditCheckpoint::create(&log, &signer, None).unwrap();
+        assert_eq!(checkpoint.format, LogFormat::V1);
+        assert_eq!(checkpoint.tail_hash, audit.get_tip());
+
+        let (format, hashes) = read_event_hashes(&log).unwrap();
+        let checkpoints = vec![checkpoint];
+        let report = verify_checkpoints(format, &hashes, &checkpoints, Some(&signer));
+        assert!(report.ok, "{:?}", report.error);
+
+        let report = verify_checkpoints(format, &hashes, &checkpoints, Some(&other));
+        assert!(!report.ok);
+        assert!(report.error.unwrap().contains("not trusted"));
+
+        let mut forged = checkpoints[0].clone();
+        forged.tree_size = 1;
+        let report = verify_checkpoints(format, &hashes, &[forged], None);
+        assert!(!report.ok);
+        assert!(report.error.unwrap().contains("invalid signature"));
+    }
+}
@@ -0,0 +1,312 @@
+//! Audit Event Inclusion Proofs
+//!
+//! Discloses a single audit event (e.g. `key_rotated` or `registry_add`) to an
+//! external auditor without handing over the whole log. The proof carries the
+//! event as stored, its Merkle path to the root of a signed checkpoint and the
+//! checkpoint itself, so `AuditEventProof::verify` needs nothing else
+//! (optionally a key provider to pin the checkpoint signer).
+
+use anyhow::{anyhow, bail, Result};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+use super::accumulator::{event_leaf, ACCUMULATOR_SCHEME};
+use super::checkpoint::{read_event_hashes, verify_checkpoints, AuditCheckpoint, LogFormat};
+use super::hash_chain::AuditEvent;
+use super::v1_0::AuditEntry;
+use crate::merkle::{self, MerkleTree};
+use crate::providers::KeyProvider;
+
+/// Proof format version
+pub const EVENT_PROOF_VERSION: &str = "audit.event_proof.v1";
+
+/// Inclusion proof of one audit event against a signed checkpoint
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditEventProof {
+    pub version: String,
+    pub scheme: String,
+    /// 1-based position of the event in the log (v1: `seq`)
+    pub seq: u64,
+    /// The disclosed event exactly as stored in the log
+    pub event: serde_json::Value,
+    /// Hash of the event (v1 `digest`, v2 `self_hash`)
+    pub event_hash: String,
+    pub tree_size: u64,
+    /// Sibling hashes from leaf to root level
+    pub path: Vec<String>,
+    /// Merkle root (0x-prefixed), equals `checkpoint.root_hash`
+    pub root_hash: String,
+    pub checkpoint: AuditCheckpoint,
+}
+
+impl AuditEventProof {
+    /// Builds the proof for event `seq` against the latest covering checkpoint
+    ///
+    /// The log must still match every checkpoint; checkpoints without a Merkle
+    /// root (created before the accumulator existed) cannot anchor proofs.
+    ///
+    /// # Arguments
+    /// * `log_path` - Audit log (v1 or v2)
+    /// * `checkpoints` - Checkpoints of the log in file order
+    /// * `seq` - 1-based event number
+    pub fn create<P: AsRef<Path>>(
+        log_path: P,
+        checkpoints: &[AuditCheckpoint],
+        seq: u64,
+    ) -> Result<Self> {
+        let (format, hashes) = read_event_hashes(&log_path)?;
+        if seq == 0 || seq > hashes.len() as u64 {
+            bail!("Event {} not found (log has {} events)", seq, hashes.len());
+        }
+
+        let report = verify_checkpoints(format, &hashes, checkpoints, None);
+        if !report.ok {
+            bail!(
+                "Log does not match its checkpoints: {}",
+                report.error.unwrap_or_default()
+            );
+        }
+
+        let checkpoint = checkpoints
+            .iter()
+            .rev()
+            .find(|cp| cp.root_hash.is_some() && cp.tree_size >= seq)
+            .ok_or_else(|| {
+                anyhow!(
+                    "No checkpoint with Merkle root covers event {}; run 'audit checkpoint' first",
+                    seq
+                )
+            })?
+            .clone();
+
+        let leaves = hashes[..checkpoint.tree_size as usize]
+            .iter()
+            .map(|hash| event_leaf(hash))
+            .collect();
+        let tree = MerkleTree::from_leaf_hashes(leaves);
+        let path = tree
+            .inclusion_path((seq - 1) as usize)
+            .ok_or_else(|| anyhow!("Event {} outside of the checkpointed tree", seq))?;
+
+        Ok(AuditEventProof {
+            version: EVENT_PROOF_VERSION.to_string(),
+            scheme: ACCUMULATOR_SCHEME.to_string(),
+            seq,
+            event: read_event(&log_path, seq)?,
+            event_hash: hashes[(seq - 1) as usize].clone(),
+            tree_size: checkpoint.tree_size,
+            path: path.iter().map(merkle::to_hex).collect(),
+            root_hash: tree.root_hex(),
+            checkpoint,
+        })
+    }
+
+    /// Loads a proof from a JSON file
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Ok(serde_json::from_str(&std::fs::read_to_string(path)?)?)
+    }
+
+    /// Saves the proof as a JSON file
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Verifies the proof without access to the log
+    ///
+    /// Checks that the event content hashes to `event_hash`, that the Merkle
+    /// path leads from the event to the checkpointed root and that the
+    /// checkpoint is signed (by a key of `trusted`, if given).
+    pub fn verify(&self, trusted: Option<&dyn KeyProvider>) -> Result<()> {
+        if self.version != EVENT_PROOF_VERSION {
+            bail!("Unsupported proof version {}", self.version);
+        }
+        if self.scheme != ACCUMULATOR_SCHEME {
+            bail!("Unsupported Merkle scheme {}", self.scheme);
+        }
+
+        self.verify_event()?;
+
+        if self.tree_size != self.checkpoint.tree_size
+            || Some(&self.root_hash) != self.checkpoint.root_hash.as_ref()
+        {
+            bail!(
+                "Proof is for tree size {} (root {}), checkpoint covers {} events (root {})",
+                self.tree_size,
+                self.root_hash,
+                self.checkpoint.tree_size,
+                self.checkpoint.root_hash.as_deref().unwrap_or("none")
+            );
+        }
+        if self.seq == 0 || self.seq > self.tree_size {
+            bail!(
+                "Event {} outside of the checkpointed tree ({} events)",
+                self.seq,
+                self.tree_size
+            );
+        }
+
+        let path = self
+            .path
+            .iter()
+            .map(|hash| merkle::from_hex(hash).map_err(|e| anyhow!("{}", e)))
+            .collect::<Result<Vec<_>>>()?;
+        let root = merkle::from_hex(&self.root_hash).map_err(|e| anyhow!("{}", e))?;
+        if !merkle::verify_inclusion(
+            &event_leaf(&self.event_hash),
+            (self.seq - 1) as usize,
+            self.tree_size as usize,
+            &path,
+            &root,
+        ) {
+            bail!(
+                "Inclusion path for event {} does not lead to root {}",
+                self.seq,
+                self.root_hash
+            );
+        }
+
+        self.checkpoint
+            .verify_signature()
+            .map_err(|e| anyhow!("Checkpoint signature invalid: {}", e))?;
+        if let Some(provider) = trusted {
+            self.checkpoint.verify_signer(provider)?;
+        }
+        Ok(())
+    }
+
+    /// Recomputes the event hash from the disclosed event
+    fn verify_event(&self) -> Result<()> {
+        let (hash, intact) = match self.checkpoint.format {
+            LogFormat::V1 => {
+                let entry: AuditEntry = serde_json::from_value(self.event.clone())?;
+                if entry.seq != self.seq {
+                    bail!("Event has seq {}, proof claims {}", entry.seq, self.seq);
+                }
+                let intact = entry.verify_digest();
+                (entry.digest, intact)
+            }
+            LogFormat::V2 => {
+                let event: AuditEvent = serde_json::from_value(self.event.clone())?;
+                let intact = event.verify_self_hash();
+                (event.self_hash, intact)
+            }
+        };
+        if !intact {
+            bail!("Event content does not match its hash");
+        }
+        if hash != self.event_hash {
+            bail!(
+                "Event hash {} does not match proof ({})",
+                hash,
+                self.event_hash
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Reads the `seq`-th (1-based) event of an audit log as JSON
+fn read_event<P: AsRef<Path>>(path: P, seq: u64) -> Result<serde_json::Value> {
+    let reader = BufReader::new(File::open(path)?);
+    let mut lines = reader
+        .lines()
+        .filter(|line| line.as_ref().map(|l| !l.trim().is_empty()).unwrap_or(true));
+    let line = lines
+        .nth((seq - 1) as usize)
+        .ok_or_else(|| anyhow!("Event {} not found", seq))??;
+    Ok(serde_json::from_str(&line)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audit::{AuditChain, AuditLog};
+    use crate::providers::SoftwareProvider;
+    use ed25519_dalek::SigningKey;
+    use tempfile::tempdir;
+
+    fn software_provider(keys_dir: &Path) -> SoftwareProvider {
+        let signing_key = SigningKey::generate(&mut rand::rngs::OsRng);
+        crate::keys::KeyMetadata::new(
+            &signing_key.verifying_key().to_bytes(),
+            "Audit",
+            "ed25519",
+            365,
+        )
+        .unwrap()
+        .save(keys_dir.join("audit.json"))
+        .unwrap();
+        std::fs::write(keys_dir.join("audit.ed25519"), signing_key.to_bytes()).unwrap();
+        SoftwareProvider::new(keys_dir, Some("audit".to_string())).unwrap()
+    }
+
+    #[test]
+    fn test_event_proof_roundtrip_v2() {
+        let dir = tempdir().unwrap();
+        let provider = software_provider(dir.path());
+        let log = dir.path().join("audit_chain.jsonl");
+
+        let mut chain = AuditChain::new(&log).unwrap();
+        for i in 0..7 {
+            chain
+                .append(format!("event_{}", i), None, None, None, None, None)
+                .unwrap();
+        }
+        let checkpoint = AuditCheckpoint::create(&log, &provider, None).unwrap();
+        assert_eq!(
+            checkpoint.root_hash.as_deref(),
+            Some(chain.merkle_root().as_str())
+        );
+
+        // Events appended after the checkpoint are not provable yet
+        chain
+            .append("late".to_string(), None, None, None, None, None)
+            .unwrap();
+        let checkpoints = vec![checkpoint];
+        assert!(AuditEventProof::create(&log, &checkpoints, 8).is_err());
+
+        for seq in 1..=7 {
+            let proof = AuditEventProof::create(&log, &checkpoints, seq).unwrap();
+            assert_eq!(proof.event["event"], format!("event_{}", seq - 1));
+            proof.verify(Some(&provider)).unwrap();
+        }
+
+        // Altered event content
+        let mut proof = AuditEventProof::create(&log, &checkpoints, 3).unwrap();
+        proof.event["event"] = serde_json::json!("key_rotated");
+        assert!(proof.verify(None).is_err());
+
+        // Valid event presented at another position
+        let mut proof = AuditEventProof::create(&log, &checkpoints, 3).unwrap();
+        proof.seq = 4;
+        assert!(proof.verify(None).is_err());
+    }
+
+    #[test]
+    fn test_event_proof_v1_log() {
+        let dir = tempdir().unwrap();
+        let provider = software_provider(dir.path());
+        let log = dir.path().join("agent.audit.jsonl");
+
+        let mut audit = AuditLog::new(&log).unwrap();
+        audit
+            .log_event("registry_add", serde_json::json!({"id": "proof_001"}))
+            .unwrap();
+        audit
+            .log_event("key_rotated", serde_json::json!({"kid": "abc"}))
+            .unwrap();
+        audit.log_event("export", serde_json::json!({})).unwrap();
+
+        let checkpoints = vec![AuditCheckpoint::create(&log, &provider, None).unwrap()];
+        let proof = AuditEventProof::create(&log, &checkpoints, 2).unwrap();
+        assert_eq!(proof.event["event"], "key_rotated");
+        proof.verify(Some(&provider)).unwrap();
+
+        let mut tampered = proof.clone();
+        tampered.event["details"]["kid"] = serde_json::json!("other");
+        assert!(tampered.verify(None).is_err());
+    }
+}
@@ -9,6 +9,11 @@ use std::fs::{File, OpenOptions};
 use std::io::{BufRead, BufReader, Write as IoWrite};
 use std::path::Path;
 
+use super::accumulator::MerkleAccumulator;
+use super::query::{query_events, AuditQuery};
+use super::segment::{self, chain_start, RotationPolicy, SegmentSeal};
+use crate::providers::KeyProvider;
+
 /// Audit event result
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "UPPERCASE")]
@@ -28,6 +33,23 @@ impl std::fmt::Display for AuditEventResult {
     }
 }
 
+impl std::str::FromStr for AuditEventResult {
+    type Err = String;
+
+    /// Parses `OK`, `WARN` or `FAIL` (case-insensitive)
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_uppercase().as_str() {
+            "OK" => Ok(Self::Ok),
+            "WARN" => Ok(Self::Warn),
+            "FAIL" => Ok(Self::Fail),
+            _ => Err(format!(
+                "Invalid result: {}. Valid options: OK, WARN, FAIL",
+                s
+            )),
+        }
+    }
+}
+
 /// Structured audit event (Track A)
 ///
 /// Contains cryptographic hash chain with tamper detection.
@@ -59,6 +81,10 @@ pub struct AuditEvent {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub run_id: Option<String>,
 
+    /// Free-form event details (desktop events, migrated v1.0 entries)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub details: Option<serde_json::Value>,
+
     /// Previous event hash (hash chain)
     pub prev_hash: String,
 
@@ -89,25 +115,67 @@ impl AuditEvent {
         result: Option<AuditEventResult>,
         run_id: Option<String>,
     ) -> Self {
-        let ts = Utc::now().to_rfc3339();
-
-        // Compute self_hash from canonical JSON (without self_hash field)
-        let mut temp_event = AuditEvent {
-            ts: ts.clone(),
-            event: event.clone(),
-            policy_id: policy_id.clone(),
-            ir_hash: ir_hash.clone(),
-            manifest_hash: manifest_hash.clone(),
-            result: result.clone(),
-            run_id: run_id.clone(),
-            prev_hash: prev_hash.clone(),
+        AuditEvent {
+            ts: Utc::now().to_rfc3339(),
+            event,
+            policy_id,
+            ir_hash,
+            manifest_hash,
+            result,
+            run_id,
+            details: None,
+            prev_hash,
             self_hash: String::new(), // Placeholder
-        };
+        }
+        .with_self_hash()
+    }
 
-        let self_hash = temp_event.compute_hash();
+    /// Creates an audit event from free-form JSON details
+    ///
+    /// The structured fields (`policy_id`, `ir_hash`, `manifest_hash`,
+    /// `result`, `run_id`) are taken from `details` if present; the details
+    /// themselves are kept unchanged and covered by the self hash.
+    ///
+    /// # Arguments
+    /// * `event` - Event type
+    /// * `prev_hash` - Previous event hash (or genesis "0x00...00")
+    /// * `ts` - Timestamp (preserved as is, e.g. when migrating)
+    /// * `details` - JSON details/metadata for the event
+    ///
+    /// # Returns
+    /// New audit event with computed self_hash
+    pub fn from_details(
+        event: String,
+        prev_hash: String,
+        ts: String,
+        details: serde_json::Value,
+    ) -> Self {
+        let field = |name: &str| details.get(name).and_then(|v| v.as_str()).map(String::from);
+        let result = details
+            .get("result")
+            .and_then(|v| v.as_str())
+            .and_then(|s| s.parse().ok());
+
+        AuditEvent {
+            ts,
+            event,
+            policy_id: field("policy_id"),
+            ir_hash: field("ir_hash"),
+            manifest_hash: field("manifest_hash"),
+            result,
+            run_id: field("run_id"),
+            // `null` does not survive a JSON round trip as Option
+            details: (!details.is_null()).then_some(details),
+            prev_hash,
+            self_hash: String::new(), // Placeholder
+        }
+        .with_self_hash()
+    }
 
-        temp_event.self_hash = self_hash;
-        temp_event
+    /// Computes self_hash from canonical JSON (without self_hash field)
+    fn with_self_hash(mut self) -> Self {
+        self.self_hash = self.compute_hash();
+        self
     }
 
     /// Computes SHA3-256 hash of event (without self_hash field)
@@ -134,6 +202,8 @@ impl AuditEvent {
             result: &'a Option<AuditEventResult>,
             #[serde(skip_serializing_if = "Option::is_none")]
             run_id: &'a Option<String>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            details: &'a Option<serde_json::Value>,
             prev_hash: &'a str,
         }
 
@@ -145,6 +215,7 @@ impl AuditEvent {
             manifest_hash: &self.manifest_hash,
             result: &self.result,
             run_id: &self.run_id,
+            details: &self.details,
             prev_hash: &self.prev_hash,
         };
 
@@ -206,9 +277,14 @@ impl VerifyReport {
 }
 
 /// Audit chain manager (JSONL storage)
+///
+/// Besides the hash chain, the manager maintains a Merkle accumulator over
+/// the event hashes (see `accumulator`), used for checkpoints and per-event
+/// inclusion proofs.
 pub struct AuditChain {
     path: String,
     last_hash: String,
+    accumulator: MerkleAccumulator,
 }
 
 impl AuditChain {
@@ -230,33 +306,39 @@ impl AuditChain {
             .ok_or_else(|| anyhow!("Invalid path"))?
             .to_string();
 
-        let last_hash = if path.as_ref().exists() {
-            Self::read_last_hash(&path_str)?
+        let (last_hash, accumulator) = if path.as_ref().exists() {
+            Self::read_state(&path_str)?
         } else {
-            Self::GENESIS_HASH.to_string()
+            (chain_start(&path_str)?, MerkleAccumulator::new())
         };
 
         Ok(Self {
             path: path_str,
             last_hash,
+            accumulator,
         })
     }
 
-    /// Reads last event hash from chain
-    fn read_last_hash(path: &str) -> Result<String> {
+    /// Reads last event hash and Merkle accumulator from chain
+    ///
+    /// Only the active segment is read; an empty segment continues from the
+    /// tail of the last sealed segment.
+    fn read_state(path: &str) -> Result<(String, MerkleAccumulator)> {
         let file = File::open(path)?;
         let reader = BufReader::new(file);
-        let mut last_hash = Self::GENESIS_HASH.to_string();
+        let mut last_hash = chain_start(path)?;
+        let mut accumulator = MerkleAccumulator::new();
 
         for line in reader.lines() {
             let line = line?;
             if !line.trim().is_empty() {
                 let event: AuditEvent = serde_json::from_str(&line)?;
-                last_hash = event.self_hash.clone();
+                accumulator.push_event(&event.self_hash);
+                last_hash = event.self_hash;
             }
         }
 
-        Ok(last_hash)
+        Ok((last_hash, accumulator))
     }
 
     /// Appends an event to the chain
@@ -289,6 +371,40 @@ impl AuditChain {
             result,
             run_id,
         );
+        self.push(audit_event)
+    }
+
+    /// Appends an event with free-form JSON details (see `AuditEvent::from_details`)
+    ///
+    /// # Arguments
+    /// * `event` - Event type
+    /// * `details` - JSON details/metadata for the event
+    ///
+    /// # Returns
+    /// The appended event
+    pub fn append_details(
+        &mut self,
+        event: &str,
+        details: serde_json::Value,
+    ) -> Result<AuditEvent> {
+        let audit_event = AuditEvent::from_details(
+            event.to_string(),
+            self.last_hash.clone(),
+            Utc::now().to_rfc3339(),
+            details,
+        );
+        self.push(audit_event)
+    }
+
+    /// Writes a prepared event that links to the current tail
+    pub(crate) fn push(&mut self, audit_event: AuditEvent) -> Result<AuditEvent> {
+        if audit_event.prev_hash != self.last_hash {
+            return Err(anyhow!(
+                "Event does not link to chain tail {} (prev_hash {})",
+                self.last_hash,
+                audit_event.prev_hash
+            ));
+        }
 
         // Write to JSONL
         let mut file = OpenOptions::new()
@@ -299,7 +415,8 @@ impl AuditChain {
         let json = serde_json::to_string(&audit_event)?;
         writeln!(file, "{}", json)?;
 
-        // Update last hash
+        // Update last hash and accumulator
+        self.accumulator.push_event(&audit_event.self_hash);
         self.last_hash = audit_event.self_hash.clone();
 
         Ok(audit_event)
@@ -310,20 +427,73 @@ impl AuditChain {
     pub fn tail_hash(&self) -> &str {
         &self.last_hash
     }
+
+    /// Returns the number of events in the chain
+    pub fn len(&self) -> u64 {
+        self.accumulator.len()
+    }
+
+    /// Returns true if the chain has no events
+    #[allow(dead_code)] // Public API - pairs with len()
+    pub fn is_empty(&self) -> bool {
+        self.accumulator.len() == 0
+    }
+
+    /// Returns the Merkle root over all event hashes
+    pub fn merkle_root(&self) -> String {
+        self.accumulator.root_hex()
+    }
+
+    /// Seals the active segment if the rotation policy says it is due
+    ///
+    /// # Arguments
+    /// * `policy` - Size and/or period limit of a segment
+    /// * `signer` - Optional key provider (and kid) that signs the seal
+    ///
+    /// # Returns
+    /// The seal of the rotated segment, or None if no rotation was due
+    pub fn rotate_if_due(
+        &mut self,
+        policy: &RotationPolicy,
+        signer: Option<(&dyn KeyProvider, Option<&str>)>,
+    ) -> Result<Option<SegmentSeal>> {
+        if policy.due(&self.path)?.is_none() {
+            return Ok(None);
+        }
+        let seal = segment::seal_segment(&self.path, signer)?;
+        if seal.is_some() {
+            // The new segment starts empty, the hash chain continues
+            self.accumulator = MerkleAccumulator::new();
+        }
+        Ok(seal)
+    }
 }
 
 /// Verifies the integrity of an audit chain
 ///
+/// For segmented logs (see `segment`) the file is the active segment and its
+/// first event must link to the tail of the last sealed segment.
+///
 /// # Arguments
 /// * `path` - Path to JSONL file
 ///
 /// # Returns
 /// VerifyReport with tamper detection
 pub fn verify_chain<P: AsRef<Path>>(path: P) -> Result<VerifyReport> {
-    let file = File::open(path)?;
-    let reader = BufReader::new(file);
+    let start_hash = chain_start(&path)?;
+    verify_chain_from(BufReader::new(File::open(path)?), &start_hash)
+}
 
-    let mut prev_hash = AuditChain::GENESIS_HASH.to_string();
+/// Verifies a stream of chained events whose first event links to `start_hash`
+///
+/// # Arguments
+/// * `reader` - JSONL events
+/// * `start_hash` - Expected `prev_hash` of the first event
+///
+/// # Returns
+/// VerifyReport with tamper detection
+pub fn verify_chain_from<R: BufRead>(reader: R, start_hash: &str) -> Result<VerifyReport> {
+    let mut prev_hash = start_hash.to_string();
     let mut index = 0;
 
     for line in reader.lines() {
@@ -364,6 +534,9 @@ pub fn verify_chain<P: AsRef<Path>>(path: P) -> Result<VerifyReport> {
 
 /// Exports events filtered by time range and/or policy
 ///
+/// Covers the whole history of the log (see `query::read_events`); use
+/// `query::AuditQuery` for the full set of filters.
+///
 /// # Arguments
 /// * `path` - Path to JSONL file
 /// * `from_ts` - Start timestamp (inclusive, optional)
@@ -372,49 +545,20 @@ pub fn verify_chain<P: AsRef<Path>>(path: P) -> Result<VerifyReport> {
 ///
 /// # Returns
 /// Vector of filtered events
+#[allow(dead_code)] // Public API - the CLI uses query::query_events
 pub fn export_events<P: AsRef<Path>>(
     path: P,
     from_ts: Option<&str>,
     to_ts: Option<&str>,
     policy_id: Option<&str>,
 ) -> Result<Vec<AuditEvent>> {
-    let file = File::open(path)?;
-    let reader = BufReader::new(file);
-
-    let mut events = Vec::new();
-
-    for line in reader.lines() {
-        let line = line?;
-        if line.trim().is_empty() {
-            continue;
-        }
-
-        let event: AuditEvent = serde_json::from_str(&line)?;
-
-        // Filter by timestamp
-        if let Some(from) = from_ts {
-            if event.ts.as_str() < from {
-                continue;
-            }
-        }
-
-        if let Some(to) = to_ts {
-            if event.ts.as_str() > to {
-                continue;
-            }
-        }
-
-        // Filter by policy_id
-        if let Some(policy) = policy_id {
-            if event.policy_id.as_deref() != Some(policy) {
-                continue;
-            }
-        }
-
-        events.push(event);
-    }
-
-    Ok(events)
+    let query = AuditQuery {
+        from: from_ts.map(String::from),
+        to: to_ts.map(String::from),
+        policy_id: policy_id.map(String::from),
+        ..Default::default()
+    };
+    query_events(path, &query)
 }
 
 #[cfg(test)]
@@ -438,6 +582,40 @@ mod tests {
         assert!(event.verify_self_hash());
     }
 
+    #[test]
+    fn test_event_details_hashed() {
+        let event = AuditEvent::from_details(
+            "verify_response".to_string(),
+            AuditChain::GENESIS_HASH.to_string(),
+            "2025-11-24T13:49:14+00:00".to_string(),
+            serde_json::json!({"policy_id": "lksg.v1", "result": "ok", "note": "x"}),
+        );
+        assert_eq!(event.policy_id.as_deref(), Some("lksg.v1"));
+        assert_eq!(event.result, Some(AuditEventResult::Ok));
+        assert!(event.verify_self_hash());
+
+        let mut tampered = event.clone();
+        tampered.details = Some(serde_json::json!({"policy_id": "lksg.v1", "note": "y"}));
+        assert!(!tampered.verify_self_hash());
+
+        // Events without details keep their original hash
+        let plain: AuditEvent = serde_json::from_str(
+            &serde_json::to_string(&AuditEvent::new(
+                "plain".to_string(),
+                AuditChain::GENESIS_HASH.to_string(),
+                None,
+                None,
+                None,
+                None,
+                None,
+            ))
+            .unwrap(),
+        )
+        .unwrap();
+        assert!(plain.details.is_none());
+        assert!(plain.verify_self_hash());
+    }
+
     #[test]
     fn test_audit_chain_append() {
         let temp_file = NamedTempFile::new().unwrap();
@@ -465,6 +643,11 @@ mod tests {
 
         assert_eq!(event2.prev_hash, event1.self_hash);
         assert_eq!(chain.tail_hash(), &event2.self_hash);
+
+        // Reopening rebuilds the Merkle accumulator from the file
+        let reopened = AuditChain::new(temp_file.path()).unwrap();
+        assert_eq!(reopened.len(), 2);
+        assert_eq!(reopened.merkle_root(), chain.merkle_root());
     }
 
     #[test]
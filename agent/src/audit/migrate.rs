@@ -0,0 +1,342 @@
+//! Migration of v1.0 Audit Logs to the Structured Chain
+//!
+//! Converts an `AuditLog` file (`seq`/`digest`) into the structured v2 format
+//! (`prev_hash`/`self_hash`) without losing information: every entry keeps its
+//! timestamp, event type and details, and `seq`, `prev_digest` and `digest`
+//! follow from the position in the log. The migrated events are followed by a
+//! bridge event (`audit_migrated`) that records the event count and the old
+//! tail digest. `verify_migration` recomputes the v1.0 digests from the
+//! migrated events and checks that they lead to exactly that tail, so a
+//! previously published v1.0 tip (`audit tip`) stays verifiable.
+
+use anyhow::{anyhow, bail, Result};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+
+use super::checkpoint::checkpoint_path;
+use super::hash_chain::{AuditChain, AuditEvent};
+use super::v1_0::{AuditEntry, AuditLog};
+
+/// Event type of the bridge event
+pub const MIGRATION_EVENT: &str = "audit_migrated";
+
+/// Source format recorded in the bridge event
+pub const SOURCE_FORMAT: &str = "v1.0";
+
+/// Details of the bridge event
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MigrationBridge {
+    pub source_format: String,
+    /// Number of migrated v1.0 entries (they precede the bridge event)
+    pub source_events: u64,
+    /// `digest` of the last v1.0 entry (genesis for an empty log)
+    pub source_tail_digest: String,
+}
+
+/// Result of a migration
+#[derive(Debug, Clone, Serialize)]
+pub struct MigrationReport {
+    pub bridge: MigrationBridge,
+    /// Events in the migrated log, including the bridge event
+    pub events: u64,
+    pub tail_hash: String,
+    /// Untouched v1.0 original (in-place migration only)
+    pub backup: Option<PathBuf>,
+}
+
+/// Result of the bridge verification
+#[derive(Debug, Clone, Serialize)]
+pub struct BridgeReport {
+    #[serde(flatten)]
+    pub bridge: MigrationBridge,
+    pub ok: bool,
+    pub error: Option<String>,
+}
+
+/// Migrates a v1.0 audit log into a new structured chain
+///
+/// The source must verify as an intact v1.0 chain; a broken log is never
+/// migrated.
+///
+/// # Arguments
+/// * `source` - v1.0 audit log (`AuditLog`)
+/// * `target` - New v2 audit chain (must not exist yet)
+///
+/// # Returns
+/// MigrationReport with the bridge and the new tail hash
+pub fn migrate_v1_log<P: AsRef<Path>, Q: AsRef<Path>>(
+    source: P,
+    target: Q,
+) -> Result<MigrationReport> {
+    let target = target.as_ref();
+    if target.exists() {
+        bail!("Target {} already exists", target.display());
+    }
+
+    let report = AuditLog::verify_file(&source).map_err(|e| anyhow!("{}", e))?;
+    if !report.ok {
+        bail!(
+            "Source is not an intact v1.0 audit log: {}",
+            report.error.unwrap_or_default()
+        );
+    }
+
+    let mut chain = AuditChain::new(target)?;
+    let mut tail_digest = AuditChain::GENESIS_HASH.to_string();
+    let mut source_events = 0;
+
+    for line in BufReader::new(File::open(&source)?).lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let entry: AuditEntry = serde_json::from_str(&line)?;
+        chain.push(AuditEvent::from_details(
+            entry.event,
+            chain.tail_hash().to_string(),
+            entry.ts,
+            entry.details,
+        ))?;
+        tail_digest = entry.digest;
+        source_events += 1;
+    }
+
+    let bridge = MigrationBridge {
+        source_format: SOURCE_FORMAT.to_string(),
+        source_events,
+        source_tail_digest: tail_digest,
+    };
+    chain.push(AuditEvent::from_details(
+        MIGRATION_EVENT.to_string(),
+        chain.tail_hash().to_string(),
+        Utc::now().to_rfc3339(),
+        serde_json::to_value(&bridge)?,
+    ))?;
+
+    Ok(MigrationReport {
+        bridge,
+        events: chain.len(),
+        tail_hash: chain.tail_hash().to_string(),
+        backup: None,
+    })
+}
+
+/// Migrates a v1.0 audit log in place
+///
+/// The original is kept as `<name>.v1.jsonl` (its checkpoints move along to
+/// `<name>.v1.checkpoints.jsonl`), the structured chain takes its path.
+///
+/// # Arguments
+/// * `path` - v1.0 audit log
+///
+/// # Returns
+/// MigrationReport with the backup path
+pub fn migrate_v1_in_place<P: AsRef<Path>>(path: P) -> Result<MigrationReport> {
+    let path = path.as_ref();
+    let backup = backup_path(path);
+    if backup.exists() {
+        bail!("Backup {} already exists", backup.display());
+    }
+
+    let tmp = path.with_extension("migrating");
+    if tmp.exists() {
+        fs::remove_file(&tmp)?;
+    }
+    let mut report = match migrate_v1_log(path, &tmp) {
+        Ok(report) => report,
+        Err(e) => {
+            let _ = fs::remove_file(&tmp);
+            return Err(e);
+        }
+    };
+
+    fs::rename(path, &backup)?;
+    let checkpoints = checkpoint_path(path);
+    if checkpoints.exists() {
+        fs::rename(&checkpoints, checkpoint_path(&backup))?;
+    }
+    fs::rename(&tmp, path)?;
+
+    report.backup = Some(backup);
+    Ok(report)
+}
+
+/// Path of the v1.0 original after an in-place migration
+pub fn backup_path<P: AsRef<Path>>(path: P) -> PathBuf {
+    path.as_ref().with_extension("v1.jsonl")
+}
+
+/// Verifies the bridge of a migrated audit chain
+///
+/// Recomputes the v1.0 digests of the events before the bridge event and
+/// compares the resulting tail with the recorded one. The v2 hash chain itself
+/// is checked by `verify_chain`.
+///
+/// # Arguments
+/// * `path` - Structured audit chain
+///
+/// # Returns
+/// BridgeReport, or None if the log was not migrated
+pub fn verify_migration<P: AsRef<Path>>(path: P) -> Result<Option<BridgeReport>> {
+    let mut events = Vec::new();
+    let mut bridge = None;
+
+    for line in BufReader::new(File::open(path)?).lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let event: AuditEvent = serde_json::from_str(&line)?;
+        if event.event == MIGRATION_EVENT {
+            let details = event
+                .details
+                .ok_or_else(|| anyhow!("Bridge event without details"))?;
+            bridge = Some(serde_json::from_value::<MigrationBridge>(details)?);
+            break;
+        }
+        events.push(event);
+    }
+
+    let Some(bridge) = bridge else {
+        return Ok(None);
+    };
+
+    let error = if bridge.source_format != SOURCE_FORMAT {
+        Some(format!(
+            "Unsupported source format {}",
+            bridge.source_format
+        ))
+    } else if events.len() as u64 != bridge.source_events {
+        Some(format!(
+            "Bridge records {} v1.0 entries, {} events precede it",
+            bridge.source_events,
+            events.len()
+        ))
+    } else {
+        let tail = events.iter().enumerate().fold(
+            AuditChain::GENESIS_HASH.to_string(),
+            |prev, (i, event)| {
+                AuditLog::compute_digest(
+                    i as u64 + 1,
+                    &event.ts,
+                    &event.event,
+                    event.details.as_ref().unwrap_or(&serde_json::Value::Null),
+                    &prev,
+                )
+            },
+        );
+        (tail != bridge.source_tail_digest).then(|| {
+            format!(
+                "v1.0 tail digest mismatch: recomputed {}, bridge records {}",
+                tail, bridge.source_tail_digest
+            )
+        })
+    };
+
+    Ok(Some(BridgeReport {
+        bridge,
+        ok: error.is_none(),
+        error,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audit::hash_chain::verify_chain;
+    use serde_json::json;
+    use tempfile::tempdir;
+
+    fn write_v1_log(path: &Path) -> String {
+        let mut log = AuditLog::new(path).unwrap();
+        log.log_event(
+            "csv_imported",
+            json!({"csv_type": "suppliers", "record_count": 3}),
+        )
+        .unwrap();
+        log.log_event("policy_loaded", json!({"policy_id": "lksg.v1"}))
+            .unwrap();
+        log.log_event("export", json!(null)).unwrap();
+        log.get_tip()
+    }
+
+    #[test]
+    fn test_migrate_in_place_keeps_bridge_to_v1_tail() {
+        let dir = tempdir().unwrap();
+        let log = dir.path().join("agent.audit.jsonl");
+        let tip = write_v1_log(&log);
+
+        let report = migrate_v1_in_place(&log).unwrap();
+        assert_eq!(report.bridge.source_events, 3);
+        assert_eq!(report.bridge.source_tail_digest, tip);
+        assert_eq!(report.events, 4);
+        assert!(backup_path(&log).exists());
+        assert!(AuditLog::verify_file(backup_path(&log)).unwrap().ok);
+
+        assert!(verify_chain(&log).unwrap().ok);
+        let bridge = verify_migration(&log).unwrap().unwrap();
+        assert!(bridge.ok, "{:?}", bridge.error);
+
+        // Details survive and structured fields are extracted
+        let migrated: Vec<AuditEvent> = fs::read_to_string(&log)
+            .unwrap()
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        assert_eq!(migrated[0].details.as_ref().unwrap()["record_count"], 3);
+        assert_eq!(migrated[1].policy_id.as_deref(), Some("lksg.v1"));
+
+        // The chain keeps growing in the structured format
+        let mut chain = AuditChain::new(&log).unwrap();
+        chain.append_details("manifest_built", json!({})).unwrap();
+        assert!(verify_chain(&log).unwrap().ok);
+        assert!(verify_migration(&log).unwrap().unwrap().ok);
+
+        // A second migration would overwrite the backup
+        assert!(migrate_v1_in_place(&log).is_err());
+    }
+
+    #[test]
+    fn test_migration_rejects_broken_source_and_detects_forged_bridge() {
+        let dir = tempdir().unwrap();
+        let source = dir.path().join("agent.audit.jsonl");
+        write_v1_log(&source);
+
+        let content = fs::read_to_string(&source).unwrap();
+        let broken = dir.path().join("broken.jsonl");
+        fs::write(&broken, content.replace("suppliers", "ubos")).unwrap();
+        assert!(migrate_v1_log(&broken, dir.path().join("out.jsonl")).is_err());
+
+        // Re-chained log with a bridge to another tail: v2 chain intact, bridge not
+        let target = dir.path().join("chain.jsonl");
+        migrate_v1_log(&source, &target).unwrap();
+        let events: Vec<AuditEvent> = fs::read_to_string(&target)
+            .unwrap()
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        let forged = dir.path().join("forged.jsonl");
+        let mut chain = AuditChain::new(&forged).unwrap();
+        for event in &events[..2] {
+            chain
+                .push(AuditEvent::from_details(
+                    event.event.clone(),
+                    chain.tail_hash().to_string(),
+                    event.ts.clone(),
+                    event.details.clone().unwrap(),
+                ))
+                .unwrap();
+        }
+        let mut details = events[3].details.clone().unwrap();
+        details["source_events"] = json!(2);
+        chain.append_details(MIGRATION_EVENT, details).unwrap();
+
+        assert!(verify_chain(&forged).unwrap().ok);
+        let bridge = verify_migration(&forged).unwrap().unwrap();
+        assert!(!bridge.ok);
+        assert!(bridge.error.unwrap().contains("tail digest mismatch"));
+    }
+}
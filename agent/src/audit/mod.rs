@@ -2,7 +2,15 @@
 //!
 //! Modular audit structure with hash-chain verification and export.
 
+pub mod accumulator;
+pub mod checkpoint;
+pub mod event_proof;
 pub mod hash_chain;
+pub mod migrate;
+pub mod query;
+pub mod report;
+pub mod segment;
+pub mod traits;
 pub mod v1_0;
 
 // Re-export v1.0 types for backwards compatibility (used in tests)
@@ -12,5 +20,41 @@ pub use v1_0::{AuditEntry, AuditLog};
 // Re-export v2 types (Track A, used in tests)
 #[allow(unused_imports)]
 pub use hash_chain::{
-    export_events, verify_chain, AuditChain, AuditEvent, AuditEventResult, VerifyReport,
+    export_events, verify_chain, verify_chain_from, AuditChain, AuditEvent, AuditEventResult,
+    VerifyReport,
 };
+
+// Re-export checkpoint types (signed tail commitments)
+#[allow(unused_imports)]
+pub use checkpoint::{
+    append_checkpoint, checkpoint_path, load_checkpoints, verify_log_checkpoints, AuditCheckpoint,
+    CheckpointReport, LogFormat,
+};
+
+// Re-export Merkle accumulator and per-event inclusion proofs
+#[allow(unused_imports)]
+pub use accumulator::MerkleAccumulator;
+#[allow(unused_imports)]
+pub use event_proof::AuditEventProof;
+
+// Re-export segment types (rotation, sealed archives)
+#[allow(unused_imports)]
+pub use segment::{
+    seal_segment, verify_segments, RotationPeriod, RotationPolicy, SegmentReport, SegmentSeal,
+};
+
+// Re-export event query (filters, CSV export)
+#[allow(unused_imports)]
+pub use query::{query_events, read_events, write_csv, AuditQuery};
+
+// Re-export audit report (Markdown/HTML)
+#[allow(unused_imports)]
+pub use report::{AuditReport, ReportOptions, ReportPeriod};
+
+// Re-export unified store interface (used by the desktop app)
+#[allow(unused_imports)]
+pub use traits::{create_audit_store, AuditStore};
+
+// Re-export v1.0 migration (bridge to the old tail digest)
+#[allow(unused_imports)]
+pub use migrate::{migrate_v1_in_place, migrate_v1_log, verify_migration, MigrationReport};
@@ -0,0 +1,335 @@
+//! Audit Query - Filtering and CSV export of audit events
+//!
+//! `AuditQuery` filters events by time range, event type, `policy_id`,
+//! `run_id`, `manifest_hash` and result. `read_events` loads the whole history
+//! of a log: archived segments (where the archive is still present) followed
+//! by the active log. v1.0 logs are presented in the structured shape, with
+//! the structured fields taken from `details` and `prev_hash`/`self_hash`
+//! carrying `prev_digest`/`digest`.
+//!
+//! Time bounds are inclusive and compared as instants; a plain date
+//! (`2025-12-31`) covers the whole day.
+
+use anyhow::{bail, Result};
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveTime};
+use std::fs::File;
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+
+use super::hash_chain::{AuditEvent, AuditEventResult};
+use super::segment::{load_seals, parse_events, read_archive};
+use super::v1_0::AuditEntry;
+
+/// Filter over audit events (all set filters must match)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AuditQuery {
+    /// From (RFC3339 or YYYY-MM-DD, inclusive)
+    pub from: Option<String>,
+    /// To (RFC3339 or YYYY-MM-DD, inclusive)
+    pub to: Option<String>,
+    /// Event types (any of; empty = all)
+    pub events: Vec<String>,
+    pub policy_id: Option<String>,
+    pub run_id: Option<String>,
+    pub manifest_hash: Option<String>,
+    pub result: Option<AuditEventResult>,
+}
+
+/// Parses a time bound; plain dates cover the whole day
+fn parse_bound(value: &str, end_of_day: bool) -> Option<DateTime<FixedOffset>> {
+    if let Ok(ts) = DateTime::parse_from_rfc3339(value) {
+        return Some(ts);
+    }
+    let date = NaiveDate::parse_from_str(value, "%Y-%m-%d").ok()?;
+    let time = if end_of_day {
+        NaiveTime::from_hms_nano_opt(23, 59, 59, 999_999_999)?
+    } else {
+        NaiveTime::MIN
+    };
+    Some(date.and_time(time).and_utc().fixed_offset())
+}
+
+impl AuditQuery {
+    /// Checks the time bounds
+    pub fn validate(&self) -> Result<()> {
+        for (bound, end_of_day) in [(&self.from, false), (&self.to, true)] {
+            if let Some(value) = bound {
+                if parse_bound(value, end_of_day).is_none() {
+                    bail!("Invalid time bound (RFC3339 or YYYY-MM-DD): {}", value);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// True if the event passes all filters
+    ///
+    /// Events with an unparsable timestamp never pass a time filter.
+    pub fn matches(&self, event: &AuditEvent) -> bool {
+        let eq = |filter: &Option<String>, value: &Option<String>| match filter {
+            Some(expected) => value.as_deref() == Some(expected.as_str()),
+            None => true,
+        };
+        if !eq(&self.policy_id, &event.policy_id)
+            || !eq(&self.run_id, &event.run_id)
+            || !eq(&self.manifest_hash, &event.manifest_hash)
+        {
+            return false;
+        }
+        if !self.events.is_empty() && !self.events.contains(&event.event) {
+            return false;
+        }
+        if self.result.is_some() && self.result != event.result {
+            return false;
+        }
+
+        if self.from.is_some() || self.to.is_some() {
+            let Ok(ts) = DateTime::parse_from_rfc3339(&event.ts) else {
+                return false;
+            };
+            let from = self.from.as_deref().and_then(|v| parse_bound(v, false));
+            let to = self.to.as_deref().and_then(|v| parse_bound(v, true));
+            if from.is_some_and(|from| ts < from) || to.is_some_and(|to| ts > to) {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Keeps the matching events (in log order)
+    pub fn apply(&self, events: Vec<AuditEvent>) -> Vec<AuditEvent> {
+        events.into_iter().filter(|e| self.matches(e)).collect()
+    }
+}
+
+/// Presents a v1.0 entry in the structured shape
+fn v1_view(entry: AuditEntry) -> AuditEvent {
+    let mut event =
+        AuditEvent::from_details(entry.event, entry.prev_digest, entry.ts, entry.details);
+    event.self_hash = entry.digest;
+    event
+}
+
+/// Reads the events of one log file (v1.0 or v2)
+fn read_log_events<P: AsRef<Path>>(path: P) -> Result<Vec<AuditEvent>> {
+    let reader = BufReader::new(File::open(path)?);
+    let mut events = Vec::new();
+
+    for line in reader.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let value: serde_json::Value = serde_json::from_str(&line)?;
+        let event = if value.get("self_hash").is_some() {
+            serde_json::from_value(value)?
+        } else {
+            v1_view(serde_json::from_value(value)?)
+        };
+        events.push(event);
+    }
+
+    Ok(events)
+}
+
+/// Reads the full history of a log: archived segments, then the active log
+///
+/// Segments whose archive was removed (retention) are skipped; their seals
+/// still cover them in `verify_segments`.
+pub fn read_events<P: AsRef<Path>>(log_path: P) -> Result<Vec<AuditEvent>> {
+    let mut events = Vec::new();
+    for seal in load_seals(&log_path)? {
+        if let Some(content) = read_archive(&log_path, &seal)? {
+            events.extend(parse_events(&content)?);
+        }
+    }
+    events.extend(read_log_events(&log_path)?);
+    Ok(events)
+}
+
+/// Reads the history of a log and applies the query
+pub fn query_events<P: AsRef<Path>>(log_path: P, query: &AuditQuery) -> Result<Vec<AuditEvent>> {
+    query.validate()?;
+    Ok(query.apply(read_events(log_path)?))
+}
+
+/// Writes events as CSV (one row per event, `details` as JSON)
+pub fn write_csv<W: Write>(events: &[AuditEvent], writer: W) -> Result<()> {
+    let mut csv = csv::Writer::from_writer(writer);
+    csv.write_record([
+        "ts",
+        "event",
+        "policy_id",
+        "ir_hash",
+        "manifest_hash",
+        "result",
+        "run_id",
+        "details",
+        "prev_hash",
+        "self_hash",
+    ])?;
+
+    for event in events {
+        let details = match &event.details {
+            Some(details) => serde_json::to_string(details)?,
+            None => String::new(),
+        };
+        let result = event
+            .result
+            .as_ref()
+            .map(|r| r.to_string())
+            .unwrap_or_default();
+        csv.write_record([
+            event.ts.as_str(),
+            event.event.as_str(),
+            event.policy_id.as_deref().unwrap_or(""),
+            event.ir_hash.as_deref().unwrap_or(""),
+            event.manifest_hash.as_deref().unwrap_or(""),
+            result.as_str(),
+            event.run_id.as_deref().unwrap_or(""),
+            details.as_str(),
+            event.prev_hash.as_str(),
+            event.self_hash.as_str(),
+        ])?;
+    }
+
+    csv.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audit::{seal_segment, AuditChain, AuditLog};
+    use serde_json::json;
+    use tempfile::tempdir;
+
+    fn event(ts: &str, name: &str, details: serde_json::Value) -> AuditEvent {
+        AuditEvent::from_details(
+            name.to_string(),
+            AuditChain::GENESIS_HASH.to_string(),
+            ts.to_string(),
+            details,
+        )
+    }
+
+    fn events() -> Vec<AuditEvent> {
+        vec![
+            event(
+                "2025-01-15T10:00:00Z",
+                "verify_response",
+                json!({"policy_id": "lksg.v1", "run_id": "run-1", "result": "OK"}),
+            ),
+            event(
+                "2025-03-31T23:30:00+00:00",
+                "verify_response",
+                json!({"policy_id": "lksg.v1", "run_id": "run-2", "result": "FAIL", "manifest_hash": "0xm2"}),
+            ),
+            event(
+                "2025-04-01T01:00:00+02:00",
+                "key_rotated",
+                json!({"kid": "abc"}),
+            ),
+            event("not a date", "export", json!({})),
+        ]
+    }
+
+    fn names(query: &AuditQuery) -> Vec<String> {
+        query
+            .apply(events())
+            .iter()
+            .map(|e| format!("{}@{}", e.event, &e.ts[..10]))
+            .collect()
+    }
+
+    #[test]
+    fn test_filters() {
+        let query = |f: fn(&mut AuditQuery)| {
+            let mut query = AuditQuery::default();
+            f(&mut query);
+            query.validate().unwrap();
+            names(&query)
+        };
+
+        assert_eq!(query(|_| {}).len(), 4);
+        assert_eq!(
+            query(|q| q.events = vec!["key_rotated".to_string(), "export".to_string()]),
+            ["key_rotated@2025-04-01", "export@not a date"]
+        );
+        assert_eq!(query(|q| q.run_id = Some("run-2".to_string())).len(), 1);
+        assert_eq!(
+            query(|q| q.manifest_hash = Some("0xm2".to_string())).len(),
+            1
+        );
+        assert_eq!(
+            query(|q| q.result = Some(AuditEventResult::Fail)),
+            ["verify_response@2025-03-31"]
+        );
+        assert_eq!(
+            query(|q| q.policy_id = Some("lksg.v1".to_string())).len(),
+            2
+        );
+
+        // Plain dates cover the whole day, instants are compared across offsets
+        assert_eq!(
+            query(|q| {
+                q.from = Some("2025-01-15".to_string());
+                q.to = Some("2025-03-31".to_string());
+            }),
+            [
+                "verify_response@2025-01-15",
+                "verify_response@2025-03-31",
+                "key_rotated@2025-04-01"
+            ]
+        );
+        assert_eq!(
+            query(|q| q.to = Some("2025-03-31T22:00:00Z".to_string())),
+            ["verify_response@2025-01-15"]
+        );
+
+        let invalid = AuditQuery {
+            from: Some("last year".to_string()),
+            ..Default::default()
+        };
+        assert!(invalid.validate().is_err());
+    }
+
+    #[test]
+    fn test_read_events_v1_and_segments() {
+        let dir = tempdir().unwrap();
+
+        // v1.0 log: structured fields come from details
+        let v1 = dir.path().join("agent.audit.jsonl");
+        let mut log = AuditLog::new(&v1).unwrap();
+        log.log_event("policy_loaded", json!({"policy_id": "lksg.v1"}))
+            .unwrap();
+        let events = read_events(&v1).unwrap();
+        assert_eq!(events[0].policy_id.as_deref(), Some("lksg.v1"));
+        assert_eq!(events[0].self_hash, log.get_tip());
+
+        // Rotated chain: archived and active events in order
+        let chain_path = dir.path().join("audit_chain.jsonl");
+        let mut chain = AuditChain::new(&chain_path).unwrap();
+        chain
+            .append_details("first", json!({"run_id": "r1"}))
+            .unwrap();
+        seal_segment(&chain_path, None).unwrap();
+        let mut chain = AuditChain::new(&chain_path).unwrap();
+        chain
+            .append_details("second", json!({"run_id": "r2"}))
+            .unwrap();
+
+        let events = read_events(&chain_path).unwrap();
+        assert_eq!(
+            events.iter().map(|e| e.event.as_str()).collect::<Vec<_>>(),
+            ["first", "second"]
+        );
+
+        let mut csv = Vec::new();
+        write_csv(&events, &mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        assert!(csv.starts_with("ts,event,policy_id"));
+        assert!(csv.contains(r#"{""run_id"":""r2""}"#));
+        assert_eq!(csv.lines().count(), 3);
+    }
+}
@@ -0,0 +1,716 @@
+//! Audit Report - Printable summary of an audit log (Markdown/HTML)
+//!
+//! Built for attaching to the annual LkSG report: the report states the
+//! integrity of the log (hash chain, v1.0 migration bridge, sealed segments,
+//! signed checkpoints), lists the time anchors (timestamp events in the log
+//! and anchors of given manifests, each located in the log by its audit tip)
+//! and summarises the events per period with the key events spelled out.
+//! `AuditReport` is plain data (also serialisable as JSON); `to_markdown`
+//! and `to_html` render the same content.
+
+use anyhow::{anyhow, bail, Result};
+use chrono::{DateTime, Datelike, Utc};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+use super::checkpoint::{
+    checkpoint_path, load_checkpoints, read_event_hashes, verify_log_checkpoints, CheckpointReport,
+    LogFormat,
+};
+use super::hash_chain::{verify_chain, AuditEvent, AuditEventResult};
+use super::migrate::{verify_migration, BridgeReport};
+use super::query::{read_events, AuditQuery};
+use super::segment::{segments_dir, verify_segments, SegmentReport};
+use super::v1_0::AuditLog;
+use crate::manifest::Manifest;
+use crate::providers::KeyProvider;
+
+/// Event types listed individually in the report (besides WARN/FAIL results)
+pub const KEY_EVENTS: &[&str] = &[
+    "key_generated",
+    "key_rotated",
+    "key_archived",
+    "key_attested",
+    "policy_loaded",
+    "policy_rules_activated",
+    "manifest_signed",
+    "registry_entry_added",
+    "registry_entry_revoked",
+    "registry_snapshot_imported",
+    "registry_migrated",
+    "registry_schema_migrated",
+    "bundle_exported",
+    "timestamp_generated",
+    "audit_migrated",
+];
+
+/// Event types that anchor the audit tip externally
+const ANCHOR_EVENTS: &[&str] = &["timestamp_generated"];
+
+/// Grouping of events in the report
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportPeriod {
+    Monthly,
+    #[default]
+    Quarterly,
+    Yearly,
+}
+
+impl ReportPeriod {
+    /// Parses `monthly`, `quarterly` or `yearly`
+    pub fn parse(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "monthly" => Ok(Self::Monthly),
+            "quarterly" => Ok(Self::Quarterly),
+            "yearly" => Ok(Self::Yearly),
+            _ => bail!(
+                "Invalid report period: {}. Valid options: monthly, quarterly, yearly",
+                s
+            ),
+        }
+    }
+
+    /// Period label of a timestamp (e.g. `2025-03`, `2025-Q1`, `2025`)
+    fn label(&self, ts: &str) -> String {
+        let Ok(ts) = DateTime::parse_from_rfc3339(ts) else {
+            return "ohne Zeitstempel".to_string();
+        };
+        let ts = ts.with_timezone(&Utc);
+        match self {
+            Self::Monthly => ts.format("%Y-%m").to_string(),
+            Self::Quarterly => format!("{}-Q{}", ts.year(), ts.month0() / 3 + 1),
+            Self::Yearly => ts.format("%Y").to_string(),
+        }
+    }
+}
+
+/// Options of a report
+#[derive(Debug, Clone, Default)]
+pub struct ReportOptions {
+    pub title: Option<String>,
+    /// Events covered by the period statistics
+    pub query: AuditQuery,
+    pub period: ReportPeriod,
+    /// Manifests whose time anchors are listed
+    pub manifests: Vec<PathBuf>,
+    /// Checkpoint file (default: next to the log)
+    pub checkpoints: Option<PathBuf>,
+}
+
+/// Hash chain verification result
+#[derive(Debug, Clone, Serialize)]
+pub struct ChainStatus {
+    pub ok: bool,
+    pub total_events: usize,
+    pub tamper_index: Option<usize>,
+    pub error: Option<String>,
+}
+
+/// Signed checkpoint as listed in the report
+#[derive(Debug, Clone, Serialize)]
+pub struct CheckpointSummary {
+    pub timestamp: String,
+    pub tree_size: u64,
+    pub kid: String,
+    pub root_hash: Option<String>,
+}
+
+/// Time anchor of the audit tip
+#[derive(Debug, Clone, Serialize)]
+pub struct AnchorSummary {
+    /// `log` or the manifest path
+    pub source: String,
+    pub kind: String,
+    pub reference: String,
+    pub audit_tip: String,
+    pub created_at: String,
+    /// 1-based position of the anchored event in the log (None: not found)
+    pub event_index: Option<usize>,
+}
+
+/// Individually listed event
+#[derive(Debug, Clone, Serialize)]
+pub struct KeyEvent {
+    pub ts: String,
+    pub event: String,
+    pub result: Option<AuditEventResult>,
+    pub policy_id: Option<String>,
+    pub manifest_hash: Option<String>,
+    pub run_id: Option<String>,
+}
+
+/// Event statistics of one period
+#[derive(Debug, Clone, Serialize)]
+pub struct PeriodSummary {
+    pub period: String,
+    pub events: usize,
+    pub by_type: BTreeMap<String, usize>,
+    pub by_result: BTreeMap<String, usize>,
+    pub key_events: Vec<KeyEvent>,
+}
+
+/// Audit report of one log
+#[derive(Debug, Clone, Serialize)]
+pub struct AuditReport {
+    pub title: String,
+    pub generated_at: String,
+    pub log: String,
+    pub format: LogFormat,
+    pub from: Option<String>,
+    pub to: Option<String>,
+    pub period: ReportPeriod,
+    pub chain: ChainStatus,
+    pub migration: Option<BridgeReport>,
+    pub segments: Option<SegmentReport>,
+    pub checkpoints: CheckpointReport,
+    pub checkpoint_list: Vec<CheckpointSummary>,
+    pub anchors: Vec<AnchorSummary>,
+    pub periods: Vec<PeriodSummary>,
+}
+
+/// Normalised audit tip for comparisons (no `0x`, lowercase)
+fn normalize_tip(tip: &str) -> String {
+    tip.trim().trim_start_matches("0x").to_lowercase()
+}
+
+fn is_key_event(event: &AuditEvent) -> bool {
+    KEY_EVENTS.contains(&event.event.as_str())
+        || matches!(
+            event.result,
+            Some(AuditEventResult::Warn | AuditEventResult::Fail)
+        )
+}
+
+impl AuditReport {
+    /// Verifies the log and collects the report data
+    ///
+    /// # Arguments
+    /// * `log_path` - Audit log (v1.0 or structured, possibly segmented)
+    /// * `options` - Title, event filter, period, manifests, checkpoints
+    /// * `trusted` - Optional key provider that must have signed checkpoints and seals
+    pub fn build<P: AsRef<Path>>(
+        log_path: P,
+        options: &ReportOptions,
+        trusted: Option<&dyn KeyProvider>,
+    ) -> Result<Self> {
+        let log_path = log_path.as_ref();
+        options.query.validate()?;
+
+        // Integrity
+        let (format, _) = read_event_hashes(log_path)?;
+        let verify = match format {
+            LogFormat::V1 => AuditLog::verify_file(log_path).map_err(|e| anyhow!("{}", e))?,
+            LogFormat::V2 => verify_chain(log_path)?,
+        };
+        let migration = match format {
+            LogFormat::V1 => None,
+            LogFormat::V2 => verify_migration(log_path)?,
+        };
+        let segments = if segments_dir(log_path).exists() {
+            Some(verify_segments(log_path, trusted)?)
+        } else {
+            None
+        };
+        let cp_path = options
+            .checkpoints
+            .clone()
+            .unwrap_or_else(|| checkpoint_path(log_path));
+        let checkpoints = verify_log_checkpoints(log_path, &cp_path, trusted)?;
+        let checkpoint_list = if cp_path.exists() {
+            load_checkpoints(&cp_path)?
+                .into_iter()
+                .map(|cp| CheckpointSummary {
+                    timestamp: cp.timestamp,
+                    tree_size: cp.tree_size,
+                    kid: cp.kid,
+                    root_hash: cp.root_hash,
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        // Events of the whole history, positions for locating anchors
+        let all_events = read_events(log_path)?;
+        let positions: HashMap<String, usize> = all_events
+            .iter()
+            .enumerate()
+            .map(|(i, e)| (normalize_tip(&e.self_hash), i + 1))
+            .collect();
+        let locate = |tip: &str| positions.get(&normalize_tip(tip)).copied();
+
+        let in_range = AuditQuery {
+            from: options.query.from.clone(),
+            to: options.query.to.clone(),
+            ..Default::default()
+        };
+        let mut anchors: Vec<AnchorSummary> = all_events
+            .iter()
+            .filter(|e| ANCHOR_EVENTS.contains(&e.event.as_str()) && in_range.matches(e))
+            .filter_map(|e| {
+                let details = e.details.as_ref()?;
+                let tip = details.get("audit_tip")?.as_str()?;
+                let field = |name: &str| details.get(name).and_then(|v| v.as_str());
+                Some(AnchorSummary {
+                    source: "log".to_string(),
+                    kind: field("version").unwrap_or("timestamp").to_string(),
+                    reference: field("tsa").unwrap_or_default().to_string(),
+                    audit_tip: tip.to_string(),
+                    created_at: field("created_at").unwrap_or(&e.ts).to_string(),
+                    event_index: locate(tip),
+                })
+            })
+            .collect();
+        for manifest_path in &options.manifests {
+            let manifest = Manifest::load(manifest_path)
+                .map_err(|e| anyhow!("Cannot load manifest {}: {}", manifest_path.display(), e))?;
+            let Some(anchor) = manifest.time_anchor else {
+                continue;
+            };
+            let source = manifest_path.display().to_string();
+            let reference = match &anchor.public {
+                Some(public) => format!("{}:{}", public.chain, public.txid),
+                None => anchor.reference.clone(),
+            };
+            anchors.push(AnchorSummary {
+                source,
+                kind: anchor.kind.clone(),
+                reference,
+                event_index: locate(&anchor.audit_tip_hex),
+                audit_tip: anchor.audit_tip_hex,
+                created_at: anchor.created_at,
+            });
+        }
+
+        // Events per period
+        let mut periods: BTreeMap<String, PeriodSummary> = BTreeMap::new();
+        for event in options.query.apply(all_events) {
+            let period = options.period.label(&event.ts);
+            let summary = periods
+                .entry(period.clone())
+                .or_insert_with(|| PeriodSummary {
+                    period,
+                    events: 0,
+                    by_type: BTreeMap::new(),
+                    by_result: BTreeMap::new(),
+                    key_events: Vec::new(),
+                });
+            summary.events += 1;
+            *summary.by_type.entry(event.event.clone()).or_default() += 1;
+            if let Some(result) = &event.result {
+                *summary.by_result.entry(result.to_string()).or_default() += 1;
+            }
+            if is_key_event(&event) {
+                summary.key_events.push(KeyEvent {
+                    ts: event.ts,
+                    event: event.event,
+                    result: event.result,
+                    policy_id: event.policy_id,
+                    manifest_hash: event.manifest_hash,
+                    run_id: event.run_id,
+                });
+            }
+        }
+
+        Ok(AuditReport {
+            title: options
+                .title
+                .clone()
+                .unwrap_or_else(|| "Audit-Bericht".to_string()),
+            generated_at: Utc::now().to_rfc3339(),
+            log: log_path.display().to_string(),
+            format,
+            from: options.query.from.clone(),
+            to: options.query.to.clone(),
+            period: options.period,
+            chain: ChainStatus {
+                ok: verify.ok,
+                total_events: verify.total_events,
+                tamper_index: verify.tamper_index,
+                error: verify.error,
+            },
+            migration,
+            segments,
+            checkpoints,
+            checkpoint_list,
+            anchors,
+            periods: periods.into_values().collect(),
+        })
+    }
+
+    /// True if chain, migration bridge, segments and checkpoints verified
+    pub fn ok(&self) -> bool {
+        self.chain.ok
+            && self.migration.as_ref().map(|m| m.ok).unwrap_or(true)
+            && self.segments.as_ref().map(|s| s.ok).unwrap_or(true)
+            && self.checkpoints.ok
+    }
+
+    /// Report content as format-neutral blocks
+    fn blocks(&self) -> Vec<Block> {
+        let status = |ok: bool, error: &Option<String>| match (ok, error) {
+            (true, _) => "OK".to_string(),
+            (false, Some(error)) => format!("VERLETZT: {}", error),
+            (false, None) => "VERLETZT".to_string(),
+        };
+        let opt = |value: &Option<String>| value.clone().unwrap_or_else(|| "-".to_string());
+
+        let mut blocks = vec![
+            Block::Title(self.title.clone()),
+            Block::Fields(vec![
+                ("Audit-Log".to_string(), self.log.clone()),
+                ("Format".to_string(), self.format.to_string()),
+                (
+                    "Zeitraum".to_string(),
+                    format!("{} bis {}", opt(&self.from), opt(&self.to)),
+                ),
+                ("Erstellt".to_string(), self.generated_at.clone()),
+                (
+                    "Gesamtergebnis".to_string(),
+                    if self.ok() { "OK" } else { "VERLETZT" }.to_string(),
+                ),
+            ]),
+            Block::Heading("Integrität".to_string()),
+        ];
+
+        let mut integrity = vec![
+            (
+                "Hash-Chain".to_string(),
+                status(self.chain.ok, &self.chain.error),
+            ),
+            (
+                "Events (aktives Log)".to_string(),
+                self.chain.total_events.to_string(),
+            ),
+        ];
+        if let Some(migration) = &self.migration {
+            integrity.push((
+                "v1.0-Migrations-Bridge".to_string(),
+                format!(
+                    "{} ({} Events, Tail {})",
+                    status(migration.ok, &migration.error),
+                    migration.bridge.source_events,
+                    migration.bridge.source_tail_digest
+                ),
+            ));
+        }
+        if let Some(segments) = &self.segments {
+            let summary_only = segments.segments.iter().filter(|s| !s.archived).count();
+            integrity.push((
+                "Segmente".to_string(),
+                format!(
+                    "{} ({} Segmente, {} Events, davon {} nur Siegel)",
+                    status(segments.ok, &segments.error),
+                    segments.segments.len(),
+                    segments.sealed_events,
+                    summary_only
+                ),
+            ));
+        }
+        integrity.push((
+            "Checkpoints".to_string(),
+            if self.checkpoints.checkpoints == 0 {
+                "keine (Truncation nicht erkennbar)".to_string()
+            } else {
+                format!(
+                    "{} ({} Checkpoints, {} von {} Events abgedeckt)",
+                    status(self.checkpoints.ok, &self.checkpoints.error),
+                    self.checkpoints.checkpoints,
+                    self.checkpoints.covered_events,
+                    self.checkpoints.total_events
+                )
+            },
+        ));
+        blocks.push(Block::Fields(integrity));
+
+        if !self.checkpoint_list.is_empty() {
+            blocks.push(Block::Heading("Checkpoints".to_string()));
+            blocks.push(Block::Table {
+                header: vec!["Zeitpunkt", "Events", "KID", "Merkle-Root"],
+                rows: self
+                    .checkpoint_list
+                    .iter()
+                    .map(|cp| {
+                        vec![
+                            cp.timestamp.clone(),
+                            cp.tree_size.to_string(),
+                            cp.kid.clone(),
+                            opt(&cp.root_hash),
+                        ]
+                    })
+                    .collect(),
+            });
+        }
+
+        blocks.push(Block::Heading("Anker".to_string()));
+        if self.anchors.is_empty() {
+            blocks.push(Block::Text("Keine Zeitanker gefunden.".to_string()));
+        } else {
+            blocks.push(Block::Table {
+                header: vec![
+                    "Quelle",
+                    "Art",
+                    "Referenz",
+                    "Audit-Tip",
+                    "Erstellt",
+                    "Im Log",
+                ],
+                rows: self
+                    .anchors
+                    .iter()
+                    .map(|a| {
+                        vec![
+                            a.source.clone(),
+                            a.kind.clone(),
+                            a.reference.clone(),
+                            a.audit_tip.clone(),
+                            a.created_at.clone(),
+                            a.event_index
+                                .map(|i| format!("Event {}", i))
+                                .unwrap_or_else(|| "nicht gefunden".to_string()),
+                        ]
+                    })
+                    .collect(),
+            });
+        }
+
+        blocks.push(Block::Heading("Ereignisse je Zeitraum".to_string()));
+        if self.periods.is_empty() {
+            blocks.push(Block::Text("Keine Ereignisse im Zeitraum.".to_string()));
+        }
+        for period in &self.periods {
+            blocks.push(Block::Subheading(format!(
+                "{} ({} Events)",
+                period.period, period.events
+            )));
+            let count = |map: &BTreeMap<String, usize>| {
+                map.iter()
+                    .map(|(key, n)| format!("{} {}", key, n))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            };
+            let mut fields = vec![("Event-Typen".to_string(), count(&period.by_type))];
+            if !period.by_result.is_empty() {
+                fields.push(("Ergebnisse".to_string(), count(&period.by_result)));
+            }
+            blocks.push(Block::Fields(fields));
+            if !period.key_events.is_empty() {
+                blocks.push(Block::Table {
+                    header: vec![
+                        "Zeitpunkt",
+                        "Event",
+                        "Ergebnis",
+                        "Policy",
+                        "Manifest",
+                        "Run",
+                    ],
+                    rows: period
+                        .key_events
+                        .iter()
+                        .map(|e| {
+                            vec![
+                                e.ts.clone(),
+                                e.event.clone(),
+                                e.result.as_ref().map(|r| r.to_string()).unwrap_or_default(),
+                                e.policy_id.clone().unwrap_or_default(),
+                                e.manifest_hash.clone().unwrap_or_default(),
+                                e.run_id.clone().unwrap_or_default(),
+                            ]
+                        })
+                        .collect(),
+                });
+            }
+        }
+
+        blocks
+    }
+
+    /// Renders the report as Markdown
+    pub fn to_markdown(&self) -> String {
+        let cell = |s: &str| s.replace('|', "\\|").replace('\n', " ");
+        let mut out = String::new();
+        for block in self.blocks() {
+            match block {
+                Block::Title(text) => out.push_str(&format!("# {}\n\n", text)),
+                Block::Heading(text) => out.push_str(&format!("## {}\n\n", text)),
+                Block::Subheading(text) => out.push_str(&format!("### {}\n\n", text)),
+                Block::Text(text) => out.push_str(&format!("{}\n\n", text)),
+                Block::Fields(fields) => {
+                    for (label, value) in fields {
+                        out.push_str(&format!("- **{}:** {}\n", label, value));
+                    }
+                    out.push('\n');
+                }
+                Block::Table { header, rows } => {
+                    out.push_str(&format!("| {} |\n", header.join(" | ")));
+                    out.push_str(&format!("|{}\n", "---|".repeat(header.len())));
+                    for row in rows {
+                        let row: Vec<String> = row.iter().map(|c| cell(c)).collect();
+                        out.push_str(&format!("| {} |\n", row.join(" | ")));
+                    }
+                    out.push('\n');
+                }
+            }
+        }
+        out
+    }
+
+    /// Renders the report as a self-contained HTML page (printable)
+    pub fn to_html(&self) -> String {
+        let mut out = format!(
+            "<!DOCTYPE html>\n<html lang=\"de\">\n<head>\n<meta charset=\"utf-8\">\n\
+             <title>{}</title>\n<style>\n\
+             body {{ font-family: sans-serif; max-width: 60em; margin: 2em auto; }}\n\
+             table {{ border-collapse: collapse; margin-bottom: 1em; }}\n\
+             th, td {{ border: 1px solid #999; padding: 0.2em 0.5em; text-align: left; \
+             vertical-align: top; word-break: break-all; }}\n\
+             </style>\n</head>\n<body>\n",
+            escape_html(&self.title)
+        );
+        for block in self.blocks() {
+            match block {
+                Block::Title(text) => out.push_str(&format!("<h1>{}</h1>\n", escape_html(&text))),
+                Block::Heading(text) => out.push_str(&format!("<h2>{}</h2>\n", escape_html(&text))),
+                Block::Subheading(text) => {
+                    out.push_str(&format!("<h3>{}</h3>\n", escape_html(&text)))
+                }
+                Block::Text(text) => out.push_str(&format!("<p>{}</p>\n", escape_html(&text))),
+                Block::Fields(fields) => {
+                    out.push_str("<table>\n");
+                    for (label, value) in fields {
+                        out.push_str(&format!(
+                            "<tr><th>{}</th><td>{}</td></tr>\n",
+                            escape_html(&label),
+                            escape_html(&value)
+                        ));
+                    }
+                    out.push_str("</table>\n");
+                }
+                Block::Table { header, rows } => {
+                    out.push_str("<table>\n<tr>");
+                    for title in header {
+                        out.push_str(&format!("<th>{}</th>", escape_html(title)));
+                    }
+                    out.push_str("</tr>\n");
+                    for row in rows {
+                        out.push_str("<tr>");
+                        for cell in row {
+                            out.push_str(&format!("<td>{}</td>", escape_html(&cell)));
+                        }
+                        out.push_str("</tr>\n");
+                    }
+                    out.push_str("</table>\n");
+                }
+            }
+        }
+        out.push_str("</body>\n</html>\n");
+        out
+    }
+}
+
+/// Format-neutral report content
+enum Block {
+    Title(String),
+    Heading(String),
+    Subheading(String),
+    Text(String),
+    Fields(Vec<(String, String)>),
+    Table {
+        header: Vec<&'static str>,
+        rows: Vec<Vec<String>>,
+    },
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audit::AuditChain;
+    use serde_json::json;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_report_periods_anchors_and_rendering() {
+        let dir = tempdir().unwrap();
+        let log = dir.path().join("audit_chain.jsonl");
+
+        let mut chain = AuditChain::new(&log).unwrap();
+        chain
+            .append_details("policy_loaded", json!({"policy_id": "lksg.v1"}))
+            .unwrap();
+        let tip = chain
+            .append_details(
+                "verify_response",
+                json!({"policy_id": "lksg.v1", "result": "FAIL", "run_id": "<run>"}),
+            )
+            .unwrap()
+            .self_hash;
+        chain
+            .append_details(
+                "timestamp_generated",
+                json!({"audit_tip": tip.trim_start_matches("0x"), "tsa": "mock"}),
+            )
+            .unwrap();
+        chain
+            .append_details("verify_response", json!({"result": "OK"}))
+            .unwrap();
+
+        let options = ReportOptions {
+            title: Some("LkSG Audit 2026".to_string()),
+            period: ReportPeriod::Yearly,
+            ..Default::default()
+        };
+        let report = AuditReport::build(&log, &options, None).unwrap();
+        assert!(report.ok());
+        assert_eq!(report.chain.total_events, 4);
+        assert_eq!(report.anchors.len(), 1);
+        assert_eq!(report.anchors[0].event_index, Some(2));
+
+        assert_eq!(report.periods.len(), 1);
+        let period = &report.periods[0];
+        assert_eq!(period.events, 4);
+        assert_eq!(period.by_type["verify_response"], 2);
+        assert_eq!(period.by_result["FAIL"], 1);
+        // policy_loaded, the failed verification and the timestamp
+        assert_eq!(period.key_events.len(), 3);
+
+        let markdown = report.to_markdown();
+        assert!(markdown.starts_with("# LkSG Audit 2026"));
+        assert!(markdown.contains("- **Hash-Chain:** OK"));
+        assert!(markdown.contains("Event 2"));
+
+        let html = report.to_html();
+        assert!(html.contains("<h2>Anker</h2>"));
+        assert!(html.contains("&lt;run&gt;"));
+        assert!(!html.contains("<run>"));
+
+        // Event filter narrows the statistics, not the integrity checks
+        let options = ReportOptions {
+            query: AuditQuery {
+                result: Some(AuditEventResult::Fail),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let report = AuditReport::build(&log, &options, None).unwrap();
+        assert_eq!(report.periods[0].events, 1);
+        assert_eq!(report.chain.total_events, 4);
+    }
+
+    #[test]
+    fn test_report_period_labels() {
+        let ts = "2025-04-01T01:00:00+02:00";
+        assert_eq!(ReportPeriod::Monthly.label(ts), "2025-03");
+        assert_eq!(ReportPeriod::Quarterly.label(ts), "2025-Q1");
+        assert_eq!(ReportPeriod::Yearly.label(ts), "2025");
+        assert!(ReportPeriod::parse("weekly").is_err());
+    }
+}
@@ -0,0 +1,760 @@
+//! Segmented Audit Logs (Rotation, Archiving, Cross-Segment Verification)
+//!
+//! Multi-year audit chains are split into segments so that opening the chain
+//! and verifying the active log only touch the current segment. The active
+//! segment stays at the configured log path. Sealing it
+//! - archives the segment gzip-compressed to `<log>.segments/NNNNNN.jsonl.gz`,
+//! - writes a seal (`audit.segment_seal.v1`, `NNNNNN.seal.json`) with event
+//!   count, time range, tail hash, Merkle root, content hash and the
+//!   `prev_segment_tail` link, optionally signed through a `KeyProvider`,
+//! - moves the segment's checkpoints next to the archive and empties the log.
+//!
+//! The hash chain continues across segments: the first event of a segment has
+//! the previous segment's tail as `prev_hash`. `verify_segments` walks the
+//! chain of seals; archived segments are fully re-verified, segments whose
+//! archive was removed (retention) are accepted on their seal alone.
+
+use anyhow::{anyhow, bail, Result};
+use base64::{engine::general_purpose, Engine};
+use chrono::{DateTime, Utc};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+
+use super::accumulator::MerkleAccumulator;
+use super::checkpoint::{
+    check_trusted_signer, checkpoint_path, load_checkpoints, verify_checkpoints, verify_ed25519,
+    LogFormat, SIGNATURE_SCHEME,
+};
+use super::hash_chain::{verify_chain_from, AuditChain, AuditEvent};
+use crate::providers::KeyProvider;
+
+/// Seal format version
+pub const SEAL_VERSION: &str = "audit.segment_seal.v1";
+
+/// Calendar period after which a segment is rolled over
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RotationPeriod {
+    Daily,
+    Monthly,
+    Yearly,
+}
+
+impl RotationPeriod {
+    /// Parses `daily`, `monthly` or `yearly`
+    pub fn parse(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "daily" => Ok(Self::Daily),
+            "monthly" => Ok(Self::Monthly),
+            "yearly" => Ok(Self::Yearly),
+            _ => bail!(
+                "Invalid rotation period: {}. Valid options: daily, monthly, yearly",
+                s
+            ),
+        }
+    }
+
+    /// Period a timestamp belongs to (e.g. `2025-11` for monthly)
+    fn key(&self, ts: &DateTime<Utc>) -> String {
+        match self {
+            Self::Daily => ts.format("%Y-%m-%d").to_string(),
+            Self::Monthly => ts.format("%Y-%m").to_string(),
+            Self::Yearly => ts.format("%Y").to_string(),
+        }
+    }
+}
+
+/// When the active segment is rolled over
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RotationPolicy {
+    /// Roll over once the segment reaches this size
+    pub max_bytes: Option<u64>,
+    /// Roll over once the period of the segment's first event has ended
+    pub period: Option<RotationPeriod>,
+}
+
+impl RotationPolicy {
+    /// Returns why the active segment is due for rotation (None if not due)
+    pub fn due<P: AsRef<Path>>(&self, log_path: P) -> Result<Option<String>> {
+        let path = log_path.as_ref();
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let size = fs::metadata(path)?.len();
+        if size == 0 {
+            return Ok(None);
+        }
+        if let Some(max_bytes) = self.max_bytes {
+            if size >= max_bytes {
+                return Ok(Some(format!("size {} bytes >= {}", size, max_bytes)));
+            }
+        }
+
+        if let Some(period) = self.period {
+            if let Some(first) = first_event(path)? {
+                let first_ts = DateTime::parse_from_rfc3339(&first.ts)?.with_timezone(&Utc);
+                let first_key = period.key(&first_ts);
+                if first_key != period.key(&Utc::now()) {
+                    return Ok(Some(format!("period {} ended", first_key)));
+                }
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+/// Sealed summary of an archived segment
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SegmentSeal {
+    pub version: String,
+    /// 1-based segment number
+    pub segment: u64,
+    /// Archive file name in the segments directory
+    pub archive: String,
+    /// Tail hash of the previous segment (genesis hash for segment 1)
+    pub prev_segment_tail: String,
+    /// `self_hash` of the last event in the segment
+    pub tail_hash: String,
+    pub event_count: u64,
+    pub first_ts: String,
+    pub last_ts: String,
+    /// Merkle root over the segment's event hashes (see `accumulator`)
+    pub root_hash: String,
+    /// BLAKE3 over the uncompressed segment (0x-prefixed)
+    pub content_hash: String,
+    /// RFC3339 sealing time
+    pub sealed_at: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kid: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provider: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub public_key: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature_scheme: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+}
+
+impl SegmentSeal {
+    /// BLAKE3 hash over all fields except the signature
+    pub fn core_hash(&self) -> Result<[u8; 32]> {
+        let mut core = self.clone();
+        core.signature = None;
+        Ok(*blake3::hash(&serde_json::to_vec(&core)?).as_bytes())
+    }
+
+    /// Signs the seal through a key provider
+    ///
+    /// # Arguments
+    /// * `provider` - Key provider used for signing
+    /// * `kid` - Signing key (default: the provider's current key)
+    pub fn sign(&mut self, provider: &dyn KeyProvider, kid: Option<&str>) -> Result<()> {
+        let kid = match kid {
+            Some(kid) => kid.to_string(),
+            None => provider.current_kid()?,
+        };
+        self.public_key = Some(general_purpose::STANDARD.encode(provider.public_key(&kid)?));
+        self.provider = Some(provider.provider_id().to_string());
+        self.signature_scheme = Some(SIGNATURE_SCHEME.to_string());
+        self.kid = Some(kid);
+
+        let signature = provider.sign(self.kid.as_deref(), &self.core_hash()?)?;
+        self.signature = Some(general_purpose::STANDARD.encode(signature));
+        Ok(())
+    }
+
+    /// Verifies the signature (and optionally the signer)
+    ///
+    /// Unsigned seals are accepted unless `trusted` is given.
+    pub fn verify_signature(&self, trusted: Option<&dyn KeyProvider>) -> Result<()> {
+        let (signature, public_key) = match (&self.signature, &self.public_key) {
+            (Some(signature), Some(public_key)) => (signature, public_key),
+            _ if trusted.is_some() => bail!("seal is not signed"),
+            _ => return Ok(()),
+        };
+        if self.signature_scheme.as_deref() != Some(SIGNATURE_SCHEME) {
+            bail!(
+                "unsupported signature scheme {}",
+                self.signature_scheme.as_deref().unwrap_or("none")
+            );
+        }
+        verify_ed25519(public_key, signature, &self.core_hash()?)
+            .map_err(|e| anyhow!("invalid seal signature: {}", e))?;
+
+        if let Some(provider) = trusted {
+            let kid = self
+                .kid
+                .as_deref()
+                .ok_or_else(|| anyhow!("seal has no kid"))?;
+            check_trusted_signer(provider, kid, public_key)?;
+        }
+        Ok(())
+    }
+}
+
+/// Directory of sealed segments next to an audit log
+///
+/// `build/audit_chain.jsonl` → `build/audit_chain.segments/`
+pub fn segments_dir<P: AsRef<Path>>(log_path: P) -> PathBuf {
+    log_path.as_ref().with_extension("segments")
+}
+
+fn seal_file(segment: u64) -> String {
+    format!("{:06}.seal.json", segment)
+}
+
+fn archive_file(segment: u64) -> String {
+    format!("{:06}.jsonl.gz", segment)
+}
+
+fn checkpoints_file(segment: u64) -> String {
+    format!("{:06}.checkpoints.jsonl", segment)
+}
+
+/// Segment numbers with a seal file, ascending
+fn sealed_segments(dir: &Path) -> Result<Vec<u64>> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut segments = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let name = entry?.file_name();
+        if let Some(number) = name
+            .to_str()
+            .and_then(|name| name.strip_suffix(".seal.json"))
+            .and_then(|number| number.parse().ok())
+        {
+            segments.push(number);
+        }
+    }
+    segments.sort_unstable();
+    Ok(segments)
+}
+
+fn load_seal(dir: &Path, segment: u64) -> Result<SegmentSeal> {
+    let path = dir.join(seal_file(segment));
+    let seal: SegmentSeal = serde_json::from_str(&fs::read_to_string(&path)?)
+        .map_err(|e| anyhow!("{}: {}", path.display(), e))?;
+    if seal.version != SEAL_VERSION {
+        bail!("Unsupported seal version {}", seal.version);
+    }
+    Ok(seal)
+}
+
+/// Loads all seals of a log, ordered by segment number
+#[allow(dead_code)] // Public API - seal listing for tooling
+pub fn load_seals<P: AsRef<Path>>(log_path: P) -> Result<Vec<SegmentSeal>> {
+    let dir = segments_dir(log_path);
+    sealed_segments(&dir)?
+        .into_iter()
+        .map(|segment| load_seal(&dir, segment))
+        .collect()
+}
+
+/// Loads the seal of the most recent segment (reads a single file)
+pub fn last_seal<P: AsRef<Path>>(log_path: P) -> Result<Option<SegmentSeal>> {
+    let dir = segments_dir(log_path);
+    match sealed_segments(&dir)?.last() {
+        Some(segment) => Ok(Some(load_seal(&dir, *segment)?)),
+        None => Ok(None),
+    }
+}
+
+/// Expected `prev_hash` of the first event in the active segment
+pub fn chain_start<P: AsRef<Path>>(log_path: P) -> Result<String> {
+    Ok(last_seal(log_path)?
+        .map(|seal| seal.tail_hash)
+        .unwrap_or_else(|| AuditChain::GENESIS_HASH.to_string()))
+}
+
+fn first_event(path: &Path) -> Result<Option<AuditEvent>> {
+    for line in BufReader::new(fs::File::open(path)?).lines() {
+        let line = line?;
+        if !line.trim().is_empty() {
+            return Ok(Some(serde_json::from_str(&line)?));
+        }
+    }
+    Ok(None)
+}
+
+pub(crate) fn parse_events(content: &[u8]) -> Result<Vec<AuditEvent>> {
+    let mut events = Vec::new();
+    for line in content.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let event = serde_json::from_str(&line).map_err(|e| {
+            anyhow!(
+                "Event {} is not a v2 audit chain event ({}); v1 logs cannot be segmented",
+                events.len(),
+                e
+            )
+        })?;
+        events.push(event);
+    }
+    Ok(events)
+}
+
+/// Writes a file via a temporary file and rename
+fn write_atomic(path: &Path, data: &[u8]) -> Result<()> {
+    let tmp = path.with_extension("tmp");
+    fs::write(&tmp, data)?;
+    fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+/// Seals the active segment and starts a new, empty one
+///
+/// The segment's hash chain is verified before sealing; a broken chain is
+/// never sealed.
+///
+/// # Arguments
+/// * `log_path` - Active segment (v2 audit chain)
+/// * `signer` - Optional key provider (and kid) that signs the seal
+///
+/// # Returns
+/// The seal, or None if the active segment has no events
+pub fn seal_segment<P: AsRef<Path>>(
+    log_path: P,
+    signer: Option<(&dyn KeyProvider, Option<&str>)>,
+) -> Result<Option<SegmentSeal>> {
+    let path = log_path.as_ref();
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = fs::read(path)?;
+    let events = parse_events(&content)?;
+    let (first, last) = match (events.first(), events.last()) {
+        (Some(first), Some(last)) => (first, last),
+        _ => return Ok(None),
+    };
+
+    let previous = last_seal(path)?;
+    let prev_segment_tail = previous
+        .as_ref()
+        .map(|seal| seal.tail_hash.clone())
+        .unwrap_or_else(|| AuditChain::GENESIS_HASH.to_string());
+    let report = verify_chain_from(&content[..], &prev_segment_tail)?;
+    if !report.ok {
+        bail!(
+            "Refusing to seal a broken chain: {}",
+            report.error.unwrap_or_default()
+        );
+    }
+
+    let segment = previous.map(|seal| seal.segment + 1).unwrap_or(1);
+    let mut seal = SegmentSeal {
+        version: SEAL_VERSION.to_string(),
+        segment,
+        archive: archive_file(segment),
+        prev_segment_tail,
+        tail_hash: last.self_hash.clone(),
+        event_count: events.len() as u64,
+        first_ts: first.ts.clone(),
+        last_ts: last.ts.clone(),
+        root_hash: MerkleAccumulator::from_event_hashes(events.iter().map(|e| &e.self_hash))
+            .root_hex(),
+        content_hash: format!("0x{}", blake3::hash(&content).to_hex()),
+        sealed_at: Utc::now().to_rfc3339(),
+        kid: None,
+        provider: None,
+        public_key: None,
+        signature_scheme: None,
+        signature: None,
+    };
+    if let Some((provider, kid)) = signer {
+        seal.sign(provider, kid)?;
+    }
+
+    let dir = segments_dir(path);
+    fs::create_dir_all(&dir)?;
+
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::best());
+    encoder.write_all(&content)?;
+    write_atomic(&dir.join(&seal.archive), &encoder.finish()?)?;
+
+    let checkpoints = checkpoint_path(path);
+    if checkpoints.exists() {
+        fs::rename(&checkpoints, dir.join(checkpoints_file(segment)))?;
+    }
+
+    write_atomic(
+        &dir.join(seal_file(segment)),
+        serde_json::to_string_pretty(&seal)?.as_bytes(),
+    )?;
+    fs::write(path, b"")?;
+
+    Ok(Some(seal))
+}
+
+/// Reads and decompresses an archived segment
+pub fn read_archive<P: AsRef<Path>>(log_path: P, seal: &SegmentSeal) -> Result<Option<Vec<u8>>> {
+    let path = segments_dir(log_path).join(&seal.archive);
+    if !path.exists() {
+        return Ok(None);
+    }
+    let mut content = Vec::new();
+    GzDecoder::new(fs::File::open(path)?).read_to_end(&mut content)?;
+    Ok(Some(content))
+}
+
+/// Verification state of one sealed segment
+#[derive(Debug, Clone, Serialize)]
+pub struct SegmentStatus {
+    pub segment: u64,
+    pub events: u64,
+    /// Archive present and re-verified (false: sealed summary only)
+    pub archived: bool,
+    pub signed: bool,
+}
+
+/// Result of verifying the chain of sealed segments
+#[derive(Debug, Clone, Serialize)]
+pub struct SegmentReport {
+    /// Segments verified so far
+    pub segments: Vec<SegmentStatus>,
+
+    /// Events in the verified segments
+    pub sealed_events: u64,
+
+    /// Verification result
+    pub ok: bool,
+
+    /// Number of the first segment that failed (if any)
+    pub failed_segment: Option<u64>,
+
+    /// Error message (if any)
+    pub error: Option<String>,
+}
+
+/// Verifies the chain of sealed segments of a log
+///
+/// Checks numbering and `prev_segment_tail` links and the seal signatures
+/// (all seals must be signed by a key of `trusted`, if given). Archived
+/// segments are decompressed and checked against their seal (content hash,
+/// hash chain, count, time range, tail, Merkle root, archived checkpoints).
+/// If seals exist, the active segment must be a v2 chain whose first event
+/// links to the last seal's tail (an empty active segment is accepted); the
+/// rest of its chain is verified by `verify_chain`.
+pub fn verify_segments<P: AsRef<Path>>(
+    log_path: P,
+    trusted: Option<&dyn KeyProvider>,
+) -> Result<SegmentReport> {
+    let dir = segments_dir(&log_path);
+    let mut report = SegmentReport {
+        segments: Vec::new(),
+        sealed_events: 0,
+        ok: true,
+        failed_segment: None,
+        error: None,
+    };
+
+    let mut prev_tail = AuditChain::GENESIS_HASH.to_string();
+    for (index, segment) in sealed_segments(&dir)?.into_iter().enumerate() {
+        let checked = load_seal(&dir, segment).and_then(|seal| {
+            check_segment(&log_path, &seal, index as u64 + 1, &prev_tail, trusted)
+                .map(|archived| (seal, archived))
+        });
+        match checked {
+            Ok((seal, archived)) => {
+                report.sealed_events += seal.event_count;
+                report.segments.push(SegmentStatus {
+                    segment,
+                    events: seal.event_count,
+                    archived,
+                    signed: seal.signature.is_some(),
+                });
+                prev_tail = seal.tail_hash;
+            }
+            Err(e) => {
+                report.ok = false;
+                report.failed_segment = Some(segment);
+                report.error = Some(format!("Segment {}: {}", segment, e));
+                break;
+            }
+        }
+    }
+
+    if report.ok && !report.segments.is_empty() {
+        if let Err(e) = check_active_link(log_path.as_ref(), &prev_tail) {
+            report.ok = false;
+            report.failed_segment = Some(report.segments.len() as u64 + 1);
+            report.error = Some(format!("Active segment: {}", e));
+        }
+    }
+
+    Ok(report)
+}
+
+/// Checks that the active segment continues the chain at `prev_tail`
+fn check_active_link(log_path: &Path, prev_tail: &str) -> Result<()> {
+    if !log_path.exists() {
+        return Ok(());
+    }
+    let first = BufReader::new(fs::File::open(log_path)?)
+        .lines()
+        .find(|line| !matches!(line, Ok(l) if l.trim().is_empty()))
+        .transpose()?;
+    let Some(first) = first else {
+        return Ok(());
+    };
+
+    let value: serde_json::Value = serde_json::from_str(&first)?;
+    if value.get("self_hash").is_none() {
+        bail!("not in the v2 chain format, cannot continue the sealed segments");
+    }
+    let event: AuditEvent = serde_json::from_value(value)?;
+    if event.prev_hash != prev_tail {
+        bail!(
+            "first prev_hash {} does not link to the last seal's tail {}",
+            event.prev_hash,
+            prev_tail
+        );
+    }
+    Ok(())
+}
+
+/// Checks one seal; returns whether the archive was available
+fn check_segment<P: AsRef<Path>>(
+    log_path: P,
+    seal: &SegmentSeal,
+    expected_segment: u64,
+    prev_tail: &str,
+    trusted: Option<&dyn KeyProvider>,
+) -> Result<bool> {
+    if seal.segment != expected_segment {
+        bail!("segment {} is missing", expected_segment);
+    }
+    if seal.prev_segment_tail != prev_tail {
+        bail!(
+            "prev_segment_tail {} does not link to the previous tail {}",
+            seal.prev_segment_tail,
+            prev_tail
+        );
+    }
+    seal.verify_signature(trusted)?;
+
+    let content = match read_archive(&log_path, seal)? {
+        Some(content) => content,
+        None => return Ok(false),
+    };
+    if format!("0x{}", blake3::hash(&content).to_hex()) != seal.content_hash {
+        bail!("archive does not match the sealed content hash");
+    }
+
+    let report = verify_chain_from(&content[..], prev_tail)?;
+    if !report.ok {
+        bail!(
+            "archived chain broken: {}",
+            report.error.unwrap_or_default()
+        );
+    }
+    let events = parse_events(&content)?;
+    let hashes: Vec<String> = events.iter().map(|e| e.self_hash.clone()).collect();
+    let summary_matches = events.len() as u64 == seal.event_count
+        && hashes.last() == Some(&seal.tail_hash)
+        && events.first().map(|e| &e.ts) == Some(&seal.first_ts)
+        && events.last().map(|e| &e.ts) == Some(&seal.last_ts)
+        && MerkleAccumulator::from_event_hashes(&hashes).root_hex() == seal.root_hash;
+    if !summary_matches {
+        bail!("archive does not match the seal summary");
+    }
+
+    let checkpoints = segments_dir(&log_path).join(checkpoints_file(seal.segment));
+    if checkpoints.exists() {
+        let report = verify_checkpoints(
+            LogFormat::V2,
+            &hashes,
+            &load_checkpoints(checkpoints)?,
+            trusted,
+        );
+        if !report.ok {
+            bail!("archived checkpoints: {}", report.error.unwrap_or_default());
+        }
+    }
+
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audit::verify_chain;
+    use crate::providers::SoftwareProvider;
+    use ed25519_dalek::SigningKey;
+    use tempfile::tempdir;
+
+    fn software_provider(keys_dir: &Path) -> SoftwareProvider {
+        let signing_key = SigningKey::generate(&mut rand::rngs::OsRng);
+        crate::keys::KeyMetadata::new(
+            &signing_key.verifying_key().to_bytes(),
+            "Audit",
+            "ed25519",
+            365,
+        )
+        .unwrap()
+        .save(keys_dir.join("audit.json"))
+        .unwrap();
+        fs::write(keys_dir.join("audit.ed25519"), signing_key.to_bytes()).unwrap();
+        SoftwareProvider::new(keys_dir, Some("audit".to_string())).unwrap()
+    }
+
+    fn append(chain: &mut AuditChain, count: usize) {
+        for i in 0..count {
+            chain
+                .append(format!("event_{}", i), None, None, None, None, None)
+                .unwrap();
+        }
+    }
+
+    #[test]
+    fn test_rotation_continues_chain_across_segments() {
+        let dir = tempdir().unwrap();
+        let keys = dir.path().join("keys");
+        fs::create_dir_all(&keys).unwrap();
+        let provider = software_provider(&keys);
+        let log = dir.path().join("audit_chain.jsonl");
+
+        let mut chain = AuditChain::new(&log).unwrap();
+        append(&mut chain, 3);
+        let policy = RotationPolicy {
+            max_bytes: Some(1),
+            period: None,
+        };
+        let seal = chain
+            .rotate_if_due(&policy, Some((&provider, None)))
+            .unwrap()
+            .unwrap();
+        assert_eq!(seal.segment, 1);
+        assert_eq!(seal.event_count, 3);
+        assert_eq!(seal.prev_segment_tail, AuditChain::GENESIS_HASH);
+        assert_eq!(fs::read_to_string(&log).unwrap(), "");
+        assert!(segments_dir(&log).join("000001.jsonl.gz").exists());
+
+        // Nothing to rotate in an empty segment
+        assert!(chain.rotate_if_due(&policy, None).unwrap().is_none());
+
+        // Reopening continues from the sealed tail
+        let mut chain = AuditChain::new(&log).unwrap();
+        assert_eq!(chain.tail_hash(), seal.tail_hash);
+        append(&mut chain, 2);
+        let seal2 = seal_segment(&log, None).unwrap().unwrap();
+        assert_eq!(seal2.prev_segment_tail, seal.tail_hash);
+        append(&mut chain, 1);
+
+        assert!(verify_chain(&log).unwrap().ok);
+        let report = verify_segments(&log, None).unwrap();
+        assert!(report.ok, "{:?}", report.error);
+        assert_eq!(report.sealed_events, 5);
+        assert!(report.segments.iter().all(|s| s.archived));
+
+        // Trusted verification requires every seal to be signed
+        let report = verify_segments(&log, Some(&provider)).unwrap();
+        assert!(!report.ok);
+        assert_eq!(report.failed_segment, Some(2));
+    }
+
+    #[test]
+    fn test_verify_with_summary_only_and_tampered_archive() {
+        let dir = tempdir().unwrap();
+        let log = dir.path().join("audit_chain.jsonl");
+
+        let mut chain = AuditChain::new(&log).unwrap();
+        for _ in 0..3 {
+            append(&mut chain, 2);
+            seal_segment(&log, None).unwrap().unwrap();
+        }
+
+        // Old archive removed by retention: seal alone still links the chain
+        let segments = segments_dir(&log);
+        fs::remove_file(segments.join("000001.jsonl.gz")).unwrap();
+        let report = verify_segments(&log, None).unwrap();
+        assert!(report.ok, "{:?}", report.error);
+        assert!(!report.segments[0].archived);
+        assert!(report.segments[1].archived);
+
+        // Archive replaced with different content
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(b"{}\n").unwrap();
+        fs::write(segments.join("000002.jsonl.gz"), encoder.finish().unwrap()).unwrap();
+        let report = verify_segments(&log, None).unwrap();
+        assert!(!report.ok);
+        assert_eq!(report.failed_segment, Some(2));
+
+        // Missing seal in the middle breaks the segment chain
+        fs::remove_file(segments.join("000002.seal.json")).unwrap();
+        let report = verify_segments(&log, None).unwrap();
+        assert_eq!(report.failed_segment, Some(3));
+        assert!(report.error.unwrap().contains("missing"));
+    }
+
+    #[test]
+    fn test_active_segment_must_link_to_last_seal() {
+        let dir = tempdir().unwrap();
+        let log = dir.path().join("audit_chain.jsonl");
+
+        let mut chain = AuditChain::new(&log).unwrap();
+        append(&mut chain, 2);
+        let seal = seal_segment(&log, None).unwrap().unwrap();
+
+        // Empty active segment right after rotation
+        assert!(verify_segments(&log, None).unwrap().ok);
+
+        // Active segment restarted from genesis instead of the sealed tail
+        let other = dir.path().join("other.jsonl");
+        append(&mut AuditChain::new(&other).unwrap(), 1);
+        fs::copy(&other, &log).unwrap();
+        let report = verify_segments(&log, None).unwrap();
+        assert!(!report.ok);
+        assert_eq!(report.failed_segment, Some(2));
+        assert!(report.error.unwrap().contains(&seal.tail_hash));
+
+        // Active segment replaced by a v1.0 log
+        fs::write(
+            &log,
+            "{\"seq\":1,\"ts\":\"2025-01-01T00:00:00Z\",\"event\":\"x\",\"details\":{},\"prev_digest\":\"0x00\",\"digest\":\"0x01\"}\n",
+        )
+        .unwrap();
+        let report = verify_segments(&log, None).unwrap();
+        assert!(!report.ok);
+        assert!(report.error.unwrap().contains("v2"));
+
+        // Correctly continued chain
+        fs::write(&log, "").unwrap();
+        append(&mut AuditChain::new(&log).unwrap(), 1);
+        assert!(verify_segments(&log, None).unwrap().ok);
+    }
+
+    #[test]
+    fn test_rotation_policy_period() {
+        let dir = tempdir().unwrap();
+        let log = dir.path().join("audit_chain.jsonl");
+        let policy = RotationPolicy {
+            max_bytes: None,
+            period: Some(RotationPeriod::parse("monthly").unwrap()),
+        };
+        assert!(policy.due(&log).unwrap().is_none());
+
+        let mut chain = AuditChain::new(&log).unwrap();
+        append(&mut chain, 1);
+        assert!(policy.due(&log).unwrap().is_none());
+
+        let content = fs::read_to_string(&log).unwrap();
+        let event: AuditEvent = serde_json::from_str(content.trim()).unwrap();
+        let old = content.replace(&event.ts, "2020-01-15T10:00:00Z");
+        fs::write(&log, old).unwrap();
+        assert_eq!(
+            policy.due(&log).unwrap().as_deref(),
+            Some("period 2020-01 ended")
+        );
+        assert!(RotationPeriod::parse("weekly").is_err());
+    }
+}
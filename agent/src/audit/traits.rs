@@ -77,38 +77,8 @@ struct AuditChainAdapter(super::hash_chain::AuditChain);
 
 impl AuditStore for AuditChainAdapter {
     fn append_event(&mut self, event: &str, details: Value) -> Result<String> {
-        // Extract optional fields from details for structured logging
-        let policy_id = details
-            .get("policy_id")
-            .and_then(|v| v.as_str())
-            .map(String::from);
-        let ir_hash = details
-            .get("ir_hash")
-            .and_then(|v| v.as_str())
-            .map(String::from);
-        let manifest_hash = details
-            .get("manifest_hash")
-            .and_then(|v| v.as_str())
-            .map(String::from);
-        let run_id = details
-            .get("run_id")
-            .and_then(|v| v.as_str())
-            .map(String::from);
-
-        // Parse result if present
-        let result = details.get("result").and_then(|v| {
-            v.as_str().and_then(|s| match s.to_uppercase().as_str() {
-                "OK" => Some(super::hash_chain::AuditEventResult::Ok),
-                "WARN" => Some(super::hash_chain::AuditEventResult::Warn),
-                "FAIL" => Some(super::hash_chain::AuditEventResult::Fail),
-                _ => None,
-            })
-        });
-
-        let audit_event =
-            self.0
-                .append(event.to_string(), policy_id, ir_hash, manifest_hash, result, run_id)?;
-
+        // Structured fields are extracted from details, details are kept
+        let audit_event = self.0.append_details(event, details)?;
         Ok(audit_event.self_hash)
     }
 
@@ -6,6 +6,13 @@ use std::fs::{File, OpenOptions};
 use std::io::{BufRead, BufReader, Write};
 use std::path::Path;
 
+use super::checkpoint::{read_event_hashes, LogFormat};
+use super::hash_chain::{AuditChain, VerifyReport};
+use super::segment::{last_seal, segments_dir};
+
+/// Genesis-Digest (prev_digest des ersten Eintrags)
+const GENESIS_DIGEST: &str = "0x0000000000000000000000000000000000000000000000000000000000000000";
+
 /// Audit-Log-Eintrag mit Hash-Chain
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct AuditEntry {
@@ -17,11 +24,29 @@ pub struct AuditEntry {
     pub digest: String,
 }
 
+impl AuditEntry {
+    /// Prüft, ob `digest` zu den Feldern des Eintrags passt
+    pub fn verify_digest(&self) -> bool {
+        AuditLog::compute_digest(
+            self.seq,
+            &self.ts,
+            &self.event,
+            &self.details,
+            &self.prev_digest,
+        ) == self.digest
+    }
+}
+
 /// Audit-Log-Manager für kryptografische Event-Logs
+///
+/// Wurde die Datei bereits ins strukturierte Format migriert (`audit migrate`)
+/// oder segmentiert (`audit rotate`), werden neue Events über die `AuditChain`
+/// angehängt – auch wenn das aktive Segment nach einer Rotation leer ist.
 pub struct AuditLog {
     path: String,
     last_digest: String,
     seq: u64,
+    chain: Option<AuditChain>,
 }
 
 impl AuditLog {
@@ -35,20 +60,37 @@ impl AuditLog {
     pub fn new<P: AsRef<Path>>(path: P) -> Result<Self, Box<dyn Error>> {
         let path_str = path.as_ref().to_str().unwrap().to_string();
 
+        // Migrierte und segmentierte Logs werden im strukturierten Format
+        // fortgeschrieben; ein leeres aktives Segment setzt an `chain_start` an
+        let has_v2_events = if path.as_ref().exists() {
+            let (format, hashes) = read_event_hashes(&path)?;
+            format == LogFormat::V2 && !hashes.is_empty()
+        } else {
+            false
+        };
+        let is_segmented = segments_dir(&path).exists() || last_seal(&path)?.is_some();
+        if has_v2_events || is_segmented {
+            let chain = AuditChain::new(&path)?;
+            return Ok(AuditLog {
+                path: path_str,
+                last_digest: chain.tail_hash().to_string(),
+                seq: chain.len(),
+                chain: Some(chain),
+            });
+        }
+
         // Lese letzten Eintrag falls Datei existiert
         let (last_digest, seq) = if path.as_ref().exists() {
             Self::read_last_entry(&path_str)?
         } else {
-            (
-                "0x0000000000000000000000000000000000000000000000000000000000000000".to_string(),
-                0,
-            )
+            (GENESIS_DIGEST.to_string(), 0)
         };
 
         Ok(AuditLog {
             path: path_str,
             last_digest,
             seq,
+            chain: None,
         })
     }
 
@@ -74,10 +116,7 @@ impl AuditLog {
 
         match last_entry {
             Some(entry) => Ok((entry.digest, entry.seq)),
-            None => Ok((
-                "0x0000000000000000000000000000000000000000000000000000000000000000".to_string(),
-                0,
-            )),
+            None => Ok((GENESIS_DIGEST.to_string(), 0)),
         }
     }
 
@@ -89,7 +128,7 @@ impl AuditLog {
     ///
     /// # Rückgabe
     /// Hex-String des SHA3-256 Hashes
-    fn compute_digest(
+    pub(crate) fn compute_digest(
         seq: u64,
         ts: &str,
         event: &str,
@@ -120,6 +159,13 @@ impl AuditLog {
         event: &str,
         details: serde_json::Value,
     ) -> Result<(), Box<dyn Error>> {
+        if let Some(chain) = &mut self.chain {
+            let audit_event = chain.append_details(event, details)?;
+            self.seq = chain.len();
+            self.last_digest = audit_event.self_hash;
+            return Ok(());
+        }
+
         self.seq += 1;
         let ts = Utc::now().to_rfc3339();
         let prev_digest = self.last_digest.clone();
@@ -195,6 +241,53 @@ impl AuditLog {
             Ok(format!("0x{}", hex))
         }
     }
+
+    /// Verifiziert die Hash-Chain einer Audit-Datei (seq, prev_digest, digest)
+    ///
+    /// # Argumente
+    /// * `path` - Pfad zur JSONL-Audit-Datei
+    ///
+    /// # Rückgabe
+    /// VerifyReport (Index des ersten manipulierten Eintrags bei Fehler)
+    pub fn verify_file<P: AsRef<Path>>(path: P) -> Result<VerifyReport, Box<dyn Error>> {
+        let reader = BufReader::new(File::open(path)?);
+        let mut prev_digest = GENESIS_DIGEST.to_string();
+        let mut index = 0;
+
+        for line in reader.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let entry: AuditEntry = serde_json::from_str(&line)?;
+            let error = if entry.seq != index as u64 + 1 {
+                Some(format!(
+                    "Sequence broken: expected seq {}, got {}",
+                    index + 1,
+                    entry.seq
+                ))
+            } else if entry.prev_digest != prev_digest {
+                Some(format!(
+                    "Hash chain broken: expected prev_digest {}, got {}",
+                    prev_digest, entry.prev_digest
+                ))
+            } else if !entry.verify_digest() {
+                Some(format!("Digest mismatch at entry {}", index))
+            } else {
+                None
+            };
+
+            if let Some(error) = error {
+                return Ok(VerifyReport::fail(index + 1, index, error));
+            }
+
+            prev_digest = entry.digest;
+            index += 1;
+        }
+
+        Ok(VerifyReport::ok(index))
+    }
 }
 
 #[cfg(test)]
@@ -249,6 +342,76 @@ mod tests {
         assert_ne!(digest1, digest2);
     }
 
+    #[test]
+    fn verify_file_detects_tampering() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("agent.audit.jsonl");
+
+        let mut audit = AuditLog::new(&path).unwrap();
+        audit.log_event("event1", json!({"a": 1})).unwrap();
+        audit.log_event("event2", json!({"b": 2})).unwrap();
+
+        let report = AuditLog::verify_file(&path).unwrap();
+        assert!(report.ok);
+        assert_eq!(report.total_events, 2);
+
+        let content = fs::read_to_string(&path).unwrap();
+        fs::write(&path, content.replace("\"a\":1", "\"a\":9")).unwrap();
+
+        let report = AuditLog::verify_file(&path).unwrap();
+        assert!(!report.ok);
+        assert_eq!(report.tamper_index, Some(0));
+    }
+
+    #[test]
+    fn migrated_log_is_continued_in_chain_format() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("agent.audit.jsonl");
+
+        let mut audit = AuditLog::new(&path).unwrap();
+        audit.log_event("event1", json!({"a": 1})).unwrap();
+        crate::audit::migrate_v1_in_place(&path).unwrap();
+
+        let mut audit = AuditLog::new(&path).unwrap();
+        assert_eq!(audit.current_seq(), 2);
+        audit.log_event("event2", json!({"b": 2})).unwrap();
+        assert_eq!(audit.current_seq(), 3);
+
+        assert!(crate::audit::verify_chain(&path).unwrap().ok);
+        assert!(crate::audit::verify_migration(&path).unwrap().unwrap().ok);
+        assert_eq!(AuditLog::new(&path).unwrap().get_tip(), audit.get_tip());
+    }
+
+    #[test]
+    fn sealed_log_with_empty_segment_continues_chain() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("agent.audit.jsonl");
+
+        let mut chain = AuditChain::new(&path).unwrap();
+        chain.append_details("event1", json!({"a": 1})).unwrap();
+        let seal = crate::audit::segment::seal_segment(&path, None)
+            .unwrap()
+            .unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap_or_default(), "");
+
+        let mut audit = AuditLog::new(&path).unwrap();
+        assert_eq!(audit.get_tip(), seal.tail_hash);
+        audit.log_event("event2", json!({"b": 2})).unwrap();
+
+        let content = fs::read_to_string(&path).unwrap();
+        let event: serde_json::Value = serde_json::from_str(content.trim()).unwrap();
+        assert_eq!(
+            event["prev_hash"],
+            crate::audit::segment::chain_start(&path).unwrap()
+        );
+        assert!(crate::audit::verify_chain(&path).unwrap().ok);
+        assert!(
+            crate::audit::segment::verify_segments(&path, None)
+                .unwrap()
+                .ok
+        );
+    }
+
     #[test]
     fn tip_write_and_read_ok() {
         let temp_audit = "/tmp/test_tip_audit.jsonl";
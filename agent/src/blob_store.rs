@@ -7,6 +7,7 @@ use rusqlite::{params, Connection};
 use std::path::Path;
 
 use crate::crypto;
+use crate::registry::sqlite_schema;
 
 /// BLOB Store Trait
 pub trait BlobStore {
@@ -49,31 +50,22 @@ pub struct SqliteBlobStore {
 
 impl SqliteBlobStore {
     /// Create or open a BLOB store
+    ///
+    /// The `blobs` table is part of the shared `registry.sqlite` schema and
+    /// created by its migrations (`registry::sqlite_schema`).
     pub fn new<P: AsRef<Path>>(path: P) -> Result<Self> {
-        let conn = Connection::open(path)?;
+        let path = path.as_ref();
+        let mut conn = Connection::open(path)?;
 
         // Enable WAL mode for better concurrency
         conn.execute_batch("PRAGMA journal_mode=WAL")?;
 
-        // Create blobs table if not exists
-        conn.execute(
-            r#"
-            CREATE TABLE IF NOT EXISTS blobs (
-                blob_id TEXT PRIMARY KEY,
-                size INTEGER NOT NULL,
-                media_type TEXT NOT NULL,
-                data BLOB NOT NULL,
-                refcount INTEGER NOT NULL DEFAULT 0
-            )
-            "#,
-            [],
-        )?;
-
-        // Create index on refcount for GC
-        conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_blobs_refcount ON blobs(refcount)",
-            [],
-        )?;
+        sqlite_schema::migrate(
+            &mut conn,
+            sqlite_schema::file_path(path),
+            sqlite_schema::MigrationOptions::default(),
+        )
+        .map_err(|e| anyhow!("{}", e))?;
 
         Ok(Self { conn })
     }
@@ -2,8 +2,12 @@
 //!
 //! Extrahiert aus main.rs für bessere Wartbarkeit.
 //! Enthält: run_audit_tip, run_audit_anchor, run_audit_set_private_anchor,
-//!          run_audit_set_public_anchor, run_audit_verify_anchor, run_audit_timestamp,
-//!          run_audit_verify_timestamp, run_audit_append, run_audit_verify_chain, run_audit_export
+//!          run_audit_set_public_anchor, run_audit_anchor_batch, run_audit_verify_anchor,
+//!          run_audit_timestamp,
+//!          run_audit_verify_timestamp, run_audit_append, run_audit_checkpoint,
+//!          run_audit_migrate, run_audit_rotate, run_audit_verify_chain, run_audit_prove,
+//!          run_audit_verify_proof,
+//!          run_audit_export
 
 use super::output;
 use crate::audit::AuditLog;
@@ -93,12 +97,29 @@ pub fn run_audit_set_private_anchor(
 
     output::success("Private Anchor gesetzt:");
     output::detail("Audit-Tip", audit_tip);
-    output::detail("Created-At", &created_at.unwrap_or_else(|| "jetzt".to_string()));
+    output::detail(
+        "Created-At",
+        &created_at.unwrap_or_else(|| "jetzt".to_string()),
+    );
     output::detail("Manifest", manifest_path);
 
     Ok(())
 }
 
+/// Parst den Chain-Namen eines Public Anchors
+fn parse_chain(chain: &str) -> Result<manifest::PublicChain, Box<dyn Error>> {
+    match chain.to_lowercase().as_str() {
+        "ethereum" => Ok(manifest::PublicChain::Ethereum),
+        "hedera" => Ok(manifest::PublicChain::Hedera),
+        "btc" => Ok(manifest::PublicChain::Btc),
+        _ => Err(format!(
+            "Invalid chain: {}. Valid options: ethereum, hedera, btc",
+            chain
+        )
+        .into()),
+    }
+}
+
 /// Audit set-public-anchor - Setzt Public Anchor (Dual-Anchor v0.9.0)
 pub fn run_audit_set_public_anchor(
     manifest_path: &str,
@@ -110,18 +131,7 @@ pub fn run_audit_set_public_anchor(
     output::network("Setze Public Anchor...");
 
     // Parse chain
-    let chain_enum = match chain.to_lowercase().as_str() {
-        "ethereum" => manifest::PublicChain::Ethereum,
-        "hedera" => manifest::PublicChain::Hedera,
-        "btc" => manifest::PublicChain::Btc,
-        _ => {
-            return Err(format!(
-                "Invalid chain: {}. Valid options: ethereum, hedera, btc",
-                chain
-            )
-            .into())
-        }
-    };
+    let chain_enum = parse_chain(chain)?;
 
     // Lade Manifest
     let mut manifest = manifest::Manifest::load(manifest_path)?;
@@ -141,15 +151,151 @@ pub fn run_audit_set_public_anchor(
     output::detail("Chain", chain);
     output::detail("TxID", txid);
     output::detail("Digest", digest);
-    output::detail("Created-At", &created_at.unwrap_or_else(|| "jetzt".to_string()));
+    output::detail(
+        "Created-At",
+        &created_at.unwrap_or_else(|| "jetzt".to_string()),
+    );
     output::detail("Manifest", manifest_path);
 
     Ok(())
 }
 
+/// Audit anchor-batch - Batch-Anchoring über mehrere Manifeste
+///
+/// Baut den Merkle-Tree über die Batch-Blätter und gibt die Root aus. Mit
+/// `--chain`/`--txid` (Verankerung der Root) erhält jedes Manifest seinen
+/// Inclusion-Pfad (`time_anchor.batch`) und den Public Anchor auf die Root.
+///
+/// Reihenfolge: anchor-batch → manifest sign. Die Manifest-Signatur deckt
+/// `time_anchor` ab, bereits signierte Manifeste werden daher abgelehnt.
+pub fn run_audit_anchor_batch(
+    manifest_paths: &[String],
+    leaf: &str,
+    chain: Option<&str>,
+    txid: Option<&str>,
+    created_at: Option<String>,
+    out: Option<String>,
+) -> Result<(), Box<dyn Error>> {
+    output::network("Erstelle Batch-Anchor...");
+
+    let leaf_kind: manifest::BatchLeafKind = leaf.parse()?;
+    let mut manifests = manifest_paths
+        .iter()
+        .map(|path| manifest::Manifest::load(path))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // Die Signatur deckt time_anchor ab: Batch-Anchor erst setzen, dann signieren
+    let signed: Vec<&str> = manifests
+        .iter()
+        .zip(manifest_paths)
+        .filter(|(manifest, _)| !manifest.signatures.is_empty())
+        .map(|(_, path)| path.as_str())
+        .collect();
+    if !signed.is_empty() {
+        return Err(format!(
+            "Manifest bereits signiert (Signatur deckt time_anchor ab): {} – erst anchor-batch, dann manifest sign",
+            signed.join(", ")
+        )
+        .into());
+    }
+    let batch = manifest::AnchorBatch::build(&manifests, leaf_kind)?;
+    let root = batch.root_hex();
+
+    output::detail_fmt("Manifeste", batch.len());
+    output::detail_fmt("Blatt-Art", batch.leaf_kind());
+    output::detail("Batch-Root", &root);
+
+    let anchored = match (chain, txid) {
+        (Some(chain), Some(txid)) => {
+            let chain_enum = parse_chain(chain)?;
+            let created_at = created_at.unwrap_or_else(|| chrono::Utc::now().to_rfc3339());
+
+            // Erst alle Manifeste im Speicher verankern, dann speichern: ein
+            // Fehler hinterlässt keinen teilweise verankerten Batch
+            for (index, manifest) in manifests.iter_mut().enumerate() {
+                let anchor = batch
+                    .anchor_for(index)
+                    .ok_or_else(|| format!("Kein Batch-Pfad für Manifest {}", index))?;
+                manifest.set_batch_anchor(anchor)?;
+                manifest.set_public_anchor(
+                    chain_enum.clone(),
+                    txid.to_string(),
+                    root.clone(),
+                    Some(created_at.clone()),
+                )?;
+            }
+            for (manifest, path) in manifests.iter().zip(manifest_paths) {
+                manifest.save(path)?;
+            }
+
+            output::success(&format!(
+                "Batch-Anchor gesetzt für {} Manifeste",
+                manifests.len()
+            ));
+            output::detail("Chain", chain);
+            output::detail("TxID", txid);
+            Some(json!({ "chain": chain_enum, "txid": txid, "created_at": created_at }))
+        }
+        _ => {
+            output::info("Batch-Root on-chain verankern und mit --chain/--txid erneut ausführen");
+            None
+        }
+    };
+
+    if let Some(out_path) = out {
+        let leaves: Vec<_> = manifest_paths
+            .iter()
+            .enumerate()
+            .map(|(index, path)| {
+                json!({
+                    "index": index,
+                    "manifest": path,
+                    "leaf_hash": batch.leaf_hex(index),
+                })
+            })
+            .collect();
+        let batch_file = json!({
+            "version": manifest::batch::BATCH_FILE_VERSION,
+            "scheme": manifest::batch::BATCH_SCHEME,
+            "leaf_kind": batch.leaf_kind(),
+            "root": root,
+            "tree_size": batch.len(),
+            "leaves": leaves,
+            "public": anchored,
+        });
+        std::fs::write(&out_path, serde_json::to_string_pretty(&batch_file)?)?;
+        output::saving(&format!("Batch-Datei gespeichert: {}", out_path));
+    }
+
+    Ok(())
+}
+
+/// Prüft den Inclusion-Proof des Public Anchors (offline, gegen gepinnte Header)
+fn verify_public_anchor_proof(
+    public: Option<&manifest::TimeAnchorPublic>,
+    proof_path: &str,
+    headers_path: Option<&str>,
+) -> Result<manifest::anchor_proof::AnchorAttestation, Box<dyn Error>> {
+    let public = public.ok_or("manifest has no public anchor")?;
+    let proof = manifest::anchor_proof::AnchorProof::load(proof_path)?;
+    let headers = headers_path
+        .map(manifest::anchor_proof::HeaderStore::load)
+        .transpose()?;
+    Ok(manifest::anchor_proof::verify_public_anchor(
+        public,
+        &proof,
+        headers.as_ref(),
+    )?)
+}
+
 /// Audit verify-anchor - Verifiziert Dual-Anchor-Konsistenz
+///
+/// Mit `--proof` wird zusätzlich geprüft, dass der Digest des Public Anchors
+/// on-chain enthalten ist; `--headers` pinnt die Block-Header dafür.
 pub fn run_audit_verify_anchor(
     manifest_path: &str,
+    proof_path: Option<&str>,
+    headers_path: Option<&str>,
     out: Option<String>,
 ) -> Result<(), Box<dyn Error>> {
     output::searching("Verifiziere Dual-Anchor-Konsistenz...");
@@ -158,47 +304,92 @@ pub fn run_audit_verify_anchor(
     let manifest = manifest::Manifest::load(manifest_path)?;
 
     // Validiere Dual-Anchor
-    let validation_result = manifest.validate_dual_anchor();
-
-    let report = if let Err(e) = validation_result {
-        json!({
-            "status": "fail",
-            "manifest": manifest_path,
-            "errors": [e.to_string()],
-            "private_ok": false,
-            "public_ok": false,
-            "digest_match": false,
-        })
-    } else {
-        // Check individual components
-        let (has_private, has_public) = manifest
-            .time_anchor
-            .as_ref()
-            .map(|anchor| (anchor.private.is_some(), anchor.public.is_some()))
-            .unwrap_or((false, false));
+    let mut errors = Vec::new();
+    if let Err(e) = manifest.validate_dual_anchor() {
+        errors.push(e.to_string());
+    }
 
-        json!({
-            "status": "ok",
-            "manifest": manifest_path,
-            "errors": [],
-            "private_ok": has_private,
-            "public_ok": has_public,
-            "digest_match": true, // TODO: actual digest validation if needed
-        })
+    // Check individual components
+    let (has_private, public) = manifest
+        .time_anchor
+        .as_ref()
+        .map(|anchor| (anchor.private.is_some(), anchor.public.as_ref()))
+        .unwrap_or((false, None));
+
+    // Public Anchor on-chain (nur mit Inclusion-Proof)
+    let attestation = match proof_path {
+        Some(path) if errors.is_empty() => {
+            match verify_public_anchor_proof(public, path, headers_path) {
+                Ok(attestation) => Some(attestation),
+                Err(e) => {
+                    errors.push(format!("Public anchor proof invalid: {}", e));
+                    None
+                }
+            }
+        }
+        _ => None,
     };
 
+    let valid = errors.is_empty();
+    let report = json!({
+        "status": if valid { "ok" } else { "fail" },
+        "manifest": manifest_path,
+        "errors": errors,
+        "private_ok": valid && has_private,
+        "public_ok": valid && public.is_some(),
+        // null, wenn kein Inclusion-Proof angegeben wurde
+        "digest_match": proof_path.map(|_| attestation.is_some()),
+        "public_proof": attestation,
+    });
+
     // Print result
     output::section("");
     output::stats("Verifikationsergebnis:");
     output::detail("Status", report["status"].as_str().unwrap_or("unknown"));
     output::detail(
         "Private Anchor",
-        if report["private_ok"].as_bool().unwrap_or(false) { "gültig" } else { "fehlt/ungültig" },
+        if report["private_ok"].as_bool().unwrap_or(false) {
+            "gültig"
+        } else {
+            "fehlt/ungültig"
+        },
     );
     output::detail(
         "Public Anchor",
-        if report["public_ok"].as_bool().unwrap_or(false) { "gültig" } else { "fehlt/ungültig" },
+        if report["public_ok"].as_bool().unwrap_or(false) {
+            "gültig"
+        } else {
+            "fehlt/ungültig"
+        },
     );
+    match &attestation {
+        Some(attestation) => {
+            output::detail(
+                "On-Chain Proof",
+                &format!(
+                    "{} ({}, Block {})",
+                    if attestation.header_pinned {
+                        "gültig"
+                    } else {
+                        "gültig, Header nicht gepinnt"
+                    },
+                    attestation.kind,
+                    attestation.block_hash
+                ),
+            );
+            if let Some(height) = attestation.height {
+                output::detail_fmt("Block-Höhe", height);
+            }
+            output::detail("Block-Zeit", &attestation.block_time);
+            if !attestation.header_pinned {
+                output::warning("Block-Header nicht gepinnt (--headers fehlt)");
+            }
+        }
+        None if proof_path.is_none() => {
+            output::detail("On-Chain Proof", "nicht geprüft (--proof fehlt)")
+        }
+        None => output::detail("On-Chain Proof", "ungültig"),
+    }
 
     if let Some(errors) = report["errors"].as_array() {
         if !errors.is_empty() {
@@ -227,12 +418,18 @@ pub fn run_audit_verify_anchor(
 }
 
 /// Audit timestamp - Erstellt einen Timestamp für den Audit-Head
+///
+/// Mit `--tsa-url` wird ein echtes RFC 3161 Token bei der TSA angefordert
+/// (optional gegen `--tsa-cert` geprüft), sonst ein Mock-Timestamp erstellt.
 pub fn run_audit_timestamp(
     head_path: &str,
     out: Option<String>,
     is_mock: bool,
     tsa_url: Option<String>,
+    tsa_cert: Option<&str>,
 ) -> Result<(), Box<dyn Error>> {
+    use registry::TimestampProvider;
+
     output::timing("Erstelle Timestamp für Audit-Head...");
 
     // Lade Audit-Tip
@@ -240,15 +437,17 @@ pub fn run_audit_timestamp(
     let audit_tip_hex = audit_tip_hex.trim().to_string();
 
     // Erstelle Timestamp
-    let timestamp = if is_mock {
+    let timestamp = if let Some(url) = tsa_url {
+        output::detail("TSA-URL", &url);
+        let certificate = tsa_cert
+            .map(registry::rfc3161::load_certificate)
+            .transpose()?;
+        registry::RealRfc3161Provider::new(url)
+            .with_certificate(certificate)
+            .create(&audit_tip_hex)?
+    } else if is_mock {
         output::warning("MOCK TIMESTAMP (nicht für Produktion geeignet)");
         registry::Timestamp::create_mock(audit_tip_hex)
-    } else if let Some(url) = tsa_url {
-        return Err(format!(
-            "Echter TSA-Timestamp noch nicht implementiert. TSA-URL: {}",
-            url
-        )
-        .into());
     } else {
         return Err("Bitte --mock oder --tsa-url angeben".into());
     };
@@ -265,6 +464,7 @@ pub fn run_audit_timestamp(
             "audit_tip": timestamp.audit_tip_hex,
             "output": out_path,
             "tsa": timestamp.tsa,
+            "version": timestamp.version,
             "created_at": timestamp.created_at
         }),
     )?;
@@ -279,9 +479,12 @@ pub fn run_audit_timestamp(
 }
 
 /// Audit verify-timestamp - Verifiziert einen Timestamp gegen Audit-Head
+///
+/// RFC 3161 Timestamps werden gegen das TSA-Zertifikat (`--tsa-cert`) geprüft.
 pub fn run_audit_verify_timestamp(
     head_path: &str,
     timestamp_path: &str,
+    tsa_cert: Option<&str>,
 ) -> Result<(), Box<dyn Error>> {
     output::searching("Verifiziere Timestamp...");
 
@@ -293,29 +496,49 @@ pub fn run_audit_verify_timestamp(
     let timestamp = registry::Timestamp::load(timestamp_path)?;
 
     // Verifiziere
-    if timestamp.verify(audit_tip_hex) {
-        output::success("Timestamp valid");
-        output::detail("Audit-Tip", &timestamp.audit_tip_hex);
-        output::detail("Erstellt", &timestamp.created_at);
-        output::detail("TSA", &timestamp.tsa);
-
-        // Log Audit-Event
-        let mut audit = AuditLog::new("build/agent.audit.jsonl")?;
-        audit.log_event(
-            "timestamp_verified",
-            json!({
-                "audit_tip": audit_tip_hex,
-                "timestamp_file": timestamp_path,
-                "status": "ok"
-            }),
-        )?;
-
-        Ok(())
+    let result: Result<String, Box<dyn Error>> = if timestamp.is_rfc3161() {
+        let path = tsa_cert.ok_or("RFC 3161 Timestamp: --tsa-cert erforderlich")?;
+        let certificate = registry::rfc3161::load_certificate(path)?;
+        timestamp
+            .verify_with_certificate(audit_tip_hex, &certificate)
+            .map(|token| format!("RFC 3161, Serial {}", token.serial_number))
     } else {
-        output::error("Timestamp invalid or mismatched head");
-        output::detail("Erwartet", audit_tip_hex);
-        output::detail("Gefunden", &timestamp.audit_tip_hex);
-        Err("Timestamp-Verifikation fehlgeschlagen".into())
+        output::warning("MOCK TIMESTAMP (keine RFC 3161 Signatur)");
+        match timestamp.verify(audit_tip_hex) {
+            Ok(true) => Ok("Mock".to_string()),
+            Ok(false) => Err("Mock-Signatur ungültig oder Audit-Tip abweichend".into()),
+            Err(e) => Err(e),
+        }
+    };
+
+    match result {
+        Ok(kind) => {
+            output::success("Timestamp valid");
+            output::detail("Audit-Tip", &timestamp.audit_tip_hex);
+            output::detail("Erstellt", &timestamp.created_at);
+            output::detail("TSA", &timestamp.tsa);
+            output::detail("Typ", &kind);
+
+            // Log Audit-Event
+            let mut audit = AuditLog::new("build/agent.audit.jsonl")?;
+            audit.log_event(
+                "timestamp_verified",
+                json!({
+                    "audit_tip": audit_tip_hex,
+                    "timestamp_file": timestamp_path,
+                    "status": "ok"
+                }),
+            )?;
+
+            Ok(())
+        }
+        Err(e) => {
+            output::error("Timestamp invalid or mismatched head");
+            output::detail("Erwartet", audit_tip_hex);
+            output::detail("Gefunden", &timestamp.audit_tip_hex);
+            output::detail("Fehler", &e.to_string());
+            Err("Timestamp-Verifikation fehlgeschlagen".into())
+        }
     }
 }
 
@@ -360,21 +583,95 @@ pub fn run_audit_append(
     output::detail("Event", &audit_event.event);
     output::detail("Timestamp", &audit_event.ts);
     output::detail("Self-Hash", &audit_event.self_hash);
+    output::detail_fmt("Events", chain.len());
+    output::detail("Merkle-Root", &chain.merkle_root());
     output::detail("Chain-Datei", file_path);
 
     Ok(())
 }
 
-/// Audit verify - Verifiziert Audit-Chain-Integrität (Track A)
+/// Lädt den KeyProvider für Checkpoints (Provider-Config oder Software-Keys)
+fn load_key_provider(
+    provider_config: Option<&str>,
+    keys_dir: &str,
+    key: Option<String>,
+) -> Result<Box<dyn crate::providers::KeyProvider>, Box<dyn Error>> {
+    use crate::providers::{create_provider, load_config, SoftwareProvider};
+
+    match provider_config {
+        Some(config_path) => Ok(create_provider(load_config(config_path)?)?),
+        None => Ok(Box::new(SoftwareProvider::new(keys_dir, key)?)),
+    }
+}
+
+/// Lädt den KeyProvider, dessen Schlüssel als Checkpoint-Signer akzeptiert werden (optional)
+fn load_trusted_provider(
+    provider_config: Option<&str>,
+    keys_dir: Option<&str>,
+) -> Result<Option<Box<dyn crate::providers::KeyProvider>>, Box<dyn Error>> {
+    match (provider_config, keys_dir) {
+        (None, None) => Ok(None),
+        (config, dir) => Ok(Some(load_key_provider(
+            config,
+            dir.unwrap_or("keys"),
+            None,
+        )?)),
+    }
+}
+
+/// Audit checkpoint - Signiert Größe und Tail-Hash eines Audit-Logs
+pub fn run_audit_checkpoint(
+    file_path: &str,
+    out: Option<String>,
+    keys_dir: &str,
+    key: Option<String>,
+    kid: Option<String>,
+    provider_config: Option<String>,
+) -> Result<(), Box<dyn Error>> {
+    use crate::audit::{append_checkpoint, checkpoint_path, AuditCheckpoint};
+
+    output::secure("Signiere Audit-Checkpoint...");
+
+    let provider = load_key_provider(provider_config.as_deref(), keys_dir, key)?;
+    let checkpoint = AuditCheckpoint::create(file_path, provider.as_ref(), kid.as_deref())?;
+
+    let out_path = out
+        .map(std::path::PathBuf::from)
+        .unwrap_or_else(|| checkpoint_path(file_path));
+    append_checkpoint(&out_path, &checkpoint)?;
+
+    output::success("Checkpoint signiert");
+    output::detail("Format", &checkpoint.format.to_string());
+    output::detail_fmt("Events", checkpoint.tree_size);
+    output::detail("Tail-Hash", &checkpoint.tail_hash);
+    output::detail("KID", &checkpoint.kid);
+    output::detail("Provider", &checkpoint.provider);
+    output::detail("Checkpoints", &out_path.display().to_string());
+
+    Ok(())
+}
+
+/// Audit verify - Verifiziert Audit-Chain-Integrität (Track A) und signierte Checkpoints
 pub fn run_audit_verify_chain(
     file_path: &str,
     out: Option<String>,
+    checkpoints: Option<String>,
+    keys_dir: Option<String>,
+    provider_config: Option<String>,
 ) -> Result<(), Box<dyn Error>> {
-    use crate::audit::verify_chain;
+    use crate::audit::segment::segments_dir;
+    use crate::audit::{
+        checkpoint, checkpoint_path, verify_chain, verify_log_checkpoints, verify_migration,
+        verify_segments,
+    };
 
     output::searching("Verifiziere Audit-Chain...");
 
-    let report = verify_chain(file_path)?;
+    let (format, _) = checkpoint::read_event_hashes(file_path)?;
+    let report = match format {
+        checkpoint::LogFormat::V1 => AuditLog::verify_file(file_path)?,
+        checkpoint::LogFormat::V2 => verify_chain(file_path)?,
+    };
 
     if report.ok {
         output::success("Chain-Integrität OK");
@@ -391,13 +688,83 @@ pub fn run_audit_verify_chain(
         }
     }
 
+    // Migrations-Bridge (aus v1.0 migrierte Logs)
+    let bridge = match format {
+        checkpoint::LogFormat::V1 => None,
+        checkpoint::LogFormat::V2 => verify_migration(file_path)?,
+    };
+    if let Some(bridge) = &bridge {
+        if bridge.ok {
+            output::success("Migrations-Bridge OK");
+            output::detail_fmt("v1.0-Events", bridge.bridge.source_events);
+            output::detail("v1.0-Tail", &bridge.bridge.source_tail_digest);
+        } else {
+            output::error("Migrations-Bridge FEHLGESCHLAGEN");
+            if let Some(err) = &bridge.error {
+                output::detail("Fehler", err);
+            }
+        }
+    }
+    let bridge_ok = bridge.as_ref().map(|b| b.ok).unwrap_or(true);
+
+    let trusted = load_trusted_provider(provider_config.as_deref(), keys_dir.as_deref())?;
+
+    // Versiegelte Segmente (rotierte Logs)
+    let seg_report = if segments_dir(file_path).exists() {
+        let seg_report = verify_segments(file_path, trusted.as_deref())?;
+        if seg_report.ok {
+            let summary_only = seg_report.segments.iter().filter(|s| !s.archived).count();
+            output::success("Segmente OK");
+            output::detail_fmt("Segmente", seg_report.segments.len());
+            output::detail_fmt("Versiegelte Events", seg_report.sealed_events);
+            if summary_only > 0 {
+                output::detail_fmt("Nur Siegel (Archiv fehlt)", summary_only);
+            }
+        } else {
+            output::error("Segment-Prüfung FEHLGESCHLAGEN");
+            if let Some(err) = &seg_report.error {
+                output::detail("Fehler", err);
+            }
+        }
+        Some(seg_report)
+    } else {
+        None
+    };
+    let segments_ok = seg_report.as_ref().map(|r| r.ok).unwrap_or(true);
+
+    // Signierte Checkpoints (Truncation/Rewrite-Erkennung)
+    let checkpoints_path = checkpoints
+        .map(std::path::PathBuf::from)
+        .unwrap_or_else(|| checkpoint_path(file_path));
+    let cp_report = verify_log_checkpoints(file_path, &checkpoints_path, trusted.as_deref())?;
+
+    if cp_report.checkpoints == 0 {
+        output::warning("Keine signierten Checkpoints gefunden (Truncation nicht erkennbar)");
+    } else if cp_report.ok {
+        output::success("Checkpoints OK");
+        output::detail_fmt("Checkpoints", cp_report.checkpoints);
+        output::detail_fmt("Abgedeckt", cp_report.covered_events);
+        if trusted.is_none() {
+            output::detail("Signer", "nicht gepinnt (--keys-dir/--provider-config)");
+        }
+    } else {
+        output::error("Checkpoint-Prüfung FEHLGESCHLAGEN");
+        if let Some(err) = &cp_report.error {
+            output::detail("Fehler", err);
+        }
+    }
+
     // Write JSON report if requested
     if let Some(out_path) = out {
         let report_json = serde_json::json!({
-            "ok": report.ok,
+            "ok": report.ok && bridge_ok && segments_ok && cp_report.ok,
+            "format": format,
             "total_events": report.total_events,
             "tamper_index": report.tamper_index,
             "error": report.error,
+            "migration": bridge,
+            "segments": seg_report,
+            "checkpoints": cp_report,
         });
         std::fs::write(&out_path, serde_json::to_string_pretty(&report_json)?)?;
         output::document(&format!("Report gespeichert: {}", out_path));
@@ -406,39 +773,313 @@ pub fn run_audit_verify_chain(
     if !report.ok {
         return Err("Chain-Verifikation fehlgeschlagen".into());
     }
+    if !bridge_ok {
+        return Err("Migrations-Bridge-Verifikation fehlgeschlagen".into());
+    }
+    if !segments_ok {
+        return Err("Segment-Verifikation fehlgeschlagen".into());
+    }
+    if !cp_report.ok {
+        return Err("Checkpoint-Verifikation fehlgeschlagen".into());
+    }
 
     Ok(())
 }
 
-/// Audit export - Exportiert Events aus Audit-Chain (Track A)
+/// Audit migrate - Migriert ein v1.0-Audit-Log verlustfrei in das strukturierte Format
+pub fn run_audit_migrate(file_path: &str, out: Option<String>) -> Result<(), Box<dyn Error>> {
+    use crate::audit::{migrate_v1_in_place, migrate_v1_log};
+
+    output::packaging("Migriere v1.0-Audit-Log...");
+
+    let report = match &out {
+        Some(out_path) => migrate_v1_log(file_path, out_path)?,
+        None => migrate_v1_in_place(file_path)?,
+    };
+
+    output::success("Audit-Log migriert");
+    output::detail("Ziel", out.as_deref().unwrap_or(file_path));
+    output::detail_fmt("v1.0-Events", report.bridge.source_events);
+    output::detail("v1.0-Tail", &report.bridge.source_tail_digest);
+    output::detail_fmt("Events (inkl. Bridge)", report.events);
+    output::detail("Tail-Hash", &report.tail_hash);
+    if let Some(backup) = &report.backup {
+        output::detail("Original", &backup.display().to_string());
+    }
+
+    Ok(())
+}
+
+/// Audit rotate - Versiegelt das aktive Segment und beginnt ein neues
+#[allow(clippy::too_many_arguments)]
+pub fn run_audit_rotate(
+    file_path: &str,
+    max_bytes: Option<u64>,
+    period: Option<String>,
+    sign: bool,
+    keys_dir: &str,
+    key: Option<String>,
+    kid: Option<String>,
+    provider_config: Option<String>,
+) -> Result<(), Box<dyn Error>> {
+    use crate::audit::{seal_segment, AuditChain, RotationPeriod, RotationPolicy};
+
+    output::packaging("Rotiere Audit-Log...");
+
+    let policy = RotationPolicy {
+        max_bytes,
+        period: period.as_deref().map(RotationPeriod::parse).transpose()?,
+    };
+    let signer = if sign || provider_config.is_some() {
+        Some(load_key_provider(
+            provider_config.as_deref(),
+            keys_dir,
+            key,
+        )?)
+    } else {
+        None
+    };
+    let signer = signer.as_deref().map(|provider| (provider, kid.as_deref()));
+
+    // Ohne Limits wird sofort rotiert
+    let seal = if policy == RotationPolicy::default() {
+        seal_segment(file_path, signer)?
+    } else {
+        match policy.due(file_path)? {
+            Some(reason) => {
+                output::detail("Grund", &reason);
+                AuditChain::new(file_path)?.rotate_if_due(&policy, signer)?
+            }
+            None => {
+                output::info("Keine Rotation fällig");
+                return Ok(());
+            }
+        }
+    };
+
+    match seal {
+        Some(seal) => {
+            output::success_with("Segment versiegelt", seal.segment);
+            output::detail_fmt("Events", seal.event_count);
+            output::detail("Zeitraum", &format!("{} - {}", seal.first_ts, seal.last_ts));
+            output::detail("Tail-Hash", &seal.tail_hash);
+            output::detail("Archiv", &seal.archive);
+            output::detail("KID", seal.kid.as_deref().unwrap_or("unsigniert"));
+        }
+        None => output::info("Keine Events im aktiven Segment - nichts zu versiegeln"),
+    }
+
+    Ok(())
+}
+
+/// Audit prove - Erzeugt einen Inclusion-Proof für ein einzelnes Event
+pub fn run_audit_prove(
+    file_path: &str,
+    event: u64,
+    checkpoints: Option<String>,
+    out: Option<String>,
+) -> Result<(), Box<dyn Error>> {
+    use crate::audit::{checkpoint_path, load_checkpoints, AuditEventProof};
+
+    output::searching(&format!("Erzeuge Inclusion-Proof für Event {}...", event));
+
+    let checkpoints_path = checkpoints
+        .map(std::path::PathBuf::from)
+        .unwrap_or_else(|| checkpoint_path(file_path));
+    if !checkpoints_path.exists() {
+        return Err(format!(
+            "Keine Checkpoints gefunden: {}. Führe zuerst 'audit checkpoint' aus.",
+            checkpoints_path.display()
+        )
+        .into());
+    }
+
+    let proof = AuditEventProof::create(file_path, &load_checkpoints(&checkpoints_path)?, event)?;
+
+    let out_path = out.unwrap_or_else(|| {
+        std::path::Path::new(file_path)
+            .with_extension(format!("{}.inclusion.json", event))
+            .display()
+            .to_string()
+    });
+    proof.save(&out_path)?;
+
+    output::success_with("Inclusion-Proof gespeichert", &out_path);
+    output::detail("Event", proof.event["event"].as_str().unwrap_or("?"));
+    output::detail("Event-Hash", &proof.event_hash);
+    output::detail_fmt("Tree-Size", proof.tree_size);
+    output::detail("Root", &proof.root_hash);
+    output::detail("Checkpoint-KID", &proof.checkpoint.kid);
+
+    Ok(())
+}
+
+/// Audit verify-proof - Verifiziert einen Event-Inclusion-Proof (ohne Audit-Log)
+pub fn run_audit_verify_proof(
+    proof_path: &str,
+    keys_dir: Option<String>,
+    provider_config: Option<String>,
+) -> Result<(), Box<dyn Error>> {
+    use crate::audit::AuditEventProof;
+
+    output::searching("Verifiziere Event-Inclusion-Proof...");
+
+    let proof = AuditEventProof::load(proof_path)?;
+    let trusted = load_trusted_provider(provider_config.as_deref(), keys_dir.as_deref())?;
+
+    if let Err(e) = proof.verify(trusted.as_deref()) {
+        output::error("Inclusion-Proof UNGÜLTIG");
+        output::detail("Fehler", &e.to_string());
+        return Err("Proof-Verifikation fehlgeschlagen".into());
+    }
+
+    output::success("Inclusion-Proof gültig");
+    output::detail_fmt("Event", proof.seq);
+    output::detail("Typ", proof.event["event"].as_str().unwrap_or("?"));
+    output::detail("Zeit", proof.event["ts"].as_str().unwrap_or("?"));
+    output::detail_fmt("Tree-Size", proof.tree_size);
+    output::detail("Root", &proof.root_hash);
+    output::detail("Checkpoint-KID", &proof.checkpoint.kid);
+    output::detail("Checkpoint-Zeit", &proof.checkpoint.timestamp);
+    if trusted.is_none() {
+        output::detail("Signer", "nicht gepinnt (--keys-dir/--provider-config)");
+    }
+
+    Ok(())
+}
+
+/// Baut die Event-Abfrage aus den CLI-Filtern
+fn build_audit_query(
+    from: Option<String>,
+    to: Option<String>,
+    events: Vec<String>,
+    policy_id: Option<String>,
+    run_id: Option<String>,
+    manifest_hash: Option<String>,
+    result: Option<String>,
+) -> Result<crate::audit::AuditQuery, Box<dyn Error>> {
+    let query = crate::audit::AuditQuery {
+        from,
+        to,
+        events,
+        policy_id,
+        run_id,
+        manifest_hash,
+        result: result.map(|r| r.parse()).transpose()?,
+    };
+    query.validate()?;
+    Ok(query)
+}
+
+/// Audit export - Exportiert gefilterte Events (JSON oder CSV)
+#[allow(clippy::too_many_arguments)]
 pub fn run_audit_export(
     file_path: &str,
     from: Option<String>,
     to: Option<String>,
     policy_id: Option<String>,
+    events: Vec<String>,
+    run_id: Option<String>,
+    manifest_hash: Option<String>,
+    result: Option<String>,
+    format: &str,
     out: Option<String>,
 ) -> Result<(), Box<dyn Error>> {
-    use crate::audit::export_events;
+    use crate::audit::{query_events, write_csv};
+
+    if !matches!(format, "json" | "csv") {
+        return Err(format!("Unbekanntes Format: {} (json, csv)", format).into());
+    }
+    let query = build_audit_query(from, to, events, policy_id, run_id, manifest_hash, result)?;
 
     output::packaging("Exportiere Events aus Audit-Chain...");
 
-    let events = export_events(
-        file_path,
-        from.as_deref(),
-        to.as_deref(),
-        policy_id.as_deref(),
-    )?;
+    let events = query_events(file_path, &query)?;
 
     output::success_with("Events exportiert", &format!("{}", events.len()));
 
     // Output to file or stdout
-    let json_output = serde_json::to_string_pretty(&events)?;
+    let rendered = if format == "csv" {
+        let mut csv = Vec::new();
+        write_csv(&events, &mut csv)?;
+        String::from_utf8(csv)?
+    } else {
+        serde_json::to_string_pretty(&events)?
+    };
 
     if let Some(out_path) = out {
-        std::fs::write(&out_path, &json_output)?;
+        std::fs::write(&out_path, &rendered)?;
         output::document(&format!("Events gespeichert: {}", out_path));
     } else {
-        println!("\n{}", json_output);
+        println!("\n{}", rendered);
+    }
+
+    Ok(())
+}
+
+/// Audit report - Erstellt einen druckbaren Audit-Bericht (Markdown, HTML oder JSON)
+#[allow(clippy::too_many_arguments)]
+pub fn run_audit_report(
+    file_path: &str,
+    from: Option<String>,
+    to: Option<String>,
+    events: Vec<String>,
+    policy_id: Option<String>,
+    period: &str,
+    manifests: Vec<String>,
+    checkpoints: Option<String>,
+    keys_dir: Option<String>,
+    provider_config: Option<String>,
+    format: &str,
+    title: Option<String>,
+    out: Option<String>,
+) -> Result<(), Box<dyn Error>> {
+    use crate::audit::{AuditReport, ReportOptions, ReportPeriod};
+
+    let extension = match format {
+        "markdown" => "md",
+        "html" => "html",
+        "json" => "json",
+        _ => return Err(format!("Unbekanntes Format: {} (markdown, html, json)", format).into()),
+    };
+    let options = ReportOptions {
+        title,
+        query: build_audit_query(from, to, events, policy_id, None, None, None)?,
+        period: ReportPeriod::parse(period)?,
+        manifests: manifests.into_iter().map(Into::into).collect(),
+        checkpoints: checkpoints.map(Into::into),
+    };
+    let trusted = load_trusted_provider(provider_config.as_deref(), keys_dir.as_deref())?;
+
+    output::document("Erstelle Audit-Bericht...");
+
+    let report = AuditReport::build(file_path, &options, trusted.as_deref())?;
+    let rendered = match format {
+        "html" => report.to_html(),
+        "json" => serde_json::to_string_pretty(&report)?,
+        _ => report.to_markdown(),
+    };
+
+    let out_path = out.unwrap_or_else(|| format!("build/audit_report.{}", extension));
+    if let Some(parent) = std::path::Path::new(&out_path).parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&out_path, rendered)?;
+
+    let events: usize = report.periods.iter().map(|p| p.events).sum();
+    if report.ok() {
+        output::success("Audit-Bericht erstellt");
+    } else {
+        output::error("Audit-Bericht erstellt, Integrität VERLETZT");
+    }
+    output::detail_fmt("Events", events);
+    output::detail_fmt("Zeiträume", report.periods.len());
+    output::detail_fmt("Anker", report.anchors.len());
+    output::detail_fmt("Checkpoints", report.checkpoints.checkpoints);
+    output::detail("Bericht", &out_path);
+
+    if !report.ok() {
+        return Err("Audit-Log-Verifikation fehlgeschlagen".into());
     }
 
     Ok(())
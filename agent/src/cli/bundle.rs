@@ -18,7 +18,11 @@ const VERSION: &str = "0.8.0";
 // ============================================================================
 
 /// Erstellt die _meta.json mit Hashes
-fn create_bundle_meta(out: &str, manifest_hash: &str, proof_hash: &str) -> Result<(), Box<dyn Error>> {
+fn create_bundle_meta(
+    out: &str,
+    manifest_hash: &str,
+    proof_hash: &str,
+) -> Result<(), Box<dyn Error>> {
     output::step(5, 7, "Creating _meta.json...");
     let meta = serde_json::json!({
         "bundle_version": "cap-proof.v2.0",
@@ -37,7 +41,11 @@ fn create_bundle_meta(out: &str, manifest_hash: &str, proof_hash: &str) -> Resul
 }
 
 /// Erstellt die README.txt für Bundle v2
-fn create_bundle_v2_readme(out: &str, manifest_hash: &str, proof_hash: &str) -> Result<(), Box<dyn Error>> {
+fn create_bundle_v2_readme(
+    out: &str,
+    manifest_hash: &str,
+    proof_hash: &str,
+) -> Result<(), Box<dyn Error>> {
     output::step(6, 7, "Creating README.txt...");
     let readme_content = format!(
         r#"CAP Proof Bundle v2.0
@@ -214,68 +222,64 @@ pub fn run_verify_bundle(bundle: &str, out: Option<String>) -> Result<(), Box<dy
         return Err(format!("Proof not found: {}", proof_path).into());
     }
 
-    // Validate bundle integrity via _meta.json hashes
-    output::step(2, 5, "Validating bundle integrity...");
+    // Expected hashes from _meta.json (checked by the verification engine)
+    output::step(2, 5, "Reading bundle metadata...");
     let meta_path = format!("{}/_meta.json", bundle);
     let manifest_bytes = fs::read(&manifest_path)?;
     let proof_bytes = fs::read(&proof_path)?;
-    let manifest_hash = crypto::hex_lower_prefixed32(crypto::sha3_256(&manifest_bytes));
-    let proof_hash = crypto::hex_lower_prefixed32(crypto::sha3_256(&proof_bytes));
 
-    if Path::new(&meta_path).exists() {
-        let meta_content = fs::read_to_string(&meta_path)?;
-        let meta: serde_json::Value = serde_json::from_str(&meta_content)?;
-
-        // Check manifest hash
-        if let Some(expected_manifest_hash) = meta["hashes"]["manifest_sha3"].as_str() {
-            if manifest_hash != expected_manifest_hash {
-                return Err(format!(
-                    "Bundle integrity check failed: Manifest hash mismatch\n  Expected: {}\n  Actual:   {}",
-                    expected_manifest_hash, manifest_hash
-                ).into());
-            }
-            output::indent("Manifest hash valid");
-        }
-
-        // Check proof hash
-        if let Some(expected_proof_hash) = meta["hashes"]["proof_sha3"].as_str() {
-            if proof_hash != expected_proof_hash {
-                return Err(format!(
-                    "Bundle integrity check failed: Proof hash mismatch\n  Expected: {}\n  Actual:   {}",
-                    expected_proof_hash, proof_hash
-                ).into());
-            }
-            output::indent("Proof hash valid");
-        }
+    let meta: Option<serde_json::Value> = if Path::new(&meta_path).exists() {
+        Some(serde_json::from_str(&fs::read_to_string(&meta_path)?)?)
     } else {
         output::warning("No _meta.json found, skipping hash validation");
-    }
+        None
+    };
+    let expected_hash = |key: &str| meta.as_ref().and_then(|m| m["hashes"][key].as_str());
 
     output::step(3, 5, "Using native verifier...");
 
-    // Parse manifest as JSON Value (verifier expects serde_json::Value)
-    let manifest_json: serde_json::Value = serde_json::from_slice(&manifest_bytes)?;
-
-    // Create verification options
+    // Run verification engine
     output::step(4, 5, "Running verification...");
     let options = verifier::core::VerifyOptions {
         check_timestamp: false,
         check_registry: false,
+        registry: None,
+        key_store: None,
     };
+    let report = verifier::core::verify_bytes(
+        &manifest_bytes,
+        &proof_bytes,
+        expected_hash("manifest_sha3"),
+        expected_hash("proof_sha3"),
+        &options,
+    )?;
 
-    // Extract statement and verify
-    let stmt = verifier::core::extract_statement_from_manifest(&manifest_json)?;
-    let report = verifier::core::verify(&manifest_json, &proof_bytes, &stmt, &options)?;
+    for check in &report.checks {
+        match (check.id.as_str(), check.status.is_pass()) {
+            ("hash_match_manifest", true) if expected_hash("manifest_sha3").is_some() => {
+                output::indent("Manifest hash valid")
+            }
+            ("hash_match_proof", true) if expected_hash("proof_sha3").is_some() => {
+                output::indent("Proof hash valid")
+            }
+            (_, false) => output::indent(&format!("❌ {}: {}", check.id, check.message)),
+            _ => {}
+        }
+    }
 
     output::step(5, 5, "Generating report...");
     output::section("");
     output::stats("Verification Report:");
     output::detail("Status", &report.status);
-    output::detail("Manifest Hash", &manifest_hash);
-    output::detail("Proof Hash", &proof_hash);
+    output::detail("Manifest Hash", &report.manifest_hash);
+    output::detail("Proof Hash", &report.proof_hash);
     output::detail(
         "Signature",
-        if report.signature_valid { "valid" } else { "invalid" },
+        if report.signature_valid {
+            "valid"
+        } else {
+            "invalid"
+        },
     );
 
     // Save report if requested
@@ -287,7 +291,13 @@ pub fn run_verify_bundle(bundle: &str, out: Option<String>) -> Result<(), Box<dy
     }
 
     if report.status != "ok" {
-        return Err("Verification failed".into());
+        let failed: Vec<String> = report
+            .checks
+            .iter()
+            .filter(|c| !c.status.is_pass())
+            .map(|c| c.message.clone())
+            .collect();
+        return Err(format!("Verification failed: {}", failed.join("; ")).into());
     }
 
     Ok(())
@@ -0,0 +1,192 @@
+//! CLI-Handler für Inclusion-Proof-Kommandos
+//!
+//! Enthält: run_inclusion_prove, run_inclusion_verify
+
+use super::output;
+use crate::audit::AuditLog;
+use crate::commitment::{self, RecordNonces};
+use crate::inclusion::{self, RecordInclusionProof, RecordKind};
+use crate::io::{self, JsonPersistent};
+use crate::manifest::Manifest;
+use serde_json::json;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+/// Wählt die Position eines Datensatzes über Name oder Index aus
+fn select_index(
+    names: &[&str],
+    name: Option<&str>,
+    index: Option<usize>,
+) -> Result<usize, Box<dyn Error>> {
+    match (name, index) {
+        (Some(_), Some(_)) => {
+            Err("Bitte entweder --name oder --index angeben, nicht beides".into())
+        }
+        (None, Some(i)) => Ok(i),
+        (Some(n), None) => {
+            let matches: Vec<usize> = names
+                .iter()
+                .enumerate()
+                .filter(|(_, candidate)| **candidate == n)
+                .map(|(i, _)| i)
+                .collect();
+            match matches.as_slice() {
+                [i] => Ok(*i),
+                [] => Err(format!("Kein Datensatz mit Name gefunden: {}", n).into()),
+                _ => Err(format!(
+                    "Name nicht eindeutig ({} Treffer) – bitte --index verwenden",
+                    matches.len()
+                )
+                .into()),
+            }
+        }
+        (None, None) => Err("Bitte --name oder --index angeben".into()),
+    }
+}
+
+/// Inclusion prove - Erzeugt Inclusion-Proof für einen Supplier oder UBO
+///
+/// Legt nur die Felder in `reveal` offen; die übrigen Felder erscheinen
+/// ausschließlich als gesalzenes Commitment.
+pub fn run_inclusion_prove(
+    kind: &str,
+    csv: &str,
+    name: Option<String>,
+    index: Option<usize>,
+    commitments_path: Option<String>,
+    reveal: &[String],
+    out: Option<String>,
+) -> Result<(), Box<dyn Error>> {
+    let kind: RecordKind = kind.parse()?;
+    output::secure(&format!("Erzeuge Inclusion-Proof ({})...", kind));
+
+    // Die Blätter sind mit den privaten Nonces aus prepare gesalzen
+    let commitments_path = commitments_path.unwrap_or_else(|| "build/commitments.json".to_string());
+    let nonces_path = commitment::nonces_path(&commitments_path);
+    let nonces = RecordNonces::load(&nonces_path).map_err(|e| {
+        format!(
+            "Nonces {} nicht lesbar ({}) – bitte prepare ausführen",
+            nonces_path.display(),
+            e
+        )
+    })?;
+    let reveal: Vec<&str> = reveal.iter().map(|f| f.trim()).collect();
+
+    let proof = match kind {
+        RecordKind::Supplier => {
+            let suppliers = io::read_suppliers_csv(csv)?;
+            let names: Vec<&str> = suppliers.iter().map(|s| s.name.as_str()).collect();
+            let idx = select_index(&names, name.as_deref(), index)?;
+            inclusion::prove_supplier(&suppliers, &nonces.suppliers, idx, &reveal)?
+        }
+        RecordKind::Ubo => {
+            let ubos = io::read_ubos_csv(csv)?;
+            let names: Vec<&str> = ubos.iter().map(|u| u.name.as_str()).collect();
+            let idx = select_index(&names, name.as_deref(), index)?;
+            inclusion::prove_ubo(&ubos, &nonces.ubos, idx, &reveal)?
+        }
+    };
+
+    // Gegenprüfung mit committetem Root
+    let commitments = commitment::load_commitments(&commitments_path)?;
+    let committed_root = match kind {
+        RecordKind::Supplier => &commitments.supplier_root,
+        RecordKind::Ubo => &commitments.ubo_root,
+    };
+    if *committed_root != proof.root {
+        return Err(format!(
+            "CSV passt nicht zu {} (Root {} ≠ {})",
+            commitments_path, proof.root, committed_root
+        )
+        .into());
+    }
+    output::success("Root stimmt mit Commitments überein");
+
+    let out_path = out.unwrap_or_else(|| format!("build/inclusion.{}.json", kind));
+    if let Some(parent) = Path::new(&out_path).parent() {
+        fs::create_dir_all(parent)?;
+    }
+    proof.save(&out_path)?;
+
+    output::success("Inclusion-Proof erstellt:");
+    output::detail_fmt("Leaf Index", proof.leaf_index);
+    output::detail_fmt("Tree Size", proof.tree_size);
+    output::detail_fmt("Path Length", proof.path.len());
+    output::detail("Root", &proof.root);
+    output::detail("Offengelegt", &reveal.join(", "));
+    output::detail("Output", &out_path);
+
+    // Audit-Event ohne Klartext-Datensatz
+    let mut audit = AuditLog::new("build/agent.audit.jsonl")?;
+    audit.log_event(
+        "inclusion_proof_created",
+        json!({
+            "kind": kind.to_string(),
+            "leaf_index": proof.leaf_index,
+            "tree_size": proof.tree_size,
+            "disclosed": &reveal,
+            "root": proof.root,
+            "output": out_path,
+        }),
+    )?;
+
+    Ok(())
+}
+
+/// Inclusion verify - Verifiziert Inclusion-Proof offline gegen Manifest oder Root
+pub fn run_inclusion_verify(
+    proof_path: &str,
+    manifest_path: Option<String>,
+    root: Option<String>,
+) -> Result<(), Box<dyn Error>> {
+    output::searching(&format!("Verifiziere Inclusion-Proof: {}", proof_path));
+
+    let proof = RecordInclusionProof::load(proof_path)?;
+
+    let expected_root = match (manifest_path, root) {
+        (Some(_), Some(_)) => {
+            return Err("Bitte entweder --manifest oder --root angeben, nicht beides".into())
+        }
+        (Some(path), None) => {
+            let manifest = Manifest::load(&path)?;
+            output::detail("Manifest", &path);
+            inclusion::expected_root(&manifest, proof.kind)?
+        }
+        (None, Some(r)) => r,
+        (None, None) => return Err("Bitte --manifest oder --root angeben".into()),
+    };
+
+    proof.verify(&expected_root)?;
+
+    output::success("Inclusion-Proof gültig");
+    output::detail("Kind", &proof.kind.to_string());
+    output::detail("Root", &expected_root);
+    for (field, disclosed) in &proof.disclosed {
+        output::detail(field, &disclosed.value.to_string());
+    }
+    output::detail_fmt("Verdeckte Felder", proof.hidden.len());
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_select_index_by_name() {
+        let names = ["A", "B", "C"];
+        assert_eq!(select_index(&names, Some("B"), None).unwrap(), 1);
+        assert!(select_index(&names, Some("X"), None).is_err());
+        assert!(select_index(&names, None, None).is_err());
+        assert!(select_index(&names, Some("A"), Some(0)).is_err());
+    }
+
+    #[test]
+    fn test_select_index_ambiguous_name() {
+        let names = ["A", "A"];
+        assert!(select_index(&names, Some("A"), None).is_err());
+        assert_eq!(select_index(&names, None, Some(1)).unwrap(), 1);
+    }
+}
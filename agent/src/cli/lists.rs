@@ -0,0 +1,59 @@
+//! CLI-Handler für Lists-Kommandos
+//!
+//! Enthält: run_lists_sanctions_root, run_lists_jurisdictions_root
+
+use super::{output, paths};
+use crate::audit::AuditLog;
+use crate::lists::{ListKind, ListRootFile};
+use serde_json::json;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+/// Erzeugt die versionierte Root-Datei einer Liste
+fn run_lists_root(kind: ListKind, csv_path: &str, out_path: &str) -> Result<(), Box<dyn Error>> {
+    output::secure(&format!("Erzeuge {}-Root aus {}...", kind, csv_path));
+
+    let root_file = ListRootFile::build(csv_path, kind)?;
+    if let Some(parent) = Path::new(out_path).parent() {
+        fs::create_dir_all(parent)?;
+    }
+    root_file.save(out_path)?;
+
+    fs::create_dir_all(paths::BUILD_DIR)?;
+    let mut audit = AuditLog::new(paths::AUDIT_LOG)?;
+    audit.log_event(
+        "list_root_built",
+        json!({
+            "list": kind,
+            "root": &root_file.root,
+            "entry_count": root_file.entry_count,
+            "source_hash": &root_file.source_hash,
+            "output": out_path
+        }),
+    )?;
+
+    output::success_with("Root-Datei erstellt", out_path);
+    output::detail("Root", &root_file.root);
+    output::detail_fmt("Einträge", root_file.entry_count);
+    output::detail_fmt("Quellzeilen", root_file.source_rows);
+    output::detail("Source Hash", &root_file.source_hash);
+    output::detail("Normalisierung", &root_file.normalization);
+
+    Ok(())
+}
+
+/// Lists sanctions-root - Erzeugt Root-Datei aus Sanctions-CSV
+pub fn run_lists_sanctions_root(csv_path: &str, out: Option<String>) -> Result<(), Box<dyn Error>> {
+    let out_path = out.unwrap_or_else(|| paths::SANCTIONS_ROOT.to_string());
+    run_lists_root(ListKind::Sanctions, csv_path, &out_path)
+}
+
+/// Lists jurisdictions-root - Erzeugt Root-Datei aus Jurisdictions-CSV
+pub fn run_lists_jurisdictions_root(
+    csv_path: &str,
+    out: Option<String>,
+) -> Result<(), Box<dyn Error>> {
+    let out_path = out.unwrap_or_else(|| paths::JURISDICTIONS_ROOT.to_string());
+    run_lists_root(ListKind::Jurisdictions, csv_path, &out_path)
+}
@@ -5,9 +5,11 @@
 use super::output;
 use crate::audit::AuditLog;
 use crate::commitment;
+use crate::lists::{self, ListKind, ListRootFile};
 use crate::manifest;
 use crate::policy;
-use crate::registry;
+use crate::policy_v2;
+use crate::verifier;
 use crate::verifier::core as verifier_core;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
@@ -30,73 +32,39 @@ pub struct VerificationReport {
 // Helper-Funktionen für run_manifest_verify
 // ============================================================================
 
-/// Verifiziert Timestamp aus Datei (optional)
-fn verify_timestamp_if_provided(timestamp_path: Option<&str>) -> bool {
-    match timestamp_path {
-        Some(ts_path) => {
-            let valid = registry::verify_timestamp_from_file(ts_path);
-            println!(
-                "      Timestamp: {}",
-                if valid {
-                    "✅ gültig"
-                } else {
-                    "❌ ungültig"
-                }
-            );
-            valid
-        }
-        None => {
-            println!("      Timestamp: ⚠️  nicht angegeben (optional)");
-            true
-        }
+/// Lädt eine optionale JSON-Datei (Timestamp-Token, Anchor-Proof, Header-Store) für die Verification Engine
+fn load_json_input(path: Option<&str>) -> Result<Option<serde_json::Value>, Box<dyn Error>> {
+    match path {
+        Some(path) => Ok(Some(serde_json::from_str(&fs::read_to_string(path)?)?)),
+        None => Ok(None),
     }
 }
 
-/// Verifiziert Registry-Eintrag
-fn verify_registry_entry(
+/// Registry-Quelle für den Registry-Check der Verification Engine
+fn registry_source(
     registry_path: &str,
-    manifest_hash: &str,
-    proof_hash: &str,
-) -> bool {
-    let registry_match = registry::verify_entry_from_file(
-        registry_path,
-        manifest_hash,
-        proof_hash,
-    )
-    .unwrap_or(false);
-
-    println!(
-        "      Registry: {}",
-        if registry_match {
-            "✅ Eintrag gefunden"
-        } else {
-            "❌ Kein Eintrag"
-        }
-    );
-
-    registry_match
+    backend: &str,
+) -> Result<verifier::RegistrySource, Box<dyn Error>> {
+    let path = registry_path.to_string();
+    match backend {
+        "json" => Ok(verifier::RegistrySource::Json { path }),
+        "sqlite" => Ok(verifier::RegistrySource::Sqlite { path }),
+        other => Err(format!("Unbekanntes Backend: {}", other).into()),
+    }
 }
 
 /// Erstellt VerificationReport und speichert ihn
 fn create_and_save_report(
     core_report: &verifier_core::VerifyReport,
-    timestamp_valid: bool,
-    registry_match: bool,
     out_path: Option<String>,
 ) -> Result<(VerificationReport, String), Box<dyn Error>> {
-    let all_ok = core_report.signature_valid
-        && timestamp_valid
-        && registry_match
-        && core_report.status == "ok";
-    let status = if all_ok { "ok" } else { "fail" }.to_string();
-
     let report = VerificationReport {
         manifest_hash: core_report.manifest_hash.clone(),
         proof_hash: core_report.proof_hash.clone(),
-        timestamp_valid,
-        registry_match,
+        timestamp_valid: core_report.timestamp_valid.unwrap_or(true),
+        registry_match: core_report.registry_match.unwrap_or(false),
         signature_valid: core_report.signature_valid,
-        status,
+        status: core_report.status.clone(),
     };
 
     let report_path = out_path.unwrap_or_else(|| "build/verification.report.json".to_string());
@@ -140,24 +108,45 @@ fn log_verification_audit(
 pub fn run_manifest_build(
     policy_path: &str,
     out: Option<String>,
+    sanctions_list: Option<String>,
+    jurisdiction_list: Option<String>,
+    sanctions_root: Option<String>,
+    jurisdiction_root: Option<String>,
 ) -> Result<(), Box<dyn Error>> {
     output::writing("Erstelle Manifest...");
 
     fs::create_dir_all("build")?;
     let mut audit = AuditLog::new("build/agent.audit.jsonl")?;
 
-    // Lade Policy
-    let policy = policy::Policy::load(policy_path)?;
-    policy.validate()?;
-    let policy_info = policy.to_info()?;
+    // Lade Policy (v1-YAML oder kompilierte v2-IR)
+    let policy_info = load_policy_info(policy_path)?;
+
+    // Abgelaufene oder noch nicht gültige Policies werden abgelehnt
+    policy_info
+        .validity()?
+        .check_today()
+        .map_err(|e| format!("Policy {} {}", policy_info.name, e))?;
 
     // Lade Commitments
     let commitments = commitment::load_commitments("build/commitments.json")?;
 
     // Erstelle Manifest
-    let manifest =
+    let policy_info_name = policy_info.name.clone();
+    let mut manifest =
         manifest::Manifest::build(&commitments, policy_info, "build/agent.audit.jsonl")?;
 
+    // Optionale Listen-Roots (Sanktionen / Jurisdiktionen)
+    manifest.sanctions_root = resolve_list_root(
+        ListKind::Sanctions,
+        sanctions_list.as_deref(),
+        sanctions_root.as_deref(),
+    )?;
+    manifest.jurisdiction_root = resolve_list_root(
+        ListKind::Jurisdictions,
+        jurisdiction_list.as_deref(),
+        jurisdiction_root.as_deref(),
+    )?;
+
     // Speichere Manifest
     let output_path = out.unwrap_or_else(|| "build/manifest.json".to_string());
     manifest.save(&output_path)?;
@@ -166,7 +155,9 @@ pub fn run_manifest_build(
         "manifest_built",
         json!({
             "output": &output_path,
-            "policy": &policy.name
+            "policy": &policy_info_name,
+            "sanctions_root": &manifest.sanctions_root,
+            "jurisdiction_root": &manifest.jurisdiction_root
         }),
     )?;
 
@@ -175,6 +166,56 @@ pub fn run_manifest_build(
     Ok(())
 }
 
+/// Lädt die Policy-Info aus einer v1-Policy oder einer kompilierten v2-IR
+///
+/// Bei einer IR wird der Policy-Hash der IR übernommen, damit
+/// `proof build` mit derselben IR gegen das Manifest prüfen kann.
+fn load_policy_info(policy_path: &str) -> Result<policy::PolicyInfo, Box<dyn Error>> {
+    if policy_v2::is_ir_file(policy_path) {
+        let ir = policy_v2::load_ir(policy_path)?;
+        return Ok(policy::PolicyInfo {
+            name: ir.policy_id.clone(),
+            version: ir.policy_id.clone(),
+            hash: ir.policy_hash_hex(),
+            valid_from: ir.valid_from.clone(),
+            valid_until: ir.valid_until.clone(),
+        });
+    }
+
+    let policy = policy::Policy::load(policy_path)?;
+    policy.validate()?;
+    policy.to_info()
+}
+
+/// Ermittelt einen Listen-Root aus Listen-CSV oder Root-Datei
+fn resolve_list_root(
+    kind: ListKind,
+    list_csv: Option<&str>,
+    root_file: Option<&str>,
+) -> Result<Option<String>, Box<dyn Error>> {
+    let flag = kind.statement_root_key().trim_end_matches("_root");
+    match (list_csv, root_file) {
+        (Some(_), Some(_)) => Err(format!(
+            "Bitte entweder --{0}-list oder --{0}-root angeben, nicht beides",
+            flag
+        )
+        .into()),
+        (Some(path), None) => {
+            let tree = lists::load_list_tree(path, kind)?;
+            output::detail_fmt(&format!("{}-Einträge", kind), tree.len());
+            Ok(Some(tree.root_hex()))
+        }
+        (None, Some(path)) => {
+            let root_file = ListRootFile::load(path)?;
+            let root = root_file.root_for(kind)?.to_string();
+            output::detail_fmt(&format!("{}-Einträge", kind), root_file.entry_count);
+            output::detail(&format!("{}-Quelle", kind), &root_file.source_hash);
+            Ok(Some(root))
+        }
+        (None, None) => Ok(None),
+    }
+}
+
 /// Manifest validate - Validiert ein Manifest gegen das JSON Schema
 pub fn run_manifest_validate(
     manifest_path: &str,
@@ -232,11 +273,17 @@ pub fn run_manifest_validate(
 }
 
 /// Manifest verify - Führt vollständige Offline-Verifikation eines Proof-Pakets durch
+#[allow(clippy::too_many_arguments)]
 pub fn run_manifest_verify(
     manifest_path: &str,
     proof_path: &str,
     registry_path: &str,
+    registry_backend: &str,
+    keys_dir: Option<&str>,
     timestamp_path: Option<String>,
+    tsa_cert_path: Option<&str>,
+    anchor_proof_path: Option<&str>,
+    anchor_headers_path: Option<&str>,
     out_path: Option<String>,
 ) -> Result<(), Box<dyn Error>> {
     output::searching("Starte vollständige Offline-Verifikation (mit portable core)...");
@@ -253,41 +300,78 @@ pub fn run_manifest_verify(
     let stmt = verifier_core::extract_statement_from_manifest(&manifest_json)?;
     output::indent("   ✅ Statement extrahiert");
     output::indent(&format!("      Policy Hash: {}", stmt.policy_hash));
-    output::indent(&format!("      Company Root: {}", stmt.company_commitment_root));
+    output::indent(&format!(
+        "      Company Root: {}",
+        stmt.company_commitment_root
+    ));
+
+    // 3️⃣ Timestamp, anchor proof and registry inputs
+    output::step(3, 5, "Lade Timestamp, Anchor-Proof und Registry-Quelle");
+    let timestamp = load_json_input(timestamp_path.as_deref())?;
+    let tsa_certificate = tsa_cert_path.map(fs::read).transpose()?;
+    let anchor_proof = load_json_input(anchor_proof_path)?;
+    let anchor_headers = load_json_input(anchor_headers_path)?;
+    let registry = registry_source(registry_path, registry_backend)?;
+    output::indent(&format!(
+        "      Timestamp: {}",
+        if timestamp.is_some() {
+            "geladen"
+        } else {
+            "nicht angegeben (optional)"
+        }
+    ));
+    output::indent(&format!(
+        "      Anchor-Proof: {}",
+        match (&anchor_proof, &anchor_headers) {
+            (Some(_), Some(_)) => "geladen (mit gepinnten Headern)",
+            (Some(_), None) => "geladen (ohne gepinnte Header)",
+            _ => "nicht angegeben (optional)",
+        }
+    ));
+    output::indent(&format!(
+        "      Registry: {} ({}){}",
+        registry_path,
+        registry_backend,
+        if keys_dir.is_some() {
+            ", Key-Status wird geprüft"
+        } else {
+            ""
+        }
+    ));
 
-    // 3️⃣ Core verification
-    output::step(3, 5, "Führe Verifikation durch");
+    // 4️⃣ Verification engine
+    output::step(4, 5, "Führe Verifikation durch");
     let opts = verifier_core::VerifyOptions {
-        check_timestamp: timestamp_path.is_some(),
+        check_timestamp: timestamp_path.is_some() || anchor_proof.is_some(),
         check_registry: true,
+        registry: Some(registry),
+        key_store: keys_dir.map(str::to_string),
     };
-    let core_report = verifier_core::verify(&manifest_json, &proof_bytes, &stmt, &opts)?;
-
-    output::indent("   ✅ Core Verifikation abgeschlossen");
-    output::indent(&format!("      Manifest Hash: {}", core_report.manifest_hash));
-    output::indent(&format!("      Proof Hash: {}", core_report.proof_hash));
+    let input =
+        verifier::VerifyInput::new(&manifest_json, &manifest_bytes, &proof_bytes, &stmt, &opts)
+            .with_timestamp(timestamp.as_ref())
+            .with_tsa_certificate(tsa_certificate.as_deref())
+            .with_anchor_proof(anchor_proof.as_ref(), anchor_headers.as_ref());
+    let core_report = verifier_core::verify_input(&input);
+
+    output::indent("   ✅ Verifikation abgeschlossen");
     output::indent(&format!(
-        "      Signatur: {}",
-        if core_report.signature_valid { "✅" } else { "⚠️" }
+        "      Manifest Hash: {}",
+        core_report.manifest_hash
     ));
-
-    // 4️⃣ Additional checks (timestamp and registry)
-    output::step(4, 5, "Zusätzliche Prüfungen");
-    let timestamp_valid = verify_timestamp_if_provided(timestamp_path.as_deref());
-    let registry_match = verify_registry_entry(
-        registry_path,
-        &core_report.manifest_hash,
-        &core_report.proof_hash,
-    );
+    output::indent(&format!("      Proof Hash: {}", core_report.proof_hash));
+    for check in &core_report.checks {
+        output::indent(&format!(
+            "      {} {}: {}",
+            if check.status.is_pass() { "✅" } else { "❌" },
+            check.id,
+            check.message
+        ));
+    }
 
     // 5️⃣ Create and save report
     output::step(5, 5, "Speichere Report");
-    let (report, report_path) = create_and_save_report(
-        &core_report,
-        timestamp_valid,
-        registry_match,
-        out_path,
-    )?;
+    let (report, report_path) = create_and_save_report(&core_report, out_path)?;
 
     // 6️⃣ Log audit event
     log_verification_audit(
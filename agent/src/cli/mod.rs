@@ -6,9 +6,12 @@
 pub mod audit;
 pub mod blob;
 pub mod bundle;
+pub mod inclusion;
 pub mod keys;
+pub mod lists;
 pub mod manifest;
 pub mod output;
+pub mod paths;
 pub mod policy;
 pub mod prepare;
 pub mod proof;
@@ -52,6 +55,9 @@ pub enum Commands {
         /// Pfad zur commitments.json Datei
         path: String,
     },
+    /// Inclusion-Proofs für einzelne Supplier/UBOs (Merkle v1)
+    #[command(subcommand)]
+    Inclusion(InclusionCommands),
     /// Policy-Commands
     #[command(subcommand)]
     Policy(PolicyCommands),
@@ -116,6 +122,55 @@ pub enum Commands {
     Version,
 }
 
+#[derive(Subcommand)]
+pub enum InclusionCommands {
+    /// Erzeugt einen Inclusion-Proof für einen einzelnen Supplier oder UBO
+    Prove {
+        /// Datensatz-Typ (supplier|ubo)
+        #[arg(long)]
+        kind: String,
+
+        /// Pfad zur Suppliers- bzw. UBOs-CSV (identisch zur prepare-Eingabe)
+        #[arg(long)]
+        csv: String,
+
+        /// Name des Datensatzes (exakte Übereinstimmung)
+        #[arg(long)]
+        name: Option<String>,
+
+        /// Position des Datensatzes in der CSV (0-basiert)
+        #[arg(long)]
+        index: Option<usize>,
+
+        /// Commitments-Datei (default: build/commitments.json); die privaten
+        /// Nonces werden aus commitment_nonces.json daneben gelesen
+        #[arg(long)]
+        commitments: Option<String>,
+
+        /// Offenzulegende Felder, kommagetrennt (übrige Felder bleiben verdeckt)
+        #[arg(long, value_delimiter = ',', default_value = "name")]
+        reveal: Vec<String>,
+
+        /// Output-Pfad (default: build/inclusion.<kind>.json)
+        #[arg(long)]
+        out: Option<String>,
+    },
+    /// Verifiziert einen Inclusion-Proof offline gegen Manifest oder Root
+    Verify {
+        /// Pfad zum Inclusion-Proof
+        #[arg(long)]
+        proof: String,
+
+        /// Manifest mit supplier_root/ubo_root
+        #[arg(long)]
+        manifest: Option<String>,
+
+        /// Erwarteter Root (0x-Hex, Alternative zu --manifest)
+        #[arg(long)]
+        root: Option<String>,
+    },
+}
+
 #[derive(Subcommand)]
 pub enum PolicyCommands {
     /// Validiert eine Policy-Datei (Legacy)
@@ -151,13 +206,29 @@ pub enum PolicyCommands {
 pub enum ManifestCommands {
     /// Erstellt ein Manifest aus Commitments und Policy
     Build {
-        /// Pfad zur Policy-Datei
+        /// Pfad zur Policy-Datei (v1-YAML oder kompilierte v2-IR)
         #[arg(long)]
         policy: String,
 
         /// Optional: Output-Pfad (default: build/manifest.json)
         #[arg(long)]
         out: Option<String>,
+
+        /// Optional: Sanktionsliste (CSV, Spalte name) → sanctions_root
+        #[arg(long)]
+        sanctions_list: Option<String>,
+
+        /// Optional: Jurisdiktions-Blocklist (CSV, Spalte iso_code) → jurisdiction_root
+        #[arg(long)]
+        jurisdiction_list: Option<String>,
+
+        /// Optional: Root-Datei aus `lists sanctions-root` → sanctions_root
+        #[arg(long)]
+        sanctions_root: Option<String>,
+
+        /// Optional: Root-Datei aus `lists jurisdictions-root` → jurisdiction_root
+        #[arg(long)]
+        jurisdiction_root: Option<String>,
     },
     /// Validiert ein Manifest gegen das JSON Schema
     Validate {
@@ -183,10 +254,30 @@ pub enum ManifestCommands {
         #[arg(long)]
         registry: String,
 
+        /// Registry-Backend (json|sqlite, default: json)
+        #[arg(long, default_value = "json")]
+        registry_backend: String,
+
+        /// Optional: Keys-Verzeichnis; prüft Signatur-Key (kid) des Registry-Eintrags
+        #[arg(long)]
+        keys_dir: Option<String>,
+
         /// Optional: Pfad zur Timestamp-Datei
         #[arg(long)]
         timestamp: Option<String>,
 
+        /// Optional: TSA-Zertifikat (PEM/DER) für RFC 3161 Timestamps
+        #[arg(long)]
+        tsa_cert: Option<String>,
+
+        /// Optional: Inclusion-Proof für den Public Anchor (anchor.proof.v1)
+        #[arg(long)]
+        anchor_proof: Option<String>,
+
+        /// Optional: Gepinnte Block-Header (anchor.headers.v1) für --anchor-proof
+        #[arg(long)]
+        anchor_headers: Option<String>,
+
         /// Optional: Output-Pfad für Verification Report (default: build/verification.report.json)
         #[arg(long)]
         out: Option<String>,
@@ -207,13 +298,29 @@ pub enum ProofCommands {
     },
     /// Erstellt einen strukturierten Proof (Tag 3)
     Build {
-        /// Pfad zur Policy-Datei
+        /// Pfad zur Policy-Datei (v1-YAML oder kompilierte v2-IR)
         #[arg(long)]
         policy: String,
 
         /// Pfad zum Manifest
         #[arg(long)]
         manifest: String,
+
+        /// Supplier-CSV (für Listen-Ausschlüsse, muss zu supplier_root passen)
+        #[arg(long)]
+        suppliers: Option<String>,
+
+        /// UBO-CSV (für Sanktions-Ausschluss, muss zu ubo_root passen)
+        #[arg(long)]
+        ubos: Option<String>,
+
+        /// Sanktionsliste (CSV) – erforderlich, wenn das Manifest sanctions_root enthält
+        #[arg(long)]
+        sanctions_list: Option<String>,
+
+        /// Jurisdiktions-Blocklist (CSV) – erforderlich, wenn das Manifest jurisdiction_root enthält
+        #[arg(long)]
+        jurisdiction_list: Option<String>,
     },
     /// Verifiziert einen Proof gegen Manifest
     Verify {
@@ -224,6 +331,10 @@ pub enum ProofCommands {
         /// Pfad zum Manifest
         #[arg(long)]
         manifest: String,
+
+        /// Private Witness-Datei der Listen-Ausschlüsse (z.B. build/list_witnesses.json)
+        #[arg(long)]
+        list_witnesses: Option<String>,
     },
     /// Exportiert ein standardisiertes CAP Proof-Paket
     Export {
@@ -445,9 +556,13 @@ pub enum AuditCommands {
         #[arg(long, default_value = "true")]
         mock: bool,
 
-        /// Optionale TSA-URL (für echten Timestamp)
+        /// Optionale TSA-URL (echter RFC 3161 Timestamp, hat Vorrang vor --mock)
         #[arg(long)]
         tsa_url: Option<String>,
+
+        /// Optional: TSA-Zertifikat (PEM/DER), gegen das die Antwort geprüft wird
+        #[arg(long)]
+        tsa_cert: Option<String>,
     },
     /// Verifiziert einen Timestamp gegen Audit-Head
     VerifyTimestamp {
@@ -458,6 +573,10 @@ pub enum AuditCommands {
         /// Pfad zur Timestamp-Datei
         #[arg(long)]
         timestamp: String,
+
+        /// TSA-Zertifikat (PEM/DER), erforderlich für RFC 3161 Timestamps
+        #[arg(long)]
+        tsa_cert: Option<String>,
     },
     /// Setzt Private Anchor (Dual-Anchor v0.9.0)
     SetPrivateAnchor {
@@ -495,12 +614,46 @@ pub enum AuditCommands {
         #[arg(long)]
         created_at: Option<String>,
     },
-    /// Verifiziert Dual-Anchor-Konsistenz
+    /// Batch-Anchoring: Merkle-Root über viele Manifeste, ein Public Anchor für alle (vor manifest sign)
+    AnchorBatch {
+        /// Manifest-Pfade (mehrfach angeben; Reihenfolge = Blatt-Index)
+        #[arg(long = "manifest", required = true)]
+        manifests: Vec<String>,
+
+        /// Blatt-Art: audit-tip (time_anchor.audit_tip_hex) oder manifest (Manifest-Hash)
+        #[arg(long, default_value = "audit-tip")]
+        leaf: String,
+
+        /// Blockchain der verankerten Root (ethereum, hedera, btc); ohne --chain/--txid wird nur die Root berechnet
+        #[arg(long, requires = "txid")]
+        chain: Option<String>,
+
+        /// Transaction ID der Root-Verankerung
+        #[arg(long, requires = "chain")]
+        txid: Option<String>,
+
+        /// Created-at Timestamp (RFC3339, optional, default: jetzt)
+        #[arg(long)]
+        created_at: Option<String>,
+
+        /// Output Batch-Datei (Root, Blätter, Verankerung; optional)
+        #[arg(long)]
+        out: Option<String>,
+    },
+    /// Verifiziert Dual-Anchor-Konsistenz und optional den Public Anchor on-chain (offline)
     VerifyAnchor {
         /// Manifest-Pfad
         #[arg(long)]
         manifest: String,
 
+        /// Inclusion-Proof für den Public Anchor (anchor.proof.v1, z.B. BTC SPV oder OTS)
+        #[arg(long)]
+        proof: Option<String>,
+
+        /// Gepinnte Block-Header (anchor.headers.v1), gegen die der Proof geprüft wird
+        #[arg(long)]
+        headers: Option<String>,
+
         /// Output JSON-Report (optional)
         #[arg(long)]
         out: Option<String>,
@@ -535,7 +688,109 @@ pub enum AuditCommands {
         #[arg(long)]
         run_id: Option<String>,
     },
-    /// Verifiziert Audit-Chain-Integrität (Track A)
+    /// Signiert einen Checkpoint (Größe + Tail-Hash) über ein Audit-Log (v1 oder v2)
+    Checkpoint {
+        /// Pfad zum Audit-Log (default: build/audit_chain.jsonl)
+        #[arg(long, default_value = "build/audit_chain.jsonl")]
+        file: String,
+
+        /// Checkpoint-Datei (default: <log>.checkpoints.jsonl neben dem Log)
+        #[arg(long)]
+        out: Option<String>,
+
+        /// Schlüsselverzeichnis des Software-Providers
+        #[arg(long, default_value = "keys")]
+        keys_dir: String,
+
+        /// Schlüsselname im Schlüsselverzeichnis (z.B. company für keys/company.json)
+        #[arg(long)]
+        key: Option<String>,
+
+        /// KID des Signaturschlüssels (default: aktueller Schlüssel des Providers)
+        #[arg(long)]
+        kid: Option<String>,
+
+        /// KeyProvider-Konfiguration (YAML; software, pkcs11, cloudkms)
+        #[arg(long, conflicts_with_all = ["keys_dir", "key"])]
+        provider_config: Option<String>,
+    },
+    /// Migriert ein v1.0-Audit-Log (seq/digest) verlustfrei in das strukturierte Format
+    Migrate {
+        /// Pfad zum v1.0-Audit-Log
+        #[arg(long)]
+        file: String,
+
+        /// Ziel-Datei (default: in place, Original bleibt als <name>.v1.jsonl erhalten)
+        #[arg(long)]
+        out: Option<String>,
+    },
+    /// Rotiert das Audit-Log: versiegelt das aktive Segment (komprimiertes Archiv + Siegel)
+    Rotate {
+        /// Pfad zum Audit-Log (default: build/audit_chain.jsonl)
+        #[arg(long, default_value = "build/audit_chain.jsonl")]
+        file: String,
+
+        /// Nur rotieren, wenn das Segment mindestens so groß ist (Bytes)
+        #[arg(long)]
+        max_bytes: Option<u64>,
+
+        /// Nur rotieren, wenn der Zeitraum des ersten Events abgelaufen ist (daily, monthly, yearly)
+        #[arg(long)]
+        period: Option<String>,
+
+        /// Siegel signieren (Software-Provider aus --keys-dir/--key)
+        #[arg(long)]
+        sign: bool,
+
+        /// Schlüsselverzeichnis des Software-Providers
+        #[arg(long, default_value = "keys")]
+        keys_dir: String,
+
+        /// Schlüsselname im Schlüsselverzeichnis (z.B. company für keys/company.json)
+        #[arg(long)]
+        key: Option<String>,
+
+        /// KID des Signaturschlüssels (default: aktueller Schlüssel des Providers)
+        #[arg(long)]
+        kid: Option<String>,
+
+        /// KeyProvider-Konfiguration (YAML; impliziert --sign)
+        #[arg(long, conflicts_with_all = ["keys_dir", "key"])]
+        provider_config: Option<String>,
+    },
+    /// Erzeugt einen Inclusion-Proof für ein einzelnes Audit-Event gegen den letzten Checkpoint
+    Prove {
+        /// Pfad zum Audit-Log (default: build/audit_chain.jsonl)
+        #[arg(long, default_value = "build/audit_chain.jsonl")]
+        file: String,
+
+        /// Nummer des Events (1-basiert; bei v1-Logs = seq)
+        #[arg(long)]
+        event: u64,
+
+        /// Checkpoint-Datei (default: <log>.checkpoints.jsonl neben dem Log)
+        #[arg(long)]
+        checkpoints: Option<String>,
+
+        /// Output-Datei (default: <log>.<event>.inclusion.json)
+        #[arg(long)]
+        out: Option<String>,
+    },
+    /// Verifiziert einen Event-Inclusion-Proof ohne Zugriff auf das Audit-Log
+    VerifyProof {
+        /// Proof-Datei (audit.event_proof.v1)
+        #[arg(long)]
+        proof: String,
+
+        /// Nur Checkpoints von Schlüsseln aus diesem Verzeichnis akzeptieren
+        #[arg(long)]
+        keys_dir: Option<String>,
+
+        /// Nur Checkpoints von Schlüsseln dieses KeyProviders akzeptieren (YAML)
+        #[arg(long, conflicts_with = "keys_dir")]
+        provider_config: Option<String>,
+    },
+    /// Verifiziert Audit-Chain-Integrität (Track A), versiegelte Segmente und signierte Checkpoints
     Verify {
         /// Pfad zur Audit-Chain-Datei (default: build/audit_chain.jsonl)
         #[arg(long, default_value = "build/audit_chain.jsonl")]
@@ -544,6 +799,18 @@ pub enum AuditCommands {
         /// Output JSON-Report (optional)
         #[arg(long)]
         out: Option<String>,
+
+        /// Checkpoint-Datei (default: <log>.checkpoints.jsonl neben dem Log)
+        #[arg(long)]
+        checkpoints: Option<String>,
+
+        /// Nur Checkpoints von Schlüsseln aus diesem Verzeichnis akzeptieren
+        #[arg(long)]
+        keys_dir: Option<String>,
+
+        /// Nur Checkpoints von Schlüsseln dieses KeyProviders akzeptieren (YAML)
+        #[arg(long, conflicts_with = "keys_dir")]
+        provider_config: Option<String>,
     },
     /// Exportiert Events aus Audit-Chain (Track A)
     Export {
@@ -563,10 +830,84 @@ pub enum AuditCommands {
         #[arg(long)]
         policy_id: Option<String>,
 
+        /// Event-Typ Filter (mehrfach angebbar, optional)
+        #[arg(long = "event")]
+        events: Vec<String>,
+
+        /// Run ID Filter (optional)
+        #[arg(long)]
+        run_id: Option<String>,
+
+        /// Manifest-Hash Filter (optional)
+        #[arg(long)]
+        manifest_hash: Option<String>,
+
+        /// Ergebnis-Filter (OK, WARN, FAIL; optional)
+        #[arg(long)]
+        result: Option<String>,
+
+        /// Ausgabeformat (json, csv)
+        #[arg(long, default_value = "json")]
+        format: String,
+
         /// Output-Datei (default: stdout)
         #[arg(long)]
         out: Option<String>,
     },
+    /// Erstellt einen druckbaren Audit-Bericht (Integrität, Anker, Checkpoints, Events je Zeitraum)
+    Report {
+        /// Pfad zum Audit-Log (default: build/audit_chain.jsonl)
+        #[arg(long, default_value = "build/audit_chain.jsonl")]
+        file: String,
+
+        /// Start des Berichtszeitraums (RFC3339 oder YYYY-MM-DD, optional)
+        #[arg(long)]
+        from: Option<String>,
+
+        /// Ende des Berichtszeitraums (RFC3339 oder YYYY-MM-DD, optional)
+        #[arg(long)]
+        to: Option<String>,
+
+        /// Event-Typ Filter für die Statistik (mehrfach angebbar, optional)
+        #[arg(long = "event")]
+        events: Vec<String>,
+
+        /// Policy ID Filter für die Statistik (optional)
+        #[arg(long)]
+        policy_id: Option<String>,
+
+        /// Gruppierung der Events (monthly, quarterly, yearly)
+        #[arg(long, default_value = "quarterly")]
+        period: String,
+
+        /// Manifest, dessen Zeitanker aufgeführt wird (mehrfach angebbar)
+        #[arg(long = "manifest")]
+        manifests: Vec<String>,
+
+        /// Checkpoint-Datei (default: <log>.checkpoints.jsonl neben dem Log)
+        #[arg(long)]
+        checkpoints: Option<String>,
+
+        /// Nur Checkpoints/Siegel von Schlüsseln aus diesem Verzeichnis akzeptieren
+        #[arg(long)]
+        keys_dir: Option<String>,
+
+        /// Nur Checkpoints/Siegel von Schlüsseln dieses KeyProviders akzeptieren (YAML)
+        #[arg(long, conflicts_with = "keys_dir")]
+        provider_config: Option<String>,
+
+        /// Ausgabeformat (markdown, html, json)
+        #[arg(long, default_value = "markdown")]
+        format: String,
+
+        /// Titel des Berichts (default: Audit-Bericht)
+        #[arg(long)]
+        title: Option<String>,
+
+        /// Output-Datei (default: build/audit_report.<md|html|json>)
+        #[arg(long)]
+        out: Option<String>,
+    },
 }
 
 #[derive(Subcommand)]
@@ -629,7 +970,7 @@ pub enum RegistryCommands {
         #[arg(long, default_value = "keys")]
         keys_dir: String,
     },
-    /// Listet alle Registry-Einträge auf
+    /// Listet Registry-Einträge auf (optional gefiltert, sortiert und paginiert)
     List {
         /// Registry-Datei (default: build/registry.json oder build/registry.sqlite)
         #[arg(long)]
@@ -638,6 +979,30 @@ pub enum RegistryCommands {
         /// Registry-Backend (json|sqlite, default: json)
         #[arg(long, default_value = "json")]
         backend: String,
+
+        /// Filter key=value (mehrfach): kid, from, to, selfverify, policy, scheme, blob
+        #[arg(long)]
+        filter: Vec<String>,
+
+        /// Sortierfeld (registered_at|id)
+        #[arg(long, default_value = "registered_at")]
+        sort: String,
+
+        /// Sortierrichtung (asc|desc)
+        #[arg(long, default_value = "asc")]
+        order: String,
+
+        /// Maximale Anzahl Einträge
+        #[arg(long)]
+        limit: Option<usize>,
+
+        /// Anzahl übersprungener Einträge
+        #[arg(long, default_value_t = 0)]
+        offset: usize,
+
+        /// Ausgabe als JSON (Seite mit total/offset/limit/entries)
+        #[arg(long)]
+        json: bool,
     },
     /// Verifiziert einen Proof gegen die Registry
     Verify {
@@ -656,6 +1021,178 @@ pub enum RegistryCommands {
         /// Registry-Backend (json|sqlite, default: json)
         #[arg(long, default_value = "json")]
         backend: String,
+
+        /// Früherer Signed Tree Head: prüft, dass die Registry ihn erweitert
+        #[arg(long)]
+        since: Option<String>,
+    },
+    /// Widerruft einen Registry-Eintrag (signierter Lifecycle-Record)
+    Revoke {
+        /// ID des Registry-Eintrags (z.B. proof_001)
+        #[arg(long)]
+        id: String,
+
+        /// Grund (data-corrected|key-compromise|issued-in-error|policy-changed|withdrawn)
+        #[arg(long)]
+        reason: String,
+
+        /// Wirksam ab (RFC3339, default: jetzt)
+        #[arg(long)]
+        effective_at: Option<String>,
+
+        /// Registry-Datei (default: build/registry.json oder build/registry.sqlite)
+        #[arg(long)]
+        registry: Option<String>,
+
+        /// Registry-Backend (json|sqlite, default: json)
+        #[arg(long, default_value = "json")]
+        backend: String,
+
+        /// Signing-Key für den Record (Ed25519, default: keys/company.ed25519)
+        #[arg(long, default_value = "keys/company.ed25519")]
+        signing_key: String,
+    },
+    /// Ersetzt einen Registry-Eintrag durch einen Nachfolger (signierter Lifecycle-Record)
+    Supersede {
+        /// ID des ersetzten Registry-Eintrags
+        #[arg(long)]
+        id: String,
+
+        /// ID des Nachfolger-Eintrags
+        #[arg(long)]
+        successor: String,
+
+        /// Grund (default: data-corrected)
+        #[arg(long, default_value = "data-corrected")]
+        reason: String,
+
+        /// Wirksam ab (RFC3339, default: jetzt)
+        #[arg(long)]
+        effective_at: Option<String>,
+
+        /// Registry-Datei (default: build/registry.json oder build/registry.sqlite)
+        #[arg(long)]
+        registry: Option<String>,
+
+        /// Registry-Backend (json|sqlite, default: json)
+        #[arg(long, default_value = "json")]
+        backend: String,
+
+        /// Signing-Key für den Record (Ed25519, default: keys/company.ed25519)
+        #[arg(long, default_value = "keys/company.ed25519")]
+        signing_key: String,
+    },
+    /// Signiert den aktuellen Tree Head des Transparency-Logs
+    LogHead {
+        /// Registry-Datei (default: build/registry.json oder build/registry.sqlite)
+        #[arg(long)]
+        registry: Option<String>,
+
+        /// Registry-Backend (json|sqlite, default: json)
+        #[arg(long, default_value = "json")]
+        backend: String,
+
+        /// Signing-Key für den Tree Head (Ed25519, default: keys/company.ed25519)
+        #[arg(long, default_value = "keys/company.ed25519")]
+        signing_key: String,
+
+        /// Output-Datei (default: build/registry.sth.json)
+        #[arg(long)]
+        out: Option<String>,
+    },
+    /// Erzeugt den Inclusion-Beweis eines Eintrags im Transparency-Log
+    LogProof {
+        /// ID des Registry-Eintrags (z.B. proof_001)
+        #[arg(long)]
+        id: String,
+
+        /// Registry-Datei (default: build/registry.json oder build/registry.sqlite)
+        #[arg(long)]
+        registry: Option<String>,
+
+        /// Registry-Backend (json|sqlite, default: json)
+        #[arg(long, default_value = "json")]
+        backend: String,
+
+        /// Output-Datei (default: build/registry.<id>.inclusion.json)
+        #[arg(long)]
+        out: Option<String>,
+    },
+    /// Erzeugt den Consistency-Beweis von einem früheren Tree Head zum aktuellen Log
+    LogConsistency {
+        /// Früherer Signed Tree Head
+        #[arg(long)]
+        old: String,
+
+        /// Registry-Datei (default: build/registry.json oder build/registry.sqlite)
+        #[arg(long)]
+        registry: Option<String>,
+
+        /// Registry-Backend (json|sqlite, default: json)
+        #[arg(long, default_value = "json")]
+        backend: String,
+
+        /// Output-Datei (default: build/registry.consistency.json)
+        #[arg(long)]
+        out: Option<String>,
+    },
+    /// Exportiert die Registry als signierten Snapshot
+    Export {
+        /// Registry-Datei (default: build/registry.json oder build/registry.sqlite)
+        #[arg(long)]
+        registry: Option<String>,
+
+        /// Registry-Backend (json|sqlite, default: json)
+        #[arg(long, default_value = "json")]
+        backend: String,
+
+        /// Signing-Key für den Snapshot (Ed25519, default: keys/company.ed25519)
+        #[arg(long, default_value = "keys/company.ed25519")]
+        signing_key: String,
+
+        /// Herkunft des Snapshots (z.B. Team oder Standort)
+        #[arg(long)]
+        source: Option<String>,
+
+        /// Output-Datei (default: build/registry.snapshot.json)
+        #[arg(long)]
+        out: Option<String>,
+    },
+    /// Importiert einen signierten Snapshot in die lokale Registry
+    Import {
+        /// Snapshot-Datei (aus `registry export`)
+        #[arg(long)]
+        snapshot: String,
+
+        /// Registry-Datei (default: build/registry.json oder build/registry.sqlite)
+        #[arg(long)]
+        registry: Option<String>,
+
+        /// Registry-Backend (json|sqlite, default: json)
+        #[arg(long, default_value = "json")]
+        backend: String,
+
+        /// Optional: Keys-Verzeichnis; prüft den Signatur-Key (kid) des Snapshots
+        #[arg(long)]
+        keys_dir: Option<String>,
+
+        /// Konfliktträchtige Einträge überspringen statt den Import abzubrechen
+        #[arg(long)]
+        skip_conflicts: bool,
+    },
+    /// Bringt eine SQLite-Registry auf die neueste Schema-Version
+    SchemaMigrate {
+        /// SQLite-Datei (default: build/registry.sqlite)
+        #[arg(long)]
+        registry: Option<String>,
+
+        /// Ausstehende Schritte nur prüfen (Transaktion wird zurückgerollt)
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Kein Backup vor der Migration anlegen
+        #[arg(long)]
+        no_backup: bool,
     },
     /// Migriert Registry zwischen Backends
     Migrate {
@@ -26,6 +26,12 @@ pub const REGISTRY_JSON: &str = "build/registry.json";
 /// Registry file (SQLite format)
 pub const REGISTRY_SQLITE: &str = "build/registry.sqlite";
 
+/// Signed tree head of the registry transparency log
+pub const REGISTRY_TREE_HEAD: &str = "build/registry.sth.json";
+
+/// Signed registry snapshot for exchange between registries
+pub const REGISTRY_SNAPSHOT: &str = "build/registry.snapshot.json";
+
 /// Sanctions list Merkle root
 pub const SANCTIONS_ROOT: &str = "build/sanctions.root";
 
@@ -48,8 +54,7 @@ pub const COMPANY_KEY: &str = "keys/company.ed25519";
 
 /// Returns the audit log path, allowing override via environment variable
 pub fn audit_log_path() -> String {
-    std::env::var("CAP_AUDIT_LOG")
-        .unwrap_or_else(|_| AUDIT_LOG.to_string())
+    std::env::var("CAP_AUDIT_LOG").unwrap_or_else(|_| AUDIT_LOG.to_string())
 }
 
 /// Returns the registry path based on backend type
@@ -63,14 +68,12 @@ pub fn registry_path(backend: &str) -> &'static str {
 
 /// Returns the commitments path, allowing override via environment variable
 pub fn commitments_path() -> String {
-    std::env::var("CAP_COMMITMENTS")
-        .unwrap_or_else(|_| COMMITMENTS.to_string())
+    std::env::var("CAP_COMMITMENTS").unwrap_or_else(|_| COMMITMENTS.to_string())
 }
 
 /// Returns the keys directory, allowing override via environment variable
 pub fn keys_dir() -> String {
-    std::env::var("CAP_KEYS_DIR")
-        .unwrap_or_else(|_| KEYS_DIR.to_string())
+    std::env::var("CAP_KEYS_DIR").unwrap_or_else(|_| KEYS_DIR.to_string())
 }
 
 #[cfg(test)]
@@ -5,9 +5,10 @@
 use super::output;
 use crate::audit::AuditLog;
 use crate::commitment::{
-    self, compute_company_root, compute_supplier_root, compute_ubo_root, Commitments,
+    self, compute_company_root, compute_supplier_root, compute_ubo_root, CommitmentScheme,
+    Commitments, RecordNonces,
 };
-use crate::io::{self, Supplier, Ubo};
+use crate::io::{self, JsonPersistent, Supplier, Ubo};
 use serde_json::json;
 use std::error::Error;
 use std::fs;
@@ -37,14 +38,15 @@ fn load_csv_data(
 fn compute_merkle_roots(
     suppliers: &[Supplier],
     ubos: &[Ubo],
+    nonces: &RecordNonces,
     audit: &mut AuditLog,
 ) -> Result<(String, String, String), Box<dyn Error>> {
     output::stats("Berechne Supplier-Root...");
-    let supplier_root = compute_supplier_root(suppliers)?;
+    let supplier_root = compute_supplier_root(suppliers, &nonces.suppliers)?;
     audit.log_event("merkle_root_computed", json!({"target": "suppliers", "root": &supplier_root}))?;
 
     output::stats("Berechne UBO-Root...");
-    let ubo_root = compute_ubo_root(ubos)?;
+    let ubo_root = compute_ubo_root(ubos, &nonces.ubos)?;
     audit.log_event("merkle_root_computed", json!({"target": "ubos", "root": &ubo_root}))?;
 
     output::stats("Berechne Company-Commitment-Root...");
@@ -55,7 +57,13 @@ fn compute_merkle_roots(
 }
 
 /// Zeigt Ergebnis-Zusammenfassung an
-fn print_results(supplier_root: &str, ubo_root: &str, company_root: &str, output_path: &str) {
+fn print_results(
+    supplier_root: &str,
+    ubo_root: &str,
+    company_root: &str,
+    output_path: &str,
+    nonces_path: &str,
+) {
     output::success("Erfolgreich abgeschlossen!");
     output::section("Ergebnisse:");
     output::indent(&format!("Supplier Root:  {}", supplier_root));
@@ -63,6 +71,7 @@ fn print_results(supplier_root: &str, ubo_root: &str, company_root: &str, output
     output::indent(&format!("Company Root:   {}", company_root));
     output::section("Ausgabedateien:");
     output::indent(&format!("- {}", output_path));
+    output::indent(&format!("- {} (privat, nicht weitergeben)", nonces_path));
     output::indent("- build/agent.audit.jsonl");
 }
 
@@ -81,8 +90,12 @@ pub fn run_prepare(suppliers_path: &str, ubos_path: &str) -> Result<(), Box<dyn
     // Lade Daten
     let (suppliers, ubos) = load_csv_data(suppliers_path, ubos_path, &mut audit)?;
 
+    // Private Blinding-Nonces je Datensatz (nie Teil von Proof oder Bundle)
+    let nonces = RecordNonces::generate(suppliers.len(), ubos.len());
+
     // Berechne Roots
-    let (supplier_root, ubo_root, company_root) = compute_merkle_roots(&suppliers, &ubos, &mut audit)?;
+    let (supplier_root, ubo_root, company_root) =
+        compute_merkle_roots(&suppliers, &ubos, &nonces, &mut audit)?;
 
     // Erstelle und speichere Commitments
     let commitments = Commitments {
@@ -91,14 +104,18 @@ pub fn run_prepare(suppliers_path: &str, ubos_path: &str) -> Result<(), Box<dyn
         company_commitment_root: company_root.clone(),
         supplier_count: Some(suppliers.len()),
         ubo_count: Some(ubos.len()),
+        commitment_scheme: Some(CommitmentScheme::CURRENT),
     };
 
     let output_path = "build/commitments.json";
     output::saving(&format!("Speichere Commitments nach: {}", output_path));
     commitment::save_commitments(&commitments, output_path)?;
-    audit.log_event("commitments_saved", json!({"path": output_path}))?;
+    let nonces_path = commitment::nonces_path(output_path);
+    nonces.save(&nonces_path)?;
+    let nonces_path = nonces_path.to_string_lossy();
+    audit.log_event("commitments_saved", json!({"path": output_path, "nonces": &nonces_path}))?;
 
-    print_results(&supplier_root, &ubo_root, &company_root, output_path);
+    print_results(&supplier_root, &ubo_root, &company_root, output_path, &nonces_path);
     Ok(())
 }
 
@@ -4,9 +4,12 @@
 
 use super::output;
 use crate::audit::AuditLog;
-use crate::io::JsonPersistent;
-use crate::{commitment, io, manifest, policy, proof_engine, proof_mock};
 use crate::bundle::export;
+use crate::commitment::{CommitmentScheme, RecordNonces};
+use crate::io::JsonPersistent;
+use crate::lists::{self, ListKind, RecordKind};
+use crate::sorted_merkle::SortedMerkleTree;
+use crate::{commitment, io, manifest, policy, policy_v2, proof_engine, proof_mock};
 use serde_json::json;
 use std::error::Error;
 use std::fs;
@@ -52,49 +55,225 @@ pub fn run_proof_mock(policy_path: &str, manifest_path: &str) -> Result<(), Box<
 }
 
 /// Proof build - Erstellt strukturierten Proof
-pub fn run_proof_build(policy_path: &str, manifest_path: &str) -> Result<(), Box<dyn Error>> {
+///
+/// Enthält das Manifest `sanctions_root`/`jurisdiction_root`, werden zusätzlich
+/// Ausschluss-Nachweise gegen die jeweilige Liste erzeugt.
+/// Ist `policy_path` eine kompilierte v2-IR, bestimmen deren Regeln die Checks.
+pub fn run_proof_build(
+    policy_path: &str,
+    manifest_path: &str,
+    suppliers_path: Option<String>,
+    ubos_path: Option<String>,
+    sanctions_list: Option<String>,
+    jurisdiction_list: Option<String>,
+) -> Result<(), Box<dyn Error>> {
     output::secure("Erstelle Proof...");
 
     fs::create_dir_all("build")?;
     let mut audit = AuditLog::new("build/agent.audit.jsonl")?;
 
-    // Lade Policy und Manifest
-    let policy = policy::Policy::load(policy_path)?;
-    let manifest = manifest::Manifest::load(manifest_path)?;
-
     // Lade Commitments für Count-Daten
-    let commitments = commitment::load_commitments("build/commitments.json")?;
+    let commitments_path = "build/commitments.json";
+    let commitments = commitment::load_commitments(commitments_path)?;
     let supplier_count = commitments.supplier_count.unwrap_or(0);
     let ubo_count = commitments.ubo_count.unwrap_or(0);
 
+    let manifest = manifest::Manifest::load(manifest_path)?;
+    let scheme = CommitmentScheme::from_field(manifest.commitment_scheme);
+
+    // Listen-Ausschlüsse binden jeden Datensatz per Inclusion-Pfad an die Roots
+    let has_list_roots = manifest.sanctions_root.is_some() || manifest.jurisdiction_root.is_some();
+    if has_list_roots && scheme != CommitmentScheme::BlindedMerkleV2 {
+        return Err(format!(
+            "Listen-Ausschluss benötigt Commitment-Schema {} (Manifest: {})",
+            CommitmentScheme::BlindedMerkleV2.as_str(),
+            scheme.as_str()
+        )
+        .into());
+    }
+
+    // Private Nonces werden nur zum Nachrechnen der gesalzenen Roots benötigt
+    let has_csv = suppliers_path.is_some() || ubos_path.is_some();
+    let nonces = if has_csv && scheme == CommitmentScheme::BlindedMerkleV2 {
+        load_nonces(commitments_path)?
+    } else {
+        RecordNonces::default()
+    };
+
+    if policy_v2::is_ir_file(policy_path) {
+        let ir = policy_v2::load_ir(policy_path)?;
+        output::detail("Policy (IR)", &ir.policy_id);
+        if let Some(valid_until) = &ir.valid_until {
+            output::detail("Gültig bis", valid_until);
+        }
+
+        let suppliers = suppliers_path
+            .as_deref()
+            .map(|p| load_committed_suppliers(Some(p), &manifest, scheme, &nonces))
+            .transpose()?;
+        let ubos = ubos_path
+            .as_deref()
+            .map(|p| load_committed_ubos(Some(p), &manifest, scheme, &nonces))
+            .transpose()?;
+
+        let mut trees = Vec::new();
+        for (kind, root, list) in [
+            (
+                ListKind::Sanctions,
+                &manifest.sanctions_root,
+                &sanctions_list,
+            ),
+            (
+                ListKind::Jurisdictions,
+                &manifest.jurisdiction_root,
+                &jurisdiction_list,
+            ),
+        ] {
+            if let (Some(root), Some(list)) = (root, list) {
+                trees.push((kind, load_list_for_root(kind, Some(list), root)?));
+            }
+        }
+
+        let ctx = proof_engine::build_eval_context(
+            &manifest,
+            suppliers.as_deref(),
+            ubos.as_deref(),
+            &nonces,
+            supplier_count,
+            ubo_count,
+            &trees,
+        )?;
+        let (proof, evaluation) = proof_engine::Proof::build_from_ir(&ir, &manifest, &ctx)?;
+
+        if ir.adaptivity.is_some() {
+            audit.log_event(
+                "policy_rules_activated",
+                json!({
+                    "policy_id": &ir.policy_id,
+                    "ir_hash": &ir.ir_hash,
+                    "activations": &evaluation.activations
+                }),
+            )?;
+            for activation in evaluation.activations.iter().filter(|a| !a.active) {
+                output::detail(
+                    &format!("Regel {} inaktiv", activation.rule_id),
+                    &activation.reason,
+                );
+            }
+        }
+
+        for outcome in evaluation.failed() {
+            output::warning(&format!(
+                "Regel {} ({}) nicht erfüllt: {}",
+                outcome.rule_id,
+                outcome.op,
+                outcome.reason.as_deref().unwrap_or("-")
+            ));
+        }
+
+        return save_proof(&proof, &evaluation.witnesses, &mut audit);
+    }
+
+    let policy = policy::Policy::load(policy_path)?;
+
     // Generiere Proof
-    let proof = proof_engine::Proof::build(&policy, &manifest, supplier_count, ubo_count)?;
+    let mut proof = proof_engine::Proof::build(&policy, &manifest, supplier_count, ubo_count)?;
+    let mut witnesses = Vec::new();
+
+    // Listen-Ausschlüsse gegen die Statement-Roots des Manifests
+    if let Some(root) = &manifest.jurisdiction_root {
+        let tree = load_list_for_root(ListKind::Jurisdictions, jurisdiction_list.as_deref(), root)?;
+        let suppliers =
+            load_committed_suppliers(suppliers_path.as_deref(), &manifest, scheme, &nonces)?;
+        let records = lists::prove_records(RecordKind::Supplier, &suppliers, &nonces.suppliers)?;
+        let result = proof.add_list_exclusion(ListKind::Jurisdictions, &tree, &records);
+        witnesses.extend(report_list_hits(ListKind::Jurisdictions, result));
+    } else if jurisdiction_list.is_some() {
+        output::warning(
+            "Manifest enthält keinen jurisdiction_root – --jurisdiction-list ignoriert",
+        );
+    }
+
+    if let Some(root) = &manifest.sanctions_root {
+        let tree = load_list_for_root(ListKind::Sanctions, sanctions_list.as_deref(), root)?;
+        let suppliers =
+            load_committed_suppliers(suppliers_path.as_deref(), &manifest, scheme, &nonces)?;
+        let ubos = load_committed_ubos(ubos_path.as_deref(), &manifest, scheme, &nonces)?;
+        let mut records =
+            lists::prove_records(RecordKind::Supplier, &suppliers, &nonces.suppliers)?;
+        records.extend(lists::prove_records(RecordKind::Ubo, &ubos, &nonces.ubos)?);
+        let result = proof.add_list_exclusion(ListKind::Sanctions, &tree, &records);
+        witnesses.extend(report_list_hits(ListKind::Sanctions, result));
+    } else if sanctions_list.is_some() {
+        output::warning("Manifest enthält keinen sanctions_root – --sanctions-list ignoriert");
+    }
+
+    save_proof(&proof, &witnesses, &mut audit)
+}
 
+/// Speichert den Proof als .dat und .json und protokolliert ihn im Audit-Log
+///
+/// Listen-Ausschluss-Nachweise enthalten die geprüften Datensatz-Werte und
+/// landen deshalb nur in der privaten Witness-Datei, nie im Proof.
+fn save_proof(
+    proof: &proof_engine::Proof,
+    witnesses: &[lists::ListExclusionWitness],
+    audit: &mut AuditLog,
+) -> Result<(), Box<dyn Error>> {
     // Speichere als .dat und .json
     let output_path_dat = "build/proof.dat";
     let output_path_json = "build/proof.json";
     proof.save_as_dat(output_path_dat)?;
     proof.save(output_path_json)?;
 
+    // Private Witness-Datei; eine veraltete Datei eines früheren Laufs wird entfernt
+    let output_path_witnesses = "build/list_witnesses.json";
+    let has_witnesses = !witnesses.is_empty();
+    if has_witnesses {
+        let file = lists::ListWitnessFile::new(witnesses.to_vec());
+        fs::write(output_path_witnesses, serde_json::to_string_pretty(&file)?)?;
+    } else if std::path::Path::new(output_path_witnesses).exists() {
+        fs::remove_file(output_path_witnesses)?;
+    }
+
     audit.log_event(
         "proof_built",
         json!({
             "output_dat": output_path_dat,
             "output_json": output_path_json,
-            "status": &proof.status
+            "status": &proof.status,
+            "ir_hash": &proof.ir_hash,
+            "list_exclusions": proof.proof_data.list_exclusions.len(),
+            "list_witnesses": has_witnesses.then_some(output_path_witnesses)
         }),
     )?;
 
     output::success("Proof erstellt:");
     output::indent(&format!("- {}", output_path_dat));
     output::indent(&format!("- {}", output_path_json));
+    if has_witnesses {
+        output::indent(&format!(
+            "- {} (privat, nicht weitergeben)",
+            output_path_witnesses
+        ));
+    }
+    if let Some(ir_hash) = &proof.ir_hash {
+        output::detail("IR Hash", ir_hash);
+    }
     output::detail("Status", &proof.status);
 
     Ok(())
 }
 
 /// Proof verify - Verifiziert Proof gegen Manifest
-pub fn run_proof_verify_v3(proof_path: &str, manifest_path: &str) -> Result<(), Box<dyn Error>> {
+///
+/// Mit `list_witnesses` werden die Listen-Ausschlüsse zusätzlich gegen die
+/// private Witness-Datei nachgeprüft.
+pub fn run_proof_verify_v3(
+    proof_path: &str,
+    manifest_path: &str,
+    list_witnesses: Option<&str>,
+) -> Result<(), Box<dyn Error>> {
     output::searching("Verifiziere Proof...");
 
     let mut audit = AuditLog::new("build/agent.audit.jsonl")?;
@@ -112,12 +291,32 @@ pub fn run_proof_verify_v3(proof_path: &str, manifest_path: &str) -> Result<(),
     // Verifiziere
     proof.verify(&manifest)?;
 
-    audit.log_event("proof_verified", json!({ "proof": proof_path }))?;
+    if let Some(path) = list_witnesses {
+        let file: lists::ListWitnessFile = serde_json::from_str(&fs::read_to_string(path)?)?;
+        if file.version != lists::LIST_WITNESSES_VERSION {
+            return Err(format!("Unbekannte Witness-Datei-Version: {}", file.version).into());
+        }
+        proof.verify_list_witnesses(&manifest, &file.witnesses)?;
+    }
+
+    audit.log_event(
+        "proof_verified",
+        json!({ "proof": proof_path, "list_witnesses": list_witnesses }),
+    )?;
 
     output::success("Proof ist gültig!");
     output::detail("Manifest Hash", &proof.manifest_hash);
     output::detail("Policy Hash", &proof.policy_hash);
     output::detail("Status", &proof.status);
+    if list_witnesses.is_some() {
+        output::detail(
+            "Listen-Ausschlüsse",
+            &format!(
+                "{} Nachweis(e) geprüft",
+                proof.proof_data.list_exclusions.len()
+            ),
+        );
+    }
 
     Ok(())
 }
@@ -174,5 +373,101 @@ pub fn run_proof_export(
 // adaptive orchestration (run_proof_adapt) removed in minimal local agent.
 // These features require:
 // - zk_system module (real ZK backends)
-// - lists module (sanctions lists)
 // - orchestrator module (adaptive proof selection)
+
+// ============================================================================
+// Hilfsfunktionen für Listen-Ausschlüsse
+// ============================================================================
+
+/// Lädt eine Listen-CSV und prüft ihren Root gegen den Manifest-Root
+fn load_list_for_root(
+    kind: ListKind,
+    path: Option<&str>,
+    expected_root: &str,
+) -> Result<SortedMerkleTree, Box<dyn Error>> {
+    let path = path.ok_or_else(|| {
+        format!(
+            "Manifest enthält {} – bitte --{}-list angeben",
+            kind.statement_root_key(),
+            kind.statement_root_key().trim_end_matches("_root")
+        )
+    })?;
+
+    let tree = lists::load_list_tree(path, kind)?;
+    if tree.root_hex() != expected_root.to_lowercase() {
+        return Err(format!(
+            "{} passt nicht zu {} im Manifest ({} ≠ {})",
+            path,
+            kind.statement_root_key(),
+            tree.root_hex(),
+            expected_root
+        )
+        .into());
+    }
+    Ok(tree)
+}
+
+/// Lädt die privaten Nonces, die `prepare` neben den Commitments ablegt
+fn load_nonces(commitments_path: &str) -> Result<RecordNonces, Box<dyn Error>> {
+    let path = commitment::nonces_path(commitments_path);
+    RecordNonces::load(&path).map_err(|e| {
+        format!(
+            "Nonces {} nicht lesbar ({}) – bitte prepare erneut ausführen",
+            path.display(),
+            e
+        )
+        .into()
+    })
+}
+
+/// Lädt die Supplier-CSV und prüft sie gegen den committeten supplier_root
+fn load_committed_suppliers(
+    path: Option<&str>,
+    manifest: &manifest::Manifest,
+    scheme: CommitmentScheme,
+    nonces: &RecordNonces,
+) -> Result<Vec<io::Supplier>, Box<dyn Error>> {
+    let path = path.ok_or("Listen-Ausschluss benötigt --suppliers")?;
+    let suppliers = io::read_suppliers_csv(path)?;
+    if commitment::compute_supplier_root_with(&suppliers, scheme, &nonces.suppliers)?
+        != manifest.supplier_root
+    {
+        return Err(format!("{} passt nicht zum supplier_root im Manifest", path).into());
+    }
+    Ok(suppliers)
+}
+
+/// Lädt die UBO-CSV und prüft sie gegen den committeten ubo_root
+fn load_committed_ubos(
+    path: Option<&str>,
+    manifest: &manifest::Manifest,
+    scheme: CommitmentScheme,
+    nonces: &RecordNonces,
+) -> Result<Vec<io::Ubo>, Box<dyn Error>> {
+    let path = path.ok_or("Sanktions-Ausschluss benötigt --ubos")?;
+    let ubos = io::read_ubos_csv(path)?;
+    if commitment::compute_ubo_root_with(&ubos, scheme, &nonces.ubos)? != manifest.ubo_root {
+        return Err(format!("{} passt nicht zum ubo_root im Manifest", path).into());
+    }
+    Ok(ubos)
+}
+
+/// Gibt Listen-Treffer aus (Proof-Status ist dann "failed")
+fn report_list_hits(
+    kind: ListKind,
+    result: Result<lists::ListExclusionWitness, Vec<String>>,
+) -> Option<lists::ListExclusionWitness> {
+    match result {
+        Ok(witness) => {
+            output::success(&format!("Keine Treffer in {}-Liste", kind));
+            Some(witness)
+        }
+        Err(hits) => {
+            output::warning(&format!("{} Treffer in {}-Liste:", hits.len(), kind));
+            for hit in &hits {
+                output::indent(&format!("- {}", hit));
+            }
+            None
+        }
+    }
+}
@@ -1,14 +1,20 @@
-//! CLI-Handler für Registry- und Lists-Kommandos
+//! CLI-Handler für Registry-Kommandos
 //!
 //! Extrahiert aus main.rs für bessere Wartbarkeit.
-//! Enthält: run_lists_sanctions_root, run_lists_jurisdictions_root,
-//!          run_registry_add, run_registry_list, run_registry_verify,
+//! Enthält: run_registry_add, run_registry_list, run_registry_verify,
+//!          run_registry_revoke, run_registry_supersede,
+//!          run_registry_log_head, run_registry_log_proof, run_registry_log_consistency,
+//!          run_registry_export, run_registry_import, run_registry_schema_migrate,
 //!          run_registry_migrate, run_registry_inspect, run_registry_backfill_kid
 
-use super::output;
+use super::{output, paths};
 use crate::audit::AuditLog;
 use crate::registry;
-use registry::RegistryBackend;
+use registry::sqlite_schema;
+use registry::{
+    ConflictKind, ImportPlan, LifecycleAction, LifecycleRecord, RegistryBackend, RegistrySnapshot,
+    RevocationReason, SignedTreeHead,
+};
 use serde_json::json;
 use std::error::Error;
 use std::fs;
@@ -45,17 +51,17 @@ fn get_registry_file(registry_path: Option<String>, backend: RegistryBackend) ->
 
 /// Erstellt einen neuen RegistryEntry mit Default-Werten
 fn create_registry_entry(
-    id: String,
     manifest_hash: String,
     proof_hash: String,
     timestamp_path: Option<String>,
 ) -> registry::RegistryEntry {
+    let registered_at = chrono::Utc::now().to_rfc3339();
     registry::RegistryEntry {
-        id,
+        id: registry::RegistryEntry::derive_id(&manifest_hash, &proof_hash, &registered_at),
         manifest_hash,
         proof_hash,
         timestamp_file: timestamp_path,
-        registered_at: chrono::Utc::now().to_rfc3339(),
+        registered_at,
         signature: None,
         public_key: None,
         blob_manifest: None,
@@ -68,9 +74,29 @@ fn create_registry_entry(
         verifier_version: None,
         kid: None,
         signature_scheme: None,
+        policy_id: None,
     }
 }
 
+/// Lädt einen Ed25519 Signing-Key (32 Bytes roh)
+fn load_signing_key(key_file: &str) -> Result<ed25519_dalek::SigningKey, Box<dyn Error>> {
+    let key_bytes = fs::read(key_file)
+        .map_err(|e| format!("Failed to read signing key from {}: {}", key_file, e))?;
+
+    if key_bytes.len() != 32 {
+        return Err(format!(
+            "Invalid signing key length (expected 32 bytes, got {})",
+            key_bytes.len()
+        )
+        .into());
+    }
+
+    let key_array: [u8; 32] = key_bytes
+        .try_into()
+        .map_err(|_| "Failed to convert key bytes to array")?;
+    Ok(ed25519_dalek::SigningKey::from_bytes(&key_array))
+}
+
 /// Signiert Entry mit optionalem Schlüssel und validiert Key-Status
 fn sign_and_validate_entry(
     entry: &mut registry::RegistryEntry,
@@ -89,23 +115,7 @@ fn sign_and_validate_entry(
     };
 
     output::detail("Signing-Key", key_file);
-
-    // Load signing key
-    let key_bytes = fs::read(key_file)
-        .map_err(|e| format!("Failed to read signing key from {}: {}", key_file, e))?;
-
-    if key_bytes.len() != 32 {
-        return Err(format!(
-            "Invalid signing key length (expected 32 bytes, got {})",
-            key_bytes.len()
-        )
-        .into());
-    }
-
-    let key_array: [u8; 32] = key_bytes
-        .try_into()
-        .map_err(|_| "Failed to convert key bytes to array")?;
-    let signing_key = ed25519_dalek::SigningKey::from_bytes(&key_array);
+    let signing_key = load_signing_key(key_file)?;
 
     // Sign entry
     registry::sign_entry(entry, &signing_key)?;
@@ -129,9 +139,6 @@ fn sign_and_validate_entry(
 // Öffentliche CLI-Funktionen
 // ============================================================================
 
-// Note: run_lists_sanctions_root and run_lists_jurisdictions_root removed
-// in minimal local agent. These features require the lists module.
-
 /// Registry add - Fügt einen Proof zur Registry hinzu
 #[allow(clippy::too_many_arguments)]
 pub fn run_registry_add(
@@ -160,17 +167,17 @@ pub fn run_registry_add(
     output::detail("Manifest-Hash", &manifest_hash);
     output::detail("Proof-Hash", &proof_hash);
 
-    // Load current registry to get next ID
-    let current_reg = store.load()?;
-    let id = format!("proof_{:03}", current_reg.entries.len() + 1);
-
-    // Create entry
+    // Create entry (ID aus den Hashes abgeleitet, global eindeutig)
     let mut entry = create_registry_entry(
-        id.clone(),
         manifest_hash.clone(),
         proof_hash.clone(),
         timestamp_path.clone(),
     );
+    let id = entry.id.clone();
+    // Policy-ID für Registry-Abfragen (best-effort; nicht Teil der Signatur)
+    entry.policy_id = crate::manifest::Manifest::load(manifest_path)
+        .ok()
+        .map(|manifest| manifest.policy.hash);
 
     // Sign entry if signing key provided
     sign_and_validate_entry(&mut entry, signing_key_path, validate_key, keys_dir)?;
@@ -201,35 +208,64 @@ pub fn run_registry_add(
     Ok(())
 }
 
-/// Registry list - Listet alle Registry-Einträge auf
+/// Registry list - Listet Registry-Einträge auf (optional gefiltert/paginiert)
+#[allow(clippy::too_many_arguments)]
 pub fn run_registry_list(
     registry_path: Option<String>,
     backend_str: &str,
+    filters: &[String],
+    sort: &str,
+    order: &str,
+    limit: Option<usize>,
+    offset: usize,
+    json_output: bool,
 ) -> Result<(), Box<dyn Error>> {
     let backend = parse_backend(backend_str);
     let registry_file = get_registry_file(registry_path, backend);
 
+    let mut query = registry::RegistryQuery {
+        sort: sort.parse()?,
+        order: order.parse()?,
+        limit,
+        offset,
+        ..Default::default()
+    };
+    for filter in filters {
+        query.add_filter(filter)?;
+    }
+    query.validate()?;
+
     if !Path::new(&registry_file).exists() {
+        if json_output {
+            return Err(format!("Registry-Datei nicht gefunden: {}", registry_file).into());
+        }
         output::warning(&format!("Registry-Datei nicht gefunden: {}", registry_file));
         output::indent("Verwende 'registry add' um Einträge hinzuzufügen.");
         return Ok(());
     }
 
-    // Open store and load entries
-    let store = registry::open_store(backend, Path::new(&registry_file))?;
-    let entries = store.list()?;
+    // Open store (read-only, without schema migration) and run query
+    let store = registry::open_store_read_only(backend, Path::new(&registry_file))?;
+    let page = store.query(&query)?;
+
+    if json_output {
+        return output::write_json(&page, None);
+    }
+
+    let registry_data = store.load()?;
+    let now = chrono::Utc::now();
 
     output::separator();
     output::info(&format!("Proofs in local registry ({})", registry_file));
     output::separator();
 
-    if entries.is_empty() {
+    if page.entries.is_empty() {
         output::indent("(keine Einträge)");
     } else {
-        for (idx, entry) in entries.iter().enumerate() {
+        for (idx, entry) in page.entries.iter().enumerate() {
             output::indent(&format!(
                 "#{:<3} Manifest: {}…  Proof: {}…  Date: {}",
-                idx + 1,
+                page.offset + idx + 1,
                 &entry.manifest_hash[..12],
                 &entry.proof_hash[..12],
                 entry.registered_at
@@ -237,21 +273,132 @@ pub fn run_registry_list(
             if let Some(ref ts) = entry.timestamp_file {
                 output::indent(&format!("     Timestamp: {}", ts));
             }
+            let status = registry::entry_status(entry, &registry_data.lifecycle_of(&entry.id), now);
+            if status != registry::EntryStatus::Active {
+                output::indent(&format!("     Status: {}", status));
+            }
         }
     }
 
     output::separator();
-    output::detail_fmt("Total", format!("{} Einträge", entries.len()));
+    if page.entries.len() == page.total {
+        output::detail_fmt("Total", format!("{} Einträge", page.total));
+    } else {
+        output::detail_fmt(
+            "Total",
+            format!(
+                "{} Einträge (angezeigt: {}–{})",
+                page.total,
+                page.offset + 1.min(page.entries.len()),
+                page.offset + page.entries.len()
+            ),
+        );
+    }
 
     Ok(())
 }
 
+/// Öffnet den Store einer bestehenden Registry nur lesend (ohne Schema-Migration)
+fn open_existing_store(
+    registry_path: Option<String>,
+    backend_str: &str,
+) -> Result<(String, Box<dyn registry::RegistryStore>), Box<dyn Error>> {
+    let backend = parse_backend(backend_str);
+    let registry_file = get_registry_file(registry_path, backend);
+    if !Path::new(&registry_file).exists() {
+        return Err(format!("Registry-Datei nicht gefunden: {}", registry_file).into());
+    }
+    let store = registry::open_store_read_only(backend, Path::new(&registry_file))?;
+    Ok((registry_file, store))
+}
+
+/// Lädt das Transparency-Log und prüft es gegen die aktuellen Einträge
+///
+/// Geänderte oder gelöschte geloggte Einträge sind ein Fehler; Einträge aus
+/// der Zeit vor dem Log werden nur gemeldet.
+fn load_audited_log(
+    store: &dyn registry::RegistryStore,
+) -> Result<registry::TransparencyLog, Box<dyn Error>> {
+    let log = store.transparency_log()?;
+    let audit = log.audit_entries(&store.list()?);
+
+    if !audit.unlogged.is_empty() {
+        output::warning(&format!(
+            "{} Einträge nicht im Transparency-Log (vor Einführung registriert): {}",
+            audit.unlogged.len(),
+            audit.unlogged.join(", ")
+        ));
+    }
+    if !audit.is_consistent() {
+        if !audit.modified.is_empty() {
+            output::error(&format!(
+                "Geänderte Einträge: {}",
+                audit.modified.join(", ")
+            ));
+        }
+        if !audit.missing.is_empty() {
+            output::error(&format!("Gelöschte Einträge: {}", audit.missing.join(", ")));
+        }
+        return Err("Registry weicht vom Transparency-Log ab".into());
+    }
+    Ok(log)
+}
+
+/// Prüft, dass das Log einen früheren Tree Head erweitert und den Eintrag enthält
+fn verify_log_extension(
+    store: &dyn registry::RegistryStore,
+    since_path: &str,
+    entry_id: &str,
+) -> Result<(), Box<dyn Error>> {
+    let old = SignedTreeHead::load(since_path)?;
+    let kid = old
+        .verify_signature()
+        .map_err(|e| format!("Tree Head {} ungültig: {}", since_path, e))?;
+
+    let log = load_audited_log(store)?;
+    let proof = log
+        .consistency_proof(&old)
+        .map_err(|e| format!("Registry erweitert Tree Head {} nicht: {}", since_path, e))?;
+    output::success("Registry erweitert früheren Tree Head");
+    output::detail("Tree Head", since_path);
+    output::detail("Signiert von", &kid);
+    output::detail(
+        "Log-Größe",
+        &format!("{} → {}", proof.old_size, proof.new_size),
+    );
+    output::detail("Root", &proof.new_root);
+
+    match log.index_of(entry_id) {
+        Some(index) => {
+            let inclusion = log
+                .inclusion_proof(index)
+                .ok_or("Inclusion-Beweis konnte nicht erzeugt werden")?;
+            inclusion.verify(&log.tree_head())?;
+            let status = if index < old.tree_size {
+                "bereits im früheren Tree Head"
+            } else {
+                "neu seit dem früheren Tree Head"
+            };
+            output::indent(&format!(
+                "✓ Eintrag im Transparency-Log (Index {}, {})",
+                index, status
+            ));
+        }
+        None => output::warning(&format!(
+            "Eintrag {} ist nicht im Transparency-Log",
+            entry_id
+        )),
+    }
+    Ok(())
+}
+
 /// Registry verify - Verifiziert einen Proof gegen die Registry
 pub fn run_registry_verify(
     manifest_path: &str,
     proof_path: &str,
     registry_path: Option<String>,
     backend_str: &str,
+    since: Option<&str>,
 ) -> Result<(), Box<dyn Error>> {
     output::searching("Verifiziere Proof gegen Registry...");
 
@@ -269,8 +416,8 @@ pub fn run_registry_verify(
     output::detail("Manifest-Hash", &manifest_hash);
     output::detail("Proof-Hash", &proof_hash);
 
-    // Open store and find entry
-    let store = registry::open_store(backend, Path::new(&registry_file))?;
+    // Open store (read-only, without schema migration) and find entry
+    let store = registry::open_store_read_only(backend, Path::new(&registry_file))?;
     let entry_opt = store.find_by_hashes(&manifest_hash, &proof_hash)?;
 
     // Verifiziere
@@ -290,6 +437,27 @@ pub fn run_registry_verify(
             output::indent("⚠ No signature present (backward compatibility)");
         }
 
+        // Lifecycle: widerrufen schlägt fehl, ersetzt warnt
+        let (status, _) = registry::verified_entry_status(
+            &entry,
+            &store.lifecycle_records(&entry.id)?,
+            chrono::Utc::now(),
+        )?;
+        match &status {
+            registry::EntryStatus::Active => {}
+            registry::EntryStatus::Superseded { .. } => {
+                output::warning(&format!("Proof {}", status));
+            }
+            registry::EntryStatus::Revoked { .. } => {
+                output::error(&format!("Proof {}", status));
+            }
+        }
+
+        // Transparency-Log: Registry erweitert den früheren Tree Head
+        if let Some(since_path) = since {
+            verify_log_extension(store.as_ref(), since_path, &entry.id)?;
+        }
+
         // Log Audit-Event
         let mut audit = AuditLog::new("build/agent.audit.jsonl")?;
         audit.log_event(
@@ -300,10 +468,19 @@ pub fn run_registry_verify(
                 "registry_file": registry_file,
                 "backend": backend_str,
                 "signature_valid": signature_valid,
-                "status": "ok"
+                "lifecycle_status": status.as_str(),
+                "since_tree_head": since,
+                "status": if matches!(status, registry::EntryStatus::Revoked { .. }) {
+                    "fail"
+                } else {
+                    "ok"
+                }
             }),
         )?;
 
+        if matches!(status, registry::EntryStatus::Revoked { .. }) {
+            return Err(format!("Proof {} wurde widerrufen", entry.id).into());
+        }
         Ok(())
     } else {
         let total = store.list()?.len();
@@ -314,6 +491,465 @@ pub fn run_registry_verify(
     }
 }
 
+/// Schreibt einen signierten Lifecycle-Record (Widerruf oder Ersetzung)
+#[allow(clippy::too_many_arguments)]
+fn run_registry_lifecycle(
+    action: LifecycleAction,
+    id: &str,
+    successor: Option<&str>,
+    reason_str: &str,
+    effective_at: Option<String>,
+    registry_path: Option<String>,
+    backend_str: &str,
+    signing_key_path: &str,
+) -> Result<(), Box<dyn Error>> {
+    let reason: RevocationReason = reason_str.parse()?;
+    let backend = parse_backend(backend_str);
+    let registry_file = get_registry_file(registry_path, backend);
+
+    if !Path::new(&registry_file).exists() {
+        return Err(format!("Registry-Datei nicht gefunden: {}", registry_file).into());
+    }
+    let store = registry::open_store(backend, Path::new(&registry_file))?;
+
+    let entry = store
+        .find_by_id(id)?
+        .ok_or_else(|| format!("Registry-Eintrag nicht gefunden: {}", id))?;
+    if let Some(successor) = successor {
+        if successor == id {
+            return Err("Ein Eintrag kann sich nicht selbst ersetzen".into());
+        }
+        if store.find_by_id(successor)?.is_none() {
+            return Err(format!("Nachfolger-Eintrag nicht gefunden: {}", successor).into());
+        }
+    }
+
+    // Widerruf ist endgültig; doppelte Ersetzung ist mehrdeutig
+    let existing = store.lifecycle_records(id)?;
+    if existing
+        .iter()
+        .any(|r| r.applies_to(&entry) && r.action == LifecycleAction::Revoke)
+    {
+        return Err(format!("Registry-Eintrag {} ist bereits widerrufen", id).into());
+    }
+    if action == LifecycleAction::Supersede
+        && existing
+            .iter()
+            .any(|r| r.applies_to(&entry) && r.action == LifecycleAction::Supersede)
+    {
+        return Err(format!("Registry-Eintrag {} ist bereits ersetzt", id).into());
+    }
+
+    let effective_at = effective_at.unwrap_or_else(|| chrono::Utc::now().to_rfc3339());
+    let mut record = LifecycleRecord::new(
+        &entry,
+        action,
+        reason,
+        successor.map(str::to_string),
+        effective_at,
+    )?;
+    output::detail("Signing-Key", signing_key_path);
+    record.sign(&load_signing_key(signing_key_path)?)?;
+    store.add_lifecycle_record(record.clone())?;
+
+    let mut audit = AuditLog::new("build/agent.audit.jsonl")?;
+    audit.log_event(
+        match action {
+            LifecycleAction::Revoke => "registry_entry_revoked",
+            LifecycleAction::Supersede => "registry_entry_superseded",
+        },
+        json!({
+            "id": id,
+            "successor_id": record.successor_id,
+            "reason": record.reason,
+            "effective_at": record.effective_at,
+            "kid": record.kid,
+            "registry_file": registry_file,
+            "backend": backend_str
+        }),
+    )?;
+
+    match action {
+        LifecycleAction::Revoke => output::success("Registry-Eintrag widerrufen:"),
+        LifecycleAction::Supersede => output::success("Registry-Eintrag ersetzt:"),
+    }
+    output::detail("ID", id);
+    if let Some(ref successor) = record.successor_id {
+        output::detail("Nachfolger", successor);
+    }
+    output::detail_fmt("Grund", record.reason);
+    output::detail("Wirksam ab", &record.effective_at);
+    output::detail("Registry", &registry_file);
+
+    Ok(())
+}
+
+/// Registry revoke - Widerruft einen Registry-Eintrag
+pub fn run_registry_revoke(
+    id: &str,
+    reason: &str,
+    effective_at: Option<String>,
+    registry_path: Option<String>,
+    backend_str: &str,
+    signing_key_path: &str,
+) -> Result<(), Box<dyn Error>> {
+    output::writing(&format!("Widerrufe Registry-Eintrag {}...", id));
+    run_registry_lifecycle(
+        LifecycleAction::Revoke,
+        id,
+        None,
+        reason,
+        effective_at,
+        registry_path,
+        backend_str,
+        signing_key_path,
+    )
+}
+
+/// Registry supersede - Ersetzt einen Registry-Eintrag durch einen Nachfolger
+pub fn run_registry_supersede(
+    id: &str,
+    successor: &str,
+    reason: &str,
+    effective_at: Option<String>,
+    registry_path: Option<String>,
+    backend_str: &str,
+    signing_key_path: &str,
+) -> Result<(), Box<dyn Error>> {
+    output::writing(&format!(
+        "Ersetze Registry-Eintrag {} durch {}...",
+        id, successor
+    ));
+    run_registry_lifecycle(
+        LifecycleAction::Supersede,
+        id,
+        Some(successor),
+        reason,
+        effective_at,
+        registry_path,
+        backend_str,
+        signing_key_path,
+    )
+}
+
+/// Registry log-head - Signiert den aktuellen Tree Head des Transparency-Logs
+pub fn run_registry_log_head(
+    registry_path: Option<String>,
+    backend_str: &str,
+    signing_key_path: &str,
+    out: Option<String>,
+) -> Result<(), Box<dyn Error>> {
+    output::secure("Signiere Tree Head des Transparency-Logs...");
+
+    let (registry_file, store) = open_existing_store(registry_path, backend_str)?;
+    let log = load_audited_log(store.as_ref())?;
+
+    let mut head = log.tree_head();
+    output::detail("Signing-Key", signing_key_path);
+    head.sign(&load_signing_key(signing_key_path)?)?;
+
+    let out_path = out.unwrap_or_else(|| paths::REGISTRY_TREE_HEAD.to_string());
+    if let Some(parent) = Path::new(&out_path).parent() {
+        fs::create_dir_all(parent)?;
+    }
+    head.save(&out_path)?;
+
+    let mut audit = AuditLog::new(paths::AUDIT_LOG)?;
+    audit.log_event(
+        "registry_tree_head_signed",
+        json!({
+            "tree_size": head.tree_size,
+            "root_hash": head.root_hash,
+            "kid": head.kid,
+            "registry_file": registry_file,
+            "backend": backend_str,
+            "output": out_path
+        }),
+    )?;
+
+    output::success_with("Tree Head gespeichert", &out_path);
+    output::detail_fmt("Log-Größe", head.tree_size);
+    output::detail("Root", &head.root_hash);
+    output::detail("Zeitpunkt", &head.timestamp);
+
+    Ok(())
+}
+
+/// Registry log-proof - Inclusion-Beweis eines Eintrags im Transparency-Log
+pub fn run_registry_log_proof(
+    id: &str,
+    registry_path: Option<String>,
+    backend_str: &str,
+    out: Option<String>,
+) -> Result<(), Box<dyn Error>> {
+    output::searching(&format!("Erzeuge Inclusion-Beweis für {}...", id));
+
+    let (_, store) = open_existing_store(registry_path, backend_str)?;
+    let log = load_audited_log(store.as_ref())?;
+    let index = log
+        .index_of(id)
+        .ok_or_else(|| format!("Eintrag {} ist nicht im Transparency-Log", id))?;
+    let proof = log
+        .inclusion_proof(index)
+        .ok_or("Inclusion-Beweis konnte nicht erzeugt werden")?;
+
+    let out_path =
+        out.unwrap_or_else(|| format!("{}/registry.{}.inclusion.json", paths::BUILD_DIR, id));
+    if let Some(parent) = Path::new(&out_path).parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&out_path, serde_json::to_string_pretty(&proof)?)?;
+
+    output::success_with("Inclusion-Beweis gespeichert", &out_path);
+    output::detail_fmt("Index", proof.leaf_index);
+    output::detail_fmt("Log-Größe", proof.tree_size);
+    output::detail("Root", &proof.root_hash);
+
+    Ok(())
+}
+
+/// Registry log-consistency - Consistency-Beweis von einem früheren Tree Head
+pub fn run_registry_log_consistency(
+    old_path: &str,
+    registry_path: Option<String>,
+    backend_str: &str,
+    out: Option<String>,
+) -> Result<(), Box<dyn Error>> {
+    output::searching(&format!("Prüfe Log gegen Tree Head {}...", old_path));
+
+    let old = SignedTreeHead::load(old_path)?;
+    let kid = old.verify_signature()?;
+    let (_, store) = open_existing_store(registry_path, backend_str)?;
+    let log = load_audited_log(store.as_ref())?;
+    let proof = log
+        .consistency_proof(&old)
+        .map_err(|e| format!("Registry erweitert Tree Head {} nicht: {}", old_path, e))?;
+
+    let out_path = out.unwrap_or_else(|| format!("{}/registry.consistency.json", paths::BUILD_DIR));
+    if let Some(parent) = Path::new(&out_path).parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&out_path, serde_json::to_string_pretty(&proof)?)?;
+
+    output::success_with("Consistency-Beweis gespeichert", &out_path);
+    output::detail("Signiert von", &kid);
+    output::detail(
+        "Log-Größe",
+        &format!("{} → {}", proof.old_size, proof.new_size),
+    );
+    output::detail("Alter Root", &proof.old_root);
+    output::detail("Neuer Root", &proof.new_root);
+
+    Ok(())
+}
+
+/// Registry export - Exportiert die Registry als signierten Snapshot
+pub fn run_registry_export(
+    registry_path: Option<String>,
+    backend_str: &str,
+    signing_key_path: &str,
+    source: Option<String>,
+    out: Option<String>,
+) -> Result<(), Box<dyn Error>> {
+    output::packaging("Exportiere Registry-Snapshot...");
+
+    let (registry_file, store) = open_existing_store(registry_path, backend_str)?;
+    let mut snapshot = RegistrySnapshot::new(store.load()?, source)?;
+    output::detail("Signing-Key", signing_key_path);
+    snapshot.sign(&load_signing_key(signing_key_path)?)?;
+
+    let out_path = out.unwrap_or_else(|| paths::REGISTRY_SNAPSHOT.to_string());
+    if let Some(parent) = Path::new(&out_path).parent() {
+        fs::create_dir_all(parent)?;
+    }
+    snapshot.save(&out_path)?;
+
+    let mut audit = AuditLog::new(paths::AUDIT_LOG)?;
+    audit.log_event(
+        "registry_snapshot_exported",
+        json!({
+            "entries": snapshot.entry_count,
+            "registry_hash": snapshot.registry_hash,
+            "source": snapshot.source,
+            "kid": snapshot.kid,
+            "registry_file": registry_file,
+            "backend": backend_str,
+            "output": out_path
+        }),
+    )?;
+
+    output::success_with("Snapshot gespeichert", &out_path);
+    output::detail_fmt("Einträge", snapshot.entry_count);
+    output::detail_fmt("Lifecycle-Records", snapshot.registry.lifecycle.len());
+    output::detail("Registry-Hash", &snapshot.registry_hash);
+
+    Ok(())
+}
+
+/// Registry import - Führt einen signierten Snapshot in die lokale Registry zusammen
+pub fn run_registry_import(
+    snapshot_path: &str,
+    registry_path: Option<String>,
+    backend_str: &str,
+    keys_dir: Option<&str>,
+    skip_conflicts: bool,
+) -> Result<(), Box<dyn Error>> {
+    output::input(&format!(
+        "Importiere Registry-Snapshot {}...",
+        snapshot_path
+    ));
+
+    let snapshot = RegistrySnapshot::load(snapshot_path)?;
+    let kid = snapshot
+        .verify()
+        .map_err(|e| format!("Snapshot-Prüfung fehlgeschlagen: {}", e))?;
+    if let Some(dir) = keys_dir {
+        registry::validate_key_status(&kid, dir)?;
+    }
+    output::detail("Signiert von", &kid);
+    if let Some(ref source) = snapshot.source {
+        output::detail("Herkunft", source);
+    }
+
+    let backend = parse_backend(backend_str);
+    let registry_file = get_registry_file(registry_path, backend);
+    let store = registry::open_store(backend, Path::new(&registry_file))?;
+    let plan = ImportPlan::new(&store.load()?, &snapshot.registry);
+
+    for invalid in &plan.invalid {
+        output::error_with(
+            &format!("Ungültige Signatur: {}", invalid.id),
+            &invalid.error,
+        );
+    }
+    for conflict in &plan.conflicts {
+        output::warning(&match conflict.kind {
+            ConflictKind::Hashes => format!(
+                "Konflikt: Manifest/Proof {} / {} ist lokal als {} mit anderem Signer oder Inhalt registriert (Snapshot {})",
+                conflict.imported_manifest_hash,
+                conflict.imported_proof_hash,
+                conflict.local_id,
+                conflict.id
+            ),
+            ConflictKind::Id => format!(
+                "Konflikt: ID {} ist lokal mit anderen Hashes belegt (lokal {} / {}, Snapshot {} / {})",
+                conflict.id,
+                conflict.local_manifest_hash,
+                conflict.local_proof_hash,
+                conflict.imported_manifest_hash,
+                conflict.imported_proof_hash
+            ),
+        });
+    }
+    if !plan.invalid.is_empty() {
+        return Err(format!(
+            "Import abgebrochen: {} ungültige Signaturen im Snapshot",
+            plan.invalid.len()
+        )
+        .into());
+    }
+    if !plan.conflicts.is_empty() && !skip_conflicts {
+        return Err(format!(
+            "Import abgebrochen: {} Konflikte (--skip-conflicts überspringt sie)",
+            plan.conflicts.len()
+        )
+        .into());
+    }
+
+    plan.apply(store.as_ref())?;
+
+    let mut audit = AuditLog::new(paths::AUDIT_LOG)?;
+    audit.log_event(
+        "registry_snapshot_imported",
+        json!({
+            "snapshot": snapshot_path,
+            "registry_hash": snapshot.registry_hash,
+            "source": snapshot.source,
+            "kid": kid,
+            "imported": plan.imported,
+            "duplicates": plan.duplicates.len(),
+            "conflicts_skipped": plan.conflicts.iter().map(|c| &c.id).collect::<Vec<_>>(),
+            "unsigned": plan.unsigned,
+            "lifecycle_records": plan.lifecycle.len(),
+            "registry_file": registry_file,
+            "backend": backend_str
+        }),
+    )?;
+
+    output::success_with("Snapshot importiert", &registry_file);
+    output::detail_fmt("Neue Einträge", plan.imported.len());
+    output::detail_fmt("Duplikate", plan.duplicates.len());
+    output::detail_fmt("Konflikte übersprungen", plan.conflicts.len());
+    output::detail_fmt("Lifecycle-Records", plan.lifecycle.len());
+    if !plan.unsigned.is_empty() {
+        output::warning(&format!(
+            "{} importierte Einträge sind unsigniert: {}",
+            plan.unsigned.len(),
+            plan.unsigned.join(", ")
+        ));
+    }
+
+    Ok(())
+}
+
+/// Registry schema-migrate - Wendet ausstehende SQLite-Schema-Migrationen an
+pub fn run_registry_schema_migrate(
+    registry_path: Option<String>,
+    dry_run: bool,
+    no_backup: bool,
+) -> Result<(), Box<dyn Error>> {
+    let registry_file = get_registry_file(registry_path, RegistryBackend::Sqlite);
+    output::info(&format!("Prüfe Schema von {}...", registry_file));
+
+    let report = sqlite_schema::migrate_file(
+        Path::new(&registry_file),
+        sqlite_schema::MigrationOptions {
+            dry_run,
+            backup: !no_backup,
+        },
+    )?;
+
+    if report.applied.is_empty() {
+        output::success_with("Schema ist aktuell, Version", report.from_version);
+        return Ok(());
+    }
+
+    for step in &report.applied {
+        output::indent(&format!("v{}: {}", step.version, step.description));
+    }
+    if report.dry_run {
+        output::success(&format!(
+            "Dry Run: {} Schritte ausführbar (Version {} → {}), nichts geändert",
+            report.applied.len(),
+            report.from_version,
+            report.to_version
+        ));
+        return Ok(());
+    }
+
+    let mut audit = AuditLog::new(paths::AUDIT_LOG)?;
+    audit.log_event(
+        "registry_schema_migrated",
+        json!({
+            "registry_file": registry_file,
+            "from_version": report.from_version,
+            "to_version": report.to_version,
+            "applied": report.applied.iter().map(|m| m.version).collect::<Vec<_>>(),
+            "backup": report.backup
+        }),
+    )?;
+
+    output::success(&format!(
+        "Schema migriert: Version {} → {}",
+        report.from_version, report.to_version
+    ));
+    if let Some(ref backup) = report.backup {
+        output::detail("Backup", &backup.display().to_string());
+    }
+
+    Ok(())
+}
+
 /// Registry migrate - Migriert Registry zwischen Backends
 pub fn run_registry_migrate(
     from_backend_str: &str,
@@ -332,8 +968,8 @@ pub fn run_registry_migrate(
         return Err(format!("Quell-Registry nicht gefunden: {}", from_path).into());
     }
 
-    // Open source store and load data
-    let from_store = registry::open_store(from_backend, Path::new(from_path))?;
+    // Open source store (read-only) and load data
+    let from_store = registry::open_store_read_only(from_backend, Path::new(from_path))?;
     output::indent("Lade Daten...");
     let registry_data = from_store.load()?;
     let entry_count = registry_data.entries.len();
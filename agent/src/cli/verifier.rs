@@ -47,10 +47,23 @@ pub fn run_verifier_run(package_path: &str) -> Result<(), Box<dyn Error>> {
         output::detail_fmt("Proof Units", result.unit_results.len());
 
         // Zeige einzelne Unit-Ergebnisse
+        let mut failed = Vec::new();
         for (unit_id, unit_result) in &result.unit_results {
             output::section(&format!("  📋 Unit '{}': {:?}", unit_id, unit_result.status));
             output::indent(&format!("  Manifest Hash: {}", unit_result.manifest_hash));
             output::indent(&format!("  Proof Hash: {}", unit_result.proof_hash));
+            for check in unit_result.checks.iter().filter(|c| !c.status.is_pass()) {
+                output::indent(&format!("  ❌ {}: {}", check.id, check.message));
+                failed.push(format!("{} ({}): {}", unit_id, check.id, check.message));
+            }
+        }
+
+        if !result.status.is_pass() {
+            return Err(format!(
+                "Bundle-Verifikation fehlgeschlagen:\n  {}",
+                failed.join("\n  ")
+            )
+            .into());
         }
     } else {
         // Fallback zu Legacy Verifier (Backward-Compatibility)
@@ -75,6 +88,14 @@ pub fn run_verifier_run(package_path: &str) -> Result<(), Box<dyn Error>> {
             }),
         )?;
 
+        if !result.success {
+            return Err(format!(
+                "Verifikation fehlgeschlagen:\n  {}",
+                result.failed_checks.join("\n  ")
+            )
+            .into());
+        }
+
         output::section("");
         output::success("Verifikation erfolgreich!");
         output::detail("Manifest Hash", &result.manifest_hash);
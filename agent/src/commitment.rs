@@ -1,11 +1,103 @@
 use blake3::Hasher;
+use rand::RngCore;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::error::Error;
 use std::fs::File;
 use std::io::Write;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
-use crate::io::{Supplier, Ubo};
+use crate::io::{JsonPersistent, Supplier, Ubo};
+use crate::merkle::{self, MerkleTree};
+
+/// Dateiname der privaten Blinding-Nonces (liegt neben commitments.json)
+pub const NONCES_FILE_NAME: &str = "commitment_nonces.json";
+
+/// Domain-Tag für Feld-Commitments (Schema blake3-blinded-merkle.v2)
+const FIELD_DOMAIN: &[u8] = b"cap.record-field.v2";
+
+/// Version des Commitment-Schemas für supplier_root und ubo_root
+///
+/// Fehlt das Feld in Commitments oder Manifest, wurden die Roots mit dem
+/// Legacy-Schema berechnet (Dateien vor Einführung der Versionierung).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CommitmentScheme {
+    /// BLAKE3 über die konkatenierten Record-Hashes (kein echter Merkle-Tree)
+    #[serde(rename = "blake3-concat.v0")]
+    LegacyConcat,
+    /// Domain-separierter binärer Merkle-Tree (siehe `merkle`)
+    #[serde(rename = "blake3-merkle.v1")]
+    MerkleV1,
+    /// Merkle-Tree über gesalzene Feld-Commitments je Datensatz
+    ///
+    /// Jeder Datensatz erhält eine private Nonce; Inclusion-Proofs legen nur
+    /// einzelne Felder samt Feld-Salt offen.
+    #[serde(rename = "blake3-blinded-merkle.v2")]
+    BlindedMerkleV2,
+}
+
+impl CommitmentScheme {
+    /// Aktuelles Schema für neu erzeugte Commitments
+    pub const CURRENT: CommitmentScheme = CommitmentScheme::BlindedMerkleV2;
+
+    /// Löst ein optionales Schema-Feld auf (None = Legacy)
+    pub fn from_field(field: Option<CommitmentScheme>) -> Self {
+        field.unwrap_or(CommitmentScheme::LegacyConcat)
+    }
+
+    /// String-Repräsentation (identisch zur Serialisierung)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CommitmentScheme::LegacyConcat => "blake3-concat.v0",
+            CommitmentScheme::MerkleV1 => "blake3-merkle.v1",
+            CommitmentScheme::BlindedMerkleV2 => "blake3-blinded-merkle.v2",
+        }
+    }
+}
+
+/// Private Blinding-Nonces je Datensatz (Schema blake3-blinded-merkle.v2)
+///
+/// Die Nonces gehören nie in Proof, Bundle oder Registry: Inclusion-Proofs
+/// enthalten nur die daraus abgeleiteten Salts der offengelegten Felder.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordNonces {
+    /// Eine Nonce (0x-Hex, 32 Byte) je Supplier in Commitment-Reihenfolge
+    pub suppliers: Vec<String>,
+    /// Eine Nonce (0x-Hex, 32 Byte) je UBO in Commitment-Reihenfolge
+    pub ubos: Vec<String>,
+}
+
+impl JsonPersistent for RecordNonces {}
+
+impl RecordNonces {
+    /// Erzeugt zufällige Nonces für die gegebenen Datensatz-Anzahlen
+    pub fn generate(supplier_count: usize, ubo_count: usize) -> Self {
+        let generate = |count: usize| {
+            (0..count)
+                .map(|_| {
+                    let mut nonce = [0u8; 32];
+                    rand::rngs::OsRng.fill_bytes(&mut nonce);
+                    merkle::to_hex(&nonce)
+                })
+                .collect()
+        };
+        RecordNonces {
+            suppliers: generate(supplier_count),
+            ubos: generate(ubo_count),
+        }
+    }
+}
+
+/// Pfad der Nonce-Datei zu einer Commitments-Datei
+///
+/// # Argumente
+/// * `commitments_path` - Pfad zu commitments.json
+///
+/// # Rückgabe
+/// `commitment_nonces.json` im selben Verzeichnis
+pub fn nonces_path<P: AsRef<Path>>(commitments_path: P) -> PathBuf {
+    commitments_path.as_ref().with_file_name(NONCES_FILE_NAME)
+}
 
 /// Struktur für alle generierten Commitments (Merkle-Roots)
 #[derive(Debug, Serialize, Deserialize)]
@@ -17,6 +109,9 @@ pub struct Commitments {
     pub supplier_count: Option<usize>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub ubo_count: Option<usize>,
+    /// Commitment-Schema (None = Legacy, vor v0.13)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commitment_scheme: Option<CommitmentScheme>,
 }
 
 impl Commitments {
@@ -31,6 +126,36 @@ impl Commitments {
     pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, Box<dyn Error>> {
         load_commitments(path)
     }
+
+    /// Effektives Commitment-Schema dieser Commitments
+    pub fn scheme(&self) -> CommitmentScheme {
+        CommitmentScheme::from_field(self.commitment_scheme)
+    }
+
+    /// Prüft, ob die Roots zu den gegebenen Rohdaten passen
+    ///
+    /// Verwendet das in den Commitments vermerkte Schema, sodass auch
+    /// Legacy-Commitments weiterhin nachgerechnet werden können. Die Nonces
+    /// werden nur für blake3-blinded-merkle.v2 benötigt.
+    ///
+    /// # Rückgabe
+    /// true wenn supplier_root, ubo_root und company_commitment_root übereinstimmen
+    #[allow(dead_code)] // Public API - used by lib consumers
+    pub fn verify_against(
+        &self,
+        suppliers: &[Supplier],
+        ubos: &[Ubo],
+        nonces: &RecordNonces,
+    ) -> Result<bool, Box<dyn Error>> {
+        let scheme = self.scheme();
+        let supplier_root = compute_supplier_root_with(suppliers, scheme, &nonces.suppliers)?;
+        let ubo_root = compute_ubo_root_with(ubos, scheme, &nonces.ubos)?;
+        let company_root = compute_company_root(&supplier_root, &ubo_root);
+
+        Ok(supplier_root == self.supplier_root
+            && ubo_root == self.ubo_root
+            && company_root == self.company_commitment_root)
+    }
 }
 
 /// Berechnet einen BLAKE3-Hash für einen einzelnen Datensatz
@@ -46,17 +171,169 @@ fn hash_record<T: Serialize>(data: &T) -> Result<String, Box<dyn Error>> {
     Ok(format!("0x{}", hash.to_hex()))
 }
 
-/// Berechnet einen Merkle-Root aus einer Liste von Hashes
+/// Berechnet den kanonischen Blatt-Hash eines Datensatzes (Merkle v1)
+///
+/// Blattinhalt ist die kompakte JSON-Serialisierung des Datensatzes.
+///
+/// # Argumente
+/// * `data` - Serialisierbarer Datensatz
+///
+/// # Rückgabe
+/// Domain-separierter Blatt-Hash
+pub fn record_leaf_hash<T: Serialize>(data: &T) -> Result<merkle::Hash32, Box<dyn Error>> {
+    let json = serde_json::to_string(data)?;
+    Ok(merkle::leaf_hash(json.as_bytes()))
+}
+
+/// Baut den Merkle-Tree (v1) über eine Liste von Datensätzen
+///
+/// # Argumente
+/// * `records` - Datensätze in Commitment-Reihenfolge
+///
+/// # Rückgabe
+/// Vollständiger Merkle-Tree
+pub fn build_record_tree<T: Serialize>(records: &[T]) -> Result<MerkleTree, Box<dyn Error>> {
+    let leaf_hashes = records
+        .iter()
+        .map(record_leaf_hash)
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(MerkleTree::from_leaf_hashes(leaf_hashes))
+}
+
+/// Felder eines Datensatzes in kanonischer (alphabetischer) Reihenfolge
+///
+/// # Argumente
+/// * `data` - Serialisierbarer Datensatz (JSON-Objekt)
+///
+/// # Rückgabe
+/// Feldname → Wert
+pub fn record_fields<T: Serialize>(
+    data: &T,
+) -> Result<BTreeMap<String, serde_json::Value>, Box<dyn Error>> {
+    match serde_json::to_value(data)? {
+        serde_json::Value::Object(map) => Ok(map.into_iter().collect()),
+        other => Err(format!("Datensatz ist kein JSON-Objekt: {}", other).into()),
+    }
+}
+
+/// Leitet den Salt eines Feldes aus der Datensatz-Nonce ab
+///
+/// Keyed BLAKE3 über den Feldnamen: Ein offengelegter Salt verrät weder die
+/// Nonce noch die Salts der übrigen Felder.
+pub fn field_salt(nonce: &merkle::Hash32, field: &str) -> merkle::Hash32 {
+    *blake3::keyed_hash(nonce, field.as_bytes()).as_bytes()
+}
+
+/// Berechnet das Commitment eines einzelnen Feldes (Merkle v2)
+///
+/// # Argumente
+/// * `salt` - Feld-Salt aus [`field_salt`]
+/// * `field` - Feldname
+/// * `value` - Feldwert (kompakte JSON-Serialisierung)
+pub fn field_commitment(
+    salt: &merkle::Hash32,
+    field: &str,
+    value: &serde_json::Value,
+) -> Result<merkle::Hash32, Box<dyn Error>> {
+    let mut hasher = Hasher::new();
+    hasher.update(FIELD_DOMAIN);
+    hasher.update(salt);
+    hasher.update(field.as_bytes());
+    hasher.update(&[0]);
+    hasher.update(serde_json::to_string(value)?.as_bytes());
+    Ok(*hasher.finalize().as_bytes())
+}
+
+/// Berechnet den Blatt-Hash aus den Feld-Commitments eines Datensatzes
+///
+/// # Argumente
+/// * `commitments` - Feld-Commitments in kanonischer Feldreihenfolge
+pub fn blinded_leaf_hash<'a, I>(commitments: I) -> merkle::Hash32
+where
+    I: IntoIterator<Item = &'a merkle::Hash32>,
+{
+    let bytes: Vec<u8> = commitments.into_iter().flatten().copied().collect();
+    merkle::leaf_hash(&bytes)
+}
+
+/// Berechnet die Feld-Commitments eines Datensatzes mit seiner Nonce
+///
+/// # Argumente
+/// * `data` - Serialisierbarer Datensatz
+/// * `nonce` - Private Nonce des Datensatzes (0x-Hex)
 ///
-/// Diese Implementierung hasht alle einzelnen Hashes konkateniert.
-/// In einer Produktionsumgebung würde man einen echten Merkle-Tree verwenden.
+/// # Rückgabe
+/// Feldname → Commitment, in kanonischer Reihenfolge
+pub fn record_field_commitments<T: Serialize>(
+    data: &T,
+    nonce: &str,
+) -> Result<BTreeMap<String, merkle::Hash32>, Box<dyn Error>> {
+    let nonce = merkle::from_hex(nonce)?;
+    record_fields(data)?
+        .into_iter()
+        .map(|(field, value)| {
+            let commitment = field_commitment(&field_salt(&nonce, &field), &field, &value)?;
+            Ok((field, commitment))
+        })
+        .collect()
+}
+
+/// Berechnet den gesalzenen Blatt-Hash eines Datensatzes (Merkle v2)
+///
+/// # Argumente
+/// * `data` - Serialisierbarer Datensatz
+/// * `nonce` - Private Nonce des Datensatzes (0x-Hex)
+///
+/// # Rückgabe
+/// Domain-separierter Blatt-Hash über die Feld-Commitments
+pub fn record_blinded_leaf_hash<T: Serialize>(
+    data: &T,
+    nonce: &str,
+) -> Result<merkle::Hash32, Box<dyn Error>> {
+    Ok(blinded_leaf_hash(
+        record_field_commitments(data, nonce)?.values(),
+    ))
+}
+
+/// Baut den gesalzenen Merkle-Tree (v2) über eine Liste von Datensätzen
+///
+/// # Argumente
+/// * `records` - Datensätze in Commitment-Reihenfolge
+/// * `nonces` - Eine Nonce je Datensatz (aus [`RecordNonces`])
+///
+/// # Rückgabe
+/// Vollständiger Merkle-Tree
+pub fn build_blinded_tree<T: Serialize>(
+    records: &[T],
+    nonces: &[String],
+) -> Result<MerkleTree, Box<dyn Error>> {
+    if records.len() != nonces.len() {
+        return Err(format!(
+            "{} Datensätze, aber {} Nonces – passt die Nonce-Datei zu den Commitments?",
+            records.len(),
+            nonces.len()
+        )
+        .into());
+    }
+    let leaf_hashes = records
+        .iter()
+        .zip(nonces)
+        .map(|(record, nonce)| record_blinded_leaf_hash(record, nonce))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(MerkleTree::from_leaf_hashes(leaf_hashes))
+}
+
+/// Berechnet einen Legacy-Root aus einer Liste von Hashes (Schema blake3-concat.v0)
+///
+/// Hasht alle einzelnen Hashes konkateniert. Bleibt nur erhalten, damit
+/// bestehende Manifeste weiterhin verifiziert werden können.
 ///
 /// # Argumente
 /// * `hashes` - Liste von Hash-Strings
 ///
 /// # Rückgabe
 /// Root-Hash als Hex-String
-fn compute_merkle_root(hashes: &[String]) -> String {
+fn compute_legacy_root(hashes: &[String]) -> String {
     let mut hasher = Hasher::new();
     for hash in hashes {
         hasher.update(hash.as_bytes());
@@ -65,34 +342,84 @@ fn compute_merkle_root(hashes: &[String]) -> String {
     format!("0x{}", result.to_hex())
 }
 
-/// Berechnet den Merkle-Root für Supplier-Daten
+/// Berechnet den Root über Datensätze im angegebenen Schema
+fn compute_records_root<T: Serialize>(
+    records: &[T],
+    scheme: CommitmentScheme,
+    nonces: &[String],
+) -> Result<String, Box<dyn Error>> {
+    match scheme {
+        CommitmentScheme::LegacyConcat => {
+            let hashes = records
+                .iter()
+                .map(hash_record)
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(compute_legacy_root(&hashes))
+        }
+        CommitmentScheme::MerkleV1 => Ok(build_record_tree(records)?.root_hex()),
+        CommitmentScheme::BlindedMerkleV2 => Ok(build_blinded_tree(records, nonces)?.root_hex()),
+    }
+}
+
+/// Berechnet den Merkle-Root für Supplier-Daten (aktuelles Schema)
 ///
 /// # Argumente
 /// * `suppliers` - Vector mit Supplier-Objekten
+/// * `nonces` - Eine private Nonce je Datensatz
 ///
 /// # Rückgabe
 /// Root-Hash als Hex-String
-pub fn compute_supplier_root(suppliers: &[Supplier]) -> Result<String, Box<dyn Error>> {
-    let mut hashes = Vec::new();
-    for supplier in suppliers {
-        hashes.push(hash_record(supplier)?);
-    }
-    Ok(compute_merkle_root(&hashes))
+pub fn compute_supplier_root(
+    suppliers: &[Supplier],
+    nonces: &[String],
+) -> Result<String, Box<dyn Error>> {
+    compute_supplier_root_with(suppliers, CommitmentScheme::CURRENT, nonces)
 }
 
-/// Berechnet den Merkle-Root für UBO-Daten
+/// Berechnet den Supplier-Root im angegebenen Commitment-Schema
+///
+/// # Argumente
+/// * `suppliers` - Vector mit Supplier-Objekten
+/// * `scheme` - Commitment-Schema
+/// * `nonces` - Private Nonces (nur für blake3-blinded-merkle.v2, sonst ignoriert)
+///
+/// # Rückgabe
+/// Root-Hash als Hex-String
+pub fn compute_supplier_root_with(
+    suppliers: &[Supplier],
+    scheme: CommitmentScheme,
+    nonces: &[String],
+) -> Result<String, Box<dyn Error>> {
+    compute_records_root(suppliers, scheme, nonces)
+}
+
+/// Berechnet den Merkle-Root für UBO-Daten (aktuelles Schema)
 ///
 /// # Argumente
 /// * `ubos` - Vector mit UBO-Objekten
+/// * `nonces` - Eine private Nonce je Datensatz
 ///
 /// # Rückgabe
 /// Root-Hash als Hex-String
-pub fn compute_ubo_root(ubos: &[Ubo]) -> Result<String, Box<dyn Error>> {
-    let mut hashes = Vec::new();
-    for ubo in ubos {
-        hashes.push(hash_record(ubo)?);
-    }
-    Ok(compute_merkle_root(&hashes))
+pub fn compute_ubo_root(ubos: &[Ubo], nonces: &[String]) -> Result<String, Box<dyn Error>> {
+    compute_ubo_root_with(ubos, CommitmentScheme::CURRENT, nonces)
+}
+
+/// Berechnet den UBO-Root im angegebenen Commitment-Schema
+///
+/// # Argumente
+/// * `ubos` - Vector mit UBO-Objekten
+/// * `scheme` - Commitment-Schema
+/// * `nonces` - Private Nonces (nur für blake3-blinded-merkle.v2, sonst ignoriert)
+///
+/// # Rückgabe
+/// Root-Hash als Hex-String
+pub fn compute_ubo_root_with(
+    ubos: &[Ubo],
+    scheme: CommitmentScheme,
+    nonces: &[String],
+) -> Result<String, Box<dyn Error>> {
+    compute_records_root(ubos, scheme, nonces)
 }
 
 /// Berechnet den Company-Commitment-Root aus Supplier- und UBO-Roots
@@ -157,13 +484,71 @@ mod tests {
     }
 
     #[test]
-    fn test_merkle_root_deterministic() {
+    fn test_legacy_root_deterministic() {
         let hashes = vec!["0xabc".to_string(), "0xdef".to_string()];
-        let root1 = compute_merkle_root(&hashes);
-        let root2 = compute_merkle_root(&hashes);
+        let root1 = compute_legacy_root(&hashes);
+        let root2 = compute_legacy_root(&hashes);
         assert_eq!(root1, root2);
     }
 
+    #[test]
+    fn test_legacy_scheme_matches_previous_algorithm() {
+        let suppliers = vec![
+            Supplier {
+                name: "A".to_string(),
+                jurisdiction: "DE".to_string(),
+                tier: 1,
+            },
+            Supplier {
+                name: "B".to_string(),
+                jurisdiction: "FR".to_string(),
+                tier: 2,
+            },
+        ];
+        let hashes: Vec<String> = suppliers.iter().map(|s| hash_record(s).unwrap()).collect();
+        let legacy =
+            compute_supplier_root_with(&suppliers, CommitmentScheme::LegacyConcat, &[]).unwrap();
+        assert_eq!(legacy, compute_legacy_root(&hashes));
+        let nonces = RecordNonces::generate(suppliers.len(), 0);
+        assert_ne!(
+            legacy,
+            compute_supplier_root(&suppliers, &nonces.suppliers).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_missing_scheme_is_legacy() {
+        let json = r#"{"supplier_root":"0x1","ubo_root":"0x2","company_commitment_root":"0x3"}"#;
+        let commitments: Commitments = serde_json::from_str(json).unwrap();
+        assert_eq!(commitments.scheme(), CommitmentScheme::LegacyConcat);
+        assert!(!serde_json::to_string(&commitments)
+            .unwrap()
+            .contains("commitment_scheme"));
+    }
+
+    #[test]
+    fn test_blinded_root_depends_on_nonces() {
+        let suppliers = vec![Supplier {
+            name: "A".to_string(),
+            jurisdiction: "DE".to_string(),
+            tier: 1,
+        }];
+        let first = RecordNonces::generate(1, 0);
+        let second = RecordNonces::generate(1, 0);
+
+        let root = compute_supplier_root(&suppliers, &first.suppliers).unwrap();
+        assert_eq!(
+            root,
+            compute_supplier_root(&suppliers, &first.suppliers).unwrap()
+        );
+        // Ohne Nonce lässt sich der Root nicht aus dem Klartext nachrechnen
+        assert_ne!(
+            root,
+            compute_supplier_root(&suppliers, &second.suppliers).unwrap()
+        );
+        assert!(compute_supplier_root(&suppliers, &[]).is_err());
+    }
+
     #[test]
     fn test_company_root() {
         let supplier_root = "0xabc123";
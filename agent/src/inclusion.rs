@@ -0,0 +1,440 @@
+//! Inclusion-Proofs für einzelne Supplier- und UBO-Datensätze
+//!
+//! Ermöglicht den Nachweis, dass ein einzelner Datensatz Teil des committeten
+//! `supplier_root` bzw. `ubo_root` ist, ohne die übrigen Datensätze offenzulegen.
+//! Setzt das Commitment-Schema `blake3-blinded-merkle.v2` voraus: Offengelegt
+//! werden nur die angefragten Felder samt Feld-Salt, alle anderen Felder nur
+//! als gesalzenes Commitment. Die Datensatz-Nonce bleibt privat.
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::error::Error;
+
+use crate::commitment::{
+    blinded_leaf_hash, build_blinded_tree, field_commitment, field_salt, record_fields,
+    CommitmentScheme,
+};
+use crate::io::{JsonPersistent, Supplier, Ubo};
+use crate::manifest::Manifest;
+use crate::merkle;
+
+/// Format-Version der Inclusion-Proof-Datei
+pub const INCLUSION_PROOF_VERSION: &str = "cap-inclusion-proof.v2";
+
+/// Art des bewiesenen Datensatzes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RecordKind {
+    Supplier,
+    Ubo,
+}
+
+impl std::str::FromStr for RecordKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "supplier" => Ok(RecordKind::Supplier),
+            "ubo" => Ok(RecordKind::Ubo),
+            other => Err(format!(
+                "Unbekannter Datensatz-Typ: {} (erwartet: supplier|ubo)",
+                other
+            )),
+        }
+    }
+}
+
+impl RecordKind {
+    /// Felder des Datensatz-Typs in kanonischer Reihenfolge
+    pub fn fields(&self) -> &'static [&'static str] {
+        match self {
+            RecordKind::Supplier => &["jurisdiction", "name", "tier"],
+            RecordKind::Ubo => &["birthdate", "citizenship", "name"],
+        }
+    }
+}
+
+impl std::fmt::Display for RecordKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RecordKind::Supplier => write!(f, "supplier"),
+            RecordKind::Ubo => write!(f, "ubo"),
+        }
+    }
+}
+
+/// Offengelegtes Feld eines Datensatzes
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DisclosedField {
+    pub value: serde_json::Value,
+    /// Feld-Salt (0x-Hex), aus der privaten Datensatz-Nonce abgeleitet
+    pub salt: String,
+}
+
+/// Inclusion-Proof für einen einzelnen Datensatz
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordInclusionProof {
+    pub version: String,
+    pub kind: RecordKind,
+    pub commitment_scheme: CommitmentScheme,
+    /// Offengelegte Felder (einziger Klartext im Proof)
+    pub disclosed: BTreeMap<String, DisclosedField>,
+    /// Commitments der nicht offengelegten Felder (0x-Hex)
+    #[serde(default)]
+    pub hidden: BTreeMap<String, String>,
+    pub leaf_index: usize,
+    pub tree_size: usize,
+    pub leaf_hash: String,
+    /// Geschwister-Hashes von Blatt- zu Root-Ebene
+    pub path: Vec<String>,
+    pub root: String,
+    pub created_at: String,
+}
+
+impl JsonPersistent for RecordInclusionProof {}
+
+/// Prüft, dass alle angefragten Felder zum Datensatz-Typ gehören
+fn check_fields(kind: RecordKind, fields: &[&str]) -> Result<(), Box<dyn Error>> {
+    match fields.iter().find(|f| !kind.fields().contains(f)) {
+        Some(unknown) => Err(format!(
+            "Unbekanntes {}-Feld: {} (erwartet: {})",
+            kind,
+            unknown,
+            kind.fields().join("|")
+        )
+        .into()),
+        None => Ok(()),
+    }
+}
+
+/// Erzeugt einen Inclusion-Proof für einen Datensatz einer Liste
+fn prove_record<T: Serialize>(
+    kind: RecordKind,
+    records: &[T],
+    nonces: &[String],
+    index: usize,
+    reveal: &[&str],
+) -> Result<RecordInclusionProof, Box<dyn Error>> {
+    let tree = build_blinded_tree(records, nonces)?;
+    prove_in_tree(kind, &tree, records, nonces, index, reveal)
+}
+
+/// Erzeugt einen Inclusion-Proof aus einem bereits gebauten Record-Tree
+///
+/// Erlaubt Proofs für viele Datensätze derselben Liste, ohne den Baum
+/// jedes Mal neu zu bauen. Nur die Felder in `reveal` werden offengelegt.
+pub(crate) fn prove_in_tree<T: Serialize>(
+    kind: RecordKind,
+    tree: &merkle::MerkleTree,
+    records: &[T],
+    nonces: &[String],
+    index: usize,
+    reveal: &[&str],
+) -> Result<RecordInclusionProof, Box<dyn Error>> {
+    check_fields(kind, reveal)?;
+    let path = tree.inclusion_path(index).ok_or_else(|| {
+        format!(
+            "Index {} außerhalb der {}-Liste (Größe {})",
+            index,
+            kind,
+            records.len()
+        )
+    })?;
+    let nonce = merkle::from_hex(nonces.get(index).ok_or("Nonce für Datensatz fehlt")?)?;
+
+    let mut commitments = Vec::new();
+    let mut disclosed = BTreeMap::new();
+    let mut hidden = BTreeMap::new();
+    for (field, value) in record_fields(&records[index])? {
+        let salt = field_salt(&nonce, &field);
+        let commitment = field_commitment(&salt, &field, &value)?;
+        commitments.push(commitment);
+        if reveal.contains(&field.as_str()) {
+            disclosed.insert(
+                field,
+                DisclosedField {
+                    value,
+                    salt: merkle::to_hex(&salt),
+                },
+            );
+        } else {
+            hidden.insert(field, merkle::to_hex(&commitment));
+        }
+    }
+
+    Ok(RecordInclusionProof {
+        version: INCLUSION_PROOF_VERSION.to_string(),
+        kind,
+        commitment_scheme: CommitmentScheme::BlindedMerkleV2,
+        disclosed,
+        hidden,
+        leaf_index: index,
+        tree_size: tree.len(),
+        leaf_hash: merkle::to_hex(&blinded_leaf_hash(&commitments)),
+        path: path.iter().map(merkle::to_hex).collect(),
+        root: tree.root_hex(),
+        created_at: Utc::now().to_rfc3339(),
+    })
+}
+
+/// Erzeugt einen Inclusion-Proof für den Supplier an Position `index`
+///
+/// # Argumente
+/// * `suppliers` - Vollständige Supplier-Liste in Commitment-Reihenfolge
+/// * `nonces` - Private Nonces der Suppliers
+/// * `index` - Position des zu beweisenden Suppliers
+/// * `reveal` - Offenzulegende Felder (z.B. `["name"]`)
+///
+/// # Rückgabe
+/// Inclusion-Proof gegen den Supplier-Root
+pub fn prove_supplier(
+    suppliers: &[Supplier],
+    nonces: &[String],
+    index: usize,
+    reveal: &[&str],
+) -> Result<RecordInclusionProof, Box<dyn Error>> {
+    prove_record(RecordKind::Supplier, suppliers, nonces, index, reveal)
+}
+
+/// Erzeugt einen Inclusion-Proof für den UBO an Position `index`
+///
+/// # Argumente
+/// * `ubos` - Vollständige UBO-Liste in Commitment-Reihenfolge
+/// * `nonces` - Private Nonces der UBOs
+/// * `index` - Position des zu beweisenden UBOs
+/// * `reveal` - Offenzulegende Felder (z.B. `["name"]`)
+///
+/// # Rückgabe
+/// Inclusion-Proof gegen den UBO-Root
+pub fn prove_ubo(
+    ubos: &[Ubo],
+    nonces: &[String],
+    index: usize,
+    reveal: &[&str],
+) -> Result<RecordInclusionProof, Box<dyn Error>> {
+    prove_record(RecordKind::Ubo, ubos, nonces, index, reveal)
+}
+
+/// Ermittelt den erwarteten Root für einen Datensatz-Typ aus einem Manifest
+///
+/// # Rückgabe
+/// `supplier_root` bzw. `ubo_root`; Fehler bei Manifesten ohne gesalzene Blätter
+pub fn expected_root(manifest: &Manifest, kind: RecordKind) -> Result<String, Box<dyn Error>> {
+    let scheme = CommitmentScheme::from_field(manifest.commitment_scheme);
+    if scheme != CommitmentScheme::BlindedMerkleV2 {
+        return Err(format!(
+            "Manifest verwendet Commitment-Schema {} – Inclusion-Proofs benötigen {}",
+            scheme.as_str(),
+            CommitmentScheme::BlindedMerkleV2.as_str()
+        )
+        .into());
+    }
+
+    Ok(match kind {
+        RecordKind::Supplier => manifest.supplier_root.clone(),
+        RecordKind::Ubo => manifest.ubo_root.clone(),
+    })
+}
+
+impl RecordInclusionProof {
+    /// Wert eines offengelegten Feldes
+    #[allow(dead_code)] // Public API - used by lib consumers (lists)
+    pub fn field(&self, name: &str) -> Option<&serde_json::Value> {
+        self.disclosed.get(name).map(|f| &f.value)
+    }
+
+    /// Reduziert die Offenlegung auf die angegebenen Felder
+    ///
+    /// Alle übrigen offengelegten Felder werden durch ihr Commitment ersetzt;
+    /// der Proof bleibt gegen denselben Root gültig.
+    ///
+    /// # Rückgabe
+    /// Reduzierter Proof; Fehler, wenn ein angefragtes Feld nicht offengelegt ist
+    #[allow(dead_code)] // Public API - used by lib consumers (lists)
+    pub fn disclose_only(&self, fields: &[&str]) -> Result<Self, Box<dyn Error>> {
+        check_fields(self.kind, fields)?;
+        let mut reduced = self.clone();
+        for field in fields {
+            if !self.disclosed.contains_key(*field) {
+                return Err(format!(
+                    "{} #{}: Feld '{}' ist nicht offengelegt",
+                    self.kind, self.leaf_index, field
+                )
+                .into());
+            }
+        }
+        for (field, disclosed) in &self.disclosed {
+            if !fields.contains(&field.as_str()) {
+                let salt = merkle::from_hex(&disclosed.salt)?;
+                let commitment = field_commitment(&salt, field, &disclosed.value)?;
+                reduced.disclosed.remove(field);
+                reduced
+                    .hidden
+                    .insert(field.clone(), merkle::to_hex(&commitment));
+            }
+        }
+        Ok(reduced)
+    }
+
+    /// Berechnet den Blatt-Hash aus offengelegten Feldern und Commitments
+    fn compute_leaf_hash(&self) -> Result<merkle::Hash32, Box<dyn Error>> {
+        let mut commitments = BTreeMap::new();
+        for (field, disclosed) in &self.disclosed {
+            let salt = merkle::from_hex(&disclosed.salt)?;
+            commitments.insert(
+                field.as_str(),
+                field_commitment(&salt, field, &disclosed.value)?,
+            );
+        }
+        for (field, commitment) in &self.hidden {
+            if commitments
+                .insert(field.as_str(), merkle::from_hex(commitment)?)
+                .is_some()
+            {
+                return Err(format!("Feld '{}' zugleich offengelegt und verdeckt", field).into());
+            }
+        }
+        // Vollständige Feldmenge, sonst ließe sich ein Feld unterschlagen
+        if !commitments.keys().eq(self.kind.fields().iter()) {
+            return Err(format!(
+                "Felder passen nicht zum Datensatz-Typ {} (erwartet: {})",
+                self.kind,
+                self.kind.fields().join(", ")
+            )
+            .into());
+        }
+        Ok(blinded_leaf_hash(commitments.values()))
+    }
+
+    /// Verifiziert den Proof offline gegen einen erwarteten Root
+    ///
+    /// Prüft Format-Version, Schema, Blatt-Hash aus offengelegten Feldern und
+    /// Feld-Commitments sowie den Inclusion-Pfad.
+    ///
+    /// # Argumente
+    /// * `expected_root` - Root aus Manifest oder Commitments (0x-Hex)
+    pub fn verify(&self, expected_root: &str) -> Result<(), Box<dyn Error>> {
+        if self.version != INCLUSION_PROOF_VERSION {
+            return Err(format!("Nicht unterstützte Proof-Version: {}", self.version).into());
+        }
+        if self.commitment_scheme != CommitmentScheme::BlindedMerkleV2 {
+            return Err(format!(
+                "Nicht unterstütztes Commitment-Schema: {}",
+                self.commitment_scheme.as_str()
+            )
+            .into());
+        }
+
+        let leaf = self.compute_leaf_hash()?;
+        if merkle::to_hex(&leaf) != self.leaf_hash.to_lowercase() {
+            return Err("Blatt-Hash passt nicht zu den offengelegten Feldern".into());
+        }
+
+        let root = merkle::from_hex(expected_root)?;
+        if merkle::from_hex(&self.root)? != root {
+            return Err(format!(
+                "Root im Proof ({}) stimmt nicht mit erwartetem Root überein ({})",
+                self.root, expected_root
+            )
+            .into());
+        }
+
+        let path = self
+            .path
+            .iter()
+            .map(|h| merkle::from_hex(h))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if !merkle::verify_inclusion(&leaf, self.leaf_index, self.tree_size, &path, &root) {
+            return Err("Inclusion-Pfad ungültig".into());
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commitment::{compute_supplier_root, RecordNonces};
+
+    fn suppliers() -> Vec<Supplier> {
+        (0..5)
+            .map(|i| Supplier {
+                name: format!("Supplier {}", i),
+                jurisdiction: "DE".to_string(),
+                tier: 1,
+            })
+            .collect()
+    }
+
+    fn nonces() -> Vec<String> {
+        RecordNonces::generate(5, 0).suppliers
+    }
+
+    #[test]
+    fn test_supplier_proof_verifies_against_root() {
+        let suppliers = suppliers();
+        let nonces = nonces();
+        let root = compute_supplier_root(&suppliers, &nonces).unwrap();
+
+        for index in 0..suppliers.len() {
+            let proof = prove_supplier(&suppliers, &nonces, index, &["name"]).unwrap();
+            assert_eq!(proof.root, root);
+            proof.verify(&root).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_only_requested_fields_are_disclosed() {
+        let suppliers = suppliers();
+        let nonces = nonces();
+        let root = compute_supplier_root(&suppliers, &nonces).unwrap();
+
+        let proof = prove_supplier(&suppliers, &nonces, 1, &["name"]).unwrap();
+        assert_eq!(proof.field("name"), Some(&serde_json::json!("Supplier 1")));
+        assert!(proof.field("jurisdiction").is_none());
+        let json = serde_json::to_string(&proof).unwrap();
+        assert!(!json.contains("\"DE\""), "{}", json);
+        assert!(!json.contains(&nonces[1]), "{}", json);
+
+        // Nachträgliche Reduktion bleibt gegen denselben Root gültig
+        let full = prove_supplier(&suppliers, &nonces, 1, RecordKind::Supplier.fields()).unwrap();
+        let reduced = full.disclose_only(&["name"]).unwrap();
+        assert_eq!(reduced.disclosed, proof.disclosed);
+        reduced.verify(&root).unwrap();
+        assert!(proof.disclose_only(&["tier"]).is_err());
+        assert!(prove_supplier(&suppliers, &nonces, 1, &["iban"]).is_err());
+    }
+
+    #[test]
+    fn test_tampered_record_is_rejected() {
+        let suppliers = suppliers();
+        let nonces = nonces();
+        let root = compute_supplier_root(&suppliers, &nonces).unwrap();
+
+        let mut proof = prove_supplier(&suppliers, &nonces, 2, &["jurisdiction"]).unwrap();
+        proof.disclosed.get_mut("jurisdiction").unwrap().value = serde_json::json!("FR");
+        assert!(proof.verify(&root).is_err());
+
+        // Ein verdecktes Feld darf nicht wegfallen
+        let mut proof = prove_supplier(&suppliers, &nonces, 2, &["name"]).unwrap();
+        proof.hidden.remove("tier");
+        assert!(proof.verify(&root).is_err());
+    }
+
+    #[test]
+    fn test_wrong_root_is_rejected() {
+        let suppliers = suppliers();
+        let nonces = nonces();
+        let proof = prove_supplier(&suppliers, &nonces, 0, &["name"]).unwrap();
+        let other_root = compute_supplier_root(&suppliers[..4], &nonces[..4]).unwrap();
+        assert!(proof.verify(&other_root).is_err());
+    }
+
+    #[test]
+    fn test_index_out_of_range() {
+        assert!(prove_supplier(&suppliers(), &nonces(), 5, &["name"]).is_err());
+    }
+}
@@ -6,6 +6,9 @@
 // Core cryptographic modules
 pub mod commitment;
 pub mod crypto;
+pub mod merkle;
+pub mod sorted_merkle;
+pub mod lists;
 pub mod sign;
 
 // Audit and registry
@@ -15,6 +18,7 @@ pub mod registry;
 // Bundle handling
 pub mod bundle;
 pub mod manifest;
+pub mod inclusion;
 pub mod io;
 pub mod blob_store;
 
@@ -0,0 +1,876 @@
+//! Sanktions- und Jurisdiktions-Listen
+//!
+//! Normalisiert Listeneinträge, baut daraus einen [`SortedMerkleTree`] und
+//! erzeugt Ausschluss-Nachweise (Non-Membership) für committete Datensätze,
+//! z.B. alle Supplier-Jurisdiktionen gegen einen Jurisdiktions-Blocklist-Root.
+//!
+//! Jeder geprüfte Wert ist über das offengelegte Prüffeld (mit Feld-Salt)
+//! und den Inclusion-Pfad seines Datensatzes an `supplier_root`/`ubo_root`
+//! gebunden; alle übrigen Felder bleiben verdeckt. Die Verifikation verlangt
+//! je geprüftem Datensatz-Typ alle committeten Datensätze.
+//!
+//! Der vollständige Nachweis ([`ListExclusionWitness`]) enthält geprüfte
+//! Namen und Jurisdiktionen und bleibt deshalb in einer privaten
+//! Witness-Datei ([`ListWitnessFile`]). Der Proof trägt nur die
+//! [`ListExclusionClaim`]: Listen-Root, Anzahl geprüfter Datensätze und den
+//! Hash des privaten Nachweises.
+
+pub mod normalize;
+pub mod root_file;
+
+pub use normalize::NORMALIZATION_VERSION;
+pub use root_file::{ListRootFile, LIST_ROOT_VERSION};
+
+use csv::ReaderBuilder;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::error::Error;
+use std::path::Path;
+
+use crate::commitment::build_blinded_tree;
+use crate::inclusion::prove_in_tree;
+use crate::merkle;
+use crate::sorted_merkle::{NonMembershipProof, SortedMerkleTree};
+
+pub use crate::inclusion::{RecordInclusionProof, RecordKind};
+
+/// Format-Version der privaten Witness-Datei
+pub const LIST_WITNESSES_VERSION: &str = "cap-list-witnesses.v1";
+
+/// Art der Liste
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ListKind {
+    /// Sanktionsliste (Personen-/Firmennamen)
+    Sanctions,
+    /// Jurisdiktions-Blocklist (ISO-Ländercodes)
+    Jurisdictions,
+}
+
+impl ListKind {
+    /// Name des zugehörigen Statement-Roots im Manifest
+    pub fn statement_root_key(&self) -> &'static str {
+        match self {
+            ListKind::Sanctions => "sanctions_root",
+            ListKind::Jurisdictions => "jurisdiction_root",
+        }
+    }
+
+    /// CSV-Spalte mit dem Listeneintrag
+    pub fn csv_column(&self) -> &'static str {
+        match self {
+            ListKind::Sanctions => "name",
+            ListKind::Jurisdictions => "iso_code",
+        }
+    }
+
+    /// Normalisiert einen Eintrag bzw. Prüfwert
+    ///
+    /// Listeneinträge und geprüfte Werte müssen identisch normalisiert
+    /// werden, sonst ist ein Non-Membership-Proof wertlos.
+    pub fn normalize(&self, raw: &str) -> String {
+        match self {
+            ListKind::Sanctions => normalize::fold_name(raw),
+            ListKind::Jurisdictions => normalize::normalize_country_code(raw),
+        }
+    }
+
+    /// Feld eines Datensatzes, das gegen die Liste geprüft wird
+    ///
+    /// Sanktionen: Name; Jurisdiktionen: Supplier-Jurisdiktion bzw.
+    /// UBO-Staatsangehörigkeit.
+    pub fn record_field(&self, record: RecordKind) -> &'static str {
+        match (self, record) {
+            (ListKind::Sanctions, _) => "name",
+            (ListKind::Jurisdictions, RecordKind::Supplier) => "jurisdiction",
+            (ListKind::Jurisdictions, RecordKind::Ubo) => "citizenship",
+        }
+    }
+
+    /// Normalisierter Prüfwert eines offengelegten Datensatzes
+    ///
+    /// Werte, die sich nicht zu einem gültigen Listeneintrag normalisieren
+    /// lassen (z.B. "Russia" statt "RU"), sind nicht prüfbar und werden
+    /// abgelehnt – sonst wäre ihr Ausschluss trivial beweisbar.
+    fn screened_value(&self, record: &RecordInclusionProof) -> Result<String, String> {
+        let field = self.record_field(record.kind);
+        let raw = record
+            .field(field)
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| {
+                format!(
+                    "{} #{} ohne Feld '{}'",
+                    record.kind, record.leaf_index, field
+                )
+            })?;
+        let value = self.normalize(raw);
+        if !self.is_valid_entry(&value) {
+            return Err(format!(
+                "{} #{}: '{}' ist kein gültiger {}-Wert",
+                record.kind, record.leaf_index, raw, self
+            ));
+        }
+        Ok(value)
+    }
+
+    /// Prüft einen normalisierten Listeneintrag auf Gültigkeit
+    pub fn is_valid_entry(&self, normalized: &str) -> bool {
+        match self {
+            ListKind::Sanctions => !normalized.is_empty(),
+            ListKind::Jurisdictions => normalize::is_country_code(normalized),
+        }
+    }
+
+    /// Baut den sortierten Merkle-Tree aus rohen Einträgen
+    pub fn build_tree<I, S>(&self, entries: I) -> SortedMerkleTree
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        SortedMerkleTree::from_entries(
+            entries
+                .into_iter()
+                .map(|e| self.normalize(e.as_ref()))
+                .filter(|e| !e.is_empty()),
+        )
+    }
+
+    /// Baut den Listen-Baum und lehnt ungültige Einträge ab
+    ///
+    /// Leere Zeilen werden übersprungen; bei Jurisdiktionen muss jeder
+    /// Eintrag ein ISO 3166-1 Code (alpha-2 oder alpha-3) sein.
+    pub fn build_validated_tree<S: AsRef<str>>(
+        &self,
+        entries: &[S],
+    ) -> Result<SortedMerkleTree, Box<dyn Error>> {
+        let invalid: Vec<String> = entries
+            .iter()
+            .filter(|e| !e.as_ref().trim().is_empty())
+            .map(|e| self.normalize(e.as_ref()))
+            .filter(|e| !self.is_valid_entry(e))
+            .collect();
+        if !invalid.is_empty() {
+            return Err(format!(
+                "Ungültige Einträge in {}-Liste: {}",
+                self,
+                invalid.join(", ")
+            )
+            .into());
+        }
+        Ok(self.build_tree(entries))
+    }
+}
+
+impl std::fmt::Display for ListKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ListKind::Sanctions => write!(f, "sanctions"),
+            ListKind::Jurisdictions => write!(f, "jurisdictions"),
+        }
+    }
+}
+
+/// Liest die Einträge einer Listen-CSV (Spalte gemäß [`ListKind::csv_column`])
+///
+/// # Argumente
+/// * `path` - Pfad zur CSV-Datei (mit Header)
+/// * `kind` - Art der Liste
+///
+/// # Rückgabe
+/// Rohe (nicht normalisierte) Einträge
+pub fn read_list_csv<P: AsRef<Path>>(
+    path: P,
+    kind: ListKind,
+) -> Result<Vec<String>, Box<dyn Error>> {
+    let mut reader = ReaderBuilder::new().has_headers(true).from_path(path)?;
+    let column = kind.csv_column();
+    let position = reader
+        .headers()?
+        .iter()
+        .position(|h| h.trim() == column)
+        .ok_or_else(|| format!("Spalte '{}' fehlt in {}-Liste", column, kind))?;
+
+    let mut entries = Vec::new();
+    for record in reader.records() {
+        let record = record?;
+        if let Some(value) = record.get(position) {
+            entries.push(value.to_string());
+        }
+    }
+    Ok(entries)
+}
+
+/// Liest eine Listen-CSV und baut den sortierten Merkle-Tree
+pub fn load_list_tree<P: AsRef<Path>>(
+    path: P,
+    kind: ListKind,
+) -> Result<SortedMerkleTree, Box<dyn Error>> {
+    let entries = read_list_csv(path, kind)?;
+    kind.build_validated_tree(&entries)
+}
+
+/// Nachweis, dass keiner der geprüften Datensätze in einer Liste enthalten ist
+///
+/// Enthält die geprüften Werte im Klartext – nur für die private
+/// Witness-Datei, nie für proof.json/proof.dat.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ListExclusionWitness {
+    pub list: ListKind,
+    /// Root der Liste (0x-Hex)
+    pub root: String,
+    /// Ein Non-Membership-Proof je (normalisiertem, eindeutigem) Wert
+    pub proofs: Vec<NonMembershipProof>,
+    /// Geprüfte Datensätze (nur Prüffeld offengelegt) mit Inclusion-Pfad
+    /// gegen supplier_root/ubo_root
+    #[serde(default)]
+    pub records: Vec<RecordInclusionProof>,
+}
+
+/// Öffentliche Zusage eines Ausschluss-Nachweises (Teil des Proofs)
+///
+/// Enthält weder Datensätze noch geprüfte Werte; der private Nachweis ist
+/// über `witness_hash` gebunden.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ListExclusionClaim {
+    pub list: ListKind,
+    /// Root der Liste (0x-Hex)
+    pub root: String,
+    /// Anzahl geprüfter Datensätze je Datensatz-Typ
+    pub records: BTreeMap<RecordKind, usize>,
+    /// BLAKE3 über den privaten Nachweis (0x-Hex)
+    pub witness_hash: String,
+}
+
+/// Private Witness-Datei zu einem Proof
+///
+/// Wird neben dem Proof abgelegt, aber nie exportiert; nur wer sie erhält,
+/// kann die Ausschlüsse vollständig nachprüfen.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ListWitnessFile {
+    pub version: String,
+    pub witnesses: Vec<ListExclusionWitness>,
+}
+
+impl ListWitnessFile {
+    /// Erstellt eine Witness-Datei im aktuellen Format
+    pub fn new(witnesses: Vec<ListExclusionWitness>) -> Self {
+        ListWitnessFile {
+            version: LIST_WITNESSES_VERSION.to_string(),
+            witnesses,
+        }
+    }
+}
+
+/// Committete Datensatz-Roots und -Anzahlen aus Manifest/Statement
+///
+/// Ausschluss-Nachweise müssen für jeden geprüften Datensatz-Typ alle
+/// `*_count` Datensätze abdecken.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecordCommitments<'a> {
+    pub supplier_root: &'a str,
+    pub ubo_root: &'a str,
+    pub supplier_count: Option<usize>,
+    pub ubo_count: Option<usize>,
+}
+
+impl RecordCommitments<'_> {
+    /// Liest Roots und Anzahlen aus einem Manifest (JSON)
+    ///
+    /// Fehlende Felder ergeben leere Roots bzw. `None`; die Verifikation
+    /// schlägt dann fehl.
+    pub fn from_manifest_json(manifest: &serde_json::Value) -> RecordCommitments<'_> {
+        let root = |key: &str| manifest.get(key).and_then(|v| v.as_str()).unwrap_or("");
+        let count = |key: &str| {
+            manifest
+                .get(key)
+                .and_then(|v| v.as_u64())
+                .map(|n| n as usize)
+        };
+        RecordCommitments {
+            supplier_root: root("supplier_root"),
+            ubo_root: root("ubo_root"),
+            supplier_count: count("supplier_count"),
+            ubo_count: count("ubo_count"),
+        }
+    }
+
+    fn root(&self, kind: RecordKind) -> &str {
+        match kind {
+            RecordKind::Supplier => self.supplier_root,
+            RecordKind::Ubo => self.ubo_root,
+        }
+    }
+
+    fn count(&self, kind: RecordKind) -> Option<usize> {
+        match kind {
+            RecordKind::Supplier => self.supplier_count,
+            RecordKind::Ubo => self.ubo_count,
+        }
+    }
+}
+
+/// Erzeugt Inclusion-Proofs für alle Datensätze einer committeten Liste
+///
+/// Alle Felder sind offengelegt; [`prove_exclusion`] reduziert jeden
+/// Datensatz auf das Prüffeld der Liste.
+///
+/// # Argumente
+/// * `kind` - Datensatz-Typ (bestimmt den Root im Manifest)
+/// * `records` - Vollständige Liste in Commitment-Reihenfolge
+/// * `nonces` - Private Nonces der Datensätze
+///
+/// # Rückgabe
+/// Ein Inclusion-Proof je Datensatz (Schema blake3-blinded-merkle.v2)
+pub fn prove_records<T: Serialize>(
+    kind: RecordKind,
+    records: &[T],
+    nonces: &[String],
+) -> Result<Vec<RecordInclusionProof>, Box<dyn Error>> {
+    let tree = build_blinded_tree(records, nonces)?;
+    (0..records.len())
+        .map(|index| prove_in_tree(kind, &tree, records, nonces, index, kind.fields()))
+        .collect()
+}
+
+/// Erzeugt einen Ausschluss-Nachweis für alle `records` gegen `tree`
+///
+/// # Argumente
+/// * `kind` - Art der Liste (bestimmt Prüffeld und Normalisierung)
+/// * `tree` - Aus [`ListKind::build_tree`] erzeugter Listen-Baum
+/// * `records` - Geprüfte Datensätze (aus [`prove_records`])
+///
+/// # Rückgabe
+/// Nachweis oder – als Fehler – die in der Liste gefundenen bzw. nicht
+/// prüfbaren Werte (z.B. Jurisdiktionen ohne ISO-Code)
+pub fn prove_exclusion(
+    kind: ListKind,
+    tree: &SortedMerkleTree,
+    records: &[RecordInclusionProof],
+) -> Result<ListExclusionWitness, Vec<String>> {
+    let mut values = BTreeSet::new();
+    let mut screened = Vec::with_capacity(records.len());
+    let mut invalid = Vec::new();
+    for record in records {
+        let reduced = record
+            .disclose_only(&[kind.record_field(record.kind)])
+            .map_err(|e| e.to_string())
+            .and_then(|reduced| Ok((kind.screened_value(&reduced)?, reduced)));
+        match reduced {
+            Ok((value, reduced)) => {
+                values.insert(value);
+                screened.push(reduced);
+            }
+            Err(e) => invalid.push(e),
+        }
+    }
+    if !invalid.is_empty() {
+        return Err(invalid);
+    }
+
+    let mut proofs = Vec::with_capacity(values.len());
+    let mut hits = Vec::new();
+    for value in &values {
+        match tree.prove_non_membership(value) {
+            Some(proof) => proofs.push(proof),
+            None => hits.push(value.clone()),
+        }
+    }
+
+    if !hits.is_empty() {
+        return Err(hits);
+    }
+
+    Ok(ListExclusionWitness {
+        list: kind,
+        root: tree.root_hex(),
+        proofs,
+        records: screened,
+    })
+}
+
+impl ListExclusionWitness {
+    /// BLAKE3-Hash des Nachweises (kompakte JSON-Serialisierung, 0x-Hex)
+    pub fn hash(&self) -> Result<String, String> {
+        let json = serde_json::to_vec(self).map_err(|e| e.to_string())?;
+        Ok(format!("0x{}", blake3::hash(&json).to_hex()))
+    }
+
+    /// Öffentliche Zusage für den Proof
+    pub fn claim(&self) -> Result<ListExclusionClaim, String> {
+        let mut records = BTreeMap::new();
+        for record in &self.records {
+            *records.entry(record.kind).or_insert(0) += 1;
+        }
+        Ok(ListExclusionClaim {
+            list: self.list,
+            root: self.root.clone(),
+            records,
+            witness_hash: self.hash()?,
+        })
+    }
+
+    /// Prüft, dass der Nachweis je Datensatz-Typ alle committeten Datensätze abdeckt
+    fn check_coverage(&self, commitments: &RecordCommitments) -> Result<(), String> {
+        let mut covered: BTreeMap<RecordKind, BTreeSet<usize>> = BTreeMap::new();
+        for record in &self.records {
+            let count = commitments.count(record.kind).ok_or_else(|| {
+                format!(
+                    "Manifest enthält keinen {}_count – {}-Ausschluss nicht an alle Datensätze bindbar",
+                    record.kind, self.list
+                )
+            })?;
+            if record.tree_size != count {
+                return Err(format!(
+                    "{} #{}: Baumgröße {} passt nicht zu {}_count {}",
+                    record.kind, record.leaf_index, record.tree_size, record.kind, count
+                ));
+            }
+            covered
+                .entry(record.kind)
+                .or_default()
+                .insert(record.leaf_index);
+        }
+        for (record_kind, indices) in covered {
+            let count = commitments.count(record_kind).unwrap_or_default();
+            if indices.len() != count {
+                return Err(format!(
+                    "{}-Ausschluss deckt {} von {} {}-Datensätzen ab",
+                    self.list,
+                    indices.len(),
+                    count,
+                    record_kind
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Verifiziert den Nachweis gegen einen erwarteten Listen-Root
+    ///
+    /// Prüft die Non-Membership-Proofs, die Inclusion-Pfade der Datensätze
+    /// gegen die committeten Roots und dass jeder Datensatz-Wert einen
+    /// Non-Membership-Proof hat. Die Vollständigkeit über alle Datensätze
+    /// prüft [`verify_statement_exclusions`].
+    ///
+    /// # Argumente
+    /// * `expected_root` - Root aus Manifest/Statement (0x-Hex)
+    /// * `commitments` - supplier_root/ubo_root aus dem Manifest
+    ///
+    /// # Rückgabe
+    /// Ok(()) oder Fehlerbeschreibung
+    pub fn verify(
+        &self,
+        expected_root: &str,
+        commitments: &RecordCommitments,
+    ) -> Result<(), String> {
+        let expected = merkle::from_hex(expected_root).map_err(|e| e.to_string())?;
+        let root = merkle::from_hex(&self.root).map_err(|e| e.to_string())?;
+        if root != expected {
+            return Err(format!(
+                "{}-Root im Proof ({}) stimmt nicht mit erwartetem Root überein ({})",
+                self.list, self.root, expected_root
+            ));
+        }
+        if self.records.is_empty() {
+            return Err(format!(
+                "Ausschluss-Nachweis für {} enthält keine Datensätze",
+                self.list
+            ));
+        }
+
+        let mut excluded = BTreeSet::new();
+        for proof in &self.proofs {
+            // Nicht normalisierte Werte könnten einen Listeneintrag umgehen
+            if self.list.normalize(&proof.value) != proof.value {
+                return Err(format!("Wert nicht normalisiert: {}", proof.value));
+            }
+            if !proof.verify(&root) {
+                return Err(format!("Non-Membership-Proof für {} ungültig", proof.value));
+            }
+            excluded.insert(proof.value.as_str());
+        }
+
+        for record in &self.records {
+            record
+                .verify(commitments.root(record.kind))
+                .map_err(|e| format!("{} #{}: {}", record.kind, record.leaf_index, e))?;
+            let value = self.list.screened_value(record)?;
+            if !excluded.contains(value.as_str()) {
+                return Err(format!(
+                    "Kein Non-Membership-Proof für {} #{} ({})",
+                    record.kind, record.leaf_index, value
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Geprüfte (normalisierte) Werte
+    pub fn values(&self) -> Vec<&str> {
+        self.proofs.iter().map(|p| p.value.as_str()).collect()
+    }
+}
+
+/// Prüft, dass für jeden gesetzten Statement-Root eine passende Zusage vorliegt
+///
+/// Jede Zusage im Proof muss auf den Root ihrer Liste lauten; Zusagen ohne
+/// passenden Statement-Root sind ungültig. Je Zusage muss jeder geprüfte
+/// Datensatz-Typ vollständig abgedeckt sein: genau die `supplier_count`
+/// bzw. `ubo_count` committeten Datensätze. Die Nachweise selbst prüft
+/// [`verify_statement_witnesses`].
+///
+/// # Argumente
+/// * `claims` - Ausschluss-Zusagen aus dem Proof
+/// * `sanctions_root` - Sanktions-Root aus Manifest/Statement
+/// * `jurisdiction_root` - Jurisdiktions-Root aus Manifest/Statement
+/// * `commitments` - Datensatz-Roots und -Anzahlen aus dem Manifest
+///
+/// # Rückgabe
+/// Ok(()) oder Fehlerbeschreibung
+pub fn verify_statement_exclusions(
+    claims: &[ListExclusionClaim],
+    sanctions_root: Option<&str>,
+    jurisdiction_root: Option<&str>,
+    commitments: &RecordCommitments,
+) -> Result<(), String> {
+    let required = [
+        (ListKind::Sanctions, sanctions_root),
+        (ListKind::Jurisdictions, jurisdiction_root),
+    ];
+
+    for claim in claims {
+        let root = required
+            .iter()
+            .find(|(kind, _)| *kind == claim.list)
+            .and_then(|(_, root)| *root)
+            .ok_or_else(|| {
+                format!(
+                    "Ausschluss-Nachweis für {} ohne {} im Statement",
+                    claim.list,
+                    claim.list.statement_root_key()
+                )
+            })?;
+        let expected = merkle::from_hex(root).map_err(|e| e.to_string())?;
+        if merkle::from_hex(&claim.root).map_err(|e| e.to_string())? != expected {
+            return Err(format!(
+                "{}-Root im Proof ({}) stimmt nicht mit erwartetem Root überein ({})",
+                claim.list, claim.root, root
+            ));
+        }
+        if claim.records.is_empty() {
+            return Err(format!(
+                "Ausschluss-Nachweis für {} enthält keine Datensätze",
+                claim.list
+            ));
+        }
+        for (record_kind, covered) in &claim.records {
+            let count = commitments.count(*record_kind).ok_or_else(|| {
+                format!(
+                    "Manifest enthält keinen {}_count – {}-Ausschluss nicht an alle Datensätze bindbar",
+                    record_kind, claim.list
+                )
+            })?;
+            if *covered != count {
+                return Err(format!(
+                    "{}-Ausschluss deckt {} von {} {}-Datensätzen ab",
+                    claim.list, covered, count, record_kind
+                ));
+            }
+        }
+    }
+
+    for (kind, root) in required {
+        if root.is_some() && !claims.iter().any(|c| c.list == kind) {
+            return Err(format!(
+                "Ausschluss-Nachweis für {} fehlt im Proof",
+                kind.statement_root_key()
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Prüft die Zusagen eines Proofs vollständig gegen die privaten Nachweise
+///
+/// Zusätzlich zu [`verify_statement_exclusions`] muss es zu jeder Zusage
+/// einen Nachweis mit dem zugesagten Hash geben, der gegen Listen-Root und
+/// `supplier_root`/`ubo_root` verifiziert und alle Datensätze abdeckt.
+///
+/// # Argumente
+/// * `claims` - Ausschluss-Zusagen aus dem Proof
+/// * `witnesses` - Private Nachweise (aus der Witness-Datei)
+/// * `sanctions_root` / `jurisdiction_root` - Listen-Roots aus dem Manifest
+/// * `commitments` - Datensatz-Roots und -Anzahlen aus dem Manifest
+///
+/// # Rückgabe
+/// Ok(()) oder Fehlerbeschreibung
+pub fn verify_statement_witnesses(
+    claims: &[ListExclusionClaim],
+    witnesses: &[ListExclusionWitness],
+    sanctions_root: Option<&str>,
+    jurisdiction_root: Option<&str>,
+    commitments: &RecordCommitments,
+) -> Result<(), String> {
+    verify_statement_exclusions(claims, sanctions_root, jurisdiction_root, commitments)?;
+
+    for claim in claims {
+        let witness = witnesses
+            .iter()
+            .find(|w| w.hash().as_deref() == Ok(claim.witness_hash.as_str()))
+            .ok_or_else(|| {
+                format!(
+                    "Kein privater Nachweis für {} mit Hash {}",
+                    claim.list, claim.witness_hash
+                )
+            })?;
+        witness.verify(&claim.root, commitments)?;
+        witness.check_coverage(commitments)?;
+        if witness.claim()? != *claim {
+            return Err(format!(
+                "Nachweis für {} passt nicht zur Zusage im Proof",
+                claim.list
+            ));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalization() {
+        assert_eq!(ListKind::Jurisdictions.normalize(" de "), "DE");
+        assert_eq!(
+            ListKind::Sanctions.normalize("  Maria   Petrova "),
+            "MARIA PETROVA"
+        );
+    }
+
+    fn suppliers(jurisdictions: &[&str]) -> Vec<crate::io::Supplier> {
+        jurisdictions
+            .iter()
+            .enumerate()
+            .map(|(i, j)| crate::io::Supplier {
+                name: format!("S{}", i),
+                jurisdiction: j.to_string(),
+                tier: 1,
+            })
+            .collect()
+    }
+
+    fn prove_suppliers(jurisdictions: &[&str]) -> Vec<RecordInclusionProof> {
+        let nonces = crate::commitment::RecordNonces::generate(jurisdictions.len(), 0);
+        prove_records(
+            RecordKind::Supplier,
+            &suppliers(jurisdictions),
+            &nonces.suppliers,
+        )
+        .unwrap()
+    }
+
+    /// Prüft Zusagen und private Nachweise gemeinsam (wie `proof verify --list-witnesses`)
+    fn verify_witnesses(
+        witnesses: &[ListExclusionWitness],
+        jurisdiction_root: &str,
+        committed: &RecordCommitments,
+    ) -> Result<(), String> {
+        let claims = witnesses
+            .iter()
+            .map(ListExclusionWitness::claim)
+            .collect::<Result<Vec<_>, _>>()?;
+        verify_statement_witnesses(&claims, witnesses, None, Some(jurisdiction_root), committed)
+    }
+
+    fn commitments(records: &[RecordInclusionProof]) -> (String, usize) {
+        (records[0].root.clone(), records.len())
+    }
+
+    #[test]
+    fn test_exclusion_roundtrip() {
+        let kind = ListKind::Jurisdictions;
+        let tree = kind.build_tree(["IR", "KP", "ru", "SY"]);
+        let records = prove_suppliers(&["DE", "us", "DE", "SE"]);
+        let (root, count) = commitments(&records);
+        let committed = RecordCommitments {
+            supplier_root: &root,
+            ubo_root: "0x00",
+            supplier_count: Some(count),
+            ubo_count: None,
+        };
+        let witness = prove_exclusion(kind, &tree, &records).unwrap();
+
+        assert_eq!(witness.values(), ["DE", "SE", "US"]);
+        witness.verify(&tree.root_hex(), &committed).unwrap();
+        verify_witnesses(std::slice::from_ref(&witness), &tree.root_hex(), &committed).unwrap();
+
+        let other = kind.build_tree(["IR"]);
+        assert!(witness.verify(&other.root_hex(), &committed).is_err());
+
+        // Datensätze müssen zum committeten supplier_root gehören
+        let foreign = RecordCommitments {
+            supplier_root: &tree.root_hex(),
+            ..committed
+        };
+        assert!(witness.verify(&tree.root_hex(), &foreign).is_err());
+    }
+
+    #[test]
+    fn test_exclusion_reports_hits() {
+        let kind = ListKind::Jurisdictions;
+        let tree = kind.build_tree(["IR", "KP", "RU"]);
+        let records = prove_suppliers(&["DE", "ru"]);
+        let hits = prove_exclusion(kind, &tree, &records).unwrap_err();
+        assert_eq!(hits, ["RU"]);
+    }
+
+    #[test]
+    fn test_non_iso_jurisdiction_is_rejected() {
+        let kind = ListKind::Jurisdictions;
+        let tree = kind.build_tree(["RU"]);
+        // "Russia" normalisiert nicht zu "RU" und wäre sonst "ausgeschlossen"
+        let records = prove_suppliers(&["DE", "Russia"]);
+        let errors = prove_exclusion(kind, &tree, &records).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("Russia"), "{}", errors[0]);
+
+        // Alpha-3 wird weiterhin auf Alpha-2 abgebildet
+        let records = prove_suppliers(&["DEU", "rus"]);
+        assert_eq!(prove_exclusion(kind, &tree, &records).unwrap_err(), ["RU"]);
+    }
+
+    #[test]
+    fn test_unnormalized_value_is_rejected() {
+        let kind = ListKind::Jurisdictions;
+        let tree = kind.build_tree(["RU"]);
+        let records = prove_suppliers(&["DE"]);
+        let (root, count) = commitments(&records);
+        let committed = RecordCommitments {
+            supplier_root: &root,
+            ubo_root: "0x00",
+            supplier_count: Some(count),
+            ubo_count: None,
+        };
+        let mut witness = prove_exclusion(kind, &tree, &records).unwrap();
+        // "ru" liegt sortiert hinter "RU" und wäre formal ausgeschlossen
+        witness.proofs = vec![tree.prove_non_membership("ru").unwrap()];
+        assert!(witness.verify(&tree.root_hex(), &committed).is_err());
+    }
+
+    #[test]
+    fn test_empty_witness_is_rejected() {
+        let kind = ListKind::Jurisdictions;
+        let tree = kind.build_tree(["RU"]);
+        let records = prove_suppliers(&["DE"]);
+        let (root, count) = commitments(&records);
+        let committed = RecordCommitments {
+            supplier_root: &root,
+            ubo_root: "0x00",
+            supplier_count: Some(count),
+            ubo_count: None,
+        };
+
+        let empty = prove_exclusion(kind, &tree, &[]).unwrap();
+        assert!(empty.verify(&tree.root_hex(), &committed).is_err());
+        assert!(verify_witnesses(&[empty], &tree.root_hex(), &committed).is_err());
+
+        // Datensätze ohne Non-Membership-Proofs
+        let mut witness = prove_exclusion(kind, &tree, &records).unwrap();
+        witness.proofs.clear();
+        assert!(witness.verify(&tree.root_hex(), &committed).is_err());
+    }
+
+    #[test]
+    fn test_dropped_value_is_rejected() {
+        let kind = ListKind::Jurisdictions;
+        let tree = kind.build_tree(["IR", "KP"]);
+        // Der dritte Supplier sitzt in einer gelisteten Jurisdiktion
+        let records = prove_suppliers(&["DE", "SE", "IR"]);
+        let (root, count) = commitments(&records);
+        let committed = RecordCommitments {
+            supplier_root: &root,
+            ubo_root: "0x00",
+            supplier_count: Some(count),
+            ubo_count: None,
+        };
+
+        // Datensatz samt Wert weggelassen: Nachweis selbst gültig, aber unvollständig
+        let witness = prove_exclusion(kind, &tree, &records[..2]).unwrap();
+        witness.verify(&tree.root_hex(), &committed).unwrap();
+        let err = verify_witnesses(std::slice::from_ref(&witness), &tree.root_hex(), &committed)
+            .unwrap_err();
+        assert!(err.contains("2 von 3"), "{}", err);
+
+        // Nur den Wert weglassen: Datensatz ohne Non-Membership-Proof
+        let full = prove_suppliers(&["DE", "SE", "DE"]);
+        let mut witness = prove_exclusion(kind, &tree, &full).unwrap();
+        witness.proofs.retain(|p| p.value != "SE");
+        assert!(witness.verify(&tree.root_hex(), &committed).is_err());
+
+        // Ohne supplier_count im Manifest ist keine Vollständigkeit prüfbar
+        let uncounted = RecordCommitments {
+            supplier_count: None,
+            ..committed
+        };
+        assert!(
+            verify_witnesses(std::slice::from_ref(&witness), &tree.root_hex(), &uncounted).is_err()
+        );
+    }
+
+    #[test]
+    fn test_claim_reveals_no_screened_values() {
+        let kind = ListKind::Sanctions;
+        let tree = kind.build_tree(["Ivan Petrov"]);
+        let records = prove_suppliers(&["DE", "SE"]);
+        let (root, count) = commitments(&records);
+        let committed = RecordCommitments {
+            supplier_root: &root,
+            ubo_root: "0x00",
+            supplier_count: Some(count),
+            ubo_count: None,
+        };
+        let witness = prove_exclusion(kind, &tree, &records).unwrap();
+
+        // Der private Nachweis enthält nur das Prüffeld, nicht die Jurisdiktion
+        let witness_json = serde_json::to_string(&witness).unwrap();
+        assert!(witness_json.contains("S0"));
+        assert!(!witness_json.contains("\"DE\""), "{}", witness_json);
+
+        // Die Zusage im Proof enthält weder Namen noch Werte
+        let claim = witness.claim().unwrap();
+        let claim_json = serde_json::to_string(&claim).unwrap();
+        assert!(!claim_json.contains("S0") && !claim_json.contains("S1"));
+        assert_eq!(claim.records[&RecordKind::Supplier], 2);
+        let claims = std::slice::from_ref(&claim);
+        verify_statement_exclusions(claims, Some(&tree.root_hex()), None, &committed).unwrap();
+        verify_statement_witnesses(
+            claims,
+            std::slice::from_ref(&witness),
+            Some(&tree.root_hex()),
+            None,
+            &committed,
+        )
+        .unwrap();
+
+        // Zusage mit zu wenigen Datensätzen oder fremdem Nachweis-Hash
+        let mut partial = claim.clone();
+        partial.records.insert(RecordKind::Supplier, 1);
+        assert!(verify_statement_exclusions(
+            std::slice::from_ref(&partial),
+            Some(&tree.root_hex()),
+            None,
+            &committed
+        )
+        .is_err());
+        let other = prove_exclusion(kind, &tree, &prove_suppliers(&["DE", "SE"])).unwrap();
+        assert!(verify_statement_witnesses(
+            claims,
+            &[other],
+            Some(&tree.root_hex()),
+            None,
+            &committed
+        )
+        .is_err());
+    }
+}
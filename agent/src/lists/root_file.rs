@@ -0,0 +1,149 @@
+//! Versionierte Root-Dateien für Sanktions- und Jurisdiktions-Listen
+//!
+//! Eine Root-Datei hält den Root des sortierten Merkle-Trees zusammen mit
+//! Metadaten zur Herkunft fest (Anzahl Einträge, Hash der Quelldatei,
+//! Normalisierungsversion, Build-Datum). Manifeste referenzieren sie über
+//! `manifest build --sanctions-root/--jurisdiction-root`.
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+use super::normalize::NORMALIZATION_VERSION;
+use super::{read_list_csv, ListKind};
+use crate::crypto;
+
+/// Format-Version der Root-Datei
+pub const LIST_ROOT_VERSION: &str = "cap-list-root.v1";
+
+/// Root-Datei einer Liste
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ListRootFile {
+    pub version: String,
+    pub list: ListKind,
+    /// Root des sortierten Merkle-Trees (0x-Hex)
+    pub root: String,
+    /// Anzahl eindeutiger, normalisierter Einträge (= Blätter)
+    pub entry_count: usize,
+    /// Anzahl Datenzeilen der Quelldatei
+    pub source_rows: usize,
+    pub source_file: String,
+    /// SHA3-256 der Quelldatei (0x-Hex)
+    pub source_hash: String,
+    pub normalization: String,
+    pub built_at: String,
+}
+
+impl ListRootFile {
+    /// Baut die Root-Datei aus einer Listen-CSV
+    ///
+    /// # Argumente
+    /// * `csv_path` - Pfad zur Listen-CSV
+    /// * `kind` - Art der Liste
+    ///
+    /// # Rückgabe
+    /// Root-Datei oder Fehler (z.B. ungültige Ländercodes)
+    pub fn build<P: AsRef<Path>>(csv_path: P, kind: ListKind) -> Result<Self, Box<dyn Error>> {
+        let csv_path = csv_path.as_ref();
+        let source_bytes = fs::read(csv_path)?;
+        let rows = read_list_csv(csv_path, kind)?;
+        let tree = kind.build_validated_tree(&rows)?;
+
+        Ok(ListRootFile {
+            version: LIST_ROOT_VERSION.to_string(),
+            list: kind,
+            root: tree.root_hex(),
+            entry_count: tree.len(),
+            source_rows: rows.len(),
+            source_file: csv_path.display().to_string(),
+            source_hash: crypto::hex_lower_prefixed32(crypto::sha3_256(&source_bytes)),
+            normalization: NORMALIZATION_VERSION.to_string(),
+            built_at: Utc::now().to_rfc3339(),
+        })
+    }
+
+    /// Speichert die Root-Datei als JSON
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), Box<dyn Error>> {
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Lädt eine Root-Datei und prüft Format- und Normalisierungsversion
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, Box<dyn Error>> {
+        let root_file: ListRootFile = serde_json::from_str(&fs::read_to_string(path)?)?;
+        if root_file.version != LIST_ROOT_VERSION {
+            return Err(format!(
+                "Nicht unterstützte Root-Datei-Version: {}",
+                root_file.version
+            )
+            .into());
+        }
+        if root_file.normalization != NORMALIZATION_VERSION {
+            return Err(format!(
+                "Nicht unterstützte Normalisierung: {} (erwartet {})",
+                root_file.normalization, NORMALIZATION_VERSION
+            )
+            .into());
+        }
+        Ok(root_file)
+    }
+
+    /// Liefert den Root, sofern die Datei zur erwarteten Liste gehört
+    pub fn root_for(&self, kind: ListKind) -> Result<&str, Box<dyn Error>> {
+        if self.list != kind {
+            return Err(format!(
+                "Root-Datei gehört zur {}-Liste, erwartet: {}",
+                self.list, kind
+            )
+            .into());
+        }
+        Ok(&self.root)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_build_save_load_roundtrip() {
+        let dir = TempDir::new().unwrap();
+        let csv = dir.path().join("jurisdictions.csv");
+        fs::write(
+            &csv,
+            "iso_code,risk_level\nIR,HIGH\nirn,HIGH\nPRK,HIGH\nru,HIGH\n",
+        )
+        .unwrap();
+
+        let root_file = ListRootFile::build(&csv, ListKind::Jurisdictions).unwrap();
+        assert_eq!(root_file.entry_count, 3);
+        assert_eq!(root_file.source_rows, 4);
+        assert_eq!(
+            root_file.root,
+            ListKind::Jurisdictions
+                .build_tree(["IR", "KP", "RU"])
+                .root_hex()
+        );
+        assert!(root_file.source_hash.starts_with("0x"));
+
+        let path = dir.path().join("jurisdictions.root");
+        root_file.save(&path).unwrap();
+        let loaded = ListRootFile::load(&path).unwrap();
+        assert_eq!(loaded, root_file);
+        assert!(loaded.root_for(ListKind::Jurisdictions).is_ok());
+        assert!(loaded.root_for(ListKind::Sanctions).is_err());
+    }
+
+    #[test]
+    fn test_invalid_country_code_is_rejected() {
+        let dir = TempDir::new().unwrap();
+        let csv = dir.path().join("jurisdictions.csv");
+        fs::write(&csv, "iso_code\nIR\nGermany\n").unwrap();
+
+        let err = ListRootFile::build(&csv, ListKind::Jurisdictions).unwrap_err();
+        assert!(err.to_string().contains("GERMANY"));
+    }
+}
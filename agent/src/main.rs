@@ -3,6 +3,7 @@ mod blob_store;
 mod bundle;
 mod cli;
 mod commitment;
+mod inclusion;
 mod io;
 mod keys;
 mod manifest;
@@ -15,13 +16,18 @@ mod sign;
 
 // Re-export library modules for use by bin modules (crate::crypto, etc.)
 pub use cap_agent::crypto;
+pub use cap_agent::lists;
+pub use cap_agent::merkle;
+pub use cap_agent::policy_v2;
+pub use cap_agent::providers;
+pub use cap_agent::sorted_merkle;
 pub use cap_agent::verifier;
 pub use cap_agent::bundle as cap_bundle;
 
 use clap::Parser;
 use cli::{
-    AuditCommands, BlobCommands, Cli, Commands, KeysCommands, ManifestCommands,
-    PolicyCommands, ProofCommands, RegistryCommands, SignCommands, VerifierCommands,
+    AuditCommands, BlobCommands, Cli, Commands, InclusionCommands, KeysCommands, ListsCommands,
+    ManifestCommands, PolicyCommands, ProofCommands, RegistryCommands, SignCommands, VerifierCommands,
 };
 use serde::{Deserialize, Serialize};
 
@@ -47,7 +53,8 @@ struct VerificationReport {
 // - cli::sign (run_sign_*, run_verify_manifest)
 // - cli::verifier (run_verifier_*)
 // - cli::audit (run_audit_*)
-// - cli::registry (run_lists_*, run_registry_*)
+// - cli::lists (run_lists_*)
+// - cli::registry (run_registry_*)
 // - cli::keys (run_keys_*)
 // - cli::bundle (run_bundle_v2, run_verify_bundle)
 // - cli::blob (run_blob_*)
@@ -63,28 +70,61 @@ fn main() {
     let result = match &cli.command {
         Commands::Prepare { suppliers, ubos } => cli::prepare::run_prepare(suppliers, ubos),
         Commands::Inspect { path } => cli::prepare::run_inspect(path),
+        Commands::Inclusion(cmd) => match cmd {
+            InclusionCommands::Prove {
+                kind,
+                csv,
+                name,
+                index,
+                commitments,
+                reveal,
+                out,
+            } => cli::inclusion::run_inclusion_prove(
+                kind,
+                csv,
+                name.clone(),
+                *index,
+                commitments.clone(),
+                reveal,
+                out.clone(),
+            ),
+            InclusionCommands::Verify {
+                proof,
+                manifest,
+                root,
+            } => cli::inclusion::run_inclusion_verify(proof, manifest.clone(), root.clone()),
+        },
         Commands::Policy(cmd) => match cmd {
             PolicyCommands::Validate { file } => cli::policy::run_policy_validate(file),
             PolicyCommands::Lint { file, strict } => {
-                use cap_agent::policy_v2;
                 let exit_code = policy_v2::run_lint(file, *strict).unwrap_or(1);
                 std::process::exit(exit_code);
             }
             PolicyCommands::Compile { file, output } => {
-                use cap_agent::policy_v2;
                 let exit_code = policy_v2::run_compile(file, output).unwrap_or(1);
                 std::process::exit(exit_code);
             }
             PolicyCommands::Show { file } => {
-                use cap_agent::policy_v2;
                 let exit_code = policy_v2::run_show(file).unwrap_or(1);
                 std::process::exit(exit_code);
             }
         },
         Commands::Manifest(cmd) => match cmd {
-            ManifestCommands::Build { policy, out } => {
-                cli::manifest::run_manifest_build(policy, out.clone())
-            }
+            ManifestCommands::Build {
+                policy,
+                out,
+                sanctions_list,
+                jurisdiction_list,
+                sanctions_root,
+                jurisdiction_root,
+            } => cli::manifest::run_manifest_build(
+                policy,
+                out.clone(),
+                sanctions_list.clone(),
+                jurisdiction_list.clone(),
+                sanctions_root.clone(),
+                jurisdiction_root.clone(),
+            ),
             ManifestCommands::Validate { file, schema } => {
                 cli::manifest::run_manifest_validate(file, schema.clone())
             }
@@ -92,13 +132,23 @@ fn main() {
                 manifest,
                 proof,
                 registry,
+                registry_backend,
+                keys_dir,
                 timestamp,
+                tsa_cert,
+                anchor_proof,
+                anchor_headers,
                 out,
             } => cli::manifest::run_manifest_verify(
                 manifest,
                 proof,
                 registry,
+                registry_backend,
+                keys_dir.as_deref(),
                 timestamp.clone(),
+                tsa_cert.as_deref(),
+                anchor_proof.as_deref(),
+                anchor_headers.as_deref(),
                 out.clone(),
             ),
         },
@@ -106,12 +156,26 @@ fn main() {
             ProofCommands::Mock { policy, manifest } => {
                 cli::proof::run_proof_mock(policy, manifest)
             }
-            ProofCommands::Build { policy, manifest } => {
-                cli::proof::run_proof_build(policy, manifest)
-            }
-            ProofCommands::Verify { proof, manifest } => {
-                cli::proof::run_proof_verify_v3(proof, manifest)
-            }
+            ProofCommands::Build {
+                policy,
+                manifest,
+                suppliers,
+                ubos,
+                sanctions_list,
+                jurisdiction_list,
+            } => cli::proof::run_proof_build(
+                policy,
+                manifest,
+                suppliers.clone(),
+                ubos.clone(),
+                sanctions_list.clone(),
+                jurisdiction_list.clone(),
+            ),
+            ProofCommands::Verify {
+                proof,
+                manifest,
+                list_witnesses,
+            } => cli::proof::run_proof_verify_v3(proof, manifest, list_witnesses.as_deref()),
             ProofCommands::Export {
                 manifest,
                 proof,
@@ -177,10 +241,19 @@ fn main() {
                 out,
                 mock,
                 tsa_url,
-            } => cli::audit::run_audit_timestamp(head, out.clone(), *mock, tsa_url.clone()),
-            AuditCommands::VerifyTimestamp { head, timestamp } => {
-                cli::audit::run_audit_verify_timestamp(head, timestamp)
-            }
+                tsa_cert,
+            } => cli::audit::run_audit_timestamp(
+                head,
+                out.clone(),
+                *mock,
+                tsa_url.clone(),
+                tsa_cert.as_deref(),
+            ),
+            AuditCommands::VerifyTimestamp {
+                head,
+                timestamp,
+                tsa_cert,
+            } => cli::audit::run_audit_verify_timestamp(head, timestamp, tsa_cert.as_deref()),
             AuditCommands::SetPrivateAnchor {
                 manifest,
                 audit_tip,
@@ -199,9 +272,32 @@ fn main() {
                 digest,
                 created_at.clone(),
             ),
-            AuditCommands::VerifyAnchor { manifest, out } => {
-                cli::audit::run_audit_verify_anchor(manifest, out.clone())
-            }
+            AuditCommands::AnchorBatch {
+                manifests,
+                leaf,
+                chain,
+                txid,
+                created_at,
+                out,
+            } => cli::audit::run_audit_anchor_batch(
+                manifests,
+                leaf,
+                chain.as_deref(),
+                txid.as_deref(),
+                created_at.clone(),
+                out.clone(),
+            ),
+            AuditCommands::VerifyAnchor {
+                manifest,
+                proof,
+                headers,
+                out,
+            } => cli::audit::run_audit_verify_anchor(
+                manifest,
+                proof.as_deref(),
+                headers.as_deref(),
+                out.clone(),
+            ),
             AuditCommands::Append {
                 file,
                 event,
@@ -219,28 +315,132 @@ fn main() {
                 result.clone(),
                 run_id.clone(),
             ),
-            AuditCommands::Verify { file, out } => {
-                cli::audit::run_audit_verify_chain(file, out.clone())
+            AuditCommands::Checkpoint {
+                file,
+                out,
+                keys_dir,
+                key,
+                kid,
+                provider_config,
+            } => cli::audit::run_audit_checkpoint(
+                file,
+                out.clone(),
+                keys_dir,
+                key.clone(),
+                kid.clone(),
+                provider_config.clone(),
+            ),
+            AuditCommands::Migrate { file, out } => {
+                cli::audit::run_audit_migrate(file, out.clone())
             }
+            AuditCommands::Rotate {
+                file,
+                max_bytes,
+                period,
+                sign,
+                keys_dir,
+                key,
+                kid,
+                provider_config,
+            } => cli::audit::run_audit_rotate(
+                file,
+                *max_bytes,
+                period.clone(),
+                *sign,
+                keys_dir,
+                key.clone(),
+                kid.clone(),
+                provider_config.clone(),
+            ),
+            AuditCommands::Prove {
+                file,
+                event,
+                checkpoints,
+                out,
+            } => cli::audit::run_audit_prove(file, *event, checkpoints.clone(), out.clone()),
+            AuditCommands::VerifyProof {
+                proof,
+                keys_dir,
+                provider_config,
+            } => cli::audit::run_audit_verify_proof(
+                proof,
+                keys_dir.clone(),
+                provider_config.clone(),
+            ),
+            AuditCommands::Verify {
+                file,
+                out,
+                checkpoints,
+                keys_dir,
+                provider_config,
+            } => cli::audit::run_audit_verify_chain(
+                file,
+                out.clone(),
+                checkpoints.clone(),
+                keys_dir.clone(),
+                provider_config.clone(),
+            ),
             AuditCommands::Export {
                 file,
                 from,
                 to,
                 policy_id,
+                events,
+                run_id,
+                manifest_hash,
+                result,
+                format,
                 out,
             } => cli::audit::run_audit_export(
                 file,
                 from.clone(),
                 to.clone(),
                 policy_id.clone(),
+                events.clone(),
+                run_id.clone(),
+                manifest_hash.clone(),
+                result.clone(),
+                format,
+                out.clone(),
+            ),
+            AuditCommands::Report {
+                file,
+                from,
+                to,
+                events,
+                policy_id,
+                period,
+                manifests,
+                checkpoints,
+                keys_dir,
+                provider_config,
+                format,
+                title,
+                out,
+            } => cli::audit::run_audit_report(
+                file,
+                from.clone(),
+                to.clone(),
+                events.clone(),
+                policy_id.clone(),
+                period,
+                manifests.clone(),
+                checkpoints.clone(),
+                keys_dir.clone(),
+                provider_config.clone(),
+                format,
+                title.clone(),
                 out.clone(),
             ),
         },
-        // Note: Lists commands removed in minimal local agent
-        Commands::Lists(_cmd) => {
-            eprintln!("Lists-Commands nicht verfügbar im minimalen lokalen Agenten");
-            Err("Lists module removed".into())
-        }
+        Commands::Lists(cmd) => match cmd {
+            ListsCommands::SanctionsRoot { csv, out } => {
+                cli::lists::run_lists_sanctions_root(csv, out.clone())
+            }
+            ListsCommands::JurisdictionsRoot { csv, out } => {
+                cli::lists::run_lists_jurisdictions_root(csv, out.clone())
+            }
+        },
         Commands::Registry(cmd) => match cmd {
             RegistryCommands::Add {
                 manifest,
@@ -261,15 +461,131 @@ fn main() {
                 *validate_key,
                 keys_dir,
             ),
-            RegistryCommands::List { registry, backend } => {
-                cli::registry::run_registry_list(registry.clone(), backend)
-            }
+            RegistryCommands::List {
+                registry,
+                backend,
+                filter,
+                sort,
+                order,
+                limit,
+                offset,
+                json,
+            } => cli::registry::run_registry_list(
+                registry.clone(),
+                backend,
+                filter,
+                sort,
+                order,
+                *limit,
+                *offset,
+                *json,
+            ),
             RegistryCommands::Verify {
                 manifest,
                 proof,
                 registry,
                 backend,
-            } => cli::registry::run_registry_verify(manifest, proof, registry.clone(), backend),
+                since,
+            } => cli::registry::run_registry_verify(
+                manifest,
+                proof,
+                registry.clone(),
+                backend,
+                since.as_deref(),
+            ),
+            RegistryCommands::Revoke {
+                id,
+                reason,
+                effective_at,
+                registry,
+                backend,
+                signing_key,
+            } => cli::registry::run_registry_revoke(
+                id,
+                reason,
+                effective_at.clone(),
+                registry.clone(),
+                backend,
+                signing_key,
+            ),
+            RegistryCommands::Supersede {
+                id,
+                successor,
+                reason,
+                effective_at,
+                registry,
+                backend,
+                signing_key,
+            } => cli::registry::run_registry_supersede(
+                id,
+                successor,
+                reason,
+                effective_at.clone(),
+                registry.clone(),
+                backend,
+                signing_key,
+            ),
+            RegistryCommands::LogHead {
+                registry,
+                backend,
+                signing_key,
+                out,
+            } => cli::registry::run_registry_log_head(
+                registry.clone(),
+                backend,
+                signing_key,
+                out.clone(),
+            ),
+            RegistryCommands::LogProof {
+                id,
+                registry,
+                backend,
+                out,
+            } => cli::registry::run_registry_log_proof(id, registry.clone(), backend, out.clone()),
+            RegistryCommands::LogConsistency {
+                old,
+                registry,
+                backend,
+                out,
+            } => cli::registry::run_registry_log_consistency(
+                old,
+                registry.clone(),
+                backend,
+                out.clone(),
+            ),
+            RegistryCommands::Export {
+                registry,
+                backend,
+                signing_key,
+                source,
+                out,
+            } => cli::registry::run_registry_export(
+                registry.clone(),
+                backend,
+                signing_key,
+                source.clone(),
+                out.clone(),
+            ),
+            RegistryCommands::Import {
+                snapshot,
+                registry,
+                backend,
+                keys_dir,
+                skip_conflicts,
+            } => cli::registry::run_registry_import(
+                snapshot,
+                registry.clone(),
+                backend,
+                keys_dir.as_deref(),
+                *skip_conflicts,
+            ),
+            RegistryCommands::SchemaMigrate {
+                registry,
+                dry_run,
+                no_backup,
+            } => {
+                cli::registry::run_registry_schema_migrate(registry.clone(), *dry_run, *no_backup)
+            }
             RegistryCommands::Migrate {
                 from,
                 input,
@@ -4,6 +4,9 @@
 
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use super::batch::TimeAnchorBatch;
 
 /// Public Chain Enum für Blockchain-Anker
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
@@ -15,6 +18,16 @@ pub enum PublicChain {
     Btc,
 }
 
+impl fmt::Display for PublicChain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            PublicChain::Ethereum => "ethereum",
+            PublicChain::Hedera => "hedera",
+            PublicChain::Btc => "btc",
+        })
+    }
+}
+
 /// Private Anchor (lokaler Audit-Tip)
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub struct TimeAnchorPrivate {
@@ -44,6 +57,10 @@ pub struct TimeAnchor {
     pub private: Option<TimeAnchorPrivate>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub public: Option<TimeAnchorPublic>,
+
+    // Batch-Anchor: Public Anchor verankert die Batch-Root
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub batch: Option<TimeAnchorBatch>,
 }
 
 impl TimeAnchor {
@@ -56,6 +73,7 @@ impl TimeAnchor {
             created_at: Utc::now().to_rfc3339(),
             private: None,
             public: None,
+            batch: None,
         }
     }
 }
@@ -0,0 +1,969 @@
+//! Public Anchor Proofs - Offline verification of on-chain anchors
+//!
+//! A [`TimeAnchorPublic`] only records where a digest was notarized. An
+//! [`AnchorProof`] exported next to it (transaction plus inclusion proof)
+//! shows that a block actually commits to the digest, and a [`HeaderStore`]
+//! pins the block headers that are trusted locally. No network access is
+//! needed.
+//!
+//! Proof kinds are pluggable ([`AnchorVerifier`], [`AnchorVerifierRegistry`]).
+//! Built in:
+//!
+//! - `btc_spv`: raw Bitcoin transaction with an `OP_RETURN` output carrying the
+//!   digest, its Merkle branch and the block header (SPV proof)
+//! - `ots`: OpenTimestamps-style attestation, i.e. operations (`append`,
+//!   `prepend`, `sha256`) that lead from the digest to the Merkle root of the
+//!   Bitcoin block at a given height
+//!
+//! Hashes (txid, Merkle branch, block hash) are hex in display byte order
+//! (reversed, as shown by block explorers); block headers are the raw
+//! 80-byte serialization.
+
+use super::anchor::{PublicChain, TimeAnchorPublic};
+use anyhow::{anyhow, bail, Context, Result};
+use chrono::DateTime;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Version of the anchor proof file format
+pub const ANCHOR_PROOF_VERSION: &str = "anchor.proof.v1";
+
+/// Version of the header store file format
+pub const HEADER_STORE_VERSION: &str = "anchor.headers.v1";
+
+const OP_RETURN: u8 = 0x6a;
+
+fn sha256d(data: &[u8]) -> [u8; 32] {
+    Sha256::digest(Sha256::digest(data)).into()
+}
+
+fn parse_hash32(field: &str, value: &str) -> Result<[u8; 32]> {
+    hex::decode(value.trim_start_matches("0x"))
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| anyhow!("{} '{}' is not a 32-byte hex value", field, value))
+}
+
+/// Parses a hash in display byte order into internal byte order
+fn parse_display_hash(field: &str, value: &str) -> Result<[u8; 32]> {
+    let mut hash = parse_hash32(field, value)?;
+    hash.reverse();
+    Ok(hash)
+}
+
+/// Formats a hash (internal byte order) in display byte order
+pub fn display_hash(hash: &[u8; 32]) -> String {
+    let mut bytes = *hash;
+    bytes.reverse();
+    hex::encode(bytes)
+}
+
+fn normalize_txid(txid: &str) -> String {
+    txid.trim_start_matches("0x").to_lowercase()
+}
+
+// ============================================================================
+// Bitcoin primitives
+// ============================================================================
+
+/// Bitcoin block header (80 bytes)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockHeader {
+    pub version: i32,
+    pub prev_block: [u8; 32],
+    pub merkle_root: [u8; 32],
+    pub time: u32,
+    pub bits: u32,
+    pub nonce: u32,
+}
+
+impl BlockHeader {
+    pub const SIZE: usize = 80;
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() != Self::SIZE {
+            bail!(
+                "block header has {} bytes (expected {})",
+                bytes.len(),
+                Self::SIZE
+            );
+        }
+        let u32_at = |i: usize| u32::from_le_bytes(bytes[i..i + 4].try_into().unwrap());
+        Ok(BlockHeader {
+            version: u32_at(0) as i32,
+            prev_block: bytes[4..36].try_into().unwrap(),
+            merkle_root: bytes[36..68].try_into().unwrap(),
+            time: u32_at(68),
+            bits: u32_at(72),
+            nonce: u32_at(76),
+        })
+    }
+
+    pub fn from_hex(value: &str) -> Result<Self> {
+        Self::from_bytes(&hex::decode(value).context("block header is not hex")?)
+    }
+
+    pub fn to_bytes(self) -> [u8; Self::SIZE] {
+        let mut bytes = [0u8; Self::SIZE];
+        bytes[0..4].copy_from_slice(&self.version.to_le_bytes());
+        bytes[4..36].copy_from_slice(&self.prev_block);
+        bytes[36..68].copy_from_slice(&self.merkle_root);
+        bytes[68..72].copy_from_slice(&self.time.to_le_bytes());
+        bytes[72..76].copy_from_slice(&self.bits.to_le_bytes());
+        bytes[76..80].copy_from_slice(&self.nonce.to_le_bytes());
+        bytes
+    }
+
+    /// Block hash (double SHA-256, internal byte order)
+    pub fn hash(&self) -> [u8; 32] {
+        sha256d(&self.to_bytes())
+    }
+
+    /// Proof-of-work target from the compact `bits` encoding (big-endian)
+    pub fn target(&self) -> Result<[u8; 32]> {
+        let exponent = (self.bits >> 24) as usize;
+        let mantissa = self.bits & 0x007f_ffff;
+        if self.bits & 0x0080_0000 != 0 || mantissa == 0 || exponent > 32 {
+            bail!("invalid difficulty bits {:#010x}", self.bits);
+        }
+
+        let mut target = [0u8; 32];
+        if exponent <= 3 {
+            let value = mantissa >> (8 * (3 - exponent));
+            target[28..].copy_from_slice(&value.to_be_bytes());
+        } else {
+            let start = 32 - exponent;
+            target[start..start + 3].copy_from_slice(&mantissa.to_be_bytes()[1..]);
+        }
+        Ok(target)
+    }
+
+    /// Checks that the block hash meets the target encoded in `bits`
+    pub fn check_pow(&self) -> Result<()> {
+        let mut hash = self.hash();
+        hash.reverse();
+        if hash > self.target()? {
+            bail!(
+                "block {} does not meet its proof-of-work target",
+                display_hash(&self.hash())
+            );
+        }
+        Ok(())
+    }
+
+    /// Block time as RFC3339 timestamp
+    pub fn time_rfc3339(&self) -> String {
+        DateTime::from_timestamp(i64::from(self.time), 0)
+            .map(|t| t.to_rfc3339())
+            .unwrap_or_default()
+    }
+}
+
+/// Merkle root over transaction IDs (internal byte order)
+///
+/// Odd levels duplicate their last node, as in Bitcoin.
+#[allow(dead_code)] // Public API - building proofs and test fixtures
+pub fn btc_merkle_root(txids: &[[u8; 32]]) -> [u8; 32] {
+    match txids.first() {
+        Some(first) => merkle_root_from_branch(*first, &btc_merkle_branch(txids, 0), 0)
+            .expect("branch built for index 0"),
+        None => [0u8; 32],
+    }
+}
+
+/// Merkle branch for the transaction at `index` (internal byte order)
+#[allow(dead_code)] // Public API - building proofs and test fixtures
+pub fn btc_merkle_branch(txids: &[[u8; 32]], mut index: usize) -> Vec<[u8; 32]> {
+    let mut branch = Vec::new();
+    let mut level = txids.to_vec();
+    while level.len() > 1 {
+        if level.len() % 2 == 1 {
+            level.push(level[level.len() - 1]);
+        }
+        branch.push(level[index ^ 1]);
+        level = level
+            .chunks(2)
+            .map(|pair| sha256d(&[pair[0], pair[1]].concat()))
+            .collect();
+        index /= 2;
+    }
+    branch
+}
+
+fn merkle_root_from_branch(leaf: [u8; 32], branch: &[[u8; 32]], index: u32) -> Result<[u8; 32]> {
+    if branch.len() > 32 || (branch.len() < 32 && index >> branch.len() != 0) {
+        bail!(
+            "tx_index {} is out of range for a Merkle branch of length {}",
+            index,
+            branch.len()
+        );
+    }
+
+    let mut hash = leaf;
+    for (level, sibling) in branch.iter().enumerate() {
+        hash = if (index >> level) & 1 == 0 {
+            sha256d(&[hash, *sibling].concat())
+        } else {
+            sha256d(&[*sibling, hash].concat())
+        };
+    }
+    Ok(hash)
+}
+
+/// Cursor over a raw transaction
+struct TxReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> TxReader<'a> {
+    fn take(&mut self, len: u64) -> Result<&'a [u8]> {
+        let end = usize::try_from(len)
+            .ok()
+            .and_then(|len| self.pos.checked_add(len))
+            .filter(|end| *end <= self.data.len())
+            .ok_or_else(|| anyhow!("transaction is truncated"))?;
+        let bytes = &self.data[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn varint(&mut self) -> Result<u64> {
+        let prefix = self.take(1)?[0];
+        let width = match prefix {
+            0xfd => 2,
+            0xfe => 4,
+            0xff => 8,
+            n => return Ok(u64::from(n)),
+        };
+        let mut bytes = [0u8; 8];
+        bytes[..width].copy_from_slice(self.take(width as u64)?);
+        Ok(u64::from_le_bytes(bytes))
+    }
+}
+
+/// Output scripts of a raw transaction (legacy serialization without witness data)
+fn tx_output_scripts(tx: &[u8]) -> Result<Vec<&[u8]>> {
+    let mut reader = TxReader { data: tx, pos: 0 };
+    reader.take(4)?; // version
+
+    let inputs = reader.varint()?;
+    if inputs == 0 {
+        bail!("transaction has no inputs (export it without witness data)");
+    }
+    for _ in 0..inputs {
+        reader.take(36)?; // previous output
+        let script_len = reader.varint()?;
+        reader.take(script_len)?;
+        reader.take(4)?; // sequence
+    }
+
+    let outputs = reader.varint()?;
+    let mut scripts = Vec::new();
+    for _ in 0..outputs {
+        reader.take(8)?; // value
+        let script_len = reader.varint()?;
+        scripts.push(reader.take(script_len)?);
+    }
+
+    reader.take(4)?; // lock time
+    if reader.pos != tx.len() {
+        bail!("trailing bytes after transaction");
+    }
+    Ok(scripts)
+}
+
+// ============================================================================
+// Header store
+// ============================================================================
+
+/// Block header pinned at a height (file format)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PinnedHeader {
+    pub height: u64,
+    /// Raw header (hex)
+    pub header: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+struct HeaderStoreFile {
+    version: String,
+    chain: PublicChain,
+    headers: Vec<PinnedHeader>,
+}
+
+/// Locally pinned block headers
+///
+/// Every header must meet its proof-of-work target and headers at
+/// consecutive heights must link to each other. Only Bitcoin headers are
+/// supported.
+#[derive(Debug, Clone)]
+pub struct HeaderStore {
+    chain: PublicChain,
+    headers: BTreeMap<u64, BlockHeader>,
+}
+
+impl HeaderStore {
+    pub fn new(
+        chain: PublicChain,
+        headers: impl IntoIterator<Item = (u64, BlockHeader)>,
+    ) -> Result<Self> {
+        if chain != PublicChain::Btc {
+            bail!("header store for chain {} is not supported", chain);
+        }
+
+        let mut pinned = BTreeMap::new();
+        for (height, header) in headers {
+            header
+                .check_pow()
+                .with_context(|| format!("header at height {}", height))?;
+            if pinned.insert(height, header).is_some() {
+                bail!("duplicate header at height {}", height);
+            }
+        }
+        for (height, header) in &pinned {
+            if let Some(prev) = height.checked_sub(1).and_then(|h| pinned.get(&h)) {
+                if header.prev_block != prev.hash() {
+                    bail!(
+                        "header at height {} does not link to height {}",
+                        height,
+                        height - 1
+                    );
+                }
+            }
+        }
+
+        Ok(HeaderStore {
+            chain,
+            headers: pinned,
+        })
+    }
+
+    /// Parses a header store file (`anchor.headers.v1`)
+    pub fn from_json(value: &serde_json::Value) -> Result<Self> {
+        let file: HeaderStoreFile =
+            serde_json::from_value(value.clone()).context("invalid header store")?;
+        if file.version != HEADER_STORE_VERSION {
+            bail!("unsupported header store version '{}'", file.version);
+        }
+        let headers = file
+            .headers
+            .iter()
+            .map(|pinned| {
+                BlockHeader::from_hex(&pinned.header)
+                    .with_context(|| format!("header at height {}", pinned.height))
+                    .map(|header| (pinned.height, header))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Self::new(file.chain, headers)
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let json = std::fs::read_to_string(path)
+            .with_context(|| format!("cannot read header store {}", path.display()))?;
+        Self::from_json(&serde_json::from_str(&json)?)
+    }
+
+    /// Serializes the store in the file format
+    #[allow(dead_code)] // Public API - writing header store files
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::to_value(HeaderStoreFile {
+            version: HEADER_STORE_VERSION.to_string(),
+            chain: self.chain.clone(),
+            headers: self
+                .headers
+                .iter()
+                .map(|(height, header)| PinnedHeader {
+                    height: *height,
+                    header: hex::encode(header.to_bytes()),
+                })
+                .collect(),
+        })
+        .expect("header store serializes")
+    }
+
+    pub fn chain(&self) -> &PublicChain {
+        &self.chain
+    }
+
+    pub fn header_at(&self, height: u64) -> Option<&BlockHeader> {
+        self.headers.get(&height)
+    }
+
+    /// Height of a pinned header by block hash (internal byte order)
+    pub fn height_of(&self, hash: &[u8; 32]) -> Option<u64> {
+        self.headers
+            .iter()
+            .find(|(_, header)| header.hash() == *hash)
+            .map(|(height, _)| *height)
+    }
+}
+
+// ============================================================================
+// Proofs and verifiers
+// ============================================================================
+
+/// Exported inclusion proof for a public anchor (`anchor.proof.v1`)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AnchorProof {
+    pub version: String,
+    /// Proof kind, selects the verifier (e.g. `btc_spv`, `ots`)
+    pub kind: String,
+    pub chain: PublicChain,
+    /// Digest committed on chain (0x-prefixed, equals `TimeAnchorPublic::digest`)
+    pub digest: String,
+    /// Kind-specific proof data
+    #[serde(flatten)]
+    pub data: serde_json::Map<String, serde_json::Value>,
+}
+
+impl AnchorProof {
+    pub fn from_json(value: &serde_json::Value) -> Result<Self> {
+        serde_json::from_value(value.clone()).context("invalid anchor proof")
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let json = std::fs::read_to_string(path)
+            .with_context(|| format!("cannot read anchor proof {}", path.display()))?;
+        Self::from_json(&serde_json::from_str(&json)?)
+    }
+
+    /// Parses the kind-specific proof data
+    pub fn data<T: DeserializeOwned>(&self) -> Result<T> {
+        serde_json::from_value(serde_json::Value::Object(self.data.clone()))
+            .with_context(|| format!("invalid {} proof", self.kind))
+    }
+}
+
+/// Result of a successful anchor proof verification
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub struct AnchorAttestation {
+    pub chain: PublicChain,
+    pub kind: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub txid: Option<String>,
+    /// Hash of the block that commits to the digest (display byte order)
+    pub block_hash: String,
+    /// Height of the block (known if the header is pinned)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub height: Option<u64>,
+    /// Block time (RFC3339)
+    pub block_time: String,
+    /// Whether the block header is in the pinned header store
+    pub header_pinned: bool,
+}
+
+impl AnchorAttestation {
+    fn new(kind: &str, txid: Option<String>, header: &BlockHeader, height: Option<u64>) -> Self {
+        AnchorAttestation {
+            chain: PublicChain::Btc,
+            kind: kind.to_string(),
+            txid,
+            block_hash: display_hash(&header.hash()),
+            height,
+            block_time: header.time_rfc3339(),
+            header_pinned: height.is_some(),
+        }
+    }
+}
+
+/// A verifier for one proof kind on one chain
+pub trait AnchorVerifier: Send + Sync {
+    /// Proof kind handled by this verifier (`AnchorProof::kind`)
+    fn kind(&self) -> &'static str;
+
+    fn chain(&self) -> PublicChain;
+
+    /// Checks that the proof commits to `digest` and returns the block it is anchored in
+    ///
+    /// Without a header store the block header cannot be pinned; verifiers
+    /// that can still check the proof return `header_pinned: false`.
+    fn verify(
+        &self,
+        proof: &AnchorProof,
+        digest: &[u8; 32],
+        headers: Option<&HeaderStore>,
+    ) -> Result<AnchorAttestation>;
+}
+
+/// Bitcoin SPV proof: transaction, Merkle branch and block header
+pub struct BtcSpvVerifier;
+
+#[derive(Debug, Deserialize)]
+struct BtcSpvProof {
+    /// Raw transaction (hex, without witness data)
+    tx: String,
+    /// Position of the transaction in the block
+    tx_index: u32,
+    merkle_branch: Vec<String>,
+    /// Raw block header (hex)
+    block_header: String,
+}
+
+impl AnchorVerifier for BtcSpvVerifier {
+    fn kind(&self) -> &'static str {
+        "btc_spv"
+    }
+
+    fn chain(&self) -> PublicChain {
+        PublicChain::Btc
+    }
+
+    fn verify(
+        &self,
+        proof: &AnchorProof,
+        digest: &[u8; 32],
+        headers: Option<&HeaderStore>,
+    ) -> Result<AnchorAttestation> {
+        let data: BtcSpvProof = proof.data()?;
+
+        let tx = hex::decode(&data.tx).context("tx is not hex")?;
+        let commits = tx_output_scripts(&tx)?.iter().any(|script| {
+            script.len() == 34
+                && script[0] == OP_RETURN
+                && script[1] == 32
+                && script[2..] == digest[..]
+        });
+        if !commits {
+            bail!("transaction has no OP_RETURN output with the digest");
+        }
+        let txid = sha256d(&tx);
+
+        let branch = data
+            .merkle_branch
+            .iter()
+            .map(|hash| parse_display_hash("merkle_branch", hash))
+            .collect::<Result<Vec<_>>>()?;
+        let root = merkle_root_from_branch(txid, &branch, data.tx_index)?;
+
+        let header = BlockHeader::from_hex(&data.block_header)?;
+        header.check_pow()?;
+        if root != header.merkle_root {
+            bail!("Merkle branch does not lead to the Merkle root of the block header");
+        }
+
+        let height = match headers {
+            Some(store) => Some(store.height_of(&header.hash()).ok_or_else(|| {
+                anyhow!(
+                    "block {} is not in the pinned header store",
+                    display_hash(&header.hash())
+                )
+            })?),
+            None => None,
+        };
+        Ok(AnchorAttestation::new(
+            self.kind(),
+            Some(display_hash(&txid)),
+            &header,
+            height,
+        ))
+    }
+}
+
+/// OpenTimestamps-style attestation: digest operations up to a block's Merkle root
+pub struct OtsVerifier;
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+enum OtsOp {
+    Append { arg: String },
+    Prepend { arg: String },
+    Sha256,
+}
+
+#[derive(Debug, Deserialize)]
+struct OtsProof {
+    ops: Vec<OtsOp>,
+    /// Height of the attested block
+    height: u64,
+}
+
+impl AnchorVerifier for OtsVerifier {
+    fn kind(&self) -> &'static str {
+        "ots"
+    }
+
+    fn chain(&self) -> PublicChain {
+        PublicChain::Btc
+    }
+
+    fn verify(
+        &self,
+        proof: &AnchorProof,
+        digest: &[u8; 32],
+        headers: Option<&HeaderStore>,
+    ) -> Result<AnchorAttestation> {
+        let data: OtsProof = proof.data()?;
+        let store =
+            headers.ok_or_else(|| anyhow!("ots attestations require a pinned header store"))?;
+
+        let mut message = digest.to_vec();
+        for op in &data.ops {
+            match op {
+                OtsOp::Append { arg } => {
+                    message.extend(hex::decode(arg).context("append argument is not hex")?)
+                }
+                OtsOp::Prepend { arg } => {
+                    let mut prefixed = hex::decode(arg).context("prepend argument is not hex")?;
+                    prefixed.extend(message);
+                    message = prefixed;
+                }
+                OtsOp::Sha256 => message = Sha256::digest(&message).to_vec(),
+            }
+        }
+
+        let header = store
+            .header_at(data.height)
+            .ok_or_else(|| anyhow!("no pinned header at height {}", data.height))?;
+        if message != header.merkle_root {
+            bail!(
+                "attestation does not lead to the Merkle root of block {}",
+                data.height
+            );
+        }
+        Ok(AnchorAttestation::new(
+            self.kind(),
+            None,
+            header,
+            Some(data.height),
+        ))
+    }
+}
+
+/// Set of anchor verifiers, selected by proof kind and chain
+pub struct AnchorVerifierRegistry {
+    verifiers: Vec<Box<dyn AnchorVerifier>>,
+}
+
+impl Default for AnchorVerifierRegistry {
+    fn default() -> Self {
+        Self::standard()
+    }
+}
+
+impl AnchorVerifierRegistry {
+    pub fn empty() -> Self {
+        AnchorVerifierRegistry {
+            verifiers: Vec::new(),
+        }
+    }
+
+    /// Registry with the built-in verifiers (`btc_spv`, `ots`)
+    pub fn standard() -> Self {
+        let mut registry = Self::empty();
+        registry.register(BtcSpvVerifier).register(OtsVerifier);
+        registry
+    }
+
+    pub fn register(&mut self, verifier: impl AnchorVerifier + 'static) -> &mut Self {
+        self.verifiers.push(Box::new(verifier));
+        self
+    }
+
+    /// Verifies that `proof` anchors the digest of `anchor` on its chain
+    ///
+    /// The proof must be for the anchor's chain and digest; if the verifier
+    /// yields a transaction ID, it must match the anchor's txid.
+    pub fn verify(
+        &self,
+        anchor: &TimeAnchorPublic,
+        proof: &AnchorProof,
+        headers: Option<&HeaderStore>,
+    ) -> Result<AnchorAttestation> {
+        if proof.version != ANCHOR_PROOF_VERSION {
+            bail!("unsupported anchor proof version '{}'", proof.version);
+        }
+        if proof.chain != anchor.chain {
+            bail!(
+                "proof is for chain {}, anchor is on {}",
+                proof.chain,
+                anchor.chain
+            );
+        }
+        if !proof.digest.eq_ignore_ascii_case(&anchor.digest) {
+            bail!(
+                "proof digest {} does not match anchor digest {}",
+                proof.digest,
+                anchor.digest
+            );
+        }
+        if let Some(store) = headers {
+            if store.chain() != &proof.chain {
+                bail!("header store is for chain {}", store.chain());
+            }
+        }
+
+        let verifier = self
+            .verifiers
+            .iter()
+            .find(|v| v.kind() == proof.kind && v.chain() == proof.chain)
+            .ok_or_else(|| {
+                anyhow!(
+                    "no anchor verifier for {} proofs on {}",
+                    proof.kind,
+                    proof.chain
+                )
+            })?;
+        let digest = parse_hash32("digest", &anchor.digest)?;
+        let attestation = verifier.verify(proof, &digest, headers)?;
+
+        if let Some(txid) = &attestation.txid {
+            if normalize_txid(txid) != normalize_txid(&anchor.txid) {
+                bail!(
+                    "proof transaction {} does not match anchor txid {}",
+                    txid,
+                    anchor.txid
+                );
+            }
+        }
+        Ok(attestation)
+    }
+}
+
+/// Verifies a public anchor with the built-in verifiers
+pub fn verify_public_anchor(
+    anchor: &TimeAnchorPublic,
+    proof: &AnchorProof,
+    headers: Option<&HeaderStore>,
+) -> Result<AnchorAttestation> {
+    AnchorVerifierRegistry::standard().verify(anchor, proof, headers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DIGEST: &str = "0x5555555555555555555555555555555555555555555555555555555555555555";
+
+    /// Bitcoin genesis block header
+    const GENESIS: &str = "0100000000000000000000000000000000000000000000000000000000000000000000003ba3edfd7a7b12b27ac72c3e67768f617fc81bc3888a51323a9fb8aa4b1e5e4a29ab5f49ffff001d1dac2b7c";
+
+    /// Transaction with one input and an OP_RETURN output carrying `payload`
+    fn op_return_tx(payload: &[u8; 32]) -> Vec<u8> {
+        let mut tx = vec![1, 0, 0, 0, 1];
+        tx.extend([0xab; 36]);
+        tx.extend([0, 0xff, 0xff, 0xff, 0xff, 1]);
+        tx.extend([0; 8]);
+        tx.extend([34, OP_RETURN, 32]);
+        tx.extend(payload);
+        tx.extend([0; 4]);
+        tx
+    }
+
+    /// Header with regtest difficulty, nonce searched until the PoW is met
+    fn mine(prev_block: [u8; 32], merkle_root: [u8; 32]) -> BlockHeader {
+        let mut header = BlockHeader {
+            version: 0x2000_0000,
+            prev_block,
+            merkle_root,
+            time: 1_767_225_600,
+            bits: 0x207f_ffff,
+            nonce: 0,
+        };
+        while header.check_pow().is_err() {
+            header.nonce += 1;
+        }
+        header
+    }
+
+    struct Fixture {
+        anchor: TimeAnchorPublic,
+        proof: AnchorProof,
+        store: HeaderStore,
+        header: BlockHeader,
+        txids: Vec<[u8; 32]>,
+    }
+
+    /// Block 101 with three transactions, the anchor transaction at index 2
+    fn fixture() -> Fixture {
+        let digest = parse_hash32("digest", DIGEST).unwrap();
+        let anchor_tx = op_return_tx(&digest);
+        let txids = vec![[1u8; 32], [2u8; 32], sha256d(&anchor_tx)];
+
+        let parent = mine([0u8; 32], [9u8; 32]);
+        let header = mine(parent.hash(), btc_merkle_root(&txids));
+        let store = HeaderStore::new(PublicChain::Btc, [(100, parent), (101, header)]).unwrap();
+
+        let proof = AnchorProof::from_json(&serde_json::json!({
+            "version": ANCHOR_PROOF_VERSION,
+            "kind": "btc_spv",
+            "chain": "btc",
+            "digest": DIGEST,
+            "tx": hex::encode(&anchor_tx),
+            "tx_index": 2,
+            "merkle_branch": btc_merkle_branch(&txids, 2)
+                .iter()
+                .map(display_hash)
+                .collect::<Vec<_>>(),
+            "block_header": hex::encode(header.to_bytes()),
+        }))
+        .unwrap();
+        let anchor = TimeAnchorPublic {
+            chain: PublicChain::Btc,
+            txid: display_hash(&txids[2]),
+            digest: DIGEST.to_string(),
+            created_at: "2026-01-01T00:00:00Z".to_string(),
+        };
+
+        Fixture {
+            anchor,
+            proof,
+            store,
+            header,
+            txids,
+        }
+    }
+
+    #[test]
+    fn test_genesis_header() {
+        let genesis = BlockHeader::from_hex(GENESIS).unwrap();
+        assert_eq!(
+            display_hash(&genesis.hash()),
+            "000000000019d6689c085ae165831e934ff763ae46a2a6c172b3f1b60a8ce26f"
+        );
+        assert!(genesis.check_pow().is_ok());
+        assert_eq!(hex::encode(genesis.to_bytes()), GENESIS);
+        assert_eq!(genesis.time_rfc3339(), "2009-01-03T18:15:05+00:00");
+
+        let mut harder = genesis;
+        harder.bits = 0x1c00_ffff;
+        assert!(harder.check_pow().is_err());
+    }
+
+    #[test]
+    fn test_btc_spv_proof_verifies() {
+        let f = fixture();
+
+        let pinned = verify_public_anchor(&f.anchor, &f.proof, Some(&f.store)).unwrap();
+        assert!(pinned.header_pinned);
+        assert_eq!(pinned.height, Some(101));
+        assert_eq!(pinned.block_hash, display_hash(&f.header.hash()));
+        assert_eq!(pinned.txid, Some(display_hash(&f.txids[2])));
+
+        let unpinned = verify_public_anchor(&f.anchor, &f.proof, None).unwrap();
+        assert!(!unpinned.header_pinned);
+        assert_eq!(unpinned.height, None);
+    }
+
+    #[test]
+    fn test_btc_spv_proof_rejects_mismatches() {
+        let f = fixture();
+        let fails = |anchor: &TimeAnchorPublic, proof: &AnchorProof, store: &HeaderStore| {
+            verify_public_anchor(anchor, proof, Some(store))
+                .unwrap_err()
+                .to_string()
+        };
+
+        let mut other_txid = f.anchor.clone();
+        other_txid.txid = display_hash(&f.txids[1]);
+        assert!(fails(&other_txid, &f.proof, &f.store).contains("does not match anchor txid"));
+
+        let other_digest = "0x6666666666666666666666666666666666666666666666666666666666666666";
+        let mut anchor = f.anchor.clone();
+        anchor.digest = other_digest.to_string();
+        let mut proof = f.proof.clone();
+        proof.digest = other_digest.to_string();
+        assert!(fails(&anchor, &proof, &f.store).contains("no OP_RETURN output"));
+
+        let mut wrong_index = f.proof.clone();
+        wrong_index.data["tx_index"] = serde_json::json!(1);
+        assert!(fails(&f.anchor, &wrong_index, &f.store).contains("Merkle root"));
+
+        let unrelated =
+            HeaderStore::new(PublicChain::Btc, [(7, mine([0u8; 32], [7u8; 32]))]).unwrap();
+        assert!(fails(&f.anchor, &f.proof, &unrelated).contains("not in the pinned header store"));
+
+        let mut ethereum = f.anchor.clone();
+        ethereum.chain = PublicChain::Ethereum;
+        assert!(fails(&ethereum, &f.proof, &f.store).contains("anchor is on ethereum"));
+
+        let mut unknown = f.proof.clone();
+        unknown.kind = "eth_receipt".to_string();
+        assert!(fails(&f.anchor, &unknown, &f.store).contains("no anchor verifier"));
+    }
+
+    #[test]
+    fn test_ots_attestation_verifies() {
+        let f = fixture();
+        // digest -> anchor tx -> txid -> Merkle root, the way OpenTimestamps walks it
+        let tx = hex::decode(f.proof.data["tx"].as_str().unwrap()).unwrap();
+        let branch = btc_merkle_branch(&f.txids, 2);
+        let mut ops = vec![
+            serde_json::json!({"op": "prepend", "arg": hex::encode(&tx[..tx.len() - 36])}),
+            serde_json::json!({"op": "append", "arg": hex::encode(&tx[tx.len() - 4..])}),
+            serde_json::json!({"op": "sha256"}),
+            serde_json::json!({"op": "sha256"}),
+        ];
+        // Index 2 is a left child on level 0 and a right child on level 1
+        for (sibling, op) in branch.iter().zip(["append", "prepend"]) {
+            ops.push(serde_json::json!({"op": op, "arg": hex::encode(sibling)}));
+            ops.push(serde_json::json!({"op": "sha256"}));
+            ops.push(serde_json::json!({"op": "sha256"}));
+        }
+        let mut proof = AnchorProof::from_json(&serde_json::json!({
+            "version": ANCHOR_PROOF_VERSION,
+            "kind": "ots",
+            "chain": "btc",
+            "digest": DIGEST,
+            "ops": ops,
+            "height": 101,
+        }))
+        .unwrap();
+
+        let attestation = verify_public_anchor(&f.anchor, &proof, Some(&f.store)).unwrap();
+        assert!(attestation.header_pinned);
+        assert_eq!(attestation.txid, None);
+        assert_eq!(attestation.block_hash, display_hash(&f.header.hash()));
+
+        assert!(verify_public_anchor(&f.anchor, &proof, None)
+            .unwrap_err()
+            .to_string()
+            .contains("require a pinned header store"));
+
+        proof.data["height"] = serde_json::json!(100);
+        assert!(verify_public_anchor(&f.anchor, &proof, Some(&f.store)).is_err());
+    }
+
+    #[test]
+    fn test_header_store_validation() {
+        let f = fixture();
+        let json = f.store.to_json();
+        let loaded = HeaderStore::from_json(&json).unwrap();
+        assert_eq!(loaded.height_of(&f.header.hash()), Some(101));
+
+        let unlinked = mine([3u8; 32], [4u8; 32]);
+        let err = HeaderStore::new(
+            PublicChain::Btc,
+            [(100, *f.store.header_at(100).unwrap()), (101, unlinked)],
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("does not link"));
+
+        let mut no_pow = BlockHeader::from_hex(GENESIS).unwrap();
+        no_pow.nonce += 1;
+        assert!(HeaderStore::new(PublicChain::Btc, [(0, no_pow)]).is_err());
+        assert!(HeaderStore::new(PublicChain::Hedera, []).is_err());
+
+        let mut wrong_version = json.clone();
+        wrong_version["version"] = serde_json::json!("anchor.headers.v0");
+        assert!(HeaderStore::from_json(&wrong_version).is_err());
+    }
+
+    #[test]
+    fn test_tx_parsing_rejects_malformed() {
+        let tx = op_return_tx(&[5u8; 32]);
+        assert_eq!(tx_output_scripts(&tx).unwrap().len(), 1);
+        assert!(tx_output_scripts(&tx[..tx.len() - 1]).is_err());
+        assert!(tx_output_scripts(&[tx.as_slice(), &[0]].concat()).is_err());
+
+        // Witness serialization (marker 0x00, flag 0x01)
+        let mut witness = tx.clone();
+        witness.splice(4..4, [0, 1]);
+        assert!(tx_output_scripts(&witness).is_err());
+    }
+}
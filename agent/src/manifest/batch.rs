@@ -0,0 +1,299 @@
+//! Batch-Anchoring - Ein Public Anchor für viele Manifeste
+//!
+//! Über die Batch-Blätter mehrerer Manifeste wird ein Merkle-Tree
+//! (`blake3-merkle.v1`, siehe `merkle`) gebaut; nur dessen Root wird
+//! on-chain verankert (`time_anchor.public.digest`). Jedes Manifest trägt in
+//! `time_anchor.batch` seinen eigenen Inclusion-Pfad zur Root.
+//!
+//! Blatt-Arten:
+//! - `audit_tip`: `time_anchor.audit_tip_hex` des Manifests (32 Bytes)
+//! - `manifest`: SHA3-256 über das kanonische Manifest-JSON ohne `time_anchor`
+//!   und `signatures` (beides wird erst beim bzw. nach dem Anchoring gesetzt)
+
+use crate::crypto;
+use crate::merkle::{self, Hash32, MerkleTree};
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use super::types::Manifest;
+
+/// Merkle-Schema des Batch-Trees
+pub const BATCH_SCHEME: &str = "blake3-merkle.v1";
+
+/// Version der Batch-Datei (`audit anchor-batch --out`)
+pub const BATCH_FILE_VERSION: &str = "anchor.batch.v1";
+
+/// Inhalt der Batch-Blätter
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchLeafKind {
+    AuditTip,
+    Manifest,
+}
+
+impl fmt::Display for BatchLeafKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BatchLeafKind::AuditTip => write!(f, "audit_tip"),
+            BatchLeafKind::Manifest => write!(f, "manifest"),
+        }
+    }
+}
+
+impl FromStr for BatchLeafKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "audit-tip" | "audit_tip" => Ok(BatchLeafKind::AuditTip),
+            "manifest" => Ok(BatchLeafKind::Manifest),
+            other => Err(format!(
+                "Invalid batch leaf: {}. Valid options: audit-tip, manifest",
+                other
+            )),
+        }
+    }
+}
+
+/// Batch-Anchor eines Manifests: Inclusion-Pfad zur verankerten Batch-Root
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct TimeAnchorBatch {
+    pub scheme: String,
+    pub leaf_kind: BatchLeafKind,
+    pub leaf_index: usize,
+    pub tree_size: usize,
+    /// Geschwister-Hashes von Blatt- zu Root-Ebene
+    pub path: Vec<String>,
+    /// Batch-Root (0x-präfixiert), entspricht dem Digest des Public Anchors
+    pub root: String,
+}
+
+/// Hash über das Manifest ohne `time_anchor` und `signatures`
+pub fn manifest_leaf_digest(manifest: &Manifest) -> Result<Hash32, Box<dyn Error>> {
+    let mut content = manifest.clone();
+    content.time_anchor = None;
+    content.signatures.clear();
+    Ok(crypto::sha3_256(content.to_canonical_json()?.as_bytes()))
+}
+
+/// Blatt-Hash eines Manifests für die gewählte Blatt-Art
+pub fn batch_leaf(manifest: &Manifest, kind: BatchLeafKind) -> Result<Hash32, Box<dyn Error>> {
+    let data = match kind {
+        BatchLeafKind::AuditTip => {
+            let anchor = manifest
+                .time_anchor
+                .as_ref()
+                .ok_or("time_anchor must be initialized before batch anchoring")?;
+            merkle::from_hex(&anchor.audit_tip_hex)?
+        }
+        BatchLeafKind::Manifest => manifest_leaf_digest(manifest)?,
+    };
+    Ok(merkle::leaf_hash(&data))
+}
+
+impl TimeAnchorBatch {
+    /// Prüft, dass der Inclusion-Pfad vom Blatt des Manifests zur Batch-Root führt
+    pub fn verify(&self, manifest: &Manifest) -> Result<(), Box<dyn Error>> {
+        if self.scheme != BATCH_SCHEME {
+            return Err(format!("Unsupported batch scheme: {}", self.scheme).into());
+        }
+
+        let leaf = batch_leaf(manifest, self.leaf_kind)?;
+        let path = self
+            .path
+            .iter()
+            .map(|hash| merkle::from_hex(hash))
+            .collect::<Result<Vec<_>, _>>()?;
+        let root = merkle::from_hex(&self.root)?;
+
+        if !merkle::verify_inclusion(&leaf, self.leaf_index, self.tree_size, &path, &root) {
+            return Err(format!(
+                "Batch inclusion path (leaf {} of {}) does not lead to batch root {}",
+                self.leaf_index, self.tree_size, self.root
+            )
+            .into());
+        }
+        Ok(())
+    }
+}
+
+/// Merkle-Aggregation über mehrere Manifeste (Reihenfolge = Blatt-Index)
+#[derive(Debug, Clone)]
+pub struct AnchorBatch {
+    leaf_kind: BatchLeafKind,
+    tree: MerkleTree,
+}
+
+impl AnchorBatch {
+    /// Baut den Batch-Tree über die Blätter der Manifeste
+    pub fn build(manifests: &[Manifest], leaf_kind: BatchLeafKind) -> Result<Self, Box<dyn Error>> {
+        if manifests.is_empty() {
+            return Err("Batch requires at least one manifest".into());
+        }
+
+        let leaves = manifests
+            .iter()
+            .enumerate()
+            .map(|(i, manifest)| {
+                batch_leaf(manifest, leaf_kind).map_err(|e| format!("Manifest {}: {}", i, e))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            leaf_kind,
+            tree: MerkleTree::from_leaf_hashes(leaves),
+        })
+    }
+
+    pub fn leaf_kind(&self) -> BatchLeafKind {
+        self.leaf_kind
+    }
+
+    /// Anzahl der Manifeste im Batch
+    pub fn len(&self) -> usize {
+        self.tree.len()
+    }
+
+    #[allow(dead_code)] // Public API - paired with len()
+    pub fn is_empty(&self) -> bool {
+        self.tree.is_empty()
+    }
+
+    /// Batch-Root als 0x-präfixierter Hex-String (wird on-chain verankert)
+    pub fn root_hex(&self) -> String {
+        self.tree.root_hex()
+    }
+
+    /// Blatt-Hash des Manifests an Position `index`
+    pub fn leaf_hex(&self, index: usize) -> Option<String> {
+        self.tree.leaf(index).map(merkle::to_hex)
+    }
+
+    /// Batch-Anchor für das Manifest an Position `index`
+    pub fn anchor_for(&self, index: usize) -> Option<TimeAnchorBatch> {
+        let path = self.tree.inclusion_path(index)?;
+        Some(TimeAnchorBatch {
+            scheme: BATCH_SCHEME.to_string(),
+            leaf_kind: self.leaf_kind,
+            leaf_index: index,
+            tree_size: self.tree.len(),
+            path: path.iter().map(merkle::to_hex).collect(),
+            root: self.tree.root_hex(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::SignatureInfo;
+
+    fn manifest(tip_byte: u8) -> Manifest {
+        let mut manifest: Manifest = serde_json::from_value(serde_json::json!({
+            "version": "manifest.v1.0",
+            "created_at": "2026-01-01T00:00:00Z",
+            "supplier_root": format!("0x{}", "aa".repeat(32)),
+            "ubo_root": format!("0x{}", "bb".repeat(32)),
+            "company_commitment_root": format!("0x{:02x}{}", tip_byte, "cc".repeat(31)),
+            "policy": {"name": "Test", "version": "lksg.v1", "hash": format!("0x{}", "dd".repeat(32))},
+            "audit": {"tail_digest": format!("0x{}", "ee".repeat(32)), "events_count": 1},
+            "proof": {"type": "none", "status": "none"},
+            "signatures": [],
+        }))
+        .unwrap();
+        manifest.set_time_anchor(
+            "blockchain".to_string(),
+            "batch".to_string(),
+            format!("0x{}", hex::encode([tip_byte; 32])),
+        );
+        manifest
+    }
+
+    #[test]
+    fn test_batch_paths_verify() {
+        for kind in [BatchLeafKind::AuditTip, BatchLeafKind::Manifest] {
+            let manifests: Vec<_> = (1..=5).map(manifest).collect();
+            let batch = AnchorBatch::build(&manifests, kind).unwrap();
+            assert_eq!(batch.len(), 5);
+
+            for (i, manifest) in manifests.iter().enumerate() {
+                let anchor = batch.anchor_for(i).unwrap();
+                assert_eq!(anchor.root, batch.root_hex());
+                assert_eq!(anchor.leaf_index, i);
+                anchor.verify(manifest).unwrap();
+
+                // Pfad eines anderen Manifests passt nicht
+                let other = &manifests[(i + 1) % manifests.len()];
+                assert!(anchor.verify(other).is_err());
+            }
+            assert!(batch.anchor_for(5).is_none());
+        }
+    }
+
+    #[test]
+    fn test_manifest_leaf_ignores_anchor_and_signatures() {
+        let mut manifest = manifest(1);
+        let leaf = batch_leaf(&manifest, BatchLeafKind::Manifest).unwrap();
+
+        let batch =
+            AnchorBatch::build(std::slice::from_ref(&manifest), BatchLeafKind::Manifest).unwrap();
+        manifest
+            .set_batch_anchor(batch.anchor_for(0).unwrap())
+            .unwrap();
+        manifest.signatures.push(SignatureInfo {
+            alg: "Ed25519".to_string(),
+            signer: "Test".to_string(),
+            pubkey_hex: "0x00".to_string(),
+            sig_hex: "0x00".to_string(),
+        });
+        assert_eq!(
+            batch_leaf(&manifest, BatchLeafKind::Manifest).unwrap(),
+            leaf
+        );
+
+        manifest.supplier_root = format!("0x{}", "ab".repeat(32));
+        assert_ne!(
+            batch_leaf(&manifest, BatchLeafKind::Manifest).unwrap(),
+            leaf
+        );
+    }
+
+    #[test]
+    fn test_tampered_batch_anchor_rejected() {
+        let manifests: Vec<_> = (1..=3).map(manifest).collect();
+        let batch = AnchorBatch::build(&manifests, BatchLeafKind::AuditTip).unwrap();
+        let anchor = batch.anchor_for(2).unwrap();
+
+        let mut wrong_index = anchor.clone();
+        wrong_index.leaf_index = 1;
+        assert!(wrong_index.verify(&manifests[2]).is_err());
+
+        let mut wrong_size = anchor.clone();
+        wrong_size.tree_size = 4;
+        assert!(wrong_size.verify(&manifests[2]).is_err());
+
+        let mut wrong_scheme = anchor;
+        wrong_scheme.scheme = "sha256-merkle.v0".to_string();
+        assert!(wrong_scheme.verify(&manifests[2]).is_err());
+    }
+
+    #[test]
+    fn test_build_errors() {
+        assert!(AnchorBatch::build(&[], BatchLeafKind::AuditTip).is_err());
+
+        let mut no_anchor = manifest(1);
+        no_anchor.time_anchor = None;
+        assert!(
+            AnchorBatch::build(&[manifest(2), no_anchor.clone()], BatchLeafKind::AuditTip)
+                .unwrap_err()
+                .to_string()
+                .contains("Manifest 1")
+        );
+        assert!(AnchorBatch::build(&[no_anchor], BatchLeafKind::Manifest).is_ok());
+
+        assert_eq!("audit-tip".parse(), Ok(BatchLeafKind::AuditTip));
+        assert!("tip".parse::<BatchLeafKind>().is_err());
+    }
+}
@@ -11,6 +11,7 @@ use std::io::{BufRead, BufReader, Write};
 use std::path::Path;
 
 use super::anchor::{PublicChain, TimeAnchor, TimeAnchorPrivate, TimeAnchorPublic};
+use super::batch::TimeAnchorBatch;
 use super::types::{AuditInfo, Manifest, ProofInfo, MANIFEST_SCHEMA_VERSION};
 
 impl Manifest {
@@ -39,6 +40,11 @@ impl Manifest {
             },
             signatures: Vec::new(),
             time_anchor: None,
+            commitment_scheme: commitments.commitment_scheme,
+            sanctions_root: None,
+            jurisdiction_root: None,
+            supplier_count: commitments.supplier_count,
+            ubo_count: commitments.ubo_count,
         })
     }
 
@@ -118,7 +124,22 @@ impl Manifest {
         Ok(())
     }
 
-    /// Validiert Dual-Anchor-Konsistenz
+    /// Setzt den Batch-Anchor (Inclusion-Pfad zur Batch-Root)
+    ///
+    /// Der Public Anchor muss anschließend auf die Batch-Root gesetzt werden.
+    pub fn set_batch_anchor(&mut self, batch: TimeAnchorBatch) -> Result<(), Box<dyn Error>> {
+        batch.verify(self)?;
+
+        let anchor = self
+            .time_anchor
+            .as_mut()
+            .ok_or("time_anchor must be initialized before setting batch anchor")?;
+        anchor.batch = Some(batch);
+
+        Ok(())
+    }
+
+    /// Validiert Dual-Anchor-Konsistenz (inkl. Batch-Pfad zur Public-Anchor-Root)
     pub fn validate_dual_anchor(&self) -> Result<(), Box<dyn Error>> {
         let anchor = match &self.time_anchor {
             Some(a) => a,
@@ -146,6 +167,19 @@ impl Manifest {
             }
         }
 
+        if let Some(batch) = &anchor.batch {
+            batch.verify(self)?;
+            if let Some(public) = &anchor.public {
+                if !public.digest.eq_ignore_ascii_case(&batch.root) {
+                    return Err(format!(
+                        "Public anchor digest {} does not match batch root {}",
+                        public.digest, batch.root
+                    )
+                    .into());
+                }
+            }
+        }
+
         Ok(())
     }
 
@@ -7,10 +7,14 @@
 //!
 //! - `types`: Manifest, AuditInfo, ProofInfo, SignatureInfo
 //! - `anchor`: TimeAnchor, TimeAnchorPrivate, TimeAnchorPublic, PublicChain
+//! - `anchor_proof`: Offline verification of public anchors (SPV, OTS-style) against pinned headers
+//! - `batch`: Batch-Anchoring (Merkle-Aggregation vieler Manifeste unter einem Public Anchor)
 //! - `signed`: SignedManifest
 //! - `io`: build, save, load, anchor methods
 
 pub mod anchor;
+pub mod anchor_proof;
+pub mod batch;
 pub mod io;
 pub mod signed;
 pub mod types;
@@ -19,6 +23,8 @@ pub mod types;
 #[allow(unused_imports)]
 pub use anchor::{PublicChain, TimeAnchor, TimeAnchorPrivate, TimeAnchorPublic};
 #[allow(unused_imports)]
+pub use batch::{AnchorBatch, BatchLeafKind, TimeAnchorBatch};
+#[allow(unused_imports)]
 pub use io::read_audit_tail;
 pub use signed::SignedManifest;
 #[allow(unused_imports)]
@@ -42,6 +48,8 @@ mod tests {
                 name: "Test".to_string(),
                 version: "lksg.v1".to_string(),
                 hash: "0xhash".to_string(),
+                valid_from: None,
+                valid_until: None,
             },
             audit: AuditInfo {
                 tail_digest: "0xtail".to_string(),
@@ -53,6 +61,11 @@ mod tests {
             },
             signatures: Vec::new(),
             time_anchor: None,
+            commitment_scheme: None,
+            sanctions_root: None,
+            jurisdiction_root: None,
+            supplier_count: None,
+            ubo_count: None,
         }
     }
 
@@ -64,12 +77,15 @@ mod tests {
             company_commitment_root: "0x123".to_string(),
             supplier_count: Some(5),
             ubo_count: Some(2),
+            commitment_scheme: None,
         };
 
         let policy_info = PolicyInfo {
             name: "Test".to_string(),
             version: "lksg.v1".to_string(),
             hash: "0xpolicy".to_string(),
+            valid_from: None,
+            valid_until: None,
         };
 
         let temp_audit = "/tmp/test_manifest_audit.jsonl";
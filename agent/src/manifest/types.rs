@@ -2,6 +2,7 @@
 //!
 //! Provides the main Manifest struct and related info types.
 
+use crate::commitment::CommitmentScheme;
 use crate::policy::PolicyInfo;
 use serde::{Deserialize, Serialize};
 
@@ -48,4 +49,19 @@ pub struct Manifest {
     pub signatures: Vec<SignatureInfo>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub time_anchor: Option<TimeAnchor>,
+    /// Commitment-Schema der Roots (None = Legacy, vor v0.13)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commitment_scheme: Option<CommitmentScheme>,
+    /// Root der Sanktionsliste (sortierter Merkle-Tree, optional)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sanctions_root: Option<String>,
+    /// Root der Jurisdiktions-Blocklist (sortierter Merkle-Tree, optional)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jurisdiction_root: Option<String>,
+    /// Anzahl committeter Supplier (Vollständigkeit von Listen-Ausschlüssen)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub supplier_count: Option<usize>,
+    /// Anzahl committeter UBOs (Vollständigkeit von Listen-Ausschlüssen)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ubo_count: Option<usize>,
 }
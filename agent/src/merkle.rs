@@ -0,0 +1,501 @@
+//! Binärer Merkle-Tree mit Domain-Separation
+//!
+//! Grundbaustein für Supplier-/UBO-Commitments und alle weiteren Merkle-Strukturen.
+//!
+//! ## Konstruktion
+//!
+//! - Leaf-Hash: `BLAKE3(0x00 || leaf_bytes)`
+//! - Node-Hash: `BLAKE3(0x01 || left || right)`
+//! - Ungerade Anzahl Knoten auf einer Ebene: Der letzte Knoten wird unverändert
+//!   in die nächste Ebene übernommen (keine Duplikation). Damit entspricht die
+//!   Baumform der Definition aus RFC 6962.
+//! - Leerer Baum: `BLAKE3("")`
+
+use std::error::Error;
+
+/// Domain-Prefix für Blatt-Hashes
+pub const LEAF_PREFIX: u8 = 0x00;
+
+/// Domain-Prefix für innere Knoten
+pub const NODE_PREFIX: u8 = 0x01;
+
+/// 32-Byte BLAKE3-Digest
+pub type Hash32 = [u8; 32];
+
+/// Berechnet den domain-separierten Hash eines Blattes
+pub fn leaf_hash(data: &[u8]) -> Hash32 {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(&[LEAF_PREFIX]);
+    hasher.update(data);
+    *hasher.finalize().as_bytes()
+}
+
+/// Berechnet den domain-separierten Hash eines inneren Knotens
+pub fn node_hash(left: &Hash32, right: &Hash32) -> Hash32 {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(&[NODE_PREFIX]);
+    hasher.update(left);
+    hasher.update(right);
+    *hasher.finalize().as_bytes()
+}
+
+/// Root eines leeren Baums
+pub fn empty_root() -> Hash32 {
+    *blake3::hash(&[]).as_bytes()
+}
+
+/// Formatiert einen Hash als 0x-präfixierten Hex-String
+pub fn to_hex(hash: &Hash32) -> String {
+    format!("0x{}", hex::encode(hash))
+}
+
+/// Parst einen (optional 0x-präfixierten) Hex-String in einen Hash
+pub fn from_hex(s: &str) -> Result<Hash32, Box<dyn Error>> {
+    let bytes = hex::decode(s.trim_start_matches("0x"))?;
+    bytes
+        .try_into()
+        .map_err(|_| format!("Invalid hash length (expected 32 bytes): {}", s).into())
+}
+
+/// Binärer Merkle-Tree über eine feste Menge von Blättern
+///
+/// Speichert alle Ebenen (Ebene 0 = Blatt-Hashes), damit Pfade ohne
+/// Neuberechnung erzeugt werden können.
+#[derive(Debug, Clone)]
+pub struct MerkleTree {
+    levels: Vec<Vec<Hash32>>,
+}
+
+impl MerkleTree {
+    /// Baut den Baum aus rohen Blattdaten
+    pub fn from_leaves<T: AsRef<[u8]>>(leaves: &[T]) -> Self {
+        let hashes = leaves.iter().map(|l| leaf_hash(l.as_ref())).collect();
+        Self::from_leaf_hashes(hashes)
+    }
+
+    /// Baut den Baum aus bereits berechneten Blatt-Hashes
+    pub fn from_leaf_hashes(leaf_hashes: Vec<Hash32>) -> Self {
+        let mut levels = vec![leaf_hashes];
+
+        while levels[levels.len() - 1].len() > 1 {
+            let current = &levels[levels.len() - 1];
+            let next = current
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => node_hash(left, right),
+                    [single] => *single,
+                    _ => unreachable!("chunks(2) yields one or two elements"),
+                })
+                .collect();
+            levels.push(next);
+        }
+
+        Self { levels }
+    }
+
+    /// Anzahl der Blätter
+    pub fn len(&self) -> usize {
+        self.levels[0].len()
+    }
+
+    /// Prüft, ob der Baum leer ist
+    pub fn is_empty(&self) -> bool {
+        self.levels[0].is_empty()
+    }
+
+    /// Root-Hash des Baums
+    pub fn root(&self) -> Hash32 {
+        match self.levels.last().and_then(|level| level.first()) {
+            Some(root) => *root,
+            None => empty_root(),
+        }
+    }
+
+    /// Root-Hash als 0x-präfixierter Hex-String
+    pub fn root_hex(&self) -> String {
+        to_hex(&self.root())
+    }
+
+    /// Blatt-Hash an Position `index`
+    pub fn leaf(&self, index: usize) -> Option<&Hash32> {
+        self.levels[0].get(index)
+    }
+
+    /// Erzeugt den Inclusion-Pfad (Geschwister-Hashes von unten nach oben)
+    ///
+    /// Ebenen, auf denen der Knoten ohne Geschwister übernommen wird,
+    /// tragen keinen Eintrag bei.
+    ///
+    /// # Rückgabe
+    /// Pfad oder None, wenn `index` außerhalb des Baums liegt
+    pub fn inclusion_path(&self, index: usize) -> Option<Vec<Hash32>> {
+        if index >= self.len() {
+            return None;
+        }
+
+        let mut path = Vec::new();
+        let mut idx = index;
+        for level in &self.levels[..self.levels.len() - 1] {
+            let sibling = idx ^ 1;
+            if sibling < level.len() {
+                path.push(level[sibling]);
+            }
+            idx /= 2;
+        }
+        Some(path)
+    }
+
+    /// Root des Teilbaums über die Blätter `start..end` (MTH nach RFC 9162)
+    ///
+    /// Vollständige, ausgerichtete Teilbäume werden direkt aus den
+    /// gespeicherten Ebenen gelesen.
+    fn subtree_root(&self, start: usize, end: usize) -> Hash32 {
+        let n = end - start;
+        if n.is_power_of_two() && start.is_multiple_of(n) {
+            let level = n.trailing_zeros() as usize;
+            return self.levels[level][start >> level];
+        }
+        let k = largest_power_of_two_below(n);
+        node_hash(
+            &self.subtree_root(start, start + k),
+            &self.subtree_root(start + k, end),
+        )
+    }
+
+    /// Erzeugt den Consistency-Beweis zwischen dem Baum über die ersten
+    /// `old_size` Blätter und diesem Baum
+    ///
+    /// Algorithmus nach RFC 9162, Abschnitt 2.1.4.1. Für `old_size == 0`
+    /// und `old_size == len()` ist der Beweis leer.
+    ///
+    /// # Rückgabe
+    /// Beweis oder None, wenn `old_size` größer als der Baum ist
+    pub fn consistency_proof(&self, old_size: usize) -> Option<Vec<Hash32>> {
+        if old_size > self.len() {
+            return None;
+        }
+        let mut proof = Vec::new();
+        if old_size > 0 {
+            self.subproof(old_size, 0, self.len(), true, &mut proof);
+        }
+        Some(proof)
+    }
+
+    /// SUBPROOF(m, D[start:end], b) aus RFC 9162
+    fn subproof(
+        &self,
+        m: usize,
+        start: usize,
+        end: usize,
+        complete: bool,
+        proof: &mut Vec<Hash32>,
+    ) {
+        let n = end - start;
+        if m == n {
+            if !complete {
+                proof.push(self.subtree_root(start, end));
+            }
+            return;
+        }
+
+        let k = largest_power_of_two_below(n);
+        if m <= k {
+            self.subproof(m, start, start + k, complete, proof);
+            proof.push(self.subtree_root(start + k, end));
+        } else {
+            self.subproof(m - k, start + k, end, false, proof);
+            proof.push(self.subtree_root(start, start + k));
+        }
+    }
+}
+
+/// Größte Zweierpotenz echt kleiner als `n` (n > 1)
+fn largest_power_of_two_below(n: usize) -> usize {
+    1 << (usize::BITS - 1 - (n - 1).leading_zeros())
+}
+
+/// Rekonstruiert den Root aus Blatt-Hash und Inclusion-Pfad
+///
+/// Algorithmus nach RFC 9162, Abschnitt 2.1.3.2.
+///
+/// # Argumente
+/// * `leaf` - Blatt-Hash
+/// * `index` - Position des Blattes
+/// * `tree_size` - Anzahl Blätter im Baum
+/// * `path` - Inclusion-Pfad
+///
+/// # Rückgabe
+/// Root oder None, wenn Pfadlänge und Baumgröße nicht zusammenpassen
+pub fn root_from_inclusion_path(
+    leaf: &Hash32,
+    index: usize,
+    tree_size: usize,
+    path: &[Hash32],
+) -> Option<Hash32> {
+    if index >= tree_size {
+        return None;
+    }
+
+    let mut f_n = index;
+    let mut s_n = tree_size - 1;
+    let mut r = *leaf;
+
+    for p in path {
+        if s_n == 0 {
+            return None;
+        }
+        if f_n & 1 == 1 || f_n == s_n {
+            r = node_hash(p, &r);
+            while f_n & 1 == 0 && f_n != 0 {
+                f_n >>= 1;
+                s_n >>= 1;
+            }
+        } else {
+            r = node_hash(&r, p);
+        }
+        f_n >>= 1;
+        s_n >>= 1;
+    }
+
+    if s_n != 0 {
+        return None;
+    }
+    Some(r)
+}
+
+/// Prüft einen Inclusion-Pfad gegen einen erwarteten Root
+pub fn verify_inclusion(
+    leaf: &Hash32,
+    index: usize,
+    tree_size: usize,
+    path: &[Hash32],
+    root: &Hash32,
+) -> bool {
+    root_from_inclusion_path(leaf, index, tree_size, path).as_ref() == Some(root)
+}
+
+/// Prüft einen Consistency-Beweis zwischen zwei Baumgrößen
+///
+/// Algorithmus nach RFC 9162, Abschnitt 2.1.4.2: Der Baum mit `new_size`
+/// Blättern und Root `new_root` ist eine reine Erweiterung des Baums mit
+/// `old_size` Blättern und Root `old_root`.
+pub fn verify_consistency(
+    old_size: usize,
+    new_size: usize,
+    old_root: &Hash32,
+    new_root: &Hash32,
+    proof: &[Hash32],
+) -> bool {
+    if old_size > new_size {
+        return false;
+    }
+    if old_size == new_size {
+        return proof.is_empty() && old_root == new_root;
+    }
+    if old_size == 0 {
+        return proof.is_empty() && *old_root == empty_root();
+    }
+
+    let mut path = Vec::with_capacity(proof.len() + 1);
+    if old_size.is_power_of_two() {
+        path.push(*old_root);
+    }
+    path.extend_from_slice(proof);
+    let Some((first, rest)) = path.split_first() else {
+        return false;
+    };
+
+    let mut f_n = old_size - 1;
+    let mut s_n = new_size - 1;
+    while f_n & 1 == 1 {
+        f_n >>= 1;
+        s_n >>= 1;
+    }
+
+    let mut f_r = *first;
+    let mut s_r = *first;
+    for c in rest {
+        if s_n == 0 {
+            return false;
+        }
+        if f_n & 1 == 1 || f_n == s_n {
+            f_r = node_hash(c, &f_r);
+            s_r = node_hash(c, &s_r);
+            while f_n & 1 == 0 && f_n != 0 {
+                f_n >>= 1;
+                s_n >>= 1;
+            }
+        } else {
+            s_r = node_hash(&s_r, c);
+        }
+        f_n >>= 1;
+        s_n >>= 1;
+    }
+
+    s_n == 0 && f_r == *old_root && s_r == *new_root
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_leaf_and_node_prefixes_differ() {
+        let a = leaf_hash(b"a");
+        let b = leaf_hash(b"b");
+        let mut concat = Vec::new();
+        concat.extend_from_slice(&a);
+        concat.extend_from_slice(&b);
+        // Ein Blatt mit dem Inhalt left||right darf nicht mit dem Knoten kollidieren
+        assert_ne!(node_hash(&a, &b), leaf_hash(&concat));
+    }
+
+    #[test]
+    fn test_empty_and_single_leaf() {
+        let empty = MerkleTree::from_leaves::<&[u8]>(&[]);
+        assert!(empty.is_empty());
+        assert_eq!(empty.root(), empty_root());
+
+        let single = MerkleTree::from_leaves(&[b"x"]);
+        assert_eq!(single.root(), leaf_hash(b"x"));
+    }
+
+    #[test]
+    fn test_odd_leaf_is_promoted() {
+        let tree = MerkleTree::from_leaves(&[b"a", b"b", b"c"]);
+        let expected = node_hash(
+            &node_hash(&leaf_hash(b"a"), &leaf_hash(b"b")),
+            &leaf_hash(b"c"),
+        );
+        assert_eq!(tree.root(), expected);
+    }
+
+    #[test]
+    fn test_order_matters() {
+        let t1 = MerkleTree::from_leaves(&[b"a", b"b"]);
+        let t2 = MerkleTree::from_leaves(&[b"b", b"a"]);
+        assert_ne!(t1.root(), t2.root());
+    }
+
+    #[test]
+    fn test_inclusion_paths_verify_for_all_sizes() {
+        for size in 1..=17usize {
+            let leaves: Vec<Vec<u8>> = (0..size).map(|i| vec![i as u8]).collect();
+            let tree = MerkleTree::from_leaves(&leaves);
+            for index in 0..size {
+                let path = tree.inclusion_path(index).unwrap();
+                let leaf = tree.leaf(index).unwrap();
+                assert!(
+                    verify_inclusion(leaf, index, size, &path, &tree.root()),
+                    "size {} index {}",
+                    size,
+                    index
+                );
+            }
+            assert!(tree.inclusion_path(size).is_none());
+        }
+    }
+
+    #[test]
+    fn test_inclusion_rejects_wrong_position_or_leaf() {
+        let tree = MerkleTree::from_leaves(&[b"a", b"b", b"c", b"d", b"e"]);
+        let path = tree.inclusion_path(1).unwrap();
+        let leaf = tree.leaf(1).unwrap();
+
+        assert!(!verify_inclusion(leaf, 0, 5, &path, &tree.root()));
+        assert!(!verify_inclusion(leaf, 1, 2, &path, &tree.root()));
+        assert!(!verify_inclusion(
+            &leaf_hash(b"x"),
+            1,
+            5,
+            &path,
+            &tree.root()
+        ));
+    }
+
+    #[test]
+    fn test_consistency_proofs_verify_for_all_sizes() {
+        let leaves: Vec<Vec<u8>> = (0..17u8).map(|i| vec![i]).collect();
+        for new_size in 1..=leaves.len() {
+            let new_tree = MerkleTree::from_leaves(&leaves[..new_size]);
+            for old_size in 0..=new_size {
+                let old_root = MerkleTree::from_leaves(&leaves[..old_size]).root();
+                let proof = new_tree.consistency_proof(old_size).unwrap();
+                assert!(
+                    verify_consistency(old_size, new_size, &old_root, &new_tree.root(), &proof),
+                    "old {} new {}",
+                    old_size,
+                    new_size
+                );
+            }
+            assert!(new_tree.consistency_proof(new_size + 1).is_none());
+        }
+    }
+
+    #[test]
+    fn test_consistency_rejects_rewritten_history() {
+        let leaves: Vec<Vec<u8>> = (0..7u8).map(|i| vec![i]).collect();
+        let old_root = MerkleTree::from_leaves(&leaves[..3]).root();
+        let new_tree = MerkleTree::from_leaves(&leaves);
+        let proof = new_tree.consistency_proof(3).unwrap();
+
+        // Geändertes Blatt im alten Bereich
+        let mut rewritten = leaves.clone();
+        rewritten[1] = vec![99];
+        let rewritten_tree = MerkleTree::from_leaves(&rewritten);
+        let rewritten_proof = rewritten_tree.consistency_proof(3).unwrap();
+        assert!(!verify_consistency(
+            3,
+            7,
+            &old_root,
+            &rewritten_tree.root(),
+            &rewritten_proof
+        ));
+
+        // Falsche Größen, falscher Root, manipulierter Pfad
+        assert!(!verify_consistency(
+            4,
+            7,
+            &old_root,
+            &new_tree.root(),
+            &proof
+        ));
+        assert!(!verify_consistency(
+            7,
+            3,
+            &old_root,
+            &new_tree.root(),
+            &proof
+        ));
+        assert!(!verify_consistency(
+            3,
+            7,
+            &new_tree.root(),
+            &new_tree.root(),
+            &proof
+        ));
+        let mut tampered = proof.clone();
+        tampered[0] = leaf_hash(b"x");
+        assert!(!verify_consistency(
+            3,
+            7,
+            &old_root,
+            &new_tree.root(),
+            &tampered
+        ));
+        assert!(!verify_consistency(
+            3,
+            7,
+            &old_root,
+            &new_tree.root(),
+            &proof[1..]
+        ));
+    }
+
+    #[test]
+    fn test_hex_roundtrip() {
+        let root = MerkleTree::from_leaves(&[b"a"]).root();
+        assert_eq!(from_hex(&to_hex(&root)).unwrap(), root);
+        assert!(from_hex("0xabcd").is_err());
+    }
+}
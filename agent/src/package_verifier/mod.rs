@@ -36,13 +36,15 @@ mod tests {
         Manifest {
             version: "manifest.v1.0".to_string(),
             created_at: "2025-10-25T10:00:00Z".to_string(),
-            supplier_root: "0xabc".to_string(),
-            ubo_root: "0xdef".to_string(),
-            company_commitment_root: "0x123".to_string(),
+            supplier_root: format!("0x{}", "a".repeat(64)),
+            ubo_root: format!("0x{}", "d".repeat(64)),
+            company_commitment_root: format!("0x{}", "1".repeat(64)),
             policy: PolicyInfo {
                 name: "Test Policy".to_string(),
                 version: "lksg.v1".to_string(),
-                hash: "0xpolicy".to_string(),
+                hash: format!("0x{}", "f".repeat(64)),
+                valid_from: None,
+                valid_until: None,
             },
             audit: AuditInfo {
                 tail_digest: "0xtail".to_string(),
@@ -54,9 +56,22 @@ mod tests {
             },
             signatures: Vec::new(),
             time_anchor: None,
+            commitment_scheme: None,
+            sanctions_root: None,
+            jurisdiction_root: None,
+            supplier_count: None,
+            ubo_count: None,
         }
     }
 
+    /// Signiert das Manifest (Signatur wird angehängt)
+    fn sign(mut manifest: Manifest) -> Manifest {
+        let key = ed25519_dalek::SigningKey::from_bytes(&[7u8; 32]);
+        let signed = crate::sign::sign_manifest(&manifest, &key, "Test").unwrap();
+        manifest.signatures.push(signed.signature);
+        manifest
+    }
+
     #[test]
     fn test_verifier_check_integrity() {
         let test_dir = "/tmp/test_proof_package_mod";
@@ -101,11 +116,14 @@ mod tests {
             statement: "policy:lksg.v1".to_string(),
             manifest_hash,
             policy_hash: "0xpolicy".to_string(),
+            ir_hash: None,
             proof_data: ProofData {
                 checked_constraints: vec![ConstraintCheck {
                     name: "test_check".to_string(),
                     ok: true,
                 }],
+                list_exclusions: Vec::new(),
+                rule_activations: Vec::new(),
             },
             status: "ok".to_string(),
         };
@@ -130,7 +148,7 @@ mod tests {
         let _ = fs::remove_dir_all(test_dir);
         fs::create_dir_all(test_dir).unwrap();
 
-        let manifest = create_test_manifest();
+        let manifest = sign(create_test_manifest());
         let manifest_hash = Proof::compute_manifest_hash(&manifest).unwrap();
 
         let proof = Proof {
@@ -138,7 +156,8 @@ mod tests {
             proof_type: "mock".to_string(),
             statement: "policy:lksg.v1".to_string(),
             manifest_hash: manifest_hash.clone(),
-            policy_hash: "0xpolicy".to_string(),
+            policy_hash: manifest.policy.hash.clone(),
+            ir_hash: None,
             proof_data: ProofData {
                 checked_constraints: vec![
                     ConstraintCheck {
@@ -150,6 +169,8 @@ mod tests {
                         ok: true,
                     },
                 ],
+                list_exclusions: Vec::new(),
+                rule_activations: Vec::new(),
             },
             status: "ok".to_string(),
         };
@@ -164,8 +185,23 @@ mod tests {
         let verifier = Verifier::new(test_dir);
         let result = verifier.verify().unwrap();
 
-        assert!(result.success);
+        assert!(result.success, "{:?}", result.failed_checks);
         assert_eq!(result.manifest_hash, manifest_hash);
+        assert_eq!(result.checks_passed, result.checks_total);
+
+        // Ohne Signatur schlägt dasselbe Paket fehl (wie in jedem anderen Verifier)
+        create_test_manifest()
+            .save(format!("{}/manifest.json", test_dir))
+            .unwrap();
+        let mut unsigned_proof = proof.clone();
+        unsigned_proof.manifest_hash =
+            Proof::compute_manifest_hash(&create_test_manifest()).unwrap();
+        unsigned_proof
+            .save_as_dat(format!("{}/proof.dat", test_dir))
+            .unwrap();
+        let result = verifier.verify().unwrap();
+        assert!(!result.success);
+        assert!(result.failed_checks[0].starts_with("signatures:"));
 
         fs::remove_dir_all(test_dir).ok();
     }
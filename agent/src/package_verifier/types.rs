@@ -22,6 +22,8 @@ pub struct VerificationResult {
     pub proof_status: String,
     pub checks_passed: usize,
     pub checks_total: usize,
+    /// Fehlgeschlagene Checks ("id: message")
+    pub failed_checks: Vec<String>,
 }
 
 /// Bundle-Verifikations-Ergebnis
@@ -36,6 +38,9 @@ pub struct BundleVerifyResult {
 }
 
 /// Aggregiert den Gesamtstatus aus allen Unit-Ergebnissen
+///
+/// Der Status einer Unit ergibt sich aus ihren Check-Ergebnissen; ein
+/// Report mit Status "fail" zählt mindestens als Fail.
 pub fn aggregate_status(
     unit_results: &[(String, crate::verifier::core::VerifyReport)],
 ) -> VerifyStatus {
@@ -43,16 +48,10 @@ pub fn aggregate_status(
         .iter()
         .fold(VerifyStatus::Ok, |acc, (_id, report)| {
             let unit_status = match report.status.as_str() {
-                "ok" => VerifyStatus::Ok,
-                "fail" => VerifyStatus::Fail,
-                _ => VerifyStatus::Warn,
+                "ok" => report.overall_status(),
+                _ => report.overall_status().combine(VerifyStatus::Fail),
             };
-            match (acc, unit_status) {
-                (VerifyStatus::Error, _) | (_, VerifyStatus::Error) => VerifyStatus::Error,
-                (VerifyStatus::Fail, _) | (_, VerifyStatus::Fail) => VerifyStatus::Fail,
-                (VerifyStatus::Warn, _) | (_, VerifyStatus::Warn) => VerifyStatus::Warn,
-                _ => VerifyStatus::Ok,
-            }
+            acc.combine(unit_status)
         })
 }
 
@@ -6,9 +6,7 @@
 use crate::manifest::Manifest;
 use crate::proof_engine::Proof;
 use crate::bundle::meta::{check_dependency_cycles, load_bundle_meta, BundleMeta, ProofUnitMeta};
-use crate::verifier::core::{
-    extract_statement_from_manifest, verify as core_verify, VerifyOptions,
-};
+use crate::verifier::core::{verify_bytes, VerifyOptions, VerifyReport};
 use std::error::Error;
 use std::path::{Path, PathBuf};
 
@@ -80,30 +78,20 @@ impl Verifier {
             .get("proof.dat")
             .ok_or("proof.dat missing in validated files")?;
 
-        // 5. Parse manifest JSON
-        let manifest_json: serde_json::Value = serde_json::from_slice(manifest_bytes)?;
-
-        // 6. Extract statement from manifest
-        println!("📝 Extrahiere Proof-Statement...");
-        let stmt = extract_statement_from_manifest(&manifest_json)?;
-
-        // 7. Verify with Core-Verify API
+        // 5. Verify with the verification engine
         println!("🔍 Führe Kern-Verifikation aus...");
-        let opts = VerifyOptions {
-            check_timestamp: false,
-            check_registry: false,
-        };
-        let report = core_verify(&manifest_json, proof_bytes, &stmt, &opts)?;
-
-        // 8. Build result
-        Ok(VerificationResult {
-            success: report.status == "ok",
-            manifest_hash: report.manifest_hash,
-            policy_hash: stmt.policy_hash,
-            proof_status: report.status,
-            checks_passed: 0,
-            checks_total: 0,
-        })
+        let expected_hash = |file: &str| meta.files.get(file).map(|f| f.hash.as_str());
+        let report = verify_bytes(
+            manifest_bytes,
+            proof_bytes,
+            expected_hash("manifest.json"),
+            expected_hash("proof.dat"),
+            &VerifyOptions::default(),
+        )?;
+
+        // 6. Build result
+        let manifest_json: serde_json::Value = serde_json::from_slice(manifest_bytes)?;
+        Ok(Self::result_from_report(&manifest_json, &report))
     }
 
     /// Legacy bundle verification (fallback for old bundles)
@@ -122,28 +110,55 @@ impl Verifier {
         }
         let proof = Proof::load_from_dat(&proof_path)?;
 
-        // 3. Verifiziere Proof gegen Manifest
+        // 3. Prüfe Proof-Bindung an das Manifest (Legacy-Format)
         proof.verify(&manifest)?;
 
-        // 4. Zähle Checks
-        let checks_total = proof.proof_data.checked_constraints.len();
-        let checks_passed = proof
-            .proof_data
-            .checked_constraints
-            .iter()
-            .filter(|c| c.ok)
-            .count();
-
+        // 4. Verifiziere mit der Verification Engine
+        let manifest_bytes = std::fs::read(&manifest_path)?;
+        let proof_bytes = std::fs::read(&proof_path)?;
+        let report = verify_bytes(
+            &manifest_bytes,
+            &proof_bytes,
+            None,
+            None,
+            &VerifyOptions::default(),
+        )?;
+
+        // Legacy-Proofs sind an den Hash des Manifest-Structs gebunden
+        let manifest_json: serde_json::Value = serde_json::from_slice(&manifest_bytes)?;
         Ok(VerificationResult {
-            success: true,
             manifest_hash: proof.manifest_hash.clone(),
-            policy_hash: proof.policy_hash.clone(),
-            proof_status: proof.status.clone(),
-            checks_passed,
-            checks_total,
+            ..Self::result_from_report(&manifest_json, &report)
         })
     }
 
+    /// Baut das Verifier-Ergebnis aus einem Engine-Report
+    fn result_from_report(manifest: &serde_json::Value, report: &VerifyReport) -> VerificationResult {
+        let failed: Vec<String> = report
+            .checks
+            .iter()
+            .filter(|c| !c.status.is_pass())
+            .map(|c| format!("{}: {}", c.id, c.message))
+            .collect();
+
+        VerificationResult {
+            success: report.status == "ok",
+            manifest_hash: report.manifest_hash.clone(),
+            policy_hash: manifest["policy"]["hash"]
+                .as_str()
+                .unwrap_or_default()
+                .to_string(),
+            proof_status: report.status.clone(),
+            checks_passed: report
+                .checks
+                .iter()
+                .filter(|c| c.status.is_pass())
+                .count(),
+            checks_total: report.checks.len(),
+            failed_checks: failed,
+        }
+    }
+
     /// Extrahiert Manifest-Informationen
     pub fn extract_manifest(&self) -> Result<Manifest, Box<dyn Error>> {
         let manifest_path = self.package_dir.join("manifest.json");
@@ -221,7 +236,7 @@ impl Verifier {
         &self,
         unit: &ProofUnitMeta,
         meta: &BundleMeta,
-    ) -> anyhow::Result<crate::verifier::core::VerifyReport> {
+    ) -> anyhow::Result<VerifyReport> {
         // 1. Lade Manifest
         let manifest_path = self.package_dir.join(&unit.manifest_file);
         let manifest_bytes = std::fs::read(&manifest_path)?;
@@ -230,7 +245,7 @@ impl Verifier {
         let proof_path = self.package_dir.join(&unit.proof_file);
         let proof_bytes = std::fs::read(&proof_path)?;
 
-        // 3. Validiere Hashes gegen _meta.json
+        // 3. Soll-Hashes aus _meta.json
         let manifest_file_meta = meta.files.get(&unit.manifest_file).ok_or_else(|| {
             anyhow::anyhow!(
                 "Manifest file not found in _meta.json: {}",
@@ -243,40 +258,14 @@ impl Verifier {
             .get(&unit.proof_file)
             .ok_or_else(|| anyhow::anyhow!("Proof file not found in _meta.json: {}", unit.proof_file))?;
 
-        let computed_manifest_hash =
-            crate::crypto::hex_lower_prefixed32(crate::crypto::sha3_256(&manifest_bytes));
-        let computed_proof_hash =
-            crate::crypto::hex_lower_prefixed32(crate::crypto::sha3_256(&proof_bytes));
-
-        if computed_manifest_hash != manifest_file_meta.hash {
-            return Err(anyhow::anyhow!(
-                "Manifest hash mismatch: expected {}, got {}",
-                manifest_file_meta.hash,
-                computed_manifest_hash
-            ));
-        }
-
-        if computed_proof_hash != proof_file_meta.hash {
-            return Err(anyhow::anyhow!(
-                "Proof hash mismatch: expected {}, got {}",
-                proof_file_meta.hash,
-                computed_proof_hash
-            ));
-        }
-
-        // 4. Parse manifest and extract statement
-        let manifest_json: serde_json::Value = serde_json::from_slice(&manifest_bytes)?;
-        let stmt = extract_statement_from_manifest(&manifest_json)?;
-
-        // 5. Create verify options
-        let opts = VerifyOptions {
-            check_timestamp: false,
-            check_registry: false,
-        };
-
-        // 6. Call Core-Verify API
-        let report = core_verify(&manifest_json, &proof_bytes, &stmt, &opts)?;
-        Ok(report)
+        // 4. Verification Engine (inkl. Hash-Abgleich gegen _meta.json)
+        verify_bytes(
+            &manifest_bytes,
+            &proof_bytes,
+            Some(&manifest_file_meta.hash),
+            Some(&proof_file_meta.hash),
+            &VerifyOptions::default(),
+        )
     }
 }
 
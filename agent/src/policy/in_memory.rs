@@ -148,6 +148,8 @@ mod tests {
                 require_statement_roots: None,
             },
             notes: "Test policy".to_string(),
+            valid_from: None,
+            valid_until: None,
         }
     }
 
@@ -7,10 +7,13 @@ pub mod metadata; // PolicyMetadata, PolicyStatus, CompiledPolicy
 pub mod sqlite;
 pub mod store; // PolicyStore Trait
 pub mod types; // Core Policy structures (Policy, PolicyConstraints, PolicyInfo) // SqlitePolicyStore
-               // pub mod filesystem;   // FileSystemPolicyStore (planned)
+pub mod validity; // ValidityWindow (valid_from / valid_until)
+                  // pub mod filesystem;   // FileSystemPolicyStore (planned)
 
 // Re-export core types for backward compatibility
 pub use types::{Policy, PolicyInfo};
+#[allow(unused_imports)]
+pub use validity::{ValidityError, ValidityWindow};
 
 // Allow unused imports for types that may be used in different build targets
 #[allow(unused_imports)]
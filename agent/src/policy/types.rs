@@ -4,6 +4,8 @@ use std::error::Error;
 use std::fs::File;
 use std::path::Path;
 
+use super::validity::{ValidityError, ValidityWindow};
+
 /// Policy-Datenstruktur gemäß LkSG v1 Schema
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Policy {
@@ -13,6 +15,12 @@ pub struct Policy {
     pub constraints: PolicyConstraints,
     #[serde(default)]
     pub notes: String,
+    /// Erster Gültigkeitstag (ISO 8601, optional)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub valid_from: Option<String>,
+    /// Letzter Gültigkeitstag (ISO 8601, optional)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub valid_until: Option<String>,
 }
 
 /// Policy-Constraints (Regeln)
@@ -34,6 +42,18 @@ pub struct PolicyInfo {
     pub name: String,
     pub version: String,
     pub hash: String,
+    /// Gültigkeitszeitraum der Policy (für die Prüfung beim Verifizieren)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub valid_from: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub valid_until: Option<String>,
+}
+
+impl PolicyInfo {
+    /// Gültigkeitszeitraum der Policy
+    pub fn validity(&self) -> Result<ValidityWindow, ValidityError> {
+        ValidityWindow::parse(self.valid_from.as_deref(), self.valid_until.as_deref())
+    }
 }
 
 impl Policy {
@@ -86,6 +106,9 @@ impl Policy {
             return Err("Ungültiges Timestamp-Format (erwartet RFC3339)".into());
         }
 
+        // Gültigkeitszeitraum (Datumsformat, valid_from <= valid_until)
+        self.validity()?;
+
         // Semantische Validierung
         if self.constraints.supplier_count_max == 0 {
             return Err("supplier_count_max muss > 0 sein".into());
@@ -136,9 +159,16 @@ impl Policy {
             name: self.name.clone(),
             version: self.version.clone(),
             hash: self.compute_hash()?,
+            valid_from: self.valid_from.clone(),
+            valid_until: self.valid_until.clone(),
         })
     }
 
+    /// Gültigkeitszeitraum der Policy
+    pub fn validity(&self) -> Result<ValidityWindow, ValidityError> {
+        ValidityWindow::parse(self.valid_from.as_deref(), self.valid_until.as_deref())
+    }
+
     /// Prüft ob alle geforderten Statement-Roots vorhanden sind
     ///
     /// # Argumente
@@ -200,6 +230,8 @@ mod tests {
                 require_statement_roots: None,
             },
             notes: "Test".to_string(),
+            valid_from: None,
+            valid_until: None,
         };
 
         assert!(policy.validate().is_ok());
@@ -218,6 +250,8 @@ mod tests {
                 require_statement_roots: None,
             },
             notes: "".to_string(),
+            valid_from: None,
+            valid_until: None,
         };
 
         assert!(policy.validate().is_err());
@@ -236,6 +270,8 @@ mod tests {
                 require_statement_roots: None,
             },
             notes: "".to_string(),
+            valid_from: None,
+            valid_until: None,
         };
 
         let hash1 = policy.compute_hash().unwrap();
@@ -281,6 +317,8 @@ notes: "Test notes"
                 require_statement_roots: None,
             },
             notes: "".to_string(),
+            valid_from: None,
+            valid_until: None,
         };
         assert!(policy_ok.validate().is_ok());
 
@@ -296,6 +334,8 @@ notes: "Test notes"
                 require_statement_roots: None,
             },
             notes: "".to_string(),
+            valid_from: None,
+            valid_until: None,
         };
         assert!(policy_fail.validate().is_err());
     }
@@ -314,6 +354,8 @@ notes: "Test notes"
                 require_statement_roots: None,
             },
             notes: "".to_string(),
+            valid_from: None,
+            valid_until: None,
         };
         assert!(policy_ok.validate().is_ok());
 
@@ -329,6 +371,8 @@ notes: "Test notes"
                 require_statement_roots: None,
             },
             notes: "".to_string(),
+            valid_from: None,
+            valid_until: None,
         };
         assert!(policy_fail.validate().is_err());
     }
@@ -346,6 +390,8 @@ notes: "Test notes"
                 require_statement_roots: Some(vec!["sanctions_root".to_string()]),
             },
             notes: "".to_string(),
+            valid_from: None,
+            valid_until: None,
         };
 
         // Policy ist valid
@@ -0,0 +1,160 @@
+//! Gültigkeitszeitraum von Policies (`valid_from` / `valid_until`)
+//!
+//! Beide Grenzen sind ISO-8601-Daten (`YYYY-MM-DD`) und inklusiv. Fehlt eine
+//! Grenze, ist der Zeitraum in diese Richtung offen.
+
+use chrono::{DateTime, NaiveDate, Utc};
+use std::fmt;
+
+/// Geparster Gültigkeitszeitraum einer Policy
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ValidityWindow {
+    pub valid_from: Option<NaiveDate>,
+    pub valid_until: Option<NaiveDate>,
+}
+
+/// Fehler bei Parsing oder Prüfung des Gültigkeitszeitraums
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidityError {
+    /// Ungültiges Datum oder `valid_from` nach `valid_until`
+    Invalid(String),
+    /// Referenzdatum liegt vor `valid_from`
+    NotYetValid {
+        valid_from: NaiveDate,
+        date: NaiveDate,
+    },
+    /// Referenzdatum liegt nach `valid_until`
+    Expired {
+        valid_until: NaiveDate,
+        date: NaiveDate,
+    },
+}
+
+impl fmt::Display for ValidityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidityError::Invalid(msg) => write!(f, "invalid validity window: {}", msg),
+            ValidityError::NotYetValid { valid_from, date } => write!(
+                f,
+                "not yet valid (valid from {}, reference date {})",
+                valid_from, date
+            ),
+            ValidityError::Expired { valid_until, date } => write!(
+                f,
+                "expired (valid until {}, reference date {})",
+                valid_until, date
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ValidityError {}
+
+fn parse_date(field: &str, value: &str) -> Result<NaiveDate, ValidityError> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map_err(|e| ValidityError::Invalid(format!("{} '{}': {}", field, value, e)))
+}
+
+impl ValidityWindow {
+    /// Parst `valid_from` / `valid_until` (jeweils optional)
+    pub fn parse(
+        valid_from: Option<&str>,
+        valid_until: Option<&str>,
+    ) -> Result<Self, ValidityError> {
+        let window = ValidityWindow {
+            valid_from: valid_from
+                .map(|v| parse_date("valid_from", v))
+                .transpose()?,
+            valid_until: valid_until
+                .map(|v| parse_date("valid_until", v))
+                .transpose()?,
+        };
+        if let (Some(from), Some(until)) = (window.valid_from, window.valid_until) {
+            if from > until {
+                return Err(ValidityError::Invalid(format!(
+                    "valid_from {} is after valid_until {}",
+                    from, until
+                )));
+            }
+        }
+        Ok(window)
+    }
+
+    /// Prüft, ob die Policy am Datum `date` gültig ist
+    pub fn check_on(&self, date: NaiveDate) -> Result<(), ValidityError> {
+        if let Some(valid_from) = self.valid_from {
+            if date < valid_from {
+                return Err(ValidityError::NotYetValid { valid_from, date });
+            }
+        }
+        if let Some(valid_until) = self.valid_until {
+            if date > valid_until {
+                return Err(ValidityError::Expired { valid_until, date });
+            }
+        }
+        Ok(())
+    }
+
+    /// Prüft die Gültigkeit zu einem RFC3339-Zeitpunkt (Datum in UTC)
+    #[allow(dead_code)] // Public API - used by verifier::verify
+    pub fn check_at(&self, timestamp: &str) -> Result<(), ValidityError> {
+        let date = DateTime::parse_from_rfc3339(timestamp)
+            .map_err(|e| ValidityError::Invalid(format!("timestamp '{}': {}", timestamp, e)))?
+            .with_timezone(&Utc)
+            .date_naive();
+        self.check_on(date)
+    }
+
+    /// Prüft die Gültigkeit am heutigen Tag (UTC)
+    pub fn check_today(&self) -> Result<(), ValidityError> {
+        self.check_on(Utc::now().date_naive())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_window_bounds_inclusive() {
+        let window = ValidityWindow::parse(Some("2025-01-01"), Some("2025-12-31")).unwrap();
+        assert!(window.check_on(date("2025-01-01")).is_ok());
+        assert!(window.check_on(date("2025-12-31")).is_ok());
+        assert!(matches!(
+            window.check_on(date("2024-12-31")),
+            Err(ValidityError::NotYetValid { .. })
+        ));
+        assert!(matches!(
+            window.check_on(date("2026-01-01")),
+            Err(ValidityError::Expired { .. })
+        ));
+    }
+
+    #[test]
+    fn test_unbounded_window() {
+        let window = ValidityWindow::parse(None, None).unwrap();
+        assert_eq!(window, ValidityWindow::default());
+        assert!(window.check_on(date("1970-01-01")).is_ok());
+        assert!(window.check_today().is_ok());
+    }
+
+    #[test]
+    fn test_invalid_window() {
+        assert!(ValidityWindow::parse(Some("soon"), None).is_err());
+        assert!(ValidityWindow::parse(Some("2026-01-01"), Some("2025-01-01")).is_err());
+    }
+
+    #[test]
+    fn test_check_at_uses_utc_date() {
+        let window = ValidityWindow::parse(None, Some("2025-12-31")).unwrap();
+        assert!(window.check_at("2025-12-31T23:30:00Z").is_ok());
+        // 2026-01-01T00:30+02:00 ist 2025-12-31T22:30Z
+        assert!(window.check_at("2026-01-01T00:30:00+02:00").is_ok());
+        assert!(window.check_at("2026-01-01T00:00:01Z").is_err());
+        assert!(window.check_at("not a timestamp").is_err());
+    }
+}
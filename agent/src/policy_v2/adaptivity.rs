@@ -0,0 +1,459 @@
+//! Adaptivity engine
+//!
+//! Evaluates the predicates of a compiled policy against the input context
+//! and resolves which rules are active.
+//!
+//! Predicate expressions are JSON values:
+//! * `{ "var": "name" }` - input from the context
+//! * `{ "func": "name", "args": [...] }` - builtin function
+//! * any other bool/integer/string/array - literal
+//!
+//! Builtins: `and`, `or`, `not`, `eq`, `ne`, `lt`, `le`, `gt`, `ge`,
+//! `len`, `min`, `max`, `in`.
+//!
+//! Activation semantics: rules not referenced by any activation are always
+//! active; referenced rules are active iff at least one of their
+//! activations has a true predicate.
+
+use super::evaluator::{EvalContext, InputValue};
+use super::types::IrV1;
+use anyhow::{anyhow, bail, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Value of a predicate (sub-)expression
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PredicateValue {
+    Bool(bool),
+    Integer(i64),
+    String(String),
+    List(Vec<PredicateValue>),
+}
+
+impl PredicateValue {
+    fn type_name(&self) -> &'static str {
+        match self {
+            PredicateValue::Bool(_) => "bool",
+            PredicateValue::Integer(_) => "integer",
+            PredicateValue::String(_) => "string",
+            PredicateValue::List(_) => "array",
+        }
+    }
+
+    fn from_input(value: &InputValue) -> Self {
+        match value {
+            InputValue::Integer(n) => PredicateValue::Integer(*n),
+            InputValue::String(s) => PredicateValue::String(s.clone()),
+            InputValue::StringList(items) => PredicateValue::List(
+                items
+                    .iter()
+                    .map(|s| PredicateValue::String(s.clone()))
+                    .collect(),
+            ),
+            InputValue::IntegerList(items) => {
+                PredicateValue::List(items.iter().map(|n| PredicateValue::Integer(*n)).collect())
+            }
+            InputValue::Records(records) => PredicateValue::List(
+                records
+                    .iter()
+                    .map(|r| PredicateValue::String(r.hash.clone()))
+                    .collect(),
+            ),
+            InputValue::ListRoot(root) => PredicateValue::String(root.root.clone()),
+        }
+    }
+
+    fn from_literal(value: &serde_json::Value) -> Result<Self> {
+        match value {
+            serde_json::Value::Bool(b) => Ok(PredicateValue::Bool(*b)),
+            serde_json::Value::Number(n) => n
+                .as_i64()
+                .map(PredicateValue::Integer)
+                .ok_or_else(|| anyhow!("literal {} is not an integer", n)),
+            serde_json::Value::String(s) => Ok(PredicateValue::String(s.clone())),
+            serde_json::Value::Array(items) => items
+                .iter()
+                .map(Self::from_literal)
+                .collect::<Result<Vec<_>>>()
+                .map(PredicateValue::List),
+            other => bail!("unsupported literal {}", other),
+        }
+    }
+
+    fn as_bool(&self) -> Result<bool> {
+        match self {
+            PredicateValue::Bool(b) => Ok(*b),
+            other => bail!("expected bool, got {}", other.type_name()),
+        }
+    }
+
+    fn as_integer(&self) -> Result<i64> {
+        match self {
+            PredicateValue::Integer(n) => Ok(*n),
+            other => bail!("expected integer, got {}", other.type_name()),
+        }
+    }
+
+    fn as_list(&self) -> Result<&[PredicateValue]> {
+        match self {
+            PredicateValue::List(items) => Ok(items),
+            other => bail!("expected array, got {}", other.type_name()),
+        }
+    }
+}
+
+/// Evaluates a predicate expression against `ctx`
+pub fn eval_expr(expr: &serde_json::Value, ctx: &EvalContext) -> Result<PredicateValue> {
+    let serde_json::Value::Object(map) = expr else {
+        return PredicateValue::from_literal(expr);
+    };
+
+    if let Some(var) = map.get("var") {
+        let name = var
+            .as_str()
+            .ok_or_else(|| anyhow!("var must be a string, got {}", var))?;
+        let value = ctx
+            .get(name)
+            .ok_or_else(|| anyhow!("input '{}' not provided", name))?;
+        return Ok(PredicateValue::from_input(value));
+    }
+
+    let func = map
+        .get("func")
+        .and_then(|f| f.as_str())
+        .ok_or_else(|| anyhow!("expression must be var, func or literal: {}", expr))?;
+    let args = match map.get("args") {
+        Some(serde_json::Value::Array(args)) => args.as_slice(),
+        Some(other) => bail!("{}: args must be an array, got {}", func, other),
+        None => &[],
+    };
+
+    eval_func(func, args, ctx).map_err(|e| anyhow!("{}: {}", func, e))
+}
+
+fn eval_func(func: &str, args: &[serde_json::Value], ctx: &EvalContext) -> Result<PredicateValue> {
+    let arity = |n: usize| {
+        if args.len() == n {
+            Ok(())
+        } else {
+            Err(anyhow!("expected {} argument(s), got {}", n, args.len()))
+        }
+    };
+
+    let value = match func {
+        "and" | "or" => {
+            if args.is_empty() {
+                bail!("expected at least one argument");
+            }
+            let short_circuit = func == "or";
+            for arg in args {
+                if eval_expr(arg, ctx)?.as_bool()? == short_circuit {
+                    return Ok(PredicateValue::Bool(short_circuit));
+                }
+            }
+            PredicateValue::Bool(!short_circuit)
+        }
+        "not" => {
+            arity(1)?;
+            PredicateValue::Bool(!eval_expr(&args[0], ctx)?.as_bool()?)
+        }
+        "eq" | "ne" => {
+            arity(2)?;
+            let equal = eval_expr(&args[0], ctx)? == eval_expr(&args[1], ctx)?;
+            PredicateValue::Bool(equal == (func == "eq"))
+        }
+        "lt" | "le" | "gt" | "ge" => {
+            arity(2)?;
+            let lhs = eval_expr(&args[0], ctx)?.as_integer()?;
+            let rhs = eval_expr(&args[1], ctx)?.as_integer()?;
+            PredicateValue::Bool(match func {
+                "lt" => lhs < rhs,
+                "le" => lhs <= rhs,
+                "gt" => lhs > rhs,
+                _ => lhs >= rhs,
+            })
+        }
+        "len" => {
+            arity(1)?;
+            PredicateValue::Integer(eval_expr(&args[0], ctx)?.as_list()?.len() as i64)
+        }
+        "min" | "max" => {
+            arity(1)?;
+            let list = eval_expr(&args[0], ctx)?;
+            let numbers = list
+                .as_list()?
+                .iter()
+                .map(PredicateValue::as_integer)
+                .collect::<Result<Vec<_>>>()?;
+            let extreme = if func == "min" {
+                numbers.into_iter().min()
+            } else {
+                numbers.into_iter().max()
+            };
+            PredicateValue::Integer(extreme.ok_or_else(|| anyhow!("empty array"))?)
+        }
+        "in" => {
+            arity(2)?;
+            let needle = eval_expr(&args[0], ctx)?;
+            let haystack = eval_expr(&args[1], ctx)?;
+            PredicateValue::Bool(haystack.as_list()?.contains(&needle))
+        }
+        other => bail!("unknown function '{}'", other),
+    };
+    Ok(value)
+}
+
+/// Activation state of a single rule
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RuleActivation {
+    pub rule_id: String,
+    pub active: bool,
+    /// Why the rule is (in)active
+    pub reason: String,
+}
+
+/// Resolved predicates and rule activations of an IR
+#[derive(Debug, Clone, Default)]
+pub struct ActivationSet {
+    /// Predicate ID → value
+    pub predicates: BTreeMap<String, bool>,
+    /// One entry per rule, in IR order
+    pub rules: Vec<RuleActivation>,
+}
+
+impl ActivationSet {
+    pub fn is_active(&self, rule_id: &str) -> bool {
+        self.rules.iter().any(|r| r.rule_id == rule_id && r.active)
+    }
+
+    /// IDs of all active rules
+    pub fn active_rules(&self) -> impl Iterator<Item = &str> {
+        self.rules
+            .iter()
+            .filter(|r| r.active)
+            .map(|r| r.rule_id.as_str())
+    }
+}
+
+/// Evaluates all predicates of `ir` and resolves the active rule set
+///
+/// Fails if a predicate cannot be evaluated to a bool or an activation
+/// references an unknown predicate; a rule set must never be chosen on
+/// incomplete inputs.
+pub fn resolve_activations(ir: &IrV1, ctx: &EvalContext) -> Result<ActivationSet> {
+    let Some(adaptivity) = &ir.adaptivity else {
+        return Ok(ActivationSet {
+            predicates: BTreeMap::new(),
+            rules: ir
+                .rules
+                .iter()
+                .map(|r| RuleActivation {
+                    rule_id: r.id.clone(),
+                    active: true,
+                    reason: "unconditional".to_string(),
+                })
+                .collect(),
+        });
+    };
+
+    let mut predicates = BTreeMap::new();
+    for predicate in &adaptivity.predicates {
+        let value = eval_expr(&predicate.expr, ctx)
+            .and_then(|v| v.as_bool())
+            .map_err(|e| anyhow!("predicate '{}': {}", predicate.id, e))?;
+        predicates.insert(predicate.id.clone(), value);
+    }
+
+    for activation in &adaptivity.activations {
+        if !predicates.contains_key(&activation.when) {
+            bail!(
+                "activation references unknown predicate '{}'",
+                activation.when
+            );
+        }
+    }
+
+    let rules = ir
+        .rules
+        .iter()
+        .map(|rule| {
+            let triggers: Vec<&str> = adaptivity
+                .activations
+                .iter()
+                .filter(|a| a.rules.contains(&rule.id))
+                .map(|a| a.when.as_str())
+                .collect();
+
+            if triggers.is_empty() {
+                return RuleActivation {
+                    rule_id: rule.id.clone(),
+                    active: true,
+                    reason: "unconditional".to_string(),
+                };
+            }
+
+            let fired: Vec<&str> = triggers
+                .iter()
+                .copied()
+                .filter(|p| predicates[*p])
+                .collect();
+            let (active, reason) = if fired.is_empty() {
+                (
+                    false,
+                    format!("predicate(s) false: {}", triggers.join(", ")),
+                )
+            } else {
+                (true, format!("activated by: {}", fired.join(", ")))
+            };
+            RuleActivation {
+                rule_id: rule.id.clone(),
+                active,
+                reason,
+            }
+        })
+        .collect();
+
+    Ok(ActivationSet { predicates, rules })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::policy_v2::types::{Activation, IrAdaptivity, IrPredicate, IrRule};
+    use serde_json::json;
+
+    fn context() -> EvalContext {
+        let mut ctx = EvalContext::new();
+        ctx.insert("supplier_tiers", InputValue::IntegerList(vec![1, 1, 3]))
+            .insert(
+                "supplier_jurisdictions",
+                InputValue::StringList(vec!["DE".to_string(), "CN".to_string()]),
+            )
+            .insert("ubo_count", InputValue::Integer(2));
+        ctx
+    }
+
+    fn ir(predicates: Vec<(&str, serde_json::Value)>, activations: Vec<(&str, &[&str])>) -> IrV1 {
+        let rule = |id: &str| IrRule {
+            id: id.to_string(),
+            op: "range_min".to_string(),
+            lhs: serde_json::from_value(json!({"var": "ubo_count"})).unwrap(),
+            rhs: serde_json::from_value(json!(1)).unwrap(),
+        };
+        IrV1 {
+            ir_version: "1.0".to_string(),
+            policy_id: "test.v1".to_string(),
+            policy_hash: "sha3-256:abc".to_string(),
+            rules: vec![rule("base"), rule("deep_tier_audit"), rule("tier1_only")],
+            adaptivity: Some(IrAdaptivity {
+                predicates: predicates
+                    .into_iter()
+                    .map(|(id, expr)| IrPredicate {
+                        id: id.to_string(),
+                        expr,
+                    })
+                    .collect(),
+                activations: activations
+                    .into_iter()
+                    .map(|(when, rules)| Activation {
+                        when: when.to_string(),
+                        rules: rules.iter().map(|r| r.to_string()).collect(),
+                    })
+                    .collect(),
+            }),
+            valid_from: None,
+            valid_until: None,
+            ir_hash: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_eval_expr_builtins() {
+        let ctx = context();
+        let eval = |expr: serde_json::Value| eval_expr(&expr, &ctx).unwrap();
+
+        assert_eq!(
+            eval(json!({"func": "max", "args": [{"var": "supplier_tiers"}]})),
+            PredicateValue::Integer(3)
+        );
+        assert_eq!(
+            eval(json!({"func": "len", "args": [{"var": "supplier_jurisdictions"}]})),
+            PredicateValue::Integer(2)
+        );
+        assert_eq!(
+            eval(json!({"func": "and", "args": [
+                {"func": "in", "args": ["CN", {"var": "supplier_jurisdictions"}]},
+                {"func": "not", "args": [{"func": "lt", "args": [{"var": "ubo_count"}, 2]}]}
+            ]})),
+            PredicateValue::Bool(true)
+        );
+        assert_eq!(
+            eval(json!({"func": "ne", "args": [[1, 2], [1, 2]]})),
+            PredicateValue::Bool(false)
+        );
+    }
+
+    #[test]
+    fn test_eval_expr_errors() {
+        let ctx = context();
+        let err = |expr: serde_json::Value| eval_expr(&expr, &ctx).unwrap_err().to_string();
+
+        assert!(err(json!({"var": "audit_dates"})).contains("not provided"));
+        assert!(err(json!({"func": "now"})).contains("unknown function"));
+        assert!(
+            err(json!({"func": "gt", "args": [{"var": "supplier_jurisdictions"}, 1]}))
+                .contains("expected integer")
+        );
+        assert!(err(json!({"func": "not", "args": [true, false]})).contains("argument"));
+    }
+
+    #[test]
+    fn test_resolve_activations_tiers() {
+        let ir = ir(
+            vec![
+                (
+                    "deep_tier",
+                    json!({"func": "gt", "args": [{"func": "max", "args": [{"var": "supplier_tiers"}]}, 1]}),
+                ),
+                (
+                    "tier1",
+                    json!({"func": "eq", "args": [{"func": "max", "args": [{"var": "supplier_tiers"}]}, 1]}),
+                ),
+            ],
+            vec![
+                ("deep_tier", &["deep_tier_audit"]),
+                ("tier1", &["tier1_only"]),
+            ],
+        );
+
+        let set = resolve_activations(&ir, &context()).unwrap();
+        assert!(set.predicates["deep_tier"]);
+        assert!(!set.predicates["tier1"]);
+        assert_eq!(
+            set.active_rules().collect::<Vec<_>>(),
+            ["base", "deep_tier_audit"]
+        );
+        assert_eq!(set.rules[0].reason, "unconditional");
+        assert_eq!(set.rules[1].reason, "activated by: deep_tier");
+        assert!(!set.is_active("tier1_only"));
+        assert!(set.rules[2].reason.contains("tier1"));
+    }
+
+    #[test]
+    fn test_resolve_activations_rejects_bad_predicates() {
+        let non_bool = ir(vec![("p", json!({"var": "ubo_count"}))], vec![]);
+        assert!(resolve_activations(&non_bool, &context()).is_err());
+
+        let unknown = ir(vec![("p", json!(true))], vec![("q", &["base"])]);
+        assert!(resolve_activations(&unknown, &context()).is_err());
+    }
+
+    #[test]
+    fn test_without_adaptivity_all_rules_active() {
+        let mut ir = ir(vec![], vec![]);
+        ir.adaptivity = None;
+        let set = resolve_activations(&ir, &EvalContext::new()).unwrap();
+        assert_eq!(set.active_rules().count(), 3);
+        assert!(set.predicates.is_empty());
+    }
+}
@@ -87,9 +87,7 @@ pub fn run_compile(file: &str, output: &str) -> Result<i32> {
     let mut ir = generate_ir(&policy, policy_hash)?;
 
     // Compute IR hash
-    let ir_canonical = canonicalize(&ir)?;
-    let ir_hash = sha3_256_hex(&ir_canonical);
-    ir.ir_hash = ir_hash;
+    ir.ir_hash = compute_ir_hash(&ir)?;
 
     // Write IR
     let ir_json = serde_json::to_string_pretty(&ir)?;
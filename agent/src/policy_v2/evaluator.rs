@@ -0,0 +1,652 @@
+//! IR v1 evaluator
+//!
+//! Runs the rules of a compiled policy against a typed input context.
+//! Supported operators: `eq`, `range_min`, `non_membership`,
+//! `non_intersection` (inline blocklist) and `range` (per-value bounds).
+//!
+//! Only rules activated by the policy's adaptivity section are evaluated
+//! (see [`super::adaptivity`]).
+//!
+//! Rule failures (violated constraint, missing input, type mismatch) are
+//! reported per rule as `ok = false` with a reason; only structural
+//! problems (unsupported IR version, unevaluable predicate) abort the
+//! evaluation.
+
+use super::adaptivity::{self, RuleActivation};
+use super::types::{IrExpression, IrRule, IrV1};
+use crate::lists::{self, ListExclusionWitness, ListKind, RecordInclusionProof};
+use crate::sorted_merkle::SortedMerkleTree;
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// IR version understood by this evaluator
+pub const SUPPORTED_IR_VERSION: &str = "1.0";
+
+/// Committed record exposed to the evaluator
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordInput {
+    /// Leaf hash of the record in the supplier/UBO tree (0x-hex)
+    pub hash: String,
+    pub name: String,
+    /// Supplier jurisdiction or UBO citizenship
+    pub jurisdiction: String,
+    /// Disclosed record with its inclusion path (binds list screening to the root)
+    pub inclusion: RecordInclusionProof,
+}
+
+/// Committed list root, optionally with the list that produced it
+#[derive(Debug, Clone)]
+pub struct ListRootInput {
+    pub kind: ListKind,
+    /// Root committed in the manifest (0x-hex)
+    pub root: String,
+    pub tree: Option<SortedMerkleTree>,
+}
+
+/// Typed value of a policy input
+#[derive(Debug, Clone)]
+pub enum InputValue {
+    Integer(i64),
+    String(String),
+    StringList(Vec<String>),
+    IntegerList(Vec<i64>),
+    Records(Vec<RecordInput>),
+    ListRoot(ListRootInput),
+}
+
+impl InputValue {
+    fn type_name(&self) -> &'static str {
+        match self {
+            InputValue::Integer(_) => "integer",
+            InputValue::String(_) => "string",
+            InputValue::StringList(_) => "array<string>",
+            InputValue::IntegerList(_) => "array<integer>",
+            InputValue::Records(_) => "array<record>",
+            InputValue::ListRoot(_) => "list_root",
+        }
+    }
+}
+
+/// Named inputs available to the rules
+#[derive(Debug, Clone, Default)]
+pub struct EvalContext {
+    values: BTreeMap<String, InputValue>,
+}
+
+impl EvalContext {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds (or replaces) an input
+    pub fn insert(&mut self, name: impl Into<String>, value: InputValue) -> &mut Self {
+        self.values.insert(name.into(), value);
+        self
+    }
+
+    pub fn get(&self, name: &str) -> Option<&InputValue> {
+        self.values.get(name)
+    }
+
+    /// Names of all inputs, sorted
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.values.keys().map(String::as_str)
+    }
+}
+
+/// Result of a single rule
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RuleOutcome {
+    pub rule_id: String,
+    pub op: String,
+    pub ok: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// Result of evaluating a whole IR
+#[derive(Debug, Clone)]
+pub struct Evaluation {
+    pub policy_id: String,
+    pub ir_hash: String,
+    /// One outcome per active rule, in IR order
+    pub outcomes: Vec<RuleOutcome>,
+    /// Activation state of every rule, in IR order
+    pub activations: Vec<RuleActivation>,
+    /// Exclusion witnesses produced by successful `non_membership` rules
+    pub witnesses: Vec<ListExclusionWitness>,
+}
+
+impl Evaluation {
+    pub fn all_ok(&self) -> bool {
+        self.outcomes.iter().all(|o| o.ok)
+    }
+
+    pub fn failed(&self) -> impl Iterator<Item = &RuleOutcome> {
+        self.outcomes.iter().filter(|o| !o.ok)
+    }
+}
+
+/// Evaluates all active rules of `ir` against `ctx`
+pub fn evaluate(ir: &IrV1, ctx: &EvalContext) -> Result<Evaluation> {
+    if ir.ir_version != SUPPORTED_IR_VERSION {
+        bail!(
+            "Unsupported IR version: {} (expected {})",
+            ir.ir_version,
+            SUPPORTED_IR_VERSION
+        );
+    }
+
+    let activation = adaptivity::resolve_activations(ir, ctx)?;
+    let mut outcomes = Vec::with_capacity(ir.rules.len());
+    let mut witnesses = Vec::new();
+
+    for rule in ir.rules.iter().filter(|r| activation.is_active(&r.id)) {
+        let result = match rule.op.as_str() {
+            "eq" => eval_eq(rule, ctx),
+            "range_min" => eval_range_min(rule, ctx),
+            "non_intersection" => eval_non_intersection(rule, ctx),
+            "range" => eval_range(rule, ctx),
+            "non_membership" => eval_non_membership(rule, ctx).map(|w| witnesses.push(w)),
+            other => Err(format!("unsupported operator '{}'", other)),
+        };
+
+        outcomes.push(RuleOutcome {
+            rule_id: rule.id.clone(),
+            op: rule.op.clone(),
+            ok: result.is_ok(),
+            reason: result.err(),
+        });
+    }
+
+    Ok(Evaluation {
+        policy_id: ir.policy_id.clone(),
+        ir_hash: ir.ir_hash.clone(),
+        outcomes,
+        activations: activation.rules,
+        witnesses,
+    })
+}
+
+/// Resolved operand: either a context input or an inline literal
+enum Operand<'a> {
+    Input(&'a str, &'a InputValue),
+    Literal(&'a serde_json::Value),
+}
+
+fn resolve<'a>(expr: &'a IrExpression, ctx: &'a EvalContext) -> Result<Operand<'a>, String> {
+    match expr {
+        IrExpression::Var { var } => ctx
+            .get(var)
+            .map(|value| Operand::Input(var, value))
+            .ok_or_else(|| format!("input '{}' not provided", var)),
+        IrExpression::Literal(value) => Ok(Operand::Literal(value)),
+    }
+}
+
+/// Scalar view of an operand for `eq`
+fn scalar(operand: &Operand) -> Result<serde_json::Value, String> {
+    match operand {
+        Operand::Input(_, InputValue::Integer(n)) => Ok((*n).into()),
+        Operand::Input(_, InputValue::String(s)) => Ok(s.clone().into()),
+        Operand::Input(_, InputValue::ListRoot(root)) => Ok(root.root.clone().into()),
+        Operand::Input(name, value) => Err(format!(
+            "input '{}' is {}, expected scalar",
+            name,
+            value.type_name()
+        )),
+        Operand::Literal(value) if value.is_string() || value.is_number() || value.is_boolean() => {
+            Ok((*value).clone())
+        }
+        Operand::Literal(value) => Err(format!("literal {} is not a scalar", value)),
+    }
+}
+
+/// Numeric view of an operand for `range_min` (lists count their elements)
+fn magnitude(operand: &Operand) -> Result<i64, String> {
+    match operand {
+        Operand::Input(_, InputValue::Integer(n)) => Ok(*n),
+        Operand::Input(_, InputValue::StringList(items)) => Ok(items.len() as i64),
+        Operand::Input(_, InputValue::IntegerList(items)) => Ok(items.len() as i64),
+        Operand::Input(_, InputValue::Records(records)) => Ok(records.len() as i64),
+        Operand::Input(name, value) => Err(format!(
+            "input '{}' is {}, expected integer or array",
+            name,
+            value.type_name()
+        )),
+        Operand::Literal(value) => value
+            .as_i64()
+            .ok_or_else(|| format!("literal {} is not an integer", value)),
+    }
+}
+
+fn eval_eq(rule: &IrRule, ctx: &EvalContext) -> Result<(), String> {
+    let lhs = scalar(&resolve(&rule.lhs, ctx)?)?;
+    let rhs = scalar(&resolve(&rule.rhs, ctx)?)?;
+    if lhs == rhs {
+        Ok(())
+    } else {
+        Err(format!("{} ≠ {}", lhs, rhs))
+    }
+}
+
+fn eval_range_min(rule: &IrRule, ctx: &EvalContext) -> Result<(), String> {
+    let value = magnitude(&resolve(&rule.lhs, ctx)?)?;
+    let min = magnitude(&resolve(&rule.rhs, ctx)?)?;
+    if value >= min {
+        Ok(())
+    } else {
+        Err(format!("{} < minimum {}", value, min))
+    }
+}
+
+fn eval_non_membership(rule: &IrRule, ctx: &EvalContext) -> Result<ListExclusionWitness, String> {
+    let list = match resolve(&rule.rhs, ctx)? {
+        Operand::Input(_, InputValue::ListRoot(list)) => list,
+        Operand::Input(name, value) => {
+            return Err(format!(
+                "input '{}' is {}, expected list_root",
+                name,
+                value.type_name()
+            ))
+        }
+        Operand::Literal(value) => {
+            return Err(format!("literal {} cannot be used as list root", value))
+        }
+    };
+
+    let tree = list
+        .tree
+        .as_ref()
+        .ok_or_else(|| format!("{} list not provided", list.kind))?;
+    if tree.root_hex() != list.root.to_lowercase() {
+        return Err(format!(
+            "{} list does not match committed root {}",
+            list.kind, list.root
+        ));
+    }
+
+    // Only committed records can be bound to supplier_root/ubo_root
+    let records: Vec<RecordInclusionProof> = match resolve(&rule.lhs, ctx)? {
+        Operand::Input(_, InputValue::Records(records)) => {
+            records.iter().map(|r| r.inclusion.clone()).collect()
+        }
+        Operand::Input(name, value) => {
+            return Err(format!(
+                "input '{}' is {}, expected records (supplier_hashes, ubo_hashes)",
+                name,
+                value.type_name()
+            ))
+        }
+        Operand::Literal(value) => return Err(format!("literal {} cannot be screened", value)),
+    };
+
+    lists::prove_exclusion(list.kind, tree, &records).map_err(|hits| {
+        format!(
+            "{} hit(s) in {} list: {}",
+            hits.len(),
+            list.kind,
+            hits.join(", ")
+        )
+    })
+}
+
+/// Field values of an operand as strings (one per supplier/UBO)
+fn field_values(operand: &Operand) -> Result<Vec<String>, String> {
+    match operand {
+        Operand::Input(_, InputValue::StringList(items)) => Ok(items.clone()),
+        Operand::Input(_, InputValue::IntegerList(items)) => {
+            Ok(items.iter().map(i64::to_string).collect())
+        }
+        Operand::Input(_, InputValue::String(s)) => Ok(vec![s.clone()]),
+        Operand::Input(_, InputValue::Integer(n)) => Ok(vec![n.to_string()]),
+        Operand::Input(name, value) => Err(format!(
+            "input '{}' is {}, expected array",
+            name,
+            value.type_name()
+        )),
+        Operand::Literal(value) => Err(format!("literal {} cannot be screened", value)),
+    }
+}
+
+/// Blocklist comparison key (case- and whitespace-insensitive)
+fn blocklist_key(value: &str) -> String {
+    value.trim().to_uppercase()
+}
+
+fn eval_non_intersection(rule: &IrRule, ctx: &EvalContext) -> Result<(), String> {
+    let values = field_values(&resolve(&rule.lhs, ctx)?)?;
+    let blocked: Vec<String> = match resolve(&rule.rhs, ctx)? {
+        Operand::Literal(serde_json::Value::Array(items)) => items
+            .iter()
+            .map(|item| match item {
+                serde_json::Value::String(s) => blocklist_key(s),
+                other => blocklist_key(&other.to_string()),
+            })
+            .collect(),
+        _ => return Err("rhs must be an array literal".to_string()),
+    };
+
+    let mut hits: Vec<&str> = values
+        .iter()
+        .filter(|v| blocked.contains(&blocklist_key(v)))
+        .map(String::as_str)
+        .collect();
+    hits.sort_unstable();
+    hits.dedup();
+
+    if hits.is_empty() {
+        Ok(())
+    } else {
+        Err(format!(
+            "{} blocked value(s): {}",
+            hits.len(),
+            hits.join(", ")
+        ))
+    }
+}
+
+fn eval_range(rule: &IrRule, ctx: &EvalContext) -> Result<(), String> {
+    let values = match resolve(&rule.lhs, ctx)? {
+        Operand::Input(_, InputValue::IntegerList(items)) => items.clone(),
+        Operand::Input(_, InputValue::Integer(n)) => vec![*n],
+        Operand::Input(name, value) => {
+            return Err(format!(
+                "input '{}' is {}, expected array<integer>",
+                name,
+                value.type_name()
+            ))
+        }
+        Operand::Literal(value) => return Err(format!("literal {} cannot be ranged", value)),
+    };
+    let bound = |key: &str| match resolve(&rule.rhs, ctx)? {
+        Operand::Literal(serde_json::Value::Object(bounds)) => {
+            Ok(bounds.get(key).and_then(|b| b.as_i64()))
+        }
+        _ => Err("rhs must be a literal { min, max }".to_string()),
+    };
+    let (min, max) = (bound("min")?, bound("max")?);
+
+    let outside: Vec<String> = values
+        .iter()
+        .filter(|v| min.is_some_and(|min| **v < min) || max.is_some_and(|max| **v > max))
+        .map(i64::to_string)
+        .collect();
+
+    if outside.is_empty() {
+        Ok(())
+    } else {
+        Err(format!(
+            "{} value(s) outside [{}, {}]: {}",
+            outside.len(),
+            min.map_or("-".to_string(), |m| m.to_string()),
+            max.map_or("-".to_string(), |m| m.to_string()),
+            outside.join(", ")
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn rule(id: &str, op: &str, lhs: serde_json::Value, rhs: serde_json::Value) -> IrRule {
+        IrRule {
+            id: id.to_string(),
+            op: op.to_string(),
+            lhs: serde_json::from_value(lhs).unwrap(),
+            rhs: serde_json::from_value(rhs).unwrap(),
+        }
+    }
+
+    fn ir(rules: Vec<IrRule>) -> IrV1 {
+        IrV1 {
+            ir_version: "1.0".to_string(),
+            policy_id: "test.v1".to_string(),
+            policy_hash: "sha3-256:abc".to_string(),
+            rules,
+            adaptivity: None,
+            valid_from: None,
+            valid_until: None,
+            ir_hash: "sha3-256:def".to_string(),
+        }
+    }
+
+    fn record(name: &str, jurisdiction: &str) -> RecordInput {
+        let supplier = crate::io::Supplier {
+            name: name.to_string(),
+            jurisdiction: jurisdiction.to_string(),
+            tier: 1,
+        };
+        let nonces = crate::commitment::RecordNonces::generate(1, 0);
+        let inclusion =
+            lists::prove_records(lists::RecordKind::Supplier, &[supplier], &nonces.suppliers)
+                .unwrap()
+                .remove(0);
+        RecordInput {
+            hash: inclusion.leaf_hash.clone(),
+            name: name.to_string(),
+            jurisdiction: jurisdiction.to_string(),
+            inclusion,
+        }
+    }
+
+    fn context() -> EvalContext {
+        let sanctions = ListKind::Sanctions.build_tree(["Ivan Petrov"]);
+        let mut ctx = EvalContext::new();
+        ctx.insert(
+            "supplier_hashes",
+            InputValue::Records(vec![record("Acme", "DE"), record("Nordic", "SE")]),
+        )
+        .insert("ubo_count", InputValue::Integer(1))
+        .insert("supplier_root", InputValue::String("0xaa".to_string()))
+        .insert(
+            "sanctions_root",
+            InputValue::ListRoot(ListRootInput {
+                kind: ListKind::Sanctions,
+                root: sanctions.root_hex(),
+                tree: Some(sanctions),
+            }),
+        );
+        ctx
+    }
+
+    #[test]
+    fn test_all_operators_pass() {
+        let ir = ir(vec![
+            rule(
+                "count",
+                "range_min",
+                json!({"var": "supplier_hashes"}),
+                json!(2),
+            ),
+            rule("ubo", "range_min", json!({"var": "ubo_count"}), json!(1)),
+            rule("root", "eq", json!({"var": "supplier_root"}), json!("0xaa")),
+            rule(
+                "sanctions",
+                "non_membership",
+                json!({"var": "supplier_hashes"}),
+                json!({"var": "sanctions_root"}),
+            ),
+        ]);
+
+        let eval = evaluate(&ir, &context()).unwrap();
+        assert!(eval.all_ok(), "{:?}", eval.outcomes);
+        assert_eq!(eval.outcomes.len(), 4);
+        assert_eq!(eval.witnesses.len(), 1);
+        assert_eq!(eval.witnesses[0].values(), ["ACME", "NORDIC"]);
+        assert_eq!(eval.ir_hash, "sha3-256:def");
+    }
+
+    #[test]
+    fn test_violations_are_reported_per_rule() {
+        let mut ctx = context();
+        ctx.insert(
+            "ubo_hashes",
+            InputValue::Records(vec![record("Ivan  Petrov", "RU")]),
+        );
+        let ir = ir(vec![
+            rule(
+                "count",
+                "range_min",
+                json!({"var": "supplier_hashes"}),
+                json!(3),
+            ),
+            rule("root", "eq", json!({"var": "supplier_root"}), json!("0xbb")),
+            rule(
+                "sanctions",
+                "non_membership",
+                json!({"var": "ubo_hashes"}),
+                json!({"var": "sanctions_root"}),
+            ),
+        ]);
+
+        let eval = evaluate(&ir, &ctx).unwrap();
+        assert!(!eval.all_ok());
+        assert_eq!(eval.failed().count(), 3);
+        assert!(eval.witnesses.is_empty());
+        assert!(eval.outcomes[2]
+            .reason
+            .as_deref()
+            .unwrap()
+            .contains("IVAN PETROV"));
+    }
+
+    #[test]
+    fn test_missing_input_and_type_mismatch() {
+        let ir = ir(vec![
+            rule(
+                "missing",
+                "range_min",
+                json!({"var": "ubo_hashes"}),
+                json!(1),
+            ),
+            rule(
+                "wrong_type",
+                "non_membership",
+                json!({"var": "supplier_hashes"}),
+                json!({"var": "supplier_root"}),
+            ),
+            rule(
+                "unknown",
+                "regex",
+                json!({"var": "supplier_root"}),
+                json!(".*"),
+            ),
+        ]);
+
+        let eval = evaluate(&ir, &context()).unwrap();
+        let reasons: Vec<_> = eval
+            .outcomes
+            .iter()
+            .map(|o| o.reason.clone().unwrap())
+            .collect();
+        assert!(reasons[0].contains("not provided"));
+        assert!(reasons[1].contains("expected list_root"));
+        assert!(reasons[2].contains("unsupported operator"));
+    }
+
+    #[test]
+    fn test_list_must_match_committed_root() {
+        let mut ctx = context();
+        ctx.insert(
+            "sanctions_root",
+            InputValue::ListRoot(ListRootInput {
+                kind: ListKind::Sanctions,
+                root: ListKind::Sanctions.build_tree(["Other"]).root_hex(),
+                tree: Some(ListKind::Sanctions.build_tree(["Ivan Petrov"])),
+            }),
+        );
+        let ir = ir(vec![rule(
+            "sanctions",
+            "non_membership",
+            json!({"var": "supplier_hashes"}),
+            json!({"var": "sanctions_root"}),
+        )]);
+
+        let eval = evaluate(&ir, &ctx).unwrap();
+        assert!(!eval.all_ok());
+        assert!(eval.outcomes[0]
+            .reason
+            .as_deref()
+            .unwrap()
+            .contains("does not match"));
+    }
+
+    #[test]
+    fn test_inactive_rules_are_skipped() {
+        let mut ir = ir(vec![
+            rule("always", "range_min", json!({"var": "ubo_count"}), json!(1)),
+            rule("gated", "range_min", json!({"var": "ubo_count"}), json!(5)),
+        ]);
+        ir.adaptivity = serde_json::from_value(json!({
+            "predicates": [{"id": "many_ubos", "expr": {"func": "gt", "args": [{"var": "ubo_count"}, 3]}}],
+            "activations": [{"when": "many_ubos", "rules": ["gated"]}]
+        }))
+        .unwrap();
+
+        let eval = evaluate(&ir, &context()).unwrap();
+        assert!(eval.all_ok());
+        assert_eq!(eval.outcomes.len(), 1);
+        assert_eq!(eval.outcomes[0].rule_id, "always");
+        assert_eq!(eval.activations.len(), 2);
+        assert!(!eval.activations[1].active);
+    }
+
+    #[test]
+    fn test_blocklist_and_range() {
+        let mut ctx = context();
+        ctx.insert(
+            "supplier_jurisdictions",
+            InputValue::StringList(vec!["DE".to_string(), "ir ".to_string()]),
+        )
+        .insert("supplier_tiers", InputValue::IntegerList(vec![1, 2, 4]));
+        let ir = ir(vec![
+            rule(
+                "blocked",
+                "non_intersection",
+                json!({"var": "supplier_jurisdictions"}),
+                json!(["KP", "IR"]),
+            ),
+            rule(
+                "allowed",
+                "non_intersection",
+                json!({"var": "supplier_jurisdictions"}),
+                json!(["SY"]),
+            ),
+            rule(
+                "tiers",
+                "range",
+                json!({"var": "supplier_tiers"}),
+                json!({"min": 1, "max": 3}),
+            ),
+            rule(
+                "tiers_min",
+                "range",
+                json!({"var": "supplier_tiers"}),
+                json!({"min": 1}),
+            ),
+        ]);
+
+        let eval = evaluate(&ir, &ctx).unwrap();
+        let ok: Vec<_> = eval.outcomes.iter().map(|o| o.ok).collect();
+        assert_eq!(ok, [false, true, false, true]);
+        assert_eq!(
+            eval.outcomes[0].reason.as_deref(),
+            Some("1 blocked value(s): ir ")
+        );
+        assert_eq!(
+            eval.outcomes[2].reason.as_deref(),
+            Some("1 value(s) outside [1, 3]: 4")
+        );
+    }
+
+    #[test]
+    fn test_unsupported_ir_version() {
+        let mut ir = ir(vec![]);
+        ir.ir_version = "2.0".to_string();
+        assert!(evaluate(&ir, &EvalContext::new()).is_err());
+    }
+}
@@ -1,5 +1,8 @@
+use super::hasher::sha3_256_hex;
 use super::types::{IrAdaptivity, IrExpression, IrPredicate, IrRule, IrV1, PolicyV2};
-use anyhow::Result;
+use crate::policy::ValidityWindow;
+use anyhow::{bail, Result};
+use std::path::Path;
 
 /// Generate IR v1 from PolicyV2
 pub fn generate_ir(policy: &PolicyV2, policy_hash: String) -> Result<IrV1> {
@@ -37,6 +40,8 @@ pub fn generate_ir(policy: &PolicyV2, policy_hash: String) -> Result<IrV1> {
         policy_hash,
         rules: ir_rules,
         adaptivity: ir_adaptivity,
+        valid_from: policy.valid_from.clone(),
+        valid_until: policy.valid_until.clone(),
         ir_hash: String::new(), // Will be filled by hasher
     };
 
@@ -49,6 +54,13 @@ fn convert_expression(expr: &serde_json::Value) -> IrExpression {
             // Simple variable reference
             IrExpression::Var { var: s.clone() }
         }
+        serde_json::Value::Object(map) if map.len() == 1 && map.contains_key("var") => {
+            // Explicit variable reference: { var: name }
+            match map.get("var") {
+                Some(serde_json::Value::String(s)) => IrExpression::Var { var: s.clone() },
+                _ => IrExpression::Literal(expr.clone()),
+            }
+        }
         other => {
             // Complex expression or literal
             IrExpression::Literal(other.clone())
@@ -64,6 +76,64 @@ pub fn canonicalize(ir: &IrV1) -> Result<String> {
     Ok(json)
 }
 
+/// Compute the IR hash (canonical form with an empty `ir_hash` field)
+pub fn compute_ir_hash(ir: &IrV1) -> Result<String> {
+    let mut unhashed = ir.clone();
+    unhashed.ir_hash = String::new();
+    Ok(sha3_256_hex(&canonicalize(&unhashed)?))
+}
+
+/// Load a compiled IR file and check its `ir_hash`
+pub fn load_ir<P: AsRef<Path>>(path: P) -> Result<IrV1> {
+    let json = std::fs::read_to_string(path.as_ref())?;
+    let ir: IrV1 = serde_json::from_str(&json)?;
+
+    let expected = compute_ir_hash(&ir)?;
+    if ir.ir_hash != expected {
+        bail!(
+            "IR hash mismatch in {}: recorded {}, computed {}",
+            path.as_ref().display(),
+            ir.ir_hash,
+            expected
+        );
+    }
+    Ok(ir)
+}
+
+/// Check whether a file is a compiled IR (JSON with `ir_version`)
+pub fn is_ir_file<P: AsRef<Path>>(path: P) -> bool {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|json| serde_json::from_str::<serde_json::Value>(&json).ok())
+        .is_some_and(|value| value.get("ir_version").is_some())
+}
+
+impl IrV1 {
+    /// Validity window of the policy (`valid_from` / `valid_until`)
+    pub fn validity(&self) -> Result<ValidityWindow> {
+        Ok(ValidityWindow::parse(
+            self.valid_from.as_deref(),
+            self.valid_until.as_deref(),
+        )?)
+    }
+
+    /// Check that the policy is valid on `date` (both bounds inclusive)
+    pub fn check_valid_on(&self, date: chrono::NaiveDate) -> Result<()> {
+        self.validity()?
+            .check_on(date)
+            .map_err(|e| anyhow::anyhow!("policy {} {}", self.policy_id, e))
+    }
+
+    /// Policy hash as 0x-hex (manifest format)
+    pub fn policy_hash_hex(&self) -> String {
+        let hex = self
+            .policy_hash
+            .strip_prefix("sha3-256:")
+            .unwrap_or(&self.policy_hash);
+        format!("0x{}", hex.trim_start_matches("0x"))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -93,6 +163,8 @@ mod tests {
                 },
             ],
             adaptivity: None,
+            valid_from: None,
+            valid_until: None,
         };
 
         let ir = generate_ir(&policy, "hash123".to_string()).unwrap();
@@ -122,6 +194,68 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_convert_expression_var_object() {
+        let expr = serde_json::json!({"var": "sanctions_root"});
+        match convert_expression(&expr) {
+            IrExpression::Var { var } => assert_eq!(var, "sanctions_root"),
+            _ => panic!("Expected Var variant"),
+        }
+    }
+
+    #[test]
+    fn test_load_ir_checks_hash() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("policy.ir.json");
+        let mut ir = IrV1 {
+            ir_version: "1.0".to_string(),
+            policy_id: "test.v1".to_string(),
+            policy_hash: "sha3-256:abc123".to_string(),
+            rules: vec![],
+            adaptivity: None,
+            valid_from: None,
+            valid_until: None,
+            ir_hash: String::new(),
+        };
+        ir.ir_hash = compute_ir_hash(&ir).unwrap();
+        std::fs::write(&path, serde_json::to_string(&ir).unwrap()).unwrap();
+        assert!(is_ir_file(&path));
+        assert_eq!(load_ir(&path).unwrap().ir_hash, ir.ir_hash);
+        assert_eq!(ir.policy_hash_hex(), "0xabc123");
+
+        ir.policy_id = "tampered".to_string();
+        std::fs::write(&path, serde_json::to_string(&ir).unwrap()).unwrap();
+        assert!(load_ir(&path).is_err());
+    }
+
+    #[test]
+    fn test_check_valid_on() {
+        let mut ir = IrV1 {
+            ir_version: "1.0".to_string(),
+            policy_id: "test.v1".to_string(),
+            policy_hash: "sha3-256:abc123".to_string(),
+            rules: vec![],
+            adaptivity: None,
+            valid_from: None,
+            valid_until: None,
+            ir_hash: String::new(),
+        };
+        let date = |s| chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap();
+        assert!(ir.check_valid_on(date("2099-01-01")).is_ok());
+
+        ir.valid_until = Some("2025-12-31".to_string());
+        assert!(ir.check_valid_on(date("2025-12-31")).is_ok());
+        let err = ir.check_valid_on(date("2026-01-01")).unwrap_err();
+        assert!(err.to_string().contains("expired"));
+
+        ir.valid_from = Some("2025-06-01".to_string());
+        let err = ir.check_valid_on(date("2025-05-31")).unwrap_err();
+        assert!(err.to_string().contains("not yet valid"));
+
+        ir.valid_until = Some("soon".to_string());
+        assert!(ir.check_valid_on(date("2025-07-01")).is_err());
+    }
+
     #[test]
     fn test_canonicalize() {
         let ir = IrV1 {
@@ -130,6 +264,8 @@ mod tests {
             policy_hash: "sha3-256:abc123".to_string(),
             rules: vec![],
             adaptivity: None,
+            valid_from: None,
+            valid_until: None,
             ir_hash: "sha3-256:def456".to_string(),
         };
 
@@ -157,6 +293,8 @@ mod tests {
                     rules: vec!["rule1".to_string()],
                 }],
             }),
+            valid_from: None,
+            valid_until: None,
         };
 
         let ir = generate_ir(&policy, "hash123".to_string()).unwrap();
@@ -174,6 +312,8 @@ mod tests {
             policy_hash: "sha3-256:abc123".to_string(),
             rules: vec![],
             adaptivity: None,
+            valid_from: None,
+            valid_until: None,
             ir_hash: "sha3-256:def456".to_string(),
         };
 
@@ -1,4 +1,7 @@
-use super::types::PolicyV2;
+use super::adaptivity::{eval_expr, PredicateValue};
+use super::evaluator::EvalContext;
+use super::types::{InputDef, PolicyV2, Rule};
+use crate::policy::ValidityWindow;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -29,16 +32,31 @@ pub enum LintCode {
     E1001, // Unknown rule ID in activation
     E1002, // Missing legal_basis
     E1003, // Duplicate rule ID
+    E1004, // Unknown predicate in activation
+    E1005, // Invalid validity window (valid_from / valid_until)
 
     // E2xxx - Expression/operator errors
     E2001, // Invalid operator
     E2003, // Unknown input reference
+    E2004, // Type mismatch in expression
 
     // E3xxx - Constraint errors
     E3002, // Invalid range_min expression
 
     // W1xxx - Warnings
     W1002, // Missing description
+    W1003, // Rule unreachable (no activation can enable it)
+}
+
+impl LintDiagnostic {
+    fn new(code: LintCode, message: String, rule_id: Option<&str>) -> Self {
+        LintDiagnostic {
+            code: code.as_str().to_string(),
+            level: code.level(),
+            message,
+            rule_id: rule_id.map(str::to_string),
+        }
+    }
 }
 
 impl LintCode {
@@ -47,10 +65,14 @@ impl LintCode {
             LintCode::E1001 => "E1001",
             LintCode::E1002 => "E1002",
             LintCode::E1003 => "E1003",
+            LintCode::E1004 => "E1004",
+            LintCode::E1005 => "E1005",
             LintCode::E2001 => "E2001",
             LintCode::E2003 => "E2003",
+            LintCode::E2004 => "E2004",
             LintCode::E3002 => "E3002",
             LintCode::W1002 => "W1002",
+            LintCode::W1003 => "W1003",
         }
     }
 
@@ -59,10 +81,13 @@ impl LintCode {
             LintCode::E1001
             | LintCode::E1002
             | LintCode::E1003
+            | LintCode::E1004
+            | LintCode::E1005
             | LintCode::E2001
             | LintCode::E2003
+            | LintCode::E2004
             | LintCode::E3002 => 422,
-            LintCode::W1002 => 200,
+            LintCode::W1002 | LintCode::W1003 => 200,
         }
     }
 
@@ -71,10 +96,13 @@ impl LintCode {
             LintCode::E1001
             | LintCode::E1002
             | LintCode::E1003
+            | LintCode::E1004
+            | LintCode::E1005
             | LintCode::E2001
             | LintCode::E2003
+            | LintCode::E2004
             | LintCode::E3002 => Level::Error,
-            LintCode::W1002 => Level::Warning,
+            LintCode::W1002 | LintCode::W1003 => Level::Warning,
         }
     }
 }
@@ -107,6 +135,13 @@ pub fn lint(policy: &PolicyV2, mode: LintMode) -> Vec<LintDiagnostic> {
         });
     }
 
+    // Check validity window (E1005)
+    if let Err(e) =
+        ValidityWindow::parse(policy.valid_from.as_deref(), policy.valid_until.as_deref())
+    {
+        diagnostics.push(LintDiagnostic::new(LintCode::E1005, e.to_string(), None));
+    }
+
     // Check rule IDs uniqueness (E1003)
     let mut seen_ids = std::collections::HashSet::new();
     for rule in &policy.rules {
@@ -121,15 +156,22 @@ pub fn lint(policy: &PolicyV2, mode: LintMode) -> Vec<LintDiagnostic> {
     }
 
     // Check valid operators (E2001)
-    const VALID_OPS: &[&str] = &["non_membership", "eq", "range_min"];
+    const VALID_OPS: &[&str] = &[
+        "non_membership",
+        "eq",
+        "range_min",
+        "non_intersection",
+        "range",
+    ];
     for rule in &policy.rules {
         if !VALID_OPS.contains(&rule.op.as_str()) {
             diagnostics.push(LintDiagnostic {
                 code: LintCode::E2001.as_str().to_string(),
                 level: Level::Error,
                 message: format!(
-                    "invalid op '{}' (allowed: non_membership, eq, range_min)",
-                    rule.op
+                    "invalid op '{}' (allowed: {})",
+                    rule.op,
+                    VALID_OPS.join(", ")
                 ),
                 rule_id: Some(rule.id.clone()),
             });
@@ -157,14 +199,323 @@ pub fn lint(policy: &PolicyV2, mode: LintMode) -> Vec<LintDiagnostic> {
                 }
             }
         }
+
+        // Check activations reference known predicates (E1004)
+        let predicate_ids: std::collections::HashSet<_> =
+            adaptivity.predicates.iter().map(|p| &p.id).collect();
+        for activation in &adaptivity.activations {
+            if !predicate_ids.contains(&activation.when) {
+                diagnostics.push(LintDiagnostic::new(
+                    LintCode::E1004,
+                    format!(
+                        "activation references unknown predicate '{}'",
+                        activation.when
+                    ),
+                    None,
+                ));
+            }
+        }
+
+        // Check predicate expressions reference declared inputs (E2003)
+        for predicate in &adaptivity.predicates {
+            let mut vars = Vec::new();
+            collect_predicate_vars(&predicate.expr, &mut vars);
+            for var in vars {
+                if !policy.inputs.contains_key(var) {
+                    diagnostics.push(LintDiagnostic::new(
+                        LintCode::E2003,
+                        format!(
+                            "predicate '{}' references unknown input '{}'",
+                            predicate.id, var
+                        ),
+                        None,
+                    ));
+                }
+            }
+        }
+
+        // Check rules gated only by predicates that can never hold (W1003)
+        for rule in &policy.rules {
+            let mut gates = adaptivity
+                .activations
+                .iter()
+                .filter(|a| a.rules.contains(&rule.id))
+                .peekable();
+            if gates.peek().is_none() {
+                continue; // not gated: always active
+            }
+            let reachable = gates.any(|a| {
+                adaptivity
+                    .predicates
+                    .iter()
+                    .any(|p| p.id == a.when && !is_constant_false(&p.expr))
+            });
+            if !reachable {
+                diagnostics.push(LintDiagnostic::new(
+                    LintCode::W1003,
+                    format!(
+                        "rule '{}' is unreachable: no activation can enable it",
+                        rule.id
+                    ),
+                    Some(&rule.id),
+                ));
+            }
+        }
     }
 
-    // TODO: E2003 - Check lhs/rhs references exist in inputs (Week 2)
-    // TODO: E3002 - Check range_min expression validity (Week 2)
+    // Check operands against declared inputs (E2003, E2004, E3002)
+    for rule in &policy.rules {
+        lint_rule_operands(rule, &policy.inputs, &mut diagnostics);
+    }
 
     diagnostics
 }
 
+/// Whether a predicate evaluates to `false` without any inputs
+///
+/// Folds the expression against an empty context; expressions that need an
+/// input (and do not short-circuit before it) count as satisfiable.
+fn is_constant_false(expr: &serde_json::Value) -> bool {
+    matches!(
+        eval_expr(expr, &EvalContext::new()),
+        Ok(PredicateValue::Bool(false))
+    )
+}
+
+/// Rule operand as seen by the IR: strings and `{ var }` are input references
+enum Operand<'a> {
+    Var(&'a str),
+    Literal(&'a serde_json::Value),
+}
+
+impl<'a> Operand<'a> {
+    fn from_expr(expr: &'a serde_json::Value) -> Self {
+        match expr {
+            serde_json::Value::String(s) => Operand::Var(s),
+            serde_json::Value::Object(map) if map.len() == 1 => match map.get("var") {
+                Some(serde_json::Value::String(s)) => Operand::Var(s),
+                _ => Operand::Literal(expr),
+            },
+            _ => Operand::Literal(expr),
+        }
+    }
+}
+
+fn is_numeric(input: &InputDef) -> bool {
+    matches!(input.r#type.as_str(), "number" | "integer")
+}
+
+fn is_textual(input: &InputDef) -> bool {
+    matches!(input.r#type.as_str(), "string" | "hex")
+}
+
+fn is_array(input: &InputDef) -> bool {
+    input.r#type == "array"
+}
+
+/// Type checks `lhs`/`rhs` of a rule against the declared inputs
+fn lint_rule_operands(
+    rule: &Rule,
+    inputs: &std::collections::BTreeMap<String, InputDef>,
+    diagnostics: &mut Vec<LintDiagnostic>,
+) {
+    // Resolve variables (E2003); unresolved operands are not type checked
+    let mut resolve = |expr| match Operand::from_expr(expr) {
+        Operand::Var(name) => match inputs.get(name) {
+            Some(input) => Some(Ok(input)),
+            None => {
+                diagnostics.push(LintDiagnostic::new(
+                    LintCode::E2003,
+                    format!("expr references unknown input '{}'", name),
+                    Some(&rule.id),
+                ));
+                None
+            }
+        },
+        Operand::Literal(value) => Some(Err(value)),
+    };
+    let (Some(lhs), Some(rhs)) = (resolve(&rule.lhs), resolve(&rule.rhs)) else {
+        return;
+    };
+
+    let mismatch = |message: String| LintDiagnostic::new(LintCode::E2004, message, Some(&rule.id));
+    let invalid_range =
+        |message: String| LintDiagnostic::new(LintCode::E3002, message, Some(&rule.id));
+
+    match rule.op.as_str() {
+        "non_membership" => {
+            match lhs {
+                Ok(input) if is_array(input) || is_textual(input) => {}
+                Ok(input) => diagnostics.push(mismatch(format!(
+                    "non_membership.lhs must be array, string or hex input, got {}",
+                    input.r#type
+                ))),
+                Err(value) => diagnostics.push(mismatch(format!(
+                    "non_membership.lhs must be an input, got literal {}",
+                    value
+                ))),
+            }
+            match rhs {
+                Ok(input) if is_textual(input) => {}
+                Ok(input) => diagnostics.push(mismatch(format!(
+                    "non_membership.rhs must be a hex/string list root, got {}",
+                    input.r#type
+                ))),
+                Err(value) => diagnostics.push(mismatch(format!(
+                    "non_membership.rhs must be an input, got literal {}",
+                    value
+                ))),
+            }
+        }
+        "range_min" => {
+            match lhs {
+                Ok(input) if is_numeric(input) || is_array(input) => {}
+                Ok(input) => diagnostics.push(invalid_range(format!(
+                    "range_min.lhs must be number or array input, got {}",
+                    input.r#type
+                ))),
+                Err(value) => diagnostics.push(invalid_range(format!(
+                    "range_min.lhs must be an input, got literal {}",
+                    value
+                ))),
+            }
+            match rhs {
+                Ok(input) if is_numeric(input) => {}
+                Ok(input) => diagnostics.push(invalid_range(format!(
+                    "range_min bound must be a number input, got {}",
+                    input.r#type
+                ))),
+                Err(value) if value.as_u64().is_some() => {}
+                Err(value) => diagnostics.push(invalid_range(format!(
+                    "range_min bound must be a non-negative integer, got {}",
+                    value
+                ))),
+            }
+        }
+        "non_intersection" => {
+            match lhs {
+                Ok(input) if is_array(input) || is_textual(input) => {}
+                Ok(input) => diagnostics.push(mismatch(format!(
+                    "non_intersection.lhs must be array, string or hex input, got {}",
+                    input.r#type
+                ))),
+                Err(value) => diagnostics.push(mismatch(format!(
+                    "non_intersection.lhs must be an input, got literal {}",
+                    value
+                ))),
+            }
+            let scalars = rhs
+                .err()
+                .and_then(|value| value.as_array())
+                .is_some_and(|items| {
+                    items
+                        .iter()
+                        .all(|v| v.is_string() || v.is_number() || v.is_boolean())
+                });
+            if !scalars {
+                diagnostics.push(mismatch(
+                    "non_intersection.rhs must be an array literal of scalars".to_string(),
+                ));
+            }
+        }
+        "range" => {
+            match lhs {
+                Ok(input) if is_numeric(input) => {}
+                Ok(input)
+                    if is_array(input)
+                        && matches!(input.items.as_deref(), Some("number" | "integer")) => {}
+                Ok(input) => diagnostics.push(invalid_range(format!(
+                    "range.lhs must be number input or array of numbers, got {}{}",
+                    input.r#type,
+                    input
+                        .items
+                        .as_deref()
+                        .map(|items| format!("<{}>", items))
+                        .unwrap_or_default()
+                ))),
+                Err(value) => diagnostics.push(invalid_range(format!(
+                    "range.lhs must be an input, got literal {}",
+                    value
+                ))),
+            }
+            if let Err(message) = check_range_bounds(rhs) {
+                diagnostics.push(invalid_range(message));
+            }
+        }
+        "eq" => {
+            let kind = |operand: Result<&InputDef, &serde_json::Value>| match operand {
+                Ok(input) if is_numeric(input) => "number",
+                Ok(input) if is_textual(input) => "string",
+                Ok(input) => match input.r#type.as_str() {
+                    "boolean" => "boolean",
+                    _ => "array",
+                },
+                Err(value) if value.is_number() => "number",
+                Err(value) if value.is_boolean() => "boolean",
+                Err(_) => "array",
+            };
+            let (lhs_kind, rhs_kind) = (kind(lhs), kind(rhs));
+            if lhs_kind == "array" || rhs_kind == "array" {
+                diagnostics.push(mismatch("eq operands must be scalars".to_string()));
+            } else if lhs_kind != rhs_kind {
+                diagnostics.push(mismatch(format!(
+                    "eq compares {} with {}",
+                    lhs_kind, rhs_kind
+                )));
+            }
+        }
+        _ => {} // reported as E2001
+    }
+}
+
+/// Checks the `{ min, max }` literal of a `range` rule
+fn check_range_bounds(rhs: Result<&InputDef, &serde_json::Value>) -> Result<(), String> {
+    let bounds = match rhs {
+        Err(serde_json::Value::Object(bounds)) => bounds,
+        _ => return Err("range.rhs must be a literal { min, max }".to_string()),
+    };
+    if let Some(key) = bounds.keys().find(|k| *k != "min" && *k != "max") {
+        return Err(format!("range.rhs has unknown key '{}'", key));
+    }
+
+    let bound = |key: &str| match bounds.get(key) {
+        None => Ok(None),
+        Some(value) => value
+            .as_i64()
+            .map(Some)
+            .ok_or_else(|| format!("range.{} must be an integer, got {}", key, value)),
+    };
+    match (bound("min")?, bound("max")?) {
+        (None, None) => Err("range needs min and/or max".to_string()),
+        (Some(min), Some(max)) if min > max => {
+            Err(format!("range min {} is greater than max {}", min, max))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Collects `{ var }` references of a predicate expression
+fn collect_predicate_vars<'a>(expr: &'a serde_json::Value, vars: &mut Vec<&'a str>) {
+    match expr {
+        serde_json::Value::Object(map) => {
+            if let Some(serde_json::Value::String(var)) = map.get("var") {
+                vars.push(var);
+            }
+            if let Some(serde_json::Value::Array(args)) = map.get("args") {
+                for arg in args {
+                    collect_predicate_vars(arg, vars);
+                }
+            }
+        }
+        serde_json::Value::Array(items) => {
+            for item in items {
+                collect_predicate_vars(item, vars);
+            }
+        }
+        _ => {}
+    }
+}
+
 /// Check if diagnostics contain errors
 pub fn has_errors(diagnostics: &[LintDiagnostic]) -> bool {
     diagnostics.iter().any(|d| d.level == Level::Error)
@@ -196,6 +547,8 @@ mod tests {
             inputs: BTreeMap::new(),
             rules: vec![],
             adaptivity: None,
+            valid_from: None,
+            valid_until: None,
         };
 
         let diagnostics = lint(&policy, LintMode::Strict);
@@ -215,6 +568,8 @@ mod tests {
             inputs: BTreeMap::new(),
             rules: vec![],
             adaptivity: None,
+            valid_from: None,
+            valid_until: None,
         };
 
         let diagnostics = lint(&policy, LintMode::Relaxed);
@@ -242,6 +597,8 @@ mod tests {
                 rhs: serde_json::json!("var2"),
             }],
             adaptivity: None,
+            valid_from: None,
+            valid_until: None,
         };
 
         let diagnostics = lint(&policy, LintMode::Strict);
@@ -276,6 +633,8 @@ mod tests {
                 },
             ],
             adaptivity: None,
+            valid_from: None,
+            valid_until: None,
         };
 
         let diagnostics = lint(&policy, LintMode::Strict);
@@ -296,7 +655,7 @@ mod tests {
                 article: None,
             }],
             description: "Test policy".to_string(),
-            inputs: BTreeMap::new(),
+            inputs: inputs(&[("a", "string", None), ("b", "hex", None)]),
             rules: vec![Rule {
                 id: "r1".to_string(),
                 op: "eq".to_string(),
@@ -304,10 +663,316 @@ mod tests {
                 rhs: serde_json::json!("b"),
             }],
             adaptivity: None,
+            valid_from: None,
+            valid_until: None,
         };
 
         let diagnostics = lint(&policy, LintMode::Strict);
         assert!(!has_errors(&diagnostics));
         assert_eq!(diagnostics.len(), 0); // No errors or warnings
     }
+
+    fn inputs(defs: &[(&str, &str, Option<&str>)]) -> BTreeMap<String, InputDef> {
+        defs.iter()
+            .map(|(name, ty, items)| {
+                (
+                    name.to_string(),
+                    InputDef {
+                        r#type: ty.to_string(),
+                        items: items.map(str::to_string),
+                    },
+                )
+            })
+            .collect()
+    }
+
+    fn rule(id: &str, op: &str, lhs: serde_json::Value, rhs: serde_json::Value) -> Rule {
+        Rule {
+            id: id.to_string(),
+            op: op.to_string(),
+            lhs,
+            rhs,
+        }
+    }
+
+    fn typed_policy(rules: Vec<Rule>, adaptivity: Option<Adaptivity>) -> PolicyV2 {
+        PolicyV2 {
+            id: "test".to_string(),
+            version: "1.0".to_string(),
+            legal_basis: vec![LegalBasisItem {
+                directive: Some("LkSG".to_string()),
+                article: None,
+            }],
+            description: "Test policy".to_string(),
+            inputs: inputs(&[
+                ("supplier_hashes", "array", Some("hex")),
+                ("supplier_tiers", "array", Some("number")),
+                ("sanctions_root", "hex", None),
+                ("ubo_count", "number", None),
+                ("active", "boolean", None),
+            ]),
+            rules,
+            adaptivity,
+            valid_from: None,
+            valid_until: None,
+        }
+    }
+
+    fn codes(diagnostics: &[LintDiagnostic]) -> Vec<&str> {
+        diagnostics.iter().map(|d| d.code.as_str()).collect()
+    }
+
+    #[test]
+    fn test_lint_unknown_input_reference() {
+        let policy = typed_policy(
+            vec![
+                rule(
+                    "no_sanctions",
+                    "non_membership",
+                    serde_json::json!("supplier_hash"),
+                    serde_json::json!({"var": "sanctions_root"}),
+                ),
+                rule(
+                    "ubos",
+                    "range_min",
+                    serde_json::json!({"var": "ubo_cnt"}),
+                    serde_json::json!(1),
+                ),
+            ],
+            None,
+        );
+
+        let diagnostics = lint(&policy, LintMode::Strict);
+        assert_eq!(codes(&diagnostics), ["E2003", "E2003"]);
+        assert_eq!(
+            diagnostics[0].message,
+            "expr references unknown input 'supplier_hash'"
+        );
+        assert_eq!(diagnostics[1].rule_id.as_deref(), Some("ubos"));
+    }
+
+    #[test]
+    fn test_lint_operand_type_mismatch() {
+        let policy = typed_policy(
+            vec![
+                rule(
+                    "a_screen_count",
+                    "non_membership",
+                    serde_json::json!("ubo_count"),
+                    serde_json::json!("sanctions_root"),
+                ),
+                rule(
+                    "b_literal_root",
+                    "non_membership",
+                    serde_json::json!("supplier_hashes"),
+                    serde_json::json!(42),
+                ),
+                rule(
+                    "c_eq_array",
+                    "eq",
+                    serde_json::json!("supplier_hashes"),
+                    serde_json::json!("sanctions_root"),
+                ),
+                rule(
+                    "d_eq_kinds",
+                    "eq",
+                    serde_json::json!("active"),
+                    serde_json::json!(1),
+                ),
+                rule(
+                    "e_eq_ok",
+                    "eq",
+                    serde_json::json!("active"),
+                    serde_json::json!(true),
+                ),
+            ],
+            None,
+        );
+
+        let diagnostics = lint(&policy, LintMode::Strict);
+        assert_eq!(codes(&diagnostics), ["E2004"; 4]);
+        let rule_ids: Vec<_> = diagnostics
+            .iter()
+            .map(|d| d.rule_id.as_deref().unwrap())
+            .collect();
+        assert_eq!(
+            rule_ids,
+            [
+                "a_screen_count",
+                "b_literal_root",
+                "c_eq_array",
+                "d_eq_kinds"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lint_range_min_bounds() {
+        let policy = typed_policy(
+            vec![
+                rule(
+                    "negative",
+                    "range_min",
+                    serde_json::json!("supplier_hashes"),
+                    serde_json::json!(-1),
+                ),
+                rule(
+                    "fraction",
+                    "range_min",
+                    serde_json::json!("ubo_count"),
+                    serde_json::json!(1.5),
+                ),
+                rule(
+                    "hex_lhs",
+                    "range_min",
+                    serde_json::json!("sanctions_root"),
+                    serde_json::json!(1),
+                ),
+                rule(
+                    "input_bound",
+                    "range_min",
+                    serde_json::json!("supplier_hashes"),
+                    serde_json::json!({"var": "ubo_count"}),
+                ),
+            ],
+            None,
+        );
+
+        let diagnostics = lint(&policy, LintMode::Strict);
+        assert_eq!(codes(&diagnostics), ["E3002"; 3]);
+        assert!(diagnostics[0].message.contains("non-negative integer"));
+        assert_eq!(diagnostics[2].rule_id.as_deref(), Some("hex_lhs"));
+    }
+
+    #[test]
+    fn test_lint_adaptivity_references() {
+        let ubo_rule = |id: &str| {
+            rule(
+                id,
+                "range_min",
+                serde_json::json!("ubo_count"),
+                serde_json::json!(1),
+            )
+        };
+        let policy = typed_policy(
+            vec![
+                ubo_rule("always"),
+                ubo_rule("deep_tier"),
+                ubo_rule("disabled"),
+                ubo_rule("orphaned"),
+            ],
+            Some(Adaptivity {
+                predicates: vec![
+                    Predicate {
+                        id: "is_deep".to_string(),
+                        expr: serde_json::json!({"func": "gt", "args": [
+                            {"func": "max", "args": [{"var": "supplier_tiers"}]}, 1
+                        ]}),
+                    },
+                    Predicate {
+                        id: "never".to_string(),
+                        expr: serde_json::json!(false),
+                    },
+                    Predicate {
+                        id: "typo".to_string(),
+                        expr: serde_json::json!({"func": "gt", "args": [{"var": "ubo_cout"}, 1]}),
+                    },
+                ],
+                activations: vec![
+                    Activation {
+                        when: "is_deep".to_string(),
+                        rules: vec!["deep_tier".to_string()],
+                    },
+                    Activation {
+                        when: "never".to_string(),
+                        rules: vec!["disabled".to_string()],
+                    },
+                    Activation {
+                        when: "missing".to_string(),
+                        rules: vec!["orphaned".to_string()],
+                    },
+                ],
+            }),
+        );
+
+        let diagnostics = lint(&policy, LintMode::Strict);
+        assert_eq!(codes(&diagnostics), ["E1004", "E2003", "W1003", "W1003"]);
+        assert!(diagnostics[0].message.contains("'missing'"));
+        assert!(diagnostics[1].message.contains("'ubo_cout'"));
+        assert_eq!(diagnostics[2].rule_id.as_deref(), Some("disabled"));
+        assert_eq!(diagnostics[3].rule_id.as_deref(), Some("orphaned"));
+    }
+
+    #[test]
+    fn test_lint_constant_false_predicates() {
+        let ubo_rule = |id: &str| {
+            rule(
+                id,
+                "range_min",
+                serde_json::json!("ubo_count"),
+                serde_json::json!(1),
+            )
+        };
+        let predicate = |id: &str, expr: serde_json::Value| Predicate {
+            id: id.to_string(),
+            expr,
+        };
+        let activation = |when: &str, rule: &str| Activation {
+            when: when.to_string(),
+            rules: vec![rule.to_string()],
+        };
+        let policy = typed_policy(
+            vec![
+                ubo_rule("folded"),
+                ubo_rule("short_circuit"),
+                ubo_rule("needs_input"),
+            ],
+            Some(Adaptivity {
+                predicates: vec![
+                    predicate(
+                        "gt_const",
+                        serde_json::json!({"func": "gt", "args": [1, 2]}),
+                    ),
+                    predicate(
+                        "and_false",
+                        serde_json::json!({"func": "and", "args": [
+                            false, {"func": "ge", "args": [{"var": "ubo_count"}, 1]}
+                        ]}),
+                    ),
+                    predicate(
+                        "input_dependent",
+                        serde_json::json!({"func": "and", "args": [
+                            {"func": "ge", "args": [{"var": "ubo_count"}, 1]}, false
+                        ]}),
+                    ),
+                ],
+                activations: vec![
+                    activation("gt_const", "folded"),
+                    activation("and_false", "short_circuit"),
+                    activation("input_dependent", "needs_input"),
+                ],
+            }),
+        );
+
+        let diagnostics = lint(&policy, LintMode::Strict);
+        assert_eq!(codes(&diagnostics), ["W1003", "W1003"]);
+        assert_eq!(diagnostics[0].rule_id.as_deref(), Some("folded"));
+        assert_eq!(diagnostics[1].rule_id.as_deref(), Some("short_circuit"));
+    }
+
+    #[test]
+    fn test_lint_validity_window() {
+        let mut policy = typed_policy(vec![], None);
+        policy.valid_from = Some("2025-01-01".to_string());
+        policy.valid_until = Some("2025-12-31".to_string());
+        assert!(lint(&policy, LintMode::Strict).is_empty());
+
+        policy.valid_until = Some("2024-12-31".to_string());
+        let diagnostics = lint(&policy, LintMode::Strict);
+        assert_eq!(codes(&diagnostics), ["E1005"]);
+        assert!(diagnostics[0].message.contains("after valid_until"));
+
+        policy.valid_until = Some("end of 2025".to_string());
+        assert_eq!(codes(&lint(&policy, LintMode::Strict)), ["E1005"]);
+    }
 }
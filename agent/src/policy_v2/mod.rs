@@ -1,16 +1,24 @@
+pub mod adaptivity;
 pub mod cli;
+pub mod evaluator;
 pub mod hasher;
 pub mod ir;
 pub mod linter;
+pub mod rule_policy;
 pub mod types;
 pub mod yaml_parser;
 
 // Re-export commonly used types
+pub use adaptivity::{resolve_activations, ActivationSet, PredicateValue, RuleActivation};
 pub use cli::{run_compile, run_lint, run_show, PolicyCli, PolicyCommand};
 pub use hasher::sha3_256_hex;
-pub use ir::{canonicalize, generate_ir};
+pub use evaluator::{
+    evaluate, EvalContext, Evaluation, InputValue, ListRootInput, RecordInput, RuleOutcome,
+};
+pub use ir::{canonicalize, compute_ir_hash, generate_ir, is_ir_file, load_ir};
 pub use linter::{
     has_errors, http_status_from_diagnostics, lint, Level, LintCode, LintDiagnostic, LintMode,
 };
+pub use rule_policy::RulePolicy;
 pub use types::{IrExpression, IrRule, IrV1, PolicyV2, Rule};
 pub use yaml_parser::{parse_yaml, parse_yaml_str};
@@ -0,0 +1,287 @@
+//! Rule-based policy format (`examples/policy.yml`)
+//!
+//! Field rules (`type: blocklist | range`) over supplier and UBO fields are
+//! translated into a [`PolicyV2`], so lint, hashing and IR generation run
+//! through the regular v2 pipeline:
+//!
+//! * `blocklist` → `non_intersection` (no field value may be in `values`)
+//! * `range` → `range` (every field value within `min`/`max`)
+//!
+//! Fields are resolved to inputs of the evaluation context, e.g.
+//! `jurisdiction` → `supplier_jurisdictions`. Field names that exist on
+//! suppliers and UBOs (`name`) must be qualified (`supplier.name`).
+
+use super::types::{InputDef, LegalBasisItem, PolicyV2, Rule};
+use crate::policy::ValidityWindow;
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Record fields that rules can refer to: (record, field, input, item type)
+const FIELDS: &[(&str, &str, &str, &str)] = &[
+    ("supplier", "name", "supplier_names", "string"),
+    (
+        "supplier",
+        "jurisdiction",
+        "supplier_jurisdictions",
+        "string",
+    ),
+    ("supplier", "tier", "supplier_tiers", "number"),
+    ("ubo", "name", "ubo_names", "string"),
+    ("ubo", "birthdate", "ubo_birthdates", "string"),
+    ("ubo", "citizenship", "ubo_citizenships", "string"),
+];
+
+/// Rule-based policy as written in YAML
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct RulePolicy {
+    pub name: String,
+    pub version: String,
+    #[serde(default)]
+    pub description: String,
+    pub rules: Vec<FieldRule>,
+    #[serde(default)]
+    pub metadata: RulePolicyMetadata,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct FieldRule {
+    pub id: String,
+    #[serde(default)]
+    pub description: String,
+    pub field: String,
+    #[serde(flatten)]
+    pub check: FieldCheck,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum FieldCheck {
+    Blocklist {
+        values: Vec<serde_json::Value>,
+    },
+    Range {
+        #[serde(default)]
+        min: Option<i64>,
+        #[serde(default)]
+        max: Option<i64>,
+    },
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct RulePolicyMetadata {
+    #[serde(default)]
+    pub compliance_framework: Option<String>,
+    #[serde(default)]
+    pub valid_from: Option<String>,
+    #[serde(default)]
+    pub valid_until: Option<String>,
+    /// Remaining metadata (author, created_at, ...) - not part of the policy
+    #[serde(flatten)]
+    pub extra: BTreeMap<String, serde_yaml::Value>,
+}
+
+/// Checks whether a parsed YAML document uses the rule-based format
+///
+/// Rule-based policies have no `inputs` and their rules carry `type`
+/// instead of `op`.
+pub fn is_rule_based(doc: &serde_yaml::Value) -> bool {
+    if doc.get("inputs").is_some() {
+        return false;
+    }
+    match doc.get("rules").and_then(|r| r.as_sequence()) {
+        Some(rules) => rules
+            .iter()
+            .any(|r| r.get("type").is_some() && r.get("op").is_none()),
+        None => false,
+    }
+}
+
+/// Resolves a rule field to its context input and item type
+fn resolve_field(field: &str) -> Result<(&'static str, &'static str)> {
+    let (record, name) = match field.split_once('.') {
+        Some((record, name)) => (Some(record), name),
+        None => (None, field),
+    };
+    let matches: Vec<_> = FIELDS
+        .iter()
+        .filter(|(r, f, _, _)| *f == name && record.is_none_or(|record| record == *r))
+        .collect();
+
+    match matches.as_slice() {
+        [(_, _, input, items)] => Ok((input, items)),
+        [] => bail!("unknown field '{}'", field),
+        _ => bail!(
+            "ambiguous field '{}' (use {})",
+            field,
+            matches
+                .iter()
+                .map(|(r, f, _, _)| format!("{}.{}", r, f))
+                .collect::<Vec<_>>()
+                .join(" or ")
+        ),
+    }
+}
+
+impl RulePolicy {
+    /// Translates the policy into PolicyV2
+    pub fn to_policy_v2(&self) -> Result<PolicyV2> {
+        ValidityWindow::parse(
+            self.metadata.valid_from.as_deref(),
+            self.metadata.valid_until.as_deref(),
+        )
+        .context("metadata")?;
+
+        let mut inputs = BTreeMap::new();
+        let mut rules = Vec::with_capacity(self.rules.len());
+
+        for rule in &self.rules {
+            let (input, items) =
+                resolve_field(&rule.field).with_context(|| format!("rule '{}'", rule.id))?;
+            inputs.insert(
+                input.to_string(),
+                InputDef {
+                    r#type: "array".to_string(),
+                    items: Some(items.to_string()),
+                },
+            );
+
+            let (op, rhs) = match &rule.check {
+                FieldCheck::Blocklist { values } => {
+                    ("non_intersection", serde_json::Value::Array(values.clone()))
+                }
+                FieldCheck::Range { min, max } => {
+                    let mut bounds = serde_json::Map::new();
+                    if let Some(min) = min {
+                        bounds.insert("min".to_string(), (*min).into());
+                    }
+                    if let Some(max) = max {
+                        bounds.insert("max".to_string(), (*max).into());
+                    }
+                    ("range", serde_json::Value::Object(bounds))
+                }
+            };
+
+            rules.push(Rule {
+                id: rule.id.clone(),
+                op: op.to_string(),
+                lhs: serde_json::json!({ "var": input }),
+                rhs,
+            });
+        }
+
+        Ok(PolicyV2 {
+            id: self.version.to_lowercase(),
+            version: self.version.clone(),
+            legal_basis: self
+                .metadata
+                .compliance_framework
+                .iter()
+                .map(|framework| LegalBasisItem {
+                    directive: Some(framework.clone()),
+                    article: None,
+                })
+                .collect(),
+            description: if self.description.is_empty() {
+                self.name.clone()
+            } else {
+                self.description.clone()
+            },
+            inputs,
+            rules,
+            adaptivity: None,
+            valid_from: self.metadata.valid_from.clone(),
+            valid_until: self.metadata.valid_until.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = r#"
+name: LkSG Demo Policy
+version: lksg.v1.0
+description: Demo
+rules:
+  - id: jurisdiction_check
+    type: blocklist
+    field: jurisdiction
+    values: [KP, IR]
+  - id: tier_validation
+    type: range
+    field: tier
+    min: 1
+    max: 3
+  - id: ubo_citizenship_check
+    type: blocklist
+    field: citizenship
+    values: [KP]
+metadata:
+  author: CAP Demo
+  compliance_framework: LkSG
+  valid_until: 2025-12-31
+"#;
+
+    #[test]
+    fn test_to_policy_v2() {
+        let doc: serde_yaml::Value = serde_yaml::from_str(EXAMPLE).unwrap();
+        assert!(is_rule_based(&doc));
+
+        let policy: RulePolicy = serde_yaml::from_value(doc).unwrap();
+        let v2 = policy.to_policy_v2().unwrap();
+
+        assert_eq!(v2.id, "lksg.v1.0");
+        assert_eq!(v2.legal_basis[0].directive.as_deref(), Some("LkSG"));
+        assert_eq!(v2.valid_until.as_deref(), Some("2025-12-31"));
+        assert_eq!(
+            v2.inputs.keys().collect::<Vec<_>>(),
+            [
+                "supplier_jurisdictions",
+                "supplier_tiers",
+                "ubo_citizenships"
+            ]
+        );
+        assert_eq!(v2.rules[0].op, "non_intersection");
+        assert_eq!(
+            v2.rules[0].lhs,
+            serde_json::json!({"var": "supplier_jurisdictions"})
+        );
+        assert_eq!(v2.rules[0].rhs, serde_json::json!(["KP", "IR"]));
+        assert_eq!(v2.rules[1].op, "range");
+        assert_eq!(v2.rules[1].rhs, serde_json::json!({"min": 1, "max": 3}));
+    }
+
+    #[test]
+    fn test_resolve_field() {
+        assert_eq!(resolve_field("tier").unwrap().0, "supplier_tiers");
+        assert_eq!(resolve_field("ubo.name").unwrap().0, "ubo_names");
+        assert!(resolve_field("name")
+            .unwrap_err()
+            .to_string()
+            .contains("supplier.name or ubo.name"));
+        assert!(resolve_field("revenue").is_err());
+        assert!(resolve_field("ubo.tier").is_err());
+    }
+
+    #[test]
+    fn test_invalid_valid_until() {
+        let yaml = EXAMPLE.replace("2025-12-31", "end of 2025");
+        let policy: RulePolicy = serde_yaml::from_str(&yaml).unwrap();
+        assert!(policy.to_policy_v2().is_err());
+
+        let yaml = EXAMPLE.replace("  valid_until:", "  valid_from: 2026-01-01\n  valid_until:");
+        let policy: RulePolicy = serde_yaml::from_str(&yaml).unwrap();
+        assert!(policy.to_policy_v2().is_err());
+    }
+
+    #[test]
+    fn test_v2_document_is_not_rule_based() {
+        let doc: serde_yaml::Value = serde_yaml::from_str(
+            "id: x\ninputs: {}\nrules:\n  - id: r\n    op: eq\n    lhs: a\n    rhs: b\n",
+        )
+        .unwrap();
+        assert!(!is_rule_based(&doc));
+    }
+}
@@ -13,6 +13,12 @@ pub struct PolicyV2 {
     pub rules: Vec<Rule>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub adaptivity: Option<Adaptivity>,
+    /// First day the policy may be used for proofs (ISO 8601 date)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub valid_from: Option<String>,
+    /// Last day the policy may be used for proofs (ISO 8601 date)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub valid_until: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -65,6 +71,10 @@ pub struct IrV1 {
     pub rules: Vec<IrRule>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub adaptivity: Option<IrAdaptivity>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub valid_from: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub valid_until: Option<String>,
     pub ir_hash: String,
 }
 
@@ -112,6 +122,8 @@ mod tests {
             inputs: BTreeMap::new(),
             rules: vec![],
             adaptivity: None,
+            valid_from: None,
+            valid_until: None,
         };
 
         let json = serde_json::to_string(&policy).unwrap();
@@ -129,6 +141,8 @@ mod tests {
             policy_hash: "sha3-256:abc123".to_string(),
             rules: vec![],
             adaptivity: None,
+            valid_from: None,
+            valid_until: None,
             ir_hash: "sha3-256:def456".to_string(),
         };
 
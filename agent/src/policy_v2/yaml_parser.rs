@@ -1,21 +1,31 @@
+use super::rule_policy::{self, RulePolicy};
 use super::types::PolicyV2;
 use anyhow::{Context, Result};
 use std::path::Path;
 
 /// Parse PolicyV2 from YAML file
+///
+/// Rule-based policies (`type: blocklist | range`) are translated to PolicyV2.
 pub fn parse_yaml<P: AsRef<Path>>(path: P) -> Result<PolicyV2> {
     let contents = std::fs::read_to_string(path.as_ref()).context("Failed to read policy file")?;
 
-    let policy: PolicyV2 = serde_yaml::from_str(&contents).context("Failed to parse YAML")?;
-
-    Ok(policy)
+    parse_document(&contents).context("Failed to parse YAML")
 }
 
 /// Parse PolicyV2 from YAML string
 pub fn parse_yaml_str(yaml: &str) -> Result<PolicyV2> {
-    let policy: PolicyV2 = serde_yaml::from_str(yaml).context("Failed to parse YAML string")?;
+    parse_document(yaml).context("Failed to parse YAML string")
+}
+
+fn parse_document(yaml: &str) -> Result<PolicyV2> {
+    let doc: serde_yaml::Value = serde_yaml::from_str(yaml)?;
 
-    Ok(policy)
+    if rule_policy::is_rule_based(&doc) {
+        let policy: RulePolicy = serde_yaml::from_value(doc)?;
+        return policy.to_policy_v2();
+    }
+
+    Ok(serde_yaml::from_value(doc)?)
 }
 
 #[cfg(test)]
@@ -95,6 +105,25 @@ adaptivity:
         assert_eq!(adaptivity.predicates[0].id, "pred1");
     }
 
+    #[test]
+    fn test_parse_rule_based_policy() {
+        let yaml = r#"
+name: Demo
+version: demo.v1
+rules:
+  - id: tier_validation
+    type: range
+    field: tier
+    max: 3
+metadata:
+  compliance_framework: LkSG
+"#;
+        let policy = parse_yaml_str(yaml).unwrap();
+        assert_eq!(policy.id, "demo.v1");
+        assert_eq!(policy.rules[0].op, "range");
+        assert!(policy.inputs.contains_key("supplier_tiers"));
+    }
+
     #[test]
     fn test_parse_invalid_yaml() {
         let yaml = "invalid: yaml: structure: [";
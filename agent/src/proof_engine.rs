@@ -1,6 +1,13 @@
-use crate::io::JsonPersistent;
+use crate::commitment::RecordNonces;
+use crate::io::{JsonPersistent, Supplier, Ubo};
+use crate::lists::{self, ListExclusionClaim, ListExclusionWitness, ListKind, RecordKind};
 use crate::manifest::Manifest;
 use crate::policy::Policy;
+use crate::policy_v2::{
+    self, EvalContext, Evaluation, InputValue, IrExpression, IrV1, ListRootInput, RecordInput,
+    RuleActivation,
+};
+use crate::sorted_merkle::SortedMerkleTree;
 use serde::{Deserialize, Serialize};
 use sha3::{Digest, Sha3_256};
 use std::error::Error;
@@ -19,6 +26,13 @@ pub struct ConstraintCheck {
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ProofData {
     pub checked_constraints: Vec<ConstraintCheck>,
+    /// Zusagen der Ausschluss-Nachweise gegen Sanktions-/Jurisdiktions-Listen
+    /// (optional; die Nachweise selbst bleiben in der privaten Witness-Datei)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub list_exclusions: Vec<ListExclusionClaim>,
+    /// Aktive/inaktive Regeln mit Begründung (nur bei IR mit Adaptivity)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rule_activations: Vec<RuleActivation>,
 }
 
 /// Proof-Objekt (v0 - Mock, später ZK)
@@ -30,6 +44,9 @@ pub struct Proof {
     pub statement: String,
     pub manifest_hash: String,
     pub policy_hash: String,
+    /// Hash der kompilierten Policy-IR (nur bei IR-basierten Proofs)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ir_hash: Option<String>,
     pub proof_data: ProofData,
     pub status: String,
 }
@@ -38,7 +55,7 @@ impl Proof {
     /// Erstellt einen neuen Proof aus Policy, Manifest und Daten
     ///
     /// # Argumente
-    /// * `policy` - Die Policy mit Constraints
+    /// * `policy` - Die Policy mit Constraints (muss heute gültig sein)
     /// * `manifest` - Das Manifest mit Commitments
     /// * `supplier_count` - Anzahl der Suppliers (für Validierung)
     /// * `ubo_count` - Anzahl der UBOs (für Validierung)
@@ -51,6 +68,11 @@ impl Proof {
         supplier_count: usize,
         ubo_count: usize,
     ) -> Result<Self, Box<dyn Error>> {
+        policy
+            .validity()?
+            .check_today()
+            .map_err(|e| format!("Policy {} {}", policy.name, e))?;
+
         let mut checks = Vec::new();
 
         // Check 1: Mindestens ein UBO erforderlich
@@ -83,8 +105,11 @@ impl Proof {
             statement: format!("policy:{}", policy.version),
             manifest_hash,
             policy_hash: manifest.policy.hash.clone(),
+            ir_hash: None,
             proof_data: ProofData {
                 checked_constraints: checks,
+                list_exclusions: Vec::new(),
+                rule_activations: Vec::new(),
             },
             status: if all_ok {
                 "ok".to_string()
@@ -94,6 +119,147 @@ impl Proof {
         })
     }
 
+    /// Erstellt einen Proof durch Auswertung einer kompilierten Policy-IR
+    ///
+    /// Jede aktive IR-Regel wird zu einem Constraint-Check; erfolgreiche
+    /// `non_membership`-Regeln liefern die Listen-Ausschluss-Nachweise. Der
+    /// Proof enthält nur deren Zusagen, die Nachweise bleiben in
+    /// `Evaluation::witnesses`.
+    /// Bei einer IR mit Adaptivity wird die Aktivierung jeder Regel im
+    /// Proof festgehalten.
+    ///
+    /// Enthält das Manifest einen `sanctions_root`/`jurisdiction_root`, muss
+    /// eine aktive `non_membership`-Regel gegen diesen Root existieren – ein
+    /// Proof ohne Ausschluss-Nachweis wäre nie verifizierbar und schlägt
+    /// daher bereits hier fehl.
+    ///
+    /// # Argumente
+    /// * `ir` - Kompilierte Policy (IR v1, muss heute gültig sein)
+    /// * `manifest` - Das Manifest mit Commitments (Policy-Hash muss zur IR passen)
+    /// * `ctx` - Eingabe-Kontext, z.B. aus [`build_eval_context`]
+    ///
+    /// # Rückgabe
+    /// Proof und Auswertung (inkl. Begründungen fehlgeschlagener Regeln)
+    pub fn build_from_ir(
+        ir: &IrV1,
+        manifest: &Manifest,
+        ctx: &EvalContext,
+    ) -> Result<(Self, Evaluation), Box<dyn Error>> {
+        if ir.policy_hash_hex() != manifest.policy.hash.to_lowercase() {
+            return Err(format!(
+                "Policy-Hash der IR ({}) passt nicht zum Manifest ({})",
+                ir.policy_hash, manifest.policy.hash
+            )
+            .into());
+        }
+
+        ir.check_valid_on(chrono::Utc::now().date_naive())?;
+        let list_roots = Self::required_list_roots(manifest);
+        for kind in &list_roots {
+            let key = kind.statement_root_key();
+            let has_rule = ir.rules.iter().any(|rule| {
+                rule.op == "non_membership"
+                    && matches!(&rule.rhs, IrExpression::Var { var } if var == key)
+            });
+            if !has_rule {
+                return Err(format!(
+                    "Manifest enthält {}, aber IR {} hat keine non_membership-Regel dagegen",
+                    key, ir.policy_id
+                )
+                .into());
+            }
+        }
+
+        let evaluation = policy_v2::evaluate(ir, ctx)?;
+
+        // Inaktive Regeln liefern keinen Nachweis – ohne ihn bestünde der Proof nie
+        for kind in &list_roots {
+            let covered = evaluation.witnesses.iter().any(|w| w.list == *kind);
+            if evaluation.all_ok() && !covered {
+                return Err(format!(
+                    "Keine aktive non_membership-Regel gegen {} – der Proof wäre nicht verifizierbar",
+                    kind.statement_root_key()
+                )
+                .into());
+            }
+        }
+        let checks = evaluation
+            .outcomes
+            .iter()
+            .map(|o| ConstraintCheck {
+                name: o.rule_id.clone(),
+                ok: o.ok,
+            })
+            .collect();
+
+        let manifest_hash = Self::compute_manifest_hash(manifest)?;
+        let claims = evaluation
+            .witnesses
+            .iter()
+            .map(ListExclusionWitness::claim)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let proof = Proof {
+            version: "proof.v0".to_string(),
+            proof_type: "mock".to_string(),
+            statement: format!("policy:{}", ir.policy_id),
+            manifest_hash,
+            policy_hash: manifest.policy.hash.clone(),
+            ir_hash: Some(ir.ir_hash.clone()),
+            proof_data: ProofData {
+                checked_constraints: checks,
+                list_exclusions: claims,
+                rule_activations: if ir.adaptivity.is_some() {
+                    evaluation.activations.clone()
+                } else {
+                    Vec::new()
+                },
+            },
+            status: if evaluation.all_ok() { "ok" } else { "failed" }.to_string(),
+        };
+        Ok((proof, evaluation))
+    }
+
+    /// Ergänzt den Proof um einen Listen-Ausschluss-Check
+    ///
+    /// Erzeugt Non-Membership-Proofs für die Prüfwerte aller `records` gegen
+    /// den Listen-Baum und nimmt den Check `<statement_root>_non_membership`
+    /// in die Constraints auf. Der Proof erhält nur die Zusage des Nachweises;
+    /// der Nachweis selbst wird zurückgegeben und gehört in die private
+    /// Witness-Datei. Der Root des Baums muss dem Root im Manifest entsprechen
+    /// und `records` alle committeten Datensätze ihres Typs umfassen, sonst
+    /// schlägt die Verifikation fehl.
+    ///
+    /// # Argumente
+    /// * `kind` - Art der Liste
+    /// * `tree` - Listen-Baum
+    /// * `records` - Geprüfte Datensätze (aus [`lists::prove_records`])
+    ///
+    /// # Rückgabe
+    /// Privater Nachweis oder – als Fehler – die in der Liste gefundenen Werte
+    pub fn add_list_exclusion(
+        &mut self,
+        kind: ListKind,
+        tree: &SortedMerkleTree,
+        records: &[lists::RecordInclusionProof],
+    ) -> Result<ListExclusionWitness, Vec<String>> {
+        let result = lists::prove_exclusion(kind, tree, records).and_then(|witness| {
+            let claim = witness.claim().map_err(|e| vec![e])?;
+            self.proof_data.list_exclusions.push(claim);
+            Ok(witness)
+        });
+
+        self.proof_data.checked_constraints.push(ConstraintCheck {
+            name: format!("{}_non_membership", kind.statement_root_key()),
+            ok: result.is_ok(),
+        });
+
+        let all_ok = self.proof_data.checked_constraints.iter().all(|c| c.ok);
+        self.status = if all_ok { "ok" } else { "failed" }.to_string();
+
+        result
+    }
+
     /// Berechnet SHA3-256 Hash eines Manifests
     ///
     /// # Argumente
@@ -148,9 +314,81 @@ impl Proof {
             }
         }
 
+        // Aktive Regeln müssen geprüft, inaktive dürfen nicht geprüft sein
+        for activation in &self.proof_data.rule_activations {
+            let checked = self
+                .proof_data
+                .checked_constraints
+                .iter()
+                .any(|c| c.name == activation.rule_id);
+            if activation.active && !checked {
+                return Err(
+                    format!("Aktive Regel '{}' wurde nicht geprüft", activation.rule_id).into(),
+                );
+            }
+            if !activation.active && checked {
+                return Err(
+                    format!("Inaktive Regel '{}' wurde geprüft", activation.rule_id).into(),
+                );
+            }
+        }
+
+        // Prüfe Listen-Ausschluss-Zusagen gegen die Statement-Roots des Manifests
+        lists::verify_statement_exclusions(
+            &self.proof_data.list_exclusions,
+            manifest.sanctions_root.as_deref(),
+            manifest.jurisdiction_root.as_deref(),
+            &Self::record_commitments(manifest),
+        )?;
+
         Ok(())
     }
 
+    /// Verifiziert die Listen-Ausschlüsse vollständig gegen die privaten Nachweise
+    ///
+    /// Ergänzt [`Proof::verify`] für Prüfer, denen die Witness-Datei
+    /// vorliegt: Jede Zusage muss durch einen gültigen Nachweis gedeckt sein.
+    ///
+    /// # Argumente
+    /// * `manifest` - Das Manifest zur Verifikation
+    /// * `witnesses` - Nachweise aus der privaten Witness-Datei
+    pub fn verify_list_witnesses(
+        &self,
+        manifest: &Manifest,
+        witnesses: &[ListExclusionWitness],
+    ) -> Result<(), Box<dyn Error>> {
+        lists::verify_statement_witnesses(
+            &self.proof_data.list_exclusions,
+            witnesses,
+            manifest.sanctions_root.as_deref(),
+            manifest.jurisdiction_root.as_deref(),
+            &Self::record_commitments(manifest),
+        )?;
+        Ok(())
+    }
+
+    /// Listen, deren Statement-Root das Manifest festlegt
+    fn required_list_roots(manifest: &Manifest) -> Vec<ListKind> {
+        [
+            (ListKind::Sanctions, &manifest.sanctions_root),
+            (ListKind::Jurisdictions, &manifest.jurisdiction_root),
+        ]
+        .into_iter()
+        .filter(|(_, root)| root.is_some())
+        .map(|(kind, _)| kind)
+        .collect()
+    }
+
+    /// Datensatz-Roots und -Anzahlen eines Manifests
+    fn record_commitments(manifest: &Manifest) -> lists::RecordCommitments<'_> {
+        lists::RecordCommitments {
+            supplier_root: &manifest.supplier_root,
+            ubo_root: &manifest.ubo_root,
+            supplier_count: manifest.supplier_count,
+            ubo_count: manifest.ubo_count,
+        }
+    }
+
     /// Speichert Proof als Base64-kodierte Datei (proof.dat Format)
     ///
     /// # Argumente
@@ -187,6 +425,124 @@ impl Proof {
     }
 }
 
+/// Baut den Eingabe-Kontext für die IR-Auswertung
+///
+/// Bereitgestellte Eingaben:
+/// * `supplier_hashes`, `ubo_hashes` - Datensätze mit gesalzenem Blatt-Hash (falls CSVs übergeben)
+/// * `supplier_count`, `ubo_count` - Anzahl der Datensätze
+/// * `supplier_names`, `supplier_jurisdictions`, `supplier_tiers` - Felder der Suppliers
+/// * `ubo_names`, `ubo_birthdates`, `ubo_citizenships` - Felder der UBOs
+/// * `supplier_root`, `ubo_root`, `company_commitment_root` - Commitments
+/// * `sanctions_root`, `jurisdiction_root` - Listen-Roots aus dem Manifest
+///
+/// # Argumente
+/// * `manifest` - Das Manifest mit Commitments
+/// * `suppliers` / `ubos` - Committete Datensätze (None = nur Counts)
+/// * `nonces` - Private Nonces der Datensätze (aus `prepare`)
+/// * `supplier_count` / `ubo_count` - Counts aus den Commitments
+/// * `lists` - Listen-Bäume (Root muss zum Manifest passen)
+pub fn build_eval_context(
+    manifest: &Manifest,
+    suppliers: Option<&[Supplier]>,
+    ubos: Option<&[Ubo]>,
+    nonces: &RecordNonces,
+    supplier_count: usize,
+    ubo_count: usize,
+    lists: &[(ListKind, SortedMerkleTree)],
+) -> Result<EvalContext, Box<dyn Error>> {
+    let mut ctx = EvalContext::new();
+
+    ctx.insert("supplier_count", InputValue::Integer(supplier_count as i64))
+        .insert("ubo_count", InputValue::Integer(ubo_count as i64))
+        .insert(
+            "supplier_root",
+            InputValue::String(manifest.supplier_root.clone()),
+        )
+        .insert("ubo_root", InputValue::String(manifest.ubo_root.clone()))
+        .insert(
+            "company_commitment_root",
+            InputValue::String(manifest.company_commitment_root.clone()),
+        );
+
+    if let Some(suppliers) = suppliers {
+        let records = suppliers
+            .iter()
+            .zip(lists::prove_records(
+                RecordKind::Supplier,
+                suppliers,
+                &nonces.suppliers,
+            )?)
+            .map(|(s, inclusion)| RecordInput {
+                hash: inclusion.leaf_hash.clone(),
+                name: s.name.clone(),
+                jurisdiction: s.jurisdiction.clone(),
+                inclusion,
+            })
+            .collect();
+        ctx.insert(
+            "supplier_jurisdictions",
+            InputValue::StringList(suppliers.iter().map(|s| s.jurisdiction.clone()).collect()),
+        )
+        .insert(
+            "supplier_tiers",
+            InputValue::IntegerList(suppliers.iter().map(|s| i64::from(s.tier)).collect()),
+        )
+        .insert(
+            "supplier_names",
+            InputValue::StringList(suppliers.iter().map(|s| s.name.clone()).collect()),
+        )
+        .insert("supplier_hashes", InputValue::Records(records));
+    }
+
+    if let Some(ubos) = ubos {
+        let records = ubos
+            .iter()
+            .zip(lists::prove_records(RecordKind::Ubo, ubos, &nonces.ubos)?)
+            .map(|(u, inclusion)| RecordInput {
+                hash: inclusion.leaf_hash.clone(),
+                name: u.name.clone(),
+                jurisdiction: u.citizenship.clone(),
+                inclusion,
+            })
+            .collect();
+        ctx.insert(
+            "ubo_names",
+            InputValue::StringList(ubos.iter().map(|u| u.name.clone()).collect()),
+        )
+        .insert(
+            "ubo_birthdates",
+            InputValue::StringList(ubos.iter().map(|u| u.birthdate.clone()).collect()),
+        )
+        .insert(
+            "ubo_citizenships",
+            InputValue::StringList(ubos.iter().map(|u| u.citizenship.clone()).collect()),
+        )
+        .insert("ubo_hashes", InputValue::Records(records));
+    }
+
+    let roots = [
+        (ListKind::Sanctions, &manifest.sanctions_root),
+        (ListKind::Jurisdictions, &manifest.jurisdiction_root),
+    ];
+    for (kind, root) in roots {
+        let Some(root) = root else { continue };
+        let tree = lists
+            .iter()
+            .find(|(k, _)| *k == kind)
+            .map(|(_, tree)| tree.clone());
+        ctx.insert(
+            kind.statement_root_key(),
+            InputValue::ListRoot(ListRootInput {
+                kind,
+                root: root.clone(),
+                tree,
+            }),
+        );
+    }
+
+    Ok(ctx)
+}
+
 /// JsonPersistent Trait für Proof - ermöglicht load()/save() via Trait
 impl JsonPersistent for Proof {}
 
@@ -209,6 +565,8 @@ mod tests {
                 require_statement_roots: None,
             },
             notes: "".to_string(),
+            valid_from: None,
+            valid_until: None,
         };
 
         let manifest = Manifest {
@@ -221,6 +579,8 @@ mod tests {
                 name: "Test".to_string(),
                 version: "lksg.v1".to_string(),
                 hash: "0xpolicy".to_string(),
+                valid_from: None,
+                valid_until: None,
             },
             audit: AuditInfo {
                 tail_digest: "0xtail".to_string(),
@@ -232,6 +592,11 @@ mod tests {
             },
             signatures: Vec::new(),
             time_anchor: None,
+            commitment_scheme: None,
+            sanctions_root: None,
+            jurisdiction_root: None,
+            supplier_count: None,
+            ubo_count: None,
         };
 
         let proof = Proof::build(&policy, &manifest, 5, 2).unwrap();
@@ -253,6 +618,8 @@ mod tests {
                 name: "Test".to_string(),
                 version: "lksg.v1".to_string(),
                 hash: "0xpolicy".to_string(),
+                valid_from: None,
+                valid_until: None,
             },
             audit: AuditInfo {
                 tail_digest: "0xtail".to_string(),
@@ -264,6 +631,11 @@ mod tests {
             },
             signatures: Vec::new(),
             time_anchor: None,
+            commitment_scheme: None,
+            sanctions_root: None,
+            jurisdiction_root: None,
+            supplier_count: None,
+            ubo_count: None,
         };
 
         let manifest_hash = Proof::compute_manifest_hash(&manifest).unwrap();
@@ -274,11 +646,14 @@ mod tests {
             statement: "policy:lksg.v1".to_string(),
             manifest_hash,
             policy_hash: "0xpolicy".to_string(),
+            ir_hash: None,
             proof_data: ProofData {
                 checked_constraints: vec![ConstraintCheck {
                     name: "test".to_string(),
                     ok: true,
                 }],
+                list_exclusions: Vec::new(),
+                rule_activations: Vec::new(),
             },
             status: "ok".to_string(),
         };
@@ -294,8 +669,11 @@ mod tests {
             statement: "policy:lksg.v1".to_string(),
             manifest_hash: "0xabc".to_string(),
             policy_hash: "0xdef".to_string(),
+            ir_hash: None,
             proof_data: ProofData {
                 checked_constraints: vec![],
+                list_exclusions: Vec::new(),
+                rule_activations: Vec::new(),
             },
             status: "ok".to_string(),
         };
@@ -329,6 +707,8 @@ mod tests {
                 require_statement_roots: None,
             },
             notes: "".to_string(),
+            valid_from: None,
+            valid_until: None,
         };
 
         let manifest = create_test_manifest();
@@ -359,6 +739,8 @@ mod tests {
                 require_statement_roots: None,
             },
             notes: "".to_string(),
+            valid_from: None,
+            valid_until: None,
         };
 
         let manifest = create_test_manifest();
@@ -388,6 +770,8 @@ mod tests {
                 require_statement_roots: None,
             },
             notes: "".to_string(),
+            valid_from: None,
+            valid_until: None,
         };
 
         let manifest = create_test_manifest();
@@ -403,6 +787,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_proof_build_rejects_policy_outside_validity_window() {
+        let manifest = create_test_manifest();
+
+        let mut policy = create_test_policy();
+        policy.valid_until = Some("2020-12-31".to_string());
+        let err = Proof::build(&policy, &manifest, 5, 1).unwrap_err();
+        assert!(err.to_string().contains("expired"));
+
+        policy.valid_from = Some("2099-01-01".to_string());
+        policy.valid_until = None;
+        let err = Proof::build(&policy, &manifest, 5, 1).unwrap_err();
+        assert!(err.to_string().contains("not yet valid"));
+
+        policy.valid_from = Some("2020-01-01".to_string());
+        assert!(Proof::build(&policy, &manifest, 5, 1).is_ok());
+    }
+
     // --- verify() Error Cases ---
 
     #[test]
@@ -415,8 +817,11 @@ mod tests {
             statement: "policy:lksg.v1".to_string(),
             manifest_hash: "0xWRONG".to_string(), // Wrong hash
             policy_hash: "0xpolicy".to_string(),
+            ir_hash: None,
             proof_data: ProofData {
                 checked_constraints: vec![],
+                list_exclusions: Vec::new(),
+                rule_activations: Vec::new(),
             },
             status: "ok".to_string(),
         };
@@ -437,8 +842,11 @@ mod tests {
             statement: "policy:lksg.v1".to_string(),
             manifest_hash,
             policy_hash: "0xWRONG".to_string(), // Wrong hash
+            ir_hash: None,
             proof_data: ProofData {
                 checked_constraints: vec![],
+                list_exclusions: Vec::new(),
+                rule_activations: Vec::new(),
             },
             status: "ok".to_string(),
         };
@@ -459,8 +867,11 @@ mod tests {
             statement: "policy:lksg.v1".to_string(),
             manifest_hash,
             policy_hash: "0xpolicy".to_string(),
+            ir_hash: None,
             proof_data: ProofData {
                 checked_constraints: vec![],
+                list_exclusions: Vec::new(),
+                rule_activations: Vec::new(),
             },
             status: "failed".to_string(), // Not OK
         };
@@ -484,11 +895,14 @@ mod tests {
             statement: "policy:lksg.v1".to_string(),
             manifest_hash,
             policy_hash: "0xpolicy".to_string(),
+            ir_hash: None,
             proof_data: ProofData {
                 checked_constraints: vec![ConstraintCheck {
                     name: "test_check".to_string(),
                     ok: false, // Failed constraint
                 }],
+                list_exclusions: Vec::new(),
+                rule_activations: Vec::new(),
             },
             status: "ok".to_string(),
         };
@@ -508,11 +922,14 @@ mod tests {
             statement: "policy:lksg.v1".to_string(),
             manifest_hash: "0xabc123".to_string(),
             policy_hash: "0xdef456".to_string(),
+            ir_hash: None,
             proof_data: ProofData {
                 checked_constraints: vec![ConstraintCheck {
                     name: "test".to_string(),
                     ok: true,
                 }],
+                list_exclusions: Vec::new(),
+                rule_activations: Vec::new(),
             },
             status: "ok".to_string(),
         };
@@ -549,6 +966,196 @@ mod tests {
         assert_eq!(hash1.len(), 66); // "0x" + 64 hex chars
     }
 
+    // --- add_list_exclusion() ---
+
+    fn create_test_policy() -> Policy {
+        Policy {
+            version: "lksg.v1".to_string(),
+            name: "Test".to_string(),
+            created_at: "2025-10-25T09:00:00Z".to_string(),
+            constraints: PolicyConstraints {
+                require_at_least_one_ubo: false,
+                supplier_count_max: 10,
+                ubo_count_min: None,
+                require_statement_roots: None,
+            },
+            notes: "".to_string(),
+            valid_from: None,
+            valid_until: None,
+        }
+    }
+
+    fn create_test_suppliers(jurisdictions: &[&str]) -> Vec<Supplier> {
+        jurisdictions
+            .iter()
+            .enumerate()
+            .map(|(i, jurisdiction)| Supplier {
+                name: format!("Supplier {}", i),
+                jurisdiction: jurisdiction.to_string(),
+                tier: 1,
+            })
+            .collect()
+    }
+
+    fn create_committed_manifest(suppliers: &[Supplier], nonces: &RecordNonces) -> Manifest {
+        let mut manifest = create_test_manifest();
+        manifest.supplier_root =
+            crate::commitment::compute_supplier_root(suppliers, &nonces.suppliers).unwrap();
+        manifest.supplier_count = Some(suppliers.len());
+        manifest
+    }
+
+    #[test]
+    fn test_list_exclusion_verifies_against_manifest_root() {
+        let tree = ListKind::Jurisdictions.build_tree(["IR", "KP", "RU"]);
+        let suppliers = create_test_suppliers(&["DE", "SE"]);
+        let nonces = RecordNonces::generate(suppliers.len(), 0);
+        let mut manifest = create_committed_manifest(&suppliers, &nonces);
+        manifest.jurisdiction_root = Some(tree.root_hex());
+        let records =
+            lists::prove_records(RecordKind::Supplier, &suppliers, &nonces.suppliers).unwrap();
+
+        let mut proof = Proof::build(&create_test_policy(), &manifest, 2, 0).unwrap();
+        let witness = proof
+            .add_list_exclusion(ListKind::Jurisdictions, &tree, &records)
+            .unwrap();
+
+        assert_eq!(proof.status, "ok");
+        assert_eq!(proof.proof_data.list_exclusions.len(), 1);
+        assert!(proof.verify(&manifest).is_ok());
+        assert!(proof
+            .verify_list_witnesses(&manifest, std::slice::from_ref(&witness))
+            .is_ok());
+        assert!(proof.verify_list_witnesses(&manifest, &[]).is_err());
+
+        // Der Proof selbst enthält keine Datensatz-Werte
+        let json = serde_json::to_string(&proof).unwrap();
+        assert!(
+            !json.contains("Supplier 0") && !json.contains("\"SE\""),
+            "{}",
+            json
+        );
+
+        // Proof ohne Nachweis genügt einem Manifest mit jurisdiction_root nicht
+        let bare = Proof::build(&create_test_policy(), &manifest, 2, 0).unwrap();
+        assert!(bare.verify(&manifest).is_err());
+    }
+
+    #[test]
+    fn test_list_exclusion_hit_fails_proof() {
+        let tree = ListKind::Jurisdictions.build_tree(["IR", "KP", "RU"]);
+        let suppliers = create_test_suppliers(&["DE", "ru"]);
+        let nonces = RecordNonces::generate(suppliers.len(), 0);
+        let records =
+            lists::prove_records(RecordKind::Supplier, &suppliers, &nonces.suppliers).unwrap();
+        let mut proof = Proof::build(&create_test_policy(), &create_test_manifest(), 2, 0).unwrap();
+
+        let hits = proof
+            .add_list_exclusion(ListKind::Jurisdictions, &tree, &records)
+            .unwrap_err();
+
+        assert_eq!(hits, ["RU"]);
+        assert_eq!(proof.status, "failed");
+        assert!(proof.proof_data.list_exclusions.is_empty());
+        let check = proof.proof_data.checked_constraints.last().unwrap();
+        assert_eq!(check.name, "jurisdiction_root_non_membership");
+        assert!(!check.ok);
+    }
+
+    #[test]
+    fn test_build_from_ir_evaluates_rules() {
+        let tree = ListKind::Jurisdictions.build_tree(["IR", "KP"]);
+        let suppliers = vec![
+            Supplier {
+                name: "Acme".to_string(),
+                jurisdiction: "DE".to_string(),
+                tier: 1,
+            },
+            Supplier {
+                name: "Nordic".to_string(),
+                jurisdiction: "SE".to_string(),
+                tier: 1,
+            },
+        ];
+        let nonces = RecordNonces::generate(suppliers.len(), 0);
+        let mut manifest = create_committed_manifest(&suppliers, &nonces);
+        manifest.policy.hash = "0xabc".to_string();
+        manifest.jurisdiction_root = Some(tree.root_hex());
+
+        let ir: IrV1 = serde_json::from_value(serde_json::json!({
+            "ir_version": "1.0",
+            "policy_id": "ir.test",
+            "policy_hash": "sha3-256:abc",
+            "rules": [
+                {"id": "min_suppliers", "op": "range_min", "lhs": {"var": "supplier_count"}, "rhs": 2},
+                {"id": "no_high_risk", "op": "non_membership",
+                 "lhs": {"var": "supplier_hashes"}, "rhs": {"var": "jurisdiction_root"}}
+            ],
+            "ir_hash": "sha3-256:ir"
+        }))
+        .unwrap();
+
+        let ctx = build_eval_context(
+            &manifest,
+            Some(&suppliers),
+            None,
+            &nonces,
+            2,
+            0,
+            &[(ListKind::Jurisdictions, tree)],
+        )
+        .unwrap();
+
+        let (proof, evaluation) = Proof::build_from_ir(&ir, &manifest, &ctx).unwrap();
+        assert!(evaluation.all_ok());
+        assert_eq!(proof.status, "ok");
+        assert_eq!(proof.statement, "policy:ir.test");
+        assert_eq!(proof.ir_hash.as_deref(), Some("sha3-256:ir"));
+        assert_eq!(proof.proof_data.checked_constraints.len(), 2);
+        assert!(proof.verify(&manifest).is_ok());
+        assert_eq!(proof.proof_data.list_exclusions.len(), 1);
+        assert!(proof
+            .verify_list_witnesses(&manifest, &evaluation.witnesses)
+            .is_ok());
+
+        // IR einer anderen Policy passt nicht zum Manifest
+        manifest.policy.hash = "0xother".to_string();
+        assert!(Proof::build_from_ir(&ir, &manifest, &ctx).is_err());
+    }
+
+    #[test]
+    fn test_build_from_ir_requires_rule_for_list_root() {
+        let tree = ListKind::Jurisdictions.build_tree(["IR", "KP"]);
+        let mut manifest = create_test_manifest();
+        manifest.policy.hash = "0xabc".to_string();
+        manifest.jurisdiction_root = Some(tree.root_hex());
+
+        // IR ohne non_membership-Regel gegen den jurisdiction_root des Manifests
+        let ir: IrV1 = serde_json::from_value(serde_json::json!({
+            "ir_version": "1.0",
+            "policy_id": "ir.test",
+            "policy_hash": "sha3-256:abc",
+            "rules": [
+                {"id": "min_suppliers", "op": "range_min", "lhs": {"var": "supplier_count"}, "rhs": 0}
+            ],
+            "ir_hash": "sha3-256:ir"
+        }))
+        .unwrap();
+        let ctx = build_eval_context(
+            &manifest,
+            None,
+            None,
+            &RecordNonces::default(),
+            0,
+            0,
+            &[(ListKind::Jurisdictions, tree)],
+        )
+        .unwrap();
+
+        let err = Proof::build_from_ir(&ir, &manifest, &ctx).unwrap_err();
+        assert!(err.to_string().contains("jurisdiction_root"), "{}", err);
+    }
+
     // --- Helper ---
 
     fn create_test_manifest() -> Manifest {
@@ -562,6 +1169,8 @@ mod tests {
                 name: "Test".to_string(),
                 version: "lksg.v1".to_string(),
                 hash: "0xpolicy".to_string(),
+                valid_from: None,
+                valid_until: None,
             },
             audit: AuditInfo {
                 tail_digest: "0xtail".to_string(),
@@ -573,6 +1182,11 @@ mod tests {
             },
             signatures: Vec::new(),
             time_anchor: None,
+            commitment_scheme: None,
+            sanctions_root: None,
+            jurisdiction_root: None,
+            supplier_count: None,
+            ubo_count: None,
         }
     }
 }
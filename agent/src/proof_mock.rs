@@ -149,6 +149,8 @@ mod tests {
                 ubo_count_min: None,
                 require_statement_roots: None,
             },
+            valid_from: None,
+            valid_until: None,
             notes: "".to_string(),
         };
 
@@ -161,6 +163,8 @@ mod tests {
             policy: PolicyInfo {
                 name: "Test".to_string(),
                 version: "lksg.v1".to_string(),
+                valid_from: None,
+                valid_until: None,
                 hash: "0xpolicy".to_string(),
             },
             audit: AuditInfo {
@@ -173,6 +177,11 @@ mod tests {
             },
             signatures: Vec::new(),
             time_anchor: None,
+            commitment_scheme: None,
+            sanctions_root: None,
+            jurisdiction_root: None,
+            supplier_count: None,
+            ubo_count: None,
         };
 
         let proof = MockProof::generate(&policy, &manifest, 5, 2).unwrap();
@@ -193,6 +202,8 @@ mod tests {
                 ubo_count_min: None,
                 require_statement_roots: None,
             },
+            valid_from: None,
+            valid_until: None,
             notes: "".to_string(),
         };
 
@@ -205,6 +216,8 @@ mod tests {
             policy: PolicyInfo {
                 name: "Test".to_string(),
                 version: "lksg.v1".to_string(),
+                valid_from: None,
+                valid_until: None,
                 hash: "0xpolicy".to_string(),
             },
             audit: AuditInfo {
@@ -217,6 +230,11 @@ mod tests {
             },
             signatures: Vec::new(),
             time_anchor: None,
+            commitment_scheme: None,
+            sanctions_root: None,
+            jurisdiction_root: None,
+            supplier_count: None,
+            ubo_count: None,
         };
 
         // 10 Suppliers, aber max ist 5
@@ -235,6 +253,8 @@ mod tests {
             policy: PolicyInfo {
                 name: "Test".to_string(),
                 version: "lksg.v1".to_string(),
+                valid_from: None,
+                valid_until: None,
                 hash: "0xpolicy".to_string(),
             },
             audit: AuditInfo {
@@ -247,6 +267,11 @@ mod tests {
             },
             signatures: Vec::new(),
             time_anchor: None,
+            commitment_scheme: None,
+            sanctions_root: None,
+            jurisdiction_root: None,
+            supplier_count: None,
+            ubo_count: None,
         };
 
         let proof = MockProof {
@@ -71,7 +71,13 @@ impl SoftwareProvider {
 
     /// Lädt KeyMetadata für einen Schlüssel
     fn load_metadata(&self, key_name: &str) -> Result<KeyMetadata, KeyError> {
-        let metadata_path = self.keys_dir.join(format!("{}.v1.json", key_name));
+        // Bevorzugt <name>.v1.json, Fallback auf <name>.json (Layout von `keys keygen`)
+        let versioned = self.keys_dir.join(format!("{}.v1.json", key_name));
+        let metadata_path = if versioned.exists() {
+            versioned
+        } else {
+            self.keys_dir.join(format!("{}.json", key_name))
+        };
 
         KeyMetadata::load(&metadata_path)
             .map_err(|e| KeyError::NotFound(format!("Key metadata not found: {}", e)))
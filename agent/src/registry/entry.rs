@@ -5,6 +5,7 @@
 //! - Signature fields (v0.8)
 //! - BLOB fields (v0.9)
 //! - Key management fields (v0.10)
+//! - Policy reference (for registry queries)
 
 use serde::{Deserialize, Serialize};
 
@@ -59,11 +60,21 @@ pub struct RegistryEntry {
     /// Signature scheme (e.g., "ed25519")
     #[serde(skip_serializing_if = "Option::is_none")]
     pub signature_scheme: Option<String>,
+
+    // Policy Reference
+    /// Policy identifier (policy hash from the manifest, not part of the signed core)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub policy_id: Option<String>,
 }
 
 impl RegistryEntry {
     /// Creates a new entry with required fields, all optional fields set to None
-    pub fn new(id: String, manifest_hash: String, proof_hash: String, registered_at: String) -> Self {
+    pub fn new(
+        id: String,
+        manifest_hash: String,
+        proof_hash: String,
+        registered_at: String,
+    ) -> Self {
         Self {
             id,
             manifest_hash,
@@ -82,7 +93,21 @@ impl RegistryEntry {
             verifier_version: None,
             kid: None,
             signature_scheme: None,
+            policy_id: None,
+        }
+    }
+
+    /// Derives a globally unique entry ID from the registered content
+    ///
+    /// BLAKE3 over manifest hash, proof hash and registration time: IDs of
+    /// independently built registries do not collide when they are merged.
+    pub fn derive_id(manifest_hash: &str, proof_hash: &str, registered_at: &str) -> String {
+        let mut hasher = blake3::Hasher::new();
+        for part in [manifest_hash, proof_hash, registered_at] {
+            hasher.update(part.as_bytes());
+            hasher.update(b"\n");
         }
+        format!("proof_{}", &hasher.finalize().to_hex()[..16])
     }
 
     /// Builder method to set timestamp_file
@@ -0,0 +1,605 @@
+//! Registry Exchange - Signed snapshots for merging registries across teams
+//!
+//! `RegistrySnapshot` (`registry.snapshot.v1`) wraps a complete registry
+//! (entries, lifecycle records, transparency log) together with the BLAKE3
+//! hash of its JSON and an Ed25519 signature of the exporting party.
+//!
+//! Importing a snapshot into a local store of either backend is planned first
+//! (`ImportPlan::new`) and only applied if the plan is clean:
+//! - every entry signature is verified; unsigned entries are reported
+//! - entries are keyed on their manifest/proof hash pair: an identical
+//!   registration (same ID, time and signer) is skipped as duplicate, the same
+//!   pair with a different signer or content is a conflict
+//! - entry IDs are derived from the registered content
+//!   (`RegistryEntry::derive_id`) and do not collide across registries; an ID
+//!   taken by a different pair (sequential IDs of older registries) is a
+//!   conflict as well, since the ID is part of the signed entry core
+//! - lifecycle records are carried over for entries present after the merge
+//!
+//! Imported entries are appended to the local transparency log like any other
+//! addition; the snapshot's own log is not replayed.
+
+use base64::{engine::general_purpose, Engine};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+use super::entry::RegistryEntry;
+use super::lifecycle::LifecycleRecord;
+use super::signing::verify_entry_signature;
+use super::store::{Registry, RegistryStore};
+use crate::keys;
+
+/// Version der Snapshot-Datei
+pub const SNAPSHOT_VERSION: &str = "registry.snapshot.v1";
+
+/// Signierter, eigenständiger Export einer Registry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegistrySnapshot {
+    pub version: String,
+    /// Zeitpunkt des Exports (RFC3339)
+    pub exported_at: String,
+    /// Herkunft (z.B. Team oder Standort)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    pub entry_count: usize,
+    /// BLAKE3 über das JSON der Registry (0x-präfixiert)
+    pub registry_hash: String,
+    pub registry: Registry,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub public_key: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kid: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature_scheme: Option<String>,
+}
+
+fn registry_hash(registry: &Registry) -> Result<String, Box<dyn Error>> {
+    let hash = blake3::hash(&serde_json::to_vec(registry)?);
+    Ok(format!("0x{}", hex::encode(hash.as_bytes())))
+}
+
+impl RegistrySnapshot {
+    /// Erstellt einen (unsignierten) Snapshot einer Registry
+    pub fn new(registry: Registry, source: Option<String>) -> Result<Self, Box<dyn Error>> {
+        Ok(Self {
+            version: SNAPSHOT_VERSION.to_string(),
+            exported_at: chrono::Utc::now().to_rfc3339(),
+            source,
+            entry_count: registry.entries.len(),
+            registry_hash: registry_hash(&registry)?,
+            registry,
+            signature: None,
+            public_key: None,
+            kid: None,
+            signature_scheme: None,
+        })
+    }
+
+    /// Lädt einen Snapshot aus einer JSON-Datei
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, Box<dyn Error>> {
+        let snapshot: RegistrySnapshot = serde_json::from_str(&fs::read_to_string(path)?)?;
+        if snapshot.version != SNAPSHOT_VERSION {
+            return Err(format!("Unsupported snapshot version: {}", snapshot.version).into());
+        }
+        Ok(snapshot)
+    }
+
+    /// Speichert den Snapshot als JSON-Datei
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), Box<dyn Error>> {
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// BLAKE3-Hash des Snapshot-Cores (ohne Registry-Inhalt und Signatur-Felder)
+    fn core_hash(&self) -> Result<Vec<u8>, Box<dyn Error>> {
+        #[derive(Serialize)]
+        struct SnapshotCore<'a> {
+            version: &'a str,
+            exported_at: &'a str,
+            source: &'a Option<String>,
+            entry_count: usize,
+            registry_hash: &'a str,
+        }
+
+        let core = SnapshotCore {
+            version: &self.version,
+            exported_at: &self.exported_at,
+            source: &self.source,
+            entry_count: self.entry_count,
+            registry_hash: &self.registry_hash,
+        };
+        Ok(blake3::hash(&serde_json::to_vec(&core)?)
+            .as_bytes()
+            .to_vec())
+    }
+
+    /// Signiert den Snapshot mit Ed25519 (wie `sign_entry`)
+    pub fn sign(&mut self, signing_key: &SigningKey) -> Result<(), Box<dyn Error>> {
+        let signature = signing_key.sign(&self.core_hash()?);
+        let pubkey_b64 = general_purpose::STANDARD.encode(signing_key.verifying_key().to_bytes());
+
+        self.signature = Some(general_purpose::STANDARD.encode(signature.to_bytes()));
+        self.kid = Some(keys::derive_kid(&pubkey_b64)?);
+        self.public_key = Some(pubkey_b64);
+        self.signature_scheme = Some("ed25519".to_string());
+        Ok(())
+    }
+
+    /// Prüft Registry-Hash, Eintragsanzahl und Signatur; liefert die kid des Signers
+    ///
+    /// Unsignierte Snapshots werden abgelehnt.
+    pub fn verify(&self) -> Result<String, Box<dyn Error>> {
+        let actual = registry_hash(&self.registry)?;
+        if actual != self.registry_hash {
+            return Err(format!(
+                "Snapshot registry hash mismatch: expected {}, got {}",
+                self.registry_hash, actual
+            )
+            .into());
+        }
+        if self.entry_count != self.registry.entries.len() {
+            return Err(format!(
+                "Snapshot entry count mismatch: expected {}, got {}",
+                self.entry_count,
+                self.registry.entries.len()
+            )
+            .into());
+        }
+
+        let (sig_b64, pubkey_b64) = match (&self.signature, &self.public_key) {
+            (Some(s), Some(p)) => (s, p),
+            _ => return Err("Snapshot is not signed".into()),
+        };
+
+        let sig_bytes = general_purpose::STANDARD.decode(sig_b64)?;
+        let pubkey_bytes = general_purpose::STANDARD.decode(pubkey_b64)?;
+        let signature = Signature::from_bytes(
+            &sig_bytes
+                .try_into()
+                .map_err(|_| "Invalid signature length")?,
+        );
+        let verifying_key = VerifyingKey::from_bytes(
+            &pubkey_bytes
+                .try_into()
+                .map_err(|_| "Invalid public key length")?,
+        )?;
+        verifying_key.verify(&self.core_hash()?, &signature)?;
+
+        let kid = keys::derive_kid(pubkey_b64)?;
+        match &self.kid {
+            Some(claimed) if *claimed != kid => {
+                Err(format!("kid {} does not match public key (kid {})", claimed, kid).into())
+            }
+            _ => Ok(kid),
+        }
+    }
+}
+
+/// Art eines Import-Konflikts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictKind {
+    /// Manifest/Proof-Paar lokal mit anderem Signer oder Inhalt registriert
+    Hashes,
+    /// ID lokal mit anderem Manifest/Proof-Paar belegt
+    Id,
+}
+
+/// Importierter Eintrag, der einem lokalen Eintrag widerspricht
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ImportConflict {
+    pub kind: ConflictKind,
+    /// ID des importierten Eintrags
+    pub id: String,
+    /// ID des kollidierenden lokalen Eintrags
+    pub local_id: String,
+    pub local_manifest_hash: String,
+    pub local_proof_hash: String,
+    pub imported_manifest_hash: String,
+    pub imported_proof_hash: String,
+}
+
+impl ImportConflict {
+    fn new(kind: ConflictKind, local: &RegistryEntry, imported: &RegistryEntry) -> Self {
+        ImportConflict {
+            kind,
+            id: imported.id.clone(),
+            local_id: local.id.clone(),
+            local_manifest_hash: local.manifest_hash.clone(),
+            local_proof_hash: local.proof_hash.clone(),
+            imported_manifest_hash: imported.manifest_hash.clone(),
+            imported_proof_hash: imported.proof_hash.clone(),
+        }
+    }
+}
+
+/// Manifest/Proof-Paar eines Eintrags (Schlüssel für Duplikate und Konflikte)
+fn hash_pair(entry: &RegistryEntry) -> (String, String) {
+    (entry.manifest_hash.clone(), entry.proof_hash.clone())
+}
+
+/// Ob zwei Einträge dieselbe Registrierung sind (signierter Kern und Signer)
+fn same_registration(a: &RegistryEntry, b: &RegistryEntry) -> bool {
+    a.id == b.id
+        && a.registered_at == b.registered_at
+        && a.timestamp_file == b.timestamp_file
+        && a.public_key == b.public_key
+}
+
+/// Eintrag oder Lifecycle-Record mit ungültiger Signatur
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct InvalidSignature {
+    pub id: String,
+    pub error: String,
+}
+
+/// Ergebnis der Import-Planung: was übernommen, übersprungen oder abgelehnt wird
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ImportPlan {
+    /// Neue Einträge (in Snapshot-Reihenfolge)
+    #[serde(skip)]
+    pub entries: Vec<RegistryEntry>,
+    /// Neue Lifecycle-Records
+    #[serde(skip)]
+    pub lifecycle: Vec<LifecycleRecord>,
+    /// IDs der neuen Einträge
+    pub imported: Vec<String>,
+    /// Importierte IDs, die lokal bereits identisch registriert sind
+    pub duplicates: Vec<String>,
+    /// Neue Einträge ohne Signatur
+    pub unsigned: Vec<String>,
+    pub conflicts: Vec<ImportConflict>,
+    pub invalid: Vec<InvalidSignature>,
+}
+
+impl ImportPlan {
+    /// Plant den Import von `incoming` in die lokale Registry `local`
+    pub fn new(local: &Registry, incoming: &Registry) -> Self {
+        let mut plan = ImportPlan::default();
+        let mut by_hashes: HashMap<(String, String), RegistryEntry> = HashMap::new();
+        let mut by_id: HashMap<String, RegistryEntry> = HashMap::new();
+        for entry in &local.entries {
+            by_hashes
+                .entry(hash_pair(entry))
+                .or_insert_with(|| entry.clone());
+            by_id
+                .entry(entry.id.clone())
+                .or_insert_with(|| entry.clone());
+        }
+
+        for entry in &incoming.entries {
+            let signed = match verify_entry_signature(entry) {
+                Ok(signed) => signed,
+                Err(e) => {
+                    plan.invalid.push(InvalidSignature {
+                        id: entry.id.clone(),
+                        error: e.to_string(),
+                    });
+                    continue;
+                }
+            };
+
+            let hashes = hash_pair(entry);
+            if let Some(existing) = by_hashes.get(&hashes) {
+                if same_registration(existing, entry) {
+                    plan.duplicates.push(entry.id.clone());
+                } else {
+                    plan.conflicts
+                        .push(ImportConflict::new(ConflictKind::Hashes, existing, entry));
+                }
+                continue;
+            }
+            if let Some(existing) = by_id.get(&entry.id) {
+                plan.conflicts
+                    .push(ImportConflict::new(ConflictKind::Id, existing, entry));
+                continue;
+            }
+
+            by_hashes.insert(hashes, entry.clone());
+            by_id.insert(entry.id.clone(), entry.clone());
+            if !signed {
+                plan.unsigned.push(entry.id.clone());
+            }
+            plan.imported.push(entry.id.clone());
+            plan.entries.push(entry.clone());
+        }
+
+        for record in &incoming.lifecycle {
+            if local.lifecycle.contains(record) || plan.lifecycle.contains(record) {
+                continue;
+            }
+            let applies = local
+                .entries
+                .iter()
+                .chain(plan.entries.iter())
+                .any(|e| record.applies_to(e));
+            if !applies {
+                continue;
+            }
+            if let Err(e) = record.verify_signature() {
+                plan.invalid.push(InvalidSignature {
+                    id: format!("{} ({})", record.entry_id, record.action),
+                    error: e.to_string(),
+                });
+                continue;
+            }
+            plan.lifecycle.push(record.clone());
+        }
+
+        plan
+    }
+
+    /// Ob der Import ohne Konflikte und ungültige Signaturen möglich ist
+    #[allow(dead_code)] // Public API - pre-check before apply
+    pub fn is_clean(&self) -> bool {
+        self.conflicts.is_empty() && self.invalid.is_empty()
+    }
+
+    /// Schreibt neue Einträge und Lifecycle-Records in den Store
+    ///
+    /// Konflikte werden übersprungen; ungültige Signaturen brechen ab.
+    pub fn apply(&self, store: &dyn RegistryStore) -> Result<(), Box<dyn Error>> {
+        if !self.invalid.is_empty() {
+            return Err(format!(
+                "Snapshot contains {} invalid signatures: {}",
+                self.invalid.len(),
+                self.invalid
+                    .iter()
+                    .map(|i| i.id.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+            .into());
+        }
+        for entry in &self.entries {
+            store.add_entry(entry.clone())?;
+        }
+        for record in &self.lifecycle {
+            store.add_lifecycle_record(record.clone())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::lifecycle::{LifecycleAction, RevocationReason};
+    use crate::registry::signing::sign_entry;
+    use crate::registry::store::{open_store, RegistryBackend};
+
+    fn signed_entry(id: &str, n: u8, key: &SigningKey) -> RegistryEntry {
+        let mut entry = RegistryEntry::new(
+            id.to_string(),
+            format!("0xmanifest{}", n),
+            format!("0xproof{}", n),
+            "2026-01-01T00:00:00Z".to_string(),
+        );
+        sign_entry(&mut entry, key).unwrap();
+        entry
+    }
+
+    fn registry_of(entries: Vec<RegistryEntry>) -> Registry {
+        let mut registry = Registry::new();
+        for entry in entries {
+            registry.append_entry(entry);
+        }
+        registry
+    }
+
+    #[test]
+    fn test_snapshot_sign_and_verify() {
+        let key = SigningKey::from_bytes(&[7u8; 32]);
+        let registry = registry_of(vec![signed_entry("proof_001", 1, &key)]);
+
+        let mut snapshot = RegistrySnapshot::new(registry, Some("desk-a".to_string())).unwrap();
+        assert!(snapshot.verify().is_err(), "unsigned snapshot accepted");
+        snapshot.sign(&key).unwrap();
+        let kid = snapshot.verify().unwrap();
+        assert_eq!(snapshot.kid.as_deref(), Some(kid.as_str()));
+
+        // Roundtrip über die Datei
+        let path = std::env::temp_dir().join("test_registry_snapshot.json");
+        snapshot.save(&path).unwrap();
+        assert!(RegistrySnapshot::load(&path).unwrap().verify().is_ok());
+        std::fs::remove_file(&path).ok();
+
+        // Geänderter Inhalt, Metadaten oder fremder Key
+        let mut tampered = snapshot.clone();
+        tampered.registry.entries[0].proof_hash = "0xforged".to_string();
+        assert!(tampered.verify().is_err());
+
+        let mut tampered = snapshot.clone();
+        tampered.source = Some("desk-b".to_string());
+        assert!(tampered.verify().is_err());
+
+        let mut tampered = snapshot.clone();
+        tampered.public_key = Some(
+            general_purpose::STANDARD.encode(
+                SigningKey::from_bytes(&[8u8; 32])
+                    .verifying_key()
+                    .to_bytes(),
+            ),
+        );
+        assert!(tampered.verify().is_err());
+    }
+
+    #[test]
+    fn test_import_plan() {
+        let key = SigningKey::from_bytes(&[7u8; 32]);
+        let local = registry_of(vec![
+            signed_entry("proof_001", 1, &key),
+            signed_entry("proof_002", 2, &key),
+        ]);
+
+        let mut forged = signed_entry("desk_b_004", 4, &key);
+        forged.manifest_hash = "0xforged".to_string();
+        let mut unsigned = signed_entry("desk_b_005", 5, &key);
+        unsigned.signature = None;
+        unsigned.public_key = None;
+
+        let mut incoming = registry_of(vec![
+            // Gleiche Hashes unter anderer ID
+            signed_entry("desk_b_001", 1, &key),
+            // Belegte ID, andere Hashes
+            signed_entry("proof_002", 3, &key),
+            forged,
+            unsigned,
+            signed_entry("desk_b_006", 6, &key),
+            // Gleiche Hashes doppelt im Snapshot
+            signed_entry("desk_b_007", 6, &key),
+            // Identische Registrierung
+            local.entries[1].clone(),
+        ]);
+        let mut revoke = LifecycleRecord::new(
+            &incoming.entries[4],
+            LifecycleAction::Revoke,
+            RevocationReason::IssuedInError,
+            None,
+            "2026-02-01T00:00:00Z".to_string(),
+        )
+        .unwrap();
+        revoke.sign(&key).unwrap();
+        incoming.lifecycle.push(revoke.clone());
+        let mut orphan = LifecycleRecord::new(
+            &incoming.entries[1],
+            LifecycleAction::Revoke,
+            RevocationReason::Withdrawn,
+            None,
+            "2026-02-01T00:00:00Z".to_string(),
+        )
+        .unwrap();
+        orphan.sign(&key).unwrap();
+        incoming.lifecycle.push(orphan);
+
+        let plan = ImportPlan::new(&local, &incoming);
+        assert_eq!(plan.imported, vec!["desk_b_005", "desk_b_006"]);
+        assert_eq!(plan.duplicates, vec!["proof_002"]);
+        assert_eq!(plan.unsigned, vec!["desk_b_005"]);
+        let conflicts: Vec<_> = plan
+            .conflicts
+            .iter()
+            .map(|c| (c.kind, c.id.as_str(), c.local_id.as_str()))
+            .collect();
+        assert_eq!(
+            conflicts,
+            vec![
+                (ConflictKind::Hashes, "desk_b_001", "proof_001"),
+                (ConflictKind::Id, "proof_002", "proof_002"),
+                (ConflictKind::Hashes, "desk_b_007", "desk_b_006"),
+            ]
+        );
+        assert_eq!(plan.conflicts[1].local_proof_hash, "0xproof2");
+        assert_eq!(plan.conflicts[1].imported_proof_hash, "0xproof3");
+        assert_eq!(plan.invalid.len(), 1);
+        assert_eq!(plan.invalid[0].id, "desk_b_004");
+        assert_eq!(plan.lifecycle, vec![revoke]);
+        assert!(!plan.is_clean());
+
+        // Erneuter Import nach dem Merge: nur Duplikate
+        let mut merged = local.clone();
+        for entry in &plan.entries {
+            merged.append_entry(entry.clone());
+        }
+        merged.lifecycle.extend(plan.lifecycle.iter().cloned());
+        let again = ImportPlan::new(&merged, &incoming);
+        assert!(again.imported.is_empty());
+        assert!(again.lifecycle.is_empty());
+    }
+
+    #[test]
+    fn test_merge_independent_registries() {
+        let key_a = SigningKey::from_bytes(&[7u8; 32]);
+        let key_b = SigningKey::from_bytes(&[9u8; 32]);
+        let register = |n: u8, registered_at: &str, key: &SigningKey| {
+            let manifest_hash = format!("0xmanifest{}", n);
+            let proof_hash = format!("0xproof{}", n);
+            let mut entry = RegistryEntry::new(
+                RegistryEntry::derive_id(&manifest_hash, &proof_hash, registered_at),
+                manifest_hash,
+                proof_hash,
+                registered_at.to_string(),
+            );
+            sign_entry(&mut entry, key).unwrap();
+            entry
+        };
+
+        // Zwei Desks registrieren unabhängig voneinander; Proof 1 an beiden
+        let desk_a = registry_of(vec![
+            register(1, "2026-01-01T00:00:00Z", &key_a),
+            register(2, "2026-01-02T00:00:00Z", &key_a),
+        ]);
+        let desk_b = registry_of(vec![
+            register(3, "2026-01-01T00:00:00Z", &key_b),
+            register(1, "2026-03-01T00:00:00Z", &key_b),
+        ]);
+        assert_ne!(desk_a.entries[0].id, desk_b.entries[0].id);
+
+        let plan = ImportPlan::new(&desk_a, &desk_b);
+        assert_eq!(plan.imported, vec![desk_b.entries[0].id.clone()]);
+        assert!(plan.duplicates.is_empty());
+        assert_eq!(plan.conflicts.len(), 1);
+        assert_eq!(plan.conflicts[0].kind, ConflictKind::Hashes);
+        assert_eq!(plan.conflicts[0].id, desk_b.entries[1].id);
+        assert_eq!(plan.conflicts[0].local_id, desk_a.entries[0].id);
+
+        // Rückrichtung: nur A's eigener Eintrag ist neu; danach reine Duplikate
+        let mut merged = desk_b.clone();
+        let reverse = ImportPlan::new(&merged, &desk_a);
+        assert_eq!(reverse.imported, vec![desk_a.entries[1].id.clone()]);
+        for entry in &reverse.entries {
+            merged.append_entry(entry.clone());
+        }
+        let again = ImportPlan::new(&merged, &desk_a);
+        assert!(again.imported.is_empty());
+        assert_eq!(again.duplicates, vec![desk_a.entries[1].id.clone()]);
+        assert_eq!(again.conflicts.len(), 1);
+    }
+
+    #[test]
+    fn test_import_into_both_backends() {
+        let key = SigningKey::from_bytes(&[7u8; 32]);
+        let dir = std::env::temp_dir().join("test_registry_import");
+        std::fs::remove_dir_all(&dir).ok();
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let incoming = registry_of(vec![
+            signed_entry("desk_b_001", 1, &key),
+            signed_entry("desk_b_002", 2, &key),
+        ]);
+
+        for (backend, file) in [
+            (RegistryBackend::Json, "registry.json"),
+            (RegistryBackend::Sqlite, "registry.sqlite"),
+        ] {
+            let store = open_store(backend, &dir.join(file)).unwrap();
+            store.add_entry(incoming.entries[0].clone()).unwrap();
+
+            let plan = ImportPlan::new(&store.load().unwrap(), &incoming);
+            assert!(plan.is_clean());
+            assert_eq!(plan.duplicates, vec!["desk_b_001"]);
+            plan.apply(store.as_ref()).unwrap();
+
+            let ids: Vec<String> = store.list().unwrap().into_iter().map(|e| e.id).collect();
+            assert_eq!(ids, vec!["desk_b_001", "desk_b_002"]);
+            assert_eq!(store.transparency_log().unwrap().size(), 2);
+        }
+
+        // Ungültige Signatur: nichts wird geschrieben
+        let mut forged = incoming.clone();
+        forged.entries[1].proof_hash = "0xforged".to_string();
+        let store = open_store(RegistryBackend::Json, &dir.join("forged.json")).unwrap();
+        assert!(ImportPlan::new(&Registry::new(), &forged)
+            .apply(store.as_ref())
+            .is_err());
+        assert!(store.list().unwrap().is_empty());
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
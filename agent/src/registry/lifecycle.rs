@@ -0,0 +1,476 @@
+//! Registry Lifecycle - Revocation and supersession of registered proofs
+//!
+//! A registered proof stays valid until a signed lifecycle record says
+//! otherwise:
+//! - `revoke`: the proof must no longer be accepted (reason code)
+//! - `supersede`: the proof was replaced by a successor entry (e.g. after a
+//!   corrected supplier dataset)
+//!
+//! Records are bound to the entry id and its manifest/proof hashes and take
+//! effect at `effective_at`. Revocation dominates supersession.
+
+use base64::{engine::general_purpose, Engine};
+use chrono::{DateTime, Utc};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use super::entry::RegistryEntry;
+use crate::keys;
+
+/// Art eines Lifecycle-Records
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LifecycleAction {
+    Revoke,
+    Supersede,
+}
+
+impl fmt::Display for LifecycleAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LifecycleAction::Revoke => write!(f, "revoke"),
+            LifecycleAction::Supersede => write!(f, "supersede"),
+        }
+    }
+}
+
+impl FromStr for LifecycleAction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "revoke" => Ok(LifecycleAction::Revoke),
+            "supersede" => Ok(LifecycleAction::Supersede),
+            other => Err(format!("Invalid lifecycle action: {}", other)),
+        }
+    }
+}
+
+/// Grund für Widerruf oder Ersetzung
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RevocationReason {
+    /// Zugrundeliegender Datensatz wurde korrigiert
+    DataCorrected,
+    /// Signatur-Key kompromittiert
+    KeyCompromise,
+    /// Proof wurde irrtümlich ausgestellt
+    IssuedInError,
+    /// Policy wurde geändert
+    PolicyChanged,
+    /// Proof wurde zurückgezogen
+    Withdrawn,
+}
+
+impl fmt::Display for RevocationReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            RevocationReason::DataCorrected => "data_corrected",
+            RevocationReason::KeyCompromise => "key_compromise",
+            RevocationReason::IssuedInError => "issued_in_error",
+            RevocationReason::PolicyChanged => "policy_changed",
+            RevocationReason::Withdrawn => "withdrawn",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl FromStr for RevocationReason {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.replace('-', "_").as_str() {
+            "data_corrected" => Ok(RevocationReason::DataCorrected),
+            "key_compromise" => Ok(RevocationReason::KeyCompromise),
+            "issued_in_error" => Ok(RevocationReason::IssuedInError),
+            "policy_changed" => Ok(RevocationReason::PolicyChanged),
+            "withdrawn" => Ok(RevocationReason::Withdrawn),
+            _ => Err(format!(
+                "Invalid reason: {}. Valid options: data-corrected, key-compromise, issued-in-error, policy-changed, withdrawn",
+                s
+            )),
+        }
+    }
+}
+
+/// Signierter Widerruf bzw. Ersetzung eines Registry-Eintrags
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LifecycleRecord {
+    pub entry_id: String,
+    /// Hashes des betroffenen Eintrags (bindet den Record an genau diesen Proof)
+    pub manifest_hash: String,
+    pub proof_hash: String,
+    pub action: LifecycleAction,
+    pub reason: RevocationReason,
+    /// Nachfolger-Eintrag (nur bei `supersede`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub successor_id: Option<String>,
+    /// Ab wann der Record gilt (RFC3339)
+    pub effective_at: String,
+    pub recorded_at: String,
+    /// Ed25519-Signatur über den Record-Core (Base64)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub public_key: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kid: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signature_scheme: Option<String>,
+}
+
+impl LifecycleRecord {
+    /// Erstellt einen (unsignierten) Record für einen Registry-Eintrag
+    pub fn new(
+        entry: &RegistryEntry,
+        action: LifecycleAction,
+        reason: RevocationReason,
+        successor_id: Option<String>,
+        effective_at: String,
+    ) -> Result<Self, Box<dyn Error>> {
+        if successor_id.is_some() != (action == LifecycleAction::Supersede) {
+            return Err("successor_id is required for supersede and not allowed for revoke".into());
+        }
+        DateTime::parse_from_rfc3339(&effective_at)
+            .map_err(|e| format!("Invalid effective_at '{}': {}", effective_at, e))?;
+
+        Ok(Self {
+            entry_id: entry.id.clone(),
+            manifest_hash: entry.manifest_hash.clone(),
+            proof_hash: entry.proof_hash.clone(),
+            action,
+            reason,
+            successor_id,
+            effective_at,
+            recorded_at: Utc::now().to_rfc3339(),
+            signature: None,
+            public_key: None,
+            kid: None,
+            signature_scheme: None,
+        })
+    }
+
+    /// BLAKE3-Hash des Record-Cores (ohne Signatur-Felder)
+    fn core_hash(&self) -> Result<Vec<u8>, Box<dyn Error>> {
+        #[derive(Serialize)]
+        struct RecordCore<'a> {
+            entry_id: &'a str,
+            manifest_hash: &'a str,
+            proof_hash: &'a str,
+            action: LifecycleAction,
+            reason: RevocationReason,
+            successor_id: &'a Option<String>,
+            effective_at: &'a str,
+            recorded_at: &'a str,
+        }
+
+        let core = RecordCore {
+            entry_id: &self.entry_id,
+            manifest_hash: &self.manifest_hash,
+            proof_hash: &self.proof_hash,
+            action: self.action,
+            reason: self.reason,
+            successor_id: &self.successor_id,
+            effective_at: &self.effective_at,
+            recorded_at: &self.recorded_at,
+        };
+        Ok(blake3::hash(&serde_json::to_vec(&core)?)
+            .as_bytes()
+            .to_vec())
+    }
+
+    /// Signiert den Record mit Ed25519 (wie `sign_entry`)
+    pub fn sign(&mut self, signing_key: &SigningKey) -> Result<(), Box<dyn Error>> {
+        let signature = signing_key.sign(&self.core_hash()?);
+        let pubkey_b64 = general_purpose::STANDARD.encode(signing_key.verifying_key().to_bytes());
+
+        self.signature = Some(general_purpose::STANDARD.encode(signature.to_bytes()));
+        self.kid = Some(keys::derive_kid(&pubkey_b64)?);
+        self.public_key = Some(pubkey_b64);
+        self.signature_scheme = Some("ed25519".to_string());
+        Ok(())
+    }
+
+    /// Verifiziert die Signatur und liefert die kid des Signers
+    ///
+    /// Unsignierte Records werden abgelehnt.
+    pub fn verify_signature(&self) -> Result<String, Box<dyn Error>> {
+        let (sig_b64, pubkey_b64) = match (&self.signature, &self.public_key) {
+            (Some(s), Some(p)) => (s, p),
+            _ => return Err("Lifecycle record is not signed".into()),
+        };
+
+        let sig_bytes = general_purpose::STANDARD.decode(sig_b64)?;
+        let pubkey_bytes = general_purpose::STANDARD.decode(pubkey_b64)?;
+        let signature = Signature::from_bytes(
+            &sig_bytes
+                .try_into()
+                .map_err(|_| "Invalid signature length")?,
+        );
+        let verifying_key = VerifyingKey::from_bytes(
+            &pubkey_bytes
+                .try_into()
+                .map_err(|_| "Invalid public key length")?,
+        )?;
+        verifying_key.verify(&self.core_hash()?, &signature)?;
+
+        let kid = keys::derive_kid(pubkey_b64)?;
+        match &self.kid {
+            Some(claimed) if *claimed != kid => {
+                Err(format!("kid {} does not match public key (kid {})", claimed, kid).into())
+            }
+            _ => Ok(kid),
+        }
+    }
+
+    /// Prüft, ob der Record zum Eintrag gehört (ID und Hashes)
+    pub fn applies_to(&self, entry: &RegistryEntry) -> bool {
+        self.entry_id == entry.id
+            && self.manifest_hash == entry.manifest_hash
+            && self.proof_hash == entry.proof_hash
+    }
+
+    /// Ob der Record zum Zeitpunkt `at` wirksam ist
+    pub fn is_effective_at(&self, at: DateTime<Utc>) -> bool {
+        DateTime::parse_from_rfc3339(&self.effective_at)
+            .map(|effective| effective <= at)
+            .unwrap_or(false)
+    }
+}
+
+/// Lifecycle-Status eines Registry-Eintrags
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EntryStatus {
+    Active,
+    Superseded {
+        successor_id: String,
+        reason: RevocationReason,
+        effective_at: String,
+    },
+    Revoked {
+        reason: RevocationReason,
+        effective_at: String,
+    },
+}
+
+impl EntryStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EntryStatus::Active => "active",
+            EntryStatus::Superseded { .. } => "superseded",
+            EntryStatus::Revoked { .. } => "revoked",
+        }
+    }
+}
+
+impl fmt::Display for EntryStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EntryStatus::Active => write!(f, "active"),
+            EntryStatus::Superseded {
+                successor_id,
+                reason,
+                effective_at,
+            } => write!(
+                f,
+                "superseded by {} ({}, effective {})",
+                successor_id, reason, effective_at
+            ),
+            EntryStatus::Revoked {
+                reason,
+                effective_at,
+            } => write!(f, "revoked ({}, effective {})", reason, effective_at),
+        }
+    }
+}
+
+/// Ermittelt den Status eines Eintrags aus seinen Records zum Zeitpunkt `at`
+///
+/// Nur Records, die zum Eintrag gehören und bereits wirksam sind, zählen;
+/// Widerruf hat Vorrang vor Ersetzung. Signaturen werden hier nicht geprüft.
+pub fn entry_status(
+    entry: &RegistryEntry,
+    records: &[LifecycleRecord],
+    at: DateTime<Utc>,
+) -> EntryStatus {
+    let effective: Vec<_> = records
+        .iter()
+        .filter(|r| r.applies_to(entry) && r.is_effective_at(at))
+        .collect();
+
+    let first = |action: LifecycleAction| {
+        effective
+            .iter()
+            .filter(|r| r.action == action)
+            .min_by_key(|r| DateTime::parse_from_rfc3339(&r.effective_at).ok())
+    };
+
+    if let Some(record) = first(LifecycleAction::Revoke) {
+        return EntryStatus::Revoked {
+            reason: record.reason,
+            effective_at: record.effective_at.clone(),
+        };
+    }
+    if let Some(record) = first(LifecycleAction::Supersede) {
+        return EntryStatus::Superseded {
+            successor_id: record.successor_id.clone().unwrap_or_default(),
+            reason: record.reason,
+            effective_at: record.effective_at.clone(),
+        };
+    }
+    EntryStatus::Active
+}
+
+/// Wie [`entry_status`], prüft aber vorher die Signaturen aller Records des Eintrags
+///
+/// Liefert zusätzlich die kids der Record-Signer (für die Key-Status-Prüfung).
+pub fn verified_entry_status(
+    entry: &RegistryEntry,
+    records: &[LifecycleRecord],
+    at: DateTime<Utc>,
+) -> Result<(EntryStatus, Vec<String>), Box<dyn Error>> {
+    let mut kids = Vec::new();
+    for record in records.iter().filter(|r| r.applies_to(entry)) {
+        let kid = record.verify_signature().map_err(|e| {
+            format!(
+                "Lifecycle record ({}) for {}: {}",
+                record.action, record.entry_id, e
+            )
+        })?;
+        kids.push(kid);
+    }
+    Ok((entry_status(entry, records, at), kids))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(id: &str) -> RegistryEntry {
+        RegistryEntry::new(
+            id.to_string(),
+            format!("0x{}", "aa".repeat(32)),
+            format!("0x{}", "bb".repeat(32)),
+            "2026-01-01T00:00:00Z".to_string(),
+        )
+    }
+
+    fn at(time: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(time)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_sign_and_verify_record() {
+        let key = SigningKey::from_bytes(&[3u8; 32]);
+        let mut record = LifecycleRecord::new(
+            &entry("proof_001"),
+            LifecycleAction::Revoke,
+            RevocationReason::KeyCompromise,
+            None,
+            "2026-02-01T00:00:00Z".to_string(),
+        )
+        .unwrap();
+
+        assert!(record.verify_signature().is_err());
+        record.sign(&key).unwrap();
+        assert_eq!(
+            record.verify_signature().unwrap(),
+            record.kid.clone().unwrap()
+        );
+
+        let mut tampered = record.clone();
+        tampered.reason = RevocationReason::Withdrawn;
+        assert!(tampered.verify_signature().is_err());
+
+        let mut moved = record;
+        moved.entry_id = "proof_002".to_string();
+        assert!(moved.verify_signature().is_err());
+    }
+
+    #[test]
+    fn test_record_requires_successor_for_supersede() {
+        let e = entry("proof_001");
+        let time = "2026-02-01T00:00:00Z".to_string();
+        assert!(LifecycleRecord::new(
+            &e,
+            LifecycleAction::Supersede,
+            RevocationReason::DataCorrected,
+            None,
+            time.clone()
+        )
+        .is_err());
+        assert!(LifecycleRecord::new(
+            &e,
+            LifecycleAction::Revoke,
+            RevocationReason::Withdrawn,
+            Some("proof_002".to_string()),
+            time
+        )
+        .is_err());
+        assert!(LifecycleRecord::new(
+            &e,
+            LifecycleAction::Revoke,
+            RevocationReason::Withdrawn,
+            None,
+            "tomorrow".to_string()
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_entry_status() {
+        let e = entry("proof_001");
+        let supersede = LifecycleRecord::new(
+            &e,
+            LifecycleAction::Supersede,
+            RevocationReason::DataCorrected,
+            Some("proof_002".to_string()),
+            "2026-02-01T00:00:00Z".to_string(),
+        )
+        .unwrap();
+        let revoke = LifecycleRecord::new(
+            &e,
+            LifecycleAction::Revoke,
+            RevocationReason::IssuedInError,
+            None,
+            "2026-03-01T00:00:00Z".to_string(),
+        )
+        .unwrap();
+        let records = vec![supersede, revoke];
+
+        assert_eq!(
+            entry_status(&e, &records, at("2026-01-15T00:00:00Z")),
+            EntryStatus::Active
+        );
+        assert_eq!(
+            entry_status(&e, &records, at("2026-02-15T00:00:00Z")).as_str(),
+            "superseded"
+        );
+        assert_eq!(
+            entry_status(&e, &records, at("2026-03-15T00:00:00Z")),
+            EntryStatus::Revoked {
+                reason: RevocationReason::IssuedInError,
+                effective_at: "2026-03-01T00:00:00Z".to_string(),
+            }
+        );
+
+        // Records eines anderen Proofs mit gleicher ID zählen nicht
+        let mut other = entry("proof_001");
+        other.proof_hash = format!("0x{}", "cc".repeat(32));
+        assert_eq!(
+            entry_status(&other, &records, at("2026-03-15T00:00:00Z")),
+            EntryStatus::Active
+        );
+
+        assert_eq!(
+            "data-corrected".parse(),
+            Ok(RevocationReason::DataCorrected)
+        );
+        assert!("typo".parse::<RevocationReason>().is_err());
+    }
+}
@@ -44,7 +44,10 @@ fn migrate_entry(v1_0: &RegistryEntryV1_0, idx: usize) -> Result<RegistryEntryV1
     };
 
     // Use defaults for missing required fields
-    let policy_id = "migrated.v1".to_string(); // Default policy ID
+    let policy_id = v1_0
+        .policy_id
+        .clone()
+        .unwrap_or_else(|| "migrated.v1".to_string()); // Default policy ID
     let ir_hash = format!("sha3-256:migrated_{}", v1_0.manifest_hash); // Derived IR hash
 
     // Create v1.1 entry
@@ -194,6 +197,7 @@ mod tests {
             selfverify_at: None,
             verifier_name: None,
             verifier_version: None,
+            policy_id: None,
         };
 
         let v1_1_entry = migrate_entry(&v1_0_entry, 0).unwrap();
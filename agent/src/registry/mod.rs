@@ -6,8 +6,14 @@
 //!
 //! - `entry`: RegistryEntry data structure
 //! - `signing`: Ed25519 signing and verification
+//! - `lifecycle`: Signed revocation/supersession records
 //! - `timestamp`: RFC3161 timestamp support (mock + real providers)
+//! - `rfc3161`: RFC 3161 DER encoding, token verification, local test TSA
 //! - `store`: Registry storage backends (JSON, SQLite)
+//! - `sqlite_schema`: Versioned schema migrations of `registry.sqlite`
+//! - `query`: Filtering, sorting and pagination of entries
+//! - `transparency`: Append-only Merkle log with signed tree heads
+//! - `exchange`: Signed snapshots for export/import between registries
 //! - `v1_0`: Re-export layer for backward compatibility
 //! - `api`: Unified registry API
 //! - `schema`: v1.1 schema definitions
@@ -15,9 +21,15 @@
 
 // Core modules (v0.11 split)
 pub mod entry;
+pub mod exchange;
+pub mod lifecycle;
+pub mod query;
+pub mod rfc3161;
 pub mod signing;
+pub mod sqlite_schema;
 pub mod store;
 pub mod timestamp;
+pub mod transparency;
 
 // Legacy re-export layer
 pub mod v1_0;
@@ -30,9 +42,27 @@ pub mod schema;
 // Re-export v1.0 types (for backward compatibility and migration)
 #[allow(unused_imports)]
 pub use v1_0::{
-    compute_file_hash, open_store, sign_entry, validate_key_status, verify_entry_from_file,
-    verify_entry_signature, verify_timestamp_from_file, Registry, RegistryBackend, RegistryEntry,
-    Timestamp,
+    compute_file_hash, open_store, open_store_read_only, sign_entry, validate_key_status,
+    verify_entry_from_file, verify_entry_signature, verify_timestamp_from_file,
+    RealRfc3161Provider, Registry, RegistryBackend, RegistryEntry, RegistryStore, Timestamp,
+    TimestampProvider,
+};
+
+#[allow(unused_imports)]
+pub use exchange::{ConflictKind, ImportConflict, ImportPlan, InvalidSignature, RegistrySnapshot};
+
+#[allow(unused_imports)]
+pub use lifecycle::{
+    entry_status, verified_entry_status, EntryStatus, LifecycleAction, LifecycleRecord,
+    RevocationReason,
+};
+
+#[allow(unused_imports)]
+pub use query::{BlobKind, RegistryPage, RegistryQuery, SortField, SortOrder};
+
+#[allow(unused_imports)]
+pub use transparency::{
+    LogConsistencyProof, LogInclusionProof, LogLeaf, SignedTreeHead, TransparencyLog,
 };
 
 // Re-export v1.1 types (used in tests)
@@ -0,0 +1,422 @@
+//! Registry Query - Filtering, sorting and pagination of registry entries
+//!
+//! `RegistryQuery` describes a filter over entries (kid, registration date
+//! range, self-verification status, policy id, signature scheme, blob
+//! presence) plus sort order and page. JSON registries evaluate it in memory
+//! (`RegistryQuery::apply`), SQLite registries translate it to an indexed
+//! `SELECT` (`RegistryQuery::sql_where`).
+//!
+//! Date bounds are inclusive and compared as instants, so `Z` and `+00:00`
+//! timestamps mix freely.
+
+use chrono::{DateTime, FixedOffset};
+use serde::Serialize;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use super::entry::RegistryEntry;
+
+/// BLOB-Feld eines Eintrags
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlobKind {
+    Manifest,
+    Proof,
+    Wasm,
+    Abi,
+    /// Mindestens ein BLOB
+    Any,
+}
+
+impl BlobKind {
+    fn present(&self, entry: &RegistryEntry) -> bool {
+        match self {
+            BlobKind::Manifest => entry.blob_manifest.is_some(),
+            BlobKind::Proof => entry.blob_proof.is_some(),
+            BlobKind::Wasm => entry.blob_wasm.is_some(),
+            BlobKind::Abi => entry.blob_abi.is_some(),
+            BlobKind::Any => [
+                BlobKind::Manifest,
+                BlobKind::Proof,
+                BlobKind::Wasm,
+                BlobKind::Abi,
+            ]
+            .iter()
+            .any(|kind| kind.present(entry)),
+        }
+    }
+
+    fn sql(&self) -> &'static str {
+        match self {
+            BlobKind::Manifest => "blob_manifest IS NOT NULL",
+            BlobKind::Proof => "blob_proof IS NOT NULL",
+            BlobKind::Wasm => "blob_wasm IS NOT NULL",
+            BlobKind::Abi => "blob_abi IS NOT NULL",
+            BlobKind::Any => {
+                "(blob_manifest IS NOT NULL OR blob_proof IS NOT NULL \
+                 OR blob_wasm IS NOT NULL OR blob_abi IS NOT NULL)"
+            }
+        }
+    }
+}
+
+impl FromStr for BlobKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "manifest" => Ok(BlobKind::Manifest),
+            "proof" => Ok(BlobKind::Proof),
+            "wasm" => Ok(BlobKind::Wasm),
+            "abi" => Ok(BlobKind::Abi),
+            "any" => Ok(BlobKind::Any),
+            other => Err(format!(
+                "Invalid blob kind: {}. Valid options: manifest, proof, wasm, abi, any",
+                other
+            )),
+        }
+    }
+}
+
+/// Sortierfeld
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SortField {
+    #[default]
+    RegisteredAt,
+    Id,
+}
+
+impl FromStr for SortField {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "registered_at" | "registered-at" | "date" => Ok(SortField::RegisteredAt),
+            "id" => Ok(SortField::Id),
+            other => Err(format!(
+                "Invalid sort field: {}. Valid options: registered_at, id",
+                other
+            )),
+        }
+    }
+}
+
+/// Sortierrichtung
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SortOrder {
+    #[default]
+    Asc,
+    Desc,
+}
+
+impl FromStr for SortOrder {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "asc" => Ok(SortOrder::Asc),
+            "desc" => Ok(SortOrder::Desc),
+            other => Err(format!(
+                "Invalid sort order: {}. Valid options: asc, desc",
+                other
+            )),
+        }
+    }
+}
+
+impl fmt::Display for SortOrder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SortOrder::Asc => write!(f, "ASC"),
+            SortOrder::Desc => write!(f, "DESC"),
+        }
+    }
+}
+
+/// Filter, Sortierung und Seite einer Registry-Abfrage
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RegistryQuery {
+    pub kid: Option<String>,
+    /// Registriert ab (RFC3339, inklusive)
+    pub registered_from: Option<String>,
+    /// Registriert bis (RFC3339, inklusive)
+    pub registered_to: Option<String>,
+    pub selfverify_status: Option<String>,
+    pub policy_id: Option<String>,
+    pub signature_scheme: Option<String>,
+    /// Nur Einträge mit diesem BLOB
+    pub has_blob: Option<BlobKind>,
+    pub sort: SortField,
+    pub order: SortOrder,
+    /// Maximale Anzahl Einträge (None = alle)
+    pub limit: Option<usize>,
+    pub offset: usize,
+}
+
+/// Eine Seite von Abfrage-Ergebnissen
+#[derive(Debug, Clone, Serialize)]
+pub struct RegistryPage {
+    /// Anzahl aller passenden Einträge (vor Paginierung)
+    pub total: usize,
+    pub offset: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<usize>,
+    pub entries: Vec<RegistryEntry>,
+}
+
+fn parse_time(value: &str) -> Option<DateTime<FixedOffset>> {
+    DateTime::parse_from_rfc3339(value).ok()
+}
+
+impl RegistryQuery {
+    /// Übernimmt einen Filter der Form `key=value` (CLI `--filter`)
+    ///
+    /// Keys: `kid`, `from`, `to`, `selfverify`, `policy`, `scheme`, `blob`
+    pub fn add_filter(&mut self, filter: &str) -> Result<(), Box<dyn Error>> {
+        let (key, value) = filter
+            .split_once('=')
+            .ok_or_else(|| format!("Invalid filter (expected key=value): {}", filter))?;
+        let value = value.trim().to_string();
+
+        match key.trim() {
+            "kid" => self.kid = Some(value),
+            "from" => self.registered_from = Some(value),
+            "to" => self.registered_to = Some(value),
+            "selfverify" | "selfverify_status" => self.selfverify_status = Some(value),
+            "policy" | "policy_id" => self.policy_id = Some(value),
+            "scheme" | "signature_scheme" => self.signature_scheme = Some(value),
+            "blob" => self.has_blob = Some(value.parse()?),
+            other => {
+                return Err(format!(
+                    "Unknown filter: {}. Valid filters: kid, from, to, selfverify, policy, scheme, blob",
+                    other
+                )
+                .into())
+            }
+        }
+        Ok(())
+    }
+
+    /// Prüft die Datumsgrenzen (RFC3339)
+    pub fn validate(&self) -> Result<(), Box<dyn Error>> {
+        for bound in [&self.registered_from, &self.registered_to]
+            .into_iter()
+            .flatten()
+        {
+            if parse_time(bound).is_none() {
+                return Err(format!("Invalid RFC3339 timestamp in filter: {}", bound).into());
+            }
+        }
+        Ok(())
+    }
+
+    /// Prüft, ob ein Eintrag alle Filter erfüllt
+    ///
+    /// Einträge mit ungültigem `registered_at` fallen bei Datumsfiltern heraus.
+    pub fn matches(&self, entry: &RegistryEntry) -> bool {
+        let eq = |filter: &Option<String>, value: &Option<String>| match filter {
+            Some(expected) => value.as_deref() == Some(expected.as_str()),
+            None => true,
+        };
+        if !eq(&self.kid, &entry.kid)
+            || !eq(&self.selfverify_status, &entry.selfverify_status)
+            || !eq(&self.policy_id, &entry.policy_id)
+            || !eq(&self.signature_scheme, &entry.signature_scheme)
+        {
+            return false;
+        }
+        if let Some(kind) = self.has_blob {
+            if !kind.present(entry) {
+                return false;
+            }
+        }
+
+        if self.registered_from.is_some() || self.registered_to.is_some() {
+            let Some(registered) = parse_time(&entry.registered_at) else {
+                return false;
+            };
+            let from = self.registered_from.as_deref().and_then(parse_time);
+            let to = self.registered_to.as_deref().and_then(parse_time);
+            if from.is_some_and(|from| registered < from) || to.is_some_and(|to| registered > to) {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Wendet Filter, Sortierung und Paginierung im Speicher an
+    pub fn apply(&self, entries: Vec<RegistryEntry>) -> RegistryPage {
+        let mut matching: Vec<RegistryEntry> =
+            entries.into_iter().filter(|e| self.matches(e)).collect();
+
+        matching.sort_by(|a, b| {
+            let ordering = match self.sort {
+                SortField::RegisteredAt => parse_time(&a.registered_at)
+                    .cmp(&parse_time(&b.registered_at))
+                    .then_with(|| a.id.cmp(&b.id)),
+                SortField::Id => a.id.cmp(&b.id),
+            };
+            match self.order {
+                SortOrder::Asc => ordering,
+                SortOrder::Desc => ordering.reverse(),
+            }
+        });
+
+        let total = matching.len();
+        let entries = matching
+            .into_iter()
+            .skip(self.offset)
+            .take(self.limit.unwrap_or(usize::MAX))
+            .collect();
+
+        RegistryPage {
+            total,
+            offset: self.offset,
+            limit: self.limit,
+            entries,
+        }
+    }
+
+    /// WHERE-Klausel und Parameter für `registry_entries` (SQLite)
+    pub fn sql_where(&self) -> (String, Vec<String>) {
+        let mut clauses: Vec<String> = Vec::new();
+        let mut params = Vec::new();
+
+        for (column, value) in [
+            ("kid", &self.kid),
+            ("selfverify_status", &self.selfverify_status),
+            ("policy_id", &self.policy_id),
+            ("signature_scheme", &self.signature_scheme),
+        ] {
+            if let Some(value) = value {
+                clauses.push(format!("{} = ?", column));
+                params.push(value.clone());
+            }
+        }
+        if let Some(from) = &self.registered_from {
+            clauses.push("julianday(registered_at) >= julianday(?)".to_string());
+            params.push(from.clone());
+        }
+        if let Some(to) = &self.registered_to {
+            clauses.push("julianday(registered_at) <= julianday(?)".to_string());
+            params.push(to.clone());
+        }
+        if let Some(kind) = self.has_blob {
+            clauses.push(kind.sql().to_string());
+        }
+
+        if clauses.is_empty() {
+            (String::new(), params)
+        } else {
+            (format!("WHERE {}", clauses.join(" AND ")), params)
+        }
+    }
+
+    /// ORDER-BY-Klausel (SQLite)
+    pub fn sql_order_by(&self) -> String {
+        match self.sort {
+            SortField::RegisteredAt => format!(
+                "ORDER BY julianday(registered_at) {order}, id {order}",
+                order = self.order
+            ),
+            SortField::Id => format!("ORDER BY id {}", self.order),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(id: &str, registered_at: &str) -> RegistryEntry {
+        RegistryEntry::new(
+            id.to_string(),
+            format!("0xm_{}", id),
+            format!("0xp_{}", id),
+            registered_at.to_string(),
+        )
+    }
+
+    fn entries() -> Vec<RegistryEntry> {
+        let mut a = entry("proof_001", "2026-01-10T00:00:00Z");
+        a.kid = Some("kid_a".to_string());
+        a.signature_scheme = Some("ed25519".to_string());
+        a.policy_id = Some("0xpolicy1".to_string());
+        let mut b = entry("proof_002", "2026-02-10T12:00:00+00:00");
+        b.kid = Some("kid_b".to_string());
+        b.blob_wasm = Some("0xwasm".to_string());
+        b.selfverify_status = Some("ok".to_string());
+        let mut c = entry("proof_003", "2026-03-10T00:00:00+02:00");
+        c.kid = Some("kid_a".to_string());
+        c.policy_id = Some("0xpolicy1".to_string());
+        let d = entry("proof_004", "not a date");
+        vec![c, a, d, b]
+    }
+
+    fn ids(page: &RegistryPage) -> Vec<&str> {
+        page.entries.iter().map(|e| e.id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_filters() {
+        let query = |filters: &[&str]| {
+            let mut query = RegistryQuery::default();
+            for filter in filters {
+                query.add_filter(filter).unwrap();
+            }
+            query.validate().unwrap();
+            query.apply(entries())
+        };
+
+        assert_eq!(ids(&query(&["kid=kid_a"])), ["proof_001", "proof_003"]);
+        assert_eq!(ids(&query(&["policy=0xpolicy1", "kid=kid_a"])).len(), 2);
+        assert_eq!(ids(&query(&["selfverify=ok"])), ["proof_002"]);
+        assert_eq!(ids(&query(&["scheme=ed25519"])), ["proof_001"]);
+        assert_eq!(ids(&query(&["blob=wasm"])), ["proof_002"]);
+        assert_eq!(ids(&query(&["blob=any"])), ["proof_002"]);
+        assert!(ids(&query(&["blob=abi"])).is_empty());
+
+        // Inklusive Grenzen, verschiedene Offsets, ungültiges Datum fällt heraus
+        assert_eq!(
+            ids(&query(&[
+                "from=2026-02-10T12:00:00Z",
+                "to=2026-03-09T22:00:00Z"
+            ])),
+            ["proof_002", "proof_003"]
+        );
+        assert_eq!(ids(&query(&["to=2026-01-31T00:00:00Z"])), ["proof_001"]);
+    }
+
+    #[test]
+    fn test_sort_and_pagination() {
+        let mut query = RegistryQuery::default();
+        let page = query.apply(entries());
+        assert_eq!(page.total, 4);
+        assert_eq!(
+            ids(&page),
+            ["proof_004", "proof_001", "proof_002", "proof_003"]
+        );
+
+        query.order = SortOrder::Desc;
+        query.offset = 1;
+        query.limit = Some(2);
+        let page = query.apply(entries());
+        assert_eq!(page.total, 4);
+        assert_eq!(ids(&page), ["proof_002", "proof_001"]);
+
+        query.sort = SortField::Id;
+        query.offset = 3;
+        assert_eq!(ids(&query.apply(entries())), ["proof_001"]);
+    }
+
+    #[test]
+    fn test_invalid_filters() {
+        let mut query = RegistryQuery::default();
+        assert!(query.add_filter("kid").is_err());
+        assert!(query.add_filter("owner=acme").is_err());
+        assert!(query.add_filter("blob=pdf").is_err());
+        query.add_filter("from=yesterday").unwrap();
+        assert!(query.validate().is_err());
+        assert!("sideways".parse::<SortOrder>().is_err());
+    }
+}
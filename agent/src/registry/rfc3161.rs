@@ -0,0 +1,823 @@
+//! RFC 3161 Time-Stamp Protocol
+//!
+//! DER encoding of `TimeStampReq`/`TimeStampResp` and validation of
+//! `TimeStampToken`s (CMS `SignedData` over `TSTInfo`):
+//!
+//! - Message imprint: SHA-256 over the audit tip bytes (hex-decoded)
+//! - Signed attributes: content type `id-ct-TSTInfo`, message digest of the `TSTInfo`
+//! - Signature: RSA PKCS#1 v1.5 (SHA-256/384/512) or Ed25519, checked with the
+//!   configured TSA certificate (or the certificate embedded in the token)
+//! - TSA certificate: extended key usage `id-kp-timeStamping`, valid at `genTime`
+//!
+//! [`LocalTsa`] is a local TSA stand-in (Ed25519, self-signed certificate) for
+//! tests and offline development. It answers requests directly
+//! ([`LocalTsa::respond`]) or over a loopback HTTP listener ([`LocalTsa::spawn`]).
+
+use anyhow::{anyhow, bail, Context, Result};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use cms::cert::{CertificateChoices, IssuerAndSerialNumber};
+use cms::content_info::{CmsVersion, ContentInfo};
+use cms::signed_data::{
+    CertificateSet, EncapsulatedContentInfo, SignedData, SignerIdentifier, SignerInfo, SignerInfos,
+};
+use der::asn1::{BitString, GeneralizedTime, ObjectIdentifier, OctetString, SetOfVec, Uint};
+use der::{Any, Decode, DecodePem, Encode, EncodePem, Sequence, Tag, Tagged};
+use ed25519_dalek::{Signer, SigningKey};
+use sha2::Digest;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, SystemTime};
+use x509_cert::attr::Attribute;
+use x509_cert::certificate::{Certificate, TbsCertificate, Version};
+use x509_cert::ext::pkix::name::GeneralName;
+use x509_cert::ext::pkix::{ExtendedKeyUsage, SubjectKeyIdentifier};
+use x509_cert::ext::{Extension, Extensions};
+use x509_cert::name::Name;
+use x509_cert::serial_number::SerialNumber;
+use x509_cert::spki::{AlgorithmIdentifierOwned, SubjectPublicKeyInfoOwned};
+use x509_cert::time::Validity;
+
+const ID_SIGNED_DATA: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.7.2");
+const ID_CT_TST_INFO: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.9.16.1.4");
+const ID_CONTENT_TYPE: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.9.3");
+const ID_MESSAGE_DIGEST: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.9.4");
+const ID_SHA256: ObjectIdentifier = ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.2.1");
+const ID_SHA384: ObjectIdentifier = ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.2.2");
+const ID_SHA512: ObjectIdentifier = ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.2.3");
+const ID_RSA_ENCRYPTION: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.1.1");
+const ID_SHA256_WITH_RSA: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.1.11");
+const ID_SHA384_WITH_RSA: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.1.12");
+const ID_SHA512_WITH_RSA: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.1.13");
+const ID_ED25519: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.101.112");
+const ID_KP_TIME_STAMPING: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.6.1.5.5.7.3.8");
+
+/// TSA policy of the local stand-in (`tsa_policy1` of the OpenSSL example TSA config)
+const LOCAL_TSA_POLICY: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.3.4.1");
+
+/// Content types for the HTTP transport (RFC 3161 Section 3.4)
+const CONTENT_TYPE_QUERY: &str = "application/timestamp-query";
+const CONTENT_TYPE_REPLY: &str = "application/timestamp-reply";
+
+// ============================================================================
+// ASN.1 Types (RFC 3161 Section 2.4)
+// ============================================================================
+
+/// `MessageImprint ::= SEQUENCE { hashAlgorithm, hashedMessage }`
+#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+pub struct MessageImprint {
+    pub hash_algorithm: AlgorithmIdentifierOwned,
+    pub hashed_message: OctetString,
+}
+
+/// `TimeStampReq`
+#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+pub struct TimeStampReq {
+    pub version: u8,
+    pub message_imprint: MessageImprint,
+    #[asn1(optional = "true")]
+    pub req_policy: Option<ObjectIdentifier>,
+    #[asn1(optional = "true")]
+    pub nonce: Option<Uint>,
+    #[asn1(default = "Default::default")]
+    pub cert_req: bool,
+    #[asn1(context_specific = "0", tag_mode = "IMPLICIT", optional = "true")]
+    pub extensions: Option<Extensions>,
+}
+
+/// `PKIStatusInfo`
+#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+pub struct PkiStatusInfo {
+    /// 0 = granted, 1 = grantedWithMods, 2 = rejection, 3 = waiting, ...
+    pub status: u8,
+    #[asn1(optional = "true")]
+    pub status_string: Option<Vec<String>>,
+    #[asn1(optional = "true")]
+    pub fail_info: Option<BitString>,
+}
+
+/// `TimeStampResp`
+#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+pub struct TimeStampResp {
+    pub status: PkiStatusInfo,
+    #[asn1(optional = "true")]
+    pub time_stamp_token: Option<ContentInfo>,
+}
+
+/// `Accuracy`
+#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+pub struct Accuracy {
+    #[asn1(optional = "true")]
+    pub seconds: Option<u32>,
+    #[asn1(context_specific = "0", tag_mode = "IMPLICIT", optional = "true")]
+    pub millis: Option<u16>,
+    #[asn1(context_specific = "1", tag_mode = "IMPLICIT", optional = "true")]
+    pub micros: Option<u16>,
+}
+
+/// `TSTInfo` (eContent of a TimeStampToken)
+#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+pub struct TstInfo {
+    pub version: u8,
+    pub policy: ObjectIdentifier,
+    pub message_imprint: MessageImprint,
+    pub serial_number: Uint,
+    /// GeneralizedTime, may carry fractional seconds (see [`parse_gen_time`])
+    pub gen_time: Any,
+    #[asn1(optional = "true")]
+    pub accuracy: Option<Accuracy>,
+    #[asn1(default = "Default::default")]
+    pub ordering: bool,
+    #[asn1(optional = "true")]
+    pub nonce: Option<Uint>,
+    #[asn1(context_specific = "0", tag_mode = "EXPLICIT", optional = "true")]
+    pub tsa: Option<GeneralName>,
+    #[asn1(context_specific = "1", tag_mode = "IMPLICIT", optional = "true")]
+    pub extensions: Option<Extensions>,
+}
+
+// ============================================================================
+// Request / Response
+// ============================================================================
+
+fn algorithm(oid: ObjectIdentifier) -> AlgorithmIdentifierOwned {
+    AlgorithmIdentifierOwned {
+        oid,
+        parameters: None,
+    }
+}
+
+fn digest(alg: &ObjectIdentifier, data: &[u8]) -> Result<Vec<u8>> {
+    Ok(match *alg {
+        ID_SHA256 => sha2::Sha256::digest(data).to_vec(),
+        ID_SHA384 => sha2::Sha384::digest(data).to_vec(),
+        ID_SHA512 => sha2::Sha512::digest(data).to_vec(),
+        other => bail!("unsupported digest algorithm {}", other),
+    })
+}
+
+fn tip_bytes(audit_tip_hex: &str) -> Result<Vec<u8>> {
+    hex::decode(audit_tip_hex.trim_start_matches("0x"))
+        .with_context(|| format!("audit tip '{}' is not hex", audit_tip_hex))
+}
+
+/// Message imprint (SHA-256) for an audit tip (`0x`-prefixed hex)
+pub fn message_imprint(audit_tip_hex: &str) -> Result<MessageImprint> {
+    Ok(MessageImprint {
+        hash_algorithm: algorithm(ID_SHA256),
+        hashed_message: OctetString::new(digest(&ID_SHA256, &tip_bytes(audit_tip_hex)?)?)?,
+    })
+}
+
+/// Builds a `TimeStampReq` for an audit tip (certReq set, so the token carries the TSA certificate)
+pub fn build_request(audit_tip_hex: &str, nonce: u64) -> Result<TimeStampReq> {
+    Ok(TimeStampReq {
+        version: 1,
+        message_imprint: message_imprint(audit_tip_hex)?,
+        req_policy: None,
+        nonce: Some(Uint::new(&nonce.to_be_bytes())?),
+        cert_req: true,
+        extensions: None,
+    })
+}
+
+/// Extracts the TimeStampToken from a DER `TimeStampResp`
+///
+/// Fails unless the status is granted (0) or grantedWithMods (1).
+pub fn token_from_response(response_der: &[u8]) -> Result<ContentInfo> {
+    let response = TimeStampResp::from_der(response_der).context("invalid TimeStampResp")?;
+    if response.status.status > 1 {
+        bail!(
+            "TSA rejected request (status {}{})",
+            response.status.status,
+            response
+                .status
+                .status_string
+                .map(|s| format!(": {}", s.join("; ")))
+                .unwrap_or_default()
+        );
+    }
+    response
+        .time_stamp_token
+        .ok_or_else(|| anyhow!("TimeStampResp contains no token"))
+}
+
+/// Sends a DER `TimeStampReq` to a TSA via HTTP(S) POST and returns the DER response
+pub fn request_timestamp(tsa_url: &str, request_der: Vec<u8>) -> Result<Vec<u8>> {
+    let response = reqwest::blocking::Client::builder()
+        .timeout(Duration::from_secs(30))
+        .build()?
+        .post(tsa_url)
+        .header(reqwest::header::CONTENT_TYPE, CONTENT_TYPE_QUERY)
+        .body(request_der)
+        .send()
+        .with_context(|| format!("TSA request to {} failed", tsa_url))?;
+    if !response.status().is_success() {
+        bail!("TSA {} answered HTTP {}", tsa_url, response.status());
+    }
+    Ok(response.bytes()?.to_vec())
+}
+
+// ============================================================================
+// Token Verification
+// ============================================================================
+
+/// Parses a GeneralizedTime (`YYYYMMDDHHMMSS[.f*]Z`)
+pub fn parse_gen_time(value: &Any) -> Result<DateTime<Utc>> {
+    if value.tag() != Tag::GeneralizedTime {
+        bail!("genTime is not a GeneralizedTime");
+    }
+    let text = std::str::from_utf8(value.value())?;
+    let text = text
+        .strip_suffix('Z')
+        .ok_or_else(|| anyhow!("genTime '{}' is not UTC", text))?;
+    let (seconds, fraction) = text.split_once('.').unwrap_or((text, ""));
+    if !fraction.chars().all(|c| c.is_ascii_digit()) {
+        bail!("genTime '{}' has an invalid fraction", text);
+    }
+    let time = NaiveDateTime::parse_from_str(seconds, "%Y%m%d%H%M%S")
+        .with_context(|| format!("genTime '{}'", text))?;
+    let nanos = format!("{:0<9}", &fraction[..fraction.len().min(9)]).parse::<i64>()?;
+    Ok(time.and_utc() + chrono::Duration::nanoseconds(nanos))
+}
+
+/// Loads a certificate from a PEM or DER file
+pub fn load_certificate<P: AsRef<Path>>(path: P) -> Result<Certificate> {
+    let path = path.as_ref();
+    let bytes = std::fs::read(path).with_context(|| format!("{}", path.display()))?;
+    parse_certificate(&bytes).with_context(|| format!("{}", path.display()))
+}
+
+/// Parses a certificate from PEM or DER bytes
+pub fn parse_certificate(bytes: &[u8]) -> Result<Certificate> {
+    if bytes.starts_with(b"-----BEGIN") {
+        Ok(Certificate::from_pem(bytes)?)
+    } else {
+        Ok(Certificate::from_der(bytes)?)
+    }
+}
+
+/// Verified content of a TimeStampToken
+#[allow(dead_code)] // Public API - all fields read by verifier::checks
+#[derive(Debug, Clone)]
+pub struct VerifiedToken {
+    pub gen_time: DateTime<Utc>,
+    /// Serial number of the token (hex)
+    pub serial_number: String,
+    pub policy: String,
+    pub nonce: Option<Uint>,
+    /// Subject of the signing TSA certificate
+    pub tsa: String,
+    /// true if the token was checked against a configured TSA certificate
+    pub certificate_pinned: bool,
+}
+
+fn signer_matches(sid: &SignerIdentifier, cert: &Certificate) -> bool {
+    match sid {
+        SignerIdentifier::IssuerAndSerialNumber(id) => {
+            id.issuer == cert.tbs_certificate.issuer
+                && id.serial_number == cert.tbs_certificate.serial_number
+        }
+        SignerIdentifier::SubjectKeyIdentifier(ski) => matches!(
+            cert.tbs_certificate.get::<SubjectKeyIdentifier>(),
+            Ok(Some((_, own))) if own == *ski
+        ),
+    }
+}
+
+fn single_attribute_value<'a>(
+    attrs: &'a SetOfVec<Attribute>,
+    oid: &ObjectIdentifier,
+) -> Result<&'a Any> {
+    let attr = attrs
+        .iter()
+        .find(|a| a.oid == *oid)
+        .ok_or_else(|| anyhow!("signed attribute {} missing", oid))?;
+    match attr.values.as_slice() {
+        [value] => Ok(value),
+        _ => bail!("signed attribute {} must have exactly one value", oid),
+    }
+}
+
+fn check_certificate(cert: &Certificate, at: &DateTime<Utc>) -> Result<()> {
+    let eku = cert
+        .tbs_certificate
+        .get::<ExtendedKeyUsage>()?
+        .ok_or_else(|| anyhow!("TSA certificate has no extended key usage"))?;
+    if !eku.1 .0.contains(&ID_KP_TIME_STAMPING) {
+        bail!("TSA certificate is not valid for time stamping");
+    }
+
+    let validity = &cert.tbs_certificate.validity;
+    let not_before = DateTime::<Utc>::from(validity.not_before.to_system_time());
+    let not_after = DateTime::<Utc>::from(validity.not_after.to_system_time());
+    if *at < not_before || *at > not_after {
+        bail!(
+            "genTime {} outside TSA certificate validity ({} - {})",
+            at.to_rfc3339(),
+            not_before.to_rfc3339(),
+            not_after.to_rfc3339()
+        );
+    }
+    Ok(())
+}
+
+fn verify_signature(
+    cert: &Certificate,
+    digest_alg: &ObjectIdentifier,
+    signature_alg: &ObjectIdentifier,
+    message: &[u8],
+    signature: &[u8],
+) -> Result<()> {
+    let spki = &cert.tbs_certificate.subject_public_key_info;
+    let key_bytes = spki.subject_public_key.raw_bytes();
+
+    match spki.algorithm.oid {
+        ID_ED25519 => {
+            let key = ed25519_dalek::VerifyingKey::from_bytes(
+                key_bytes
+                    .try_into()
+                    .map_err(|_| anyhow!("invalid Ed25519 key length"))?,
+            )?;
+            let signature = ed25519_dalek::Signature::from_slice(signature)?;
+            key.verify_strict(message, &signature)
+                .map_err(|_| anyhow!("token signature invalid"))
+        }
+        ID_RSA_ENCRYPTION => {
+            use rsa::pkcs1::DecodeRsaPublicKey;
+
+            let key = rsa::RsaPublicKey::from_pkcs1_der(key_bytes)?;
+            let hash_alg = match *signature_alg {
+                ID_SHA256_WITH_RSA => ID_SHA256,
+                ID_SHA384_WITH_RSA => ID_SHA384,
+                ID_SHA512_WITH_RSA => ID_SHA512,
+                ID_RSA_ENCRYPTION => *digest_alg,
+                other => bail!("unsupported signature algorithm {}", other),
+            };
+            let scheme = match hash_alg {
+                ID_SHA256 => rsa::Pkcs1v15Sign::new::<sha2::Sha256>(),
+                ID_SHA384 => rsa::Pkcs1v15Sign::new::<sha2::Sha384>(),
+                ID_SHA512 => rsa::Pkcs1v15Sign::new::<sha2::Sha512>(),
+                other => bail!("unsupported digest algorithm {}", other),
+            };
+            key.verify(scheme, &digest(&hash_alg, message)?, signature)
+                .map_err(|_| anyhow!("token signature invalid"))
+        }
+        other => bail!("unsupported TSA key algorithm {}", other),
+    }
+}
+
+/// Verifies a DER TimeStampToken for an audit tip
+///
+/// With `tsa_certificate` the token must be signed by exactly this
+/// certificate; without it the certificate embedded in the token is used
+/// (`certificate_pinned = false`, trust is left to the caller).
+pub fn verify_token(
+    token_der: &[u8],
+    audit_tip_hex: &str,
+    tsa_certificate: Option<&Certificate>,
+) -> Result<VerifiedToken> {
+    let token = ContentInfo::from_der(token_der).context("invalid TimeStampToken")?;
+    if token.content_type != ID_SIGNED_DATA {
+        bail!("TimeStampToken is not SignedData");
+    }
+    let signed_data: SignedData = token.content.decode_as()?;
+
+    let encap = &signed_data.encap_content_info;
+    if encap.econtent_type != ID_CT_TST_INFO {
+        bail!("TimeStampToken does not contain a TSTInfo");
+    }
+    let econtent: OctetString = encap
+        .econtent
+        .as_ref()
+        .ok_or_else(|| anyhow!("TimeStampToken has no eContent"))?
+        .decode_as()?;
+    let tst_info = TstInfo::from_der(econtent.as_bytes()).context("invalid TSTInfo")?;
+
+    // Imprint must cover the audit tip
+    let imprint = &tst_info.message_imprint;
+    if digest(&imprint.hash_algorithm.oid, &tip_bytes(audit_tip_hex)?)?
+        != imprint.hashed_message.as_bytes()
+    {
+        bail!("message imprint does not match audit tip {}", audit_tip_hex);
+    }
+    let gen_time = parse_gen_time(&tst_info.gen_time)?;
+
+    let signer = match signed_data.signer_infos.0.as_slice() {
+        [signer] => signer,
+        _ => bail!("TimeStampToken must have exactly one signer"),
+    };
+    let cert = match tsa_certificate {
+        Some(cert) => {
+            if !signer_matches(&signer.sid, cert) {
+                bail!("token is not signed by the configured TSA certificate");
+            }
+            cert.clone()
+        }
+        None => signed_data
+            .certificates
+            .iter()
+            .flat_map(|set| set.0.iter())
+            .find_map(|choice| match choice {
+                CertificateChoices::Certificate(cert) if signer_matches(&signer.sid, cert) => {
+                    Some(cert.clone())
+                }
+                _ => None,
+            })
+            .ok_or_else(|| anyhow!("no TSA certificate configured or embedded"))?,
+    };
+    check_certificate(&cert, &gen_time)?;
+
+    // Signed attributes bind the signature to the TSTInfo
+    let attrs = signer
+        .signed_attrs
+        .as_ref()
+        .ok_or_else(|| anyhow!("signer has no signed attributes"))?;
+    let content_type: ObjectIdentifier =
+        single_attribute_value(attrs, &ID_CONTENT_TYPE)?.decode_as()?;
+    if content_type != ID_CT_TST_INFO {
+        bail!("signed content type is not id-ct-TSTInfo");
+    }
+    let message_digest: OctetString =
+        single_attribute_value(attrs, &ID_MESSAGE_DIGEST)?.decode_as()?;
+    if message_digest.as_bytes() != digest(&signer.digest_alg.oid, econtent.as_bytes())? {
+        bail!("message digest does not match TSTInfo");
+    }
+    verify_signature(
+        &cert,
+        &signer.digest_alg.oid,
+        &signer.signature_algorithm.oid,
+        &attrs.to_der()?,
+        signer.signature.as_bytes(),
+    )?;
+
+    Ok(VerifiedToken {
+        gen_time,
+        serial_number: hex::encode(tst_info.serial_number.as_bytes()),
+        policy: tst_info.policy.to_string(),
+        nonce: tst_info.nonce,
+        tsa: cert.tbs_certificate.subject.to_string(),
+        certificate_pinned: tsa_certificate.is_some(),
+    })
+}
+
+// ============================================================================
+// Local TSA (test stand-in)
+// ============================================================================
+
+/// Local RFC 3161 TSA with an Ed25519 key and a self-signed certificate
+///
+/// Not a trusted time source - intended for tests and offline development.
+#[allow(dead_code)] // Public API - local TSA stand-in for tests
+pub struct LocalTsa {
+    key: SigningKey,
+    certificate: Certificate,
+    next_serial: AtomicU64,
+}
+
+#[allow(dead_code)] // Public API - local TSA stand-in for tests
+impl LocalTsa {
+    /// Creates a TSA with a fresh key and certificate (`CN=<name>`, valid 10 years)
+    pub fn new(name: &str) -> Result<Self> {
+        let key = SigningKey::generate(&mut rand::rngs::OsRng);
+        let subject: Name = format!("CN={}", name).parse()?;
+        let public_key = key.verifying_key().to_bytes();
+
+        let extensions = vec![
+            Extension {
+                extn_id: ObjectIdentifier::new_unwrap("2.5.29.37"),
+                critical: true,
+                extn_value: OctetString::new(
+                    ExtendedKeyUsage(vec![ID_KP_TIME_STAMPING]).to_der()?,
+                )?,
+            },
+            Extension {
+                extn_id: ObjectIdentifier::new_unwrap("2.5.29.14"),
+                critical: false,
+                extn_value: OctetString::new(
+                    SubjectKeyIdentifier(OctetString::new(
+                        sha2::Sha256::digest(public_key)[..20].to_vec(),
+                    )?)
+                    .to_der()?,
+                )?,
+            },
+        ];
+
+        let tbs_certificate = TbsCertificate {
+            version: Version::V3,
+            serial_number: SerialNumber::new(&rand::random::<[u8; 8]>())?,
+            signature: algorithm(ID_ED25519),
+            issuer: subject.clone(),
+            validity: Validity::from_now(Duration::from_secs(10 * 365 * 24 * 3600))?,
+            subject,
+            subject_public_key_info: SubjectPublicKeyInfoOwned {
+                algorithm: algorithm(ID_ED25519),
+                subject_public_key: BitString::from_bytes(&public_key)?,
+            },
+            issuer_unique_id: None,
+            subject_unique_id: None,
+            extensions: Some(extensions),
+        };
+        let signature = key.sign(&tbs_certificate.to_der()?);
+        let certificate = Certificate {
+            tbs_certificate,
+            signature_algorithm: algorithm(ID_ED25519),
+            signature: BitString::from_bytes(&signature.to_bytes())?,
+        };
+
+        Ok(LocalTsa {
+            key,
+            certificate,
+            next_serial: AtomicU64::new(1),
+        })
+    }
+
+    pub fn certificate(&self) -> &Certificate {
+        &self.certificate
+    }
+
+    pub fn certificate_pem(&self) -> Result<String> {
+        Ok(self.certificate.to_pem(der::pem::LineEnding::LF)?)
+    }
+
+    /// Answers a DER `TimeStampReq` with a DER `TimeStampResp`
+    ///
+    /// Invalid requests get a rejection (status 2) instead of an error.
+    pub fn respond(&self, request_der: &[u8]) -> Vec<u8> {
+        let response = match self.issue(request_der) {
+            Ok(token) => TimeStampResp {
+                status: PkiStatusInfo {
+                    status: 0,
+                    status_string: None,
+                    fail_info: None,
+                },
+                time_stamp_token: Some(token),
+            },
+            Err(e) => TimeStampResp {
+                status: PkiStatusInfo {
+                    status: 2,
+                    status_string: Some(vec![e.to_string()]),
+                    fail_info: None,
+                },
+                time_stamp_token: None,
+            },
+        };
+        response.to_der().expect("TimeStampResp encoding")
+    }
+
+    fn issue(&self, request_der: &[u8]) -> Result<ContentInfo> {
+        let request = TimeStampReq::from_der(request_der).context("invalid TimeStampReq")?;
+        if request.version != 1 {
+            bail!("unsupported request version {}", request.version);
+        }
+        let imprint = &request.message_imprint;
+        if digest(&imprint.hash_algorithm.oid, &[])?.len()
+            != imprint.hashed_message.as_bytes().len()
+        {
+            bail!("hashed message length does not match hash algorithm");
+        }
+
+        let serial = self.next_serial.fetch_add(1, Ordering::SeqCst);
+        let tst_info = TstInfo {
+            version: 1,
+            policy: request.req_policy.unwrap_or(LOCAL_TSA_POLICY),
+            message_imprint: request.message_imprint.clone(),
+            serial_number: Uint::new(&serial.to_be_bytes())?,
+            gen_time: Any::encode_from(&GeneralizedTime::from_system_time(SystemTime::now())?)?,
+            accuracy: None,
+            ordering: false,
+            nonce: request.nonce,
+            tsa: None,
+            extensions: None,
+        };
+        let econtent = tst_info.to_der()?;
+
+        // RFC 8419: Ed25519 signers use SHA-512 for the message digest
+        let attribute = |oid, value: Any| -> Result<Attribute> {
+            Ok(Attribute {
+                oid,
+                values: SetOfVec::try_from(vec![value])?,
+            })
+        };
+        let signed_attrs = SetOfVec::try_from(vec![
+            attribute(ID_CONTENT_TYPE, Any::encode_from(&ID_CT_TST_INFO)?)?,
+            attribute(
+                ID_MESSAGE_DIGEST,
+                Any::encode_from(&OctetString::new(digest(&ID_SHA512, &econtent)?)?)?,
+            )?,
+        ])?;
+        let signature = self.key.sign(&signed_attrs.to_der()?);
+
+        let tbs = &self.certificate.tbs_certificate;
+        let signer_info = SignerInfo {
+            version: CmsVersion::V1,
+            sid: SignerIdentifier::IssuerAndSerialNumber(IssuerAndSerialNumber {
+                issuer: tbs.issuer.clone(),
+                serial_number: tbs.serial_number.clone(),
+            }),
+            digest_alg: algorithm(ID_SHA512),
+            signed_attrs: Some(signed_attrs),
+            signature_algorithm: algorithm(ID_ED25519),
+            signature: OctetString::new(signature.to_bytes().to_vec())?,
+            unsigned_attrs: None,
+        };
+        let certificates = if request.cert_req {
+            Some(CertificateSet(SetOfVec::try_from(vec![
+                CertificateChoices::Certificate(self.certificate.clone()),
+            ])?))
+        } else {
+            None
+        };
+
+        let signed_data = SignedData {
+            version: CmsVersion::V3,
+            digest_algorithms: SetOfVec::try_from(vec![algorithm(ID_SHA512)])?,
+            encap_content_info: EncapsulatedContentInfo {
+                econtent_type: ID_CT_TST_INFO,
+                econtent: Some(Any::encode_from(&OctetString::new(econtent)?)?),
+            },
+            certificates,
+            crls: None,
+            signer_infos: SignerInfos(SetOfVec::try_from(vec![signer_info])?),
+        };
+        Ok(ContentInfo {
+            content_type: ID_SIGNED_DATA,
+            content: Any::encode_from(&signed_data)?,
+        })
+    }
+
+    /// Serves RFC 3161 over HTTP on a loopback port; returns the TSA URL
+    ///
+    /// The listener runs on a background thread for the rest of the process.
+    pub fn spawn(self) -> std::io::Result<String> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let url = format!("http://{}/tsr", listener.local_addr()?);
+
+        std::thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                // Errors of a single connection only affect that client
+                let _ = self.handle_http(stream);
+            }
+        });
+        Ok(url)
+    }
+
+    fn handle_http(&self, stream: std::net::TcpStream) -> std::io::Result<()> {
+        let mut reader = BufReader::new(stream.try_clone()?);
+        let mut content_length = 0usize;
+        loop {
+            let mut line = String::new();
+            if reader.read_line(&mut line)? == 0 || line == "\r\n" {
+                break;
+            }
+            if let Some((name, value)) = line.split_once(':') {
+                if name.eq_ignore_ascii_case("content-length") {
+                    content_length = value.trim().parse().unwrap_or(0);
+                }
+            }
+        }
+        let mut body = vec![0u8; content_length];
+        reader.read_exact(&mut body)?;
+
+        let response = self.respond(&body);
+        let mut stream = stream;
+        write!(
+            stream,
+            "HTTP/1.1 200 OK\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+            CONTENT_TYPE_REPLY,
+            response.len()
+        )?;
+        stream.write_all(&response)?;
+        stream.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TIP: &str = "0x3333333333333333333333333333333333333333333333333333333333333333";
+
+    fn token_for(tsa: &LocalTsa, tip: &str) -> Vec<u8> {
+        let request = build_request(tip, 42).unwrap();
+        let response = tsa.respond(&request.to_der().unwrap());
+        token_from_response(&response).unwrap().to_der().unwrap()
+    }
+
+    #[test]
+    fn test_request_der_roundtrip() {
+        let request = build_request(TIP, 42).unwrap();
+        let decoded = TimeStampReq::from_der(&request.to_der().unwrap()).unwrap();
+        assert_eq!(decoded, request);
+        assert_eq!(decoded.message_imprint.hashed_message.as_bytes().len(), 32);
+        assert!(decoded.cert_req);
+
+        assert!(build_request("not hex", 1).is_err());
+    }
+
+    #[test]
+    fn test_local_tsa_token_verifies() {
+        let tsa = LocalTsa::new("CAP Test TSA").unwrap();
+        let token = token_for(&tsa, TIP);
+
+        let pinned = verify_token(&token, TIP, Some(tsa.certificate())).unwrap();
+        assert!(pinned.certificate_pinned);
+        assert_eq!(pinned.tsa, "CN=CAP Test TSA");
+        assert_eq!(pinned.nonce, Some(Uint::new(&42u64.to_be_bytes()).unwrap()));
+        assert_eq!(pinned.policy, LOCAL_TSA_POLICY.to_string());
+
+        // Without a configured certificate the embedded one is used
+        let embedded = verify_token(&token, TIP, None).unwrap();
+        assert!(!embedded.certificate_pinned);
+    }
+
+    #[test]
+    fn test_token_rejected_for_other_tip_or_tsa() {
+        let tsa = LocalTsa::new("CAP Test TSA").unwrap();
+        let other = LocalTsa::new("CAP Test TSA").unwrap();
+        let token = token_for(&tsa, TIP);
+
+        let err = verify_token(&token, "0x44", Some(tsa.certificate())).unwrap_err();
+        assert!(err.to_string().contains("message imprint"));
+
+        let err = verify_token(&token, TIP, Some(other.certificate())).unwrap_err();
+        assert!(err.to_string().contains("configured TSA certificate"));
+    }
+
+    #[test]
+    fn test_tampered_token_rejected() {
+        let tsa = LocalTsa::new("CAP Test TSA").unwrap();
+        let token = token_for(&tsa, TIP);
+
+        // Flip one bit of the signer's signature
+        let content = ContentInfo::from_der(&token).unwrap();
+        let mut signed_data: SignedData = content.content.decode_as().unwrap();
+        let mut signer = signed_data.signer_infos.0.as_slice()[0].clone();
+        let mut signature = signer.signature.as_bytes().to_vec();
+        signature[0] ^= 0x01;
+        signer.signature = OctetString::new(signature).unwrap();
+        signed_data.signer_infos = SignerInfos(SetOfVec::try_from(vec![signer]).unwrap());
+        let tampered = ContentInfo {
+            content_type: ID_SIGNED_DATA,
+            content: Any::encode_from(&signed_data).unwrap(),
+        }
+        .to_der()
+        .unwrap();
+
+        let err = verify_token(&tampered, TIP, Some(tsa.certificate())).unwrap_err();
+        assert!(err.to_string().contains("signature invalid"));
+    }
+
+    #[test]
+    fn test_invalid_request_is_rejected() {
+        let tsa = LocalTsa::new("CAP Test TSA").unwrap();
+        let response = tsa.respond(b"garbage");
+        let err = token_from_response(&response).unwrap_err();
+        assert!(err.to_string().contains("status 2"));
+    }
+
+    #[test]
+    fn test_certificate_pem_roundtrip() {
+        let tsa = LocalTsa::new("CAP Test TSA").unwrap();
+        let pem = tsa.certificate_pem().unwrap();
+        assert_eq!(
+            &parse_certificate(pem.as_bytes()).unwrap(),
+            tsa.certificate()
+        );
+        let der = tsa.certificate().to_der().unwrap();
+        assert_eq!(&parse_certificate(&der).unwrap(), tsa.certificate());
+    }
+
+    #[test]
+    fn test_parse_gen_time_with_fraction() {
+        let time = |s: &str| Any::new(Tag::GeneralizedTime, s.as_bytes().to_vec()).unwrap();
+        assert_eq!(
+            parse_gen_time(&time("20251030120000Z"))
+                .unwrap()
+                .to_rfc3339(),
+            "2025-10-30T12:00:00+00:00"
+        );
+        assert_eq!(
+            parse_gen_time(&time("20251030120000.25Z"))
+                .unwrap()
+                .timestamp_millis()
+                % 1000,
+            250
+        );
+        assert!(parse_gen_time(&time("20251030120000")).is_err());
+    }
+
+    #[test]
+    fn test_http_roundtrip() {
+        let tsa = LocalTsa::new("CAP Test TSA").unwrap();
+        let certificate = tsa.certificate().clone();
+        let url = tsa.spawn().unwrap();
+
+        let request = build_request(TIP, 7).unwrap();
+        let response = request_timestamp(&url, request.to_der().unwrap()).unwrap();
+        let token = token_from_response(&response).unwrap().to_der().unwrap();
+        let verified = verify_token(&token, TIP, Some(&certificate)).unwrap();
+        assert_eq!(verified.nonce, request.nonce);
+    }
+}
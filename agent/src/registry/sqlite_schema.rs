@@ -0,0 +1,524 @@
+//! SQLite Schema Migrations - Versionierte, transaktionale Schema-Upgrades
+//!
+//! `registry.sqlite` wird von `SqliteRegistryStore` und `SqliteBlobStore`
+//! gemeinsam genutzt; schreibende Öffnungen bringen die Datei über `migrate`
+//! auf die neueste Schema-Version, lesende prüfen sie nur (`ensure_current`).
+//! Angewendete Schritte stehen in der Tabelle `schema_version`, jeder Schritt
+//! läuft in einer eigenen `BEGIN IMMEDIATE`-Transaktion.
+//!
+//! Datenbanken aus der Zeit vor dieser Tabelle haben Version 0. Jeder Schritt
+//! toleriert bereits vorhandene Teile seines Schemas (Tabellen über
+//! `IF NOT EXISTS`, Spalten über `add_column`), sodass solche Datenbanken
+//! unabhängig vom erzeugenden Release direkt aktualisiert werden.
+//!
+//! - Dry Run: alle ausstehenden Schritte werden ausgeführt und zurückgerollt
+//! - Backup: bestehende Datenbanken werden vor dem ersten Schritt kopiert (`VACUUM INTO`)
+
+use chrono::Utc;
+use rusqlite::{Connection, Transaction, TransactionBehavior};
+use serde::Serialize;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+/// Ein Schema-Schritt
+pub struct Migration {
+    pub version: u32,
+    pub description: &'static str,
+    up: fn(&Transaction) -> rusqlite::Result<()>,
+}
+
+/// Alle Schritte in aufsteigender Reihenfolge
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        description: "Registry-Einträge und Metadaten",
+        up: |tx| {
+            tx.execute_batch(
+                r#"
+                CREATE TABLE IF NOT EXISTS registry_meta (
+                    key TEXT PRIMARY KEY,
+                    value TEXT NOT NULL
+                );
+
+                CREATE TABLE IF NOT EXISTS registry_entries (
+                    id TEXT PRIMARY KEY,
+                    manifest_hash TEXT NOT NULL,
+                    proof_hash TEXT NOT NULL,
+                    timestamp_file TEXT,
+                    registered_at TEXT NOT NULL,
+                    signature TEXT,
+                    public_key TEXT
+                );
+
+                CREATE INDEX IF NOT EXISTS idx_registry_hashes
+                    ON registry_entries (manifest_hash, proof_hash);
+
+                INSERT OR IGNORE INTO registry_meta(key, value) VALUES('registry_version', '1.0');
+            "#,
+            )
+        },
+    },
+    Migration {
+        version: 2,
+        description: "BLOB- und Self-Verification-Felder (v0.9)",
+        up: |tx| {
+            for column in [
+                "blob_manifest",
+                "blob_proof",
+                "blob_wasm",
+                "blob_abi",
+                "selfverify_status",
+                "selfverify_at",
+                "verifier_name",
+                "verifier_version",
+            ] {
+                add_column(tx, "registry_entries", column, "TEXT")?;
+            }
+            Ok(())
+        },
+    },
+    Migration {
+        version: 3,
+        description: "Key-Management-Felder (v0.10)",
+        up: |tx| {
+            add_column(tx, "registry_entries", "kid", "TEXT")?;
+            add_column(tx, "registry_entries", "signature_scheme", "TEXT")
+        },
+    },
+    Migration {
+        version: 4,
+        description: "inhaltsadressierter BLOB-Store (v0.10.9)",
+        up: |tx| {
+            tx.execute_batch(
+                r#"
+                CREATE TABLE IF NOT EXISTS blobs (
+                    blob_id TEXT PRIMARY KEY,
+                    size INTEGER NOT NULL,
+                    media_type TEXT NOT NULL,
+                    data BLOB NOT NULL,
+                    refcount INTEGER NOT NULL DEFAULT 0
+                );
+
+                CREATE INDEX IF NOT EXISTS idx_blobs_refcount ON blobs(refcount);
+            "#,
+            )
+        },
+    },
+    Migration {
+        version: 5,
+        description: "Lifecycle-Records (Widerruf/Ersetzung)",
+        up: |tx| {
+            tx.execute_batch(
+                r#"
+                CREATE TABLE IF NOT EXISTS registry_lifecycle (
+                    seq INTEGER PRIMARY KEY AUTOINCREMENT,
+                    entry_id TEXT NOT NULL,
+                    manifest_hash TEXT NOT NULL,
+                    proof_hash TEXT NOT NULL,
+                    action TEXT NOT NULL,
+                    reason TEXT NOT NULL,
+                    successor_id TEXT,
+                    effective_at TEXT NOT NULL,
+                    recorded_at TEXT NOT NULL,
+                    signature TEXT,
+                    public_key TEXT,
+                    kid TEXT,
+                    signature_scheme TEXT
+                );
+
+                CREATE INDEX IF NOT EXISTS idx_registry_lifecycle_entry
+                    ON registry_lifecycle (entry_id);
+            "#,
+            )
+        },
+    },
+    Migration {
+        version: 6,
+        description: "Transparency-Log (seq = Blatt-Index + 1)",
+        up: |tx| {
+            tx.execute_batch(
+                r#"
+                CREATE TABLE IF NOT EXISTS registry_log (
+                    seq INTEGER PRIMARY KEY AUTOINCREMENT,
+                    entry_id TEXT NOT NULL,
+                    manifest_hash TEXT NOT NULL,
+                    proof_hash TEXT NOT NULL,
+                    registered_at TEXT NOT NULL
+                );
+            "#,
+            )
+        },
+    },
+    Migration {
+        version: 7,
+        description: "Policy-Referenz und Query-Indizes",
+        up: |tx| {
+            add_column(tx, "registry_entries", "policy_id", "TEXT")?;
+            tx.execute_batch(
+                r#"
+                CREATE INDEX IF NOT EXISTS idx_registry_kid ON registry_entries (kid);
+                CREATE INDEX IF NOT EXISTS idx_registry_policy ON registry_entries (policy_id);
+                CREATE INDEX IF NOT EXISTS idx_registry_selfverify
+                    ON registry_entries (selfverify_status);
+                CREATE INDEX IF NOT EXISTS idx_registry_registered_at
+                    ON registry_entries (julianday(registered_at));
+            "#,
+            )
+        },
+    },
+];
+
+/// Neueste Schema-Version
+pub fn latest_version() -> u32 {
+    MIGRATIONS.last().map(|m| m.version).unwrap_or(0)
+}
+
+/// Fügt eine Spalte hinzu, falls sie fehlt
+fn add_column(tx: &Transaction, table: &str, column: &str, sql_type: &str) -> rusqlite::Result<()> {
+    let exists = tx
+        .prepare(&format!(
+            "SELECT 1 FROM pragma_table_info('{}') WHERE name = ?",
+            table
+        ))?
+        .exists([column])?;
+    if !exists {
+        tx.execute(
+            &format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, sql_type),
+            [],
+        )?;
+    }
+    Ok(())
+}
+
+fn ensure_version_table(conn: &Connection) -> rusqlite::Result<()> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS schema_version (
+            version INTEGER PRIMARY KEY,
+            description TEXT NOT NULL,
+            applied_at TEXT NOT NULL
+        );
+    "#,
+    )
+}
+
+fn read_version(conn: &Connection) -> rusqlite::Result<u32> {
+    let exists = conn
+        .prepare("SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'schema_version'")?
+        .exists([])?;
+    if !exists {
+        return Ok(0);
+    }
+    conn.query_row(
+        "SELECT COALESCE(MAX(version), 0) FROM schema_version",
+        [],
+        |row| row.get(0),
+    )
+}
+
+/// Aktuelle Schema-Version (0 = vor Einführung der Versionierung oder leer)
+#[allow(dead_code)] // Public API - schema inspection
+pub fn current_version(conn: &Connection) -> Result<u32, Box<dyn Error>> {
+    Ok(read_version(conn)?)
+}
+
+/// Prüft, ohne etwas zu ändern, ob die Datenbank die neueste Schema-Version hat
+///
+/// Für lesende Öffnungen: Veraltete Datenbanken werden abgelehnt und müssen
+/// explizit migriert werden (`registry schema-migrate` oder ein schreibendes
+/// Kommando).
+pub fn ensure_current(conn: &Connection) -> Result<u32, Box<dyn Error>> {
+    let version = read_version(conn)?;
+    let latest = latest_version();
+    if version > latest {
+        return Err(format!(
+            "Schema-Version {} der Datenbank ist neuer als die unterstützte Version {}",
+            version, latest
+        )
+        .into());
+    }
+    if version < latest {
+        return Err(format!(
+            "Schema-Version {} der Datenbank ist veraltet (aktuell: {}); `registry schema-migrate` ausführen",
+            version, latest
+        )
+        .into());
+    }
+    Ok(version)
+}
+
+/// Ob die Datenbank bereits Tabellen enthält
+fn has_tables(conn: &Connection) -> rusqlite::Result<bool> {
+    conn.prepare("SELECT 1 FROM sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%'")?
+        .exists([])
+}
+
+/// Angewendeter (oder im Dry Run geprüfter) Schritt
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AppliedMigration {
+    pub version: u32,
+    pub description: String,
+}
+
+/// Ergebnis eines Migrationslaufs
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MigrationReport {
+    pub from_version: u32,
+    pub to_version: u32,
+    pub applied: Vec<AppliedMigration>,
+    pub dry_run: bool,
+    /// Backup vor dem ersten Schritt (nur bei bestehenden Datenbanken)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub backup: Option<PathBuf>,
+}
+
+/// Optionen für `migrate`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MigrationOptions {
+    /// Schritte ausführen und zurückrollen
+    pub dry_run: bool,
+    /// Bestehende Datenbank vor dem ersten Schritt sichern
+    pub backup: bool,
+}
+
+impl Default for MigrationOptions {
+    fn default() -> Self {
+        Self {
+            dry_run: false,
+            backup: true,
+        }
+    }
+}
+
+/// Backup-Pfad: `<datei>.v<version>.<zeitstempel>.bak`
+fn backup_path(path: &Path, version: u32) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(format!(
+        ".v{}.{}.bak",
+        version,
+        Utc::now().format("%Y%m%dT%H%M%S%.3fZ")
+    ));
+    PathBuf::from(name)
+}
+
+/// Bringt die Datenbank auf die neueste Schema-Version
+///
+/// `path` ist der Pfad der Datenbank (für das Backup; `None` bei In-Memory).
+/// Neuere, unbekannte Schema-Versionen werden abgelehnt.
+pub fn migrate(
+    conn: &mut Connection,
+    path: Option<&Path>,
+    options: MigrationOptions,
+) -> Result<MigrationReport, Box<dyn Error>> {
+    let from_version = read_version(conn)?;
+    let latest = latest_version();
+    if from_version > latest {
+        return Err(format!(
+            "Schema-Version {} der Datenbank ist neuer als die unterstützte Version {}",
+            from_version, latest
+        )
+        .into());
+    }
+
+    let mut report = MigrationReport {
+        from_version,
+        to_version: from_version,
+        applied: Vec::new(),
+        dry_run: options.dry_run,
+        backup: None,
+    };
+    let pending: Vec<&Migration> = MIGRATIONS
+        .iter()
+        .filter(|m| m.version > from_version)
+        .collect();
+    if pending.is_empty() {
+        return Ok(report);
+    }
+
+    if options.dry_run {
+        // Alle Schritte in einer Transaktion ausführen und zurückrollen
+        let tx = conn.transaction_with_behavior(TransactionBehavior::Immediate)?;
+        for migration in &pending {
+            (migration.up)(&tx).map_err(|e| {
+                format!(
+                    "Schema-Migration {} ({}) fehlgeschlagen: {}",
+                    migration.version, migration.description, e
+                )
+            })?;
+            report.applied.push(AppliedMigration {
+                version: migration.version,
+                description: migration.description.to_string(),
+            });
+        }
+        tx.rollback()?;
+        report.to_version = latest;
+        return Ok(report);
+    }
+
+    if options.backup && has_tables(conn)? {
+        if let Some(path) = path {
+            let backup = backup_path(path, from_version);
+            conn.execute("VACUUM INTO ?", [backup.to_string_lossy()])?;
+            report.backup = Some(backup);
+        }
+    }
+
+    ensure_version_table(conn)?;
+    for migration in pending {
+        let tx = conn.transaction_with_behavior(TransactionBehavior::Immediate)?;
+        // Ein paralleler Prozess kann den Schritt bereits angewendet haben
+        if read_version(&tx)? >= migration.version {
+            continue;
+        }
+        (migration.up)(&tx).map_err(|e| {
+            format!(
+                "Schema-Migration {} ({}) fehlgeschlagen: {}",
+                migration.version, migration.description, e
+            )
+        })?;
+        tx.execute(
+            "INSERT INTO schema_version(version, description, applied_at) VALUES(?, ?, ?)",
+            rusqlite::params![
+                migration.version,
+                migration.description,
+                Utc::now().to_rfc3339()
+            ],
+        )?;
+        tx.commit()?;
+        report.applied.push(AppliedMigration {
+            version: migration.version,
+            description: migration.description.to_string(),
+        });
+    }
+    report.to_version = read_version(conn)?;
+    Ok(report)
+}
+
+/// Öffnet eine Datenbankdatei und migriert sie (CLI `registry schema-migrate`)
+pub fn migrate_file(
+    path: &Path,
+    options: MigrationOptions,
+) -> Result<MigrationReport, Box<dyn Error>> {
+    if !path.exists() {
+        return Err(format!("Datenbank nicht gefunden: {}", path.display()).into());
+    }
+    let mut conn = Connection::open(path)?;
+    migrate(&mut conn, Some(path), options)
+}
+
+/// Pfad einer Datenbankdatei (`None` für `:memory:`)
+pub fn file_path(path: &Path) -> Option<&Path> {
+    if path.as_os_str() == ":memory:" {
+        None
+    } else {
+        Some(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn columns(conn: &Connection, table: &str) -> Vec<String> {
+        let mut stmt = conn
+            .prepare(&format!("SELECT name FROM pragma_table_info('{}')", table))
+            .unwrap();
+        let rows = stmt.query_map([], |row| row.get(0)).unwrap();
+        rows.map(|r| r.unwrap()).collect()
+    }
+
+    #[test]
+    fn test_migrations_are_ordered() {
+        for pair in MIGRATIONS.windows(2) {
+            assert_eq!(pair[1].version, pair[0].version + 1);
+        }
+        assert_eq!(MIGRATIONS[0].version, 1);
+    }
+
+    #[test]
+    fn test_fresh_database() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        let report = migrate(&mut conn, None, MigrationOptions::default()).unwrap();
+        assert_eq!(report.from_version, 0);
+        assert_eq!(report.to_version, latest_version());
+        assert_eq!(report.applied.len(), MIGRATIONS.len());
+        assert!(report.backup.is_none());
+        assert!(columns(&conn, "registry_entries").contains(&"policy_id".to_string()));
+        assert!(!columns(&conn, "blobs").is_empty());
+
+        // Ein zweiter Lauf ändert nichts
+        let again = migrate(&mut conn, None, MigrationOptions::default()).unwrap();
+        assert!(again.applied.is_empty());
+        assert_eq!(again.from_version, latest_version());
+    }
+
+    #[test]
+    fn test_legacy_database_with_backup() {
+        let path = std::env::temp_dir().join("test_sqlite_schema_legacy.sqlite");
+        std::fs::remove_file(&path).ok();
+        let mut conn = Connection::open(&path).unwrap();
+        // v0.9-Schema: BLOB-Felder, kein kid, kein BLOB-Store
+        conn.execute_batch(
+            "CREATE TABLE registry_entries (
+                id TEXT PRIMARY KEY, manifest_hash TEXT NOT NULL, proof_hash TEXT NOT NULL,
+                timestamp_file TEXT, registered_at TEXT NOT NULL, signature TEXT, public_key TEXT,
+                blob_manifest TEXT, blob_proof TEXT, blob_wasm TEXT, blob_abi TEXT,
+                selfverify_status TEXT, selfverify_at TEXT, verifier_name TEXT, verifier_version TEXT
+            );
+            INSERT INTO registry_entries(id, manifest_hash, proof_hash, registered_at)
+                VALUES('proof_001', '0xm', '0xp', '2026-01-01T00:00:00Z');",
+        )
+        .unwrap();
+
+        // Ein Dry Run ändert nichts
+        let dry = migrate(
+            &mut conn,
+            Some(&path),
+            MigrationOptions {
+                dry_run: true,
+                backup: true,
+            },
+        )
+        .unwrap();
+        assert_eq!(dry.applied.len(), MIGRATIONS.len());
+        assert!(dry.backup.is_none());
+        assert_eq!(current_version(&conn).unwrap(), 0);
+        assert!(!columns(&conn, "registry_entries").contains(&"kid".to_string()));
+
+        let report = migrate(&mut conn, Some(&path), MigrationOptions::default()).unwrap();
+        assert_eq!(report.to_version, latest_version());
+        let backup = report.backup.expect("backup of existing database");
+        let entry_columns = columns(&conn, "registry_entries");
+        for column in ["kid", "signature_scheme", "policy_id"] {
+            assert!(entry_columns.contains(&column.to_string()));
+        }
+        let count: i64 = conn
+            .query_row("SELECT COUNT(*) FROM registry_entries", [], |r| r.get(0))
+            .unwrap();
+        assert_eq!(count, 1);
+
+        // Das Backup enthält den vorherigen Stand
+        let old = Connection::open(&backup).unwrap();
+        assert_eq!(current_version(&old).unwrap(), 0);
+        assert!(!columns(&old, "registry_entries").contains(&"kid".to_string()));
+
+        drop(old);
+        drop(conn);
+        std::fs::remove_file(&backup).ok();
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_newer_schema_is_rejected() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        migrate(&mut conn, None, MigrationOptions::default()).unwrap();
+        assert_eq!(ensure_current(&conn).unwrap(), latest_version());
+        conn.execute(
+            "INSERT INTO schema_version(version, description, applied_at) VALUES(?, 'future', '')",
+            [latest_version() + 1],
+        )
+        .unwrap();
+        assert!(migrate(&mut conn, None, MigrationOptions::default()).is_err());
+        assert!(ensure_current(&conn).is_err());
+    }
+}
@@ -4,18 +4,31 @@
 //! - RegistryStore trait for abstraction
 //! - JsonRegistryStore for JSON file storage
 //! - SqliteRegistryStore for SQLite database storage
+//!
+//! Both backends store the entries, their lifecycle records (revocation,
+//! supersession) and the transparency log of all additions.
 
 use std::error::Error;
 use std::fs;
 use std::path::Path;
 
 use super::entry::RegistryEntry;
+use super::lifecycle::LifecycleRecord;
+use super::query::{RegistryPage, RegistryQuery};
+use super::sqlite_schema;
+use super::transparency::{LogLeaf, TransparencyLog};
 
 /// Lokale Registry-Struktur
-#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct Registry {
     pub registry_version: String,
     pub entries: Vec<RegistryEntry>,
+    /// Widerrufe und Ersetzungen (v1.0 Registries ohne Feld: leer)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub lifecycle: Vec<LifecycleRecord>,
+    /// Append-only Log aller hinzugefügten Einträge (v1.0 Registries ohne Feld: leer)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub transparency_log: Vec<LogLeaf>,
 }
 
 #[allow(dead_code)]
@@ -32,6 +45,8 @@ impl Registry {
         Registry {
             registry_version: "1.0".to_string(),
             entries: Vec::new(),
+            lifecycle: Vec::new(),
+            transparency_log: Vec::new(),
         }
     }
 
@@ -56,18 +71,20 @@ impl Registry {
         proof_hash: String,
         timestamp_file: Option<String>,
     ) -> String {
-        let id = format!("proof_{:03}", self.entries.len() + 1);
-        let entry = RegistryEntry::new(
-            id.clone(),
-            manifest_hash,
-            proof_hash,
-            chrono::Utc::now().to_rfc3339(),
-        )
-        .with_timestamp_file(timestamp_file);
-        self.entries.push(entry);
+        let registered_at = chrono::Utc::now().to_rfc3339();
+        let id = RegistryEntry::derive_id(&manifest_hash, &proof_hash, &registered_at);
+        let entry = RegistryEntry::new(id.clone(), manifest_hash, proof_hash, registered_at)
+            .with_timestamp_file(timestamp_file);
+        self.append_entry(entry);
         id
     }
 
+    /// Hängt einen Eintrag an und protokolliert ihn im Transparency-Log
+    pub fn append_entry(&mut self, entry: RegistryEntry) {
+        self.transparency_log.push(LogLeaf::from_entry(&entry));
+        self.entries.push(entry);
+    }
+
     /// Sucht einen Eintrag anhand von Manifest- und Proof-Hash
     pub fn find_entry(&self, manifest_hash: &str, proof_hash: &str) -> Option<&RegistryEntry> {
         self.entries
@@ -84,6 +101,20 @@ impl Registry {
     pub fn count(&self) -> usize {
         self.entries.len()
     }
+
+    /// Lifecycle-Records zu einem Eintrag
+    pub fn lifecycle_of(&self, entry_id: &str) -> Vec<LifecycleRecord> {
+        self.lifecycle
+            .iter()
+            .filter(|r| r.entry_id == entry_id)
+            .cloned()
+            .collect()
+    }
+
+    /// Transparency-Log der Registry
+    pub fn log(&self) -> TransparencyLog {
+        TransparencyLog::new(self.transparency_log.clone())
+    }
 }
 
 // ============================================================================
@@ -105,7 +136,7 @@ pub trait RegistryStore {
     /// Saves the complete registry
     fn save(&self, reg: &Registry) -> Result<(), Box<dyn Error>>;
 
-    /// Adds a single entry
+    /// Adds a single entry and appends it to the transparency log
     fn add_entry(&self, entry: RegistryEntry) -> Result<(), Box<dyn Error>>;
 
     /// Finds entry by manifest and proof hashes
@@ -117,6 +148,28 @@ pub trait RegistryStore {
 
     /// Lists all entries
     fn list(&self) -> Result<Vec<RegistryEntry>, Box<dyn Error>>;
+
+    /// Filters, sorts and paginates entries
+    fn query(&self, query: &RegistryQuery) -> Result<RegistryPage, Box<dyn Error>> {
+        query.validate()?;
+        Ok(query.apply(self.list()?))
+    }
+
+    /// Finds entry by ID
+    fn find_by_id(&self, id: &str) -> Result<Option<RegistryEntry>, Box<dyn Error>> {
+        Ok(self.list()?.into_iter().find(|e| e.id == id))
+    }
+
+    /// Appends a lifecycle record (revocation/supersession)
+    fn add_lifecycle_record(&self, record: LifecycleRecord) -> Result<(), Box<dyn Error>>;
+
+    /// Lifecycle records of an entry, in insertion order
+    fn lifecycle_records(&self, entry_id: &str) -> Result<Vec<LifecycleRecord>, Box<dyn Error>>;
+
+    /// Transparency log of all additions
+    fn transparency_log(&self) -> Result<TransparencyLog, Box<dyn Error>> {
+        Ok(self.load()?.log())
+    }
 }
 
 // ============================================================================
@@ -131,10 +184,7 @@ pub struct JsonRegistryStore {
 impl RegistryStore for JsonRegistryStore {
     fn load(&self) -> Result<Registry, Box<dyn Error>> {
         if !self.path.exists() {
-            return Ok(Registry {
-                registry_version: "1.0".to_string(),
-                entries: Vec::new(),
-            });
+            return Ok(Registry::new());
         }
         let content = fs::read_to_string(&self.path)?;
         let registry: Registry = serde_json::from_str(&content)?;
@@ -149,7 +199,7 @@ impl RegistryStore for JsonRegistryStore {
 
     fn add_entry(&self, entry: RegistryEntry) -> Result<(), Box<dyn Error>> {
         let mut reg = self.load()?;
-        reg.entries.push(entry);
+        reg.append_entry(entry);
         self.save(&reg)
     }
 
@@ -168,12 +218,29 @@ impl RegistryStore for JsonRegistryStore {
     fn list(&self) -> Result<Vec<RegistryEntry>, Box<dyn Error>> {
         Ok(self.load()?.entries)
     }
+
+    fn add_lifecycle_record(&self, record: LifecycleRecord) -> Result<(), Box<dyn Error>> {
+        let mut reg = self.load()?;
+        reg.lifecycle.push(record);
+        self.save(&reg)
+    }
+
+    fn lifecycle_records(&self, entry_id: &str) -> Result<Vec<LifecycleRecord>, Box<dyn Error>> {
+        Ok(self.load()?.lifecycle_of(entry_id))
+    }
 }
 
 // ============================================================================
 // SQLite Registry Store
 // ============================================================================
 
+/// Columns of `registry_entries` in `row_to_entry` order
+const ENTRY_COLUMNS: &str =
+    "id, manifest_hash, proof_hash, timestamp_file, registered_at, signature, public_key,
+                    blob_manifest, blob_proof, blob_wasm, blob_abi,
+                    selfverify_status, selfverify_at, verifier_name, verifier_version,
+                    kid, signature_scheme, policy_id";
+
 /// SQLite-based Registry Store
 pub struct SqliteRegistryStore {
     conn: std::cell::RefCell<rusqlite::Connection>,
@@ -183,53 +250,39 @@ pub struct SqliteRegistryStore {
 
 impl SqliteRegistryStore {
     /// Opens or creates a SQLite registry database
+    ///
+    /// Pending schema migrations are applied (see `sqlite_schema`); existing
+    /// databases are backed up first.
     pub fn open(path: &Path) -> Result<Self, Box<dyn Error>> {
-        let conn = rusqlite::Connection::open(path)?;
-
-        // Initialize schema
+        let mut conn = rusqlite::Connection::open(path)?;
         conn.execute_batch(
             r#"
             PRAGMA journal_mode = WAL;
             PRAGMA synchronous = NORMAL;
-
-            CREATE TABLE IF NOT EXISTS registry_meta (
-                key TEXT PRIMARY KEY,
-                value TEXT NOT NULL
-            );
-
-            CREATE TABLE IF NOT EXISTS registry_entries (
-                id TEXT PRIMARY KEY,
-                manifest_hash TEXT NOT NULL,
-                proof_hash TEXT NOT NULL,
-                timestamp_file TEXT,
-                registered_at TEXT NOT NULL,
-                signature TEXT,
-                public_key TEXT,
-                -- BLOB fields (v0.9)
-                blob_manifest TEXT,
-                blob_proof TEXT,
-                blob_wasm TEXT,
-                blob_abi TEXT,
-                -- Self-verification fields (v0.9)
-                selfverify_status TEXT,
-                selfverify_at TEXT,
-                verifier_name TEXT,
-                verifier_version TEXT,
-                -- Key management fields (v0.10)
-                kid TEXT,
-                signature_scheme TEXT
-            );
-
-            CREATE INDEX IF NOT EXISTS idx_registry_hashes
-                ON registry_entries (manifest_hash, proof_hash);
         "#,
         )?;
+        sqlite_schema::migrate(
+            &mut conn,
+            sqlite_schema::file_path(path),
+            sqlite_schema::MigrationOptions::default(),
+        )?;
 
-        // Ensure version
-        conn.execute(
-            "INSERT OR IGNORE INTO registry_meta(key, value) VALUES('registry_version', '1.0')",
-            [],
+        Ok(Self {
+            conn: std::cell::RefCell::new(conn),
+            path: path.to_path_buf(),
+        })
+    }
+
+    /// Öffnet eine bestehende SQLite-Registry nur lesend
+    ///
+    /// Legt weder die Datei an noch migriert sie das Schema: Datenbanken mit
+    /// veraltetem Schema werden abgelehnt (siehe `sqlite_schema::ensure_current`).
+    pub fn open_read_only(path: &Path) -> Result<Self, Box<dyn Error>> {
+        let conn = rusqlite::Connection::open_with_flags(
+            path,
+            rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY | rusqlite::OpenFlags::SQLITE_OPEN_NO_MUTEX,
         )?;
+        sqlite_schema::ensure_current(&conn).map_err(|e| format!("{}: {}", path.display(), e))?;
 
         Ok(Self {
             conn: std::cell::RefCell::new(conn),
@@ -256,6 +309,7 @@ impl SqliteRegistryStore {
             verifier_version: row.get(14).ok(),
             kid: row.get(15).ok(),
             signature_scheme: row.get(16).ok(),
+            policy_id: row.get(17).ok(),
         })
     }
 
@@ -268,8 +322,8 @@ impl SqliteRegistryStore {
                 id, manifest_hash, proof_hash, timestamp_file, registered_at, signature, public_key,
                 blob_manifest, blob_proof, blob_wasm, blob_abi,
                 selfverify_status, selfverify_at, verifier_name, verifier_version,
-                kid, signature_scheme
-             ) VALUES(?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+                kid, signature_scheme, policy_id
+             ) VALUES(?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
             rusqlite::params![
                 &entry.id,
                 &entry.manifest_hash,
@@ -287,24 +341,123 @@ impl SqliteRegistryStore {
                 &entry.verifier_name,
                 &entry.verifier_version,
                 &entry.kid,
-                &entry.signature_scheme
+                &entry.signature_scheme,
+                &entry.policy_id
             ],
         )?;
         Ok(())
     }
 }
 
-impl RegistryStore for SqliteRegistryStore {
-    fn load(&self) -> Result<Registry, Box<dyn Error>> {
+impl SqliteRegistryStore {
+    fn insert_lifecycle_record(
+        conn: &rusqlite::Connection,
+        record: &LifecycleRecord,
+    ) -> Result<(), Box<dyn Error>> {
+        conn.execute(
+            "INSERT INTO registry_lifecycle(
+                entry_id, manifest_hash, proof_hash, action, reason, successor_id,
+                effective_at, recorded_at, signature, public_key, kid, signature_scheme
+             ) VALUES(?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            rusqlite::params![
+                &record.entry_id,
+                &record.manifest_hash,
+                &record.proof_hash,
+                record.action.to_string(),
+                record.reason.to_string(),
+                &record.successor_id,
+                &record.effective_at,
+                &record.recorded_at,
+                &record.signature,
+                &record.public_key,
+                &record.kid,
+                &record.signature_scheme
+            ],
+        )?;
+        Ok(())
+    }
+
+    fn insert_log_leaf(conn: &rusqlite::Connection, leaf: &LogLeaf) -> Result<(), Box<dyn Error>> {
+        conn.execute(
+            "INSERT INTO registry_log(entry_id, manifest_hash, proof_hash, registered_at)
+             VALUES(?, ?, ?, ?)",
+            rusqlite::params![
+                &leaf.entry_id,
+                &leaf.manifest_hash,
+                &leaf.proof_hash,
+                &leaf.registered_at
+            ],
+        )?;
+        Ok(())
+    }
+
+    fn query_log(&self) -> Result<Vec<LogLeaf>, Box<dyn Error>> {
         let conn = self.conn.borrow();
         let mut stmt = conn.prepare(
-            "SELECT id, manifest_hash, proof_hash, timestamp_file, registered_at, signature, public_key,
-                    blob_manifest, blob_proof, blob_wasm, blob_abi,
-                    selfverify_status, selfverify_at, verifier_name, verifier_version,
-                    kid, signature_scheme
-             FROM registry_entries
-             ORDER BY registered_at DESC",
+            "SELECT entry_id, manifest_hash, proof_hash, registered_at
+             FROM registry_log
+             ORDER BY seq",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok(LogLeaf {
+                entry_id: row.get(0)?,
+                manifest_hash: row.get(1)?,
+                proof_hash: row.get(2)?,
+                registered_at: row.get(3)?,
+            })
+        })?;
+
+        let mut leaves = Vec::new();
+        for row in rows {
+            leaves.push(row?);
+        }
+        Ok(leaves)
+    }
+
+    fn query_lifecycle(
+        &self,
+        entry_id: Option<&str>,
+    ) -> Result<Vec<LifecycleRecord>, Box<dyn Error>> {
+        let conn = self.conn.borrow();
+        let mut stmt = conn.prepare(
+            "SELECT entry_id, manifest_hash, proof_hash, action, reason, successor_id,
+                    effective_at, recorded_at, signature, public_key, kid, signature_scheme
+             FROM registry_lifecycle
+             WHERE ?1 IS NULL OR entry_id = ?1
+             ORDER BY seq",
         )?;
+        let mut rows = stmt.query(rusqlite::params![entry_id])?;
+
+        let mut records = Vec::new();
+        while let Some(row) = rows.next()? {
+            let action: String = row.get(3)?;
+            let reason: String = row.get(4)?;
+            records.push(LifecycleRecord {
+                entry_id: row.get(0)?,
+                manifest_hash: row.get(1)?,
+                proof_hash: row.get(2)?,
+                action: action.parse()?,
+                reason: reason.parse()?,
+                successor_id: row.get(5)?,
+                effective_at: row.get(6)?,
+                recorded_at: row.get(7)?,
+                signature: row.get(8)?,
+                public_key: row.get(9)?,
+                kid: row.get(10)?,
+                signature_scheme: row.get(11)?,
+            });
+        }
+        Ok(records)
+    }
+}
+
+impl RegistryStore for SqliteRegistryStore {
+    fn load(&self) -> Result<Registry, Box<dyn Error>> {
+        let conn = self.conn.borrow();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM registry_entries ORDER BY registered_at DESC",
+            ENTRY_COLUMNS
+        ))?;
 
         let rows = stmt.query_map([], Self::row_to_entry)?;
 
@@ -313,9 +466,14 @@ impl RegistryStore for SqliteRegistryStore {
             entries.push(row?);
         }
 
+        drop(stmt);
+        drop(conn);
+
         Ok(Registry {
             registry_version: "1.0".to_string(),
             entries,
+            lifecycle: self.query_lifecycle(None)?,
+            transparency_log: self.query_log()?,
         })
     }
 
@@ -323,21 +481,33 @@ impl RegistryStore for SqliteRegistryStore {
         let mut conn = self.conn.borrow_mut();
         let tx = conn.transaction()?;
 
-        // Clear existing entries
+        // Clear existing entries, lifecycle records and log
         tx.execute("DELETE FROM registry_entries", [])?;
+        tx.execute("DELETE FROM registry_lifecycle", [])?;
+        tx.execute("DELETE FROM registry_log", [])?;
 
-        // Insert all entries
+        // Insert all entries, lifecycle records and log leaves
         for entry in &reg.entries {
             Self::insert_entry(&tx, entry)?;
         }
+        for record in &reg.lifecycle {
+            Self::insert_lifecycle_record(&tx, record)?;
+        }
+        for leaf in &reg.transparency_log {
+            Self::insert_log_leaf(&tx, leaf)?;
+        }
 
         tx.commit()?;
         Ok(())
     }
 
     fn add_entry(&self, entry: RegistryEntry) -> Result<(), Box<dyn Error>> {
-        let conn = self.conn.borrow();
-        Self::insert_entry(&conn, &entry)
+        let mut conn = self.conn.borrow_mut();
+        let tx = conn.transaction()?;
+        Self::insert_entry(&tx, &entry)?;
+        Self::insert_log_leaf(&tx, &LogLeaf::from_entry(&entry))?;
+        tx.commit()?;
+        Ok(())
     }
 
     fn find_by_hashes(
@@ -346,15 +516,10 @@ impl RegistryStore for SqliteRegistryStore {
         proof_hash: &str,
     ) -> Result<Option<RegistryEntry>, Box<dyn Error>> {
         let conn = self.conn.borrow();
-        let mut stmt = conn.prepare(
-            "SELECT id, manifest_hash, proof_hash, timestamp_file, registered_at, signature, public_key,
-                    blob_manifest, blob_proof, blob_wasm, blob_abi,
-                    selfverify_status, selfverify_at, verifier_name, verifier_version,
-                    kid, signature_scheme
-             FROM registry_entries
-             WHERE manifest_hash = ?1 AND proof_hash = ?2
-             LIMIT 1",
-        )?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM registry_entries WHERE manifest_hash = ?1 AND proof_hash = ?2 LIMIT 1",
+            ENTRY_COLUMNS
+        ))?;
 
         let mut rows = stmt.query(rusqlite::params![manifest_hash, proof_hash])?;
 
@@ -368,6 +533,58 @@ impl RegistryStore for SqliteRegistryStore {
     fn list(&self) -> Result<Vec<RegistryEntry>, Box<dyn Error>> {
         self.load().map(|r| r.entries)
     }
+
+    fn query(&self, query: &RegistryQuery) -> Result<RegistryPage, Box<dyn Error>> {
+        query.validate()?;
+        let (where_clause, params) = query.sql_where();
+        let conn = self.conn.borrow();
+
+        let total: i64 = conn.query_row(
+            &format!("SELECT COUNT(*) FROM registry_entries {}", where_clause),
+            rusqlite::params_from_iter(params.iter()),
+            |row| row.get(0),
+        )?;
+
+        // LIMIT -1 = unbegrenzt
+        let limit = query.limit.map(|l| l as i64).unwrap_or(-1);
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM registry_entries {} {} LIMIT {} OFFSET {}",
+            ENTRY_COLUMNS,
+            where_clause,
+            query.sql_order_by(),
+            limit,
+            query.offset
+        ))?;
+        let rows = stmt.query_map(
+            rusqlite::params_from_iter(params.iter()),
+            Self::row_to_entry,
+        )?;
+
+        let mut entries = Vec::new();
+        for row in rows {
+            entries.push(row?);
+        }
+
+        Ok(RegistryPage {
+            total: total as usize,
+            offset: query.offset,
+            limit: query.limit,
+            entries,
+        })
+    }
+
+    fn add_lifecycle_record(&self, record: LifecycleRecord) -> Result<(), Box<dyn Error>> {
+        let conn = self.conn.borrow();
+        Self::insert_lifecycle_record(&conn, &record)
+    }
+
+    fn lifecycle_records(&self, entry_id: &str) -> Result<Vec<LifecycleRecord>, Box<dyn Error>> {
+        self.query_lifecycle(Some(entry_id))
+    }
+
+    fn transparency_log(&self) -> Result<TransparencyLog, Box<dyn Error>> {
+        Ok(TransparencyLog::new(self.query_log()?))
+    }
 }
 
 // ============================================================================
@@ -387,11 +604,29 @@ pub fn open_store(
     }
 }
 
+/// Öffnet einen bestehenden Registry-Store zum Lesen
+///
+/// Anders als bei `open_store` werden SQLite-Datenbanken nur lesend geöffnet
+/// und nie migriert; Verifikation und Auflistung dürfen eine Registry nicht
+/// umschreiben.
+pub fn open_store_read_only(
+    backend: RegistryBackend,
+    path: &Path,
+) -> Result<Box<dyn RegistryStore>, Box<dyn Error>> {
+    match backend {
+        RegistryBackend::Json => Ok(Box::new(JsonRegistryStore {
+            path: path.to_path_buf(),
+        })),
+        RegistryBackend::Sqlite => Ok(Box::new(SqliteRegistryStore::open_read_only(path)?)),
+    }
+}
+
 // ============================================================================
 // Helper Functions
 // ============================================================================
 
 /// Verifiziert, ob ein Manifest- und Proof-Hash in einer Registry-Datei existiert
+#[allow(dead_code)] // Public API - superseded by the verifier registry/timestamp checks
 pub fn verify_entry_from_file(
     registry_path: &str,
     manifest_hash: &str,
@@ -426,18 +661,23 @@ mod tests {
     fn test_registry_add_entry() {
         let mut registry = Registry::new();
         let id = registry.add_entry("0xabc123".to_string(), "0xdef456".to_string(), None);
-        assert_eq!(id, "proof_001");
+        let entry = &registry.entries[0];
+        assert_eq!(
+            id,
+            RegistryEntry::derive_id("0xabc123", "0xdef456", &entry.registered_at)
+        );
+        assert!(id.starts_with("proof_"));
         assert_eq!(registry.count(), 1);
     }
 
     #[test]
     fn test_registry_find_entry() {
         let mut registry = Registry::new();
-        registry.add_entry("0xabc123".to_string(), "0xdef456".to_string(), None);
+        let id = registry.add_entry("0xabc123".to_string(), "0xdef456".to_string(), None);
 
         let found = registry.find_entry("0xabc123", "0xdef456");
         assert!(found.is_some());
-        assert_eq!(found.unwrap().id, "proof_001");
+        assert_eq!(found.unwrap().id, id);
 
         let not_found = registry.find_entry("0xwrong", "0xhash");
         assert!(not_found.is_none());
@@ -470,4 +710,270 @@ mod tests {
 
         std::fs::remove_file(&temp_path).ok();
     }
+
+    #[test]
+    fn test_lifecycle_records_roundtrip() {
+        use crate::registry::lifecycle::{LifecycleAction, RevocationReason};
+
+        let dir = std::env::temp_dir().join("test_registry_lifecycle");
+        std::fs::remove_dir_all(&dir).ok();
+        std::fs::create_dir_all(&dir).unwrap();
+
+        for backend in [RegistryBackend::Json, RegistryBackend::Sqlite] {
+            let path = dir.join(format!("registry_{:?}", backend));
+            let store = open_store(backend, &path).unwrap();
+            let entry = RegistryEntry::new(
+                "proof_001".to_string(),
+                "0xabc123".to_string(),
+                "0xdef456".to_string(),
+                "2026-01-01T00:00:00Z".to_string(),
+            );
+            store.add_entry(entry.clone()).unwrap();
+
+            let mut record = LifecycleRecord::new(
+                &entry,
+                LifecycleAction::Supersede,
+                RevocationReason::DataCorrected,
+                Some("proof_002".to_string()),
+                "2026-02-01T00:00:00Z".to_string(),
+            )
+            .unwrap();
+            record
+                .sign(&ed25519_dalek::SigningKey::from_bytes(&[5u8; 32]))
+                .unwrap();
+            store.add_lifecycle_record(record.clone()).unwrap();
+
+            assert_eq!(
+                store.lifecycle_records("proof_001").unwrap(),
+                vec![record.clone()]
+            );
+            assert!(store.lifecycle_records("proof_002").unwrap().is_empty());
+            assert_eq!(
+                store.find_by_id("proof_001").unwrap().unwrap().id,
+                "proof_001"
+            );
+
+            // save() ersetzt Einträge und Records (z.B. bei Migration)
+            let loaded = store.load().unwrap();
+            assert_eq!(loaded.lifecycle, vec![record]);
+            store.save(&loaded).unwrap();
+            assert_eq!(store.load().unwrap().lifecycle.len(), 1);
+        }
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_transparency_log_backends() {
+        let dir = std::env::temp_dir().join("test_registry_transparency_log");
+        std::fs::remove_dir_all(&dir).ok();
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let mut roots = Vec::new();
+        for backend in [RegistryBackend::Json, RegistryBackend::Sqlite] {
+            let path = dir.join(format!("registry_{:?}", backend));
+            let store = open_store(backend, &path).unwrap();
+            for i in 1..=3 {
+                store
+                    .add_entry(RegistryEntry::new(
+                        format!("proof_{:03}", i),
+                        format!("0xm{}", i),
+                        format!("0xp{}", i),
+                        "2026-01-01T00:00:00Z".to_string(),
+                    ))
+                    .unwrap();
+            }
+
+            let log = store.transparency_log().unwrap();
+            assert_eq!(log.size(), 3);
+            assert_eq!(log.index_of("proof_002"), Some(1));
+            assert!(log.audit_entries(&store.list().unwrap()).is_consistent());
+
+            // save() übernimmt das Log unverändert (z.B. bei Migration)
+            let loaded = store.load().unwrap();
+            store.save(&loaded).unwrap();
+            assert_eq!(store.transparency_log().unwrap().root_hex(), log.root_hex());
+            roots.push(log.root_hex());
+        }
+        assert_eq!(roots[0], roots[1]);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_query_backends_agree() {
+        use crate::registry::query::{SortField, SortOrder};
+
+        let dir = std::env::temp_dir().join("test_registry_query");
+        std::fs::remove_dir_all(&dir).ok();
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let stores: Vec<_> = [RegistryBackend::Json, RegistryBackend::Sqlite]
+            .into_iter()
+            .map(|backend| {
+                open_store(backend, &dir.join(format!("registry_{:?}", backend))).unwrap()
+            })
+            .collect();
+        for i in 1..=20usize {
+            let mut entry = RegistryEntry::new(
+                format!("proof_{:03}", i),
+                format!("0xm{}", i),
+                format!("0xp{}", i),
+                // Gemischte Offsets: +01:00 liegt eine Stunde früher als Z
+                if i % 2 == 0 {
+                    format!("2026-01-{:02}T12:00:00Z", i)
+                } else {
+                    format!("2026-01-{:02}T12:00:00+01:00", i)
+                },
+            );
+            entry.kid = Some(format!("kid_{}", i % 3));
+            entry.policy_id = Some(format!("0xpolicy{}", i % 2));
+            if i % 4 == 0 {
+                entry.blob_proof = Some(format!("0xblob{}", i));
+                entry.selfverify_status = Some("ok".to_string());
+            }
+            for store in &stores {
+                store.add_entry(entry.clone()).unwrap();
+            }
+        }
+
+        let mut queries = vec![RegistryQuery::default()];
+        for filters in [
+            vec!["kid=kid_1"],
+            vec!["policy=0xpolicy0", "blob=proof"],
+            vec!["selfverify=ok", "kid=kid_2"],
+            vec!["blob=any"],
+            vec!["from=2026-01-05T11:00:00Z", "to=2026-01-12T12:00:00Z"],
+            vec!["scheme=ed25519"],
+        ] {
+            let mut query = RegistryQuery::default();
+            for filter in filters {
+                query.add_filter(filter).unwrap();
+            }
+            queries.push(query);
+        }
+        queries.push(RegistryQuery {
+            sort: SortField::Id,
+            order: SortOrder::Desc,
+            limit: Some(5),
+            offset: 3,
+            ..Default::default()
+        });
+        queries.push(RegistryQuery {
+            order: SortOrder::Desc,
+            limit: Some(4),
+            offset: 18,
+            ..Default::default()
+        });
+
+        for query in &queries {
+            let pages: Vec<_> = stores.iter().map(|s| s.query(query).unwrap()).collect();
+            let ids = |page: &RegistryPage| -> Vec<String> {
+                page.entries.iter().map(|e| e.id.clone()).collect()
+            };
+            assert_eq!(pages[0].total, pages[1].total, "{:?}", query);
+            assert_eq!(ids(&pages[0]), ids(&pages[1]), "{:?}", query);
+        }
+
+        let page = stores[1].query(&queries[5]).unwrap();
+        assert_eq!(page.total, 8);
+        assert_eq!(page.entries[0].id, "proof_005");
+        assert_eq!(page.entries[0].policy_id.as_deref(), Some("0xpolicy1"));
+        assert_eq!(
+            stores[1]
+                .query(&queries.last().unwrap().clone())
+                .unwrap()
+                .entries
+                .len(),
+            2
+        );
+
+        let invalid = RegistryQuery {
+            registered_from: Some("yesterday".to_string()),
+            ..Default::default()
+        };
+        assert!(stores[1].query(&invalid).is_err());
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_sqlite_read_only_open_does_not_migrate() {
+        let dir = std::env::temp_dir().join("test_registry_read_only");
+        std::fs::remove_dir_all(&dir).ok();
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("registry.sqlite");
+        {
+            let conn = rusqlite::Connection::open(&path).unwrap();
+            conn.execute_batch(
+                "CREATE TABLE registry_entries (
+                    id TEXT PRIMARY KEY, manifest_hash TEXT NOT NULL, proof_hash TEXT NOT NULL,
+                    timestamp_file TEXT, registered_at TEXT NOT NULL
+                );",
+            )
+            .unwrap();
+        }
+        let before = std::fs::read(&path).unwrap();
+
+        // Veraltetes Schema: abgelehnt, Datei unverändert, kein Backup
+        let err = SqliteRegistryStore::open_read_only(&path).err().unwrap();
+        assert!(err.to_string().contains("schema-migrate"), "{}", err);
+        assert_eq!(std::fs::read(&path).unwrap(), before);
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 1);
+
+        // Fehlende Datei wird nicht angelegt
+        let missing = dir.join("missing.sqlite");
+        assert!(open_store_read_only(RegistryBackend::Sqlite, &missing).is_err());
+        assert!(!missing.exists());
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_sqlite_adds_policy_column_to_old_databases() {
+        let dir = std::env::temp_dir().join("test_registry_old_schema");
+        std::fs::remove_dir_all(&dir).ok();
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("registry.sqlite");
+        {
+            let conn = rusqlite::Connection::open(&path).unwrap();
+            conn.execute_batch(
+                "CREATE TABLE registry_entries (
+                    id TEXT PRIMARY KEY, manifest_hash TEXT NOT NULL, proof_hash TEXT NOT NULL,
+                    timestamp_file TEXT, registered_at TEXT NOT NULL, signature TEXT, public_key TEXT,
+                    blob_manifest TEXT, blob_proof TEXT, blob_wasm TEXT, blob_abi TEXT,
+                    selfverify_status TEXT, selfverify_at TEXT, verifier_name TEXT,
+                    verifier_version TEXT, kid TEXT, signature_scheme TEXT
+                );
+                INSERT INTO registry_entries(id, manifest_hash, proof_hash, registered_at)
+                    VALUES('proof_001', '0xm', '0xp', '2026-01-01T00:00:00Z');",
+            )
+            .unwrap();
+        }
+
+        let store = SqliteRegistryStore::open(&path).unwrap();
+        let entries = store.list().unwrap();
+        assert_eq!(entries.len(), 1);
+        assert!(entries[0].policy_id.is_none());
+
+        // Zweites Öffnen ist idempotent
+        drop(store);
+        assert!(SqliteRegistryStore::open(&path).is_ok());
+        assert_eq!(
+            SqliteRegistryStore::open_read_only(&path)
+                .unwrap()
+                .list()
+                .unwrap()
+                .len(),
+            1
+        );
+
+        // Genau ein Backup vor der ersten Migration
+        let backups = std::fs::read_dir(&dir)
+            .unwrap()
+            .filter(|e| e.as_ref().unwrap().path().extension() == Some("bak".as_ref()))
+            .count();
+        assert_eq!(backups, 1);
+        std::fs::remove_dir_all(&dir).ok();
+    }
 }
@@ -2,23 +2,31 @@
 //!
 //! Provides timestamp creation and verification with pluggable providers:
 //! - MockRfc3161Provider (local mock for testing)
-//! - RealRfc3161Provider (stub for future implementation)
+//! - RealRfc3161Provider (RFC 3161 TSA over HTTP, see `rfc3161`)
 
+use super::rfc3161;
+use base64::{engine::general_purpose, Engine};
 use chrono::Utc;
+use der::Encode;
 use serde::{Deserialize, Serialize};
 use sha3::{Digest, Sha3_256};
 use std::error::Error;
 use std::fs;
 use std::path::Path;
+use x509_cert::Certificate;
 
-/// Timestamp-Struktur (RFC3161-Mock)
+/// Versionskennung von Timestamps mit echtem RFC 3161 TimeStampToken
+pub const RFC3161_VERSION: &str = "tsr.rfc3161.v1";
+
+/// Timestamp-Struktur (Mock oder RFC 3161)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Timestamp {
     pub version: String,
     pub audit_tip_hex: String,
-    pub created_at: String, // RFC3339
+    pub created_at: String, // RFC3339 (bei RFC 3161: genTime des Tokens)
     pub tsa: String,
-    pub signature: String, // Base64-encoded mock signature
+    /// Mock: Hex-SHA3 über Tip und Zeit; RFC 3161: Base64-DER des TimeStampTokens
+    pub signature: String,
     pub status: String,
 }
 
@@ -46,13 +54,62 @@ impl Timestamp {
         Ok(())
     }
 
-    /// Verifiziert einen Timestamp gegen einen Audit-Tip
+    /// true, wenn der Timestamp ein echtes RFC 3161 TimeStampToken enthält
+    pub fn is_rfc3161(&self) -> bool {
+        self.version == RFC3161_VERSION
+    }
+
+    /// DER-kodiertes TimeStampToken (nur RFC 3161)
+    pub fn token_der(&self) -> Result<Vec<u8>, Box<dyn Error>> {
+        if !self.is_rfc3161() {
+            return Err(format!("Timestamp version {} has no RFC 3161 token", self.version).into());
+        }
+        Ok(general_purpose::STANDARD.decode(&self.signature)?)
+    }
+
+    /// Verifiziert einen Mock-Timestamp gegen einen Audit-Tip
     ///
-    /// Delegiert an MockRfc3161Provider für konsistente Implementierung.
-    pub fn verify(&self, audit_tip_hex: &str) -> bool {
-        MockRfc3161Provider
-            .verify(audit_tip_hex, self)
-            .unwrap_or(false)
+    /// RFC 3161 Tokens werden abgelehnt: Das eingebettete TSA-Zertifikat
+    /// beweist nichts (jede selbstsignierte TSA bestünde), sie sind mit
+    /// `verify_with_certificate` gegen ein vertrauenswürdiges Zertifikat zu prüfen.
+    pub fn verify(&self, audit_tip_hex: &str) -> Result<bool, Box<dyn Error>> {
+        if self.is_rfc3161() {
+            return Err(
+                "RFC 3161 timestamp requires a TSA certificate, use verify_with_certificate".into(),
+            );
+        }
+        MockRfc3161Provider.verify(audit_tip_hex, self)
+    }
+
+    /// Verifiziert ein RFC 3161 Token gegen einen Audit-Tip und ein TSA-Zertifikat
+    pub fn verify_with_certificate(
+        &self,
+        audit_tip_hex: &str,
+        tsa_certificate: &Certificate,
+    ) -> Result<rfc3161::VerifiedToken, Box<dyn Error>> {
+        self.verify_token(audit_tip_hex, Some(tsa_certificate))
+    }
+
+    fn verify_token(
+        &self,
+        audit_tip_hex: &str,
+        tsa_certificate: Option<&Certificate>,
+    ) -> Result<rfc3161::VerifiedToken, Box<dyn Error>> {
+        if self.status != "ok" {
+            return Err(format!("Timestamp status is '{}'", self.status).into());
+        }
+        if self.audit_tip_hex != audit_tip_hex {
+            return Err(format!(
+                "Timestamp covers audit tip {}, expected {}",
+                self.audit_tip_hex, audit_tip_hex
+            )
+            .into());
+        }
+        Ok(rfc3161::verify_token(
+            &self.token_der()?,
+            audit_tip_hex,
+            tsa_certificate,
+        )?)
     }
 }
 
@@ -86,7 +143,7 @@ pub enum ProviderKind {
 pub fn make_provider(kind: ProviderKind) -> Box<dyn TimestampProvider> {
     match kind {
         ProviderKind::MockRfc3161 => Box::new(MockRfc3161Provider),
-        ProviderKind::RealRfc3161 { tsa_url } => Box::new(RealRfc3161Provider { tsa_url }),
+        ProviderKind::RealRfc3161 { tsa_url } => Box::new(RealRfc3161Provider::new(tsa_url)),
     }
 }
 
@@ -149,26 +206,65 @@ impl TimestampProvider for MockRfc3161Provider {
 }
 
 // ============================================================================
-// Real RFC3161 Provider (stub for future implementation)
+// Real RFC3161 Provider
 // ============================================================================
 
-/// Real RFC3161 Timestamp Provider (not yet implemented)
+/// Real RFC3161 Timestamp Provider
+///
+/// Sends a TimeStampReq (SHA-256 imprint of the audit tip, random nonce) to
+/// the TSA via HTTP POST and checks the returned token. With a configured TSA
+/// certificate, tokens must be signed by that certificate; verification
+/// requires it.
 #[allow(dead_code)]
 pub struct RealRfc3161Provider {
     pub tsa_url: String,
+    pub tsa_certificate: Option<Certificate>,
+}
+
+impl RealRfc3161Provider {
+    pub fn new(tsa_url: String) -> Self {
+        RealRfc3161Provider {
+            tsa_url,
+            tsa_certificate: None,
+        }
+    }
+
+    pub fn with_certificate(mut self, tsa_certificate: Option<Certificate>) -> Self {
+        self.tsa_certificate = tsa_certificate;
+        self
+    }
 }
 
 impl TimestampProvider for RealRfc3161Provider {
-    fn create(&self, _audit_tip_hex: &str) -> Result<Timestamp, Box<dyn Error>> {
-        Err(format!(
-            "Real RFC3161 provider not yet implemented (tsa_url={}). Use --provider mock for now.",
-            self.tsa_url
-        )
-        .into())
+    fn create(&self, audit_tip_hex: &str) -> Result<Timestamp, Box<dyn Error>> {
+        let nonce: u64 = rand::random();
+        let request = rfc3161::build_request(audit_tip_hex, nonce)?;
+        let response = rfc3161::request_timestamp(&self.tsa_url, request.to_der()?)?;
+        let token = rfc3161::token_from_response(&response)?.to_der()?;
+
+        let verified = rfc3161::verify_token(&token, audit_tip_hex, self.tsa_certificate.as_ref())?;
+        if verified.nonce != request.nonce {
+            return Err("TSA response nonce does not match request".into());
+        }
+
+        Ok(Timestamp {
+            version: RFC3161_VERSION.to_string(),
+            audit_tip_hex: audit_tip_hex.to_string(),
+            created_at: verified.gen_time.to_rfc3339(),
+            tsa: verified.tsa,
+            signature: general_purpose::STANDARD.encode(token),
+            status: "ok".to_string(),
+        })
     }
 
-    fn verify(&self, _audit_tip_hex: &str, _ts: &Timestamp) -> Result<bool, Box<dyn Error>> {
-        Err("Real RFC3161 provider not yet implemented. Use --provider mock for now.".into())
+    fn verify(&self, audit_tip_hex: &str, ts: &Timestamp) -> Result<bool, Box<dyn Error>> {
+        let certificate = self
+            .tsa_certificate
+            .as_ref()
+            .ok_or("RFC 3161 verification requires a TSA certificate")?;
+        Ok(ts
+            .verify_with_certificate(audit_tip_hex, certificate)
+            .is_ok())
     }
 
     fn name(&self) -> &'static str {
@@ -187,6 +283,7 @@ impl TimestampProvider for RealRfc3161Provider {
 ///
 /// # Rückgabe
 /// true wenn Timestamp-Status "ok" ist, false sonst
+#[allow(dead_code)] // Public API - superseded by the verifier registry/timestamp checks
 pub fn verify_timestamp_from_file(ts_path: &str) -> bool {
     match Timestamp::load(ts_path) {
         Ok(ts) => ts.status == "ok",
@@ -215,7 +312,7 @@ mod tests {
         let tip = "0x1234567890abcdef".to_string();
         let ts = Timestamp::create_mock(tip.clone());
 
-        assert!(ts.verify(&tip));
+        assert!(ts.verify(&tip).unwrap());
     }
 
     #[test]
@@ -223,7 +320,7 @@ mod tests {
         let tip = "0x1234567890abcdef".to_string();
         let ts = Timestamp::create_mock(tip);
 
-        assert!(!ts.verify("0xwronghash"));
+        assert!(!ts.verify("0xwronghash").unwrap());
     }
 
     #[test]
@@ -236,11 +333,39 @@ mod tests {
 
         let loaded = Timestamp::load(&temp_path).unwrap();
         assert_eq!(loaded.audit_tip_hex, tip);
-        assert!(loaded.verify(&tip));
+        assert!(loaded.verify(&tip).unwrap());
 
         std::fs::remove_file(&temp_path).ok();
     }
 
+    #[test]
+    fn test_real_provider_with_local_tsa() {
+        let tsa = rfc3161::LocalTsa::new("CAP Test TSA").unwrap();
+        let certificate = tsa.certificate().clone();
+        let provider = RealRfc3161Provider::new(tsa.spawn().unwrap())
+            .with_certificate(Some(certificate.clone()));
+
+        let tip = "0x1234567890abcdef";
+        let ts = provider.create(tip).unwrap();
+        assert!(ts.is_rfc3161());
+        assert_eq!(ts.tsa, "CN=CAP Test TSA");
+        assert!(provider.verify(tip, &ts).unwrap());
+        assert!(!provider.verify("0xabcd", &ts).unwrap());
+        // Ohne gepinntes Zertifikat wird ein RFC 3161 Token nicht akzeptiert
+        assert!(ts.verify(tip).is_err());
+
+        // Token einer anderen TSA
+        let other = rfc3161::LocalTsa::new("CAP Test TSA").unwrap();
+        assert!(ts
+            .verify_with_certificate(tip, other.certificate())
+            .is_err());
+
+        // Ohne Zertifikat kann der Provider nicht verifizieren
+        assert!(RealRfc3161Provider::new(String::new())
+            .verify(tip, &ts)
+            .is_err());
+    }
+
     #[test]
     fn test_provider_from_cli() {
         let mock = provider_from_cli("mock", None);
@@ -0,0 +1,475 @@
+//! Registry Transparency Log - Append-only Merkle log of registry additions
+//!
+//! Every entry added to a registry store is appended as a leaf to the
+//! registry's transparency log (RFC 6962 style, tree construction see
+//! `merkle`). The registry itself stays mutable; the log makes silent edits
+//! and deletions detectable:
+//! - signed tree heads (`registry.sth.v1`) commit to size and root of the log
+//! - inclusion proofs show that an entry is part of a tree head
+//! - consistency proofs show that a later tree head extends an earlier one
+//!
+//! Entries registered before the log existed are not covered ("unlogged").
+
+use base64::{engine::general_purpose, Engine};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+use super::entry::RegistryEntry;
+use crate::keys;
+use crate::merkle::{self, Hash32, MerkleTree};
+
+/// Merkle-Schema des Logs
+pub const LOG_SCHEME: &str = "blake3-merkle.v1";
+
+/// Version der Signed-Tree-Head-Datei
+pub const TREE_HEAD_VERSION: &str = "registry.sth.v1";
+
+/// Blatt des Transparency-Logs (Kerndaten eines hinzugefügten Eintrags)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LogLeaf {
+    pub entry_id: String,
+    pub manifest_hash: String,
+    pub proof_hash: String,
+    pub registered_at: String,
+}
+
+impl LogLeaf {
+    pub fn from_entry(entry: &RegistryEntry) -> Self {
+        LogLeaf {
+            entry_id: entry.id.clone(),
+            manifest_hash: entry.manifest_hash.clone(),
+            proof_hash: entry.proof_hash.clone(),
+            registered_at: entry.registered_at.clone(),
+        }
+    }
+
+    /// Prüft, ob der Eintrag (noch) den geloggten Kerndaten entspricht
+    pub fn matches(&self, entry: &RegistryEntry) -> bool {
+        *self == Self::from_entry(entry)
+    }
+
+    /// Merkle-Blatt-Hash über das JSON des Blatts
+    pub fn leaf_hash(&self) -> Hash32 {
+        let bytes = serde_json::to_vec(self).expect("LogLeaf serializes to JSON");
+        merkle::leaf_hash(&bytes)
+    }
+}
+
+/// Signed Tree Head: Größe und Root des Logs zu einem Zeitpunkt
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedTreeHead {
+    pub version: String,
+    pub scheme: String,
+    pub tree_size: usize,
+    /// Root-Hash (0x-präfixiert)
+    pub root_hash: String,
+    /// Zeitpunkt der Erstellung (RFC3339)
+    pub timestamp: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub public_key: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kid: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature_scheme: Option<String>,
+}
+
+impl SignedTreeHead {
+    /// Lädt einen Tree Head aus einer JSON-Datei
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, Box<dyn Error>> {
+        let head: SignedTreeHead = serde_json::from_str(&fs::read_to_string(path)?)?;
+        if head.version != TREE_HEAD_VERSION {
+            return Err(format!("Unsupported tree head version: {}", head.version).into());
+        }
+        if head.scheme != LOG_SCHEME {
+            return Err(format!("Unsupported log scheme: {}", head.scheme).into());
+        }
+        Ok(head)
+    }
+
+    /// Speichert den Tree Head als JSON-Datei
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), Box<dyn Error>> {
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Root-Hash als Bytes
+    pub fn root(&self) -> Result<Hash32, Box<dyn Error>> {
+        merkle::from_hex(&self.root_hash)
+    }
+
+    /// BLAKE3-Hash des Tree-Head-Cores (ohne Signatur-Felder)
+    fn core_hash(&self) -> Result<Vec<u8>, Box<dyn Error>> {
+        #[derive(Serialize)]
+        struct TreeHeadCore<'a> {
+            version: &'a str,
+            scheme: &'a str,
+            tree_size: usize,
+            root_hash: &'a str,
+            timestamp: &'a str,
+        }
+
+        let core = TreeHeadCore {
+            version: &self.version,
+            scheme: &self.scheme,
+            tree_size: self.tree_size,
+            root_hash: &self.root_hash,
+            timestamp: &self.timestamp,
+        };
+        Ok(blake3::hash(&serde_json::to_vec(&core)?)
+            .as_bytes()
+            .to_vec())
+    }
+
+    /// Signiert den Tree Head mit Ed25519 (wie `sign_entry`)
+    pub fn sign(&mut self, signing_key: &SigningKey) -> Result<(), Box<dyn Error>> {
+        let signature = signing_key.sign(&self.core_hash()?);
+        let pubkey_b64 = general_purpose::STANDARD.encode(signing_key.verifying_key().to_bytes());
+
+        self.signature = Some(general_purpose::STANDARD.encode(signature.to_bytes()));
+        self.kid = Some(keys::derive_kid(&pubkey_b64)?);
+        self.public_key = Some(pubkey_b64);
+        self.signature_scheme = Some("ed25519".to_string());
+        Ok(())
+    }
+
+    /// Verifiziert die Signatur und liefert die kid des Signers
+    ///
+    /// Unsignierte Tree Heads werden abgelehnt.
+    pub fn verify_signature(&self) -> Result<String, Box<dyn Error>> {
+        let (sig_b64, pubkey_b64) = match (&self.signature, &self.public_key) {
+            (Some(s), Some(p)) => (s, p),
+            _ => return Err("Tree head is not signed".into()),
+        };
+
+        let sig_bytes = general_purpose::STANDARD.decode(sig_b64)?;
+        let pubkey_bytes = general_purpose::STANDARD.decode(pubkey_b64)?;
+        let signature = Signature::from_bytes(
+            &sig_bytes
+                .try_into()
+                .map_err(|_| "Invalid signature length")?,
+        );
+        let verifying_key = VerifyingKey::from_bytes(
+            &pubkey_bytes
+                .try_into()
+                .map_err(|_| "Invalid public key length")?,
+        )?;
+        verifying_key.verify(&self.core_hash()?, &signature)?;
+
+        let kid = keys::derive_kid(pubkey_b64)?;
+        match &self.kid {
+            Some(claimed) if *claimed != kid => {
+                Err(format!("kid {} does not match public key (kid {})", claimed, kid).into())
+            }
+            _ => Ok(kid),
+        }
+    }
+}
+
+/// Inclusion-Beweis eines Eintrags gegen einen Tree Head
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LogInclusionProof {
+    pub leaf: LogLeaf,
+    pub leaf_index: usize,
+    pub tree_size: usize,
+    /// Geschwister-Hashes von Blatt- zu Root-Ebene
+    pub path: Vec<String>,
+    pub root_hash: String,
+}
+
+impl LogInclusionProof {
+    /// Prüft, dass der Pfad vom Blatt zum Root des Tree Heads führt
+    pub fn verify(&self, head: &SignedTreeHead) -> Result<(), Box<dyn Error>> {
+        if self.tree_size != head.tree_size || self.root_hash != head.root_hash {
+            return Err(format!(
+                "Inclusion proof is for tree size {} (root {}), tree head has size {} (root {})",
+                self.tree_size, self.root_hash, head.tree_size, head.root_hash
+            )
+            .into());
+        }
+
+        let path = parse_path(&self.path)?;
+        if !merkle::verify_inclusion(
+            &self.leaf.leaf_hash(),
+            self.leaf_index,
+            self.tree_size,
+            &path,
+            &head.root()?,
+        ) {
+            return Err(format!(
+                "Inclusion path for {} (leaf {} of {}) does not lead to root {}",
+                self.leaf.entry_id, self.leaf_index, self.tree_size, self.root_hash
+            )
+            .into());
+        }
+        Ok(())
+    }
+}
+
+/// Consistency-Beweis zwischen zwei Tree Heads
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LogConsistencyProof {
+    pub old_size: usize,
+    pub old_root: String,
+    pub new_size: usize,
+    pub new_root: String,
+    pub path: Vec<String>,
+}
+
+impl LogConsistencyProof {
+    /// Prüft, dass der neue Tree Head eine reine Erweiterung des alten ist
+    pub fn verify(&self, old: &SignedTreeHead, new: &SignedTreeHead) -> Result<(), Box<dyn Error>> {
+        if (self.old_size, &self.old_root) != (old.tree_size, &old.root_hash)
+            || (self.new_size, &self.new_root) != (new.tree_size, &new.root_hash)
+        {
+            return Err("Consistency proof does not belong to the given tree heads".into());
+        }
+
+        let path = parse_path(&self.path)?;
+        if !merkle::verify_consistency(
+            old.tree_size,
+            new.tree_size,
+            &old.root()?,
+            &new.root()?,
+            &path,
+        ) {
+            return Err(format!(
+                "Log of size {} (root {}) does not extend log of size {} (root {})",
+                new.tree_size, new.root_hash, old.tree_size, old.root_hash
+            )
+            .into());
+        }
+        Ok(())
+    }
+}
+
+/// Abgleich zwischen Log und aktuellen Registry-Einträgen
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LogAudit {
+    /// Einträge ohne Log-Blatt (vor Einführung des Logs registriert)
+    pub unlogged: Vec<String>,
+    /// Einträge, deren Kerndaten vom letzten Log-Blatt abweichen
+    pub modified: Vec<String>,
+    /// Geloggte Einträge, die in der Registry fehlen
+    pub missing: Vec<String>,
+}
+
+impl LogAudit {
+    /// Registry stimmt mit dem Log überein (nicht geloggte Einträge zählen nicht)
+    pub fn is_consistent(&self) -> bool {
+        self.modified.is_empty() && self.missing.is_empty()
+    }
+}
+
+/// Transparency-Log über die Blätter in Einfügereihenfolge
+#[derive(Debug, Clone)]
+pub struct TransparencyLog {
+    leaves: Vec<LogLeaf>,
+    tree: MerkleTree,
+}
+
+impl TransparencyLog {
+    pub fn new(leaves: Vec<LogLeaf>) -> Self {
+        let tree = MerkleTree::from_leaf_hashes(leaves.iter().map(LogLeaf::leaf_hash).collect());
+        TransparencyLog { leaves, tree }
+    }
+
+    /// Anzahl der Blätter
+    pub fn size(&self) -> usize {
+        self.leaves.len()
+    }
+
+    #[allow(dead_code)] // Public API - leaves for external auditors
+    pub fn leaves(&self) -> &[LogLeaf] {
+        &self.leaves
+    }
+
+    /// Root als 0x-präfixierter Hex-String
+    pub fn root_hex(&self) -> String {
+        self.tree.root_hex()
+    }
+
+    /// Position des letzten Blatts zum Eintrag
+    pub fn index_of(&self, entry_id: &str) -> Option<usize> {
+        self.leaves
+            .iter()
+            .rposition(|leaf| leaf.entry_id == entry_id)
+    }
+
+    /// Unsignierter Tree Head über den aktuellen Stand
+    pub fn tree_head(&self) -> SignedTreeHead {
+        SignedTreeHead {
+            version: TREE_HEAD_VERSION.to_string(),
+            scheme: LOG_SCHEME.to_string(),
+            tree_size: self.size(),
+            root_hash: self.root_hex(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            signature: None,
+            public_key: None,
+            kid: None,
+            signature_scheme: None,
+        }
+    }
+
+    /// Inclusion-Beweis für das Blatt an Position `index`
+    pub fn inclusion_proof(&self, index: usize) -> Option<LogInclusionProof> {
+        let path = self.tree.inclusion_path(index)?;
+        Some(LogInclusionProof {
+            leaf: self.leaves[index].clone(),
+            leaf_index: index,
+            tree_size: self.size(),
+            path: path.iter().map(merkle::to_hex).collect(),
+            root_hash: self.root_hex(),
+        })
+    }
+
+    /// Consistency-Beweis vom alten Tree Head zum aktuellen Stand
+    ///
+    /// Schlägt fehl, wenn der alte Tree Head größer ist als das Log oder das
+    /// Log nicht mehr mit ihm übereinstimmt (umgeschriebene Historie).
+    pub fn consistency_proof(
+        &self,
+        old: &SignedTreeHead,
+    ) -> Result<LogConsistencyProof, Box<dyn Error>> {
+        let path = self.tree.consistency_proof(old.tree_size).ok_or_else(|| {
+            format!(
+                "Tree head size {} exceeds log size {}",
+                old.tree_size,
+                self.size()
+            )
+        })?;
+
+        let proof = LogConsistencyProof {
+            old_size: old.tree_size,
+            old_root: old.root_hash.clone(),
+            new_size: self.size(),
+            new_root: self.root_hex(),
+            path: path.iter().map(merkle::to_hex).collect(),
+        };
+        proof.verify(old, &self.tree_head())?;
+        Ok(proof)
+    }
+
+    /// Gleicht das Log mit den aktuellen Registry-Einträgen ab
+    pub fn audit_entries(&self, entries: &[RegistryEntry]) -> LogAudit {
+        let mut audit = LogAudit::default();
+
+        for entry in entries {
+            match self.index_of(&entry.id) {
+                None => audit.unlogged.push(entry.id.clone()),
+                Some(index) if !self.leaves[index].matches(entry) => {
+                    audit.modified.push(entry.id.clone())
+                }
+                Some(_) => {}
+            }
+        }
+
+        for leaf in &self.leaves {
+            if !entries.iter().any(|e| e.id == leaf.entry_id)
+                && !audit.missing.contains(&leaf.entry_id)
+            {
+                audit.missing.push(leaf.entry_id.clone());
+            }
+        }
+        audit
+    }
+}
+
+fn parse_path(path: &[String]) -> Result<Vec<Hash32>, Box<dyn Error>> {
+    path.iter().map(|hash| merkle::from_hex(hash)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(i: usize) -> RegistryEntry {
+        RegistryEntry::new(
+            format!("proof_{:03}", i),
+            format!("0x{:064x}", i),
+            format!("0x{:064x}", i + 100),
+            "2026-01-01T00:00:00Z".to_string(),
+        )
+    }
+
+    fn log(n: usize) -> TransparencyLog {
+        TransparencyLog::new((1..=n).map(|i| LogLeaf::from_entry(&entry(i))).collect())
+    }
+
+    #[test]
+    fn test_signed_tree_head_and_inclusion() {
+        let log = log(5);
+        let mut head = log.tree_head();
+        assert!(head.verify_signature().is_err());
+
+        let key = SigningKey::from_bytes(&[7u8; 32]);
+        head.sign(&key).unwrap();
+        let kid = head.verify_signature().unwrap();
+        assert_eq!(head.kid.as_deref(), Some(kid.as_str()));
+
+        for index in 0..5 {
+            log.inclusion_proof(index).unwrap().verify(&head).unwrap();
+        }
+        assert_eq!(log.index_of("proof_003"), Some(2));
+        assert!(log.inclusion_proof(5).is_none());
+
+        let mut tampered = head.clone();
+        tampered.tree_size = 4;
+        assert!(tampered.verify_signature().is_err());
+
+        let mut wrong_leaf = log.inclusion_proof(1).unwrap();
+        wrong_leaf.leaf.proof_hash = format!("0x{:064x}", 999);
+        assert!(wrong_leaf.verify(&head).is_err());
+    }
+
+    #[test]
+    fn test_consistency_between_tree_heads() {
+        let old_head = log(3).tree_head();
+        let new_log = log(8);
+
+        let proof = new_log.consistency_proof(&old_head).unwrap();
+        assert_eq!((proof.old_size, proof.new_size), (3, 8));
+        proof.verify(&old_head, &new_log.tree_head()).unwrap();
+
+        // Nachträglich geänderter Eintrag im alten Bereich
+        let mut leaves = new_log.leaves().to_vec();
+        leaves[1].manifest_hash = format!("0x{:064x}", 42);
+        let rewritten = TransparencyLog::new(leaves);
+        assert!(rewritten.consistency_proof(&old_head).is_err());
+
+        // Gelöschter Eintrag
+        let mut leaves = new_log.leaves().to_vec();
+        leaves.remove(0);
+        assert!(TransparencyLog::new(leaves)
+            .consistency_proof(&old_head)
+            .is_err());
+
+        // Alter Tree Head größer als das Log
+        assert!(log(2).consistency_proof(&old_head).is_err());
+    }
+
+    #[test]
+    fn test_audit_entries() {
+        let log = log(3);
+        let mut entries: Vec<_> = (1..=4).map(entry).collect();
+        assert_eq!(
+            log.audit_entries(&entries),
+            LogAudit {
+                unlogged: vec!["proof_004".to_string()],
+                ..Default::default()
+            }
+        );
+        assert!(log.audit_entries(&entries).is_consistent());
+
+        entries[0].proof_hash = format!("0x{:064x}", 999);
+        entries.remove(1);
+        let audit = log.audit_entries(&entries);
+        assert_eq!(audit.modified, vec!["proof_001".to_string()]);
+        assert_eq!(audit.missing, vec!["proof_002".to_string()]);
+        assert!(!audit.is_consistent());
+    }
+}
@@ -24,8 +24,8 @@ pub use super::timestamp::{
 // Re-export from store module
 #[allow(unused_imports)]
 pub use super::store::{
-    compute_file_hash, open_store, verify_entry_from_file, JsonRegistryStore, Registry,
-    RegistryBackend, RegistryStore, SqliteRegistryStore,
+    compute_file_hash, open_store, open_store_read_only, verify_entry_from_file, JsonRegistryStore,
+    Registry, RegistryBackend, RegistryStore, SqliteRegistryStore,
 };
 
 #[cfg(test)]
@@ -56,7 +56,7 @@ mod tests {
     fn test_timestamp_reexport() {
         let ts = Timestamp::create_mock("0x1234".to_string());
         assert_eq!(ts.version, "tsr.v1");
-        assert!(ts.verify("0x1234"));
+        assert!(ts.verify("0x1234").unwrap());
     }
 
     #[test]
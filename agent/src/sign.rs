@@ -120,6 +120,34 @@ pub fn sign_manifest(
     })
 }
 
+/// Kanonisches JSON, über das die Signatur `index` eines Manifests gebildet wurde
+///
+/// Signaturen werden an `signatures` angehängt; Signatur `i` deckt daher das
+/// Manifest mit den vorhergehenden Signaturen `signatures[..i]` ab (wie von
+/// [`sign_manifest`] signiert).
+///
+/// # Argumente
+/// * `manifest` - Manifest mit angehängten Signaturen
+/// * `index` - Index der Signatur in `signatures`
+///
+/// # Rückgabe
+/// Kanonisches JSON des Manifests zum Zeitpunkt der Signatur
+#[allow(dead_code)] // Public API - used by verifier::verify
+pub fn signature_payload(manifest: &Manifest, index: usize) -> Result<String, Box<dyn Error>> {
+    if index >= manifest.signatures.len() {
+        return Err(format!(
+            "Signatur-Index {} außerhalb des Bereichs ({} Signaturen)",
+            index,
+            manifest.signatures.len()
+        )
+        .into());
+    }
+
+    let mut signed = manifest.clone();
+    signed.signatures.truncate(index);
+    signed.to_canonical_json()
+}
+
 /// Verifiziert die Signatur eines signierten Manifests
 ///
 /// # Argumente
@@ -201,6 +229,8 @@ mod tests {
                 name: "Test".to_string(),
                 version: "lksg.v1".to_string(),
                 hash: "0xpolicy".to_string(),
+                valid_from: None,
+                valid_until: None,
             },
             audit: AuditInfo {
                 tail_digest: "0xtail".to_string(),
@@ -212,6 +242,11 @@ mod tests {
             },
             signatures: Vec::new(),
             time_anchor: None,
+            commitment_scheme: None,
+            sanctions_root: None,
+            jurisdiction_root: None,
+            supplier_count: None,
+            ubo_count: None,
         };
 
         // Generiere Schlüssel
@@ -241,6 +276,8 @@ mod tests {
                 name: "Test".to_string(),
                 version: "lksg.v1".to_string(),
                 hash: "0xpolicy".to_string(),
+                valid_from: None,
+                valid_until: None,
             },
             audit: AuditInfo {
                 tail_digest: "0xtail".to_string(),
@@ -252,6 +289,11 @@ mod tests {
             },
             signatures: Vec::new(),
             time_anchor: None,
+            commitment_scheme: None,
+            sanctions_root: None,
+            jurisdiction_root: None,
+            supplier_count: None,
+            ubo_count: None,
         };
 
         let mut csprng = OsRng;
@@ -0,0 +1,238 @@
+//! Sortierter Merkle-Tree mit Non-Membership-Proofs
+//!
+//! Grundlage für Sanktions- und Jurisdiktions-Listen. Die Einträge werden
+//! bytewise sortiert und dedupliziert als Blätter in einen [`MerkleTree`]
+//! übernommen (Blatt = UTF-8-Bytes des Eintrags).
+//!
+//! ## Non-Membership
+//!
+//! Ein Wert `v` ist nicht in der Liste, wenn zwei *benachbarte* Blätter
+//! `l < v < r` existieren (Index `i` und `i + 1`). An den Rändern genügt ein
+//! Nachbar: das erste Blatt mit `v < r` (Index 0) bzw. das letzte Blatt mit
+//! `l < v` (Index `n - 1`). Beide Nachbarn werden per Inclusion-Pfad gegen
+//! den Root belegt.
+
+use serde::{Deserialize, Serialize};
+
+use crate::merkle::{self, Hash32, MerkleTree};
+
+/// Sortierter, deduplizierter Merkle-Tree über String-Einträge
+#[derive(Debug, Clone)]
+pub struct SortedMerkleTree {
+    entries: Vec<String>,
+    tree: MerkleTree,
+}
+
+/// Nachbar-Blatt inkl. Inclusion-Pfad
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NeighbourWitness {
+    pub entry: String,
+    pub index: usize,
+    /// Geschwister-Hashes von Blatt- zu Root-Ebene (0x-Hex)
+    pub path: Vec<String>,
+}
+
+/// Non-Membership-Proof für einen einzelnen Wert
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NonMembershipProof {
+    pub value: String,
+    pub tree_size: usize,
+    /// Größter Eintrag kleiner als `value` (None am linken Rand)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub left: Option<NeighbourWitness>,
+    /// Kleinster Eintrag größer als `value` (None am rechten Rand)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub right: Option<NeighbourWitness>,
+}
+
+impl SortedMerkleTree {
+    /// Baut den Baum aus beliebig geordneten Einträgen (Duplikate werden entfernt)
+    pub fn from_entries<I, S>(entries: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let mut entries: Vec<String> = entries.into_iter().map(Into::into).collect();
+        entries.sort();
+        entries.dedup();
+
+        let tree = MerkleTree::from_leaves(&entries);
+        Self { entries, tree }
+    }
+
+    /// Anzahl der (eindeutigen) Einträge
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Prüft, ob die Liste leer ist
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Sortierte Einträge
+    pub fn entries(&self) -> &[String] {
+        &self.entries
+    }
+
+    /// Root-Hash
+    pub fn root(&self) -> Hash32 {
+        self.tree.root()
+    }
+
+    /// Root-Hash als 0x-präfixierter Hex-String
+    pub fn root_hex(&self) -> String {
+        self.tree.root_hex()
+    }
+
+    /// Prüft, ob `value` in der Liste enthalten ist
+    pub fn contains(&self, value: &str) -> bool {
+        self.entries
+            .binary_search_by(|e| e.as_str().cmp(value))
+            .is_ok()
+    }
+
+    fn witness(&self, index: usize) -> NeighbourWitness {
+        let path = self
+            .tree
+            .inclusion_path(index)
+            .expect("index stems from entries and is in range");
+        NeighbourWitness {
+            entry: self.entries[index].clone(),
+            index,
+            path: path.iter().map(merkle::to_hex).collect(),
+        }
+    }
+
+    /// Erzeugt einen Non-Membership-Proof für `value`
+    ///
+    /// # Rückgabe
+    /// Proof oder None, wenn `value` in der Liste enthalten ist
+    pub fn prove_non_membership(&self, value: &str) -> Option<NonMembershipProof> {
+        let insert_at = match self.entries.binary_search_by(|e| e.as_str().cmp(value)) {
+            Ok(_) => return None,
+            Err(pos) => pos,
+        };
+
+        let left = insert_at.checked_sub(1).map(|i| self.witness(i));
+        let right = (insert_at < self.entries.len()).then(|| self.witness(insert_at));
+
+        Some(NonMembershipProof {
+            value: value.to_string(),
+            tree_size: self.entries.len(),
+            left,
+            right,
+        })
+    }
+}
+
+impl NeighbourWitness {
+    /// Prüft den Inclusion-Pfad des Nachbarn gegen den Root
+    fn verify(&self, tree_size: usize, root: &Hash32) -> bool {
+        let path = match self
+            .path
+            .iter()
+            .map(|h| merkle::from_hex(h))
+            .collect::<Result<Vec<_>, _>>()
+        {
+            Ok(path) => path,
+            Err(_) => return false,
+        };
+        let leaf = merkle::leaf_hash(self.entry.as_bytes());
+        merkle::verify_inclusion(&leaf, self.index, tree_size, &path, root)
+    }
+}
+
+impl NonMembershipProof {
+    /// Verifiziert den Proof gegen einen Listen-Root
+    ///
+    /// Prüft Ordnung (`left < value < right`), Nachbarschaft der Indizes
+    /// bzw. Randlage sowie die Inclusion-Pfade beider Nachbarn.
+    pub fn verify(&self, root: &Hash32) -> bool {
+        match (&self.left, &self.right) {
+            (None, None) => self.tree_size == 0 && *root == merkle::empty_root(),
+            (Some(l), None) => {
+                l.entry.as_str() < self.value.as_str()
+                    && l.index + 1 == self.tree_size
+                    && l.verify(self.tree_size, root)
+            }
+            (None, Some(r)) => {
+                self.value.as_str() < r.entry.as_str()
+                    && r.index == 0
+                    && r.verify(self.tree_size, root)
+            }
+            (Some(l), Some(r)) => {
+                l.entry.as_str() < self.value.as_str()
+                    && self.value.as_str() < r.entry.as_str()
+                    && l.index + 1 == r.index
+                    && l.verify(self.tree_size, root)
+                    && r.verify(self.tree_size, root)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tree() -> SortedMerkleTree {
+        SortedMerkleTree::from_entries(["RU", "IR", "KP", "SY", "IR"])
+    }
+
+    #[test]
+    fn test_entries_sorted_and_deduplicated() {
+        let tree = tree();
+        assert_eq!(tree.entries(), ["IR", "KP", "RU", "SY"]);
+        assert_eq!(
+            tree.root(),
+            SortedMerkleTree::from_entries(["SY", "KP", "IR", "RU"]).root()
+        );
+        assert!(tree.contains("KP"));
+        assert!(!tree.contains("DE"));
+    }
+
+    #[test]
+    fn test_non_membership_inner_and_boundaries() {
+        let tree = tree();
+        for value in ["AA", "DE", "LT", "SE", "ZZ"] {
+            let proof = tree.prove_non_membership(value).unwrap();
+            assert!(proof.verify(&tree.root()), "value {}", value);
+        }
+        assert!(tree.prove_non_membership("IR").is_none());
+    }
+
+    #[test]
+    fn test_empty_list() {
+        let tree = SortedMerkleTree::from_entries(Vec::<String>::new());
+        let proof = tree.prove_non_membership("DE").unwrap();
+        assert!(proof.verify(&tree.root()));
+        assert!(!proof.verify(&SortedMerkleTree::from_entries(["IR"]).root()));
+    }
+
+    #[test]
+    fn test_forged_proofs_are_rejected() {
+        let tree = tree();
+        let root = tree.root();
+
+        // Wert wird ausgetauscht, Nachbarn bleiben
+        let mut proof = tree.prove_non_membership("LT").unwrap();
+        proof.value = "KP".to_string();
+        assert!(!proof.verify(&root));
+
+        // Nicht benachbarte Blätter (IR, RU) verschleiern KP
+        let mut proof = tree.prove_non_membership("JP").unwrap();
+        proof.right = tree.prove_non_membership("LT").unwrap().right;
+        proof.value = "KP".to_string();
+        assert!(!proof.verify(&root));
+
+        // Randlage ohne tatsächliches Randblatt
+        let mut proof = tree.prove_non_membership("LT").unwrap();
+        proof.right = None;
+        assert!(!proof.verify(&root));
+
+        // Falscher Root
+        let proof = tree.prove_non_membership("DE").unwrap();
+        assert!(!proof.verify(&SortedMerkleTree::from_entries(["DE"]).root()));
+    }
+}
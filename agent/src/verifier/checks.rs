@@ -0,0 +1,1137 @@
+//! Built-in Checks - Integrity, policy, signature, timestamp, anchor and registry
+//!
+//! Every check writes its findings into the legacy `details` map under the
+//! keys used by earlier versions of `verify` and returns a typed outcome.
+
+use anyhow::{anyhow, Result};
+
+use std::path::Path;
+
+use crate::crypto;
+use crate::keys::{self, KeyStatus, KeyStore};
+use crate::lists::{self, ListExclusionClaim};
+use crate::manifest::anchor_proof;
+use crate::manifest::{Manifest, TimeAnchorBatch, TimeAnchorPublic};
+use crate::policy::{ValidityError, ValidityWindow};
+use crate::registry::{
+    open_store_read_only, rfc3161, verified_entry_status, verify_entry_signature, EntryStatus,
+    LifecycleRecord, Registry, RegistryBackend, RegistryEntry, Timestamp,
+};
+use crate::sign;
+
+use super::engine::{Check, CheckOutcome, Details, VerifyInput};
+use super::types::{CheckKind, ProofStatement, RegistrySource, VerifyStatus};
+
+// ============================================================================
+// Integrity
+// ============================================================================
+
+/// The manifest must be a JSON object
+pub struct ManifestStructureCheck;
+
+impl Check for ManifestStructureCheck {
+    fn id(&self) -> &'static str {
+        "manifest_structure"
+    }
+
+    fn kind(&self) -> CheckKind {
+        CheckKind::Integrity
+    }
+
+    fn run(&self, input: &VerifyInput, _details: &mut Details) -> Option<CheckOutcome> {
+        Some(if input.manifest.is_object() {
+            CheckOutcome::ok("Manifest is a JSON object")
+        } else {
+            CheckOutcome::new(VerifyStatus::Error, "Manifest is not a JSON object")
+        })
+    }
+}
+
+/// Compares a computed hash with the expected one (if given)
+fn hash_outcome(label: &str, data: &[u8], expected: Option<&str>) -> CheckOutcome {
+    let computed = crypto::hex_lower_prefixed32(crypto::sha3_256(data));
+    match expected {
+        Some(expected) if computed == expected => {
+            CheckOutcome::ok(format!("{} hash matches: {}", label, computed))
+        }
+        Some(expected) => CheckOutcome::fail(format!(
+            "{} hash mismatch: expected {}, got {}",
+            label, expected, computed
+        )),
+        None => CheckOutcome::ok(format!("{} hash: {}", label, computed)),
+    }
+}
+
+/// Manifest bytes match the expected manifest hash
+pub struct ManifestHashCheck;
+
+impl Check for ManifestHashCheck {
+    fn id(&self) -> &'static str {
+        "hash_match_manifest"
+    }
+
+    fn kind(&self) -> CheckKind {
+        CheckKind::Integrity
+    }
+
+    fn run(&self, input: &VerifyInput, _details: &mut Details) -> Option<CheckOutcome> {
+        Some(hash_outcome(
+            "Manifest",
+            input.manifest_bytes,
+            input.expected_manifest_hash,
+        ))
+    }
+}
+
+/// Proof bytes match the expected proof hash
+pub struct ProofHashCheck;
+
+impl Check for ProofHashCheck {
+    fn id(&self) -> &'static str {
+        "hash_match_proof"
+    }
+
+    fn kind(&self) -> CheckKind {
+        CheckKind::Integrity
+    }
+
+    fn run(&self, input: &VerifyInput, _details: &mut Details) -> Option<CheckOutcome> {
+        Some(hash_outcome(
+            "Proof",
+            input.proof_bytes,
+            input.expected_proof_hash,
+        ))
+    }
+}
+
+// ============================================================================
+// Policy
+// ============================================================================
+
+/// Statement (policy hash, company commitment root) matches the manifest
+pub struct StatementCheck;
+
+impl Check for StatementCheck {
+    fn id(&self) -> &'static str {
+        "statement_match"
+    }
+
+    fn kind(&self) -> CheckKind {
+        CheckKind::Policy
+    }
+
+    fn run(&self, input: &VerifyInput, details: &mut Details) -> Option<CheckOutcome> {
+        let has_policy_hash = input
+            .manifest
+            .get("policy")
+            .and_then(|p| p.get("hash"))
+            .is_some_and(|h| h.is_string());
+        let matches = validate_statement_matches_manifest(input.manifest, input.stmt, details);
+
+        Some(if !has_policy_hash {
+            CheckOutcome::fail("Policy hash not found in manifest")
+        } else if matches {
+            CheckOutcome::ok("Statement matches manifest")
+        } else {
+            CheckOutcome::fail("Statement does not match manifest")
+        })
+    }
+}
+
+/// Validates that statement matches manifest content
+fn validate_statement_matches_manifest(
+    manifest: &serde_json::Value,
+    stmt: &ProofStatement,
+    details: &mut Details,
+) -> bool {
+    let mut checks = Vec::new();
+    let mut all_ok = true;
+
+    // Check policy hash
+    if let Some(policy) = manifest.get("policy") {
+        if let Some(hash) = policy.get("hash").and_then(|h| h.as_str()) {
+            if hash == stmt.policy_hash {
+                checks.push(serde_json::json!({"field": "policy.hash", "status": "ok"}));
+            } else {
+                checks.push(serde_json::json!({
+                    "field": "policy.hash",
+                    "status": "mismatch",
+                    "expected": stmt.policy_hash,
+                    "found": hash
+                }));
+                all_ok = false;
+            }
+        }
+    }
+
+    // Check company commitment root
+    if let Some(root) = manifest
+        .get("company_commitment_root")
+        .and_then(|r| r.as_str())
+    {
+        if root == stmt.company_commitment_root {
+            checks.push(serde_json::json!({"field": "company_commitment_root", "status": "ok"}));
+        } else {
+            checks.push(serde_json::json!({
+                "field": "company_commitment_root",
+                "status": "mismatch",
+                "expected": stmt.company_commitment_root,
+                "found": root
+            }));
+            all_ok = false;
+        }
+    }
+
+    details.insert(
+        "statement_validation".to_string(),
+        serde_json::json!(checks),
+    );
+    all_ok
+}
+
+/// List exclusion claims in the proof match the statement roots
+///
+/// Every root present in the statement (sanctions_root, jurisdiction_root)
+/// requires a matching exclusion claim covering all committed records. The
+/// witnesses behind the claims are private and not part of the proof; they
+/// are checked with `proof verify --list-witnesses`.
+pub struct ListExclusionCheck;
+
+impl Check for ListExclusionCheck {
+    fn id(&self) -> &'static str {
+        "list_exclusions"
+    }
+
+    fn kind(&self) -> CheckKind {
+        CheckKind::Policy
+    }
+
+    fn run(&self, input: &VerifyInput, details: &mut Details) -> Option<CheckOutcome> {
+        let stmt = input.stmt;
+        if stmt.sanctions_root.is_none() && stmt.jurisdiction_root.is_none() {
+            details.insert(
+                "list_exclusion_check".to_string(),
+                serde_json::json!("not_required"),
+            );
+            return None;
+        }
+
+        let claims = extract_list_exclusions(input.proof_bytes);
+        match lists::verify_statement_exclusions(
+            &claims,
+            stmt.sanctions_root.as_deref(),
+            stmt.jurisdiction_root.as_deref(),
+            &lists::RecordCommitments::from_manifest_json(input.manifest),
+        ) {
+            Ok(()) => {
+                let checked: Vec<_> = claims
+                    .iter()
+                    .map(|c| {
+                        serde_json::json!({
+                            "list": c.list,
+                            "records": c.records,
+                            "witness_hash": c.witness_hash
+                        })
+                    })
+                    .collect();
+                details.insert(
+                    "list_exclusion_check".to_string(),
+                    serde_json::json!({"status": "ok", "claims": checked}),
+                );
+                Some(CheckOutcome::ok(format!(
+                    "{} list exclusion claim(s) bound to statement roots",
+                    checked.len()
+                )))
+            }
+            Err(e) => {
+                details.insert(
+                    "list_exclusion_check".to_string(),
+                    serde_json::json!({"status": "fail", "error": e}),
+                );
+                Some(CheckOutcome::fail(e))
+            }
+        }
+    }
+}
+
+/// Extracts list exclusion claims from proof bytes (JSON or base64-encoded JSON)
+fn extract_list_exclusions(proof_bytes: &[u8]) -> Vec<ListExclusionClaim> {
+    use base64::{engine::general_purpose, Engine as _};
+
+    let proof: Option<serde_json::Value> = serde_json::from_slice(proof_bytes).ok().or_else(|| {
+        let text = std::str::from_utf8(proof_bytes).ok()?;
+        let decoded = general_purpose::STANDARD.decode(text.trim()).ok()?;
+        serde_json::from_slice(&decoded).ok()
+    });
+
+    proof
+        .and_then(|p| p.get("proof_data")?.get("list_exclusions").cloned())
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default()
+}
+
+/// The policy was valid when the manifest was created
+///
+/// The reference time is `time_anchor.created_at` if present, otherwise the
+/// manifest's `created_at`. Does not apply if the policy has no validity window.
+pub struct PolicyValidityCheck;
+
+impl Check for PolicyValidityCheck {
+    fn id(&self) -> &'static str {
+        "policy_validity"
+    }
+
+    fn kind(&self) -> CheckKind {
+        CheckKind::Policy
+    }
+
+    fn run(&self, input: &VerifyInput, details: &mut Details) -> Option<CheckOutcome> {
+        let manifest = input.manifest;
+        let policy = manifest.get("policy");
+        let valid_from = policy
+            .and_then(|p| p.get("valid_from"))
+            .and_then(|v| v.as_str());
+        let valid_until = policy
+            .and_then(|p| p.get("valid_until"))
+            .and_then(|v| v.as_str());
+
+        if valid_from.is_none() && valid_until.is_none() {
+            details.insert(
+                "policy_validity_check".to_string(),
+                serde_json::json!("not_required"),
+            );
+            return None;
+        }
+
+        let (reference_source, reference_time) = match manifest
+            .get("time_anchor")
+            .and_then(|a| a.get("created_at"))
+            .and_then(|v| v.as_str())
+        {
+            Some(anchored) => ("time_anchor", Some(anchored)),
+            None => (
+                "manifest",
+                manifest.get("created_at").and_then(|v| v.as_str()),
+            ),
+        };
+
+        let result = match reference_time {
+            Some(time) => ValidityWindow::parse(valid_from, valid_until)
+                .and_then(|window| window.check_at(time)),
+            None => Err(ValidityError::Invalid(
+                "manifest has no created_at".to_string(),
+            )),
+        };
+
+        let status = match &result {
+            Ok(()) => "ok",
+            Err(ValidityError::Expired { .. }) => "expired",
+            Err(ValidityError::NotYetValid { .. }) => "not_yet_valid",
+            Err(ValidityError::Invalid(_)) => "fail",
+        };
+        let mut check = serde_json::json!({
+            "status": status,
+            "valid_from": valid_from,
+            "valid_until": valid_until,
+            "reference_time": reference_time,
+            "reference_source": reference_source,
+        });
+        if let Err(e) = &result {
+            check["error"] = serde_json::json!(e.to_string());
+        }
+        details.insert("policy_validity_check".to_string(), check);
+
+        Some(match result {
+            Ok(()) => CheckOutcome::ok(format!(
+                "Policy valid at {} ({})",
+                reference_time.unwrap_or_default(),
+                reference_source
+            )),
+            Err(e) => CheckOutcome::fail(format!("Policy {}", e)),
+        })
+    }
+}
+
+// ============================================================================
+// Signature
+// ============================================================================
+
+/// All Ed25519 signatures of the manifest are valid
+///
+/// Signature `i` is checked against the canonical manifest JSON with only
+/// the preceding signatures `signatures[..i]` attached (see
+/// `sign::signature_payload`). A detached signature from the input is
+/// checked over the raw manifest bytes. The check passes if at least one
+/// signature is present and all of them are valid.
+pub struct SignatureCheck;
+
+impl Check for SignatureCheck {
+    fn id(&self) -> &'static str {
+        "signatures"
+    }
+
+    fn kind(&self) -> CheckKind {
+        CheckKind::Signature
+    }
+
+    fn run(&self, input: &VerifyInput, details: &mut Details) -> Option<CheckOutcome> {
+        let manifest = input.manifest;
+        let count = manifest
+            .get("signatures")
+            .and_then(|s| s.as_array())
+            .map(|arr| arr.len())
+            .unwrap_or(0);
+
+        details.insert(
+            "signature_present".to_string(),
+            serde_json::json!(count > 0 || input.detached_signature.is_some()),
+        );
+
+        let mut total = 0;
+        let mut valid = 0;
+
+        if count > 0 {
+            details.insert("signature_count".to_string(), serde_json::json!(count));
+
+            let parsed = serde_json::from_value::<Manifest>(manifest.clone());
+            let mut results = Vec::with_capacity(count);
+
+            for index in 0..count {
+                let raw = &manifest["signatures"][index];
+                let result = match &parsed {
+                    Ok(parsed) => verify_embedded_signature(parsed, index),
+                    Err(e) => Err(anyhow!("manifest cannot be parsed: {}", e)),
+                };
+
+                let mut entry = serde_json::json!({
+                    "index": index,
+                    "signer": raw.get("signer"),
+                    "alg": raw.get("alg"),
+                    "pubkey_hex": raw.get("pubkey_hex"),
+                    "status": if result.is_ok() { "valid" } else { "invalid" },
+                });
+                total += 1;
+                match result {
+                    Ok(()) => valid += 1,
+                    Err(e) => entry["error"] = serde_json::json!(e.to_string()),
+                }
+                results.push(entry);
+            }
+
+            details.insert("signatures".to_string(), serde_json::json!(results));
+        }
+
+        if let Some((signature, public_key)) = input.detached_signature {
+            total += 1;
+            let entry = match verify_detached_signature(input.manifest_bytes, signature, public_key)
+            {
+                Ok(()) => {
+                    valid += 1;
+                    serde_json::json!({"status": "valid"})
+                }
+                Err(e) => serde_json::json!({"status": "invalid", "error": e.to_string()}),
+            };
+            details.insert("detached_signature".to_string(), entry);
+        }
+
+        Some(if total == 0 {
+            CheckOutcome::fail("No signature present")
+        } else {
+            CheckOutcome::from_bool(
+                valid == total,
+                format!("{} of {} Ed25519 signature(s) valid", valid, total),
+            )
+        })
+    }
+}
+
+/// Verifies signature `index` of a manifest
+fn verify_embedded_signature(manifest: &Manifest, index: usize) -> Result<()> {
+    let info = &manifest.signatures[index];
+    if info.alg != "Ed25519" {
+        return Err(anyhow!("unsupported algorithm '{}'", info.alg));
+    }
+
+    let pubkey = crypto::Ed25519PublicKey::from_bytes(&crypto::hex_to_32b(&info.pubkey_hex)?)?;
+
+    let sig_bytes = hex::decode(info.sig_hex.strip_prefix("0x").unwrap_or(&info.sig_hex))
+        .map_err(|e| anyhow!("invalid sig_hex: {}", e))?;
+    let sig_array: [u8; 64] = sig_bytes
+        .as_slice()
+        .try_into()
+        .map_err(|_| anyhow!("invalid signature length {} (expected 64)", sig_bytes.len()))?;
+    let signature = crypto::Ed25519Signature::from_bytes(&sig_array);
+
+    let payload = sign::signature_payload(manifest, index).map_err(|e| anyhow!("{}", e))?;
+    if !crypto::ed25519_verify(&pubkey, payload.as_bytes(), &signature) {
+        return Err(anyhow!("signature does not match manifest"));
+    }
+    Ok(())
+}
+
+/// Verifies a detached Ed25519 signature over raw bytes
+fn verify_detached_signature(data: &[u8], sig: &[u8], pubkey: &[u8]) -> Result<()> {
+    let sig_array: [u8; 64] = sig
+        .try_into()
+        .map_err(|_| anyhow!("invalid signature length {} (expected 64)", sig.len()))?;
+    let pubkey_array: [u8; 32] = pubkey
+        .try_into()
+        .map_err(|_| anyhow!("invalid public key length {} (expected 32)", pubkey.len()))?;
+
+    let pubkey = crypto::Ed25519PublicKey::from_bytes(&pubkey_array)?;
+    let signature = crypto::Ed25519Signature::from_bytes(&sig_array);
+    if !crypto::ed25519_verify(&pubkey, data, &signature) {
+        return Err(anyhow!("signature does not match manifest bytes"));
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Timestamp / Anchor
+// ============================================================================
+
+/// Time anchor and timestamp token (enabled by `check_timestamp`)
+///
+/// A timestamp token from the input must be valid and, if the manifest has a
+/// time anchor, cover the anchored audit tip. Only RFC 3161 tokens checked
+/// against a pinned TSA certificate pass without a warning.
+pub struct TimestampCheck;
+
+impl Check for TimestampCheck {
+    fn id(&self) -> &'static str {
+        "timestamp"
+    }
+
+    fn kind(&self) -> CheckKind {
+        CheckKind::Timestamp
+    }
+
+    fn run(&self, input: &VerifyInput, details: &mut Details) -> Option<CheckOutcome> {
+        if !input.opts.check_timestamp {
+            details.insert("timestamp_check".to_string(), serde_json::json!("disabled"));
+            return None;
+        }
+
+        let anchor = input.manifest.get("time_anchor");
+        details.insert(
+            "timestamp_present".to_string(),
+            serde_json::json!(anchor.is_some()),
+        );
+        if let Some(anchor) = anchor {
+            details.insert("timestamp_info".to_string(), anchor.clone());
+        }
+
+        let token = match input.timestamp {
+            Some(token) => token,
+            None if anchor.is_some() => return Some(CheckOutcome::ok("Time anchor present")),
+            None => {
+                return Some(CheckOutcome::new(
+                    VerifyStatus::Warn,
+                    "No time anchor or timestamp token present",
+                ))
+            }
+        };
+
+        let anchored_tip = anchor
+            .and_then(|a| a.get("audit_tip_hex"))
+            .and_then(|v| v.as_str());
+        let outcome = verify_timestamp_token(token, anchored_tip, input.tsa_certificate)
+            .unwrap_or_else(|e| CheckOutcome::fail(format!("Timestamp token invalid: {}", e)));
+
+        let mut entry = serde_json::json!({
+            "status": outcome.status,
+            "version": token.get("version"),
+            "tsa": token.get("tsa"),
+            "created_at": token.get("created_at"),
+            "audit_tip_hex": token.get("audit_tip_hex"),
+        });
+        if outcome.status == VerifyStatus::Fail {
+            entry["error"] = serde_json::json!(outcome.message);
+        }
+        details.insert("timestamp_token".to_string(), entry);
+
+        Some(outcome)
+    }
+}
+
+/// Verifies a timestamp token
+///
+/// RFC 3161 tokens are checked against the TSA certificate from the input;
+/// without one (or for mock tokens) a valid token only yields a warning.
+fn verify_timestamp_token(
+    token: &serde_json::Value,
+    anchored_tip: Option<&str>,
+    tsa_certificate: Option<&[u8]>,
+) -> Result<CheckOutcome> {
+    let ts: Timestamp =
+        serde_json::from_value(token.clone()).map_err(|e| anyhow!("cannot be parsed: {}", e))?;
+    if ts.status != "ok" {
+        return Err(anyhow!("status is '{}'", ts.status));
+    }
+    if let Some(tip) = anchored_tip {
+        if tip != ts.audit_tip_hex {
+            return Err(anyhow!(
+                "covers audit tip {}, time anchor has {}",
+                ts.audit_tip_hex,
+                tip
+            ));
+        }
+    }
+
+    if !ts.is_rfc3161() {
+        if !ts.verify(&ts.audit_tip_hex).map_err(|e| anyhow!("{}", e))? {
+            return Err(anyhow!("signature does not match audit tip"));
+        }
+        return Ok(CheckOutcome::new(
+            VerifyStatus::Warn,
+            format!("Mock timestamp token ({}), no RFC 3161 signature", ts.tsa),
+        ));
+    }
+
+    let certificate = tsa_certificate
+        .map(rfc3161::parse_certificate)
+        .transpose()
+        .map_err(|e| anyhow!("invalid TSA certificate: {}", e))?;
+    let token_der = ts.token_der().map_err(|e| anyhow!("{}", e))?;
+    let verified = rfc3161::verify_token(&token_der, &ts.audit_tip_hex, certificate.as_ref())?;
+
+    let message = format!(
+        "RFC 3161 timestamp token valid ({}, {})",
+        verified.tsa,
+        verified.gen_time.to_rfc3339()
+    );
+    Ok(if verified.certificate_pinned {
+        CheckOutcome::ok(message)
+    } else {
+        CheckOutcome::new(
+            VerifyStatus::Warn,
+            format!("{}; TSA certificate not pinned", message),
+        )
+    })
+}
+
+/// Consistency of the private/public dual anchor (enabled by `check_timestamp`)
+pub struct AnchorCheck;
+
+impl Check for AnchorCheck {
+    fn id(&self) -> &'static str {
+        "dual_anchor"
+    }
+
+    fn kind(&self) -> CheckKind {
+        CheckKind::Anchor
+    }
+
+    fn run(&self, input: &VerifyInput, details: &mut Details) -> Option<CheckOutcome> {
+        if !input.opts.check_timestamp {
+            return None;
+        }
+        let anchor = input.manifest.get("time_anchor")?;
+
+        let private = anchor.get("private");
+        let public = anchor.get("public");
+        details.insert(
+            "dual_anchor_private".to_string(),
+            serde_json::json!(private.is_some()),
+        );
+        details.insert(
+            "dual_anchor_public".to_string(),
+            serde_json::json!(public.is_some()),
+        );
+        if private.is_none() && public.is_none() {
+            return None;
+        }
+
+        match check_dual_anchor(anchor) {
+            Ok(()) => Some(CheckOutcome::ok("Dual anchor consistent")),
+            Err(e) => {
+                details.insert("dual_anchor_error".to_string(), serde_json::json!(e));
+                Some(CheckOutcome::fail(e))
+            }
+        }
+    }
+}
+
+/// Batch anchor: the manifest is a leaf of the publicly anchored batch root
+/// (enabled by `check_timestamp`)
+///
+/// Runs if the time anchor has a `batch` entry. The inclusion path must lead
+/// from the manifest's leaf to the batch root, and the root must be the
+/// digest of the public anchor.
+pub struct BatchAnchorCheck;
+
+impl Check for BatchAnchorCheck {
+    fn id(&self) -> &'static str {
+        "batch_anchor"
+    }
+
+    fn kind(&self) -> CheckKind {
+        CheckKind::Anchor
+    }
+
+    fn run(&self, input: &VerifyInput, details: &mut Details) -> Option<CheckOutcome> {
+        if !input.opts.check_timestamp {
+            return None;
+        }
+        let anchor = input.manifest.get("time_anchor")?;
+        let batch = anchor.get("batch")?;
+
+        let public_digest = anchor
+            .get("public")
+            .and_then(|p| p.get("digest"))
+            .and_then(|v| v.as_str());
+        let outcome = match verify_batch_anchor(input.manifest, batch) {
+            Err(e) => CheckOutcome::fail(format!("Batch anchor invalid: {}", e)),
+            Ok(batch) => match public_digest {
+                None => CheckOutcome::new(
+                    VerifyStatus::Warn,
+                    format!("Batch root {} has no public anchor", batch.root),
+                ),
+                Some(digest) if !digest.eq_ignore_ascii_case(&batch.root) => {
+                    CheckOutcome::fail(format!(
+                        "Public anchor digest {} does not match batch root {}",
+                        digest, batch.root
+                    ))
+                }
+                Some(_) => CheckOutcome::ok(format!(
+                    "Manifest is leaf {} of {} under batch root {}",
+                    batch.leaf_index, batch.tree_size, batch.root
+                )),
+            },
+        };
+
+        let mut entry = serde_json::json!({
+            "status": outcome.status,
+            "root": batch.get("root"),
+            "leaf_kind": batch.get("leaf_kind"),
+            "leaf_index": batch.get("leaf_index"),
+            "tree_size": batch.get("tree_size"),
+        });
+        if outcome.status == VerifyStatus::Fail {
+            entry["error"] = serde_json::json!(outcome.message);
+        }
+        details.insert("batch_anchor".to_string(), entry);
+
+        Some(outcome)
+    }
+}
+
+fn verify_batch_anchor(
+    manifest: &serde_json::Value,
+    batch: &serde_json::Value,
+) -> Result<TimeAnchorBatch> {
+    let manifest: Manifest = serde_json::from_value(manifest.clone())
+        .map_err(|e| anyhow!("manifest cannot be parsed: {}", e))?;
+    let batch: TimeAnchorBatch =
+        serde_json::from_value(batch.clone()).map_err(|e| anyhow!("cannot be parsed: {}", e))?;
+    batch.verify(&manifest).map_err(|e| anyhow!("{}", e))?;
+    Ok(batch)
+}
+
+/// Public anchor backed by an inclusion proof (enabled by `check_timestamp`)
+///
+/// Runs if the manifest has a public anchor. The proof from the input must
+/// show that the anchored digest is committed on chain; only proofs whose
+/// block header is in the pinned header store pass without a warning.
+pub struct PublicAnchorCheck;
+
+impl Check for PublicAnchorCheck {
+    fn id(&self) -> &'static str {
+        "public_anchor"
+    }
+
+    fn kind(&self) -> CheckKind {
+        CheckKind::Anchor
+    }
+
+    fn run(&self, input: &VerifyInput, details: &mut Details) -> Option<CheckOutcome> {
+        if !input.opts.check_timestamp {
+            return None;
+        }
+        let public = input.manifest.get("time_anchor")?.get("public")?;
+
+        let Some(proof) = input.anchor_proof else {
+            details.insert(
+                "public_anchor".to_string(),
+                serde_json::json!({ "status": VerifyStatus::Warn, "proof": null }),
+            );
+            return Some(CheckOutcome::new(
+                VerifyStatus::Warn,
+                "Public anchor not verified (no inclusion proof)",
+            ));
+        };
+
+        let outcome = match verify_anchor_proof(public, proof, input.anchor_headers) {
+            Ok(attestation) => {
+                let message = format!(
+                    "Public anchor proven on {} (block {}, {})",
+                    attestation.chain, attestation.block_hash, attestation.block_time
+                );
+                let outcome = if attestation.header_pinned {
+                    CheckOutcome::ok(message)
+                } else {
+                    CheckOutcome::new(
+                        VerifyStatus::Warn,
+                        format!("{}; block header not pinned", message),
+                    )
+                };
+                details.insert(
+                    "public_anchor".to_string(),
+                    serde_json::json!({ "status": outcome.status, "proof": attestation }),
+                );
+                outcome
+            }
+            Err(e) => {
+                let outcome = CheckOutcome::fail(format!("Public anchor proof invalid: {}", e));
+                details.insert(
+                    "public_anchor".to_string(),
+                    serde_json::json!({ "status": outcome.status, "error": outcome.message }),
+                );
+                outcome
+            }
+        };
+        Some(outcome)
+    }
+}
+
+fn verify_anchor_proof(
+    public: &serde_json::Value,
+    proof: &serde_json::Value,
+    headers: Option<&serde_json::Value>,
+) -> Result<anchor_proof::AnchorAttestation> {
+    let public: TimeAnchorPublic = serde_json::from_value(public.clone())
+        .map_err(|e| anyhow!("public anchor cannot be parsed: {}", e))?;
+    let proof = anchor_proof::AnchorProof::from_json(proof)?;
+    let headers = headers
+        .map(anchor_proof::HeaderStore::from_json)
+        .transpose()?;
+    anchor_proof::verify_public_anchor(&public, &proof, headers.as_ref())
+}
+
+/// Validates private anchor consistency and public anchor format (v0.9.0)
+fn check_dual_anchor(anchor: &serde_json::Value) -> std::result::Result<(), &'static str> {
+    if let Some(private) = anchor.get("private") {
+        let private_audit_tip = private.get("audit_tip_hex").and_then(|v| v.as_str());
+        let anchor_audit_tip = anchor.get("audit_tip_hex").and_then(|v| v.as_str());
+
+        if let (Some(priv_tip), Some(anc_tip)) = (private_audit_tip, anchor_audit_tip) {
+            if priv_tip != anc_tip {
+                return Err(
+                    "Private anchor audit_tip_hex does not match time_anchor.audit_tip_hex",
+                );
+            }
+        }
+    }
+
+    if let Some(public) = anchor.get("public") {
+        if let Some(digest) = public.get("digest").and_then(|v| v.as_str()) {
+            if !digest.starts_with("0x") || digest.len() != 66 {
+                return Err("Public anchor digest has invalid format");
+            }
+        }
+
+        if let Some(txid) = public.get("txid").and_then(|v| v.as_str()) {
+            if txid.is_empty() {
+                return Err("Public anchor txid cannot be empty");
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// ============================================================================
+// Registry
+// ============================================================================
+
+/// Registry entry matches the manifest/proof hashes (enabled by `check_registry`)
+///
+/// The entry is taken from the input or looked up in `opts.registry`. Fails if
+/// no entry is found, if the entry signature is invalid or if the signing kid
+/// is unknown or revoked in `opts.key_store`. Unsigned entries warn without a
+/// key store and fail with one. Entries from a registry source are also
+/// checked against their lifecycle records: revoked entries fail, superseded
+/// entries warn. Entries from the bundle's own registry warn as well, since a
+/// self-supplied registry may simply omit a revocation.
+pub struct RegistryCheck;
+
+impl Check for RegistryCheck {
+    fn id(&self) -> &'static str {
+        "registry_match"
+    }
+
+    fn kind(&self) -> CheckKind {
+        CheckKind::Registry
+    }
+
+    fn run(&self, input: &VerifyInput, details: &mut Details) -> Option<CheckOutcome> {
+        if !input.opts.check_registry {
+            details.insert("registry_check".to_string(), serde_json::json!("disabled"));
+            return None;
+        }
+
+        let manifest_hash = crypto::hex_lower_prefixed32(crypto::sha3_256(input.manifest_bytes));
+        let proof_hash = crypto::hex_lower_prefixed32(crypto::sha3_256(input.proof_bytes));
+
+        let (entry, source) = match (input.registry_entry, &input.opts.registry) {
+            (Some(entry), _) => (Some((entry.clone(), Vec::new())), "input"),
+            (None, Some(registry)) => {
+                match lookup_registry_entry(registry, &manifest_hash, &proof_hash) {
+                    Ok(entry) => (entry, registry_source_name(registry)),
+                    Err(e) => {
+                        details.insert(
+                            "registry_check".to_string(),
+                            serde_json::json!({"status": "error", "error": e.to_string()}),
+                        );
+                        return Some(CheckOutcome::new(
+                            VerifyStatus::Error,
+                            format!("Registry lookup failed: {}", e),
+                        ));
+                    }
+                }
+            }
+            (None, None) => (None, "none"),
+        };
+
+        let (entry, lifecycle_records) = match entry {
+            Some(found) => found,
+            None => {
+                details.insert(
+                    "registry_check".to_string(),
+                    serde_json::json!({"status": "missing", "source": source}),
+                );
+                return Some(CheckOutcome::fail(if source == "none" {
+                    "No registry entry provided".to_string()
+                } else {
+                    format!("No registry entry found in {} registry", source)
+                }));
+            }
+        };
+
+        let manifest_match =
+            entry.get("manifest_hash").and_then(|v| v.as_str()) == Some(manifest_hash.as_str());
+        let proof_match =
+            entry.get("proof_hash").and_then(|v| v.as_str()) == Some(proof_hash.as_str());
+        let matched = manifest_match && proof_match;
+
+        let key_store = input.opts.key_store.as_deref();
+        let signature = verify_registry_entry_signature(&entry);
+        let key_status = match (&signature, key_store) {
+            (Ok(Some(kid)), Some(store)) => Some(lookup_key_status(kid, store)),
+            _ => None,
+        };
+
+        let outcome = if !matched {
+            CheckOutcome::fail(format!(
+                "Registry entry: manifest_match={}, proof_match={}",
+                manifest_match, proof_match
+            ))
+        } else {
+            match (&signature, &key_status) {
+                (Err(e), _) => {
+                    CheckOutcome::fail(format!("Registry entry signature invalid: {}", e))
+                }
+                (Ok(None), _) if key_store.is_some() => {
+                    CheckOutcome::fail("Registry entry is unsigned; key status cannot be checked")
+                }
+                (_, Some(Err(e))) => CheckOutcome::fail(e.clone()),
+                (Ok(Some(kid)), Some(Ok(KeyStatus::Revoked))) => {
+                    CheckOutcome::fail(format!("Registry entry signed with revoked key {}", kid))
+                }
+                (Ok(Some(kid)), Some(Ok(status))) => CheckOutcome::ok(format!(
+                    "Registry entry: manifest_match=true, proof_match=true, signed by {} key {}",
+                    status, kid
+                )),
+                (Ok(Some(kid)), None) => CheckOutcome::ok(format!(
+                    "Registry entry: manifest_match=true, proof_match=true, signed by key {}",
+                    kid
+                )),
+                (Ok(None), _) => CheckOutcome::new(
+                    VerifyStatus::Warn,
+                    "Registry entry: manifest_match=true, proof_match=true; unsigned entry, no key store",
+                ),
+            }
+        };
+
+        // Lifecycle: revoked entries fail, superseded entries warn
+        let lifecycle = registry_lifecycle_status(&entry, &lifecycle_records, key_store);
+        let outcome = match (outcome.status, &lifecycle) {
+            (VerifyStatus::Ok, Err(e)) => {
+                CheckOutcome::fail(format!("Registry lifecycle record invalid: {}", e))
+            }
+            (VerifyStatus::Ok, Ok(status @ EntryStatus::Revoked { .. })) => {
+                CheckOutcome::fail(format!("Registry entry {}", status))
+            }
+            (VerifyStatus::Ok, Ok(status @ EntryStatus::Superseded { .. })) => {
+                CheckOutcome::new(VerifyStatus::Warn, format!("Registry entry {}", status))
+            }
+            _ => outcome,
+        };
+
+        // The bundle's own registry cannot prove the absence of a revocation
+        let outcome = if source == "bundle" && outcome.status.is_pass() {
+            CheckOutcome::new(
+                VerifyStatus::Warn,
+                format!("{}; self-supplied registry", outcome.message),
+            )
+        } else {
+            outcome
+        };
+
+        details.insert(
+            "registry_check".to_string(),
+            serde_json::json!({
+                "status": match (matched, outcome.status) {
+                    (false, _) => "mismatch",
+                    (true, VerifyStatus::Ok) => "ok",
+                    (true, VerifyStatus::Warn) => "warn",
+                    _ => "invalid",
+                },
+                "source": source,
+                "entry_id": entry.get("id"),
+                "manifest_match": manifest_match,
+                "proof_match": proof_match,
+                "signature": match &signature {
+                    Ok(Some(_)) => "valid",
+                    Ok(None) => "unsigned",
+                    Err(_) => "invalid",
+                },
+                "kid": signature.as_ref().ok().cloned().flatten(),
+                "key_status": key_status.as_ref().map(|status| match status {
+                    Ok(status) => status.to_string(),
+                    Err(e) => e.clone(),
+                }),
+                "lifecycle": match &lifecycle {
+                    Ok(status) => status.as_str(),
+                    Err(_) => "invalid",
+                },
+                "successor_id": match &lifecycle {
+                    Ok(EntryStatus::Superseded { successor_id, .. }) => Some(successor_id),
+                    _ => None,
+                },
+            }),
+        );
+
+        Some(outcome)
+    }
+}
+
+fn registry_source_name(source: &RegistrySource) -> &'static str {
+    match source {
+        RegistrySource::Json { .. } => "json",
+        RegistrySource::Sqlite { .. } => "sqlite",
+        RegistrySource::Snapshot { .. } => "snapshot",
+        RegistrySource::Bundle { .. } => "bundle",
+    }
+}
+
+/// Looks up the entry for a manifest/proof hash pair and its lifecycle records
+fn lookup_registry_entry(
+    source: &RegistrySource,
+    manifest_hash: &str,
+    proof_hash: &str,
+) -> Result<Option<(serde_json::Value, Vec<LifecycleRecord>)>> {
+    let found = match source {
+        RegistrySource::Json { path } | RegistrySource::Sqlite { path } => {
+            // A verifier must neither create missing files nor migrate the schema
+            if !Path::new(path).is_file() {
+                return Err(anyhow!("Registry not found: {}", path));
+            }
+            let backend = match source {
+                RegistrySource::Sqlite { .. } => RegistryBackend::Sqlite,
+                _ => RegistryBackend::Json,
+            };
+            let store =
+                open_store_read_only(backend, Path::new(path)).map_err(|e| anyhow!("{}", e))?;
+            match store
+                .find_by_hashes(manifest_hash, proof_hash)
+                .map_err(|e| anyhow!("{}", e))?
+            {
+                Some(entry) => {
+                    let records = store
+                        .lifecycle_records(&entry.id)
+                        .map_err(|e| anyhow!("{}", e))?;
+                    Some((entry, records))
+                }
+                None => None,
+            }
+        }
+        RegistrySource::Snapshot { registry } | RegistrySource::Bundle { registry } => {
+            let registry: Registry = serde_json::from_value(registry.clone())?;
+            registry
+                .find_entry(manifest_hash, proof_hash)
+                .map(|entry| (entry.clone(), registry.lifecycle_of(&entry.id)))
+        }
+    };
+
+    match found {
+        Some((entry, records)) => Ok(Some((serde_json::to_value(entry)?, records))),
+        None => Ok(None),
+    }
+}
+
+/// Lifecycle status of a registry entry from its signed revocation/supersede records
+///
+/// With a key store, the record signers must be known and not revoked.
+fn registry_lifecycle_status(
+    entry: &serde_json::Value,
+    records: &[LifecycleRecord],
+    key_store: Option<&str>,
+) -> std::result::Result<EntryStatus, String> {
+    if records.is_empty() {
+        return Ok(EntryStatus::Active);
+    }
+
+    let entry: RegistryEntry =
+        serde_json::from_value(entry.clone()).map_err(|e| format!("invalid entry: {}", e))?;
+    let (status, kids) =
+        verified_entry_status(&entry, records, chrono::Utc::now()).map_err(|e| e.to_string())?;
+
+    if let Some(store) = key_store {
+        for kid in kids {
+            if lookup_key_status(&kid, store)? == KeyStatus::Revoked {
+                return Err(format!("record signed with revoked key {}", kid));
+            }
+        }
+    }
+    Ok(status)
+}
+
+/// Verifies the Ed25519 signature of a registry entry
+///
+/// Returns the kid of a valid signature, `None` for unsigned entries.
+fn verify_registry_entry_signature(
+    entry: &serde_json::Value,
+) -> std::result::Result<Option<String>, String> {
+    if entry.get("signature").is_none_or(|sig| sig.is_null()) {
+        return Ok(None);
+    }
+
+    let entry: RegistryEntry =
+        serde_json::from_value(entry.clone()).map_err(|e| format!("invalid entry: {}", e))?;
+    if !verify_entry_signature(&entry).map_err(|e| e.to_string())? {
+        return Err("signature without public key".to_string());
+    }
+
+    let public_key = entry.public_key.as_deref().unwrap_or_default();
+    let kid = keys::derive_kid(public_key).map_err(|e| e.to_string())?;
+    match entry.kid {
+        Some(claimed) if claimed != kid => Err(format!(
+            "kid {} does not match public key (kid {})",
+            claimed, kid
+        )),
+        _ => Ok(Some(kid)),
+    }
+}
+
+/// Status of a signing key in the key store
+fn lookup_key_status(kid: &str, key_store: &str) -> std::result::Result<KeyStatus, String> {
+    if !Path::new(key_store).is_dir() {
+        return Err(format!("Key store not found: {}", key_store));
+    }
+    let store = KeyStore::new(key_store).map_err(|e| e.to_string())?;
+    match store.find_by_kid(kid).map_err(|e| e.to_string())? {
+        Some(key) => Ok(key.status),
+        None => Err(format!("Key {} not found in key store", kid)),
+    }
+}
@@ -1,18 +1,21 @@
 //! Verifier Core – Re-export layer for backward compatibility
 //!
 //! This module re-exports types and functions from the new modular structure:
-//! - types.rs: ProofStatement, VerifyOptions, VerifyReport
+//! - types.rs: ProofStatement, VerifyOptions, VerifyReport, CheckResult
 //! - statement.rs: extract_statement_from_manifest, validate_hex32
-//! - verify.rs: verify, verify_from_source
+//! - verify.rs: verify, verify_bytes, verify_from_source
 
 // Re-export from types module
-pub use super::types::{ProofStatement, VerifyOptions, VerifyReport};
+pub use super::types::{
+    CheckKind, CheckResult, ProofStatement, RegistrySource, VerifyOptions, VerifyReport,
+    VerifyStatus,
+};
 
 // Re-export from statement module
 pub use super::statement::{extract_statement_from_manifest, validate_hex32};
 
 // Re-export from verify module
-pub use super::verify::{verify, verify_from_source};
+pub use super::verify::{verify, verify_bytes, verify_from_source, verify_input};
 
 // ============================================================================
 // Tests (kept here for integration testing of re-exports)
@@ -40,7 +43,7 @@ mod tests {
                 "events_count": 10
             },
             "proof": {
-                "proof_type": "mock",
+                "type": "mock",
                 "status": "ok"
             },
             "signatures": []
@@ -94,6 +97,8 @@ mod tests {
         let opts = VerifyOptions {
             check_timestamp: false,
             check_registry: false,
+            registry: None,
+            key_store: None,
         };
 
         let report = verify(&manifest, proof_bytes, &stmt, &opts).unwrap();
@@ -105,8 +110,47 @@ mod tests {
         assert!(report.proof_hash.starts_with("0x"));
     }
 
+    /// Hängt eine Ed25519-Signatur (wie `sign::sign_manifest`) an das Manifest an
+    fn add_signature(manifest: &mut serde_json::Value, seed: u8, signer: &str) {
+        let mut parsed: crate::manifest::Manifest =
+            serde_json::from_value(manifest.clone()).unwrap();
+        let key = ed25519_dalek::SigningKey::from_bytes(&[seed; 32]);
+        let signed = crate::sign::sign_manifest(&parsed, &key, signer).unwrap();
+        parsed.signatures.push(signed.signature);
+        *manifest = serde_json::to_value(&parsed).unwrap();
+    }
+
+    /// Kopie des Manifests mit genau einer gültigen Signatur
+    fn signed(manifest: &serde_json::Value) -> serde_json::Value {
+        let mut manifest = manifest.clone();
+        manifest["signatures"] = json!([]);
+        add_signature(&mut manifest, 7, "TestCompany");
+        manifest
+    }
+
     #[test]
     fn test_verify_ok_with_signature() {
+        let manifest = signed(&mock_manifest());
+
+        let proof_bytes = b"mock proof data";
+        let stmt = extract_statement_from_manifest(&manifest).unwrap();
+        let opts = VerifyOptions {
+            check_timestamp: false,
+            check_registry: false,
+            registry: None,
+            key_store: None,
+        };
+
+        let report = verify(&manifest, proof_bytes, &stmt, &opts).unwrap();
+
+        assert_eq!(report.status, "ok");
+        assert!(report.signature_valid);
+        assert_eq!(report.details["signatures"][0]["status"], "valid");
+        assert_eq!(report.details["signatures"][0]["signer"], "TestCompany");
+    }
+
+    #[test]
+    fn test_verify_fail_garbage_signature() {
         let mut manifest = mock_manifest();
         manifest["signatures"] = json!([
             {
@@ -117,17 +161,61 @@ mod tests {
             }
         ]);
 
-        let proof_bytes = b"mock proof data";
         let stmt = extract_statement_from_manifest(&manifest).unwrap();
-        let opts = VerifyOptions {
-            check_timestamp: false,
-            check_registry: false,
-        };
+        let report = verify(
+            &manifest,
+            b"mock proof data",
+            &stmt,
+            &VerifyOptions::default(),
+        )
+        .unwrap();
 
-        let report = verify(&manifest, proof_bytes, &stmt, &opts).unwrap();
+        assert_eq!(report.status, "fail");
+        assert!(!report.signature_valid);
+        assert_eq!(report.details["signature_present"], true);
+        assert_eq!(report.details["signatures"][0]["status"], "invalid");
+        assert!(report.details["signatures"][0]["error"].is_string());
+    }
 
-        assert_eq!(report.status, "ok");
+    #[test]
+    fn test_verify_fail_manifest_modified_after_signing() {
+        let mut manifest = signed(&mock_manifest());
+        manifest["audit"]["events_count"] = json!(11);
+
+        let stmt = extract_statement_from_manifest(&manifest).unwrap();
+        let report = verify(
+            &manifest,
+            b"mock proof data",
+            &stmt,
+            &VerifyOptions::default(),
+        )
+        .unwrap();
+
+        assert_eq!(report.status, "fail");
+        assert_eq!(
+            report.details["signatures"][0]["error"],
+            "signature does not match manifest"
+        );
+    }
+
+    #[test]
+    fn test_verify_multiple_signatures_reported_separately() {
+        let mut manifest = signed(&mock_manifest());
+        add_signature(&mut manifest, 8, "Auditor");
+
+        let stmt = extract_statement_from_manifest(&manifest).unwrap();
+        let report = verify(&manifest, b"proof", &stmt, &VerifyOptions::default()).unwrap();
         assert!(report.signature_valid);
+        assert_eq!(report.details["signature_count"], 2);
+        assert_eq!(report.details["signatures"][1]["signer"], "Auditor");
+        assert_eq!(report.details["signatures"][1]["status"], "valid");
+
+        // Zweite Signatur durch eine fremde ersetzen: nur sie wird ungültig
+        manifest["signatures"][1]["sig_hex"] = manifest["signatures"][0]["sig_hex"].clone();
+        let report = verify(&manifest, b"proof", &stmt, &VerifyOptions::default()).unwrap();
+        assert!(!report.signature_valid);
+        assert_eq!(report.details["signatures"][0]["status"], "valid");
+        assert_eq!(report.details["signatures"][1]["status"], "invalid");
     }
 
     #[test]
@@ -142,6 +230,8 @@ mod tests {
         let opts = VerifyOptions {
             check_timestamp: false,
             check_registry: false,
+            registry: None,
+            key_store: None,
         };
 
         let report = verify(&manifest, proof_bytes, &stmt, &opts).unwrap();
@@ -167,6 +257,8 @@ mod tests {
         let opts = VerifyOptions {
             check_timestamp: false,
             check_registry: false,
+            registry: None,
+            key_store: None,
         };
 
         let report = verify(&manifest, proof_bytes, &stmt, &opts).unwrap();
@@ -278,7 +370,6 @@ mod tests {
     #[test]
     fn test_verify_with_time_anchor() {
         let mut manifest = mock_manifest();
-        manifest["signatures"] = json!([{"alg": "Ed25519"}]);
         manifest["time_anchor"] = json!({
             "kind": "tsa",
             "reference": "./test.tsr",
@@ -291,6 +382,8 @@ mod tests {
         let opts = VerifyOptions {
             check_timestamp: true,
             check_registry: false,
+            registry: None,
+            key_store: None,
         };
 
         let report = verify(&manifest, proof_bytes, &stmt, &opts).unwrap();
@@ -301,7 +394,6 @@ mod tests {
     #[test]
     fn test_verify_with_dual_anchor_private() {
         let mut manifest = mock_manifest();
-        manifest["signatures"] = json!([{"alg": "Ed25519"}]);
         manifest["time_anchor"] = json!({
             "kind": "tsa",
             "audit_tip_hex": "0x4444444444444444444444444444444444444444444444444444444444444444",
@@ -316,6 +408,8 @@ mod tests {
         let opts = VerifyOptions {
             check_timestamp: true,
             check_registry: false,
+            registry: None,
+            key_store: None,
         };
 
         let report = verify(&manifest, proof_bytes, &stmt, &opts).unwrap();
@@ -328,7 +422,6 @@ mod tests {
     #[test]
     fn test_verify_dual_anchor_mismatch() {
         let mut manifest = mock_manifest();
-        manifest["signatures"] = json!([{"alg": "Ed25519"}]);
         manifest["time_anchor"] = json!({
             "audit_tip_hex": "0x5555555555555555555555555555555555555555555555555555555555555555",
             "private": {
@@ -342,6 +435,8 @@ mod tests {
         let opts = VerifyOptions {
             check_timestamp: true,
             check_registry: false,
+            registry: None,
+            key_store: None,
         };
 
         let report = verify(&manifest, proof_bytes, &stmt, &opts).unwrap();
@@ -354,7 +449,6 @@ mod tests {
     #[test]
     fn test_verify_public_anchor_invalid_digest() {
         let mut manifest = mock_manifest();
-        manifest["signatures"] = json!([{"alg": "Ed25519"}]);
         manifest["time_anchor"] = json!({
             "audit_tip_hex": "0x7777777777777777777777777777777777777777777777777777777777777777",
             "public": {
@@ -370,6 +464,8 @@ mod tests {
         let opts = VerifyOptions {
             check_timestamp: true,
             check_registry: false,
+            registry: None,
+            key_store: None,
         };
 
         let report = verify(&manifest, proof_bytes, &stmt, &opts).unwrap();
@@ -382,7 +478,6 @@ mod tests {
     #[test]
     fn test_verify_public_anchor_empty_txid() {
         let mut manifest = mock_manifest();
-        manifest["signatures"] = json!([{"alg": "Ed25519"}]);
         manifest["time_anchor"] = json!({
             "audit_tip_hex": "0x8888888888888888888888888888888888888888888888888888888888888888",
             "public": {
@@ -398,6 +493,8 @@ mod tests {
         let opts = VerifyOptions {
             check_timestamp: true,
             check_registry: false,
+            registry: None,
+            key_store: None,
         };
 
         let report = verify(&manifest, proof_bytes, &stmt, &opts).unwrap();
@@ -419,6 +516,8 @@ mod tests {
         let opts = VerifyOptions {
             check_timestamp: false,
             check_registry: false,
+            registry: None,
+            key_store: None,
         };
 
         let report = verify(&manifest, proof_bytes, &stmt, &opts).unwrap();
@@ -436,14 +535,15 @@ mod tests {
 
     #[test]
     fn test_verify_options_enable_timestamp_check() {
-        let mut manifest = mock_manifest();
-        manifest["signatures"] = json!([{"alg": "Ed25519"}]);
+        let manifest = mock_manifest();
 
         let proof_bytes = b"proof";
         let stmt = extract_statement_from_manifest(&manifest).unwrap();
         let opts = VerifyOptions {
             check_timestamp: true,
             check_registry: false,
+            registry: None,
+            key_store: None,
         };
 
         let report = verify(&manifest, proof_bytes, &stmt, &opts).unwrap();
@@ -465,4 +565,938 @@ mod tests {
             .to_string()
             .contains("Missing company_commitment_root"));
     }
+
+    fn exclusion_records() -> Vec<crate::lists::RecordInclusionProof> {
+        use crate::lists::{prove_records, RecordKind};
+
+        let suppliers: Vec<crate::io::Supplier> = serde_json::from_value(json!([
+            {"name": "Acme", "jurisdiction": "DE", "tier": 1},
+            {"name": "Globex", "jurisdiction": "US", "tier": 1}
+        ]))
+        .unwrap();
+        // Feste Nonces, damit Manifest und Proof denselben Root sehen
+        let nonces = [
+            format!("0x{}", "11".repeat(32)),
+            format!("0x{}", "22".repeat(32)),
+        ];
+        prove_records(RecordKind::Supplier, &suppliers, &nonces).unwrap()
+    }
+
+    fn manifest_with_jurisdiction_root(root: String) -> serde_json::Value {
+        let records = exclusion_records();
+        let mut manifest = mock_manifest();
+        manifest["jurisdiction_root"] = json!(root);
+        manifest["supplier_root"] = json!(records[0].root);
+        manifest["supplier_count"] = json!(records.len());
+        signed(&manifest)
+    }
+
+    fn proof_with_exclusion(tree: &crate::sorted_merkle::SortedMerkleTree) -> Vec<u8> {
+        use crate::lists::{prove_exclusion, ListKind};
+
+        let witness = prove_exclusion(ListKind::Jurisdictions, tree, &exclusion_records()).unwrap();
+        serde_json::to_vec(&json!({
+            "proof_data": {
+                "checked_constraints": [],
+                "list_exclusions": [witness.claim().unwrap()]
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_verify_list_exclusion_against_statement_root() {
+        use crate::lists::ListKind;
+        use base64::{engine::general_purpose, Engine as _};
+
+        let tree = ListKind::Jurisdictions.build_tree(["IR", "KP", "RU"]);
+        let manifest = manifest_with_jurisdiction_root(tree.root_hex());
+
+        let stmt = extract_statement_from_manifest(&manifest).unwrap();
+        let opts = VerifyOptions::default();
+
+        // JSON- und Base64-kodierte (proof.dat) Proofs werden akzeptiert
+        let json_proof = proof_with_exclusion(&tree);
+        let dat_proof = general_purpose::STANDARD.encode(&json_proof);
+        for proof_bytes in [json_proof, dat_proof.into_bytes()] {
+            let report = verify(&manifest, &proof_bytes, &stmt, &opts).unwrap();
+            assert_eq!(report.status, "ok");
+            assert_eq!(report.details["list_exclusion_check"]["status"], "ok");
+        }
+    }
+
+    #[test]
+    fn test_verify_list_exclusion_missing_or_wrong_root() {
+        use crate::lists::ListKind;
+
+        let tree = ListKind::Jurisdictions.build_tree(["IR", "KP", "RU"]);
+        let manifest = manifest_with_jurisdiction_root(tree.root_hex());
+        let stmt = extract_statement_from_manifest(&manifest).unwrap();
+        let opts = VerifyOptions::default();
+
+        // Kein Nachweis im Proof
+        let report = verify(&manifest, b"mock proof data", &stmt, &opts).unwrap();
+        assert_eq!(report.status, "fail");
+        assert_eq!(report.details["list_exclusion_check"]["status"], "fail");
+
+        // Nachweis gegen eine andere Liste
+        let other = ListKind::Jurisdictions.build_tree(["IR"]);
+        let report = verify(&manifest, &proof_with_exclusion(&other), &stmt, &opts).unwrap();
+        assert_eq!(report.status, "fail");
+    }
+
+    #[test]
+    fn test_verify_policy_validity_window() {
+        let mut manifest = mock_manifest();
+        let stmt = extract_statement_from_manifest(&manifest).unwrap();
+        let opts = VerifyOptions::default();
+
+        let report = verify(&signed(&manifest), b"proof", &stmt, &opts).unwrap();
+        assert_eq!(report.details["policy_validity_check"], "not_required");
+
+        // Manifest erstellt am 2025-10-30
+        manifest["policy"]["valid_from"] = json!("2025-01-01");
+        manifest["policy"]["valid_until"] = json!("2025-12-31");
+        let report = verify(&signed(&manifest), b"proof", &stmt, &opts).unwrap();
+        assert_eq!(report.status, "ok");
+        assert_eq!(report.details["policy_validity_check"]["status"], "ok");
+        assert_eq!(
+            report.details["policy_validity_check"]["reference_source"],
+            "manifest"
+        );
+
+        manifest["policy"]["valid_until"] = json!("2025-10-29");
+        let report = verify(&signed(&manifest), b"proof", &stmt, &opts).unwrap();
+        assert_eq!(report.status, "fail");
+        assert_eq!(report.details["policy_validity_check"]["status"], "expired");
+
+        manifest["policy"]["valid_from"] = json!("2025-10-31");
+        manifest["policy"]["valid_until"] = json!("2025-12-31");
+        let report = verify(&signed(&manifest), b"proof", &stmt, &opts).unwrap();
+        assert_eq!(report.status, "fail");
+        assert_eq!(
+            report.details["policy_validity_check"]["status"],
+            "not_yet_valid"
+        );
+    }
+
+    #[test]
+    fn test_verify_policy_validity_uses_time_anchor() {
+        let mut manifest = mock_manifest();
+        manifest["policy"]["valid_until"] = json!("2025-10-31");
+        manifest["time_anchor"] = json!({
+            "kind": "tsa",
+            "reference": "./test.tsr",
+            "audit_tip_hex": "0x3333333333333333333333333333333333333333333333333333333333333333",
+            "created_at": "2025-11-01T12:00:00Z"
+        });
+        let stmt = extract_statement_from_manifest(&manifest).unwrap();
+
+        // created_at (2025-10-30) liegt im Zeitraum, der Zeitanker nicht
+        let report = verify(
+            &signed(&manifest),
+            b"proof",
+            &stmt,
+            &VerifyOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(report.status, "fail");
+        let check = &report.details["policy_validity_check"];
+        assert_eq!(check["status"], "expired");
+        assert_eq!(check["reference_source"], "time_anchor");
+        assert_eq!(check["reference_time"], "2025-11-01T12:00:00Z");
+    }
+
+    #[test]
+    fn test_verify_bytes_checks_expected_hashes() {
+        let manifest = signed(&mock_manifest());
+        let manifest_bytes = serde_json::to_vec_pretty(&manifest).unwrap();
+        let proof_bytes = b"mock proof data";
+        let opts = VerifyOptions::default();
+
+        // Hash über die gespeicherten Bytes, nicht über das re-serialisierte JSON
+        let manifest_hash =
+            crate::crypto::hex_lower_prefixed32(crate::crypto::sha3_256(&manifest_bytes));
+        let report = verify_bytes(
+            &manifest_bytes,
+            proof_bytes,
+            Some(&manifest_hash),
+            None,
+            &opts,
+        )
+        .unwrap();
+        assert_eq!(report.status, "ok");
+        assert_eq!(report.manifest_hash, manifest_hash);
+
+        let wrong = format!("0x{}", "0".repeat(64));
+        let report = verify_bytes(&manifest_bytes, proof_bytes, None, Some(&wrong), &opts).unwrap();
+        assert_eq!(report.status, "fail");
+        let check = report
+            .checks
+            .iter()
+            .find(|c| c.id == "hash_match_proof")
+            .unwrap();
+        assert_eq!(check.kind, CheckKind::Integrity);
+        assert_eq!(check.status, VerifyStatus::Fail);
+        assert!(check.message.contains("Proof hash mismatch"));
+    }
+
+    #[test]
+    fn test_verify_registry_entry() {
+        use crate::verifier::engine::VerifyInput;
+
+        let manifest = signed(&mock_manifest());
+        let manifest_bytes = serde_json::to_vec(&manifest).unwrap();
+        let proof_bytes = b"mock proof data";
+        let stmt = extract_statement_from_manifest(&manifest).unwrap();
+        let opts = VerifyOptions {
+            check_timestamp: false,
+            check_registry: true,
+            registry: None,
+            key_store: None,
+        };
+        let hash = |data: &[u8]| crate::crypto::hex_lower_prefixed32(crate::crypto::sha3_256(data));
+        let input = VerifyInput::new(&manifest, &manifest_bytes, proof_bytes, &stmt, &opts);
+
+        // Registry-Check angefordert, aber kein Eintrag vorhanden
+        let report = verify_input(&input);
+        assert_eq!(report.status, "fail");
+        assert_eq!(report.registry_match, Some(false));
+        assert_eq!(report.details["registry_check"]["status"], "missing");
+
+        let entry = json!({
+            "id": "proof_001",
+            "manifest_hash": hash(&manifest_bytes),
+            "proof_hash": hash(proof_bytes),
+        });
+        // Unsignierter Eintrag ohne Key-Store: Hashes passen, aber nur Warnung
+        let report = verify_input(&input.with_registry_entry(Some(&entry)));
+        assert_eq!(report.status, "ok");
+        assert_eq!(report.registry_match, Some(false));
+        assert_eq!(report.details["registry_check"]["entry_id"], "proof_001");
+        assert_eq!(report.details["registry_check"]["manifest_match"], true);
+        assert_eq!(report.details["registry_check"]["proof_match"], true);
+        let check = report.checks.last().unwrap();
+        assert_eq!(check.status, VerifyStatus::Warn);
+        assert!(check.message.contains("unsigned entry, no key store"));
+
+        let other = json!({
+            "id": "proof_002",
+            "manifest_hash": hash(&manifest_bytes),
+            "proof_hash": hash(b"other proof"),
+        });
+        let report = verify_input(&input.with_registry_entry(Some(&other)));
+        assert_eq!(report.status, "fail");
+        assert_eq!(report.details["registry_check"]["proof_match"], false);
+    }
+
+    #[test]
+    fn test_verify_registry_source() {
+        use crate::keys::{KeyMetadata, KeyStore};
+        use crate::registry::{open_store, sign_entry, Registry, RegistryBackend, RegistryEntry};
+        use crate::verifier::engine::VerifyInput;
+
+        let manifest = signed(&mock_manifest());
+        let manifest_bytes = serde_json::to_vec(&manifest).unwrap();
+        let proof_bytes = b"mock proof data";
+        let stmt = extract_statement_from_manifest(&manifest).unwrap();
+        let hash = |data: &[u8]| crate::crypto::hex_lower_prefixed32(crate::crypto::sha3_256(data));
+
+        let signing_key = ed25519_dalek::SigningKey::from_bytes(&[7u8; 32]);
+        let mut entry = RegistryEntry::new(
+            "proof_001".to_string(),
+            hash(&manifest_bytes),
+            hash(proof_bytes),
+            "2026-01-01T00:00:00Z".to_string(),
+        );
+        sign_entry(&mut entry, &signing_key).unwrap();
+        let kid = entry.kid.clone().unwrap();
+
+        let mut registry = Registry::new();
+        registry.entries.push(entry.clone());
+
+        let dir = std::env::temp_dir().join("cap_verify_registry_source");
+        std::fs::remove_dir_all(&dir).ok();
+        std::fs::create_dir_all(&dir).unwrap();
+        let json_path = dir.join("registry.json");
+        registry.save(&json_path).unwrap();
+        let sqlite_path = dir.join("registry.sqlite");
+        open_store(RegistryBackend::Sqlite, &sqlite_path)
+            .unwrap()
+            .add_entry(entry.clone())
+            .unwrap();
+
+        let run = |registry: RegistrySource, key_store: Option<&std::path::Path>| {
+            let opts = VerifyOptions {
+                check_timestamp: false,
+                check_registry: true,
+                registry: Some(registry),
+                key_store: key_store.map(|p| p.to_string_lossy().to_string()),
+            };
+            verify_input(&VerifyInput::new(
+                &manifest,
+                &manifest_bytes,
+                proof_bytes,
+                &stmt,
+                &opts,
+            ))
+        };
+        let snapshot = |registry: &Registry| RegistrySource::Snapshot {
+            registry: serde_json::to_value(registry).unwrap(),
+        };
+
+        // Signierter Eintrag aus JSON, SQLite und Snapshot
+        for (source, name) in [
+            (
+                RegistrySource::Json {
+                    path: json_path.to_string_lossy().to_string(),
+                },
+                "json",
+            ),
+            (
+                RegistrySource::Sqlite {
+                    path: sqlite_path.to_string_lossy().to_string(),
+                },
+                "sqlite",
+            ),
+            (snapshot(&registry), "snapshot"),
+        ] {
+            let report = run(source, None);
+            assert_eq!(report.status, "ok", "{}: {:?}", name, report.checks);
+            assert_eq!(report.registry_match, Some(true));
+            let check = &report.details["registry_check"];
+            assert_eq!(check["source"], name);
+            assert_eq!(check["signature"], "valid");
+            assert_eq!(check["kid"], kid.as_str());
+        }
+
+        // Fehlende Registry-Datei ist ein Fehler, kein leerer Store
+        let report = run(
+            RegistrySource::Sqlite {
+                path: dir.join("missing.sqlite").to_string_lossy().to_string(),
+            },
+            None,
+        );
+        assert_eq!(report.status, "fail");
+        assert_eq!(report.details["registry_check"]["status"], "error");
+        assert!(!dir.join("missing.sqlite").exists());
+
+        // Kein Eintrag für Manifest/Proof
+        let report = run(snapshot(&Registry::new()), None);
+        assert_eq!(report.status, "fail");
+        assert_eq!(report.details["registry_check"]["status"], "missing");
+
+        // Manipulierter Eintrag: Signatur ungültig
+        let mut tampered = registry.clone();
+        tampered.entries[0].registered_at = "2026-02-01T00:00:00Z".to_string();
+        let report = run(snapshot(&tampered), None);
+        assert_eq!(report.status, "fail");
+        assert_eq!(report.details["registry_check"]["signature"], "invalid");
+
+        // Behauptete kid passt nicht zum Public Key
+        let mut wrong_kid = registry.clone();
+        wrong_kid.entries[0].kid = Some("00".repeat(16));
+        assert_eq!(run(snapshot(&wrong_kid), None).status, "fail");
+
+        // Key-Status: aktiv ok, unbekannt/revoked fail, unsignierter Eintrag fail
+        let key_store = dir.join("keys");
+        let mut key = KeyMetadata::new(
+            signing_key.verifying_key().as_bytes(),
+            "Test",
+            "ed25519",
+            365,
+        )
+        .unwrap();
+        assert_eq!(key.kid, kid);
+        KeyStore::new(&key_store).unwrap();
+
+        let report = run(snapshot(&registry), Some(&key_store));
+        assert_eq!(report.status, "fail");
+        assert!(report.checks.last().unwrap().message.contains("not found"));
+
+        key.save(key_store.join("test.json")).unwrap();
+        let report = run(snapshot(&registry), Some(&key_store));
+        assert_eq!(report.status, "ok");
+        assert_eq!(report.details["registry_check"]["key_status"], "active");
+
+        key.revoke();
+        key.save(key_store.join("test.json")).unwrap();
+        let report = run(snapshot(&registry), Some(&key_store));
+        assert_eq!(report.status, "fail");
+        assert!(report.checks.last().unwrap().message.contains("revoked"));
+
+        let mut unsigned = Registry::new();
+        unsigned.entries.push(RegistryEntry::new(
+            "proof_002".to_string(),
+            hash(&manifest_bytes),
+            hash(proof_bytes),
+            "2026-01-01T00:00:00Z".to_string(),
+        ));
+        // Unsignierter Eintrag: ohne Key-Store nur Warnung, mit Key-Store fail
+        let report = run(snapshot(&unsigned), None);
+        assert_eq!(report.status, "ok");
+        assert_eq!(report.details["registry_check"]["status"], "warn");
+        assert_eq!(run(snapshot(&unsigned), Some(&key_store)).status, "fail");
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_verify_bundle_registry_is_self_supplied() {
+        use crate::bundle::BundleSource;
+        use crate::registry::{
+            sign_entry, LifecycleAction, LifecycleRecord, Registry, RegistryEntry, RevocationReason,
+        };
+
+        let manifest = signed(&mock_manifest());
+        let manifest_bytes = serde_json::to_vec(&manifest).unwrap();
+        let proof_bytes = b"mock proof data";
+        let hash = |data: &[u8]| crate::crypto::hex_lower_prefixed32(crate::crypto::sha3_256(data));
+        let key = ed25519_dalek::SigningKey::from_bytes(&[5u8; 32]);
+
+        let mut entry = RegistryEntry::new(
+            "proof_001".to_string(),
+            hash(&manifest_bytes),
+            hash(proof_bytes),
+            "2026-01-01T00:00:00Z".to_string(),
+        );
+        sign_entry(&mut entry, &key).unwrap();
+        let mut revoke = LifecycleRecord::new(
+            &entry,
+            LifecycleAction::Revoke,
+            RevocationReason::KeyCompromise,
+            None,
+            "2026-03-01T00:00:00Z".to_string(),
+        )
+        .unwrap();
+        revoke.sign(&key).unwrap();
+
+        // Das Bundle liefert die Registry ohne den Widerruf mit
+        let mut bundled = Registry::new();
+        bundled.entries.push(entry);
+        let mut authoritative = bundled.clone();
+        authoritative.lifecycle.push(revoke);
+
+        let dir = std::env::temp_dir().join("cap_verify_bundle_registry");
+        std::fs::remove_dir_all(&dir).ok();
+        std::fs::create_dir_all(&dir).unwrap();
+        let registry_bytes = serde_json::to_vec(&bundled).unwrap();
+        let file = |role: &str, bytes: &[u8]| json!({"role": role, "hash": hash(bytes), "size": bytes.len(), "optional": role == "registry"});
+        for (name, bytes) in [
+            ("manifest.json", manifest_bytes.as_slice()),
+            ("proof.dat", proof_bytes.as_slice()),
+            ("registry.json", registry_bytes.as_slice()),
+        ] {
+            std::fs::write(dir.join(name), bytes).unwrap();
+        }
+        let meta = json!({
+            "schema": "cap-bundle.v1",
+            "bundle_id": "bundle-registry-test",
+            "created_at": "2026-01-01T00:00:00Z",
+            "files": {
+                "manifest.json": file("manifest", &manifest_bytes),
+                "proof.dat": file("proof", proof_bytes),
+                "registry.json": file("registry", &registry_bytes),
+            },
+            "proof_units": [{
+                "id": "main",
+                "manifest_file": "manifest.json",
+                "proof_file": "proof.dat",
+                "policy_id": "lksg.v1",
+                "policy_hash": manifest["policy"]["hash"],
+                "backend": "mock"
+            }]
+        });
+        std::fs::write(dir.join("_meta.json"), meta.to_string()).unwrap();
+
+        let source = BundleSource::Directory { path: dir.clone() };
+        let registry_check = |report: &VerifyReport| {
+            report
+                .checks
+                .iter()
+                .find(|c| c.id == "registry_match")
+                .unwrap()
+                .clone()
+        };
+
+        // Eigene Registry des Bundles: nur Warnung, der Widerruf bleibt unsichtbar
+        let opts = VerifyOptions {
+            check_registry: true,
+            ..VerifyOptions::default()
+        };
+        let report = verify_from_source(&source, Some(&opts)).unwrap();
+        let check = registry_check(&report);
+        assert_eq!(check.status, VerifyStatus::Warn);
+        assert!(check.message.contains("self-supplied registry"));
+        assert_eq!(report.details["registry_check"]["source"], "bundle");
+        assert_eq!(report.details["registry_check"]["lifecycle"], "active");
+
+        // Die maßgebliche Registry kennt den Widerruf
+        let opts = VerifyOptions {
+            registry: Some(RegistrySource::Snapshot {
+                registry: serde_json::to_value(&authoritative).unwrap(),
+            }),
+            ..opts
+        };
+        let report = verify_from_source(&source, Some(&opts)).unwrap();
+        assert_eq!(report.status, "fail");
+        assert_eq!(registry_check(&report).status, VerifyStatus::Fail);
+        assert_eq!(report.details["registry_check"]["lifecycle"], "revoked");
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_verify_registry_lifecycle() {
+        use crate::registry::{
+            sign_entry, LifecycleAction, LifecycleRecord, Registry, RegistryEntry, RevocationReason,
+        };
+        use crate::verifier::engine::VerifyInput;
+
+        let manifest = signed(&mock_manifest());
+        let manifest_bytes = serde_json::to_vec(&manifest).unwrap();
+        let proof_bytes = b"mock proof data";
+        let stmt = extract_statement_from_manifest(&manifest).unwrap();
+        let hash = |data: &[u8]| crate::crypto::hex_lower_prefixed32(crate::crypto::sha3_256(data));
+        let key = ed25519_dalek::SigningKey::from_bytes(&[9u8; 32]);
+
+        let mut entry = RegistryEntry::new(
+            "proof_001".to_string(),
+            hash(&manifest_bytes),
+            hash(proof_bytes),
+            "2026-01-01T00:00:00Z".to_string(),
+        );
+        sign_entry(&mut entry, &key).unwrap();
+        let record = |action, reason, successor: Option<&str>, effective_at: &str| {
+            let mut record = LifecycleRecord::new(
+                &entry,
+                action,
+                reason,
+                successor.map(str::to_string),
+                effective_at.to_string(),
+            )
+            .unwrap();
+            record.sign(&key).unwrap();
+            record
+        };
+        let run = |lifecycle: Vec<LifecycleRecord>| {
+            let mut registry = Registry::new();
+            registry.entries.push(entry.clone());
+            registry.lifecycle = lifecycle;
+            let opts = VerifyOptions {
+                check_timestamp: false,
+                check_registry: true,
+                registry: Some(RegistrySource::Snapshot {
+                    registry: serde_json::to_value(&registry).unwrap(),
+                }),
+                key_store: None,
+            };
+            verify_input(&VerifyInput::new(
+                &manifest,
+                &manifest_bytes,
+                proof_bytes,
+                &stmt,
+                &opts,
+            ))
+        };
+        let registry_check = |report: &VerifyReport| {
+            report
+                .checks
+                .iter()
+                .find(|c| c.id == "registry_match")
+                .unwrap()
+                .clone()
+        };
+
+        let supersede = record(
+            LifecycleAction::Supersede,
+            RevocationReason::DataCorrected,
+            Some("proof_002"),
+            "2026-02-01T00:00:00Z",
+        );
+        let revoke = record(
+            LifecycleAction::Revoke,
+            RevocationReason::KeyCompromise,
+            None,
+            "2026-03-01T00:00:00Z",
+        );
+
+        // Ersetzt: Warnung, Verifikation besteht
+        let report = run(vec![supersede.clone()]);
+        assert_eq!(report.status, "ok");
+        assert_eq!(registry_check(&report).status, VerifyStatus::Warn);
+        assert!(registry_check(&report)
+            .message
+            .contains("superseded by proof_002"));
+        assert_eq!(report.details["registry_check"]["lifecycle"], "superseded");
+        assert_eq!(
+            report.details["registry_check"]["successor_id"],
+            "proof_002"
+        );
+
+        // Widerrufen: Verifikation schlägt fehl
+        let report = run(vec![supersede.clone(), revoke.clone()]);
+        assert_eq!(report.status, "fail");
+        assert!(registry_check(&report).message.contains("key_compromise"));
+        assert_eq!(report.details["registry_check"]["lifecycle"], "revoked");
+
+        // Noch nicht wirksamer Widerruf
+        let pending = record(
+            LifecycleAction::Revoke,
+            RevocationReason::Withdrawn,
+            None,
+            "2999-01-01T00:00:00Z",
+        );
+        assert_eq!(registry_check(&run(vec![pending])).status, VerifyStatus::Ok);
+
+        // Manipulierter Record
+        let mut tampered = supersede;
+        tampered.successor_id = Some("proof_003".to_string());
+        let report = run(vec![tampered]);
+        assert_eq!(report.status, "fail");
+        assert_eq!(report.details["registry_check"]["lifecycle"], "invalid");
+    }
+
+    #[test]
+    fn test_verify_timestamp_token() {
+        use crate::registry::Timestamp;
+        use crate::verifier::engine::VerifyInput;
+
+        let tip = "0x3333333333333333333333333333333333333333333333333333333333333333";
+        let mut manifest = mock_manifest();
+        manifest["time_anchor"] = json!({
+            "kind": "tsa",
+            "reference": "./test.tsr",
+            "audit_tip_hex": tip,
+            "created_at": "2025-10-30T12:00:00Z"
+        });
+        let manifest = signed(&manifest);
+        let manifest_bytes = serde_json::to_vec(&manifest).unwrap();
+        let stmt = extract_statement_from_manifest(&manifest).unwrap();
+        let opts = VerifyOptions {
+            check_timestamp: true,
+            check_registry: false,
+            registry: None,
+            key_store: None,
+        };
+        let input = VerifyInput::new(&manifest, &manifest_bytes, b"proof", &stmt, &opts);
+
+        // Mock-Token: gültig, aber ohne RFC 3161 Signatur nur eine Warnung
+        let token = serde_json::to_value(Timestamp::create_mock(tip.to_string())).unwrap();
+        let report = verify_input(&input.with_timestamp(Some(&token)));
+        assert_eq!(report.status, "ok");
+        assert_eq!(report.overall_status(), VerifyStatus::Warn);
+        assert_eq!(report.timestamp_valid, Some(false));
+        assert_eq!(report.details["timestamp_token"]["status"], "warn");
+
+        // Token für einen anderen Audit-Tip als den verankerten
+        let token = serde_json::to_value(Timestamp::create_mock("0x44".to_string())).unwrap();
+        let report = verify_input(&input.with_timestamp(Some(&token)));
+        assert_eq!(report.status, "fail");
+        assert_eq!(report.timestamp_valid, Some(false));
+
+        let mut token = serde_json::to_value(Timestamp::create_mock(tip.to_string())).unwrap();
+        token["signature"] = json!("Zm9yZ2Vk");
+        let report = verify_input(&input.with_timestamp(Some(&token)));
+        assert_eq!(report.status, "fail");
+    }
+
+    #[test]
+    fn test_verify_rfc3161_timestamp_token() {
+        use crate::registry::rfc3161::LocalTsa;
+        use crate::registry::{RealRfc3161Provider, TimestampProvider};
+        use crate::verifier::engine::VerifyInput;
+        use der::EncodePem;
+
+        let tip = "0x3333333333333333333333333333333333333333333333333333333333333333";
+        let manifest = signed(&mock_manifest());
+        let manifest_bytes = serde_json::to_vec(&manifest).unwrap();
+        let stmt = extract_statement_from_manifest(&manifest).unwrap();
+        let opts = VerifyOptions {
+            check_timestamp: true,
+            check_registry: false,
+            registry: None,
+            key_store: None,
+        };
+
+        let tsa = LocalTsa::new("CAP Test TSA").unwrap();
+        let cert_pem = tsa.certificate_pem().unwrap();
+        let token = RealRfc3161Provider::new(tsa.spawn().unwrap())
+            .create(tip)
+            .unwrap();
+        let token = serde_json::to_value(token).unwrap();
+        let input = VerifyInput::new(&manifest, &manifest_bytes, b"proof", &stmt, &opts)
+            .with_timestamp(Some(&token));
+
+        // Gepinntes TSA-Zertifikat
+        let report = verify_input(&input.with_tsa_certificate(Some(cert_pem.as_bytes())));
+        assert_eq!(report.status, "ok");
+        assert_eq!(report.overall_status(), VerifyStatus::Ok);
+        assert_eq!(report.timestamp_valid, Some(true));
+
+        // Ohne Zertifikat: nur eingebettetes Zertifikat, Warnung
+        let report = verify_input(&input);
+        assert_eq!(report.overall_status(), VerifyStatus::Warn);
+        assert!(report
+            .checks
+            .iter()
+            .any(|c| c.message.contains("not pinned")));
+
+        // Zertifikat einer anderen TSA
+        let other = LocalTsa::new("CAP Test TSA").unwrap();
+        let other_pem = other
+            .certificate()
+            .to_pem(der::pem::LineEnding::LF)
+            .unwrap();
+        let report = verify_input(&input.with_tsa_certificate(Some(other_pem.as_bytes())));
+        assert_eq!(report.status, "fail");
+        assert_eq!(report.timestamp_valid, Some(false));
+    }
+
+    #[test]
+    fn test_verify_batch_anchored_manifest() {
+        use crate::manifest::{AnchorBatch, BatchLeafKind, Manifest, PublicChain};
+        use crate::verifier::engine::VerifyInput;
+
+        // Drei Manifeste mit eigenem Audit-Tip, Root als Public Anchor
+        let mut manifests: Vec<Manifest> = (1..=3u8)
+            .map(|i| {
+                let mut manifest: Manifest = serde_json::from_value(mock_manifest()).unwrap();
+                manifest.set_time_anchor(
+                    "blockchain".to_string(),
+                    "batch".to_string(),
+                    format!("0x{}", hex::encode([i; 32])),
+                );
+                manifest
+            })
+            .collect();
+        let batch = AnchorBatch::build(&manifests, BatchLeafKind::Manifest).unwrap();
+        for (i, manifest) in manifests.iter_mut().enumerate() {
+            manifest
+                .set_batch_anchor(batch.anchor_for(i).unwrap())
+                .unwrap();
+            manifest
+                .set_public_anchor(
+                    PublicChain::Btc,
+                    "ab".repeat(32),
+                    batch.root_hex(),
+                    Some("2026-01-01T00:00:00Z".to_string()),
+                )
+                .unwrap();
+        }
+
+        let opts = VerifyOptions {
+            check_timestamp: true,
+            check_registry: false,
+            registry: None,
+            key_store: None,
+        };
+        let run = |manifest: &serde_json::Value| {
+            let manifest = signed(manifest);
+            let manifest_bytes = serde_json::to_vec(&manifest).unwrap();
+            let stmt = extract_statement_from_manifest(&manifest).unwrap();
+            verify_input(&VerifyInput::new(
+                &manifest,
+                &manifest_bytes,
+                b"proof",
+                &stmt,
+                &opts,
+            ))
+        };
+        let batch_check = |report: &VerifyReport| {
+            report
+                .checks
+                .iter()
+                .find(|c| c.id == "batch_anchor")
+                .cloned()
+                .unwrap()
+        };
+
+        let manifest = serde_json::to_value(&manifests[1]).unwrap();
+        let report = run(&manifest);
+        assert_eq!(report.status, "ok");
+        assert_eq!(batch_check(&report).status, VerifyStatus::Ok);
+        assert_eq!(report.details["batch_anchor"]["leaf_index"], 1);
+        assert_eq!(report.details["batch_anchor"]["root"], batch.root_hex());
+
+        // Manifest nach dem Anchoring verändert: Blatt passt nicht mehr zum Pfad
+        let mut modified = manifest.clone();
+        modified["supplier_root"] = json!(format!("0x{}", "99".repeat(32)));
+        let report = run(&modified);
+        assert_eq!(report.status, "fail");
+        assert_eq!(batch_check(&report).status, VerifyStatus::Fail);
+
+        // Public Anchor auf eine andere Root
+        let mut other_root = manifest.clone();
+        other_root["time_anchor"]["public"]["digest"] = json!(format!("0x{}", "77".repeat(32)));
+        let report = run(&other_root);
+        assert_eq!(report.status, "fail");
+        assert!(batch_check(&report)
+            .message
+            .contains("does not match batch root"));
+
+        // Ohne Public Anchor ist die Root nicht verankert
+        let mut unanchored = manifest;
+        unanchored["time_anchor"]
+            .as_object_mut()
+            .unwrap()
+            .remove("public");
+        let report = run(&unanchored);
+        assert_eq!(report.status, "ok");
+        assert_eq!(batch_check(&report).status, VerifyStatus::Warn);
+    }
+
+    #[test]
+    fn test_verify_public_anchor_proof() {
+        use crate::manifest::anchor_proof::{
+            btc_merkle_branch, btc_merkle_root, display_hash, BlockHeader, HeaderStore,
+        };
+        use crate::manifest::PublicChain;
+        use crate::verifier::engine::VerifyInput;
+        use sha2::{Digest, Sha256};
+
+        let digest = [0x55u8; 32];
+        let sha256d = |data: &[u8]| -> [u8; 32] { Sha256::digest(Sha256::digest(data)).into() };
+
+        // Transaktion mit OP_RETURN-Output über den Digest, Block mit Regtest-Difficulty
+        let mut tx = vec![1, 0, 0, 0, 1];
+        tx.extend([0xab; 36]);
+        tx.extend([0, 0xff, 0xff, 0xff, 0xff, 1]);
+        tx.extend([0; 8]);
+        tx.extend([34, 0x6a, 32]);
+        tx.extend(digest);
+        tx.extend([0; 4]);
+        let txids = [[1u8; 32], sha256d(&tx)];
+        let mut header = BlockHeader {
+            version: 0x2000_0000,
+            prev_block: [0u8; 32],
+            merkle_root: btc_merkle_root(&txids),
+            time: 1_767_225_600,
+            bits: 0x207f_ffff,
+            nonce: 0,
+        };
+        while header.check_pow().is_err() {
+            header.nonce += 1;
+        }
+        let headers = HeaderStore::new(PublicChain::Btc, [(840_000, header)])
+            .unwrap()
+            .to_json();
+        let proof = serde_json::json!({
+            "version": "anchor.proof.v1",
+            "kind": "btc_spv",
+            "chain": "btc",
+            "digest": format!("0x{}", hex::encode(digest)),
+            "tx": hex::encode(&tx),
+            "tx_index": 1,
+            "merkle_branch": btc_merkle_branch(&txids, 1).iter().map(display_hash).collect::<Vec<_>>(),
+            "block_header": hex::encode(header.to_bytes()),
+        });
+
+        let tip = "0x3333333333333333333333333333333333333333333333333333333333333333";
+        let mut manifest = mock_manifest();
+        manifest["time_anchor"] = serde_json::json!({
+            "kind": "blockchain",
+            "reference": display_hash(&txids[1]),
+            "audit_tip_hex": tip,
+            "created_at": "2026-01-01T00:00:00Z",
+            "public": {
+                "chain": "btc",
+                "txid": display_hash(&txids[1]),
+                "digest": format!("0x{}", hex::encode(digest)),
+                "created_at": "2026-01-01T00:00:00Z",
+            },
+        });
+        let manifest = signed(&manifest);
+        let manifest_bytes = serde_json::to_vec(&manifest).unwrap();
+        let stmt = extract_statement_from_manifest(&manifest).unwrap();
+        let opts = VerifyOptions {
+            check_timestamp: true,
+            check_registry: false,
+            registry: None,
+            key_store: None,
+        };
+        let input = VerifyInput::new(&manifest, &manifest_bytes, b"proof", &stmt, &opts);
+        let public_anchor = |report: &VerifyReport| {
+            report
+                .checks
+                .iter()
+                .find(|c| c.id == "public_anchor")
+                .cloned()
+                .unwrap()
+        };
+
+        // Ohne Proof: Public Anchor ungeprüft
+        let report = verify_input(&input);
+        assert_eq!(report.status, "ok");
+        assert_eq!(public_anchor(&report).status, VerifyStatus::Warn);
+
+        // Proof gegen gepinnte Header
+        let report = verify_input(&input.with_anchor_proof(Some(&proof), Some(&headers)));
+        assert_eq!(report.status, "ok");
+        assert_eq!(public_anchor(&report).status, VerifyStatus::Ok);
+        assert_eq!(report.details["public_anchor"]["proof"]["height"], 840_000);
+        assert_eq!(report.timestamp_valid, Some(true));
+
+        // Proof ohne Header-Store: gültig, aber nicht gepinnt
+        let report = verify_input(&input.with_anchor_proof(Some(&proof), None));
+        assert_eq!(public_anchor(&report).status, VerifyStatus::Warn);
+        assert!(public_anchor(&report).message.contains("not pinned"));
+
+        // Proof für einen anderen Digest
+        let mut other = proof.clone();
+        other["digest"] = serde_json::json!(format!("0x{}", hex::encode([0x66u8; 32])));
+        let report = verify_input(&input.with_anchor_proof(Some(&other), Some(&headers)));
+        assert_eq!(report.status, "fail");
+        assert_eq!(public_anchor(&report).status, VerifyStatus::Fail);
+        assert_eq!(report.details["public_anchor"]["status"], "fail");
+    }
+
+    #[test]
+    fn test_verify_and_core_verify_agree() {
+        use crate::verifier::{verify_core, CoreVerifyInput, CoreVerifyOptions};
+
+        let hash = |data: &[u8]| crate::crypto::hex_lower_prefixed32(crate::crypto::sha3_256(data));
+        let stmt = extract_statement_from_manifest(&mock_manifest()).unwrap();
+
+        // Unsigniert, signiert, nachträglich verändert
+        let mut tampered = signed(&mock_manifest());
+        tampered["audit"]["events_count"] = json!(11);
+        for manifest in [mock_manifest(), signed(&mock_manifest()), tampered] {
+            let manifest_bytes = serde_json::to_vec(&manifest).unwrap();
+            let report = verify(&manifest, b"proof", &stmt, &VerifyOptions::default()).unwrap();
+
+            let result = verify_core(CoreVerifyInput {
+                protocol_version: "cap-core-verify.v1".to_string(),
+                manifest_hash: hash(&manifest_bytes),
+                proof_hash: hash(b"proof"),
+                manifest_bytes,
+                proof_bytes: b"proof".to_vec(),
+                policy_hash: stmt.policy_hash.clone(),
+                policy_id: "lksg.v1".to_string(),
+                backend: "mock".to_string(),
+                signature: None,
+                public_key: None,
+                timestamp_attestation: None,
+                registry_entry_json: None,
+                tsa_certificate: None,
+                anchor_proof_json: None,
+                anchor_headers_json: None,
+                options: CoreVerifyOptions {
+                    check_signature: true,
+                    check_timestamp: false,
+                    check_registry: false,
+                },
+            });
+
+            assert_eq!(report.status == "ok", result.status.is_pass());
+            assert_eq!(Some(report.signature_valid), result.signature_valid);
+            let verdicts = |checks: &[CheckResult]| {
+                checks
+                    .iter()
+                    .map(|c| (c.id.clone(), c.status))
+                    .collect::<Vec<_>>()
+            };
+            assert_eq!(verdicts(&report.checks), verdicts(&result.checks));
+        }
+    }
 }
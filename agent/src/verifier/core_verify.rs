@@ -6,10 +6,14 @@
 //!
 //! Portabel für: CLI, Tests, WASM, zkVM, REST API
 
-use anyhow::{anyhow, Result};
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
 
+use super::engine::{CheckRegistry, VerifyInput};
+use super::statement::extract_statement_from_manifest;
+pub use super::types::{CheckKind, CheckResult, VerifyStatus};
+use super::types::{ProofStatement, VerifyOptions};
+
 /// Verifikations-Optionen (alle optional/toggelbar)
 #[derive(Clone, Debug)]
 pub struct CoreVerifyOptions {
@@ -59,35 +63,14 @@ pub struct CoreVerifyInput {
     pub timestamp_attestation: Option<Vec<u8>>,
     pub registry_entry_json: Option<String>,
 
-    pub options: CoreVerifyOptions,
-}
-
-/// Status eines einzelnen Checks oder des Gesamtergebnisses
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
-#[serde(rename_all = "lowercase")]
-pub enum VerifyStatus {
-    /// Verifikation erfolgreich
-    Ok,
-    /// Warnung, aber nicht kritisch
-    Warn,
-    /// Verifikation fehlgeschlagen
-    Fail,
-    /// Interner Fehler (Parsing, etc.)
-    Error,
-}
-
-/// Einzelner Verifikations-Check
-#[derive(Clone, Debug, Serialize, Deserialize)]
-pub struct CheckResult {
-    /// Maschinenlesbare ID des Checks, z.B. "hash_match_manifest"
-    pub id: String,
+    /// Optional: TSA-Zertifikat (PEM/DER) für RFC 3161 Timestamps
+    pub tsa_certificate: Option<Vec<u8>>,
 
-    /// Kategorie des Checks: "integrity" | "policy" | "signature" | "timestamp" | "registry"
-    pub kind: String,
+    /// Optional: Inclusion-Proof des Public Anchors und gepinnte Block-Header (JSON)
+    pub anchor_proof_json: Option<String>,
+    pub anchor_headers_json: Option<String>,
 
-    pub status: VerifyStatus,
-
-    pub message: String,
+    pub options: CoreVerifyOptions,
 }
 
 /// Strukturiertes Verifikationsergebnis
@@ -113,10 +96,14 @@ pub struct CoreVerifyResult {
 
 /// Zentrale I/O-freie Verifikationsfunktion
 ///
+/// Adapter auf die Verification Engine (`verifier::engine`): führt dieselben
+/// Checks aus wie `verifier::verify`, ergänzt um die hier übergebenen
+/// Soll-Hashes, Policy-Hash, Detached-Signatur, Timestamp und Registry-Eintrag.
+///
 /// # Verifikationsschritte
 /// 1. Hash-Verifikation (Manifest & Proof)
 /// 2. Policy-/Statement-Validierung
-/// 3. Optional: Signatur-Check
+/// 3. Optional: Signatur-Check (eingebettete und Detached-Signaturen)
 /// 4. Optional: Timestamp-Validierung
 /// 5. Optional: Registry-Match
 ///
@@ -127,288 +114,88 @@ pub struct CoreVerifyResult {
 /// - Alle Inputs sind In-Memory Datenstrukturen
 pub fn verify_core(input: CoreVerifyInput) -> CoreVerifyResult {
     let started_at = Utc::now().to_rfc3339();
-    let mut checks = Vec::new();
-
-    // 1. Hash-Verifikation (Manifest)
-    let computed_manifest_hash = crate::crypto::sha3_256(&input.manifest_bytes);
-    let computed_manifest_hex = crate::crypto::hex_lower_prefixed32(computed_manifest_hash);
-
-    checks.push(CheckResult {
-        id: "hash_match_manifest".to_string(),
-        kind: "integrity".to_string(),
-        status: if computed_manifest_hex == input.manifest_hash {
-            VerifyStatus::Ok
-        } else {
-            VerifyStatus::Fail
-        },
-        message: format!(
-            "Manifest hash: expected {}, got {}",
-            input.manifest_hash, computed_manifest_hex
-        ),
-    });
-
-    // 2. Hash-Verifikation (Proof)
-    let computed_proof_hash = crate::crypto::sha3_256(&input.proof_bytes);
-    let computed_proof_hex = crate::crypto::hex_lower_prefixed32(computed_proof_hash);
-
-    checks.push(CheckResult {
-        id: "hash_match_proof".to_string(),
-        kind: "integrity".to_string(),
-        status: if computed_proof_hex == input.proof_hash {
-            VerifyStatus::Ok
-        } else {
-            VerifyStatus::Fail
-        },
-        message: format!(
-            "Proof hash: expected {}, got {}",
-            input.proof_hash, computed_proof_hex
-        ),
-    });
-
-    // 3. Policy-Verifikation
-    // Parse Manifest JSON
-    match serde_json::from_slice::<serde_json::Value>(&input.manifest_bytes) {
-        Ok(manifest) => {
-
-            // Prüfe Policy-Hash im Manifest gegen input.policy_hash
-            if let Some(policy_obj) = manifest.get("policy") {
-                if let Some(manifest_policy_hash) = policy_obj.get("hash").and_then(|v| v.as_str())
-                {
-                    checks.push(CheckResult {
-                        id: "policy_hash_match".to_string(),
-                        kind: "policy".to_string(),
-                        status: if manifest_policy_hash == input.policy_hash {
-                            VerifyStatus::Ok
-                        } else {
-                            VerifyStatus::Fail
-                        },
-                        message: format!(
-                            "Policy hash in manifest: {}, expected: {}",
-                            manifest_policy_hash, input.policy_hash
-                        ),
-                    });
-                } else {
-                    checks.push(CheckResult {
-                        id: "policy_hash_missing".to_string(),
-                        kind: "policy".to_string(),
-                        status: VerifyStatus::Warn,
-                        message: "Policy hash not found in manifest".to_string(),
-                    });
-                }
-            } else {
-                checks.push(CheckResult {
-                    id: "policy_missing".to_string(),
-                    kind: "policy".to_string(),
-                    status: VerifyStatus::Error,
-                    message: "Policy section not found in manifest".to_string(),
-                });
-            }
-
-            // TODO: Weitere Policy-Checks (z.B. Proof-Verifikation gegen Constraints)
-            // Hier würde die bestehende Policy-Engine aus verifier/core.rs integriert werden
-        }
-        Err(e) => {
-            checks.push(CheckResult {
-                id: "parse_manifest".to_string(),
-                kind: "integrity".to_string(),
-                status: VerifyStatus::Error,
-                message: format!("Failed to parse manifest JSON: {}", e),
-            });
-        }
-    }
-
-    // 4. Optional: Signatur-Check
-    if input.options.check_signature {
-        if let (Some(sig), Some(pubkey)) = (&input.signature, &input.public_key) {
-            match verify_signature(&input.manifest_bytes, sig, pubkey) {
-                Ok(valid) => {
-                    checks.push(CheckResult {
-                        id: "signature_valid".to_string(),
-                        kind: "signature".to_string(),
-                        status: if valid {
-                            VerifyStatus::Ok
-                        } else {
-                            VerifyStatus::Fail
-                        },
-                        message: if valid {
-                            "Ed25519 signature valid".to_string()
-                        } else {
-                            "Ed25519 signature invalid".to_string()
-                        },
-                    });
-                }
-                Err(e) => {
-                    checks.push(CheckResult {
-                        id: "signature_error".to_string(),
-                        kind: "signature".to_string(),
-                        status: VerifyStatus::Error,
-                        message: format!("Signature verification error: {}", e),
-                    });
-                }
-            }
-        } else {
-            checks.push(CheckResult {
-                id: "signature_missing".to_string(),
-                kind: "signature".to_string(),
-                status: VerifyStatus::Warn,
-                message: "No signature provided (check_signature enabled but no data)".to_string(),
-            });
-        }
-    }
-
-    // 5. Optional: Timestamp-Check
-    if input.options.check_timestamp {
-        if let Some(_ts) = &input.timestamp_attestation {
-            // TODO: Echte RFC3161-Verifikation (aktuell Mock)
-            checks.push(CheckResult {
-                id: "timestamp_valid".to_string(),
-                kind: "timestamp".to_string(),
-                status: VerifyStatus::Ok,
-                message: "Timestamp valid (mock)".to_string(),
-            });
-        } else {
-            checks.push(CheckResult {
-                id: "timestamp_missing".to_string(),
-                kind: "timestamp".to_string(),
-                status: VerifyStatus::Warn,
-                message: "No timestamp provided".to_string(),
-            });
-        }
-    }
 
-    // 6. Optional: Registry-Check
-    if input.options.check_registry {
-        if let Some(reg_json) = &input.registry_entry_json {
-            // Prüfe ob Hashes im Registry-Eintrag matchen
-            match serde_json::from_str::<serde_json::Value>(reg_json) {
-                Ok(entry) => {
-                    let manifest_match = entry
-                        .get("manifest_hash")
-                        .and_then(|v| v.as_str())
-                        .map(|h| h == input.manifest_hash)
-                        .unwrap_or(false);
-
-                    let proof_match = entry
-                        .get("proof_hash")
-                        .and_then(|v| v.as_str())
-                        .map(|h| h == input.proof_hash)
-                        .unwrap_or(false);
-
-                    checks.push(CheckResult {
-                        id: "registry_match".to_string(),
-                        kind: "registry".to_string(),
-                        status: if manifest_match && proof_match {
-                            VerifyStatus::Ok
-                        } else {
-                            VerifyStatus::Fail
-                        },
-                        message: format!(
-                            "Registry entry: manifest_match={}, proof_match={}",
-                            manifest_match, proof_match
-                        ),
-                    });
-                }
-                Err(e) => {
-                    checks.push(CheckResult {
-                        id: "registry_parse_error".to_string(),
-                        kind: "registry".to_string(),
-                        status: VerifyStatus::Error,
-                        message: format!("Failed to parse registry entry: {}", e),
-                    });
-                }
-            }
-        } else {
-            checks.push(CheckResult {
-                id: "registry_missing".to_string(),
-                kind: "registry".to_string(),
-                status: VerifyStatus::Warn,
-                message: "No registry entry provided".to_string(),
-            });
-        }
-    }
-
-    finalize_result(checks, input, started_at)
-}
-
-/// Finalisiert das Verifikationsergebnis
-fn finalize_result(
-    checks: Vec<CheckResult>,
-    input: CoreVerifyInput,
-    started_at: String,
-) -> CoreVerifyResult {
-    let finished_at = Utc::now().to_rfc3339();
-
-    // Gesamtstatus: Fail wenn mind. ein Fail, Error wenn mind. ein Error, Warn wenn mind. ein Warn, sonst Ok
-    let overall_status =
-        checks
-            .iter()
-            .fold(VerifyStatus::Ok, |acc, check| match (acc, check.status) {
-                (VerifyStatus::Error, _) | (_, VerifyStatus::Error) => VerifyStatus::Error,
-                (VerifyStatus::Fail, _) | (_, VerifyStatus::Fail) => VerifyStatus::Fail,
-                (VerifyStatus::Warn, _) | (_, VerifyStatus::Warn) => VerifyStatus::Warn,
-                _ => VerifyStatus::Ok,
-            });
-
-    // Extrahiere optionale Felder
-    let signature_valid = checks
-        .iter()
-        .find(|c| c.id == "signature_valid")
-        .map(|c| c.status == VerifyStatus::Ok);
-
-    let timestamp_valid = checks
-        .iter()
-        .find(|c| c.id == "timestamp_valid")
-        .map(|c| c.status == VerifyStatus::Ok);
-
-    let registry_match = checks
-        .iter()
-        .find(|c| c.id == "registry_match")
-        .map(|c| c.status == VerifyStatus::Ok);
+    let manifest: serde_json::Value =
+        serde_json::from_slice(&input.manifest_bytes).unwrap_or(serde_json::Value::Null);
+    let stmt = statement_for(&manifest, &input.policy_hash);
+    let opts = VerifyOptions {
+        check_timestamp: input.options.check_timestamp,
+        check_registry: input.options.check_registry,
+        registry: None,
+        key_store: None,
+    };
+    let timestamp: Option<serde_json::Value> = input
+        .timestamp_attestation
+        .as_deref()
+        .map(|bytes| serde_json::from_slice(bytes).unwrap_or(serde_json::Value::Null));
+    let registry_entry: Option<serde_json::Value> = input
+        .registry_entry_json
+        .as_deref()
+        .map(|json| serde_json::from_str(json).unwrap_or(serde_json::Value::Null));
+    let anchor_proof: Option<serde_json::Value> = input
+        .anchor_proof_json
+        .as_deref()
+        .map(|json| serde_json::from_str(json).unwrap_or(serde_json::Value::Null));
+    let anchor_headers: Option<serde_json::Value> = input
+        .anchor_headers_json
+        .as_deref()
+        .map(|json| serde_json::from_str(json).unwrap_or(serde_json::Value::Null));
+
+    let engine_input = VerifyInput::new(
+        &manifest,
+        &input.manifest_bytes,
+        &input.proof_bytes,
+        &stmt,
+        &opts,
+    )
+    .with_expected_hashes(Some(&input.manifest_hash), Some(&input.proof_hash))
+    .with_detached_signature(input.signature.as_deref().zip(input.public_key.as_deref()))
+    .with_timestamp(timestamp.as_ref())
+    .with_tsa_certificate(input.tsa_certificate.as_deref())
+    .with_anchor_proof(anchor_proof.as_ref(), anchor_headers.as_ref())
+    .with_registry_entry(registry_entry.as_ref());
+
+    let registry = if input.options.check_signature {
+        CheckRegistry::standard()
+    } else {
+        CheckRegistry::standard().without(CheckKind::Signature)
+    };
+    let report = registry.run(&engine_input);
 
     CoreVerifyResult {
-        status: overall_status,
+        status: report.overall_status(),
         manifest_hash: input.manifest_hash,
         proof_hash: input.proof_hash,
         policy_id: input.policy_id,
         policy_hash: input.policy_hash,
         backend: input.backend,
-        checks,
-        signature_valid,
-        timestamp_valid,
-        registry_match,
+        signature_valid: input
+            .options
+            .check_signature
+            .then_some(report.signature_valid),
+        timestamp_valid: report.timestamp_valid,
+        registry_match: report.registry_match,
+        checks: report.checks,
         started_at,
-        finished_at,
+        finished_at: Utc::now().to_rfc3339(),
     }
 }
 
-/// Verifiziert Ed25519-Signatur
-fn verify_signature(data: &[u8], sig: &[u8], pubkey: &[u8]) -> Result<bool> {
-    use crate::crypto::{Ed25519PublicKey, Ed25519Signature};
-
-    if sig.len() != 64 {
-        return Err(anyhow!(
-            "Invalid signature length: {}, expected 64",
-            sig.len()
-        ));
-    }
-
-    if pubkey.len() != 32 {
-        return Err(anyhow!(
-            "Invalid public key length: {}, expected 32",
-            pubkey.len()
-        ));
-    }
-
-    // Convert slices to fixed-size arrays
-    let pubkey_array: &[u8; 32] = pubkey.try_into()?;
-    let sig_array: &[u8; 64] = sig.try_into()?;
-
-    let pubkey = Ed25519PublicKey::from_bytes(pubkey_array)?;
-    let signature = Ed25519Signature::from_bytes(sig_array);
-
-    // ed25519_verify returns bool, not Result
-    let valid = crate::crypto::ed25519_verify(&pubkey, data, &signature);
-    Ok(valid)
+/// Statement aus dem Manifest, mit dem erwarteten Policy-Hash des Aufrufers
+fn statement_for(manifest: &serde_json::Value, policy_hash: &str) -> ProofStatement {
+    let mut stmt = extract_statement_from_manifest(manifest).unwrap_or_else(|_| ProofStatement {
+        policy_hash: String::new(),
+        company_commitment_root: manifest
+            .get("company_commitment_root")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .to_string(),
+        sanctions_root: None,
+        jurisdiction_root: None,
+        extensions: None,
+    });
+    stmt.policy_hash = policy_hash.to_string();
+    stmt
 }
 
 #[cfg(test)]
@@ -430,6 +217,9 @@ mod tests {
             public_key: None,
             timestamp_attestation: None,
             registry_entry_json: None,
+            tsa_certificate: None,
+            anchor_proof_json: None,
+            anchor_headers_json: None,
             options: CoreVerifyOptions::default(),
         };
 
@@ -459,6 +249,9 @@ mod tests {
             public_key: None,
             timestamp_attestation: None,
             registry_entry_json: None,
+            tsa_certificate: None,
+            anchor_proof_json: None,
+            anchor_headers_json: None,
             options: CoreVerifyOptions {
                 check_signature: false,
                 check_timestamp: false,
@@ -503,6 +296,9 @@ mod tests {
             public_key: None,
             timestamp_attestation: None,
             registry_entry_json: None,
+            tsa_certificate: None,
+            anchor_proof_json: None,
+            anchor_headers_json: None,
             options: CoreVerifyOptions {
                 check_signature: false,
                 check_timestamp: false,
@@ -544,6 +340,9 @@ mod tests {
             public_key: Some(vec![0u8; 32]),
             timestamp_attestation: Some(b"mock_ts".to_vec()),
             registry_entry_json: Some("{}".to_string()),
+            tsa_certificate: None,
+            anchor_proof_json: None,
+            anchor_headers_json: None,
             options: CoreVerifyOptions {
                 check_signature: false, // Deaktiviert!
                 check_timestamp: false,
@@ -554,9 +353,9 @@ mod tests {
         let result = verify_core(input);
 
         // Keine Signatur/Timestamp/Registry-Checks sollten ausgeführt worden sein
-        assert!(!result.checks.iter().any(|c| c.kind == "signature"));
-        assert!(!result.checks.iter().any(|c| c.kind == "timestamp"));
-        assert!(!result.checks.iter().any(|c| c.kind == "registry"));
+        assert!(!result.checks.iter().any(|c| c.kind == CheckKind::Signature));
+        assert!(!result.checks.iter().any(|c| c.kind == CheckKind::Timestamp));
+        assert!(!result.checks.iter().any(|c| c.kind == CheckKind::Registry));
     }
 
     #[test]
@@ -579,6 +378,9 @@ mod tests {
             public_key: None,
             timestamp_attestation: None,
             registry_entry_json: None,
+            tsa_certificate: None,
+            anchor_proof_json: None,
+            anchor_headers_json: None,
             options: CoreVerifyOptions {
                 check_signature: false,
                 check_timestamp: false,
@@ -0,0 +1,369 @@
+//! Verification Engine - Single check pipeline for all verifier entry points
+//!
+//! `verify`, `verify_from_source`, `verify_core`, the CLI commands and
+//! `package_verifier::Verifier` all run a [`CheckRegistry`] over a
+//! [`VerifyInput`]. The overall status is derived from the typed
+//! [`CheckResult`]s only, so a bundle gets the same verdict in every tool.
+//!
+//! Checks are pluggable: [`CheckRegistry::standard`] contains the built-in
+//! checks (see `checks`), further checks can be added with
+//! [`CheckRegistry::register`].
+
+use super::checks;
+use super::types::{
+    CheckKind, CheckResult, ProofStatement, VerifyOptions, VerifyReport, VerifyStatus,
+};
+use crate::crypto;
+
+/// Legacy `details` map of a [`VerifyReport`]
+pub type Details = serde_json::Map<String, serde_json::Value>;
+
+/// In-memory input of a verification run
+#[derive(Clone, Copy, Debug)]
+pub struct VerifyInput<'a> {
+    /// Parsed manifest JSON
+    pub manifest: &'a serde_json::Value,
+
+    /// Manifest bytes as stored (manifest hash is computed over these)
+    pub manifest_bytes: &'a [u8],
+
+    /// Raw proof bytes
+    pub proof_bytes: &'a [u8],
+
+    /// Statement the manifest must match
+    pub stmt: &'a ProofStatement,
+
+    pub opts: &'a VerifyOptions,
+
+    /// Expected hashes (e.g. from `_meta.json`), checked if present
+    pub expected_manifest_hash: Option<&'a str>,
+    pub expected_proof_hash: Option<&'a str>,
+
+    /// Detached Ed25519 signature over `manifest_bytes`: (signature, public key)
+    pub detached_signature: Option<(&'a [u8], &'a [u8])>,
+
+    /// Timestamp token (`registry::Timestamp` as JSON)
+    pub timestamp: Option<&'a serde_json::Value>,
+
+    /// TSA certificate (PEM or DER) RFC 3161 tokens must be signed with
+    pub tsa_certificate: Option<&'a [u8]>,
+
+    /// Inclusion proof for the public time anchor (`AnchorProof` as JSON)
+    pub anchor_proof: Option<&'a serde_json::Value>,
+
+    /// Pinned block headers the anchor proof is checked against (`HeaderStore` file as JSON)
+    pub anchor_headers: Option<&'a serde_json::Value>,
+
+    /// Registry entry for the manifest/proof pair (`RegistryEntry` as JSON)
+    pub registry_entry: Option<&'a serde_json::Value>,
+}
+
+impl<'a> VerifyInput<'a> {
+    pub fn new(
+        manifest: &'a serde_json::Value,
+        manifest_bytes: &'a [u8],
+        proof_bytes: &'a [u8],
+        stmt: &'a ProofStatement,
+        opts: &'a VerifyOptions,
+    ) -> Self {
+        VerifyInput {
+            manifest,
+            manifest_bytes,
+            proof_bytes,
+            stmt,
+            opts,
+            expected_manifest_hash: None,
+            expected_proof_hash: None,
+            detached_signature: None,
+            timestamp: None,
+            tsa_certificate: None,
+            anchor_proof: None,
+            anchor_headers: None,
+            registry_entry: None,
+        }
+    }
+
+    pub fn with_expected_hashes(
+        mut self,
+        manifest_hash: Option<&'a str>,
+        proof_hash: Option<&'a str>,
+    ) -> Self {
+        self.expected_manifest_hash = manifest_hash;
+        self.expected_proof_hash = proof_hash;
+        self
+    }
+
+    pub fn with_detached_signature(mut self, signature: Option<(&'a [u8], &'a [u8])>) -> Self {
+        self.detached_signature = signature;
+        self
+    }
+
+    pub fn with_timestamp(mut self, timestamp: Option<&'a serde_json::Value>) -> Self {
+        self.timestamp = timestamp;
+        self
+    }
+
+    pub fn with_tsa_certificate(mut self, certificate: Option<&'a [u8]>) -> Self {
+        self.tsa_certificate = certificate;
+        self
+    }
+
+    pub fn with_anchor_proof(
+        mut self,
+        proof: Option<&'a serde_json::Value>,
+        headers: Option<&'a serde_json::Value>,
+    ) -> Self {
+        self.anchor_proof = proof;
+        self.anchor_headers = headers;
+        self
+    }
+
+    pub fn with_registry_entry(mut self, entry: Option<&'a serde_json::Value>) -> Self {
+        self.registry_entry = entry;
+        self
+    }
+}
+
+/// Outcome of a single check
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CheckOutcome {
+    pub status: VerifyStatus,
+    pub message: String,
+}
+
+impl CheckOutcome {
+    pub fn new(status: VerifyStatus, message: impl Into<String>) -> Self {
+        CheckOutcome {
+            status,
+            message: message.into(),
+        }
+    }
+
+    pub fn ok(message: impl Into<String>) -> Self {
+        Self::new(VerifyStatus::Ok, message)
+    }
+
+    pub fn fail(message: impl Into<String>) -> Self {
+        Self::new(VerifyStatus::Fail, message)
+    }
+
+    /// Ok or Fail depending on `passed`
+    pub fn from_bool(passed: bool, message: impl Into<String>) -> Self {
+        let status = if passed {
+            VerifyStatus::Ok
+        } else {
+            VerifyStatus::Fail
+        };
+        Self::new(status, message)
+    }
+}
+
+/// A pluggable verification check
+pub trait Check: Send + Sync {
+    /// Machine-readable check ID
+    fn id(&self) -> &'static str;
+
+    fn kind(&self) -> CheckKind;
+
+    /// Runs the check
+    ///
+    /// Returns `None` if the check does not apply to the input (e.g. disabled
+    /// by options or nothing to check). Findings for the legacy report go
+    /// into `details`.
+    fn run(&self, input: &VerifyInput, details: &mut Details) -> Option<CheckOutcome>;
+}
+
+/// Ordered set of checks run by the engine
+pub struct CheckRegistry {
+    checks: Vec<Box<dyn Check>>,
+}
+
+impl Default for CheckRegistry {
+    fn default() -> Self {
+        Self::standard()
+    }
+}
+
+impl CheckRegistry {
+    /// Registry without any checks
+    pub fn empty() -> Self {
+        CheckRegistry { checks: Vec::new() }
+    }
+
+    /// Registry with all built-in checks
+    pub fn standard() -> Self {
+        let mut registry = Self::empty();
+        registry
+            .register(checks::ManifestStructureCheck)
+            .register(checks::ManifestHashCheck)
+            .register(checks::ProofHashCheck)
+            .register(checks::StatementCheck)
+            .register(checks::SignatureCheck)
+            .register(checks::ListExclusionCheck)
+            .register(checks::PolicyValidityCheck)
+            .register(checks::TimestampCheck)
+            .register(checks::AnchorCheck)
+            .register(checks::BatchAnchorCheck)
+            .register(checks::PublicAnchorCheck)
+            .register(checks::RegistryCheck);
+        registry
+    }
+
+    /// Appends a check
+    pub fn register(&mut self, check: impl Check + 'static) -> &mut Self {
+        self.checks.push(Box::new(check));
+        self
+    }
+
+    /// Removes all checks of a kind
+    pub fn without(mut self, kind: CheckKind) -> Self {
+        self.checks.retain(|check| check.kind() != kind);
+        self
+    }
+
+    /// IDs of the registered checks in execution order
+    pub fn ids(&self) -> Vec<&'static str> {
+        self.checks.iter().map(|check| check.id()).collect()
+    }
+
+    /// Runs all checks and builds the report
+    ///
+    /// The report passes if no check ends in `Fail` or `Error`.
+    pub fn run(&self, input: &VerifyInput) -> VerifyReport {
+        let manifest_hash = crypto::hex_lower_prefixed32(crypto::sha3_256(input.manifest_bytes));
+        let proof_hash = crypto::hex_lower_prefixed32(crypto::sha3_256(input.proof_bytes));
+
+        let mut details = Details::new();
+        details.insert(
+            "manifest_hash".to_string(),
+            serde_json::json!(manifest_hash),
+        );
+        details.insert("proof_hash".to_string(), serde_json::json!(proof_hash));
+
+        let mut results = Vec::with_capacity(self.checks.len());
+        for check in &self.checks {
+            if let Some(outcome) = check.run(input, &mut details) {
+                results.push(CheckResult {
+                    id: check.id().to_string(),
+                    kind: check.kind(),
+                    status: outcome.status,
+                    message: outcome.message,
+                });
+            }
+        }
+
+        let passed = |kind: CheckKind| -> Option<bool> {
+            let mut of_kind = results.iter().filter(|c| c.kind == kind).peekable();
+            of_kind.peek()?;
+            Some(of_kind.all(|c| c.status == VerifyStatus::Ok))
+        };
+        let signature_valid = passed(CheckKind::Signature).unwrap_or(false);
+        let timestamp_valid = match (passed(CheckKind::Timestamp), passed(CheckKind::Anchor)) {
+            (None, None) => None,
+            (ts, anchor) => Some(ts.unwrap_or(true) && anchor.unwrap_or(true)),
+        };
+        let registry_match = passed(CheckKind::Registry);
+
+        let checks_passed = results
+            .iter()
+            .filter(|c| c.status == VerifyStatus::Ok)
+            .count();
+        details.insert(
+            "checks_passed".to_string(),
+            serde_json::json!(checks_passed),
+        );
+        details.insert("checks_total".to_string(), serde_json::json!(results.len()));
+
+        let mut report = VerifyReport {
+            status: String::new(),
+            manifest_hash,
+            proof_hash,
+            signature_valid,
+            timestamp_valid,
+            registry_match,
+            details: serde_json::Value::Object(details),
+            checks: results,
+        };
+        report.status = if report.overall_status().is_pass() {
+            "ok"
+        } else {
+            "fail"
+        }
+        .to_string();
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct AlwaysWarn;
+
+    impl Check for AlwaysWarn {
+        fn id(&self) -> &'static str {
+            "always_warn"
+        }
+
+        fn kind(&self) -> CheckKind {
+            CheckKind::Policy
+        }
+
+        fn run(&self, _input: &VerifyInput, details: &mut Details) -> Option<CheckOutcome> {
+            details.insert("always_warn".to_string(), serde_json::json!(true));
+            Some(CheckOutcome::new(VerifyStatus::Warn, "custom finding"))
+        }
+    }
+
+    fn stmt() -> ProofStatement {
+        ProofStatement {
+            policy_hash: "0x00".to_string(),
+            company_commitment_root: "0x00".to_string(),
+            sanctions_root: None,
+            jurisdiction_root: None,
+            extensions: None,
+        }
+    }
+
+    #[test]
+    fn test_standard_registry_contains_all_kinds() {
+        let registry = CheckRegistry::standard();
+        let kinds: Vec<_> = registry.checks.iter().map(|c| c.kind()).collect();
+        for kind in [
+            CheckKind::Integrity,
+            CheckKind::Policy,
+            CheckKind::Signature,
+            CheckKind::Timestamp,
+            CheckKind::Registry,
+            CheckKind::Anchor,
+        ] {
+            assert!(kinds.contains(&kind), "missing {:?}", kind);
+        }
+
+        let without = CheckRegistry::standard().without(CheckKind::Signature);
+        assert!(!without.ids().contains(&"signatures"));
+    }
+
+    #[test]
+    fn test_custom_check_is_reported() {
+        let manifest = serde_json::json!({});
+        let stmt = stmt();
+        let opts = VerifyOptions::default();
+        let input = VerifyInput::new(&manifest, b"{}", b"proof", &stmt, &opts);
+
+        let mut registry = CheckRegistry::empty();
+        registry.register(AlwaysWarn);
+        let report = registry.run(&input);
+
+        // Warnings do not fail verification
+        assert_eq!(report.status, "ok");
+        assert_eq!(report.overall_status(), VerifyStatus::Warn);
+        assert_eq!(report.checks.len(), 1);
+        assert_eq!(report.checks[0].id, "always_warn");
+        assert_eq!(report.details["always_warn"], true);
+        assert_eq!(report.details["checks_passed"], 0);
+        assert_eq!(report.details["checks_total"], 1);
+        assert!(!report.signature_valid);
+        assert!(report.timestamp_valid.is_none());
+    }
+}
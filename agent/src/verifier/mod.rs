@@ -8,25 +8,31 @@
 //! - `types`: Core data structures (ProofStatement, VerifyOptions, VerifyReport)
 //! - `statement`: Statement extraction from manifests
 //! - `verify`: Pure verification logic
+//! - `engine`: Check registry and report assembly shared by all entry points
+//! - `checks`: Built-in checks (integrity, policy, signature, timestamp, anchor, registry)
 //! - `core`: Re-export layer for backward compatibility
-//! - `core_verify`: Alternative verification interface
+//! - `core_verify`: Core-Verify API (adapter on the engine)
 
 // Core modules (v0.11 split)
 pub mod statement;
 pub mod types;
 pub mod verify;
 
+// Verification engine
+pub mod checks;
+pub mod engine;
+
 // Re-export layer
 pub mod core;
 
-// Alternative verification interface
+// Core-Verify API
 pub mod core_verify;
 
 // Re-export main types for convenience (via core for backward compatibility)
 pub use core::{
-    extract_statement_from_manifest, verify, verify_from_source, ProofStatement, VerifyOptions,
-    VerifyReport,
-};
-pub use core_verify::{
-    verify_core, CheckResult, CoreVerifyInput, CoreVerifyOptions, CoreVerifyResult, VerifyStatus,
+    extract_statement_from_manifest, verify, verify_bytes, verify_from_source, ProofStatement,
+    VerifyOptions, VerifyReport,
 };
+pub use core_verify::{verify_core, CoreVerifyInput, CoreVerifyOptions, CoreVerifyResult};
+pub use engine::{Check, CheckOutcome, CheckRegistry, VerifyInput};
+pub use types::{CheckKind, CheckResult, RegistrySource, VerifyStatus};
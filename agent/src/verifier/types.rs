@@ -3,7 +3,9 @@
 //! Contains:
 //! - ProofStatement: Cryptographic commitments from manifest
 //! - VerifyOptions: Verification check configuration
+//! - RegistrySource: Registry the registry check reads entries from
 //! - VerifyReport: Structured verification results
+//! - CheckResult: Typed result of a single verification check
 
 use serde::{Deserialize, Serialize};
 
@@ -42,6 +44,35 @@ pub struct VerifyOptions {
 
     /// Check registry match (requires registry entry data)
     pub check_registry: bool,
+
+    /// Registry the registry check looks up the manifest/proof entry in
+    /// (an entry passed in `VerifyInput` takes precedence)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub registry: Option<RegistrySource>,
+
+    /// Key store directory; if set, the kid of a signed registry entry must be
+    /// known and not revoked
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key_store: Option<String>,
+}
+
+/// Source of registry entries for the registry check
+///
+/// `Json` and `Sqlite` are read from disk, `Snapshot` is an exported registry
+/// held in memory. `Bundle` is the `registry.json` shipped inside the verified
+/// bundle itself: the prover supplied it, so it cannot show that an entry was
+/// not revoked and the registry check only warns.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "backend", rename_all = "lowercase")]
+pub enum RegistrySource {
+    /// JSON registry file
+    Json { path: String },
+    /// SQLite registry database
+    Sqlite { path: String },
+    /// Exported registry (`Registry` as JSON)
+    Snapshot { registry: serde_json::Value },
+    /// Registry shipped inside the verified bundle (`Registry` as JSON)
+    Bundle { registry: serde_json::Value },
 }
 
 impl Default for VerifyOptions {
@@ -53,6 +84,8 @@ impl Default for VerifyOptions {
         Self {
             check_timestamp: false,
             check_registry: false,
+            registry: None,
+            key_store: None,
         }
     }
 }
@@ -85,6 +118,82 @@ pub struct VerifyReport {
 
     /// Structured details about verification findings
     pub details: serde_json::Value,
+
+    /// Typed results of all checks that were run
+    #[serde(default)]
+    pub checks: Vec<CheckResult>,
+}
+
+/// Category of a verification check
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckKind {
+    /// Manifest/proof hashes and structure
+    Integrity,
+    /// Statement, list exclusions and policy validity
+    Policy,
+    /// Manifest signatures
+    Signature,
+    /// Time anchor and timestamp tokens
+    Timestamp,
+    /// Registry entry match
+    Registry,
+    /// Private/public anchor consistency
+    Anchor,
+}
+
+/// Status of a single check or of the overall result
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VerifyStatus {
+    /// Check passed
+    Ok,
+    /// Finding that does not fail verification
+    Warn,
+    /// Check failed
+    Fail,
+    /// Check could not be performed (parsing, etc.)
+    Error,
+}
+
+impl VerifyStatus {
+    /// Combines two statuses; the more severe one wins (Error > Fail > Warn > Ok)
+    pub fn combine(self, other: VerifyStatus) -> VerifyStatus {
+        match (self, other) {
+            (VerifyStatus::Error, _) | (_, VerifyStatus::Error) => VerifyStatus::Error,
+            (VerifyStatus::Fail, _) | (_, VerifyStatus::Fail) => VerifyStatus::Fail,
+            (VerifyStatus::Warn, _) | (_, VerifyStatus::Warn) => VerifyStatus::Warn,
+            _ => VerifyStatus::Ok,
+        }
+    }
+
+    /// Whether the status lets verification pass (Ok or Warn)
+    pub fn is_pass(self) -> bool {
+        matches!(self, VerifyStatus::Ok | VerifyStatus::Warn)
+    }
+}
+
+/// Result of a single verification check
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CheckResult {
+    /// Machine-readable check ID, e.g. "hash_match_manifest"
+    pub id: String,
+
+    /// Check category
+    pub kind: CheckKind,
+
+    pub status: VerifyStatus,
+
+    pub message: String,
+}
+
+impl VerifyReport {
+    /// Overall status derived from all check results
+    pub fn overall_status(&self) -> VerifyStatus {
+        self.checks
+            .iter()
+            .fold(VerifyStatus::Ok, |acc, check| acc.combine(check.status))
+    }
 }
 
 #[cfg(test)]
@@ -112,4 +221,22 @@ mod tests {
         assert!(json.contains("policy_hash"));
         assert!(!json.contains("sanctions_root")); // skip_serializing_if works
     }
+
+    #[test]
+    fn test_verify_status_combine() {
+        assert_eq!(
+            VerifyStatus::Ok.combine(VerifyStatus::Warn),
+            VerifyStatus::Warn
+        );
+        assert_eq!(
+            VerifyStatus::Warn.combine(VerifyStatus::Fail),
+            VerifyStatus::Fail
+        );
+        assert_eq!(
+            VerifyStatus::Fail.combine(VerifyStatus::Error),
+            VerifyStatus::Error
+        );
+        assert!(VerifyStatus::Warn.is_pass());
+        assert!(!VerifyStatus::Fail.is_pass());
+    }
 }
@@ -155,6 +155,7 @@ fn test_migration_preserves_optional_fields() {
         selfverify_at: Some("2025-01-01T00:05:00Z".to_string()),
         verifier_name: Some("test-verifier".to_string()),
         verifier_version: Some("1.0.0".to_string()),
+        policy_id: Some("0xpolicy".to_string()),
     };
 
     v1_0.entries.push(entry);
@@ -174,6 +175,7 @@ fn test_migration_preserves_optional_fields() {
     assert_eq!(migrated.blob_wasm, Some("blob_003".to_string()));
     assert_eq!(migrated.blob_abi, Some("blob_004".to_string()));
     assert_eq!(migrated.selfverify_status, Some("ok".to_string()));
+    assert_eq!(migrated.policy_id, "0xpolicy");
 }
 
 #[test]
@@ -264,12 +266,11 @@ fn test_backfill_kid_skips_existing() {
 
 #[test]
 fn test_migration_idempotency() {
-    // Create two identical v1.0 registries
+    // Create two identical v1.0 registries (IDs derive from the registration time)
     let mut v1_0_first = Registry::new();
     v1_0_first.add_entry("0xabc".to_string(), "0xdef".to_string(), None);
 
-    let mut v1_0_second = Registry::new();
-    v1_0_second.add_entry("0xabc".to_string(), "0xdef".to_string(), None);
+    let v1_0_second = v1_0_first.clone();
 
     // Migrate both
     let v1_1_first = migrate_to_v1_1(v1_0_first, "test-tool").unwrap();
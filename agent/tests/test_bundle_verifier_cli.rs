@@ -122,6 +122,12 @@ fn create_test_manifest(path: &str, policy_id: &str) -> Result<(), Box<dyn std::
         "signatures": []
     });
 
+    // Sign (the verifier requires a valid signature)
+    let mut manifest: cap_agent::manifest::Manifest = serde_json::from_value(manifest)?;
+    let key = ed25519_dalek::SigningKey::from_bytes(&[7u8; 32]);
+    let signed = cap_agent::sign::sign_manifest(&manifest, &key, "Test")?;
+    manifest.signatures.push(signed.signature);
+
     // Write with NON-PRETTY JSON to match what the verifier expects
     fs::write(path, serde_json::to_string(&manifest)?)?;
     Ok(())
@@ -16,8 +16,21 @@ use anyhow::Result;
 use assert_cmd::Command;
 use predicates::prelude::*;
 use std::fs;
+use std::path::Path;
 use tempfile::TempDir;
 
+/// Signs a manifest in place (verifier run requires a valid signature)
+fn sign_manifest_in_place(path: &Path) -> Result<()> {
+    let mut manifest =
+        cap_agent::manifest::Manifest::load(path).map_err(|e| anyhow::anyhow!("{}", e))?;
+    let key = ed25519_dalek::SigningKey::from_bytes(&[7u8; 32]);
+    let signed = cap_agent::sign::sign_manifest(&manifest, &key, "E2E Test")
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    manifest.signatures.push(signed.signature);
+    manifest.save(path).map_err(|e| anyhow::anyhow!("{}", e))?;
+    Ok(())
+}
+
 #[test]
 fn test_cli_complete_workflow() -> Result<()> {
     // Setup test directory
@@ -104,6 +117,7 @@ notes: "End-to-End Integration Test Policy"
         manifest_content.contains("E2E Test Policy"),
         "Manifest should contain policy name"
     );
+    sign_manifest_in_place(&manifest_file)?;
 
     // Step 5: Run `cap-agent proof build`
     Command::cargo_bin("cap-agent")?
@@ -299,6 +313,1144 @@ fn test_cli_workflow_with_registry() -> Result<()> {
     Ok(())
 }
 
+/// Runs cap-agent in `dir`
+fn cap_agent(dir: &Path, args: &[&str]) -> Result<assert_cmd::assert::Assert> {
+    Ok(Command::cargo_bin("cap-agent")?
+        .current_dir(dir)
+        .args(args)
+        .assert())
+}
+
+/// Builds a signed manifest and proof in `dir/build` and a registry signing key in `dir/keys`
+fn prepare_signed_proof(test_dir: &Path) -> Result<()> {
+    fs::create_dir_all(test_dir.join("build"))?;
+    let suppliers_csv = test_dir.join("suppliers.csv");
+    fs::write(&suppliers_csv, "name,jurisdiction,tier\nSupplier A,DE,1\n")?;
+    let ubos_csv = test_dir.join("ubos.csv");
+    fs::write(
+        &ubos_csv,
+        "name,birthdate,citizenship\nOwner A,1990-01-01,DE\n",
+    )?;
+    let policy_file = test_dir.join("policy.yml");
+    fs::write(
+        &policy_file,
+        "version: lksg.v1\nname: Registry Test\ncreated_at: 2025-11-20T10:00:00Z\nconstraints:\n  require_at_least_one_ubo: true\n  supplier_count_max: 10\n",
+    )?;
+
+    cap_agent(
+        test_dir,
+        &[
+            "prepare",
+            "--suppliers",
+            "suppliers.csv",
+            "--ubos",
+            "ubos.csv",
+        ],
+    )?
+    .success();
+    cap_agent(test_dir, &["manifest", "build", "--policy", "policy.yml"])?.success();
+    cap_agent(
+        test_dir,
+        &[
+            "proof",
+            "build",
+            "--manifest",
+            "build/manifest.json",
+            "--policy",
+            "policy.yml",
+        ],
+    )?
+    .success();
+    sign_manifest_in_place(&test_dir.join("build/manifest.json"))?;
+
+    // Registry signing key (keys/registry.ed25519)
+    cap_agent(
+        test_dir,
+        &[
+            "keys",
+            "keygen",
+            "--owner",
+            "Registry Test",
+            "--out",
+            "keys/registry.json",
+        ],
+    )?
+    .success();
+
+    Ok(())
+}
+
+/// IDs of all registry entries in registration order (`registry list --json`)
+fn registry_ids(dir: &Path, args: &[&str]) -> Result<Vec<String>> {
+    let mut all = vec!["registry", "list", "--json"];
+    all.extend_from_slice(args);
+    let output = Command::cargo_bin("cap-agent")?
+        .current_dir(dir)
+        .args(&all)
+        .output()?;
+    assert!(output.status.success(), "registry list {:?} failed", args);
+    let page: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    Ok(page["entries"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|e| e["id"].as_str().unwrap().to_string())
+        .collect())
+}
+
+/// manifest verify looks up the entry in a SQLite registry and checks its signing key
+#[test]
+fn test_cli_manifest_verify_with_signed_sqlite_registry() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let test_dir = temp_dir.path();
+    prepare_signed_proof(test_dir)?;
+
+    cap_agent(
+        test_dir,
+        &[
+            "registry",
+            "add",
+            "--manifest",
+            "build/manifest.json",
+            "--proof",
+            "build/proof.dat",
+            "--registry",
+            "build/registry.sqlite",
+            "--backend",
+            "sqlite",
+            "--signing-key",
+            "keys/registry.ed25519",
+        ],
+    )?
+    .success();
+
+    let verify_args = [
+        "manifest",
+        "verify",
+        "--manifest",
+        "build/manifest.json",
+        "--proof",
+        "build/proof.dat",
+        "--registry",
+        "build/registry.sqlite",
+        "--registry-backend",
+        "sqlite",
+        "--keys-dir",
+        "keys",
+        "--out",
+        "build/verification.report.json",
+    ];
+    cap_agent(test_dir, &verify_args)?.success();
+
+    // Revoked signing key fails the registry check
+    let key_path = test_dir.join("keys/registry.json");
+    let mut key: serde_json::Value = serde_json::from_str(&fs::read_to_string(&key_path)?)?;
+    key["status"] = serde_json::json!("revoked");
+    fs::write(&key_path, key.to_string())?;
+    cap_agent(test_dir, &verify_args)?.failure();
+
+    // Without a key store only the entry signature is checked
+    cap_agent(test_dir, &verify_args[..10])?.success();
+
+    Ok(())
+}
+
+/// registry supersede warns and registry revoke fails the verification of a proof
+#[test]
+fn test_cli_registry_supersede_and_revoke() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let test_dir = temp_dir.path();
+    prepare_signed_proof(test_dir)?;
+    fs::write(test_dir.join("build/proof_v2.dat"), b"corrected proof")?;
+
+    let registry_args = ["--registry", "build/registry.sqlite", "--backend", "sqlite"];
+    let signing_args = ["--signing-key", "keys/registry.ed25519"];
+    let run = |args: &[&str], extra: &[&[&str]]| {
+        let mut all: Vec<&str> = args.to_vec();
+        for part in extra {
+            all.extend_from_slice(part);
+        }
+        cap_agent(test_dir, &all)
+    };
+
+    // First proof and its corrected successor
+    for proof in ["build/proof.dat", "build/proof_v2.dat"] {
+        run(
+            &[
+                "registry",
+                "add",
+                "--manifest",
+                "build/manifest.json",
+                "--proof",
+                proof,
+            ],
+            &[&registry_args, &signing_args],
+        )?
+        .success();
+    }
+    let ids = registry_ids(test_dir, &registry_args)?;
+    let (first, second) = (ids[0].as_str(), ids[1].as_str());
+
+    let manifest_verify = [
+        "manifest",
+        "verify",
+        "--manifest",
+        "build/manifest.json",
+        "--proof",
+        "build/proof.dat",
+        "--registry",
+        "build/registry.sqlite",
+        "--registry-backend",
+        "sqlite",
+        "--keys-dir",
+        "keys",
+    ];
+    let registry_verify = [
+        "registry",
+        "verify",
+        "--manifest",
+        "build/manifest.json",
+        "--proof",
+        "build/proof.dat",
+    ];
+
+    // Unknown entry and self-supersession are rejected
+    run(
+        &[
+            "registry",
+            "revoke",
+            "--id",
+            "proof_999",
+            "--reason",
+            "withdrawn",
+        ],
+        &[&registry_args, &signing_args],
+    )?
+    .failure();
+    run(
+        &["registry", "supersede", "--id", first, "--successor", first],
+        &[&registry_args, &signing_args],
+    )?
+    .failure();
+
+    // Superseded: verification passes with a warning
+    run(
+        &[
+            "registry",
+            "supersede",
+            "--id",
+            first,
+            "--successor",
+            second,
+        ],
+        &[&registry_args, &signing_args],
+    )?
+    .success();
+    run(&manifest_verify, &[])?
+        .success()
+        .stdout(predicate::str::contains(format!(
+            "superseded by {}",
+            second
+        )));
+    run(&registry_verify, &[&registry_args])?.success();
+    run(&["registry", "list"], &[&registry_args])?
+        .success()
+        .stdout(predicate::str::contains("superseded"));
+    run(
+        &[
+            "registry",
+            "supersede",
+            "--id",
+            first,
+            "--successor",
+            second,
+        ],
+        &[&registry_args, &signing_args],
+    )?
+    .failure();
+
+    // Revocation in the future is not yet effective
+    run(
+        &[
+            "registry",
+            "revoke",
+            "--id",
+            second,
+            "--reason",
+            "withdrawn",
+            "--effective-at",
+            "2999-01-01T00:00:00Z",
+        ],
+        &[&registry_args, &signing_args],
+    )?
+    .success();
+
+    // Revoked: verification fails
+    run(
+        &[
+            "registry",
+            "revoke",
+            "--id",
+            first,
+            "--reason",
+            "key-compromise",
+        ],
+        &[&registry_args, &signing_args],
+    )?
+    .success();
+    run(&manifest_verify, &[])?.failure();
+    run(&registry_verify, &[&registry_args])?.failure();
+    run(
+        &["registry", "revoke", "--id", first, "--reason", "withdrawn"],
+        &[&registry_args, &signing_args],
+    )?
+    .failure();
+
+    Ok(())
+}
+
+/// Registry transparency log: signed tree heads, inclusion and consistency proofs
+#[test]
+fn test_cli_registry_transparency_log() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let test_dir = temp_dir.path();
+    prepare_signed_proof(test_dir)?;
+    fs::write(test_dir.join("build/proof_v2.dat"), b"second proof")?;
+    fs::write(test_dir.join("build/proof_v3.dat"), b"third proof")?;
+
+    let add = |proof: &str| {
+        cap_agent(
+            test_dir,
+            &[
+                "registry",
+                "add",
+                "--manifest",
+                "build/manifest.json",
+                "--proof",
+                proof,
+            ],
+        )
+    };
+    let verify_since = [
+        "registry",
+        "verify",
+        "--manifest",
+        "build/manifest.json",
+        "--proof",
+        "build/proof.dat",
+        "--since",
+        "build/sth_q1.json",
+    ];
+
+    // Tree head after two additions
+    add("build/proof.dat")?.success();
+    add("build/proof_v2.dat")?.success();
+    cap_agent(
+        test_dir,
+        &[
+            "registry",
+            "log-head",
+            "--signing-key",
+            "keys/registry.ed25519",
+            "--out",
+            "build/sth_q1.json",
+        ],
+    )?
+    .success();
+    let head: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(test_dir.join("build/sth_q1.json"))?)?;
+    assert_eq!(head["tree_size"], 2);
+    assert!(head["signature"].is_string());
+
+    // The grown registry extends the earlier tree head
+    add("build/proof_v3.dat")?.success();
+    cap_agent(test_dir, &verify_since)?
+        .success()
+        .stdout(predicate::str::contains("2 → 3"));
+
+    let third = registry_ids(test_dir, &[])?.remove(2);
+    cap_agent(test_dir, &["registry", "log-proof", "--id", &third])?.success();
+    let inclusion: serde_json::Value = serde_json::from_str(&fs::read_to_string(
+        test_dir.join(format!("build/registry.{}.inclusion.json", third)),
+    )?)?;
+    assert_eq!(inclusion["leaf_index"], 2);
+    assert_eq!(inclusion["tree_size"], 3);
+
+    cap_agent(
+        test_dir,
+        &["registry", "log-consistency", "--old", "build/sth_q1.json"],
+    )?
+    .success();
+    let consistency: serde_json::Value = serde_json::from_str(&fs::read_to_string(
+        test_dir.join("build/registry.consistency.json"),
+    )?)?;
+    assert_eq!(consistency["old_size"], 2);
+    assert_eq!(consistency["new_size"], 3);
+
+    // Silently edited entry: registry no longer matches its log
+    let registry_path = test_dir.join("build/registry.json");
+    let original = fs::read_to_string(&registry_path)?;
+    let mut registry: serde_json::Value = serde_json::from_str(&original)?;
+    let forged_hash = format!("0x{}", "ab".repeat(32));
+    registry["entries"][1]["proof_hash"] = serde_json::json!(forged_hash);
+    fs::write(&registry_path, serde_json::to_string_pretty(&registry)?)?;
+    cap_agent(test_dir, &verify_since)?.failure();
+
+    // Log rewritten to match: consistency with the earlier tree head fails
+    registry["transparency_log"][1]["proof_hash"] = serde_json::json!(forged_hash);
+    fs::write(&registry_path, serde_json::to_string_pretty(&registry)?)?;
+    cap_agent(test_dir, &verify_since)?.failure();
+    cap_agent(
+        test_dir,
+        &["registry", "log-consistency", "--old", "build/sth_q1.json"],
+    )?
+    .failure();
+
+    // Tampered tree head is rejected
+    fs::write(&registry_path, original)?;
+    cap_agent(test_dir, &verify_since)?.success();
+    let mut forged_head = head;
+    forged_head["tree_size"] = serde_json::json!(1);
+    fs::write(
+        test_dir.join("build/sth_q1.json"),
+        serde_json::to_string_pretty(&forged_head)?,
+    )?;
+    cap_agent(test_dir, &verify_since)?.failure();
+
+    Ok(())
+}
+
+/// registry list filters, sorts and pages entries and prints the page as JSON
+#[test]
+fn test_cli_registry_list_query() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let test_dir = temp_dir.path();
+    prepare_signed_proof(test_dir)?;
+    fs::write(test_dir.join("build/proof_v2.dat"), b"second proof")?;
+    fs::write(test_dir.join("build/proof_v3.dat"), b"third proof")?;
+
+    for (proof, signed) in [
+        ("build/proof.dat", true),
+        ("build/proof_v2.dat", false),
+        ("build/proof_v3.dat", true),
+    ] {
+        let mut args = vec![
+            "registry",
+            "add",
+            "--backend",
+            "sqlite",
+            "--manifest",
+            "build/manifest.json",
+            "--proof",
+            proof,
+        ];
+        if signed {
+            args.extend(["--signing-key", "keys/registry.ed25519"]);
+        }
+        cap_agent(test_dir, &args)?.success();
+    }
+
+    let list = |extra: &[&str]| -> Result<serde_json::Value> {
+        let mut args = vec!["registry", "list", "--backend", "sqlite", "--json"];
+        args.extend_from_slice(extra);
+        let output = Command::cargo_bin("cap-agent")?
+            .current_dir(test_dir)
+            .args(&args)
+            .output()?;
+        assert!(output.status.success(), "registry list {:?} failed", extra);
+        Ok(serde_json::from_slice(&output.stdout)?)
+    };
+    let ids = |page: &serde_json::Value| -> Vec<String> {
+        page["entries"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|e| e["id"].as_str().unwrap().to_string())
+            .collect()
+    };
+
+    let manifest: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(test_dir.join("build/manifest.json"))?)?;
+    let policy_filter = format!("policy={}", manifest["policy"]["hash"].as_str().unwrap());
+
+    let all = list(&["--filter", &policy_filter])?;
+    assert_eq!(all["total"], 3);
+    let added = ids(&all);
+    let mut by_id = added.clone();
+    by_id.sort();
+
+    let signed = list(&[
+        "--filter",
+        "scheme=ed25519",
+        "--sort",
+        "id",
+        "--order",
+        "desc",
+    ])?;
+    assert_eq!(signed["total"], 2);
+    let mut signed_ids = vec![added[0].clone(), added[2].clone()];
+    signed_ids.sort();
+    signed_ids.reverse();
+    assert_eq!(ids(&signed), signed_ids);
+    let kid = signed["entries"][0]["kid"].as_str().unwrap().to_string();
+    assert_eq!(list(&["--filter", &format!("kid={}", kid)])?["total"], 2);
+
+    let page = list(&["--sort", "id", "--limit", "2", "--offset", "1"])?;
+    assert_eq!(page["total"], 3);
+    assert_eq!(page["limit"], 2);
+    assert_eq!(ids(&page), by_id[1..]);
+
+    assert_eq!(
+        list(&["--filter", "from=2999-01-01T00:00:00Z"])?["total"],
+        0
+    );
+
+    // Invalid filters are rejected
+    cap_agent(test_dir, &["registry", "list", "--filter", "colour=blue"])?.failure();
+    cap_agent(test_dir, &["registry", "list", "--filter", "to=tomorrow"])?.failure();
+
+    Ok(())
+}
+
+/// registry export/import merges a signed snapshot of one desk into another desk's registry
+#[test]
+fn test_cli_registry_export_import() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let test_dir = temp_dir.path();
+    prepare_signed_proof(test_dir)?;
+    fs::write(test_dir.join("build/proof_v2.dat"), b"second proof")?;
+    fs::write(test_dir.join("build/proof_v3.dat"), b"third proof")?;
+
+    let add = |registry: &str, backend: &str, proof: &str| {
+        cap_agent(
+            test_dir,
+            &[
+                "registry",
+                "add",
+                "--registry",
+                registry,
+                "--backend",
+                backend,
+                "--manifest",
+                "build/manifest.json",
+                "--proof",
+                proof,
+                "--signing-key",
+                "keys/registry.ed25519",
+            ],
+        )
+    };
+    let import = |registry: &str, extra: &[&str]| {
+        let mut args = vec![
+            "registry",
+            "import",
+            "--snapshot",
+            "build/desk_a.snapshot.json",
+            "--registry",
+            registry,
+            "--backend",
+            "sqlite",
+            "--keys-dir",
+            "keys",
+        ];
+        args.extend_from_slice(extra);
+        cap_agent(test_dir, &args)
+    };
+    let count = |registry: &str| -> Result<usize> {
+        let conn = rusqlite::Connection::open(test_dir.join(registry))?;
+        Ok(
+            conn.query_row("SELECT COUNT(*) FROM registry_entries", [], |row| {
+                row.get::<_, i64>(0)
+            })? as usize,
+        )
+    };
+
+    // Desk A (JSON) exports a signed snapshot
+    add("build/desk_a.json", "json", "build/proof.dat")?.success();
+    add("build/desk_a.json", "json", "build/proof_v2.dat")?.success();
+    cap_agent(
+        test_dir,
+        &[
+            "registry",
+            "export",
+            "--registry",
+            "build/desk_a.json",
+            "--signing-key",
+            "keys/registry.ed25519",
+            "--source",
+            "desk-a",
+            "--out",
+            "build/desk_a.snapshot.json",
+        ],
+    )?
+    .success();
+    let snapshot: serde_json::Value = serde_json::from_str(&fs::read_to_string(
+        test_dir.join("build/desk_a.snapshot.json"),
+    )?)?;
+    assert_eq!(snapshot["entry_count"], 2);
+    assert_eq!(snapshot["source"], "desk-a");
+
+    // Desk B (SQLite) merges it; a second import only finds duplicates
+    import("build/desk_b.sqlite", &[])?
+        .success()
+        .stdout(predicate::str::contains("Neue Einträge:"));
+    assert_eq!(count("build/desk_b.sqlite")?, 2);
+    import("build/desk_b.sqlite", &[])?.success();
+    assert_eq!(count("build/desk_b.sqlite")?, 2);
+
+    // Desk C was built independently: its own proof merges without ID clashes,
+    // but it registered the first proof itself (same hashes, other registration)
+    add("build/desk_c.sqlite", "sqlite", "build/proof_v3.dat")?.success();
+    add("build/desk_c.sqlite", "sqlite", "build/proof.dat")?.success();
+    import("build/desk_c.sqlite", &[])?
+        .failure()
+        .stdout(predicate::str::contains("Konflikt: Manifest/Proof"));
+    assert_eq!(count("build/desk_c.sqlite")?, 2);
+    import("build/desk_c.sqlite", &["--skip-conflicts"])?.success();
+    assert_eq!(count("build/desk_c.sqlite")?, 3);
+
+    let audit = fs::read_to_string(test_dir.join("build/agent.audit.jsonl"))?;
+    assert_eq!(audit.matches("registry_snapshot_imported").count(), 3);
+
+    // Tampered snapshot is rejected
+    let mut forged = snapshot;
+    forged["registry"]["entries"][0]["proof_hash"] = serde_json::json!("0xforged");
+    fs::write(
+        test_dir.join("build/desk_a.snapshot.json"),
+        serde_json::to_string_pretty(&forged)?,
+    )?;
+    import("build/desk_d.sqlite", &[])?.failure();
+
+    Ok(())
+}
+
+/// registry schema-migrate upgrades a pre-versioning SQLite registry after a dry run and backup
+#[test]
+fn test_cli_registry_schema_migrate() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let test_dir = temp_dir.path();
+    fs::create_dir_all(test_dir.join("build"))?;
+    let db_path = test_dir.join("build/registry.sqlite");
+
+    // v0.9 registry: no kid/signature_scheme, no blob store, no schema_version
+    {
+        let conn = rusqlite::Connection::open(&db_path)?;
+        conn.execute_batch(
+            "CREATE TABLE registry_entries (
+                id TEXT PRIMARY KEY, manifest_hash TEXT NOT NULL, proof_hash TEXT NOT NULL,
+                timestamp_file TEXT, registered_at TEXT NOT NULL, signature TEXT, public_key TEXT,
+                blob_manifest TEXT, blob_proof TEXT, blob_wasm TEXT, blob_abi TEXT,
+                selfverify_status TEXT, selfverify_at TEXT, verifier_name TEXT, verifier_version TEXT
+            );
+            INSERT INTO registry_entries(id, manifest_hash, proof_hash, registered_at)
+                VALUES('proof_001', '0xd490be94abc1230000', '0x83a8779ddef4560000', '2025-11-20T16:30:00Z');",
+        )?;
+    }
+    let schema_version = || -> Result<Option<i64>> {
+        let conn = rusqlite::Connection::open(&db_path)?;
+        let exists = conn
+            .prepare("SELECT 1 FROM sqlite_master WHERE name = 'schema_version'")?
+            .exists([])?;
+        if !exists {
+            return Ok(None);
+        }
+        Ok(Some(conn.query_row(
+            "SELECT MAX(version) FROM schema_version",
+            [],
+            |row| row.get(0),
+        )?))
+    };
+    let backups = || -> Result<usize> {
+        Ok(fs::read_dir(test_dir.join("build"))?
+            .filter(|e| {
+                e.as_ref()
+                    .map(|e| e.path().extension() == Some("bak".as_ref()))
+                    .unwrap_or(false)
+            })
+            .count())
+    };
+
+    cap_agent(test_dir, &["registry", "schema-migrate", "--dry-run"])?
+        .success()
+        .stdout(predicate::str::contains("v3: Key-Management-Felder"))
+        .stdout(predicate::str::contains("Dry Run"));
+    assert_eq!(schema_version()?, None);
+    assert_eq!(backups()?, 0);
+
+    cap_agent(test_dir, &["registry", "schema-migrate"])?
+        .success()
+        .stdout(predicate::str::contains("Backup"));
+    let migrated = schema_version()?.expect("schema_version table");
+    assert!(migrated >= 7);
+    assert_eq!(backups()?, 1);
+
+    // Up to date: nothing to do, both stores open the file
+    cap_agent(test_dir, &["registry", "schema-migrate"])?
+        .success()
+        .stdout(predicate::str::contains("aktuell"));
+    cap_agent(test_dir, &["registry", "list", "--backend", "sqlite"])?
+        .success()
+        .stdout(predicate::str::contains("1 Einträge"));
+    cap_agent(test_dir, &["blob", "list"])?.success();
+    assert_eq!(schema_version()?, Some(migrated));
+    assert_eq!(backups()?, 1);
+
+    Ok(())
+}
+
+#[test]
+fn test_cli_audit_checkpoint_detects_truncation() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let test_dir = temp_dir.path();
+    fs::create_dir_all(test_dir.join("build"))?;
+    fs::create_dir_all(test_dir.join("keys"))?;
+
+    cap_agent(
+        test_dir,
+        &[
+            "keys",
+            "keygen",
+            "--owner",
+            "Audit",
+            "--out",
+            "keys/audit.json",
+        ],
+    )?
+    .success();
+    cap_agent(
+        test_dir,
+        &[
+            "keys",
+            "keygen",
+            "--owner",
+            "Other",
+            "--out",
+            "other/other.json",
+        ],
+    )?
+    .success();
+
+    for event in ["policy_compile", "verify_response", "registry_add"] {
+        cap_agent(test_dir, &["audit", "append", "--event", event])?.success();
+    }
+    cap_agent(test_dir, &["audit", "checkpoint", "--key", "audit"])?
+        .success()
+        .stdout(predicate::str::contains("Checkpoint signiert"));
+    assert!(test_dir
+        .join("build/audit_chain.checkpoints.jsonl")
+        .exists());
+
+    cap_agent(
+        test_dir,
+        &["audit", "append", "--event", "after_checkpoint"],
+    )?
+    .success();
+    cap_agent(
+        test_dir,
+        &[
+            "audit",
+            "verify",
+            "--keys-dir",
+            "keys",
+            "--out",
+            "build/verify.json",
+        ],
+    )?
+    .success()
+    .stdout(predicate::str::contains("Checkpoints OK"));
+    let report: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(test_dir.join("build/verify.json"))?)?;
+    assert_eq!(report["checkpoints"]["covered_events"], 3);
+    assert_eq!(report["checkpoints"]["total_events"], 4);
+
+    // Signer not in the trusted key directory
+    cap_agent(test_dir, &["audit", "verify", "--keys-dir", "other"])?
+        .failure()
+        .stdout(predicate::str::contains("not trusted"));
+
+    // Cutting the tail keeps the hash chain valid, but not the checkpoint
+    let chain_path = test_dir.join("build/audit_chain.jsonl");
+    let content = fs::read_to_string(&chain_path)?;
+    let kept: Vec<&str> = content.lines().take(2).collect();
+    fs::write(&chain_path, format!("{}\n", kept.join("\n")))?;
+    cap_agent(test_dir, &["audit", "verify"])?
+        .failure()
+        .stdout(predicate::str::contains("Chain-Integrität OK"))
+        .stdout(predicate::str::contains("log truncated"));
+
+    Ok(())
+}
+
+#[test]
+fn test_cli_audit_prove_single_event() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let test_dir = temp_dir.path();
+    fs::create_dir_all(test_dir.join("build"))?;
+    fs::create_dir_all(test_dir.join("keys"))?;
+
+    cap_agent(
+        test_dir,
+        &[
+            "keys",
+            "keygen",
+            "--owner",
+            "Audit",
+            "--out",
+            "keys/audit.json",
+        ],
+    )?
+    .success();
+    for event in [
+        "policy_compile",
+        "registry_add",
+        "key_rotated",
+        "export",
+        "registry_add",
+    ] {
+        cap_agent(test_dir, &["audit", "append", "--event", event])?.success();
+    }
+
+    // Without a checkpoint there is no signed root to prove against
+    cap_agent(test_dir, &["audit", "prove", "--event", "3"])?
+        .failure()
+        .stderr(predicate::str::contains("audit checkpoint"));
+
+    cap_agent(test_dir, &["audit", "checkpoint", "--key", "audit"])?.success();
+    cap_agent(
+        test_dir,
+        &[
+            "audit",
+            "prove",
+            "--event",
+            "3",
+            "--out",
+            "key_rotated.proof.json",
+        ],
+    )?
+    .success()
+    .stdout(predicate::str::contains("key_rotated"));
+
+    // The auditor only gets the proof, not the log
+    fs::remove_dir_all(test_dir.join("build"))?;
+    cap_agent(
+        test_dir,
+        &[
+            "audit",
+            "verify-proof",
+            "--proof",
+            "key_rotated.proof.json",
+            "--keys-dir",
+            "keys",
+        ],
+    )?
+    .success()
+    .stdout(predicate::str::contains("Inclusion-Proof gültig"));
+
+    let proof_path = test_dir.join("key_rotated.proof.json");
+    let proof = fs::read_to_string(&proof_path)?;
+    fs::write(&proof_path, proof.replace("key_rotated", "export"))?;
+    cap_agent(
+        test_dir,
+        &["audit", "verify-proof", "--proof", "key_rotated.proof.json"],
+    )?
+    .failure()
+    .stdout(predicate::str::contains("UNGÜLTIG"));
+
+    Ok(())
+}
+
+#[test]
+fn test_cli_audit_rotate_segments() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let test_dir = temp_dir.path();
+    fs::create_dir_all(test_dir.join("build"))?;
+    fs::create_dir_all(test_dir.join("keys"))?;
+
+    cap_agent(
+        test_dir,
+        &[
+            "keys",
+            "keygen",
+            "--owner",
+            "Audit",
+            "--out",
+            "keys/audit.json",
+        ],
+    )?
+    .success();
+    for event in ["policy_compile", "registry_add", "key_rotated"] {
+        cap_agent(test_dir, &["audit", "append", "--event", event])?.success();
+    }
+
+    // Size limit not reached yet
+    cap_agent(test_dir, &["audit", "rotate", "--max-bytes", "1000000"])?
+        .success()
+        .stdout(predicate::str::contains("Keine Rotation fällig"));
+
+    cap_agent(test_dir, &["audit", "rotate", "--sign", "--key", "audit"])?
+        .success()
+        .stdout(predicate::str::contains("Segment versiegelt"));
+    let segments = test_dir.join("build/audit_chain.segments");
+    assert!(segments.join("000001.seal.json").exists());
+    assert!(segments.join("000001.jsonl.gz").exists());
+
+    for event in ["registry_add", "export"] {
+        cap_agent(test_dir, &["audit", "append", "--event", event])?.success();
+    }
+    cap_agent(
+        test_dir,
+        &[
+            "audit",
+            "verify",
+            "--keys-dir",
+            "keys",
+            "--out",
+            "verify.json",
+        ],
+    )?
+    .success()
+    .stdout(predicate::str::contains("Segmente OK"));
+    let report: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(test_dir.join("verify.json"))?)?;
+    assert_eq!(report["segments"]["sealed_events"], 3);
+
+    // Active segment swapped for a valid v1.0 log that does not continue the seals
+    let active = test_dir.join("build/audit_chain.jsonl");
+    let original = fs::read_to_string(&active)?;
+    let v1_log = test_dir.join("v1.audit.jsonl");
+    cap_agent::audit::AuditLog::new(&v1_log)
+        .and_then(|mut log| log.log_event("registry_add", serde_json::json!({})))
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    fs::copy(&v1_log, &active)?;
+    cap_agent(test_dir, &["audit", "verify"])?
+        .failure()
+        .stdout(predicate::str::contains("Segment-Prüfung FEHLGESCHLAGEN"));
+    fs::write(&active, original)?;
+
+    // Archive removed under retention: the seal alone still links the chain
+    fs::remove_file(segments.join("000001.jsonl.gz"))?;
+    cap_agent(test_dir, &["audit", "verify"])?
+        .success()
+        .stdout(predicate::str::contains("Nur Siegel"));
+
+    // Rewritten seal breaks the signature
+    let seal_path = segments.join("000001.seal.json");
+    let seal = fs::read_to_string(&seal_path)?;
+    fs::write(
+        &seal_path,
+        seal.replace("\"event_count\": 3", "\"event_count\": 2"),
+    )?;
+    cap_agent(test_dir, &["audit", "verify"])?
+        .failure()
+        .stdout(predicate::str::contains("Segment-Prüfung FEHLGESCHLAGEN"));
+
+    Ok(())
+}
+
+/// v1.0 audit log migrated in place; CLI keeps writing in the structured format
+#[test]
+fn test_cli_audit_migrate_v1_log() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let test_dir = temp_dir.path();
+    prepare_signed_proof(test_dir)?;
+
+    cap_agent(test_dir, &["audit", "tip", "--out", "build/v1.head"])?.success();
+    let v1_tip = format!("0x{}", fs::read_to_string(test_dir.join("build/v1.head"))?);
+
+    cap_agent(
+        test_dir,
+        &["audit", "migrate", "--file", "build/agent.audit.jsonl"],
+    )?
+    .success()
+    .stdout(predicate::str::contains("Audit-Log migriert"));
+    assert!(test_dir.join("build/agent.audit.v1.jsonl").exists());
+
+    cap_agent(
+        test_dir,
+        &[
+            "registry",
+            "add",
+            "--manifest",
+            "build/manifest.json",
+            "--proof",
+            "build/proof.dat",
+        ],
+    )?
+    .success();
+
+    cap_agent(
+        test_dir,
+        &[
+            "audit",
+            "verify",
+            "--file",
+            "build/agent.audit.jsonl",
+            "--out",
+            "build/verify.json",
+        ],
+    )?
+    .success()
+    .stdout(predicate::str::contains("Migrations-Bridge OK"));
+    let report: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(test_dir.join("build/verify.json"))?)?;
+    assert_eq!(report["format"], "v2");
+    assert_eq!(report["migration"]["source_tail_digest"], v1_tip);
+
+    // Migrated events are covered by the structured hash chain
+    let log_path = test_dir.join("build/agent.audit.jsonl");
+    let log = fs::read_to_string(&log_path)?;
+    let first = log.lines().next().unwrap_or_default().to_string();
+    let mut event: serde_json::Value = serde_json::from_str(&first)?;
+    event["ts"] = serde_json::json!("2020-01-01T00:00:00+00:00");
+    fs::write(&log_path, log.replacen(&first, &event.to_string(), 1))?;
+    cap_agent(
+        test_dir,
+        &["audit", "verify", "--file", "build/agent.audit.jsonl"],
+    )?
+    .failure()
+    .stdout(predicate::str::contains("Chain-Integrität VERLETZT"));
+
+    Ok(())
+}
+
+#[test]
+fn test_cli_audit_query_and_report() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let test_dir = temp_dir.path();
+    prepare_signed_proof(test_dir)?;
+
+    cap_agent(
+        test_dir,
+        &[
+            "keys",
+            "keygen",
+            "--owner",
+            "Audit",
+            "--out",
+            "keys/audit.json",
+        ],
+    )?
+    .success();
+
+    let runs = [
+        ("policy_loaded", "run-1", "OK"),
+        ("verify_response", "run-1", "OK"),
+        ("verify_response", "run-2", "FAIL"),
+    ];
+    for (event, run_id, result) in runs {
+        cap_agent(
+            test_dir,
+            &[
+                "audit",
+                "append",
+                "--event",
+                event,
+                "--policy-id",
+                "lksg.v1",
+                "--run-id",
+                run_id,
+                "--result",
+                result,
+            ],
+        )?
+        .success();
+    }
+    cap_agent(test_dir, &["audit", "checkpoint", "--key", "audit"])?.success();
+
+    // Filtered CSV export
+    cap_agent(
+        test_dir,
+        &[
+            "audit",
+            "export",
+            "--event",
+            "verify_response",
+            "--result",
+            "fail",
+            "--format",
+            "csv",
+            "--out",
+            "build/failed.csv",
+        ],
+    )?
+    .success();
+    let csv = fs::read_to_string(test_dir.join("build/failed.csv"))?;
+    assert_eq!(csv.lines().count(), 2);
+    assert!(csv.contains("verify_response,lksg.v1,"));
+    assert!(csv.contains(",FAIL,run-2,"));
+
+    cap_agent(test_dir, &["audit", "export", "--result", "maybe"])?.failure();
+
+    // Manifest anchored to the second event
+    let log = fs::read_to_string(test_dir.join("build/audit_chain.jsonl"))?;
+    let second: serde_json::Value = serde_json::from_str(log.lines().nth(1).unwrap_or_default())?;
+    let manifest_path = test_dir.join("build/manifest.json");
+    let mut manifest: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&manifest_path)?)?;
+    manifest["time_anchor"] = serde_json::json!({
+        "kind": "tsa",
+        "reference": "build/timestamp.tsr",
+        "audit_tip_hex": second["self_hash"],
+        "created_at": "2026-01-01T00:00:00Z"
+    });
+    fs::write(&manifest_path, serde_json::to_string_pretty(&manifest)?)?;
+
+    cap_agent(
+        test_dir,
+        &[
+            "audit",
+            "report",
+            "--period",
+            "yearly",
+            "--manifest",
+            "build/manifest.json",
+            "--keys-dir",
+            "keys",
+            "--title",
+            "LkSG Audit",
+        ],
+    )?
+    .success()
+    .stdout(predicate::str::contains("Audit-Bericht erstellt"));
+    let markdown = fs::read_to_string(test_dir.join("build/audit_report.md"))?;
+    assert!(markdown.starts_with("# LkSG Audit"));
+    assert!(markdown.contains("- **Hash-Chain:** OK"));
+    assert!(markdown.contains("## Checkpoints"));
+    assert!(markdown.contains("| build/manifest.json | tsa |"));
+    assert!(markdown.contains("| Event 2 |"));
+    assert!(markdown.contains("verify_response 2"));
+
+    cap_agent(
+        test_dir,
+        &[
+            "audit",
+            "report",
+            "--format",
+            "html",
+            "--out",
+            "build/report.html",
+        ],
+    )?
+    .success();
+    let html = fs::read_to_string(test_dir.join("build/report.html"))?;
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(html.contains("<h2>Ereignisse je Zeitraum</h2>"));
+
+    // A truncated log still yields a report, but the command fails
+    let truncated: Vec<&str> = log.lines().take(2).collect();
+    fs::write(
+        test_dir.join("build/audit_chain.jsonl"),
+        truncated.join("\n") + "\n",
+    )?;
+    cap_agent(test_dir, &["audit", "report"])?
+        .failure()
+        .stdout(predicate::str::contains("Integrität VERLETZT"));
+    let markdown = fs::read_to_string(test_dir.join("build/audit_report.md"))?;
+    assert!(markdown.contains("- **Gesamtergebnis:** VERLETZT"));
+
+    Ok(())
+}
+
 #[test]
 fn test_cli_workflow_invalid_policy_fails() -> Result<()> {
     let temp_dir = TempDir::new()?;
@@ -630,6 +1782,7 @@ fn test_legacy_bundle_backward_compatibility() -> Result<()> {
         .arg(&policy_file)
         .assert()
         .success();
+    sign_manifest_in_place(&build_dir.join("manifest.json"))?;
 
     Command::cargo_bin("cap-agent")?
         .current_dir(&test_dir)
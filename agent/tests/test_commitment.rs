@@ -3,8 +3,13 @@
 //! Diese Tests wurden aus inline test modules extrahiert um Tarpaulin Coverage-Tracking zu ermöglichen.
 //! Tarpaulin hat eine bekannte Limitation mit #[cfg(test)] inline modules.
 
-use cap_agent::commitment::{compute_company_root, compute_supplier_root, compute_ubo_root};
+use cap_agent::commitment::{
+    compute_company_root, compute_supplier_root, compute_supplier_root_with, compute_ubo_root,
+    compute_ubo_root_with, record_blinded_leaf_hash, record_leaf_hash, CommitmentScheme,
+    Commitments, RecordNonces,
+};
 use cap_agent::io::{Supplier, Ubo};
+use cap_agent::merkle;
 
 #[test]
 fn test_hash_record_creates_valid_hash() {
@@ -15,7 +20,8 @@ fn test_hash_record_creates_valid_hash() {
     };
 
     // Hash sollte mit 0x beginnen und ausreichend lang sein
-    let hash = compute_supplier_root(&[supplier]).unwrap();
+    let nonces = RecordNonces::generate(1, 0);
+    let hash = compute_supplier_root(&[supplier], &nonces.suppliers).unwrap();
     assert!(hash.starts_with("0x"), "Hash should start with 0x");
     assert!(hash.len() > 10, "Hash should be longer than 10 characters");
 }
@@ -35,9 +41,10 @@ fn test_merkle_root_is_deterministic() {
 
     let suppliers = vec![supplier1.clone(), supplier2.clone()];
 
-    // Gleiche Input → Gleicher Output
-    let root1 = compute_supplier_root(&suppliers).unwrap();
-    let root2 = compute_supplier_root(&suppliers).unwrap();
+    // Gleiche Input und Nonces → Gleicher Output
+    let nonces = RecordNonces::generate(suppliers.len(), 0);
+    let root1 = compute_supplier_root(&suppliers, &nonces.suppliers).unwrap();
+    let root2 = compute_supplier_root(&suppliers, &nonces.suppliers).unwrap();
 
     assert_eq!(root1, root2, "Merkle root should be deterministic");
 }
@@ -71,7 +78,8 @@ fn test_ubo_root_computation() {
         citizenship: "DE".to_string(),
     };
 
-    let root = compute_ubo_root(&[ubo]).unwrap();
+    let nonces = RecordNonces::generate(0, 1);
+    let root = compute_ubo_root(&[ubo], &nonces.ubos).unwrap();
 
     assert!(root.starts_with("0x"), "UBO root should start with 0x");
     assert!(
@@ -83,7 +91,7 @@ fn test_ubo_root_computation() {
 #[test]
 fn test_empty_supplier_list() {
     let suppliers: Vec<Supplier> = vec![];
-    let root = compute_supplier_root(&suppliers).unwrap();
+    let root = compute_supplier_root(&suppliers, &[]).unwrap();
 
     // Empty list sollte trotzdem einen validen Root erzeugen
     assert!(
@@ -95,7 +103,7 @@ fn test_empty_supplier_list() {
 #[test]
 fn test_empty_ubo_list() {
     let ubos: Vec<Ubo> = vec![];
-    let root = compute_ubo_root(&ubos).unwrap();
+    let root = compute_ubo_root(&ubos, &[]).unwrap();
 
     // Empty list sollte trotzdem einen validen Root erzeugen
     assert!(
@@ -118,11 +126,86 @@ fn test_different_suppliers_produce_different_roots() {
         tier: 2,
     };
 
-    let root1 = compute_supplier_root(&[supplier1]).unwrap();
-    let root2 = compute_supplier_root(&[supplier2]).unwrap();
+    let nonces = RecordNonces::generate(1, 0);
+    let root1 = compute_supplier_root(&[supplier1], &nonces.suppliers).unwrap();
+    let root2 = compute_supplier_root(&[supplier2], &nonces.suppliers).unwrap();
 
     assert_ne!(
         root1, root2,
         "Different suppliers should produce different roots"
     );
 }
+
+#[test]
+fn test_supplier_root_is_binary_merkle_root() {
+    let suppliers = vec![
+        Supplier {
+            name: "Supplier A".to_string(),
+            jurisdiction: "DE".to_string(),
+            tier: 1,
+        },
+        Supplier {
+            name: "Supplier B".to_string(),
+            jurisdiction: "FR".to_string(),
+            tier: 2,
+        },
+    ];
+
+    // Root muss sich aus den gesalzenen Blatt-Hashes der Records nachrechnen lassen
+    let nonces = RecordNonces::generate(suppliers.len(), 0);
+    let left = record_blinded_leaf_hash(&suppliers[0], &nonces.suppliers[0]).unwrap();
+    let right = record_blinded_leaf_hash(&suppliers[1], &nonces.suppliers[1]).unwrap();
+    let expected = merkle::to_hex(&merkle::node_hash(&left, &right));
+
+    assert_eq!(
+        compute_supplier_root(&suppliers, &nonces.suppliers).unwrap(),
+        expected
+    );
+
+    // Merkle v1 (ungesalzen) bleibt für bestehende Manifeste nachrechenbar
+    let left = record_leaf_hash(&suppliers[0]).unwrap();
+    let right = record_leaf_hash(&suppliers[1]).unwrap();
+    assert_eq!(
+        compute_supplier_root_with(&suppliers, CommitmentScheme::MerkleV1, &[]).unwrap(),
+        merkle::to_hex(&merkle::node_hash(&left, &right))
+    );
+}
+
+#[test]
+fn test_legacy_commitments_remain_verifiable() {
+    let suppliers = vec![Supplier {
+        name: "Supplier A".to_string(),
+        jurisdiction: "DE".to_string(),
+        tier: 1,
+    }];
+    let ubos = vec![Ubo {
+        name: "Test UBO".to_string(),
+        birthdate: "1980-01-01".to_string(),
+        citizenship: "DE".to_string(),
+    }];
+
+    // Legacy-Commitments ohne commitment_scheme-Feld
+    let supplier_root =
+        compute_supplier_root_with(&suppliers, CommitmentScheme::LegacyConcat, &[]).unwrap();
+    let ubo_root = compute_ubo_root_with(&ubos, CommitmentScheme::LegacyConcat, &[]).unwrap();
+    let json = serde_json::json!({
+        "supplier_root": supplier_root,
+        "ubo_root": ubo_root,
+        "company_commitment_root": compute_company_root(&supplier_root, &ubo_root),
+    });
+    let legacy: Commitments = serde_json::from_value(json).unwrap();
+
+    assert_eq!(legacy.scheme(), CommitmentScheme::LegacyConcat);
+    assert!(legacy
+        .verify_against(&suppliers, &ubos, &RecordNonces::default())
+        .unwrap());
+
+    // Gleiche Roots unter Merkle v1 interpretiert → passt nicht
+    let mislabeled = Commitments {
+        commitment_scheme: Some(CommitmentScheme::MerkleV1),
+        ..legacy
+    };
+    assert!(!mislabeled
+        .verify_against(&suppliers, &ubos, &RecordNonces::default())
+        .unwrap());
+}
@@ -1,6 +1,7 @@
 /// Integration tests for Dual-Anchor Schema (v0.9.0)
 ///
-/// Tests CLI commands for private and public anchor operations.
+/// Tests CLI commands for private and public anchor operations, including
+/// offline verification of public anchor proofs.
 use std::fs;
 use std::process::Command;
 
@@ -251,3 +252,307 @@ fn cli_verify_anchor_ok() {
     // Cleanup
     fs::remove_file(manifest_path).ok();
 }
+
+/// Helper: Runs cap-agent with the given arguments
+fn run_cap_agent(args: &[&str]) -> std::process::Output {
+    Command::new("cargo")
+        .args(["run", "--bin", "cap-agent", "--"])
+        .args(args)
+        .output()
+        .expect("Failed to execute command")
+}
+
+/// Helper: Writes a BTC SPV proof for `digest` and a header store pinning its block.
+/// Returns the txid of the anchor transaction.
+fn write_btc_spv_fixture(digest: &str, proof_path: &str, headers_path: &str) -> String {
+    use cap_agent::manifest::anchor_proof::{
+        btc_merkle_branch, btc_merkle_root, display_hash, BlockHeader, HeaderStore,
+    };
+    use cap_agent::manifest::PublicChain;
+    use sha2::{Digest, Sha256};
+
+    let sha256d = |data: &[u8]| -> [u8; 32] { Sha256::digest(Sha256::digest(data)).into() };
+
+    // Transaction with an OP_RETURN output carrying the digest
+    let mut tx = vec![1, 0, 0, 0, 1];
+    tx.extend([0xab; 36]);
+    tx.extend([0, 0xff, 0xff, 0xff, 0xff, 1]);
+    tx.extend([0; 8]);
+    tx.extend([34, 0x6a, 32]);
+    tx.extend(hex::decode(digest.trim_start_matches("0x")).unwrap());
+    tx.extend([0; 4]);
+    let txids = [[1u8; 32], [2u8; 32], sha256d(&tx)];
+
+    // Block with regtest difficulty
+    let mut header = BlockHeader {
+        version: 0x2000_0000,
+        prev_block: [0u8; 32],
+        merkle_root: btc_merkle_root(&txids),
+        time: 1_767_225_600,
+        bits: 0x207f_ffff,
+        nonce: 0,
+    };
+    while header.check_pow().is_err() {
+        header.nonce += 1;
+    }
+
+    let proof = serde_json::json!({
+        "version": "anchor.proof.v1",
+        "kind": "btc_spv",
+        "chain": "btc",
+        "digest": digest,
+        "tx": hex::encode(&tx),
+        "tx_index": 2,
+        "merkle_branch": btc_merkle_branch(&txids, 2)
+            .iter()
+            .map(display_hash)
+            .collect::<Vec<_>>(),
+        "block_header": hex::encode(header.to_bytes()),
+    });
+    let headers = HeaderStore::new(PublicChain::Btc, [(840_000, header)]).unwrap();
+    fs::write(proof_path, serde_json::to_string_pretty(&proof).unwrap()).unwrap();
+    fs::write(
+        headers_path,
+        serde_json::to_string_pretty(&headers.to_json()).unwrap(),
+    )
+    .unwrap();
+
+    display_hash(&txids[2])
+}
+
+/// Test: CLI verify-anchor checks a BTC SPV proof against pinned headers
+#[test]
+fn cli_verify_anchor_btc_spv_proof() {
+    fs::create_dir_all("tests/out").ok();
+    let manifest_path = "tests/out/test_verify_anchor_spv.json";
+    let proof_path = "tests/out/test_verify_anchor_spv.proof.json";
+    let headers_path = "tests/out/test_verify_anchor_spv.headers.json";
+    let report_path = "tests/out/test_verify_anchor_spv.report.json";
+    let audit_tip = "0x83a8779d12345678901234567890123456789012345678901234567890123456";
+    let digest = "0x5555555555555555555555555555555555555555555555555555555555555555";
+
+    create_test_manifest_with_anchor(manifest_path, audit_tip);
+    let txid = write_btc_spv_fixture(digest, proof_path, headers_path);
+
+    let output = run_cap_agent(&[
+        "audit",
+        "set-public-anchor",
+        "--manifest",
+        manifest_path,
+        "--chain",
+        "btc",
+        "--txid",
+        &txid,
+        "--digest",
+        digest,
+    ]);
+    assert!(output.status.success());
+
+    // Proof against pinned headers
+    let output = run_cap_agent(&[
+        "audit",
+        "verify-anchor",
+        "--manifest",
+        manifest_path,
+        "--proof",
+        proof_path,
+        "--headers",
+        headers_path,
+        "--out",
+        report_path,
+    ]);
+    assert!(
+        output.status.success(),
+        "Verification failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let report: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(report_path).unwrap()).unwrap();
+    assert_eq!(report["status"], "ok");
+    assert_eq!(report["digest_match"], true);
+    assert_eq!(report["public_proof"]["txid"], txid.as_str());
+    assert_eq!(report["public_proof"]["height"], 840_000);
+    assert_eq!(report["public_proof"]["header_pinned"], true);
+
+    // Proof does not match a different anchored digest
+    let other_digest = "0x6666666666666666666666666666666666666666666666666666666666666666";
+    run_cap_agent(&[
+        "audit",
+        "set-public-anchor",
+        "--manifest",
+        manifest_path,
+        "--chain",
+        "btc",
+        "--txid",
+        &txid,
+        "--digest",
+        other_digest,
+    ]);
+    let output = run_cap_agent(&[
+        "audit",
+        "verify-anchor",
+        "--manifest",
+        manifest_path,
+        "--proof",
+        proof_path,
+        "--headers",
+        headers_path,
+        "--out",
+        report_path,
+    ]);
+    assert!(!output.status.success());
+    let report: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(report_path).unwrap()).unwrap();
+    assert_eq!(report["status"], "fail");
+    assert_eq!(report["digest_match"], false);
+    assert!(report["errors"][0]
+        .as_str()
+        .unwrap()
+        .contains("does not match anchor digest"));
+
+    // Cleanup
+    for path in [manifest_path, proof_path, headers_path, report_path] {
+        fs::remove_file(path).ok();
+    }
+}
+
+/// Test: CLI anchor-batch anchors several manifests under one root; each
+/// manifest verifies via its inclusion path plus the proof for the root
+#[test]
+fn cli_anchor_batch_and_verify() {
+    fs::create_dir_all("tests/out").ok();
+    let manifest_paths: Vec<String> = (0..3)
+        .map(|i| format!("tests/out/test_anchor_batch_{}.json", i))
+        .collect();
+    let batch_path = "tests/out/test_anchor_batch.batch.json";
+    let proof_path = "tests/out/test_anchor_batch.proof.json";
+    let headers_path = "tests/out/test_anchor_batch.headers.json";
+    let report_path = "tests/out/test_anchor_batch.report.json";
+
+    for (i, path) in manifest_paths.iter().enumerate() {
+        create_test_manifest_with_anchor(path, &format!("0x{}", hex::encode([i as u8 + 1; 32])));
+    }
+    let manifest_args: Vec<&str> = manifest_paths
+        .iter()
+        .flat_map(|path| ["--manifest", path.as_str()])
+        .collect();
+
+    // 1. Compute the batch root only
+    let mut args = vec!["audit", "anchor-batch", "--out", batch_path];
+    args.extend(&manifest_args);
+    let output = run_cap_agent(&args);
+    assert!(
+        output.status.success(),
+        "anchor-batch failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let batch: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(batch_path).unwrap()).unwrap();
+    let root = batch["root"].as_str().unwrap().to_string();
+    assert_eq!(batch["tree_size"], 3);
+    assert!(batch["public"].is_null());
+    assert!(!fs::read_to_string(&manifest_paths[0])
+        .unwrap()
+        .contains("\"batch\""));
+
+    // 2. Anchor the root on chain, then write paths and public anchor
+    let txid = write_btc_spv_fixture(&root, proof_path, headers_path);
+    let mut args = vec!["audit", "anchor-batch", "--chain", "btc", "--txid", &txid];
+    args.extend(&manifest_args);
+    let output = run_cap_agent(&args);
+    assert!(output.status.success());
+
+    // 3. Every manifest verifies via its path and the root proof
+    for (i, path) in manifest_paths.iter().enumerate() {
+        let manifest: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap();
+        assert_eq!(manifest["time_anchor"]["batch"]["leaf_index"], i);
+        assert_eq!(manifest["time_anchor"]["public"]["digest"], root.as_str());
+
+        let output = run_cap_agent(&[
+            "audit",
+            "verify-anchor",
+            "--manifest",
+            path,
+            "--proof",
+            proof_path,
+            "--headers",
+            headers_path,
+            "--out",
+            report_path,
+        ]);
+        assert!(
+            output.status.success(),
+            "verify-anchor failed for manifest {}: {}",
+            i,
+            String::from_utf8_lossy(&output.stderr)
+        );
+        let report: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(report_path).unwrap()).unwrap();
+        assert_eq!(report["digest_match"], true);
+    }
+
+    // 4. A path taken from another manifest is rejected
+    let mut first: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&manifest_paths[0]).unwrap()).unwrap();
+    let second: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&manifest_paths[1]).unwrap()).unwrap();
+    first["time_anchor"]["batch"] = second["time_anchor"]["batch"].clone();
+    fs::write(&manifest_paths[0], first.to_string()).unwrap();
+    let output = run_cap_agent(&["audit", "verify-anchor", "--manifest", &manifest_paths[0]]);
+    assert!(!output.status.success());
+
+    // Cleanup
+    for path in manifest_paths.iter().map(String::as_str).chain([
+        batch_path,
+        proof_path,
+        headers_path,
+        report_path,
+    ]) {
+        fs::remove_file(path).ok();
+    }
+}
+
+/// Test: CLI anchor-batch refuses signed manifests (the signature covers
+/// time_anchor) and leaves every manifest of the batch untouched
+#[test]
+fn cli_anchor_batch_rejects_signed_manifest() {
+    fs::create_dir_all("tests/out").ok();
+    let manifest_paths: Vec<String> = (0..2)
+        .map(|i| format!("tests/out/test_anchor_batch_signed_{}.json", i))
+        .collect();
+
+    for (i, path) in manifest_paths.iter().enumerate() {
+        create_test_manifest_with_anchor(path, &format!("0x{}", hex::encode([i as u8 + 1; 32])));
+    }
+    let mut signed: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&manifest_paths[1]).unwrap()).unwrap();
+    signed["signatures"] = serde_json::json!([{
+        "alg": "Ed25519",
+        "signer": "Test",
+        "pubkey_hex": "0x00",
+        "sig_hex": "0x00"
+    }]);
+    fs::write(&manifest_paths[1], signed.to_string()).unwrap();
+    let before: Vec<String> = manifest_paths
+        .iter()
+        .map(|path| fs::read_to_string(path).unwrap())
+        .collect();
+
+    let mut args = vec!["audit", "anchor-batch", "--chain", "btc", "--txid", "ab"];
+    for path in &manifest_paths {
+        args.extend(["--manifest", path.as_str()]);
+    }
+    let output = run_cap_agent(&args);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("signiert"));
+
+    for (path, content) in manifest_paths.iter().zip(&before) {
+        assert_eq!(&fs::read_to_string(path).unwrap(), content);
+    }
+
+    // Cleanup
+    for path in &manifest_paths {
+        fs::remove_file(path).ok();
+    }
+}
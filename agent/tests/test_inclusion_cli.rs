@@ -0,0 +1,108 @@
+// Allow deprecated cargo_bin for compatibility with custom build directories
+#![allow(deprecated)]
+
+/**
+ * CLI Integration Test: Inclusion-Proofs für einzelne Supplier/UBOs
+ *
+ * 1. CSV Data → Commitments (prepare)
+ * 2. Commitments → Manifest (manifest build)
+ * 3. Supplier → Inclusion-Proof (inclusion prove)
+ * 4. Inclusion-Proof → Offline-Verifikation gegen Manifest (inclusion verify)
+ */
+use anyhow::Result;
+use assert_cmd::Command;
+use std::fs;
+use tempfile::TempDir;
+
+#[test]
+fn test_cli_supplier_inclusion_proof_roundtrip() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let test_dir = temp_dir.path();
+
+    let suppliers_csv = test_dir.join("suppliers.csv");
+    fs::write(
+        &suppliers_csv,
+        "name,jurisdiction,tier\nAcme Corp,DE,1\nGlobal Inc,US,2\nNordic AB,SE,1\n",
+    )?;
+    let ubos_csv = test_dir.join("ubos.csv");
+    fs::write(
+        &ubos_csv,
+        "name,birthdate,citizenship\nJohn Doe,1980-01-01,US\n",
+    )?;
+    let policy_file = test_dir.join("policy.yml");
+    fs::write(
+        &policy_file,
+        r#"
+version: "lksg.v1"
+name: "Inclusion Test Policy"
+created_at: "2025-11-20T10:00:00Z"
+constraints:
+  require_at_least_one_ubo: true
+  supplier_count_max: 10
+"#,
+    )?;
+
+    Command::cargo_bin("cap-agent")?
+        .current_dir(test_dir)
+        .args(["prepare", "--suppliers"])
+        .arg(&suppliers_csv)
+        .arg("--ubos")
+        .arg(&ubos_csv)
+        .assert()
+        .success();
+
+    Command::cargo_bin("cap-agent")?
+        .current_dir(test_dir)
+        .args(["manifest", "build", "--policy"])
+        .arg(&policy_file)
+        .assert()
+        .success();
+
+    let manifest = fs::read_to_string(test_dir.join("build/manifest.json"))?;
+    assert!(manifest.contains("blake3-blinded-merkle.v2"));
+
+    Command::cargo_bin("cap-agent")?
+        .current_dir(test_dir)
+        .args(["inclusion", "prove", "--kind", "supplier", "--csv"])
+        .arg(&suppliers_csv)
+        .args(["--name", "Global Inc"])
+        .assert()
+        .success();
+
+    let proof_path = test_dir.join("build/inclusion.supplier.json");
+    let proof: serde_json::Value = serde_json::from_str(&fs::read_to_string(&proof_path)?)?;
+    assert_eq!(proof["leaf_index"], 1);
+    assert_eq!(proof["disclosed"]["name"]["value"], "Global Inc");
+    // Nicht angefragte Felder und die private Nonce bleiben verdeckt
+    assert!(proof["disclosed"].get("jurisdiction").is_none());
+    let proof_text = fs::read_to_string(&proof_path)?;
+    assert!(!proof_text.contains("\"US\""));
+    let nonces: serde_json::Value = serde_json::from_str(&fs::read_to_string(
+        test_dir.join("build/commitment_nonces.json"),
+    )?)?;
+    assert!(!proof_text.contains(nonces["suppliers"][1].as_str().unwrap()));
+
+    Command::cargo_bin("cap-agent")?
+        .current_dir(test_dir)
+        .args(["inclusion", "verify", "--proof"])
+        .arg(&proof_path)
+        .args(["--manifest", "build/manifest.json"])
+        .assert()
+        .success();
+
+    // Manipulierter Feldwert darf nicht verifizieren
+    let mut tampered = proof.clone();
+    tampered["disclosed"]["name"]["value"] = serde_json::json!("Acme Corp");
+    let tampered_path = test_dir.join("build/inclusion.tampered.json");
+    fs::write(&tampered_path, serde_json::to_string_pretty(&tampered)?)?;
+
+    Command::cargo_bin("cap-agent")?
+        .current_dir(test_dir)
+        .args(["inclusion", "verify", "--proof"])
+        .arg(&tampered_path)
+        .args(["--manifest", "build/manifest.json"])
+        .assert()
+        .failure();
+
+    Ok(())
+}
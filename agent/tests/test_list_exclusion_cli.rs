@@ -0,0 +1,269 @@
+// Allow deprecated cargo_bin for compatibility with custom build directories
+#![allow(deprecated)]
+
+/**
+ * CLI Integration Test: Jurisdiktions-Ausschluss per Non-Membership-Proof
+ *
+ * 1. CSV Data → Commitments (prepare)
+ * 2. Commitments + Blocklist → Manifest mit jurisdiction_root (manifest build)
+ * 3. Manifest + Supplier-CSV + Blocklist → Proof mit Ausschluss-Zusage (proof build),
+ *    Nachweis in der privaten Witness-Datei
+ * 4. Proof (+ Witness-Datei) → Verifikation gegen Manifest (proof verify)
+ */
+use anyhow::Result;
+use assert_cmd::Command;
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+fn setup(test_dir: &Path, blocklist: &str) -> Result<()> {
+    fs::write(
+        test_dir.join("suppliers.csv"),
+        "name,jurisdiction,tier\nAcme Corp,DE,1\nGlobal Inc,US,2\nNordic AB,se,1\n",
+    )?;
+    fs::write(
+        test_dir.join("ubos.csv"),
+        "name,birthdate,citizenship\nJohn Doe,1980-01-01,US\n",
+    )?;
+    fs::write(test_dir.join("blocklist.csv"), blocklist)?;
+    fs::write(
+        test_dir.join("policy.yml"),
+        r#"
+version: "lksg.v1"
+name: "Blocklist Test Policy"
+created_at: "2025-11-20T10:00:00Z"
+constraints:
+  require_at_least_one_ubo: true
+  supplier_count_max: 10
+"#,
+    )?;
+
+    Command::cargo_bin("cap-agent")?
+        .current_dir(test_dir)
+        .args([
+            "prepare",
+            "--suppliers",
+            "suppliers.csv",
+            "--ubos",
+            "ubos.csv",
+        ])
+        .assert()
+        .success();
+
+    Command::cargo_bin("cap-agent")?
+        .current_dir(test_dir)
+        .args([
+            "manifest",
+            "build",
+            "--policy",
+            "policy.yml",
+            "--jurisdiction-list",
+            "blocklist.csv",
+        ])
+        .assert()
+        .success();
+
+    Ok(())
+}
+
+fn proof_build(test_dir: &Path) -> Result<()> {
+    Command::cargo_bin("cap-agent")?
+        .current_dir(test_dir)
+        .args([
+            "proof",
+            "build",
+            "--policy",
+            "policy.yml",
+            "--manifest",
+            "build/manifest.json",
+            "--suppliers",
+            "suppliers.csv",
+            "--jurisdiction-list",
+            "blocklist.csv",
+        ])
+        .assert()
+        .success();
+    Ok(())
+}
+
+#[test]
+fn test_cli_jurisdiction_exclusion_roundtrip() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let test_dir = temp_dir.path();
+    setup(test_dir, "iso_code,risk_level\nIR,HIGH\nKP,HIGH\nRU,HIGH\n")?;
+
+    let manifest: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(test_dir.join("build/manifest.json"))?)?;
+    assert!(manifest["jurisdiction_root"].is_string());
+
+    // Ohne Blocklist kann kein Nachweis erzeugt werden
+    Command::cargo_bin("cap-agent")?
+        .current_dir(test_dir)
+        .args([
+            "proof",
+            "build",
+            "--policy",
+            "policy.yml",
+            "--manifest",
+            "build/manifest.json",
+        ])
+        .assert()
+        .failure();
+
+    proof_build(test_dir)?;
+
+    let proof: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(test_dir.join("build/proof.json"))?)?;
+    assert_eq!(proof["status"], "ok");
+    let claim = &proof["proof_data"]["list_exclusions"][0];
+    assert_eq!(claim["list"], "jurisdictions");
+    assert_eq!(claim["root"], manifest["jurisdiction_root"]);
+    assert_eq!(claim["records"]["supplier"], 3);
+    assert!(claim.get("proofs").is_none());
+
+    let witnesses: serde_json::Value = serde_json::from_str(&fs::read_to_string(
+        test_dir.join("build/list_witnesses.json"),
+    )?)?;
+    assert_eq!(witnesses["version"], "cap-list-witnesses.v1");
+    assert_eq!(
+        witnesses["witnesses"][0]["proofs"]
+            .as_array()
+            .unwrap()
+            .len(),
+        3
+    );
+
+    Command::cargo_bin("cap-agent")?
+        .current_dir(test_dir)
+        .args([
+            "proof",
+            "verify",
+            "--proof",
+            "build/proof.dat",
+            "--manifest",
+            "build/manifest.json",
+        ])
+        .assert()
+        .success();
+
+    Command::cargo_bin("cap-agent")?
+        .current_dir(test_dir)
+        .args([
+            "proof",
+            "verify",
+            "--proof",
+            "build/proof.dat",
+            "--manifest",
+            "build/manifest.json",
+            "--list-witnesses",
+            "build/list_witnesses.json",
+        ])
+        .assert()
+        .success();
+
+    Ok(())
+}
+
+#[test]
+fn test_cli_proof_dat_contains_no_record_values() -> Result<()> {
+    use base64::{engine::general_purpose, Engine as _};
+
+    let temp_dir = TempDir::new()?;
+    let test_dir = temp_dir.path();
+    setup(test_dir, "iso_code,risk_level\nIR,HIGH\nKP,HIGH\n")?;
+    fs::write(test_dir.join("sanctions.csv"), "name\nIvan Petrov\n")?;
+
+    Command::cargo_bin("cap-agent")?
+        .current_dir(test_dir)
+        .args(["manifest", "build", "--policy", "policy.yml"])
+        .args(["--jurisdiction-list", "blocklist.csv"])
+        .args(["--sanctions-list", "sanctions.csv"])
+        .assert()
+        .success();
+
+    Command::cargo_bin("cap-agent")?
+        .current_dir(test_dir)
+        .args(["proof", "build", "--policy", "policy.yml"])
+        .args(["--manifest", "build/manifest.json"])
+        .args(["--suppliers", "suppliers.csv", "--ubos", "ubos.csv"])
+        .args(["--jurisdiction-list", "blocklist.csv"])
+        .args(["--sanctions-list", "sanctions.csv"])
+        .assert()
+        .success();
+
+    let dat = fs::read_to_string(test_dir.join("build/proof.dat"))?;
+    let decoded = String::from_utf8(general_purpose::STANDARD.decode(dat.trim())?)?;
+    let json = fs::read_to_string(test_dir.join("build/proof.json"))?;
+    let proof: serde_json::Value = serde_json::from_str(&json)?;
+    assert_eq!(
+        proof["proof_data"]["list_exclusions"]
+            .as_array()
+            .unwrap()
+            .len(),
+        2
+    );
+
+    let record_values = [
+        "Acme Corp",
+        "Global Inc",
+        "Nordic AB",
+        "John Doe",
+        "1980-01-01",
+        "\"DE\"",
+        "\"US\"",
+        "\"se\"",
+        "\"SE\"",
+    ];
+    for content in [&dat, &decoded, &json] {
+        for value in record_values {
+            assert!(!content.contains(value), "proof enthält {}", value);
+        }
+    }
+
+    // Die geprüften Werte stehen nur in der privaten Witness-Datei
+    let witnesses = fs::read_to_string(test_dir.join("build/list_witnesses.json"))?;
+    assert!(witnesses.contains("Acme Corp") && witnesses.contains("John Doe"));
+
+    // Manipulierte Witness-Datei passt nicht zu den Zusagen im Proof
+    fs::write(
+        test_dir.join("build/list_witnesses.json"),
+        witnesses.replace("Acme Corp", "Acme Corq"),
+    )?;
+    Command::cargo_bin("cap-agent")?
+        .current_dir(test_dir)
+        .args(["proof", "verify", "--proof", "build/proof.dat"])
+        .args(["--manifest", "build/manifest.json"])
+        .args(["--list-witnesses", "build/list_witnesses.json"])
+        .assert()
+        .failure();
+
+    Ok(())
+}
+
+#[test]
+fn test_cli_blocked_jurisdiction_fails_proof() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let test_dir = temp_dir.path();
+    setup(test_dir, "iso_code,risk_level\nIR,HIGH\nSE,HIGH\n")?;
+
+    proof_build(test_dir)?;
+
+    let proof: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(test_dir.join("build/proof.json"))?)?;
+    assert_eq!(proof["status"], "failed");
+
+    Command::cargo_bin("cap-agent")?
+        .current_dir(test_dir)
+        .args([
+            "proof",
+            "verify",
+            "--proof",
+            "build/proof.json",
+            "--manifest",
+            "build/manifest.json",
+        ])
+        .assert()
+        .failure();
+
+    Ok(())
+}
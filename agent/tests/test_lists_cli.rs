@@ -0,0 +1,163 @@
+// Allow deprecated cargo_bin for compatibility with custom build directories
+#![allow(deprecated)]
+
+/**
+ * CLI Integration Test: Listen-Roots (lists sanctions-root / jurisdictions-root)
+ *
+ * 1. Listen-CSV → versionierte Root-Datei (lists ...-root)
+ * 2. Root-Datei → Manifest-Referenz (manifest build --...-root)
+ * 3. Manifest + Listen-CSV → Proof mit Ausschluss-Nachweis (proof build)
+ */
+use anyhow::Result;
+use assert_cmd::Command;
+use std::fs;
+use tempfile::TempDir;
+
+fn read_json(path: &std::path::Path) -> Result<serde_json::Value> {
+    Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+}
+
+#[test]
+fn test_cli_lists_root_files() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let test_dir = temp_dir.path();
+
+    fs::write(
+        test_dir.join("sanctions.csv"),
+        "name,birthdate,nationality\nAli Hassan,1984-01-14,IR\n  ali   HASSAN ,1984-01-14,IR\nMaría Petrova,1973-05-22,RU\n",
+    )?;
+    fs::write(
+        test_dir.join("jurisdictions.csv"),
+        "iso_code,risk_level\nIR,HIGH\nPRK,HIGH\nrus,HIGH\n",
+    )?;
+
+    Command::cargo_bin("cap-agent")?
+        .current_dir(test_dir)
+        .args(["lists", "sanctions-root", "--csv", "sanctions.csv"])
+        .assert()
+        .success();
+
+    Command::cargo_bin("cap-agent")?
+        .current_dir(test_dir)
+        .args(["lists", "jurisdictions-root", "--csv", "jurisdictions.csv"])
+        .assert()
+        .success();
+
+    let sanctions = read_json(&test_dir.join("build/sanctions.root"))?;
+    assert_eq!(sanctions["version"], "cap-list-root.v1");
+    assert_eq!(sanctions["list"], "sanctions");
+    assert_eq!(sanctions["entry_count"], 2);
+    assert_eq!(sanctions["source_rows"], 3);
+    assert!(sanctions["source_hash"].as_str().unwrap().starts_with("0x"));
+    assert!(sanctions["built_at"].is_string());
+
+    let jurisdictions = read_json(&test_dir.join("build/jurisdictions.root"))?;
+    assert_eq!(jurisdictions["list"], "jurisdictions");
+    assert_eq!(jurisdictions["entry_count"], 3);
+
+    // Ungültige Ländercodes werden abgelehnt
+    fs::write(test_dir.join("invalid.csv"), "iso_code\nIR\nAtlantis\n")?;
+    Command::cargo_bin("cap-agent")?
+        .current_dir(test_dir)
+        .args(["lists", "jurisdictions-root", "--csv", "invalid.csv"])
+        .args(["--out", "build/invalid.root"])
+        .assert()
+        .failure();
+    assert!(!test_dir.join("build/invalid.root").exists());
+
+    Ok(())
+}
+
+#[test]
+fn test_cli_manifest_references_root_file() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let test_dir = temp_dir.path();
+
+    fs::write(
+        test_dir.join("suppliers.csv"),
+        "name,jurisdiction,tier\nAcme Corp,DEU,1\nGlobal Inc,US,2\n",
+    )?;
+    fs::write(
+        test_dir.join("ubos.csv"),
+        "name,birthdate,citizenship\nJohn Doe,1980-01-01,US\n",
+    )?;
+    fs::write(
+        test_dir.join("jurisdictions.csv"),
+        "iso_code,risk_level\nIR,HIGH\nKP,HIGH\nRU,HIGH\n",
+    )?;
+    fs::write(
+        test_dir.join("policy.yml"),
+        r#"
+version: "lksg.v1"
+name: "Root File Test Policy"
+created_at: "2025-11-20T10:00:00Z"
+constraints:
+  require_at_least_one_ubo: true
+  supplier_count_max: 10
+"#,
+    )?;
+
+    Command::cargo_bin("cap-agent")?
+        .current_dir(test_dir)
+        .args([
+            "prepare",
+            "--suppliers",
+            "suppliers.csv",
+            "--ubos",
+            "ubos.csv",
+        ])
+        .assert()
+        .success();
+
+    Command::cargo_bin("cap-agent")?
+        .current_dir(test_dir)
+        .args(["lists", "jurisdictions-root", "--csv", "jurisdictions.csv"])
+        .assert()
+        .success();
+
+    // Root-Datei der falschen Liste wird abgelehnt
+    Command::cargo_bin("cap-agent")?
+        .current_dir(test_dir)
+        .args(["manifest", "build", "--policy", "policy.yml"])
+        .args(["--sanctions-root", "build/jurisdictions.root"])
+        .assert()
+        .failure();
+
+    Command::cargo_bin("cap-agent")?
+        .current_dir(test_dir)
+        .args(["manifest", "build", "--policy", "policy.yml"])
+        .args(["--jurisdiction-root", "build/jurisdictions.root"])
+        .assert()
+        .success();
+
+    let root_file = read_json(&test_dir.join("build/jurisdictions.root"))?;
+    let manifest = read_json(&test_dir.join("build/manifest.json"))?;
+    assert_eq!(manifest["jurisdiction_root"], root_file["root"]);
+    assert!(manifest.get("sanctions_root").is_none());
+
+    Command::cargo_bin("cap-agent")?
+        .current_dir(test_dir)
+        .args(["proof", "build", "--policy", "policy.yml"])
+        .args(["--manifest", "build/manifest.json"])
+        .args(["--suppliers", "suppliers.csv"])
+        .args(["--jurisdiction-list", "jurisdictions.csv"])
+        .assert()
+        .success();
+
+    let proof = read_json(&test_dir.join("build/proof.json"))?;
+    assert_eq!(proof["status"], "ok");
+    assert_eq!(
+        proof["proof_data"]["list_exclusions"][0]["list"],
+        "jurisdictions"
+    );
+    let witnesses = read_json(&test_dir.join("build/list_witnesses.json"))?;
+    let values: Vec<&str> = witnesses["witnesses"][0]["proofs"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|p| p["value"].as_str().unwrap())
+        .collect();
+    assert_eq!(values, ["DE", "US"]);
+
+    Ok(())
+}
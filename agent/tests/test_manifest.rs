@@ -24,6 +24,8 @@ fn create_test_manifest() -> Manifest {
             name: "Test Policy".to_string(),
             version: "lksg.v1".to_string(),
             hash: "0xpolicyhash".to_string(),
+            valid_from: None,
+            valid_until: None,
         },
         audit: AuditInfo {
             tail_digest: "0xtail".to_string(),
@@ -35,6 +37,11 @@ fn create_test_manifest() -> Manifest {
         },
         signatures: Vec::new(),
         time_anchor: None,
+        commitment_scheme: None,
+        sanctions_root: None,
+        jurisdiction_root: None,
+        supplier_count: None,
+        ubo_count: None,
     }
 }
 
@@ -62,12 +69,15 @@ fn test_manifest_build() {
         company_commitment_root: "0x789ghi".to_string(),
         supplier_count: Some(5),
         ubo_count: Some(2),
+        commitment_scheme: None,
     };
 
     let policy_info = PolicyInfo {
         name: "Test Policy".to_string(),
         version: "lksg.v1".to_string(),
         hash: "0xpolicy123".to_string(),
+        valid_from: None,
+        valid_until: None,
     };
 
     // Create temporary audit log
@@ -476,12 +486,15 @@ fn test_read_audit_tail_empty_file() {
         company_commitment_root: "0x123".to_string(),
         supplier_count: Some(0),
         ubo_count: Some(0),
+        commitment_scheme: None,
     };
 
     let policy_info = PolicyInfo {
         name: "Test".to_string(),
         version: "lksg.v1".to_string(),
         hash: "0xhash".to_string(),
+        valid_from: None,
+        valid_until: None,
     };
 
     let result = Manifest::build(&commitments, policy_info, temp_audit);
@@ -513,12 +526,15 @@ fn test_read_audit_tail_multiple_entries() {
         company_commitment_root: "0x123".to_string(),
         supplier_count: Some(0),
         ubo_count: Some(0),
+        commitment_scheme: None,
     };
 
     let policy_info = PolicyInfo {
         name: "Test".to_string(),
         version: "lksg.v1".to_string(),
         hash: "0xhash".to_string(),
+        valid_from: None,
+        valid_until: None,
     };
 
     let result = Manifest::build(&commitments, policy_info, temp_audit);
@@ -541,12 +557,15 @@ fn test_read_audit_tail_file_not_found() {
         company_commitment_root: "0x123".to_string(),
         supplier_count: Some(0),
         ubo_count: Some(0),
+        commitment_scheme: None,
     };
 
     let policy_info = PolicyInfo {
         name: "Test".to_string(),
         version: "lksg.v1".to_string(),
         hash: "0xhash".to_string(),
+        valid_from: None,
+        valid_until: None,
     };
 
     let result = Manifest::build(&commitments, policy_info, "/nonexistent/path/audit.jsonl");
@@ -0,0 +1,458 @@
+// Allow deprecated cargo_bin for compatibility with custom build directories
+#![allow(deprecated)]
+
+/**
+ * CLI Integration Test: Proof-Erstellung aus kompilierter Policy v2 (IR)
+ *
+ * 1. CSV Data → Commitments (prepare)
+ * 2. Policy v2 YAML → IR (policy compile)
+ * 3. IR + Sanktionsliste → Manifest (manifest build)
+ * 4. IR-Regeln gegen committete Daten → Proof (proof build)
+ * 5. Proof → Verifikation gegen Manifest (proof verify)
+ */
+use anyhow::Result;
+use assert_cmd::Command;
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+const POLICY_V2: &str = r#"
+id: "lksg.ir.test"
+version: "1.0"
+legal_basis:
+  - directive: "LkSG"
+    article: "§3 Abs. 1"
+description: "IR evaluation test policy"
+inputs:
+  supplier_hashes:
+    type: "array"
+    items: "string"
+  ubo_hashes:
+    type: "array"
+    items: "string"
+  sanctions_root:
+    type: "string"
+rules:
+  - id: "ubo_not_sanctioned"
+    op: "non_membership"
+    lhs:
+      var: "ubo_hashes"
+    rhs:
+      var: "sanctions_root"
+  - id: "supplier_count_check"
+    op: "range_min"
+    lhs:
+      var: "supplier_hashes"
+    rhs: 2
+"#;
+
+/// Eine Policy für Tier-1 und Deep-Tier: die Audit-Regel greift nur bei Tier > 1
+const POLICY_V2_TIERED: &str = r#"
+id: "lksg.tiered"
+version: "1.0"
+legal_basis:
+  - directive: "LkSG"
+    article: "§5"
+inputs:
+  supplier_tiers:
+    type: "array"
+    items: "integer"
+  ubo_count:
+    type: "integer"
+rules:
+  - id: "deep_tier_ubo_coverage"
+    op: "range_min"
+    lhs:
+      var: "ubo_count"
+    rhs: 2
+  - id: "ubo_present"
+    op: "range_min"
+    lhs:
+      var: "ubo_count"
+    rhs: 1
+adaptivity:
+  predicates:
+    - id: "deep_tier"
+      expr:
+        func: "gt"
+        args:
+          - func: "max"
+            args:
+              - var: "supplier_tiers"
+          - 1
+  activations:
+    - when: "deep_tier"
+      rules: ["deep_tier_ubo_coverage"]
+"#;
+
+fn cap_agent(dir: &Path) -> Result<Command> {
+    let mut cmd = Command::cargo_bin("cap-agent")?;
+    cmd.current_dir(dir);
+    Ok(cmd)
+}
+
+/// Bereitet Commitments, IR und Manifest vor
+fn setup(dir: &Path, ubo_name: &str) -> Result<()> {
+    fs::write(
+        dir.join("suppliers.csv"),
+        "name,jurisdiction,tier\nAcme Corp,DE,1\nNordic AB,SE,1\n",
+    )?;
+    fs::write(
+        dir.join("ubos.csv"),
+        format!("name,birthdate,citizenship\n{},1980-01-01,US\n", ubo_name),
+    )?;
+    fs::write(dir.join("sanctions.csv"), "name\nIvan Petrov\n")?;
+    fs::write(dir.join("policy.v2.yml"), POLICY_V2)?;
+
+    cap_agent(dir)?
+        .args([
+            "prepare",
+            "--suppliers",
+            "suppliers.csv",
+            "--ubos",
+            "ubos.csv",
+        ])
+        .assert()
+        .success();
+
+    cap_agent(dir)?
+        .args(["policy", "compile", "policy.v2.yml", "-o", "policy.ir.json"])
+        .assert()
+        .success();
+
+    cap_agent(dir)?
+        .args(["manifest", "build", "--policy", "policy.ir.json"])
+        .args(["--sanctions-list", "sanctions.csv"])
+        .assert()
+        .success();
+
+    Ok(())
+}
+
+fn build_proof(dir: &Path) -> Result<()> {
+    cap_agent(dir)?
+        .args(["proof", "build", "--policy", "policy.ir.json"])
+        .args(["--manifest", "build/manifest.json"])
+        .args(["--suppliers", "suppliers.csv", "--ubos", "ubos.csv"])
+        .args(["--sanctions-list", "sanctions.csv"])
+        .assert()
+        .success();
+    Ok(())
+}
+
+#[test]
+fn test_cli_ir_policy_drives_proof() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let test_dir = temp_dir.path();
+    setup(test_dir, "John Doe")?;
+    build_proof(test_dir)?;
+
+    let ir: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(test_dir.join("policy.ir.json"))?)?;
+    let proof: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(test_dir.join("build/proof.json"))?)?;
+
+    assert_eq!(proof["status"], "ok");
+    assert_eq!(proof["statement"], "policy:lksg.ir.test");
+    assert_eq!(proof["ir_hash"], ir["ir_hash"]);
+
+    let checks: Vec<&str> = proof["proof_data"]["checked_constraints"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|c| c["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(checks, ["supplier_count_check", "ubo_not_sanctioned"]);
+    assert_eq!(
+        proof["proof_data"]["list_exclusions"][0]["list"],
+        "sanctions"
+    );
+
+    cap_agent(test_dir)?
+        .args(["proof", "verify", "--proof", "build/proof.json"])
+        .args(["--manifest", "build/manifest.json"])
+        .args(["--list-witnesses", "build/list_witnesses.json"])
+        .assert()
+        .success();
+
+    Ok(())
+}
+
+#[test]
+fn test_cli_ir_policy_sanctioned_ubo_fails() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let test_dir = temp_dir.path();
+    setup(test_dir, "Ivan Petrov")?;
+    build_proof(test_dir)?;
+
+    let proof: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(test_dir.join("build/proof.json"))?)?;
+    assert_eq!(proof["status"], "failed");
+
+    cap_agent(test_dir)?
+        .args(["proof", "verify", "--proof", "build/proof.json"])
+        .args(["--manifest", "build/manifest.json"])
+        .assert()
+        .failure();
+
+    Ok(())
+}
+
+#[test]
+fn test_cli_ir_tampered_hash_is_rejected() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let test_dir = temp_dir.path();
+    setup(test_dir, "John Doe")?;
+
+    let ir_path = test_dir.join("policy.ir.json");
+    let mut ir: serde_json::Value = serde_json::from_str(&fs::read_to_string(&ir_path)?)?;
+    ir["rules"][1]["rhs"] = serde_json::json!(0);
+    fs::write(&ir_path, serde_json::to_string_pretty(&ir)?)?;
+
+    cap_agent(test_dir)?
+        .args(["proof", "build", "--policy", "policy.ir.json"])
+        .args(["--manifest", "build/manifest.json"])
+        .args(["--suppliers", "suppliers.csv", "--ubos", "ubos.csv"])
+        .args(["--sanctions-list", "sanctions.csv"])
+        .assert()
+        .failure();
+
+    Ok(())
+}
+
+#[test]
+fn test_cli_ir_without_rule_for_list_root_fails_build() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let test_dir = temp_dir.path();
+    setup(test_dir, "John Doe")?;
+
+    // Manifest mit sanctions_root, aber IR ohne non_membership-Regel
+    fs::write(test_dir.join("policy.v2.yml"), POLICY_V2_TIERED)?;
+    cap_agent(test_dir)?
+        .args(["policy", "compile", "policy.v2.yml", "-o", "tiered.ir.json"])
+        .assert()
+        .success();
+    cap_agent(test_dir)?
+        .args(["manifest", "build", "--policy", "tiered.ir.json"])
+        .args(["--sanctions-list", "sanctions.csv"])
+        .assert()
+        .success();
+
+    cap_agent(test_dir)?
+        .args(["proof", "build", "--policy", "tiered.ir.json"])
+        .args(["--manifest", "build/manifest.json"])
+        .args(["--suppliers", "suppliers.csv", "--ubos", "ubos.csv"])
+        .args(["--sanctions-list", "sanctions.csv"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("non_membership"));
+    assert!(!test_dir.join("build/proof.json").exists());
+
+    Ok(())
+}
+
+/// Erstellt einen Proof aus der gestuften Policy für die gegebenen Supplier-Tiers
+fn build_tiered_proof(dir: &Path, tiers: &[u32]) -> Result<serde_json::Value> {
+    let suppliers: String = tiers
+        .iter()
+        .enumerate()
+        .map(|(i, tier)| format!("Supplier {},DE,{}\n", i, tier))
+        .collect();
+    fs::write(
+        dir.join("suppliers.csv"),
+        format!("name,jurisdiction,tier\n{}", suppliers),
+    )?;
+    fs::write(
+        dir.join("ubos.csv"),
+        "name,birthdate,citizenship\nJohn Doe,1980-01-01,US\n",
+    )?;
+    fs::write(dir.join("policy.v2.yml"), POLICY_V2_TIERED)?;
+
+    cap_agent(dir)?
+        .args([
+            "prepare",
+            "--suppliers",
+            "suppliers.csv",
+            "--ubos",
+            "ubos.csv",
+        ])
+        .assert()
+        .success();
+    cap_agent(dir)?
+        .args(["policy", "compile", "policy.v2.yml", "-o", "policy.ir.json"])
+        .assert()
+        .success();
+    cap_agent(dir)?
+        .args(["manifest", "build", "--policy", "policy.ir.json"])
+        .assert()
+        .success();
+    cap_agent(dir)?
+        .args(["proof", "build", "--policy", "policy.ir.json"])
+        .args(["--manifest", "build/manifest.json"])
+        .args(["--suppliers", "suppliers.csv", "--ubos", "ubos.csv"])
+        .assert()
+        .success();
+
+    Ok(serde_json::from_str(&fs::read_to_string(
+        dir.join("build/proof.json"),
+    )?)?)
+}
+
+#[test]
+fn test_cli_adaptivity_selects_rules_by_tier() -> Result<()> {
+    // Tier-1: Deep-Tier-Regel inaktiv, Proof ok
+    let tier1_dir = TempDir::new()?;
+    let proof = build_tiered_proof(tier1_dir.path(), &[1, 1])?;
+    assert_eq!(proof["status"], "ok");
+    let activations = &proof["proof_data"]["rule_activations"];
+    assert_eq!(activations[0]["rule_id"], "deep_tier_ubo_coverage");
+    assert_eq!(activations[0]["active"], false);
+    assert_eq!(activations[1]["reason"], "unconditional");
+    assert_eq!(
+        proof["proof_data"]["checked_constraints"]
+            .as_array()
+            .unwrap()
+            .len(),
+        1
+    );
+
+    let audit = fs::read_to_string(tier1_dir.path().join("build/agent.audit.jsonl"))?;
+    assert!(audit.contains("policy_rules_activated"));
+
+    cap_agent(tier1_dir.path())?
+        .args(["proof", "verify", "--proof", "build/proof.json"])
+        .args(["--manifest", "build/manifest.json"])
+        .assert()
+        .success();
+
+    // Deep-Tier: Regel aktiv, ein UBO reicht nicht
+    let deep_dir = TempDir::new()?;
+    let proof = build_tiered_proof(deep_dir.path(), &[1, 3])?;
+    assert_eq!(proof["status"], "failed");
+    let activations = &proof["proof_data"]["rule_activations"];
+    assert_eq!(activations[0]["active"], true);
+    assert_eq!(activations[0]["reason"], "activated by: deep_tier");
+
+    Ok(())
+}
+
+/// Kopiert die mitgelieferten Beispiel-Daten und die regelbasierte Policy
+fn setup_rule_policy(dir: &Path, valid_until: Option<&str>) -> Result<()> {
+    let examples = Path::new(env!("CARGO_MANIFEST_DIR")).join("../examples");
+    for file in ["suppliers.csv", "ubos.csv"] {
+        fs::copy(examples.join(file), dir.join(file))?;
+    }
+    let mut policy = fs::read_to_string(examples.join("policy.yml"))?;
+    if let Some(date) = valid_until {
+        policy = policy.replace("valid_until: 2025-12-31", &format!("valid_until: {}", date));
+    }
+    fs::write(dir.join("policy.yml"), policy)?;
+
+    cap_agent(dir)?
+        .args([
+            "prepare",
+            "--suppliers",
+            "suppliers.csv",
+            "--ubos",
+            "ubos.csv",
+        ])
+        .assert()
+        .success();
+    cap_agent(dir)?
+        .args(["policy", "compile", "policy.yml", "-o", "policy.ir.json"])
+        .assert()
+        .success();
+    Ok(())
+}
+
+fn manifest_build_rule_policy(dir: &Path) -> assert_cmd::assert::Assert {
+    cap_agent(dir)
+        .unwrap()
+        .args(["manifest", "build", "--policy", "policy.ir.json"])
+        .assert()
+}
+
+fn proof_build_rule_policy(dir: &Path) -> assert_cmd::assert::Assert {
+    cap_agent(dir)
+        .unwrap()
+        .args(["proof", "build", "--policy", "policy.ir.json"])
+        .args(["--manifest", "build/manifest.json"])
+        .args(["--suppliers", "suppliers.csv", "--ubos", "ubos.csv"])
+        .assert()
+}
+
+#[test]
+fn test_cli_rule_based_policy_drives_proof() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let test_dir = temp_dir.path();
+    setup_rule_policy(test_dir, Some("2099-12-31"))?;
+    manifest_build_rule_policy(test_dir).success();
+    let manifest: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(test_dir.join("build/manifest.json"))?)?;
+    assert_eq!(manifest["policy"]["valid_until"], "2099-12-31");
+
+    let ir: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(test_dir.join("policy.ir.json"))?)?;
+    assert_eq!(ir["policy_id"], "lksg.v1.0");
+    assert_eq!(ir["valid_until"], "2099-12-31");
+
+    proof_build_rule_policy(test_dir).success();
+    let proof: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(test_dir.join("build/proof.json"))?)?;
+    assert_eq!(proof["status"], "ok");
+    let checks: Vec<&str> = proof["proof_data"]["checked_constraints"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|c| c["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        checks,
+        [
+            "jurisdiction_check",
+            "tier_validation",
+            "ubo_citizenship_check"
+        ]
+    );
+
+    // Lieferant aus einem gesperrten Land lässt die Blocklist-Regel scheitern
+    let mut suppliers = fs::read_to_string(test_dir.join("suppliers.csv"))?;
+    suppliers.push_str("Tehran Trading,IR,1\n");
+    fs::write(test_dir.join("suppliers.csv"), suppliers)?;
+    cap_agent(test_dir)?
+        .args([
+            "prepare",
+            "--suppliers",
+            "suppliers.csv",
+            "--ubos",
+            "ubos.csv",
+        ])
+        .assert()
+        .success();
+    cap_agent(test_dir)?
+        .args(["manifest", "build", "--policy", "policy.ir.json"])
+        .assert()
+        .success();
+    proof_build_rule_policy(test_dir).success();
+    let proof: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(test_dir.join("build/proof.json"))?)?;
+    assert_eq!(proof["status"], "failed");
+    assert_eq!(proof["proof_data"]["checked_constraints"][0]["ok"], false);
+
+    Ok(())
+}
+
+#[test]
+fn test_cli_rule_based_policy_expired() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let test_dir = temp_dir.path();
+    // Mitgeliefertes Beispiel: valid_until 2025-12-31
+    setup_rule_policy(test_dir, None)?;
+
+    manifest_build_rule_policy(test_dir)
+        .failure()
+        .stderr(predicates::str::contains("expired"));
+    assert!(!test_dir.join("build/manifest.json").exists());
+
+    Ok(())
+}
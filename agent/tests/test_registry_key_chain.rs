@@ -54,6 +54,7 @@ fn test_registry_with_active_key_validation() {
         verifier_version: None,
         kid: None,
         signature_scheme: None,
+        policy_id: None,
     };
 
     // Sign entry with key1
@@ -102,6 +103,7 @@ fn test_registry_with_active_key_validation() {
         verifier_version: None,
         kid: None,
         signature_scheme: None,
+        policy_id: None,
     };
 
     // Sign with retired key (technically possible, but validation should fail)
@@ -24,6 +24,8 @@ fn create_test_manifest() -> Manifest {
             name: "Test Policy".to_string(),
             version: "lksg.v1".to_string(),
             hash: "0xpolicyhash".to_string(),
+            valid_from: None,
+            valid_until: None,
         },
         audit: AuditInfo {
             tail_digest: "0xtaildigest".to_string(),
@@ -35,6 +37,11 @@ fn create_test_manifest() -> Manifest {
         },
         signatures: Vec::new(),
         time_anchor: None,
+        commitment_scheme: None,
+        sanctions_root: None,
+        jurisdiction_root: None,
+        supplier_count: None,
+        ubo_count: None,
     }
 }
 
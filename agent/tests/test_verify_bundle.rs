@@ -26,13 +26,15 @@ fn create_test_manifest(path: &str) -> Result<(), Box<dyn std::error::Error>> {
             "type": "mock",
             "status": "ok"
         },
-        "signatures": [{
-            "signer_pubkey": "0xabc1234567890123456789012345678901234567890123456789012345678901234567890123456789012345678901234567890123456789012345678901234567",
-            "signature": "0xdef1234567890123456789012345678901234567890123456789012345678901234567890123456789012345678901234567890123456789012345678901234567890123456789012345678901234567890123456789012345678901234567890123456789012345678901234567890123456789",
-            "signed_at": "2025-10-30T10:00:00Z"
-        }]
+        "signatures": []
     });
 
+    // Mit Ed25519 signieren (verify-bundle prüft die Signatur)
+    let mut manifest: cap_agent::manifest::Manifest = serde_json::from_value(manifest)?;
+    let signing_key = ed25519_dalek::SigningKey::from_bytes(&[7u8; 32]);
+    let signed = cap_agent::sign::sign_manifest(&manifest, &signing_key, "TestCompany")?;
+    manifest.signatures.push(signed.signature);
+
     fs::write(path, serde_json::to_string_pretty(&manifest)?)?;
     Ok(())
 }
@@ -1,62 +1,30 @@
 //! Audit Logger for Taurin Desktop App
 //!
 //! Logs workflow events to the project's audit log (agent.audit.jsonl).
-//! Uses V1.0 format compatible with cap_agent.
+//! Writes through the shared cap_agent audit chain (structured format with
+//! event details); existing V1.0 logs are migrated once, losslessly.
 
-use chrono::Utc;
-use serde::Serialize;
-use sha3::{Digest, Sha3_256};
-use std::fs::{self, OpenOptions};
-use std::io::Write;
+use cap_agent::audit::checkpoint::{read_event_hashes, LogFormat};
+use cap_agent::audit::{create_audit_store, migrate_v1_in_place};
+use std::fs;
 use std::path::Path;
 
-/// Genesis hash for first event in chain
-const GENESIS_HASH: &str = "0x0000000000000000000000000000000000000000000000000000000000000000";
-
-/// V1.0 Audit Entry
-#[derive(Debug, Serialize)]
-struct AuditEntry {
-    seq: u64,
-    ts: String,
-    event: String,
-    details: serde_json::Value,
-    prev_digest: String,
-    digest: String,
-}
-
-/// Computes SHA3-256 digest for V1.0 format
-fn compute_digest(
-    seq: u64,
-    ts: &str,
-    event: &str,
-    details: &serde_json::Value,
-    prev_digest: &str,
-) -> String {
-    let mut hasher = Sha3_256::new();
-    hasher.update(seq.to_string().as_bytes());
-    hasher.update(ts.as_bytes());
-    hasher.update(event.as_bytes());
-    hasher.update(details.to_string().as_bytes());
-    hasher.update(prev_digest.as_bytes());
-
-    let result = hasher.finalize();
-    format!("0x{}", hex::encode(result))
-}
-
-/// Gets the last entry from the audit log
-fn get_last_entry(audit_path: &Path) -> Option<(u64, String)> {
+/// Migrates a V1.0 audit log (seq/digest) to the structured format
+///
+/// The original stays next to the log as `agent.audit.v1.jsonl`.
+fn migrate_if_v1(audit_path: &Path) -> Result<(), String> {
     if !audit_path.exists() {
-        return None;
+        return Ok(());
     }
 
-    let content = fs::read_to_string(audit_path).ok()?;
-    let last_line = content.lines().filter(|l| !l.trim().is_empty()).next_back()?;
-
-    let entry: serde_json::Value = serde_json::from_str(last_line).ok()?;
-    let seq = entry.get("seq")?.as_u64()?;
-    let digest = entry.get("digest")?.as_str()?.to_string();
+    let (format, _) = read_event_hashes(audit_path)
+        .map_err(|e| format!("Failed to read audit log: {}", e))?;
+    if format == LogFormat::V1 {
+        migrate_v1_in_place(audit_path)
+            .map_err(|e| format!("Failed to migrate audit log: {}", e))?;
+    }
 
-    Some((seq, digest))
+    Ok(())
 }
 
 /// Logs an event to the project's audit log
@@ -67,7 +35,7 @@ fn get_last_entry(audit_path: &Path) -> Option<(u64, String)> {
 /// * `details` - Event details as JSON
 ///
 /// # Returns
-/// Result with the event digest or error
+/// Result with the event hash or error
 pub fn log_event(
     project_path: &Path,
     event: &str,
@@ -81,42 +49,14 @@ pub fn log_event(
             .map_err(|e| format!("Failed to create audit directory: {}", e))?;
     }
 
-    // Get previous entry info
-    let (seq, prev_digest) = get_last_entry(&audit_path)
-        .map(|(s, d)| (s + 1, d))
-        .unwrap_or((1, GENESIS_HASH.to_string()));
-
-    // Create timestamp
-    let ts = Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Micros, true);
-
-    // Compute digest
-    let digest = compute_digest(seq, &ts, event, &details, &prev_digest);
-
-    // Create entry
-    let entry = AuditEntry {
-        seq,
-        ts,
-        event: event.to_string(),
-        details,
-        prev_digest,
-        digest: digest.clone(),
-    };
-
-    // Serialize to JSON
-    let json = serde_json::to_string(&entry)
-        .map_err(|e| format!("Failed to serialize audit entry: {}", e))?;
-
-    // Append to audit log
-    let mut file = OpenOptions::new()
-        .create(true)
-        .append(true)
-        .open(&audit_path)
-        .map_err(|e| format!("Failed to open audit log: {}", e))?;
+    migrate_if_v1(&audit_path)?;
 
-    writeln!(file, "{}", json)
-        .map_err(|e| format!("Failed to write audit entry: {}", e))?;
+    let mut store = create_audit_store(&audit_path, "v2")
+        .map_err(|e| format!("Failed to open audit log: {}", e))?;
 
-    Ok(digest)
+    store
+        .append_event(event, details)
+        .map_err(|e| format!("Failed to write audit entry: {}", e))
 }
 
 /// Convenience macros for logging specific events
@@ -263,13 +203,14 @@ mod tests {
         fs::create_dir_all(project.join("audit")).unwrap();
 
         // First event
-        let digest1 = log_event(project, "event1", serde_json::json!({})).unwrap();
+        let hash1 = log_event(project, "event1", serde_json::json!({})).unwrap();
 
         // Second event
-        let digest2 = log_event(project, "event2", serde_json::json!({})).unwrap();
+        let hash2 = log_event(project, "event2", serde_json::json!({"key": "value"})).unwrap();
 
         // Read and verify chain
-        let content = fs::read_to_string(project.join("audit/agent.audit.jsonl")).unwrap();
+        let audit_path = project.join("audit/agent.audit.jsonl");
+        let content = fs::read_to_string(&audit_path).unwrap();
         let lines: Vec<&str> = content.lines().collect();
 
         assert_eq!(lines.len(), 2);
@@ -277,10 +218,35 @@ mod tests {
         let entry1: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
         let entry2: serde_json::Value = serde_json::from_str(lines[1]).unwrap();
 
-        assert_eq!(entry1["seq"], 1);
-        assert_eq!(entry2["seq"], 2);
-        assert_eq!(entry1["prev_digest"], GENESIS_HASH);
-        assert_eq!(entry2["prev_digest"], digest1);
-        assert_eq!(entry2["digest"], digest2);
+        assert_eq!(entry1["prev_hash"], cap_agent::audit::AuditChain::GENESIS_HASH);
+        assert_eq!(entry1["self_hash"], hash1);
+        assert_eq!(entry2["prev_hash"], hash1);
+        assert_eq!(entry2["self_hash"], hash2);
+        assert_eq!(entry2["details"]["key"], "value");
+        assert!(cap_agent::audit::verify_chain(&audit_path).unwrap().ok);
+    }
+
+    #[test]
+    fn test_log_event_migrates_v1_log() {
+        let temp = TempDir::new().unwrap();
+        let project = temp.path();
+        let audit_path = project.join("audit/agent.audit.jsonl");
+        fs::create_dir_all(project.join("audit")).unwrap();
+
+        // Log written by an older app version
+        let mut v1 = cap_agent::audit::AuditLog::new(&audit_path).unwrap();
+        v1.log_event("project_created", serde_json::json!({"name": "demo"}))
+            .unwrap();
+        let v1_tip = v1.get_tip();
+
+        log_event(project, "csv_imported", serde_json::json!({"record_count": 3})).unwrap();
+
+        assert!(project.join("audit/agent.audit.v1.jsonl").exists());
+        assert!(cap_agent::audit::verify_chain(&audit_path).unwrap().ok);
+        let bridge = cap_agent::audit::verify_migration(&audit_path)
+            .unwrap()
+            .unwrap();
+        assert!(bridge.ok);
+        assert_eq!(bridge.bridge.source_tail_digest, v1_tip);
     }
 }
@@ -44,6 +44,8 @@ struct RawAuditEntryV2 {
     result: Option<crate::types::AuditEventResult>,
     #[serde(default)]
     run_id: Option<String>,
+    #[serde(default)]
+    details: Option<serde_json::Value>,
     prev_hash: String,
     self_hash: String,
 }
@@ -73,7 +75,7 @@ fn parse_audit_line(line: &str) -> Result<AuditEvent, String> {
             seq: None,
             ts: v2.ts,
             event: v2.event,
-            details: None,
+            details: v2.details,
             policy_id: v2.policy_id,
             ir_hash: v2.ir_hash,
             manifest_hash: v2.manifest_hash,
@@ -124,6 +126,8 @@ fn compute_hash_v2(event: &AuditEvent) -> String {
         result: &'a Option<crate::types::AuditEventResult>,
         #[serde(skip_serializing_if = "Option::is_none")]
         run_id: &'a Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        details: &'a Option<serde_json::Value>,
         prev_hash: &'a str,
     }
 
@@ -135,6 +139,7 @@ fn compute_hash_v2(event: &AuditEvent) -> String {
         manifest_hash: &event.manifest_hash,
         result: &event.result,
         run_id: &event.run_id,
+        details: &event.details,
         prev_hash: &event.prev_hash,
     };
 
@@ -461,6 +466,24 @@ mod tests {
         assert_eq!(verify.tail_hash, Some(digest1));
     }
 
+    #[tokio::test]
+    async fn test_verify_chain_valid_v2_with_details() {
+        let temp = create_test_project();
+
+        // Events written by the app through the shared audit chain
+        crate::audit_logger::log_event(temp.path(), "csv_imported", serde_json::json!({"record_count": 3})).unwrap();
+        let tail = crate::audit_logger::log_event(temp.path(), "policy_loaded", serde_json::json!({"name": "lksg"})).unwrap();
+
+        let project = temp.path().to_string_lossy().to_string();
+        let verify = verify_audit_chain(project.clone()).await.unwrap();
+        assert!(verify.valid, "Chain should be valid: {:?}", verify.errors);
+        assert_eq!(verify.verified_count, 2);
+        assert_eq!(verify.tail_hash, Some(tail));
+
+        let log = get_audit_log(project, None, None).await.unwrap();
+        assert_eq!(log.events[0].details.as_ref().unwrap()["record_count"], 3);
+    }
+
     #[tokio::test]
     async fn test_verify_chain_tampered() {
         let temp = create_test_project();
@@ -9,8 +9,11 @@ use std::fs;
 use std::path::Path;
 
 // Import CAP-Agent library functions
-use cap_agent::commitment::{compute_company_root, compute_supplier_root, compute_ubo_root};
-use cap_agent::io::{read_suppliers_csv, read_ubos_csv};
+use cap_agent::commitment::{
+    compute_company_root, compute_supplier_root, compute_ubo_root, nonces_path, CommitmentScheme,
+    RecordNonces,
+};
+use cap_agent::io::{read_suppliers_csv, read_ubos_csv, JsonPersistent};
 
 /// Creates commitments (Merkle roots) from imported CSV files
 ///
@@ -21,7 +24,8 @@ use cap_agent::io::{read_suppliers_csv, read_ubos_csv};
 /// CommitmentsResult with supplier_root, ubo_root, and company_root
 ///
 /// # Security
-/// - Uses BLAKE3 for hashing (deterministic)
+/// - Uses BLAKE3 over salted field commitments (fresh nonce per record)
+/// - Nonces stay in build/commitment_nonces.json and are never exported
 /// - No PII in output (only hashes)
 #[tauri::command]
 pub async fn create_commitments(project: String) -> Result<CommitmentsResult, String> {
@@ -46,11 +50,12 @@ pub async fn create_commitments(project: String) -> Result<CommitmentsResult, St
     let ubos = read_ubos_csv(&ubos_path)
         .map_err(|e| sanitize_error_message(&format!("Failed to read UBOs CSV: {}", e)))?;
 
-    // 3. Compute Merkle roots
-    let supplier_root = compute_supplier_root(&suppliers)
+    // 3. Compute Merkle roots over salted leaves
+    let nonces = RecordNonces::generate(suppliers.len(), ubos.len());
+    let supplier_root = compute_supplier_root(&suppliers, &nonces.suppliers)
         .map_err(|e| format!("Failed to compute supplier root: {}", e))?;
 
-    let ubo_root = compute_ubo_root(&ubos)
+    let ubo_root = compute_ubo_root(&ubos, &nonces.ubos)
         .map_err(|e| format!("Failed to compute UBO root: {}", e))?;
 
     let company_root = compute_company_root(&supplier_root, &ubo_root);
@@ -63,6 +68,7 @@ pub async fn create_commitments(project: String) -> Result<CommitmentsResult, St
         "company_root": company_root,
         "supplier_count": suppliers.len(),
         "ubo_count": ubos.len(),
+        "commitment_scheme": CommitmentScheme::CURRENT,
         "created_at": chrono::Utc::now().to_rfc3339(),
     });
 
@@ -72,6 +78,10 @@ pub async fn create_commitments(project: String) -> Result<CommitmentsResult, St
     fs::write(&commitments_path, &commitments_json)
         .map_err(|e| sanitize_error_message(&format!("Failed to write commitments: {}", e)))?;
 
+    nonces
+        .save(nonces_path(&commitments_path))
+        .map_err(|e| sanitize_error_message(&format!("Failed to write nonces: {}", e)))?;
+
     // 5. Log to audit trail
     let _ = audit_logger::events::commitments_created(project_path, &supplier_root, &ubo_root);
 
@@ -140,7 +150,7 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_create_commitments_deterministic() {
+    async fn test_create_commitments_blinded() {
         let temp = TempDir::new().unwrap();
         let workspace = temp.path().to_string_lossy().to_string();
 
@@ -170,10 +180,12 @@ mod tests {
         let result1 = create_commitments(project.path.clone()).await.unwrap();
         let result2 = create_commitments(project.path.clone()).await.unwrap();
 
-        // Hashes should be identical
-        assert_eq!(result1.supplier_root, result2.supplier_root);
-        assert_eq!(result1.ubo_root, result2.ubo_root);
-        assert_eq!(result1.company_root, result2.company_root);
+        // Fresh nonces per run: roots cannot be recomputed from the plaintext alone
+        assert_ne!(result1.supplier_root, result2.supplier_root);
+        assert_ne!(result1.ubo_root, result2.ubo_root);
+        assert!(Path::new(&project.path)
+            .join("build/commitment_nonces.json")
+            .exists());
     }
 
     #[tokio::test]
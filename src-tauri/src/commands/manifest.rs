@@ -6,6 +6,7 @@ use crate::audit_logger;
 use crate::security::{sanitize_error_message, validate_path_exists};
 use crate::types::ManifestResult;
 use blake3::Hasher;
+use cap_agent::commitment::CommitmentScheme;
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use std::fs;
@@ -17,6 +18,9 @@ struct CommitmentsFile {
     supplier_root: String,
     ubo_root: String,
     company_root: String,
+    /// Missing in commitments created before scheme versioning (legacy)
+    #[serde(default)]
+    commitment_scheme: Option<CommitmentScheme>,
 }
 
 /// Policy file structure (minimal)
@@ -38,6 +42,8 @@ struct Manifest {
     audit: AuditManifest,
     proof: ProofManifest,
     signatures: Vec<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    commitment_scheme: Option<CommitmentScheme>,
 }
 
 #[derive(Debug, Serialize)]
@@ -144,6 +150,7 @@ pub async fn build_manifest(project: String) -> Result<ManifestResult, String> {
             status: "pending".to_string(),
         },
         signatures: vec![],
+        commitment_scheme: commitments.commitment_scheme,
     };
 
     // 7. Serialize to canonical JSON (sorted keys for determinism)
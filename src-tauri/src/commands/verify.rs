@@ -42,6 +42,8 @@ pub async fn verify_bundle(request: VerifyBundleRequest) -> Result<VerifyBundleR
         VerifyOptions {
             check_timestamp: opts.check_timestamp,
             check_registry: opts.check_registry,
+            registry: None,
+            key_store: None,
         }
     } else {
         VerifyOptions::default() // Offline defaults (false, false)
@@ -61,6 +63,7 @@ pub async fn verify_bundle(request: VerifyBundleRequest) -> Result<VerifyBundleR
         timestamp_valid: report.timestamp_valid,
         registry_match: report.registry_match,
         details: report.details,
+        checks: report.checks,
     })
 }
 
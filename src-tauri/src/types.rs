@@ -3,6 +3,7 @@
 //! This module defines all request/response types used by Tauri commands.
 //! Types follow camelCase for JSON serialization (Tauri convention).
 
+use cap_agent::verifier::CheckResult;
 use serde::{Deserialize, Serialize};
 
 // ============================================================================
@@ -201,6 +202,7 @@ pub struct VerifyBundleResponse {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub registry_match: Option<bool>,
     pub details: serde_json::Value,
+    pub checks: Vec<CheckResult>,
 }
 
 #[derive(Debug, Serialize)]
@@ -248,7 +250,7 @@ pub struct AuditEvent {
     /// Event type
     pub event: String,
 
-    /// Event details (V1.0 format)
+    /// Event details (V1.0 format, V2.0 events written with details)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub details: Option<serde_json::Value>,
 
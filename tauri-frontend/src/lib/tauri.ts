@@ -64,6 +64,20 @@ export interface VerifyBundleResponse {
     signature_present: boolean;
     signature_count?: number;
   };
+
+  /** Typed results of all checks run by the verification engine */
+  checks?: VerifyCheckResult[];
+}
+
+export interface VerifyCheckResult {
+  /** Check ID, e.g. "hash_match_manifest" */
+  id: string;
+
+  kind: 'integrity' | 'policy' | 'signature' | 'timestamp' | 'registry' | 'anchor';
+
+  status: 'ok' | 'warn' | 'fail' | 'error';
+
+  message: string;
 }
 
 export interface BundleInfo {